@@ -1,3 +1,5 @@
+use crate::catalog_transfer;
+use crate::proxy_traces;
 use crate::settings;
 use crate::workbench;
 use inventiv_common::{Instance, InstanceStatus, InstanceType, LlmModel, Region, Zone};
@@ -7,36 +9,71 @@ use utoipa::OpenApi;
 #[openapi(
     paths(
         crate::handlers::instances::list_instances,
+        crate::handlers::instances::list_model_instances,
         crate::handlers::deployments::create_deployment,
+        crate::handlers::deployments::preview_cloud_init,
+        crate::handlers::commands::list_error_codes,
         crate::handlers::instances::terminate_instance,
+        crate::handlers::instances::plan_terminate_instance,
+        crate::handlers::instances::bulk_plan_terminate_instances,
+        crate::handlers::instances::resize_instance,
+        crate::handlers::instances::retry_instance,
+        crate::handlers::instances::set_instance_routing,
         // Models
         crate::handlers::models::list_models,
         crate::handlers::models::get_model,
         crate::handlers::models::create_model,
         crate::handlers::models::update_model,
         crate::handlers::models::delete_model,
+        crate::handlers::models::check_model_compat,
         // Settings
         settings::list_regions,
         settings::update_region,
         settings::list_zones,
         settings::update_zone,
         settings::list_instance_types,
-        settings::update_instance_type
-        ,
+        settings::update_instance_type,
+        settings::get_provider_tree,
+        catalog_transfer::get_catalog_export,
+        catalog_transfer::post_catalog_import,
+        proxy_traces::list_proxy_traces,
         // Workbench (persistence)
         workbench::create_workbench_run,
         workbench::list_workbench_runs,
         workbench::get_workbench_run,
         workbench::append_workbench_message,
-        workbench::complete_workbench_run
+        workbench::complete_workbench_run,
+        // OpenAI-compatible proxy
+        crate::handlers::openai::openai_list_models,
+        crate::handlers::openai::openai_proxy_chat_completions,
+        crate::handlers::openai::openai_proxy_completions,
+        crate::handlers::openai::openai_proxy_embeddings,
+        crate::handlers::openai::openai_proxy_passthrough,
+        // Worker (internal)
+        crate::handlers::worker::proxy_worker_register,
+        crate::handlers::worker::proxy_worker_heartbeat,
+        crate::handlers::worker::proxy_worker_deregister,
+        crate::handlers::worker::proxy_worker_config
     ),
     components(
         schemas(
             crate::handlers::deployments::DeploymentRequest,
             crate::handlers::deployments::DeploymentResponse,
+            crate::handlers::commands::ErrorCodeResponse,
+            inventiv_common::error_code::ErrorCode,
+            crate::handlers::deployments::CloudInitPreviewRequest,
+            crate::handlers::deployments::CloudInitPreviewResponse,
+            crate::handlers::instances::TerminationDecision,
+            crate::handlers::instances::TerminationPlan,
+            crate::handlers::instances::BulkTerminationPlanRequest,
+            crate::handlers::instances::BulkTerminationPlanResponse,
+            crate::handlers::instances::InstanceResizeRequest,
+            crate::handlers::instances::InstanceRoutingRequest,
+            crate::handlers::instances::InstanceRoutingResponse,
             crate::handlers::models::CreateModelRequest,
             crate::handlers::models::UpdateModelRequest,
             crate::handlers::models::ListModelsParams,
+            crate::handlers::models::ModelCompatResponse,
             Instance,
             InstanceStatus,
             LlmModel,
@@ -46,8 +83,20 @@ use utoipa::OpenApi;
             InstanceType,
             settings::UpdateRegionRequest,
             settings::UpdateZoneRequest,
-            settings::UpdateInstanceTypeRequest
-            ,
+            settings::UpdateInstanceTypeRequest,
+            settings::ProviderTree,
+            settings::ProviderTreeRegion,
+            settings::ProviderTreeZone,
+            settings::ProviderTreeInstanceType,
+            catalog_transfer::CatalogDocument,
+            catalog_transfer::CatalogProvider,
+            catalog_transfer::CatalogRegion,
+            catalog_transfer::CatalogZone,
+            catalog_transfer::CatalogZoneAvailability,
+            catalog_transfer::CatalogInstanceType,
+            catalog_transfer::CatalogModel,
+            catalog_transfer::CatalogImportSummary,
+            proxy_traces::ProxyTrace,
             // Workbench
             workbench::WorkbenchRunRow,
             workbench::WorkbenchMessageRow,
@@ -57,7 +106,29 @@ use utoipa::OpenApi;
             workbench::AppendWorkbenchMessageResponse,
             workbench::CompleteWorkbenchRunRequest,
             workbench::WorkbenchRunWithMessages,
-            workbench::ListWorkbenchRunsQuery
+            workbench::ListWorkbenchRunsQuery,
+            // OpenAI-compatible proxy
+            crate::handlers::openai::OpenAiModel,
+            crate::handlers::openai::OpenAiModelList,
+            crate::handlers::openai::OpenAiChatMessage,
+            crate::handlers::openai::OpenAiChatCompletionRequest,
+            crate::handlers::openai::OpenAiChatCompletionChoice,
+            crate::handlers::openai::OpenAiChatCompletionResponse,
+            crate::handlers::openai::OpenAiCompletionRequest,
+            crate::handlers::openai::OpenAiCompletionChoice,
+            crate::handlers::openai::OpenAiCompletionResponse,
+            crate::handlers::openai::OpenAiEmbeddingRequest,
+            crate::handlers::openai::OpenAiEmbedding,
+            crate::handlers::openai::OpenAiEmbeddingResponse,
+            crate::handlers::openai::OpenAiUsage,
+            // Worker (internal)
+            crate::handlers::worker::WorkerRegisterRequest,
+            crate::handlers::worker::WorkerHeartbeatRequest,
+            crate::handlers::worker::WorkerDeregisterRequest,
+            crate::handlers::worker::WorkerAgentInfo,
+            crate::handlers::worker::WorkerAckResponse,
+            crate::handlers::worker::WorkerConfigResponse,
+            inventiv_common::WorkerStatus
         )
     ),
     tags(
@@ -19,11 +19,19 @@ pub struct ApiKeyRow {
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
     pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Model scope (models.model_id). null = any public model.
+    pub allowed_models: Option<Vec<String>>,
+    /// Requests per minute on /v1/*. null = unlimited.
+    pub rate_limit_per_minute: Option<i32>,
+    /// Concurrent streaming requests on /v1/*. null = unlimited.
+    pub max_concurrent_streams: Option<i32>,
 }
 
 #[derive(Deserialize, utoipa::ToSchema)]
 pub struct CreateApiKeyRequest {
     pub name: String,
+    /// Restrict the key to these models (HF repo ids); required to call non-public models.
+    pub allowed_models: Option<Vec<String>>,
 }
 
 #[derive(Serialize, utoipa::ToSchema)]
@@ -36,6 +44,12 @@ pub struct CreateApiKeyResponse {
 #[derive(Deserialize, utoipa::ToSchema)]
 pub struct UpdateApiKeyRequest {
     pub name: String,
+    /// Replaces the model scope; an empty list removes it.
+    pub allowed_models: Option<Vec<String>>,
+    /// Requests per minute on /v1/*; 0 removes the limit.
+    pub rate_limit_per_minute: Option<i32>,
+    /// Concurrent streaming requests on /v1/*; 0 removes the limit.
+    pub max_concurrent_streams: Option<i32>,
 }
 
 #[derive(Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
@@ -64,6 +78,18 @@ fn dir_sql(dir: Option<&str>) -> &'static str {
     }
 }
 
+/// Trimmed, de-duplicated model scope (empty entries dropped).
+fn normalize_allowed_models(models: Vec<String>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for m in models {
+        let m = m.trim();
+        if !m.is_empty() && !out.iter().any(|o| o == m) {
+            out.push(m.to_string());
+        }
+    }
+    out
+}
+
 fn hex_encode(bytes: &[u8]) -> String {
     let mut s = String::with_capacity(bytes.len() * 2);
     for b in bytes {
@@ -88,12 +114,13 @@ pub async fn insert_api_key(
     name: &str,
     plaintext_key: &str,
     key_prefix: &str,
+    allowed_models: Option<&[String]>,
 ) -> Result<ApiKeyRow, sqlx::Error> {
     let id = uuid::Uuid::new_v4();
     sqlx::query(
         r#"
-        INSERT INTO api_keys (id, user_id, name, key_hash, key_prefix, metadata)
-        VALUES ($1, $2, $3, encode(digest($4::text, 'sha256'), 'hex'), $5, '{}'::jsonb)
+        INSERT INTO api_keys (id, user_id, name, key_hash, key_prefix, metadata, allowed_models)
+        VALUES ($1, $2, $3, encode(digest($4::text, 'sha256'), 'hex'), $5, '{}'::jsonb, $6)
         "#,
     )
     .bind(id)
@@ -101,12 +128,14 @@ pub async fn insert_api_key(
     .bind(name)
     .bind(plaintext_key)
     .bind(key_prefix)
+    .bind(allowed_models)
     .execute(db)
     .await?;
 
     let row = sqlx::query_as::<Postgres, ApiKeyRow>(
         r#"
-        SELECT id, name, key_prefix, created_at, last_used_at, revoked_at
+        SELECT id, name, key_prefix, created_at, last_used_at, revoked_at, allowed_models,
+               rate_limit_per_minute, max_concurrent_streams
         FROM api_keys
         WHERE id = $1
         "#,
@@ -128,7 +157,8 @@ pub async fn list_api_keys(
 ) -> Json<Vec<ApiKeyRow>> {
     let rows = sqlx::query_as::<Postgres, ApiKeyRow>(
         r#"
-        SELECT id, name, key_prefix, created_at, last_used_at, revoked_at
+        SELECT id, name, key_prefix, created_at, last_used_at, revoked_at, allowed_models,
+               rate_limit_per_minute, max_concurrent_streams
         FROM api_keys
         WHERE user_id = $1
         ORDER BY created_at DESC
@@ -176,7 +206,8 @@ pub async fn search_api_keys(
 
     let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(
         r#"
-        SELECT id, name, key_prefix, created_at, last_used_at, revoked_at
+        SELECT id, name, key_prefix, created_at, last_used_at, revoked_at, allowed_models,
+               rate_limit_per_minute, max_concurrent_streams
         FROM api_keys
         WHERE user_id = 
         "#,
@@ -235,8 +266,22 @@ pub async fn create_api_key(
             .into_response();
     }
 
+    let allowed_models = req
+        .allowed_models
+        .map(normalize_allowed_models)
+        .filter(|m| !m.is_empty());
+
     let (key, prefix) = generate_api_key();
-    match insert_api_key(&state.db, user.user_id, name, &key, &prefix).await {
+    match insert_api_key(
+        &state.db,
+        user.user_id,
+        name,
+        &key,
+        &prefix,
+        allowed_models.as_deref(),
+    )
+    .await
+    {
         Ok(row) => Json(CreateApiKeyResponse {
             key: row,
             api_key: key,
@@ -270,17 +315,46 @@ pub async fn update_api_key(
         )
             .into_response();
     }
+    if req.rate_limit_per_minute.is_some_and(|l| l < 0) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error":"invalid_request","message":"rate_limit_per_minute_negative"})),
+        )
+            .into_response();
+    }
+    if req.max_concurrent_streams.is_some_and(|l| l < 0) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error":"invalid_request","message":"max_concurrent_streams_negative"})),
+        )
+            .into_response();
+    }
 
     let res = sqlx::query(
         r#"
         UPDATE api_keys
-        SET name = $1
+        SET name = $1,
+            allowed_models = CASE
+              WHEN $4::text[] IS NULL THEN allowed_models
+              ELSE NULLIF($4, '{}'::text[])
+            END,
+            rate_limit_per_minute = CASE
+              WHEN $5::int IS NULL THEN rate_limit_per_minute
+              ELSE NULLIF($5, 0)
+            END,
+            max_concurrent_streams = CASE
+              WHEN $6::int IS NULL THEN max_concurrent_streams
+              ELSE NULLIF($6, 0)
+            END
         WHERE id = $2 AND user_id = $3
         "#,
     )
     .bind(name)
     .bind(id)
     .bind(user.user_id)
+    .bind(req.allowed_models.map(normalize_allowed_models))
+    .bind(req.rate_limit_per_minute)
+    .bind(req.max_concurrent_streams)
     .execute(&state.db)
     .await;
 
@@ -335,3 +409,116 @@ pub async fn revoke_api_key(
             .into_response(),
     }
 }
+
+#[derive(Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
+pub struct ApiKeyUsageQuery {
+    /// minute|hour|day|week_7d|month_30d|year_365d (default: day)
+    pub window: Option<String>,
+}
+
+#[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct ApiKeyModelUsage {
+    /// models.model_id (HF repo id); null if the model was deleted.
+    pub model: Option<String>,
+    pub requests: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub total_tokens: i64,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct ApiKeyUsageResponse {
+    pub api_key_id: uuid::Uuid,
+    pub window_minutes: i64,
+    pub requests: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub total_tokens: i64,
+    pub models: Vec<ApiKeyModelUsage>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api_keys/{id}/usage",
+    tag = "ApiKeys",
+    params(ApiKeyUsageQuery),
+    responses(
+        (status = 200, description = "Request counts and token totals for this key, per model", body = ApiKeyUsageResponse),
+        (status = 400, description = "Invalid window"),
+        (status = 404, description = "Key not found or not owned by the caller")
+    )
+)]
+pub async fn get_api_key_usage(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<auth::AuthUser>,
+    Path(id): Path<uuid::Uuid>,
+    Query(params): Query<ApiKeyUsageQuery>,
+) -> impl IntoResponse {
+    let window_minutes =
+        match crate::finops::parse_window(params.window.as_deref().unwrap_or("day")) {
+            Ok(m) => m,
+            Err(e) => return e.into_response(),
+        };
+
+    // Owner only; admins may read any key. Other callers get the same 404 as a missing key.
+    let owner: Result<Option<uuid::Uuid>, sqlx::Error> =
+        sqlx::query_scalar("SELECT user_id FROM api_keys WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await;
+    match owner {
+        Ok(Some(owner)) if owner == user.user_id || auth::require_admin(&user).is_ok() => {}
+        Ok(_) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error":"not_found"})),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error":"db_error","message": e.to_string()})),
+            )
+                .into_response()
+        }
+    }
+
+    let rows = sqlx::query_as::<Postgres, ApiKeyModelUsage>(
+        r#"
+        SELECT m.model_id AS model,
+               COUNT(*)::bigint AS requests,
+               COALESCE(SUM(u.input_tokens), 0)::bigint AS input_tokens,
+               COALESCE(SUM(u.output_tokens), 0)::bigint AS output_tokens,
+               COALESCE(SUM(u.total_tokens), 0)::bigint AS total_tokens
+        FROM finops.inference_usage u
+        LEFT JOIN models m ON m.id = u.model_id
+        WHERE u.api_key_id = $1
+          AND u.occurred_at > NOW() - make_interval(mins => $2::int)
+        GROUP BY m.model_id
+        ORDER BY requests DESC, m.model_id
+        "#,
+    )
+    .bind(id)
+    .bind(window_minutes as i32)
+    .fetch_all(&state.db)
+    .await;
+
+    match rows {
+        Ok(models) => Json(ApiKeyUsageResponse {
+            api_key_id: id,
+            window_minutes,
+            requests: models.iter().map(|m| m.requests).sum(),
+            input_tokens: models.iter().map(|m| m.input_tokens).sum(),
+            output_tokens: models.iter().map(|m| m.output_tokens).sum(),
+            total_tokens: models.iter().map(|m| m.total_tokens).sum(),
+            models,
+        })
+        .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error":"db_error","message": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
@@ -2,14 +2,42 @@ use redis::Client;
 use sqlx::{Pool, Postgres};
 use std::sync::Arc;
 
+use crate::openai_proxy::ProxyClients;
+use crate::provider_cache::ProviderCodeCache;
+use crate::session_affinity::AffinityTracker;
+use crate::settings_cache::SettingsCache;
+use crate::single_flight::SingleFlight;
+use crate::worker_breaker::WorkerBreaker;
+
 #[derive(Clone)]
 pub struct AppState {
     pub redis_client: Client,
     pub db: Pool<Postgres>,
+    /// Shared upstream HTTP clients for the OpenAI proxy (pooled connections).
+    pub proxy_clients: ProxyClients,
+    /// Cached provider code -> id resolution for deployments (cleared on provider writes).
+    pub provider_codes: Arc<ProviderCodeCache>,
+    /// In-flight coalesced proxy requests (opt-in, see `SingleFlight`).
+    pub inflight: Arc<SingleFlight>,
+    /// Session -> worker backend observations (opt-in, see `AffinityTracker`).
+    pub affinity: Arc<AffinityTracker>,
+    /// Workers skipped by the proxy after a connect failure (see `WorkerBreaker`).
+    pub worker_breaker: Arc<WorkerBreaker>,
+    /// Hot-path global settings (short TTL, cleared on `/settings/global` writes).
+    pub settings: Arc<SettingsCache>,
 }
 
 impl AppState {
     pub fn new(redis_client: Client, db: Pool<Postgres>) -> Arc<Self> {
-        Arc::new(Self { redis_client, db })
+        Arc::new(Self {
+            redis_client,
+            db,
+            proxy_clients: ProxyClients::new(),
+            provider_codes: Arc::new(ProviderCodeCache::default()),
+            inflight: Arc::new(SingleFlight::default()),
+            affinity: Arc::new(AffinityTracker::default()),
+            worker_breaker: Arc::new(WorkerBreaker::default()),
+            settings: Arc::new(SettingsCache::default()),
+        })
     }
 }
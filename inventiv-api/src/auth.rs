@@ -29,11 +29,19 @@ pub struct ApiKeyPrincipal {
     pub user_id: uuid::Uuid,
     pub key_prefix: String,
     pub name: String,
+    /// Model scope (models.model_id). None = any public model.
+    pub allowed_models: Option<Vec<String>>,
+    /// Requests per minute on /v1/*. None = unlimited.
+    pub rate_limit_per_minute: Option<i32>,
+    /// Concurrent streaming requests on /v1/*. None = unlimited.
+    pub max_concurrent_streams: Option<i32>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct Claims {
     iss: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    aud: Option<String>,
     sub: String, // user_id
     email: String,
     role: String,
@@ -60,6 +68,27 @@ pub fn jwt_issuer() -> String {
         .unwrap_or_else(|| "inventiv-api".to_string())
 }
 
+/// Optional `aud` claim (`JWT_AUDIENCE`): when set, sessions are signed with it and tokens
+/// without it (or with another audience) are rejected.
+pub fn jwt_audience() -> Option<String> {
+    std::env::var("JWT_AUDIENCE")
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Retired signing secrets still accepted for verification (`JWT_PREVIOUS_SECRETS`,
+/// comma-separated), so `JWT_SECRET` can be rotated without logging every session out.
+pub fn jwt_previous_secrets() -> Vec<String> {
+    std::env::var("JWT_PREVIOUS_SECRETS")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 pub fn jwt_ttl_seconds() -> u64 {
     std::env::var("JWT_TTL_SECONDS")
         .ok()
@@ -81,13 +110,29 @@ fn now_ts() -> u64 {
         .as_secs()
 }
 
+/// Sign a session with the current `JWT_SECRET`.
 pub fn sign_session_jwt(user: &AuthUser) -> anyhow::Result<String> {
+    sign_session_jwt_with(
+        user,
+        &jwt_secret(),
+        &jwt_issuer(),
+        jwt_audience().as_deref(),
+    )
+}
+
+fn sign_session_jwt_with(
+    user: &AuthUser,
+    secret: &str,
+    issuer: &str,
+    audience: Option<&str>,
+) -> anyhow::Result<String> {
     let now = now_ts() as usize;
     let exp = (now_ts() + jwt_ttl_seconds()) as usize;
     // jti is a hash of session_id + secret for secure revocation
-    let jti = sha256_hash(&format!("{}:{}", user.session_id, jwt_secret()));
+    let jti = sha256_hash(&format!("{}:{}", user.session_id, secret));
     let claims = Claims {
-        iss: jwt_issuer(),
+        iss: issuer.to_string(),
+        aud: audience.map(str::to_string),
         sub: user.user_id.to_string(),
         email: user.email.clone(),
         role: user.role.clone(),
@@ -101,7 +146,7 @@ pub fn sign_session_jwt(user: &AuthUser) -> anyhow::Result<String> {
     let token = jsonwebtoken::encode(
         &Header::default(),
         &claims,
-        &EncodingKey::from_secret(jwt_secret().as_bytes()),
+        &EncodingKey::from_secret(secret.as_bytes()),
     )?;
     Ok(token)
 }
@@ -117,15 +162,44 @@ pub fn hash_session_token(token: &str) -> String {
     sha256_hash(token)
 }
 
+/// Verify a session against the current secret, then the previous ones.
 fn decode_session_jwt(token: &str) -> anyhow::Result<AuthUser> {
+    let mut secrets = vec![jwt_secret()];
+    secrets.extend(jwt_previous_secrets());
+    decode_session_jwt_with(token, &secrets, &jwt_issuer(), jwt_audience().as_deref())
+}
+
+fn decode_session_jwt_with(
+    token: &str,
+    secrets: &[String],
+    issuer: &str,
+    audience: Option<&str>,
+) -> anyhow::Result<AuthUser> {
     let mut validation = Validation::default();
-    validation.set_issuer(&[jwt_issuer()]);
+    validation.set_issuer(&[issuer]);
+    match audience {
+        Some(aud) => {
+            validation.set_audience(&[aud]);
+            validation.set_required_spec_claims(&["exp", "iss", "aud"]);
+        }
+        None => validation.validate_aud = false,
+    }
 
-    let data = jsonwebtoken::decode::<Claims>(
-        token,
-        &DecodingKey::from_secret(jwt_secret().as_bytes()),
-        &validation,
-    )?;
+    // Only a signature mismatch moves on to the next key: a token signed with a valid key but
+    // failing another check (issuer, audience, expiry) is rejected as is.
+    let mut result = Err(jsonwebtoken::errors::ErrorKind::InvalidSignature.into());
+    for secret in secrets {
+        result = jsonwebtoken::decode::<Claims>(
+            token,
+            &DecodingKey::from_secret(secret.as_bytes()),
+            &validation,
+        );
+        match &result {
+            Err(e) if *e.kind() == jsonwebtoken::errors::ErrorKind::InvalidSignature => continue,
+            _ => break,
+        }
+    }
+    let data = result?;
     let user_id = uuid::Uuid::parse_str(&data.claims.sub)?;
     let current_organization_id = match data.claims.current_organization_id.as_deref() {
         Some(s) if !s.trim().is_empty() => uuid::Uuid::parse_str(s).ok(),
@@ -313,10 +387,22 @@ pub async fn require_user(
     }
 }
 
+/// (id, user_id, key_prefix, name, allowed_models, rate_limit_per_minute, max_concurrent_streams)
+type ApiKeyAuthRow = (
+    uuid::Uuid,
+    uuid::Uuid,
+    String,
+    String,
+    Option<Vec<String>>,
+    Option<i32>,
+    Option<i32>,
+);
+
 async fn verify_api_key_db(db: &Pool<Postgres>, token: &str) -> Option<ApiKeyPrincipal> {
-    let row: Option<(uuid::Uuid, uuid::Uuid, String, String)> = sqlx::query_as(
+    let row: Option<ApiKeyAuthRow> = sqlx::query_as(
         r#"
-        SELECT id, user_id, key_prefix, name
+        SELECT id, user_id, key_prefix, name, allowed_models, rate_limit_per_minute,
+               max_concurrent_streams
         FROM api_keys
         WHERE revoked_at IS NULL
           AND key_hash = encode(digest($1::text, 'sha256'), 'hex')
@@ -329,7 +415,16 @@ async fn verify_api_key_db(db: &Pool<Postgres>, token: &str) -> Option<ApiKeyPri
     .ok()
     .flatten();
 
-    let Some((api_key_id, user_id, key_prefix, name)) = row else {
+    let Some((
+        api_key_id,
+        user_id,
+        key_prefix,
+        name,
+        allowed_models,
+        rate_limit_per_minute,
+        max_concurrent_streams,
+    )) = row
+    else {
         return None;
     };
 
@@ -343,6 +438,9 @@ async fn verify_api_key_db(db: &Pool<Postgres>, token: &str) -> Option<ApiKeyPri
         user_id,
         key_prefix,
         name,
+        allowed_models: allowed_models.filter(|m| !m.is_empty()),
+        rate_limit_per_minute,
+        max_concurrent_streams,
     })
 }
 
@@ -597,6 +695,30 @@ pub async fn revoke_session(db: &Pool<Postgres>, session_id: uuid::Uuid) -> anyh
     Ok(())
 }
 
+/// Revoke every active session of a user, except `keep` (the caller's own session) when set.
+/// Returns the number of sessions revoked.
+pub async fn revoke_user_sessions(
+    db: &Pool<Postgres>,
+    user_id: uuid::Uuid,
+    keep: Option<uuid::Uuid>,
+) -> anyhow::Result<u64> {
+    let res = sqlx::query(
+        r#"
+        UPDATE user_sessions
+        SET revoked_at = NOW()
+        WHERE user_id = $1
+          AND revoked_at IS NULL
+          AND ($2::uuid IS NULL OR id <> $2)
+        "#,
+    )
+    .bind(user_id)
+    .bind(keep)
+    .execute(db)
+    .await?;
+
+    Ok(res.rows_affected())
+}
+
 /// Get user's last used organization (for default org selection on login)
 pub async fn get_user_last_org(
     db: &Pool<Postgres>,
@@ -685,6 +807,54 @@ mod tests {
         Some(pool)
     }
 
+    fn test_user() -> AuthUser {
+        AuthUser {
+            user_id: uuid::Uuid::new_v4(),
+            email: "rotation@test.com".to_string(),
+            role: "user".to_string(),
+            session_id: uuid::Uuid::new_v4().to_string(),
+            current_organization_id: None,
+            current_organization_role: None,
+        }
+    }
+
+    #[test]
+    fn test_token_signed_with_previous_secret_verifies_during_rotation() {
+        let user = test_user();
+        let old = sign_session_jwt_with(&user, "old-secret", "inventiv-api", None).unwrap();
+        let rotated = ["new-secret".to_string(), "old-secret".to_string()];
+
+        let decoded = decode_session_jwt_with(&old, &rotated, "inventiv-api", None).unwrap();
+        assert_eq!(decoded.user_id, user.user_id);
+        assert_eq!(decoded.session_id, user.session_id);
+        // Once the previous secret is retired, the old token no longer verifies.
+        assert!(decode_session_jwt_with(&old, &rotated[..1], "inventiv-api", None).is_err());
+    }
+
+    #[test]
+    fn test_token_with_wrong_issuer_or_audience_is_rejected() {
+        let user = test_user();
+        let secrets = ["secret".to_string()];
+
+        let foreign = sign_session_jwt_with(&user, "secret", "other-issuer", None).unwrap();
+        assert!(decode_session_jwt_with(&foreign, &secrets, "inventiv-api", None).is_err());
+
+        let dashboard =
+            sign_session_jwt_with(&user, "secret", "inventiv-api", Some("dashboard")).unwrap();
+        let unscoped = sign_session_jwt_with(&user, "secret", "inventiv-api", None).unwrap();
+        assert!(
+            decode_session_jwt_with(&dashboard, &secrets, "inventiv-api", Some("dashboard"))
+                .is_ok()
+        );
+        assert!(
+            decode_session_jwt_with(&dashboard, &secrets, "inventiv-api", Some("admin")).is_err()
+        );
+        assert!(
+            decode_session_jwt_with(&unscoped, &secrets, "inventiv-api", Some("dashboard"))
+                .is_err()
+        );
+    }
+
     #[tokio::test]
     async fn test_create_session() {
         let Some(pool) = setup_pool().await else {
@@ -870,6 +1040,83 @@ mod tests {
         assert_eq!(revoked_after, Some(true), "Session should be revoked");
     }
 
+    #[tokio::test]
+    async fn test_revoke_user_sessions() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+
+        let test_user_id = uuid::Uuid::new_v4();
+        let _ = sqlx::query(
+            "INSERT INTO users (id, email, password_hash, username) VALUES ($1, $2, crypt('password', gen_salt('bf')), $3)",
+        )
+        .bind(test_user_id)
+        .bind(format!("revoke-{}@test.com", test_user_id))
+        .bind(format!("revoke-{}", test_user_id))
+        .execute(&pool)
+        .await;
+
+        let sessions = [
+            (uuid::Uuid::new_v4(), "test_hash_laptop"),
+            (uuid::Uuid::new_v4(), "test_hash_phone"),
+            (uuid::Uuid::new_v4(), "test_hash_tablet"),
+        ];
+        for (session_id, token_hash) in sessions {
+            create_session(
+                &pool,
+                session_id,
+                test_user_id,
+                None,
+                None,
+                Some("127.0.0.1".to_string()),
+                Some("test-agent".to_string()),
+                token_hash.to_string(),
+            )
+            .await
+            .unwrap();
+        }
+
+        // Self-service: everything but the current session.
+        let others = revoke_user_sessions(&pool, test_user_id, Some(sessions[0].0))
+            .await
+            .unwrap();
+        let mut valid_after_others = Vec::new();
+        for (session_id, token_hash) in sessions {
+            valid_after_others.push(
+                verify_session_db(&pool, session_id, token_hash)
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        // Admin: every session.
+        let all = revoke_user_sessions(&pool, test_user_id, None)
+            .await
+            .unwrap();
+        let mut valid_after_all = Vec::new();
+        for (session_id, token_hash) in sessions {
+            valid_after_all.push(
+                verify_session_db(&pool, session_id, token_hash)
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        let _ = sqlx::query("DELETE FROM user_sessions WHERE user_id = $1")
+            .bind(test_user_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(test_user_id)
+            .execute(&pool)
+            .await;
+
+        assert_eq!(others, 2);
+        assert_eq!(valid_after_others, vec![true, false, false]);
+        assert_eq!(all, 1);
+        assert_eq!(valid_after_all, vec![false, false, false]);
+    }
+
     #[tokio::test]
     async fn test_update_session_org() {
         let Some(pool) = setup_pool().await else {
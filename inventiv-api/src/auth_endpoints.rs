@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
 
-use crate::{auth, AppState};
+use crate::{auth, simple_logger, AppState};
 
 #[derive(Debug, Deserialize)]
 pub struct LoginRequest {
@@ -718,3 +718,48 @@ pub async fn revoke_session_endpoint(
         }
     }
 }
+
+/// Revoke every session of the current user except the one making the request
+pub async fn revoke_other_sessions(
+    State(state): State<Arc<AppState>>,
+    axum::extract::Extension(user): axum::extract::Extension<auth::AuthUser>,
+) -> impl IntoResponse {
+    let current_session_id = match uuid::Uuid::parse_str(&user.session_id) {
+        Ok(id) => id,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error":"invalid_session_id"})),
+            )
+                .into_response();
+        }
+    };
+
+    match auth::revoke_user_sessions(&state.db, user.user_id, Some(current_session_id)).await {
+        Ok(revoked) => {
+            let _ = simple_logger::log_action_with_metadata(
+                &state.db,
+                "REVOKE_USER_SESSIONS",
+                "success",
+                None,
+                None,
+                Some(json!({
+                    "user_id": user.user_id,
+                    "revoked_by": user.user_id,
+                    "kept_session_id": current_session_id,
+                    "revoked": revoked,
+                })),
+            )
+            .await;
+            Json(json!({"status":"ok","revoked": revoked})).into_response()
+        }
+        Err(e) => {
+            tracing::error!("Failed to revoke sessions: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error":"db_error","message": e.to_string()})),
+            )
+                .into_response()
+        }
+    }
+}
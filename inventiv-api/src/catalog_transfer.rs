@@ -0,0 +1,546 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::{FromRow, Pool, Postgres};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::auth;
+use crate::simple_logger;
+use crate::AppState;
+
+/// Bumped when the document layout changes incompatibly.
+pub const CATALOG_FORMAT_VERSION: i32 = 1;
+
+/// Portable catalog snapshot. Rows reference each other by `code` (models by `model_id`),
+/// never by UUID, so a document exported from one environment imports into another.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CatalogDocument {
+    pub version: i32,
+    pub exported_at: Option<DateTime<Utc>>,
+    pub providers: Vec<CatalogProvider>,
+    pub regions: Vec<CatalogRegion>,
+    pub zones: Vec<CatalogZone>,
+    pub instance_types: Vec<CatalogInstanceType>,
+    pub models: Vec<CatalogModel>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct CatalogProvider {
+    pub code: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub is_active: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct CatalogRegion {
+    pub provider_code: String,
+    pub code: String,
+    pub name: String,
+    pub is_active: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct CatalogZone {
+    pub provider_code: String,
+    pub region_code: String,
+    pub code: String,
+    pub name: String,
+    pub is_active: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct CatalogZoneAvailability {
+    pub zone_code: String,
+    pub is_available: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct CatalogInstanceType {
+    pub provider_code: String,
+    pub code: String,
+    pub name: String,
+    pub gpu_count: i32,
+    pub vram_per_gpu_gb: i32,
+    pub cpu_count: Option<i32>,
+    pub ram_gb: Option<i32>,
+    pub bandwidth_bps: Option<i64>,
+    pub cost_per_hour: Option<f64>,
+    pub is_active: bool,
+    pub allocation_params: serde_json::Value,
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub zones: Vec<CatalogZoneAvailability>,
+}
+
+/// Model entry. `forward_headers` is deliberately not exported (it may hold worker credentials).
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct CatalogModel {
+    pub model_id: String,
+    pub name: String,
+    pub required_vram_gb: i32,
+    pub context_length: i32,
+    pub is_active: bool,
+    pub public: bool,
+    pub data_volume_gb: Option<i64>,
+    pub stale_window_seconds: Option<i32>,
+    pub boot_image_id: Option<String>,
+    pub metadata: serde_json::Value,
+    pub default_params: Option<serde_json::Value>,
+    pub deprecated_at: Option<DateTime<Utc>>,
+    /// `model_id` of the replacement model.
+    pub replacement_model: Option<String>,
+    /// `model_id` of the fallback model.
+    pub fallback_model: Option<String>,
+}
+
+/// Rows upserted per entity by an import.
+#[derive(Debug, Default, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CatalogImportSummary {
+    pub providers: usize,
+    pub regions: usize,
+    pub zones: usize,
+    pub instance_types: usize,
+    pub instance_type_zones: usize,
+    pub models: usize,
+}
+
+#[derive(Debug)]
+pub enum CatalogImportError {
+    /// The document is not importable (unsupported version, dangling code reference).
+    Invalid(String),
+    Database(sqlx::Error),
+}
+
+impl std::fmt::Display for CatalogImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CatalogImportError::Invalid(msg) => write!(f, "Invalid catalog document: {}", msg),
+            CatalogImportError::Database(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for CatalogImportError {}
+
+impl From<sqlx::Error> for CatalogImportError {
+    fn from(e: sqlx::Error) -> Self {
+        CatalogImportError::Database(e)
+    }
+}
+
+pub async fn export_catalog(db: &Pool<Postgres>) -> Result<CatalogDocument, sqlx::Error> {
+    let providers = sqlx::query_as::<_, CatalogProvider>(
+        "SELECT code, name, description, is_active FROM providers ORDER BY code",
+    )
+    .fetch_all(db)
+    .await?;
+    let regions = sqlx::query_as::<_, CatalogRegion>(
+        r#"
+        SELECT p.code AS provider_code, r.code, r.name, r.is_active
+        FROM regions r
+        JOIN providers p ON p.id = r.provider_id
+        ORDER BY p.code, r.code
+        "#,
+    )
+    .fetch_all(db)
+    .await?;
+    let zones = sqlx::query_as::<_, CatalogZone>(
+        r#"
+        SELECT p.code AS provider_code, r.code AS region_code, z.code, z.name, z.is_active
+        FROM zones z
+        JOIN regions r ON r.id = z.region_id
+        JOIN providers p ON p.id = r.provider_id
+        ORDER BY p.code, r.code, z.code
+        "#,
+    )
+    .fetch_all(db)
+    .await?;
+    let mut instance_types = sqlx::query_as::<_, CatalogInstanceType>(
+        r#"
+        SELECT p.code AS provider_code, it.code, it.name, it.gpu_count, it.vram_per_gpu_gb,
+               it.cpu_count, it.ram_gb, it.bandwidth_bps,
+               CAST(it.cost_per_hour AS float8) AS cost_per_hour,
+               it.is_active, it.allocation_params
+        FROM instance_types it
+        JOIN providers p ON p.id = it.provider_id
+        ORDER BY p.code, it.code
+        "#,
+    )
+    .fetch_all(db)
+    .await?;
+    let availability: Vec<(String, String, String, bool)> = sqlx::query_as(
+        r#"
+        SELECT p.code, it.code, z.code, COALESCE(itz.is_available, true)
+        FROM instance_type_zones itz
+        JOIN instance_types it ON it.id = itz.instance_type_id
+        JOIN providers p ON p.id = it.provider_id
+        JOIN zones z ON z.id = itz.zone_id
+        ORDER BY p.code, it.code, z.code
+        "#,
+    )
+    .fetch_all(db)
+    .await?;
+    for (provider_code, type_code, zone_code, is_available) in availability {
+        if let Some(it) = instance_types
+            .iter_mut()
+            .find(|it| it.provider_code == provider_code && it.code == type_code)
+        {
+            it.zones.push(CatalogZoneAvailability {
+                zone_code,
+                is_available,
+            });
+        }
+    }
+    let models = sqlx::query_as::<_, CatalogModel>(
+        r#"
+        SELECT m.model_id, m.name, m.required_vram_gb, m.context_length, m.is_active, m.public,
+               m.data_volume_gb, m.stale_window_seconds, m.boot_image_id, m.metadata,
+               m.default_params, m.deprecated_at,
+               rm.model_id AS replacement_model,
+               fm.model_id AS fallback_model
+        FROM models m
+        LEFT JOIN models rm ON rm.id = m.replacement_model_id
+        LEFT JOIN models fm ON fm.id = m.fallback_model_id
+        ORDER BY m.model_id
+        "#,
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(CatalogDocument {
+        version: CATALOG_FORMAT_VERSION,
+        exported_at: Some(Utc::now()),
+        providers,
+        regions,
+        zones,
+        instance_types,
+        models,
+    })
+}
+
+async fn id_by_code(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    sql: &str,
+    codes: &[&str],
+    what: &str,
+) -> Result<Uuid, CatalogImportError> {
+    let mut q = sqlx::query_scalar::<_, Uuid>(sql);
+    for c in codes {
+        q = q.bind(*c);
+    }
+    q.fetch_optional(&mut **tx).await?.ok_or_else(|| {
+        CatalogImportError::Invalid(format!("unknown {} '{}'", what, codes.join("/")))
+    })
+}
+
+async fn provider_id(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    code: &str,
+) -> Result<Uuid, CatalogImportError> {
+    id_by_code(
+        tx,
+        "SELECT id FROM providers WHERE code = $1",
+        &[code],
+        "provider",
+    )
+    .await
+}
+
+/// Upsert a catalog document in one transaction. Idempotent, like the catalog seeds: rows are
+/// matched on their codes, existing rows are updated in place and nothing is deleted.
+pub async fn import_catalog(
+    db: &Pool<Postgres>,
+    doc: &CatalogDocument,
+) -> Result<CatalogImportSummary, CatalogImportError> {
+    if doc.version != CATALOG_FORMAT_VERSION {
+        return Err(CatalogImportError::Invalid(format!(
+            "unsupported version {} (expected {})",
+            doc.version, CATALOG_FORMAT_VERSION
+        )));
+    }
+    let mut summary = CatalogImportSummary::default();
+    let mut tx = db.begin().await?;
+
+    for p in &doc.providers {
+        sqlx::query(
+            r#"
+            INSERT INTO providers (id, name, code, description, is_active)
+            VALUES (gen_random_uuid(), $1, $2, $3, $4)
+            ON CONFLICT (code) DO UPDATE
+              SET name = EXCLUDED.name,
+                  description = EXCLUDED.description,
+                  is_active = EXCLUDED.is_active
+            "#,
+        )
+        .bind(&p.name)
+        .bind(&p.code)
+        .bind(&p.description)
+        .bind(p.is_active)
+        .execute(&mut *tx)
+        .await?;
+        summary.providers += 1;
+    }
+
+    for r in &doc.regions {
+        let pid = provider_id(&mut tx, &r.provider_code).await?;
+        sqlx::query(
+            r#"
+            INSERT INTO regions (id, provider_id, name, code, is_active)
+            VALUES (gen_random_uuid(), $1, $2, $3, $4)
+            ON CONFLICT (provider_id, code) DO UPDATE
+              SET name = EXCLUDED.name,
+                  is_active = EXCLUDED.is_active
+            "#,
+        )
+        .bind(pid)
+        .bind(&r.name)
+        .bind(&r.code)
+        .bind(r.is_active)
+        .execute(&mut *tx)
+        .await?;
+        summary.regions += 1;
+    }
+
+    for z in &doc.zones {
+        let pid = provider_id(&mut tx, &z.provider_code).await?;
+        let region_id = id_by_code(
+            &mut tx,
+            "SELECT r.id FROM regions r JOIN providers p ON p.id = r.provider_id
+             WHERE p.code = $1 AND r.code = $2",
+            &[&z.provider_code, &z.region_code],
+            "region",
+        )
+        .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO zones (id, region_id, provider_id, name, code, is_active)
+            VALUES (gen_random_uuid(), $1, $2, $3, $4, $5)
+            ON CONFLICT (provider_id, code) DO UPDATE
+              SET region_id = EXCLUDED.region_id,
+                  name = EXCLUDED.name,
+                  is_active = EXCLUDED.is_active
+            "#,
+        )
+        .bind(region_id)
+        .bind(pid)
+        .bind(&z.name)
+        .bind(&z.code)
+        .bind(z.is_active)
+        .execute(&mut *tx)
+        .await?;
+        summary.zones += 1;
+    }
+
+    for it in &doc.instance_types {
+        let pid = provider_id(&mut tx, &it.provider_code).await?;
+        let type_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO instance_types
+              (id, provider_id, name, code, gpu_count, vram_per_gpu_gb, cpu_count, ram_gb,
+               bandwidth_bps, cost_per_hour, is_active, allocation_params)
+            VALUES (gen_random_uuid(), $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            ON CONFLICT (provider_id, code) DO UPDATE
+              SET name = EXCLUDED.name,
+                  gpu_count = EXCLUDED.gpu_count,
+                  vram_per_gpu_gb = EXCLUDED.vram_per_gpu_gb,
+                  cpu_count = EXCLUDED.cpu_count,
+                  ram_gb = EXCLUDED.ram_gb,
+                  bandwidth_bps = EXCLUDED.bandwidth_bps,
+                  cost_per_hour = EXCLUDED.cost_per_hour,
+                  is_active = EXCLUDED.is_active,
+                  allocation_params = EXCLUDED.allocation_params
+            RETURNING id
+            "#,
+        )
+        .bind(pid)
+        .bind(&it.name)
+        .bind(&it.code)
+        .bind(it.gpu_count)
+        .bind(it.vram_per_gpu_gb)
+        .bind(it.cpu_count)
+        .bind(it.ram_gb)
+        .bind(it.bandwidth_bps)
+        .bind(it.cost_per_hour)
+        .bind(it.is_active)
+        .bind(&it.allocation_params)
+        .fetch_one(&mut *tx)
+        .await?;
+        summary.instance_types += 1;
+
+        for a in &it.zones {
+            let zone_id = id_by_code(
+                &mut tx,
+                "SELECT z.id FROM zones z JOIN providers p ON p.id = z.provider_id
+                 WHERE p.code = $1 AND z.code = $2",
+                &[&it.provider_code, &a.zone_code],
+                "zone",
+            )
+            .await?;
+            sqlx::query(
+                r#"
+                INSERT INTO instance_type_zones (instance_type_id, zone_id, is_available)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (instance_type_id, zone_id) DO UPDATE
+                  SET is_available = EXCLUDED.is_available
+                "#,
+            )
+            .bind(type_id)
+            .bind(zone_id)
+            .bind(a.is_available)
+            .execute(&mut *tx)
+            .await?;
+            summary.instance_type_zones += 1;
+        }
+    }
+
+    for m in &doc.models {
+        sqlx::query(
+            r#"
+            INSERT INTO models
+              (id, name, model_id, required_vram_gb, context_length, is_active, public,
+               data_volume_gb, stale_window_seconds, boot_image_id, metadata, default_params,
+               deprecated_at, created_at, updated_at)
+            VALUES (gen_random_uuid(), $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, NOW(), NOW())
+            ON CONFLICT (model_id) DO UPDATE
+              SET name = EXCLUDED.name,
+                  required_vram_gb = EXCLUDED.required_vram_gb,
+                  context_length = EXCLUDED.context_length,
+                  is_active = EXCLUDED.is_active,
+                  public = EXCLUDED.public,
+                  data_volume_gb = EXCLUDED.data_volume_gb,
+                  stale_window_seconds = EXCLUDED.stale_window_seconds,
+                  boot_image_id = EXCLUDED.boot_image_id,
+                  metadata = EXCLUDED.metadata,
+                  default_params = EXCLUDED.default_params,
+                  deprecated_at = EXCLUDED.deprecated_at,
+                  updated_at = NOW()
+            "#,
+        )
+        .bind(&m.name)
+        .bind(&m.model_id)
+        .bind(m.required_vram_gb)
+        .bind(m.context_length)
+        .bind(m.is_active)
+        .bind(m.public)
+        .bind(m.data_volume_gb)
+        .bind(m.stale_window_seconds)
+        .bind(&m.boot_image_id)
+        .bind(&m.metadata)
+        .bind(&m.default_params)
+        .bind(m.deprecated_at)
+        .execute(&mut *tx)
+        .await?;
+        summary.models += 1;
+    }
+
+    // Model-to-model links once every model of the document exists.
+    for m in &doc.models {
+        let mut links = [None, None];
+        for (slot, code) in links
+            .iter_mut()
+            .zip([&m.replacement_model, &m.fallback_model])
+        {
+            if let Some(code) = code {
+                *slot = Some(
+                    id_by_code(
+                        &mut tx,
+                        "SELECT id FROM models WHERE model_id = $1",
+                        &[code],
+                        "model",
+                    )
+                    .await?,
+                );
+            }
+        }
+        sqlx::query(
+            "UPDATE models SET replacement_model_id = $2, fallback_model_id = $3 WHERE model_id = $1",
+        )
+        .bind(&m.model_id)
+        .bind(links[0])
+        .bind(links[1])
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(summary)
+}
+
+#[utoipa::path(
+    get,
+    path = "/catalog/export",
+    tag = "Settings",
+    responses(
+        (status = 200, description = "Full catalog document", body = CatalogDocument),
+        (status = 403, description = "Admin required")
+    )
+)]
+pub async fn get_catalog_export(
+    State(state): State<Arc<AppState>>,
+    axum::extract::Extension(user): axum::extract::Extension<auth::AuthUser>,
+) -> impl IntoResponse {
+    if let Err(e) = auth::require_admin(&user) {
+        return e.into_response();
+    }
+    match export_catalog(&state.db).await {
+        Ok(doc) => Json(doc).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error":"db_error","message": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/catalog/import",
+    tag = "Settings",
+    request_body = CatalogDocument,
+    responses(
+        (status = 200, description = "Catalog upserted", body = CatalogImportSummary),
+        (status = 400, description = "Unsupported version or unknown code reference"),
+        (status = 403, description = "Admin required")
+    )
+)]
+pub async fn post_catalog_import(
+    State(state): State<Arc<AppState>>,
+    axum::extract::Extension(user): axum::extract::Extension<auth::AuthUser>,
+    Json(doc): Json<CatalogDocument>,
+) -> impl IntoResponse {
+    if let Err(e) = auth::require_admin(&user) {
+        return e.into_response();
+    }
+    let start = std::time::Instant::now();
+    match import_catalog(&state.db, &doc).await {
+        Ok(summary) => {
+            let _ = simple_logger::log_action_with_metadata(
+                &state.db,
+                "CATALOG_IMPORT",
+                "success",
+                None,
+                None,
+                Some(json!({
+                    "user_id": user.user_id,
+                    "duration_ms": start.elapsed().as_millis() as u64,
+                    "summary": &summary,
+                })),
+            )
+            .await;
+            Json(summary).into_response()
+        }
+        Err(CatalogImportError::Invalid(message)) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error":"invalid_catalog","message": message})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error":"db_error","message": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
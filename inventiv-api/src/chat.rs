@@ -3,7 +3,7 @@ use serde::Serialize;
 use sqlx::{Pool, Postgres};
 use std::sync::Arc;
 
-use crate::{auth, AppState};
+use crate::{auth, worker_routing, AppState};
 
 #[derive(Debug, Serialize)]
 pub struct ChatModel {
@@ -55,22 +55,21 @@ pub async fn list_chat_models(
 ) -> impl IntoResponse {
     // 1) Compute "live" model ids from workers (same logic as /v1/models).
     let stale = worker_stale_seconds_db(&state.db).await;
-    let live: Vec<String> = sqlx::query_scalar(
+    let live: Vec<String> = sqlx::query_scalar(&format!(
         r#"
         SELECT DISTINCT worker_model_id
-        FROM instances
-        WHERE status::text = 'ready'
-          AND ip_address IS NOT NULL
-          AND (worker_status = 'ready' OR worker_status IS NULL)
+        FROM instances i
+        WHERE {routable}
           AND worker_model_id IS NOT NULL
           AND GREATEST(
               COALESCE(worker_last_heartbeat, 'epoch'::timestamptz),
               COALESCE(last_health_check, 'epoch'::timestamptz),
-              COALESCE((last_reconciliation AT TIME ZONE 'UTC'), 'epoch'::timestamptz)
+              COALESCE(last_reconciliation, 'epoch'::timestamptz)
             ) > NOW() - ($1::bigint * INTERVAL '1 second')
         ORDER BY worker_model_id
         "#,
-    )
+        routable = worker_routing::ROUTABLE_INSTANCE_SQL
+    ))
     .bind(stale)
     .fetch_all(&state.db)
     .await
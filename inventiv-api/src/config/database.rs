@@ -1,14 +1,13 @@
-use sqlx::{postgres::PgPoolOptions, Pool, Postgres};
+use inventiv_common::db_pool::PoolConfig;
+use sqlx::{Pool, Postgres};
+
+/// API pool size when DATABASE_MAX_CONNECTIONS is unset: the proxy and background jobs share it.
+const DEFAULT_MAX_CONNECTIONS: u32 = 20;
 
 /// Create and configure database connection pool
 pub async fn create_pool(database_url: &str) -> Result<Pool<Postgres>, sqlx::Error> {
-    let max_connections = std::env::var("DATABASE_MAX_CONNECTIONS")
-        .ok()
-        .and_then(|s| s.parse::<u32>().ok())
-        .unwrap_or(5);
-
-    PgPoolOptions::new()
-        .max_connections(max_connections)
+    PoolConfig::from_env(DEFAULT_MAX_CONNECTIONS)
+        .options()
         .connect(database_url)
         .await
 }
@@ -1,6 +1,8 @@
-use axum::extract::{Query, State};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
 use axum::Json;
-use chrono::Timelike;
+use inventiv_common::utc_buckets;
+use inventiv_common::NON_BILLABLE_COMPUTE_STATUSES;
 use serde::{Deserialize, Serialize};
 use sqlx::Postgres;
 use std::sync::Arc;
@@ -14,14 +16,23 @@ use crate::AppState;
 #[derive(Deserialize)]
 pub struct SeriesParams {
     pub minutes: Option<i64>,
+    /// Forecast method ("allocation" | "blended"); only used by the forecast series. Default "allocation".
+    pub method: Option<String>,
     pub provider_id: Option<uuid::Uuid>,
     pub instance_id: Option<uuid::Uuid>,
 }
 
+#[derive(Deserialize)]
+pub struct CostCurrentParams {
+    /// Forecast method ("allocation" | "blended"). Default "allocation".
+    pub method: Option<String>,
+}
+
 #[derive(Serialize, sqlx::FromRow)]
 pub struct ForecastMinuteRow {
     pub bucket_minute: chrono::DateTime<chrono::Utc>,
     pub provider_id: Option<uuid::Uuid>,
+    pub method: String,
     pub burn_rate_eur_per_hour: f64,
     pub forecast_eur_per_minute: f64,
     pub forecast_eur_per_hour: f64,
@@ -49,24 +60,135 @@ pub struct CumulativeMinuteRow {
 #[derive(Serialize)]
 pub struct CostCurrentResponse {
     pub latest_bucket_minute: Option<chrono::DateTime<chrono::Utc>>,
+    /// Forecast method every row of `forecast` was computed with.
+    pub method: &'static str,
     pub forecast: Vec<ForecastMinuteRow>,
     pub cumulative_total: Option<CumulativeMinuteRow>,
 }
 
-fn default_minutes(v: Option<i64>) -> i64 {
-    v.unwrap_or(60).clamp(1, 60 * 24 * 31)
+// -----------------------------------------------------------------------------
+// Query param validation
+// -----------------------------------------------------------------------------
+//
+// Omitted params keep their defaults, but an explicit value that is invalid or out of range is
+// rejected with 400 instead of silently falling back (users would think their filter applied).
+
+/// 400 response for an invalid query param.
+pub type ParamError = (StatusCode, Json<serde_json::Value>);
+
+const MAX_SERIES_MINUTES: i64 = 60 * 24 * 31;
+const MAX_WINDOW_MINUTES: i64 = 60 * 24 * 365;
+
+fn invalid_param(param: &str, message: String) -> ParamError {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(serde_json::json!({
+            "error": "invalid_query_param",
+            "param": param,
+            "message": message,
+        })),
+    )
+}
+
+fn parse_minutes(param: &str, v: Option<i64>, default: i64, max: i64) -> Result<i64, ParamError> {
+    match v {
+        None => Ok(default),
+        Some(m) if (1..=max).contains(&m) => Ok(m),
+        Some(m) => Err(invalid_param(
+            param,
+            format!("{} must be between 1 and {} (got {})", param, max, m),
+        )),
+    }
+}
+
+fn default_minutes(v: Option<i64>) -> Result<i64, ParamError> {
+    parse_minutes("minutes", v, 60, MAX_SERIES_MINUTES)
+}
+
+fn parse_forecast_method(v: Option<&str>) -> Result<&'static str, ParamError> {
+    match v.map(|m| m.trim().to_ascii_lowercase()).as_deref() {
+        None | Some("allocation") => Ok("allocation"),
+        Some("blended") => Ok("blended"),
+        Some(other) => Err(invalid_param(
+            "method",
+            format!(
+                "method must be one of: allocation, blended (got '{}')",
+                other
+            ),
+        )),
+    }
+}
+
+pub fn parse_window(v: &str) -> Result<i64, ParamError> {
+    window_to_minutes(v.trim()).ok_or_else(|| {
+        invalid_param(
+            "window",
+            format!(
+                "unknown window '{}' (expected minute, hour, day, week_7d, month_30d, year_365d)",
+                v
+            ),
+        )
+    })
+}
+
+/// Aggregation bin for dashboard series.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+    Minute,
+    Hour,
+    Day,
 }
 
-pub async fn get_cost_current(State(state): State<Arc<AppState>>) -> Json<CostCurrentResponse> {
+impl Granularity {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "minute" => Some(Granularity::Minute),
+            "hour" => Some(Granularity::Hour),
+            "day" => Some(Granularity::Day),
+            _ => None,
+        }
+    }
+
+    fn interval_sql(self) -> &'static str {
+        match self {
+            Granularity::Minute => "interval '1 minute'",
+            Granularity::Hour => "interval '1 hour'",
+            Granularity::Day => "interval '1 day'",
+        }
+    }
+}
+
+fn parse_granularity(v: Option<&str>) -> Result<Option<Granularity>, ParamError> {
+    match v {
+        None => Ok(None),
+        Some(raw) => Granularity::parse(raw).map(Some).ok_or_else(|| {
+            invalid_param(
+                "granularity",
+                format!(
+                    "granularity must be one of: minute, hour, day (got '{}')",
+                    raw
+                ),
+            )
+        }),
+    }
+}
+
+pub async fn get_cost_current(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<CostCurrentParams>,
+) -> Result<Json<CostCurrentResponse>, ParamError> {
     let db = &state.db;
+    let method = parse_forecast_method(params.method.as_deref())?;
 
-    let latest_bucket: Option<chrono::DateTime<chrono::Utc>> =
-        sqlx::query_scalar("SELECT MAX(bucket_minute) FROM finops.cost_forecast_minute")
-            .fetch_optional(db)
-            .await
-            .ok()
-            .flatten()
-            .flatten();
+    let latest_bucket: Option<chrono::DateTime<chrono::Utc>> = sqlx::query_scalar(
+        "SELECT MAX(bucket_minute) FROM finops.cost_forecast_minute WHERE method = $1",
+    )
+    .bind(method)
+    .fetch_optional(db)
+    .await
+    .ok()
+    .flatten()
+    .flatten();
 
     let forecast = if let Some(bucket) = latest_bucket {
         sqlx::query_as::<Postgres, ForecastMinuteRow>(
@@ -74,6 +196,7 @@ pub async fn get_cost_current(State(state): State<Arc<AppState>>) -> Json<CostCu
             SELECT
               bucket_minute,
               provider_id,
+              method,
               burn_rate_eur_per_hour::float8 as burn_rate_eur_per_hour,
               forecast_eur_per_minute::float8 as forecast_eur_per_minute,
               forecast_eur_per_hour::float8 as forecast_eur_per_hour,
@@ -81,11 +204,12 @@ pub async fn get_cost_current(State(state): State<Arc<AppState>>) -> Json<CostCu
               forecast_eur_per_month_30d::float8 as forecast_eur_per_month_30d,
               forecast_eur_per_year_365d::float8 as forecast_eur_per_year_365d
             FROM finops.cost_forecast_minute
-            WHERE bucket_minute = $1
+            WHERE bucket_minute = $1 AND method = $2
             ORDER BY provider_id NULLS FIRST
             "#,
         )
         .bind(bucket)
+        .bind(method)
         .fetch_all(db)
         .await
         .unwrap_or_default()
@@ -111,24 +235,27 @@ pub async fn get_cost_current(State(state): State<Arc<AppState>>) -> Json<CostCu
     .ok()
     .flatten();
 
-    Json(CostCurrentResponse {
+    Ok(Json(CostCurrentResponse {
         latest_bucket_minute: latest_bucket,
+        method,
         forecast,
         cumulative_total,
-    })
+    }))
 }
 
 pub async fn get_cost_forecast_series(
     State(state): State<Arc<AppState>>,
     Query(params): Query<SeriesParams>,
-) -> Json<Vec<ForecastMinuteRow>> {
-    let minutes = default_minutes(params.minutes);
+) -> Result<Json<Vec<ForecastMinuteRow>>, ParamError> {
+    let minutes = default_minutes(params.minutes)?;
+    let method = parse_forecast_method(params.method.as_deref())?;
 
     let rows = sqlx::query_as::<Postgres, ForecastMinuteRow>(
         r#"
         SELECT
           bucket_minute,
           provider_id,
+          method,
           burn_rate_eur_per_hour::float8 as burn_rate_eur_per_hour,
           forecast_eur_per_minute::float8 as forecast_eur_per_minute,
           forecast_eur_per_hour::float8 as forecast_eur_per_hour,
@@ -137,17 +264,19 @@ pub async fn get_cost_forecast_series(
           forecast_eur_per_year_365d::float8 as forecast_eur_per_year_365d
         FROM finops.cost_forecast_minute
         WHERE provider_id IS NOT DISTINCT FROM $1
+          AND method = $3
         ORDER BY bucket_minute DESC
         LIMIT $2
         "#,
     )
     .bind(params.provider_id)
     .bind(minutes)
+    .bind(method)
     .fetch_all(&state.db)
     .await
     .unwrap_or_default();
 
-    Json(rows)
+    Ok(Json(rows))
 }
 
 // -----------------------------------------------------------------------------
@@ -368,8 +497,8 @@ pub async fn get_costs_dashboard_current(
 pub async fn get_cost_actual_series(
     State(state): State<Arc<AppState>>,
     Query(params): Query<SeriesParams>,
-) -> Json<Vec<ActualMinuteRow>> {
-    let minutes = default_minutes(params.minutes);
+) -> Result<Json<Vec<ActualMinuteRow>>, ParamError> {
+    let minutes = default_minutes(params.minutes)?;
 
     let rows = sqlx::query_as::<Postgres, ActualMinuteRow>(
         r#"
@@ -392,14 +521,14 @@ pub async fn get_cost_actual_series(
     .await
     .unwrap_or_default();
 
-    Json(rows)
+    Ok(Json(rows))
 }
 
 pub async fn get_cost_cumulative_series(
     State(state): State<Arc<AppState>>,
     Query(params): Query<SeriesParams>,
-) -> Json<Vec<CumulativeMinuteRow>> {
-    let minutes = default_minutes(params.minutes);
+) -> Result<Json<Vec<CumulativeMinuteRow>>, ParamError> {
+    let minutes = default_minutes(params.minutes)?;
 
     let rows = sqlx::query_as::<Postgres, CumulativeMinuteRow>(
         r#"
@@ -422,7 +551,7 @@ pub async fn get_cost_cumulative_series(
     .await
     .unwrap_or_default();
 
-    Json(rows)
+    Ok(Json(rows))
 }
 
 // -----------------------------------------------------------------------------
@@ -525,12 +654,6 @@ pub struct SummaryParams {
     pub limit_instances: Option<i64>,
 }
 
-fn now_minute_bucket(now: chrono::DateTime<chrono::Utc>) -> chrono::DateTime<chrono::Utc> {
-    now.with_second(0)
-        .and_then(|d| d.with_nanosecond(0))
-        .unwrap_or(now)
-}
-
 fn window_to_minutes(window: &str) -> Option<i64> {
     match window.to_ascii_lowercase().as_str() {
         "minute" | "1m" => Some(1),
@@ -656,23 +779,25 @@ pub async fn get_costs_dashboard_summary(
         }
     }
 
-    // Allocation (current) snapshot: derived directly from instances + instance_types cost_per_hour.
+    // Allocation (current) snapshot: derived directly from instances + effective hourly price
+    // (pricing_overrides, else instance_types.cost_per_hour).
     // Use "now minute bucket" so the UI has a stable timestamp.
-    let at_minute = now_minute_bucket(chrono::Utc::now());
+    let at_minute = utc_buckets::minute_bucket(&chrono::Utc::now());
 
     let total_burn_rate: f64 = sqlx::query_scalar(
         r#"
-        SELECT COALESCE(SUM(COALESCE(it.cost_per_hour, 0)), 0)::float8
+        SELECT COALESCE(SUM(public.effective_cost_per_hour(i.instance_type_id, NOW())), 0)::float8
         FROM instances i
         LEFT JOIN instance_types it ON it.id = i.instance_type_id
         WHERE i.is_archived = false
           AND i.provider_instance_id IS NOT NULL
-          AND (i.status::text NOT IN ('terminated','failed','provisioning_failed','startup_failed','archived'))
+          AND NOT (i.status::text = ANY($2))
           AND i.created_at <= $1
           AND (i.terminated_at IS NULL OR i.terminated_at > $1)
         "#,
     )
     .bind(at_minute)
+    .bind(NON_BILLABLE_COMPUTE_STATUSES)
     .fetch_optional(db)
     .await
     .ok()
@@ -685,17 +810,17 @@ pub async fn get_costs_dashboard_summary(
           p.id as provider_id,
           p.code as provider_code,
           p.name as provider_name,
-          COALESCE(SUM(COALESCE(it.cost_per_hour, 0)), 0)::float8 as burn_rate_eur_per_hour,
-          (COALESCE(SUM(COALESCE(it.cost_per_hour, 0)), 0) / 60.0)::float8 as forecast_eur_per_minute,
-          (COALESCE(SUM(COALESCE(it.cost_per_hour, 0)), 0))::float8 as forecast_eur_per_hour,
-          (COALESCE(SUM(COALESCE(it.cost_per_hour, 0)), 0) * 24.0)::float8 as forecast_eur_per_day,
-          (COALESCE(SUM(COALESCE(it.cost_per_hour, 0)), 0) * 24.0 * 30.0)::float8 as forecast_eur_per_month_30d
+          COALESCE(SUM(public.effective_cost_per_hour(i.instance_type_id, NOW())), 0)::float8 as burn_rate_eur_per_hour,
+          (COALESCE(SUM(public.effective_cost_per_hour(i.instance_type_id, NOW())), 0) / 60.0)::float8 as forecast_eur_per_minute,
+          (COALESCE(SUM(public.effective_cost_per_hour(i.instance_type_id, NOW())), 0))::float8 as forecast_eur_per_hour,
+          (COALESCE(SUM(public.effective_cost_per_hour(i.instance_type_id, NOW())), 0) * 24.0)::float8 as forecast_eur_per_day,
+          (COALESCE(SUM(public.effective_cost_per_hour(i.instance_type_id, NOW())), 0) * 24.0 * 30.0)::float8 as forecast_eur_per_month_30d
         FROM instances i
         JOIN providers p ON p.id = i.provider_id
         LEFT JOIN instance_types it ON it.id = i.instance_type_id
         WHERE i.is_archived = false
           AND i.provider_instance_id IS NOT NULL
-          AND (i.status::text NOT IN ('terminated','failed','provisioning_failed','startup_failed','archived'))
+          AND NOT (i.status::text = ANY($2))
           AND i.created_at <= $1
           AND (i.terminated_at IS NULL OR i.terminated_at > $1)
         GROUP BY p.id, p.code, p.name
@@ -703,6 +828,7 @@ pub async fn get_costs_dashboard_summary(
         "#,
     )
     .bind(at_minute)
+    .bind(NON_BILLABLE_COMPUTE_STATUSES)
     .fetch_all(db)
     .await
     .unwrap_or_default();
@@ -715,11 +841,11 @@ pub async fn get_costs_dashboard_summary(
           r.id as region_id,
           r.code as region_code,
           r.name as region_name,
-          COALESCE(SUM(COALESCE(it.cost_per_hour, 0)), 0)::float8 as burn_rate_eur_per_hour,
-          (COALESCE(SUM(COALESCE(it.cost_per_hour, 0)), 0) / 60.0)::float8 as forecast_eur_per_minute,
-          (COALESCE(SUM(COALESCE(it.cost_per_hour, 0)), 0))::float8 as forecast_eur_per_hour,
-          (COALESCE(SUM(COALESCE(it.cost_per_hour, 0)), 0) * 24.0)::float8 as forecast_eur_per_day,
-          (COALESCE(SUM(COALESCE(it.cost_per_hour, 0)), 0) * 24.0 * 30.0)::float8 as forecast_eur_per_month_30d
+          COALESCE(SUM(public.effective_cost_per_hour(i.instance_type_id, NOW())), 0)::float8 as burn_rate_eur_per_hour,
+          (COALESCE(SUM(public.effective_cost_per_hour(i.instance_type_id, NOW())), 0) / 60.0)::float8 as forecast_eur_per_minute,
+          (COALESCE(SUM(public.effective_cost_per_hour(i.instance_type_id, NOW())), 0))::float8 as forecast_eur_per_hour,
+          (COALESCE(SUM(public.effective_cost_per_hour(i.instance_type_id, NOW())), 0) * 24.0)::float8 as forecast_eur_per_day,
+          (COALESCE(SUM(public.effective_cost_per_hour(i.instance_type_id, NOW())), 0) * 24.0 * 30.0)::float8 as forecast_eur_per_month_30d
         FROM instances i
         JOIN providers p ON p.id = i.provider_id
         LEFT JOIN zones z ON z.id = i.zone_id
@@ -727,7 +853,7 @@ pub async fn get_costs_dashboard_summary(
         LEFT JOIN instance_types it ON it.id = i.instance_type_id
         WHERE i.is_archived = false
           AND i.provider_instance_id IS NOT NULL
-          AND (i.status::text NOT IN ('terminated','failed','provisioning_failed','startup_failed','archived'))
+          AND NOT (i.status::text = ANY($2))
           AND i.created_at <= $1
           AND (i.terminated_at IS NULL OR i.terminated_at > $1)
         GROUP BY p.id, p.code, r.id, r.code, r.name
@@ -735,6 +861,7 @@ pub async fn get_costs_dashboard_summary(
         "#,
     )
     .bind(at_minute)
+    .bind(NON_BILLABLE_COMPUTE_STATUSES)
     .fetch_all(db)
     .await
     .unwrap_or_default();
@@ -747,17 +874,17 @@ pub async fn get_costs_dashboard_summary(
           it.id as instance_type_id,
           it.code as instance_type_code,
           it.name as instance_type_name,
-          COALESCE(SUM(COALESCE(it.cost_per_hour, 0)), 0)::float8 as burn_rate_eur_per_hour,
-          (COALESCE(SUM(COALESCE(it.cost_per_hour, 0)), 0) / 60.0)::float8 as forecast_eur_per_minute,
-          (COALESCE(SUM(COALESCE(it.cost_per_hour, 0)), 0))::float8 as forecast_eur_per_hour,
-          (COALESCE(SUM(COALESCE(it.cost_per_hour, 0)), 0) * 24.0)::float8 as forecast_eur_per_day,
-          (COALESCE(SUM(COALESCE(it.cost_per_hour, 0)), 0) * 24.0 * 30.0)::float8 as forecast_eur_per_month_30d
+          COALESCE(SUM(public.effective_cost_per_hour(i.instance_type_id, NOW())), 0)::float8 as burn_rate_eur_per_hour,
+          (COALESCE(SUM(public.effective_cost_per_hour(i.instance_type_id, NOW())), 0) / 60.0)::float8 as forecast_eur_per_minute,
+          (COALESCE(SUM(public.effective_cost_per_hour(i.instance_type_id, NOW())), 0))::float8 as forecast_eur_per_hour,
+          (COALESCE(SUM(public.effective_cost_per_hour(i.instance_type_id, NOW())), 0) * 24.0)::float8 as forecast_eur_per_day,
+          (COALESCE(SUM(public.effective_cost_per_hour(i.instance_type_id, NOW())), 0) * 24.0 * 30.0)::float8 as forecast_eur_per_month_30d
         FROM instances i
         JOIN providers p ON p.id = i.provider_id
         LEFT JOIN instance_types it ON it.id = i.instance_type_id
         WHERE i.is_archived = false
           AND i.provider_instance_id IS NOT NULL
-          AND (i.status::text NOT IN ('terminated','failed','provisioning_failed','startup_failed','archived'))
+          AND NOT (i.status::text = ANY($2))
           AND i.created_at <= $1
           AND (i.terminated_at IS NULL OR i.terminated_at > $1)
         GROUP BY p.id, p.code, it.id, it.code, it.name
@@ -765,6 +892,7 @@ pub async fn get_costs_dashboard_summary(
         "#,
     )
     .bind(at_minute)
+    .bind(NON_BILLABLE_COMPUTE_STATUSES)
     .fetch_all(db)
     .await
     .unwrap_or_default();
@@ -779,11 +907,11 @@ pub async fn get_costs_dashboard_summary(
           r.name as region_name,
           z.name as zone_name,
           it.name as instance_type_name,
-          COALESCE(it.cost_per_hour, 0)::float8 as burn_rate_eur_per_hour,
-          (COALESCE(it.cost_per_hour, 0) / 60.0)::float8 as forecast_eur_per_minute,
-          (COALESCE(it.cost_per_hour, 0))::float8 as forecast_eur_per_hour,
-          (COALESCE(it.cost_per_hour, 0) * 24.0)::float8 as forecast_eur_per_day,
-          (COALESCE(it.cost_per_hour, 0) * 24.0 * 30.0)::float8 as forecast_eur_per_month_30d
+          public.effective_cost_per_hour(i.instance_type_id, NOW())::float8 as burn_rate_eur_per_hour,
+          (public.effective_cost_per_hour(i.instance_type_id, NOW()) / 60.0)::float8 as forecast_eur_per_minute,
+          (public.effective_cost_per_hour(i.instance_type_id, NOW()))::float8 as forecast_eur_per_hour,
+          (public.effective_cost_per_hour(i.instance_type_id, NOW()) * 24.0)::float8 as forecast_eur_per_day,
+          (public.effective_cost_per_hour(i.instance_type_id, NOW()) * 24.0 * 30.0)::float8 as forecast_eur_per_month_30d
         FROM instances i
         JOIN providers p ON p.id = i.provider_id
         LEFT JOIN zones z ON z.id = i.zone_id
@@ -791,7 +919,7 @@ pub async fn get_costs_dashboard_summary(
         LEFT JOIN instance_types it ON it.id = i.instance_type_id
         WHERE i.is_archived = false
           AND i.provider_instance_id IS NOT NULL
-          AND (i.status::text NOT IN ('terminated','failed','provisioning_failed','startup_failed','archived'))
+          AND NOT (i.status::text = ANY($3))
           AND i.created_at <= $1
           AND (i.terminated_at IS NULL OR i.terminated_at > $1)
         ORDER BY burn_rate_eur_per_hour DESC, i.created_at DESC
@@ -800,6 +928,7 @@ pub async fn get_costs_dashboard_summary(
     )
     .bind(at_minute)
     .bind(limit_instances)
+    .bind(NON_BILLABLE_COMPUTE_STATUSES)
     .fetch_all(db)
     .await
     .unwrap_or_default();
@@ -850,18 +979,21 @@ pub struct CostsDashboardSeriesPoint {
 #[derive(Deserialize)]
 pub struct CostsDashboardSeriesParams {
     pub window: Option<String>, // "hour" | "day" | "week_7d" | "month_30d" | "year_365d" (aliases: 1h/1d/1w/30d/365d)
+    /// Bin size override ("minute" | "hour" | "day"); chosen from the window when omitted.
+    pub granularity: Option<String>,
     pub limit_points: Option<i64>,
 }
 
 pub async fn get_costs_dashboard_series(
     State(state): State<Arc<AppState>>,
     Query(params): Query<CostsDashboardSeriesParams>,
-) -> Json<Vec<CostsDashboardSeriesPoint>> {
+) -> Result<Json<Vec<CostsDashboardSeriesPoint>>, ParamError> {
     let db = &state.db;
-    let window_label = params.window.clone().unwrap_or_else(|| "hour".to_string());
-    let window_minutes = window_to_minutes(&window_label)
-        .unwrap_or(60)
-        .clamp(1, 60 * 24 * 365);
+    let window_minutes = match params.window.as_deref() {
+        Some(w) => parse_window(w)?,
+        None => 60,
+    };
+    let granularity = parse_granularity(params.granularity.as_deref())?;
     let limit_points = params.limit_points.unwrap_or(180).clamp(30, 400);
 
     let bucket_end: Option<chrono::DateTime<chrono::Utc>> =
@@ -873,14 +1005,16 @@ pub async fn get_costs_dashboard_series(
             .flatten();
 
     let Some(bucket_end) = bucket_end else {
-        return Json(vec![]);
+        return Ok(Json(vec![]));
     };
 
     let bucket_start = bucket_end - chrono::Duration::minutes((window_minutes - 1).max(0));
 
     // Choose an aggregation interval that keeps the number of points reasonable.
     // We use an allowlist of intervals to keep SQL injection impossible.
-    let interval_sql = if window_minutes <= 60 {
+    let interval_sql = if let Some(g) = granularity {
+        g.interval_sql()
+    } else if window_minutes <= 60 {
         "interval '1 minute'"
     } else if window_minutes <= 60 * 24 {
         // 1 day -> 15-min bins (96 pts)
@@ -897,6 +1031,7 @@ pub async fn get_costs_dashboard_series(
     };
 
     // Use date_bin for stable binning (PG14+). Timescale is PG14-compatible here.
+    // Bins are aligned on the UTC epoch, so day bins start at 00:00 UTC whatever the session TimeZone.
     let sql = format!(
         r#"
         SELECT
@@ -920,20 +1055,20 @@ pub async fn get_costs_dashboard_series(
         .await
         .unwrap_or_default();
 
-    Json(rows)
+    Ok(Json(rows))
 }
 
 pub async fn get_costs_dashboard_window(
     State(state): State<Arc<AppState>>,
     Query(params): Query<BreakdownWindowParams>,
-) -> Json<CostsDashboardWindowResponse> {
+) -> Result<Json<CostsDashboardWindowResponse>, ParamError> {
     let db = &state.db;
     let limit_instances = default_limit_instances(params.limit_instances);
 
     let window_minutes = if let Some(w) = params.window.as_deref() {
-        window_to_minutes(w).unwrap_or(60)
+        parse_window(w)?
     } else {
-        params.minutes.unwrap_or(60).clamp(1, 60 * 24 * 365)
+        parse_minutes("minutes", params.minutes, 60, MAX_WINDOW_MINUTES)?
     };
     let window_label = params
         .window
@@ -949,7 +1084,7 @@ pub async fn get_costs_dashboard_window(
             .flatten();
 
     let Some(bucket_end) = bucket_end else {
-        return Json(CostsDashboardWindowResponse {
+        return Ok(Json(CostsDashboardWindowResponse {
             window: window_label,
             window_minutes,
             bucket_end_minute: None,
@@ -959,7 +1094,7 @@ pub async fn get_costs_dashboard_window(
             by_region_eur: vec![],
             by_instance_type_eur: vec![],
             by_instance_eur: vec![],
-        });
+        }));
     };
 
     let bucket_start = bucket_end - chrono::Duration::minutes((window_minutes - 1).max(0));
@@ -1085,7 +1220,7 @@ pub async fn get_costs_dashboard_window(
     .await
     .unwrap_or_default();
 
-    Json(CostsDashboardWindowResponse {
+    Ok(Json(CostsDashboardWindowResponse {
         window: window_label,
         window_minutes,
         bucket_end_minute: Some(bucket_end),
@@ -1095,5 +1230,192 @@ pub async fn get_costs_dashboard_window(
         by_region_eur,
         by_instance_type_eur,
         by_instance_eur,
-    })
+    }))
+}
+
+#[derive(Serialize, sqlx::FromRow)]
+pub struct CostCenterCostRow {
+    /// NULL groups instances deployed without a cost_center (and no organization default).
+    pub cost_center: Option<String>,
+    pub instances_count: i64,
+    pub amount_eur: f64,
+}
+
+#[derive(Serialize)]
+pub struct CostsByCostCenterResponse {
+    pub window: String,
+    pub window_minutes: i64,
+    pub bucket_end_minute: Option<chrono::DateTime<chrono::Utc>>,
+    pub bucket_start_minute: Option<chrono::DateTime<chrono::Utc>>,
+    pub by_cost_center_eur: Vec<CostCenterCostRow>,
+}
+
+/// GET /finops/dashboard/costs/by-cost-center — actual spend per instance cost_center over a window.
+pub async fn get_costs_dashboard_by_cost_center(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<BreakdownWindowParams>,
+) -> Result<Json<CostsByCostCenterResponse>, ParamError> {
+    let db = &state.db;
+
+    let window_minutes = if let Some(w) = params.window.as_deref() {
+        parse_window(w)?
+    } else {
+        parse_minutes("minutes", params.minutes, 60, MAX_WINDOW_MINUTES)?
+    };
+    let window_label = params
+        .window
+        .clone()
+        .unwrap_or_else(|| format!("{}m", window_minutes));
+
+    let bucket_end: Option<chrono::DateTime<chrono::Utc>> =
+        sqlx::query_scalar("SELECT MAX(bucket_minute) FROM finops.cost_actual_minute")
+            .fetch_optional(db)
+            .await
+            .ok()
+            .flatten()
+            .flatten();
+
+    let Some(bucket_end) = bucket_end else {
+        return Ok(Json(CostsByCostCenterResponse {
+            window: window_label,
+            window_minutes,
+            bucket_end_minute: None,
+            bucket_start_minute: None,
+            by_cost_center_eur: vec![],
+        }));
+    };
+
+    let bucket_start = bucket_end - chrono::Duration::minutes((window_minutes - 1).max(0));
+
+    let by_cost_center_eur = sqlx::query_as::<Postgres, CostCenterCostRow>(
+        r#"
+        SELECT
+          i.cost_center,
+          COUNT(DISTINCT i.id)::bigint as instances_count,
+          COALESCE(SUM(m.amount_eur), 0)::float8 as amount_eur
+        FROM finops.cost_actual_minute m
+        JOIN instances i ON i.id = m.instance_id
+        WHERE m.bucket_minute >= $1 AND m.bucket_minute <= $2
+          AND m.instance_id IS NOT NULL
+        GROUP BY i.cost_center
+        ORDER BY amount_eur DESC
+        "#,
+    )
+    .bind(bucket_start)
+    .bind(bucket_end)
+    .fetch_all(db)
+    .await
+    .unwrap_or_default();
+
+    Ok(Json(CostsByCostCenterResponse {
+        window: window_label,
+        window_minutes,
+        bucket_end_minute: Some(bucket_end),
+        bucket_start_minute: Some(bucket_start),
+        by_cost_center_eur,
+    }))
+}
+
+// -----------------------------------------------------------------------------
+// Per-instance cost breakdown
+// -----------------------------------------------------------------------------
+
+#[derive(Serialize, sqlx::FromRow)]
+pub struct InstanceCostMinuteRow {
+    pub bucket_minute: chrono::DateTime<chrono::Utc>,
+    pub amount_eur: f64,
+}
+
+#[derive(Serialize)]
+pub struct InstanceCostSummary {
+    /// Sum of the instance's actual minutes (compute only; there is no volume pricing yet).
+    pub total_eur: f64,
+    /// Effective hourly price now (pricing override, else catalog).
+    pub hourly_rate_eur: Option<f64>,
+    pub billable_seconds: i64,
+    pub first_bucket_minute: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_bucket_minute: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Serialize)]
+pub struct InstanceCostResponse {
+    pub instance_id: uuid::Uuid,
+    pub summary: InstanceCostSummary,
+    /// Minute series (oldest first), limited to the last 31 days of the instance's life.
+    pub minutes: Vec<InstanceCostMinuteRow>,
+}
+
+/// GET /instances/:id/cost — summary + minute series from `finops.cost_actual_minute`.
+pub async fn get_instance_cost(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<Json<InstanceCostResponse>, (StatusCode, Json<serde_json::Value>)> {
+    let db = &state.db;
+
+    let instance: Option<(i64, Option<f64>)> = sqlx::query_as(
+        r#"
+        SELECT
+          public.instance_billable_seconds(i, NOW())::bigint,
+          public.effective_cost_per_hour(i.instance_type_id, NOW())::float8
+        FROM instances i
+        WHERE i.id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(db)
+    .await
+    .unwrap_or(None);
+    let Some((billable_seconds, hourly_rate_eur)) = instance else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "not_found", "message": "Instance not found"})),
+        ));
+    };
+
+    let (total_eur, first_bucket_minute, last_bucket_minute): (
+        f64,
+        Option<chrono::DateTime<chrono::Utc>>,
+        Option<chrono::DateTime<chrono::Utc>>,
+    ) = sqlx::query_as(
+        r#"
+        SELECT COALESCE(SUM(amount_eur), 0)::float8, MIN(bucket_minute), MAX(bucket_minute)
+        FROM finops.cost_actual_minute
+        WHERE instance_id = $1
+        "#,
+    )
+    .bind(id)
+    .fetch_one(db)
+    .await
+    .unwrap_or((0.0, None, None));
+
+    let minutes = sqlx::query_as::<Postgres, InstanceCostMinuteRow>(
+        r#"
+        SELECT bucket_minute, amount_eur FROM (
+          SELECT bucket_minute, SUM(amount_eur)::float8 AS amount_eur
+          FROM finops.cost_actual_minute
+          WHERE instance_id = $1
+          GROUP BY bucket_minute
+          ORDER BY bucket_minute DESC
+          LIMIT $2
+        ) recent
+        ORDER BY bucket_minute ASC
+        "#,
+    )
+    .bind(id)
+    .bind(MAX_SERIES_MINUTES)
+    .fetch_all(db)
+    .await
+    .unwrap_or_default();
+
+    Ok(Json(InstanceCostResponse {
+        instance_id: id,
+        summary: InstanceCostSummary {
+            total_eur,
+            hourly_rate_eur,
+            billable_seconds,
+            first_bucket_minute,
+            last_bucket_minute,
+        },
+        minutes,
+    }))
 }
@@ -1,5 +1,7 @@
 // Commands handlers (reconcile, catalog sync, action logs)
-use axum::extract::{Query, State};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
 use axum::Json;
 use redis::AsyncCommands;
 use serde::{Deserialize, Serialize};
@@ -9,47 +11,136 @@ use std::sync::Arc;
 use utoipa::IntoParams;
 
 use crate::app::AppState;
+use inventiv_common::error_code::{ErrorCode, Locale};
 
-/// POST /reconcile - Trigger manual reconciliation
+#[derive(Deserialize, IntoParams)]
+pub struct ReconcileParams {
+    /// Reconcile only this provider (default: the orchestrator's configured provider)
+    pub provider_code: Option<String>,
+    /// Reconcile only this zone (zone code of the provider)
+    pub zone: Option<String>,
+}
+
+/// POST /reconcile - Trigger manual reconciliation (optionally scoped to a provider and/or zone)
 #[utoipa::path(
     post,
     path = "/reconcile",
+    params(ReconcileParams),
     responses(
         (status = 200, description = "Reconciliation triggered", body = serde_json::Value),
+        (status = 404, description = "Unknown provider or zone", body = serde_json::Value),
         (status = 500, description = "Failed to trigger reconciliation", body = serde_json::Value)
     )
 )]
 pub async fn manual_reconcile_trigger(
     State(state): State<Arc<AppState>>,
-) -> Json<serde_json::Value> {
-    println!("🔍 Manual reconciliation triggered via API");
+    Query(params): Query<ReconcileParams>,
+) -> impl IntoResponse {
+    let provider_code = params
+        .provider_code
+        .as_deref()
+        .map(|s| s.trim().to_ascii_lowercase())
+        .filter(|s| !s.is_empty());
+    let zone = params
+        .zone
+        .as_deref()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    if let Some(code) = &provider_code {
+        let exists: bool =
+            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM providers WHERE code = $1)")
+                .bind(code)
+                .fetch_one(&state.db)
+                .await
+                .unwrap_or(false);
+        if !exists {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "provider_not_found",
+                    "message": format!("Unknown provider '{}'", code)
+                })),
+            );
+        }
+    }
+    if let Some(zone_code) = &zone {
+        let exists: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS(
+              SELECT 1
+              FROM zones z
+              JOIN regions r ON r.id = z.region_id
+              JOIN providers p ON p.id = r.provider_id
+              WHERE z.code = $1
+                AND ($2::text IS NULL OR p.code = $2)
+            )
+            "#,
+        )
+        .bind(zone_code)
+        .bind(provider_code.as_deref())
+        .fetch_one(&state.db)
+        .await
+        .unwrap_or(false);
+        if !exists {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "zone_not_found",
+                    "message": format!("Unknown zone '{}'", zone_code)
+                })),
+            );
+        }
+    }
+
+    println!(
+        "🔍 Manual reconciliation triggered via API (provider={:?}, zone={:?})",
+        provider_code, zone
+    );
 
     // Publish Redis event for orchestrator
     let event_payload = serde_json::json!({
-        "type": "CMD:RECONCILE"
+        "type": "CMD:RECONCILE",
+        "provider_code": provider_code,
+        "zone": zone
     })
     .to_string();
 
-    let mut conn = state
-        .redis_client
-        .get_multiplexed_async_connection()
-        .await
-        .unwrap();
+    let mut conn = match state.redis_client.get_multiplexed_async_connection().await {
+        Ok(c) => c,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "status": "error",
+                    "message": format!("Failed to trigger reconciliation: {:?}", e)
+                })),
+            )
+        }
+    };
     // Use turbofish to specify return type as unit ()
     match conn
         .publish::<_, _, ()>("orchestrator_events", &event_payload)
         .await
     {
-        Ok(_) => Json(json!({
-            "status": "triggered",
-            "message": "Reconciliation task has been triggered"
-        })),
+        Ok(_) => (
+            StatusCode::OK,
+            Json(json!({
+                "status": "triggered",
+                "provider_code": provider_code,
+                "zone": zone,
+                "message": "Reconciliation task has been triggered"
+            })),
+        ),
         Err(e) => {
             eprintln!("Failed to publish reconciliation event: {:?}", e);
-            Json(json!({
-                "status": "error",
-                "message": format!("Failed to trigger reconciliation: {:?}", e)
-            }))
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "status": "error",
+                    "message": format!("Failed to trigger reconciliation: {:?}", e)
+                })),
+            )
         }
     }
 }
@@ -98,6 +189,85 @@ pub async fn manual_catalog_sync_trigger(
     }
 }
 
+/// POST /catalog/sync/{provider_code} - Trigger catalog synchronization for a single provider
+#[utoipa::path(
+    post,
+    path = "/catalog/sync/{provider_code}",
+    params(("provider_code" = String, Path, description = "Provider code (e.g. scaleway, mock)")),
+    responses(
+        (status = 200, description = "Catalog Sync triggered for the provider", body = serde_json::Value),
+        (status = 404, description = "Unknown provider", body = serde_json::Value),
+        (status = 500, description = "Failed to trigger sync", body = serde_json::Value)
+    )
+)]
+pub async fn manual_provider_catalog_sync_trigger(
+    State(state): State<Arc<AppState>>,
+    Path(provider_code): Path<String>,
+) -> impl IntoResponse {
+    let provider_code = provider_code.trim().to_ascii_lowercase();
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM providers WHERE code = $1)")
+        .bind(&provider_code)
+        .fetch_one(&state.db)
+        .await
+        .unwrap_or(false);
+    if !exists {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "provider_not_found",
+                "message": format!("Unknown provider '{}'", provider_code)
+            })),
+        );
+    }
+
+    println!(
+        "🔄 Catalog Sync triggered via API (provider={})",
+        provider_code
+    );
+
+    let event_payload = serde_json::json!({
+        "type": "CMD:SYNC_CATALOG",
+        "provider_code": provider_code
+    })
+    .to_string();
+
+    let mut conn = match state.redis_client.get_multiplexed_async_connection().await {
+        Ok(c) => c,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "status": "error",
+                    "message": format!("Failed to trigger sync: {:?}", e)
+                })),
+            )
+        }
+    };
+    match conn
+        .publish::<_, _, ()>("orchestrator_events", &event_payload)
+        .await
+    {
+        Ok(_) => (
+            StatusCode::OK,
+            Json(json!({
+                "status": "triggered",
+                "provider_code": provider_code,
+                "message": "Catalog Sync task has been triggered"
+            })),
+        ),
+        Err(e) => {
+            eprintln!("Failed to publish sync event: {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "status": "error",
+                    "message": format!("Failed to trigger sync: {:?}", e)
+                })),
+            )
+        }
+    }
+}
+
 #[derive(Deserialize, IntoParams)]
 pub struct ActionLogQuery {
     instance_id: Option<uuid::Uuid>,
@@ -193,3 +363,42 @@ pub async fn list_action_types(
 
     Json(rows)
 }
+
+#[derive(Deserialize, IntoParams)]
+pub struct ErrorCodesQuery {
+    /// Message language: `en` (default) or `fr`
+    pub lang: Option<String>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct ErrorCodeResponse {
+    code: ErrorCode,
+    message: &'static str,
+}
+
+#[utoipa::path(
+    get,
+    path = "/error_codes",
+    params(ErrorCodesQuery),
+    responses(
+        (status = 200, description = "Failure codes written to instances and action logs", body = Vec<ErrorCodeResponse>)
+    )
+)]
+pub async fn list_error_codes(
+    Query(params): Query<ErrorCodesQuery>,
+) -> Json<Vec<ErrorCodeResponse>> {
+    let locale = params
+        .lang
+        .as_deref()
+        .map(Locale::parse)
+        .unwrap_or_default();
+    Json(
+        ErrorCode::ALL
+            .iter()
+            .map(|code| ErrorCodeResponse {
+                code: *code,
+                message: code.message(locale),
+            })
+            .collect(),
+    )
+}
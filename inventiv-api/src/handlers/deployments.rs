@@ -1,9 +1,16 @@
-use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use axum::{
+    extract::State,
+    http::{HeaderValue, StatusCode},
+    response::IntoResponse,
+    Json,
+};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::app::state::AppState;
 use crate::simple_logger;
+use inventiv_common::cloud_init;
+use inventiv_common::error_code::ErrorCode;
 use redis::AsyncCommands;
 
 #[derive(Deserialize, Serialize, utoipa::ToSchema)]
@@ -16,6 +23,31 @@ pub struct DeploymentRequest {
     pub instance_type: String,
     /// Optional model selection (UUID from /models). If omitted, orchestrator may fallback to env default.
     pub model_id: Option<uuid::Uuid>,
+    /// Optional runtime guard (hours). If omitted, the organization default applies (if any).
+    #[serde(default)]
+    pub max_runtime_hours: Option<i32>,
+    /// Terminate automatically once max_runtime_hours is exceeded (default: organization setting).
+    #[serde(default)]
+    pub auto_terminate_on_max_runtime: Option<bool>,
+    /// Optional time-to-live (minutes): the instance is terminated automatically once it expires.
+    #[serde(default)]
+    pub ttl_minutes: Option<i32>,
+    /// Optional cost attribution tag (propagated to provider tags). If omitted, the organization default applies.
+    #[serde(default)]
+    pub cost_center: Option<String>,
+    /// Optional provider volume id of an existing data volume (e.g. a persistent model cache) to
+    /// attach instead of creating a new one. It is kept when the instance terminates.
+    #[serde(default)]
+    pub reuse_volume_id: Option<String>,
+}
+
+/// A cost_center is forwarded verbatim as a provider tag, so keep it short and tag-safe.
+fn is_valid_cost_center(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= 64
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
 }
 
 #[derive(Serialize, utoipa::ToSchema)]
@@ -25,6 +57,29 @@ pub struct DeploymentResponse {
     pub message: Option<String>,
 }
 
+/// Instance statuses that no longer hold their data volumes (server gone or never created).
+const VOLUME_RELEASED_STATUSES: &[&str] =
+    &["terminated", "archived", "provisioning_failed", "failed"];
+
+/// Put the instance in its validation failure state and commit the validation transaction.
+async fn commit_failure(
+    mut tx: sqlx::Transaction<'_, sqlx::Postgres>,
+    instance_id: uuid::Uuid,
+    error_code: ErrorCode,
+    message: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE instances SET status='provisioning_failed', error_code=$2, error_message=$3, failed_at=NOW()
+         WHERE id=$1",
+    )
+    .bind(instance_id)
+    .bind(error_code.as_str())
+    .bind(message)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await
+}
+
 #[utoipa::path(
     post,
     path = "/deployments",
@@ -37,6 +92,25 @@ pub async fn create_deployment(
     State(state): State<Arc<AppState>>,
     axum::extract::Extension(user): axum::extract::Extension<crate::auth::AuthUser>,
     Json(payload): Json<DeploymentRequest>,
+) -> impl IntoResponse {
+    let uses_provider_id = payload.provider_id.is_some();
+    let mut response = deploy(state, user, payload).await.into_response();
+    if uses_provider_id {
+        // provider_id is kept for backward compatibility only; flag it so clients migrate.
+        let headers = response.headers_mut();
+        headers.insert("deprecation", HeaderValue::from_static("true"));
+        headers.insert(
+            "warning",
+            HeaderValue::from_static("299 - \"provider_id is deprecated, use provider_code\""),
+        );
+    }
+    response
+}
+
+async fn deploy(
+    state: Arc<AppState>,
+    user: crate::auth::AuthUser,
+    payload: DeploymentRequest,
 ) -> impl IntoResponse {
     let start = std::time::Instant::now();
     let instance_id_uuid = uuid::Uuid::new_v4(); // Create UUID first
@@ -53,19 +127,11 @@ pub async fn create_deployment(
     // If resolution fails we still insert an instance row (traceability), but validation will fail.
     let provider_id_resolved: Option<uuid::Uuid> = if let Some(pid) = payload.provider_id {
         Some(pid)
-    } else if let Some(code) = requested_provider_code.as_deref() {
-        sqlx::query_scalar("SELECT id FROM providers WHERE code = $1 LIMIT 1")
-            .bind(code)
-            .fetch_optional(&state.db)
-            .await
-            .unwrap_or(None)
     } else {
         // No provider specified -> default to provider code "scaleway"
         // (no hardcoded UUIDs; seed controls the actual id)
-        sqlx::query_scalar("SELECT id FROM providers WHERE code = 'scaleway' LIMIT 1")
-            .fetch_optional(&state.db)
-            .await
-            .unwrap_or(None)
+        let code = requested_provider_code.as_deref().unwrap_or("scaleway");
+        state.provider_codes.resolve(&state.db, code).await
     };
 
     let provider_id = match provider_id_resolved {
@@ -106,10 +172,11 @@ pub async fn create_deployment(
     // We want a durable instance_id from the very first request, even when validation fails.
     // So we insert the instance row first (zone/type can be NULL), then all errors can be logged with instance_id.
     //
+    // The row starts as `accepted` (queued); the orchestrator moves it to `provisioning` when it picks it up.
     // If this ever collides (extremely unlikely), we return 409 so devs notice immediately.
     let insert_initial = sqlx::query(
         "INSERT INTO instances (id, provider_id, zone_id, instance_type_id, organization_id, status, created_at, gpu_profile)
-         VALUES ($1, $2, NULL, NULL, $3, 'provisioning', NOW(), '{}')"
+         VALUES ($1, $2, NULL, NULL, $3, 'accepted', NOW(), '{}')"
     )
     .bind(instance_id_uuid)
     .bind(provider_id)
@@ -154,23 +221,55 @@ pub async fn create_deployment(
             "zone": payload.zone,
             "instance_type": payload.instance_type,
             "model_id": payload.model_id.map(|m| m.to_string()),
+            "max_runtime_hours": payload.max_runtime_hours,
+            "auto_terminate_on_max_runtime": payload.auto_terminate_on_max_runtime,
+            "ttl_minutes": payload.ttl_minutes,
+            "cost_center": payload.cost_center,
+            "reuse_volume_id": payload.reuse_volume_id,
         })),
     )
     .await
     .ok();
 
+    // Validation runs in one transaction holding the row lock; it commits either the validated
+    // update or the failure state, so a crash in between never leaves a half-updated row.
+    let mut tx = match state.db.begin().await {
+        Ok(t) => t,
+        Err(e) => {
+            let msg = format!("Database error starting validation: {:?}", e);
+            if let Some(id) = log_id {
+                let duration = start.elapsed().as_millis() as i32;
+                simple_logger::log_action_complete_with_metadata(
+                    &state.db,
+                    id,
+                    "failed",
+                    duration,
+                    Some(&msg),
+                    Some(serde_json::json!({"error_code": ErrorCode::DbError})),
+                )
+                .await
+                .ok();
+            }
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(DeploymentResponse {
+                    status: "failed".to_string(),
+                    instance_id,
+                    message: Some("Database error".to_string()),
+                }),
+            )
+                .into_response();
+        }
+    };
+    let _ = sqlx::query("SELECT 1 FROM instances WHERE id = $1 FOR UPDATE")
+        .bind(instance_id_uuid)
+        .execute(&mut *tx)
+        .await;
+
     // Basic validation: even if invalid, we keep the instance row + log tied to instance_id.
     if payload.zone.trim().is_empty() || payload.instance_type.trim().is_empty() {
         let msg = "Missing zone or instance_type";
-        let _ = sqlx::query(
-            "UPDATE instances SET status='provisioning_failed', error_code=$2, error_message=$3, failed_at=NOW()
-             WHERE id=$1"
-        )
-        .bind(instance_id_uuid)
-        .bind("MISSING_PARAMS")
-        .bind(msg)
-        .execute(&state.db)
-        .await;
+        let _ = commit_failure(tx, instance_id_uuid, ErrorCode::MissingParams, msg).await;
 
         if let Some(id) = log_id {
             let duration = start.elapsed().as_millis() as i32;
@@ -180,7 +279,7 @@ pub async fn create_deployment(
                 "failed",
                 duration,
                 Some(msg),
-                Some(serde_json::json!({"error_code": "MISSING_PARAMS"})),
+                Some(serde_json::json!({"error_code": ErrorCode::MissingParams})),
             )
             .await
             .ok();
@@ -200,15 +299,74 @@ pub async fn create_deployment(
     // Model is mandatory: request cannot be created without defining the model to install.
     if payload.model_id.is_none() {
         let msg = "Missing model_id";
-        let _ = sqlx::query(
-            "UPDATE instances SET status='provisioning_failed', error_code=$2, error_message=$3, failed_at=NOW()
-             WHERE id=$1"
+        let _ = commit_failure(tx, instance_id_uuid, ErrorCode::MissingModel, msg).await;
+
+        if let Some(id) = log_id {
+            let duration = start.elapsed().as_millis() as i32;
+            simple_logger::log_action_complete_with_metadata(
+                &state.db,
+                id,
+                "failed",
+                duration,
+                Some(msg),
+                Some(serde_json::json!({"error_code": ErrorCode::MissingModel})),
+            )
+            .await
+            .ok();
+        }
+
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(DeploymentResponse {
+                status: "failed".to_string(),
+                instance_id,
+                message: Some(msg.to_string()),
+            }),
         )
-        .bind(instance_id_uuid)
-        .bind("MISSING_MODEL")
-        .bind(msg)
-        .execute(&state.db)
-        .await;
+            .into_response();
+    }
+
+    if payload.max_runtime_hours.is_some_and(|h| h <= 0) {
+        let msg = "Invalid max_runtime_hours (must be > 0)";
+        let _ = commit_failure(tx, instance_id_uuid, ErrorCode::InvalidMaxRuntime, msg).await;
+
+        if let Some(id) = log_id {
+            let duration = start.elapsed().as_millis() as i32;
+            simple_logger::log_action_complete_with_metadata(
+                &state.db,
+                id,
+                "failed",
+                duration,
+                Some(msg),
+                Some(serde_json::json!({"error_code": ErrorCode::InvalidMaxRuntime})),
+            )
+            .await
+            .ok();
+        }
+
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(DeploymentResponse {
+                status: "failed".to_string(),
+                instance_id,
+                message: Some(msg.to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    let cost_center: Option<String> = payload
+        .cost_center
+        .as_deref()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string());
+    if cost_center
+        .as_deref()
+        .is_some_and(|c| !is_valid_cost_center(c))
+    {
+        let msg = "Invalid cost_center (1-64 chars: letters, digits, '-', '_', '.')";
+        let _ = commit_failure(tx, instance_id_uuid, ErrorCode::InvalidCostCenter, msg).await;
 
         if let Some(id) = log_id {
             let duration = start.elapsed().as_millis() as i32;
@@ -218,7 +376,36 @@ pub async fn create_deployment(
                 "failed",
                 duration,
                 Some(msg),
-                Some(serde_json::json!({"error_code": "MISSING_MODEL"})),
+                Some(serde_json::json!({"error_code": ErrorCode::InvalidCostCenter})),
+            )
+            .await
+            .ok();
+        }
+
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(DeploymentResponse {
+                status: "failed".to_string(),
+                instance_id,
+                message: Some(msg.to_string()),
+            }),
+        )
+            .into_response();
+    }
+
+    if payload.ttl_minutes.is_some_and(|m| m <= 0) {
+        let msg = "Invalid ttl_minutes (must be > 0)";
+        let _ = commit_failure(tx, instance_id_uuid, ErrorCode::InvalidTtl, msg).await;
+
+        if let Some(id) = log_id {
+            let duration = start.elapsed().as_millis() as i32;
+            simple_logger::log_action_complete_with_metadata(
+                &state.db,
+                id,
+                "failed",
+                duration,
+                Some(msg),
+                Some(serde_json::json!({"error_code": ErrorCode::InvalidTtl})),
             )
             .await
             .ok();
@@ -245,7 +432,7 @@ pub async fn create_deployment(
     } else {
         sqlx::query_scalar("SELECT COALESCE(is_active, false) FROM providers WHERE id = $1")
             .bind(provider_id)
-            .fetch_optional(&state.db)
+            .fetch_optional(&mut *tx)
             .await
             .unwrap_or(None)
             .unwrap_or(false)
@@ -253,15 +440,7 @@ pub async fn create_deployment(
 
     if !provider_active {
         let msg = "Invalid provider (not found or inactive)";
-        let _ = sqlx::query(
-            "UPDATE instances SET status='provisioning_failed', error_code=$2, error_message=$3, failed_at=NOW()
-             WHERE id=$1"
-        )
-        .bind(instance_id_uuid)
-        .bind("INVALID_PROVIDER")
-        .bind(msg)
-        .execute(&state.db)
-        .await;
+        let _ = commit_failure(tx, instance_id_uuid, ErrorCode::InvalidProvider, msg).await;
 
         if let Some(id) = log_id {
             let duration = start.elapsed().as_millis() as i32;
@@ -271,7 +450,7 @@ pub async fn create_deployment(
                 "failed",
                 duration,
                 Some(msg),
-                Some(serde_json::json!({"error_code": "INVALID_PROVIDER"})),
+                Some(serde_json::json!({"error_code": ErrorCode::InvalidProvider})),
             )
             .await
             .ok();
@@ -302,22 +481,14 @@ pub async fn create_deployment(
     )
     .bind(payload.zone.trim())
     .bind(provider_id)
-    .fetch_all(&state.db)
+    .fetch_all(&mut *tx)
     .await
     .unwrap_or_default();
 
     let zone_id = match zone_rows.as_slice() {
         &[_, _, ..] => {
             let msg = "Catalog inconsistency: duplicate zone code for provider (expected unique zones.provider_id+code)";
-            let _ = sqlx::query(
-                "UPDATE instances SET status='provisioning_failed', error_code=$2, error_message=$3, failed_at=NOW()
-                 WHERE id=$1"
-            )
-            .bind(instance_id_uuid)
-            .bind("CATALOG_INCONSISTENT")
-            .bind(msg)
-            .execute(&state.db)
-            .await;
+            let _ = commit_failure(tx, instance_id_uuid, ErrorCode::CatalogInconsistent, msg).await;
             if let Some(id) = log_id {
                 let duration = start.elapsed().as_millis() as i32;
                 simple_logger::log_action_complete_with_metadata(
@@ -326,7 +497,7 @@ pub async fn create_deployment(
                     "failed",
                     duration,
                     Some(msg),
-                    Some(serde_json::json!({"error_code": "CATALOG_INCONSISTENT"})),
+                    Some(serde_json::json!({"error_code": ErrorCode::CatalogInconsistent})),
                 )
                 .await
                 .ok();
@@ -347,15 +518,7 @@ pub async fn create_deployment(
                 zid
             } else {
                 let msg = "Invalid zone (not found, inactive, or does not belong to provider)";
-                let _ = sqlx::query(
-                    "UPDATE instances SET status='provisioning_failed', error_code=$2, error_message=$3, failed_at=NOW()
-                     WHERE id=$1"
-                )
-                .bind(instance_id_uuid)
-                .bind("INVALID_ZONE")
-                .bind(msg)
-                .execute(&state.db)
-                .await;
+                let _ = commit_failure(tx, instance_id_uuid, ErrorCode::InvalidZone, msg).await;
 
                 if let Some(id) = log_id {
                     let duration = start.elapsed().as_millis() as i32;
@@ -365,7 +528,7 @@ pub async fn create_deployment(
                         "failed",
                         duration,
                         Some(msg),
-                        Some(serde_json::json!({"error_code": "INVALID_ZONE"})),
+                        Some(serde_json::json!({"error_code": ErrorCode::InvalidZone})),
                     )
                     .await
                     .ok();
@@ -384,15 +547,7 @@ pub async fn create_deployment(
         }
         [] => {
             let msg = "Invalid zone (not found, inactive, or does not belong to provider)";
-            let _ = sqlx::query(
-                "UPDATE instances SET status='provisioning_failed', error_code=$2, error_message=$3, failed_at=NOW()
-                 WHERE id=$1"
-            )
-            .bind(instance_id_uuid)
-            .bind("INVALID_ZONE")
-            .bind(msg)
-            .execute(&state.db)
-            .await;
+            let _ = commit_failure(tx, instance_id_uuid, ErrorCode::InvalidZone, msg).await;
 
             if let Some(id) = log_id {
                 let duration = start.elapsed().as_millis() as i32;
@@ -402,7 +557,7 @@ pub async fn create_deployment(
                     "failed",
                     duration,
                     Some(msg),
-                    Some(serde_json::json!({"error_code": "INVALID_ZONE"})),
+                    Some(serde_json::json!({"error_code": ErrorCode::InvalidZone})),
                 )
                 .await
                 .ok();
@@ -431,7 +586,7 @@ pub async fn create_deployment(
     )
     .bind(payload.instance_type.trim())
     .bind(zone_id)
-    .fetch_optional(&state.db)
+    .fetch_optional(&mut *tx)
     .await
     .unwrap_or(None);
 
@@ -439,15 +594,7 @@ pub async fn create_deployment(
         Some((itid, itact)) if itact => itid,
         _ => {
             let msg = "Invalid instance_type (not found, inactive, or not available in zone)";
-            let _ = sqlx::query(
-                "UPDATE instances SET status='provisioning_failed', error_code=$2, error_message=$3, failed_at=NOW()
-                 WHERE id=$1"
-            )
-            .bind(instance_id_uuid)
-            .bind("INVALID_INSTANCE_TYPE")
-            .bind(msg)
-            .execute(&state.db)
-            .await;
+            let _ = commit_failure(tx, instance_id_uuid, ErrorCode::InvalidInstanceType, msg).await;
 
             if let Some(id) = log_id {
                 let duration = start.elapsed().as_millis() as i32;
@@ -457,7 +604,7 @@ pub async fn create_deployment(
                     "failed",
                     duration,
                     Some(msg),
-                    Some(serde_json::json!({"error_code": "INVALID_INSTANCE_TYPE"})),
+                    Some(serde_json::json!({"error_code": ErrorCode::InvalidInstanceType})),
                 )
                 .await
                 .ok();
@@ -480,22 +627,14 @@ pub async fn create_deployment(
     let model_active: bool =
         sqlx::query_scalar("SELECT COALESCE(is_active, false) FROM models WHERE id = $1")
             .bind(model_id)
-            .fetch_optional(&state.db)
+            .fetch_optional(&mut *tx)
             .await
             .unwrap_or(None)
             .unwrap_or(false);
 
     if !model_active {
         let msg = "Invalid model (not found or inactive)";
-        let _ = sqlx::query(
-            "UPDATE instances SET status='provisioning_failed', error_code=$2, error_message=$3, failed_at=NOW()
-             WHERE id=$1"
-        )
-        .bind(instance_id_uuid)
-        .bind("INVALID_MODEL")
-        .bind(msg)
-        .execute(&state.db)
-        .await;
+        let _ = commit_failure(tx, instance_id_uuid, ErrorCode::InvalidModel, msg).await;
 
         if let Some(id) = log_id {
             let duration = start.elapsed().as_millis() as i32;
@@ -505,7 +644,7 @@ pub async fn create_deployment(
                 "failed",
                 duration,
                 Some(msg),
-                Some(serde_json::json!({"error_code": "INVALID_MODEL"})),
+                Some(serde_json::json!({"error_code": ErrorCode::InvalidModel})),
             )
             .await
             .ok();
@@ -526,20 +665,18 @@ pub async fn create_deployment(
     let compatible: bool = sqlx::query_scalar("SELECT check_model_instance_compatibility($1, $2)")
         .bind(model_id)
         .bind(instance_type_id)
-        .fetch_one(&state.db)
+        .fetch_one(&mut *tx)
         .await
         .unwrap_or(false);
 
     if !compatible {
         let msg = "Model is not compatible with selected instance type (VRAM requirement exceeds available GPU memory)";
-        let _ = sqlx::query(
-            "UPDATE instances SET status='provisioning_failed', error_code=$2, error_message=$3, failed_at=NOW()
-             WHERE id=$1"
+        let _ = commit_failure(
+            tx,
+            instance_id_uuid,
+            ErrorCode::IncompatibleModelInstance,
+            msg,
         )
-        .bind(instance_id_uuid)
-        .bind("INCOMPATIBLE_MODEL_INSTANCE")
-        .bind(msg)
-        .execute(&state.db)
         .await;
 
         if let Some(id) = log_id {
@@ -550,7 +687,7 @@ pub async fn create_deployment(
                 "failed",
                 duration,
                 Some(msg),
-                Some(serde_json::json!({"error_code": "INCOMPATIBLE_MODEL_INSTANCE"})),
+                Some(serde_json::json!({"error_code": ErrorCode::IncompatibleModelInstance})),
             )
             .await
             .ok();
@@ -567,20 +704,143 @@ pub async fn create_deployment(
             .into_response();
     }
 
-    // Update instance row with validated zone/type/model
+    // Reused data volume: a tracked, non-boot volume of this provider and zone that no live
+    // instance holds. It is reserved for this instance (kept on terminate) and attached by the
+    // orchestrator instead of a new volume.
+    let reuse_volume_id: Option<String> = payload
+        .reuse_volume_id
+        .as_deref()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string());
+    if let Some(volume_id) = reuse_volume_id.as_deref() {
+        // Serialize concurrent deployments reusing the same volume.
+        let _ = sqlx::query("SELECT pg_advisory_xact_lock(hashtext('reuse_volume'), hashtext($1))")
+            .bind(volume_id)
+            .execute(&mut *tx)
+            .await;
+        let holders: Vec<(String, i64, Option<String>, String)> = sqlx::query_as(
+            r#"SELECT iv.volume_type, iv.size_bytes, iv.provider_volume_name, i.status::text
+               FROM instance_volumes iv
+               JOIN instances i ON i.id = iv.instance_id
+               WHERE iv.provider_volume_id = $1
+                 AND iv.provider_id = $2
+                 AND iv.zone_code = $3
+                 AND iv.is_boot = false
+                 AND iv.deleted_at IS NULL
+               ORDER BY iv.created_at DESC"#,
+        )
+        .bind(volume_id)
+        .bind(provider_id)
+        .bind(payload.zone.trim())
+        .fetch_all(&mut *tx)
+        .await
+        .unwrap_or_default();
+
+        let failure = match holders.first() {
+            None => Some((
+                StatusCode::BAD_REQUEST,
+                ErrorCode::ReuseVolumeNotFound,
+                "Invalid reuse_volume_id (no known data volume with this id in the zone)",
+            )),
+            Some(_)
+                if holders
+                    .iter()
+                    .any(|h| !VOLUME_RELEASED_STATUSES.contains(&h.3.as_str())) =>
+            {
+                Some((
+                    StatusCode::CONFLICT,
+                    ErrorCode::ReuseVolumeInUse,
+                    "reuse_volume_id is still attached to another instance",
+                ))
+            }
+            Some((volume_type, size_bytes, volume_name, _)) => {
+                let reserved = sqlx::query(
+                    r#"INSERT INTO instance_volumes
+                       (id, instance_id, provider_id, zone_code, provider_volume_id, provider_volume_name, volume_type, size_bytes, delete_on_terminate, status, attached_at, is_boot)
+                       VALUES ($1, $2, $3, $4, $5, $6, $7, $8, FALSE, 'reserved', NULL, FALSE)"#,
+                )
+                .bind(uuid::Uuid::new_v4())
+                .bind(instance_id_uuid)
+                .bind(provider_id)
+                .bind(payload.zone.trim())
+                .bind(volume_id)
+                .bind(volume_name.as_deref())
+                .bind(volume_type)
+                .bind(size_bytes)
+                .execute(&mut *tx)
+                .await;
+                reserved.err().map(|_| {
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        ErrorCode::DbError,
+                        "Database error reserving reuse_volume_id",
+                    )
+                })
+            }
+        };
+
+        if let Some((status, error_code, msg)) = failure {
+            let _ = commit_failure(tx, instance_id_uuid, error_code, msg).await;
+
+            if let Some(id) = log_id {
+                let duration = start.elapsed().as_millis() as i32;
+                simple_logger::log_action_complete_with_metadata(
+                    &state.db,
+                    id,
+                    "failed",
+                    duration,
+                    Some(msg),
+                    Some(serde_json::json!({"error_code": error_code})),
+                )
+                .await
+                .ok();
+            }
+
+            return (
+                status,
+                Json(DeploymentResponse {
+                    status: "failed".to_string(),
+                    instance_id,
+                    message: Some(msg.to_string()),
+                }),
+            )
+                .into_response();
+        }
+    }
+
+    // Update instance row with validated zone/type/model (+ runtime guard and cost_center, defaulting to org settings)
     let update_result = sqlx::query(
-        "UPDATE instances
+        "UPDATE instances i
          SET zone_id = $2,
              instance_type_id = $3,
-             model_id = $4
-         WHERE id = $1",
+             model_id = $4,
+             max_runtime_hours = COALESCE($5, o.default_max_runtime_hours),
+             auto_terminate_on_max_runtime = COALESCE($6, o.default_auto_terminate_on_max_runtime, false),
+             auto_terminate_at = CASE WHEN $8::int IS NULL THEN NULL ELSE NOW() + make_interval(mins => $8::int) END,
+             cost_center = COALESCE($9, o.default_cost_center)
+         FROM organizations o
+         WHERE i.id = $1
+           AND o.id = $7",
     )
     .bind(instance_id_uuid)
     .bind(zone_id)
     .bind(instance_type_id)
     .bind(model_id)
-    .execute(&state.db)
+    .bind(payload.max_runtime_hours)
+    .bind(payload.auto_terminate_on_max_runtime)
+    .bind(organization_id)
+    .bind(payload.ttl_minutes)
+    .bind(cost_center.as_deref())
+    .execute(&mut *tx)
     .await;
+    let update_result = match update_result {
+        Ok(_) => tx.commit().await,
+        Err(e) => {
+            let _ = tx.rollback().await;
+            Err(e)
+        }
+    };
 
     if let Err(e) = update_result {
         let msg = format!("Database error updating instance: {:?}", e);
@@ -589,7 +849,7 @@ pub async fn create_deployment(
              WHERE id=$1"
         )
         .bind(instance_id_uuid)
-        .bind("DB_ERROR")
+        .bind(ErrorCode::DbError.as_str())
         .bind(&msg)
         .execute(&state.db)
         .await;
@@ -602,7 +862,7 @@ pub async fn create_deployment(
                 "failed",
                 duration,
                 Some(&msg),
-                Some(serde_json::json!({"error_code": "DB_ERROR"})),
+                Some(serde_json::json!({"error_code": ErrorCode::DbError})),
             )
             .await
             .ok();
@@ -627,6 +887,7 @@ pub async fn create_deployment(
         "zone_id": zone_id.to_string(),
         "instance_type_id": instance_type_id.to_string(),
         "model_id": model_id.to_string(),
+        "reuse_volume_id": reuse_volume_id,
         "correlation_id": log_id.map(|id| id.to_string()),
     })
     .to_string();
@@ -715,3 +976,140 @@ pub async fn create_deployment(
         }
     }
 }
+
+#[derive(Deserialize, Serialize, utoipa::ToSchema)]
+pub struct CloudInitPreviewRequest {
+    /// Model served by the worker (UUID from /models). Without it, only the SSH key document is rendered.
+    #[serde(default)]
+    pub model_id: Option<uuid::Uuid>,
+    /// Instance id written into the bootstrap script (default: a random UUID).
+    #[serde(default)]
+    pub instance_id: Option<uuid::Uuid>,
+    /// Default: WORKER_CONTROL_PLANE_URL.
+    #[serde(default)]
+    pub control_plane_url: Option<String>,
+    /// Default: WORKER_VLLM_IMAGE, else the built-in image.
+    #[serde(default)]
+    pub vllm_image: Option<String>,
+    #[serde(default)]
+    pub vllm_port: Option<u16>,
+    #[serde(default)]
+    pub worker_health_port: Option<u16>,
+    #[serde(default)]
+    pub ssh_public_key: Option<String>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct CloudInitPreviewResponse {
+    /// Rendered document; worker tokens are replaced by `<redacted>`.
+    pub cloud_init: String,
+    pub size_bytes: usize,
+    pub max_bytes: usize,
+    pub within_limit: bool,
+    /// Always false: worker mTLS PEM files (CA, client cert/key) live in the orchestrator's
+    /// environment, so they are missing from the document and from `size_bytes`.
+    pub worker_client_tls_included: bool,
+}
+
+fn env_or(key: &str, default: &str) -> String {
+    std::env::var(key)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| default.to_string())
+}
+
+/// Render the cloud-init provisioning would inject for these inputs, without provisioning.
+#[utoipa::path(
+    post,
+    path = "/deployments/cloud-init-preview",
+    request_body = CloudInitPreviewRequest,
+    responses(
+        (status = 200, description = "Rendered cloud-init", body = CloudInitPreviewResponse),
+        (status = 403, description = "Admin required"),
+        (status = 404, description = "Model not found")
+    )
+)]
+pub async fn preview_cloud_init(
+    State(state): State<Arc<AppState>>,
+    axum::extract::Extension(user): axum::extract::Extension<crate::auth::AuthUser>,
+    Json(payload): Json<CloudInitPreviewRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = crate::auth::require_admin(&user) {
+        return e.into_response();
+    }
+    let ssh_pub = payload
+        .ssh_public_key
+        .as_deref()
+        .map(|s| s.trim().replace('\n', " "))
+        .unwrap_or_default();
+    let control_plane_url = payload
+        .control_plane_url
+        .map(|s| s.trim().to_string())
+        .unwrap_or_else(|| env_or("WORKER_CONTROL_PLANE_URL", ""));
+    let control_plane_url = control_plane_url.trim_end_matches('/');
+
+    let model =
+        match payload.model_id {
+            Some(id) => {
+                match sqlx::query_scalar::<_, String>("SELECT model_id FROM models WHERE id = $1")
+                    .bind(id)
+                    .fetch_optional(&state.db)
+                    .await
+                {
+                    Ok(Some(m)) => Some(m),
+                    Ok(None) => return (
+                        StatusCode::NOT_FOUND,
+                        Json(serde_json::json!({"error":"not_found","message":"model_not_found"})),
+                    )
+                        .into_response(),
+                    Err(e) => {
+                        return (
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            Json(serde_json::json!({"error":"db_error","message":e.to_string()})),
+                        )
+                            .into_response()
+                    }
+                }
+            }
+            None => None,
+        };
+
+    // Same choice as provisioning: the worker bootstrap needs a model and a control plane URL.
+    let cloud_init = match model {
+        Some(model) if !control_plane_url.is_empty() => cloud_init::build_worker_cloud_init(
+            &ssh_pub,
+            &payload
+                .instance_id
+                .unwrap_or_else(uuid::Uuid::new_v4)
+                .to_string(),
+            control_plane_url,
+            &model,
+            &payload
+                .vllm_image
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .unwrap_or_else(|| env_or("WORKER_VLLM_IMAGE", cloud_init::DEFAULT_VLLM_IMAGE)),
+            payload.vllm_port.unwrap_or(8000),
+            payload.worker_health_port.unwrap_or(8080),
+            &env_or(
+                "WORKER_AGENT_SOURCE_URL",
+                cloud_init::DEFAULT_AGENT_SOURCE_URL,
+            ),
+            cloud_init::REDACTED,
+            cloud_init::REDACTED,
+            // Worker mTLS material lives in the orchestrator's environment.
+            None,
+        ),
+        _ => cloud_init::build_ssh_key_cloud_init(&ssh_pub),
+    };
+    let size_bytes = cloud_init.len();
+    Json(CloudInitPreviewResponse {
+        cloud_init,
+        size_bytes,
+        max_bytes: cloud_init::MAX_CLOUD_INIT_BYTES,
+        within_limit: size_bytes <= cloud_init::MAX_CLOUD_INIT_BYTES,
+        worker_client_tls_included: false,
+    })
+    .into_response()
+}
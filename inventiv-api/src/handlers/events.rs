@@ -19,9 +19,72 @@ pub struct EventsStreamParams {
 #[derive(serde::Serialize)]
 struct InstancesChangedPayload {
     ids: Vec<uuid::Uuid>,
+    /// More instances changed than `ids` carries: clients should do a full refresh.
+    has_more: bool,
     emitted_at: chrono::DateTime<chrono::Utc>,
 }
 
+fn instance_events_debounce() -> std::time::Duration {
+    let ms = std::env::var("EVENTS_INSTANCE_DEBOUNCE_MS")
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .unwrap_or(3000)
+        .min(60_000);
+    std::time::Duration::from_millis(ms)
+}
+
+fn instance_events_max_ids() -> usize {
+    std::env::var("EVENTS_INSTANCE_MAX_IDS")
+        .ok()
+        .and_then(|s| s.trim().parse::<usize>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(200)
+}
+
+/// Coalesces instance changes for `instance.updated`: repeated changes to the same id within the
+/// debounce window collapse into one emission, and each emission carries at most `max_ids` ids.
+pub struct InstanceChangeCoalescer {
+    debounce: std::time::Duration,
+    max_ids: usize,
+    /// id -> first unsent change
+    pending: std::collections::HashMap<uuid::Uuid, std::time::Instant>,
+}
+
+impl InstanceChangeCoalescer {
+    pub fn new(debounce: std::time::Duration, max_ids: usize) -> Self {
+        Self {
+            debounce,
+            max_ids: max_ids.max(1),
+            pending: std::collections::HashMap::new(),
+        }
+    }
+
+    pub fn record(&mut self, id: uuid::Uuid, now: std::time::Instant) {
+        self.pending.entry(id).or_insert(now);
+    }
+
+    /// Ids whose debounce window has elapsed (sorted), plus whether the cap dropped some.
+    /// Dropped ids are not re-sent: `has_more` tells the client to refresh everything.
+    pub fn drain_ready(&mut self, now: std::time::Instant) -> Option<(Vec<uuid::Uuid>, bool)> {
+        let mut ready: Vec<uuid::Uuid> = self
+            .pending
+            .iter()
+            .filter(|(_, first)| now.saturating_duration_since(**first) >= self.debounce)
+            .map(|(id, _)| *id)
+            .collect();
+        if ready.is_empty() {
+            return None;
+        }
+        for id in &ready {
+            self.pending.remove(id);
+        }
+        ready.sort();
+        let has_more = ready.len() > self.max_ids;
+        ready.truncate(self.max_ids);
+        Some((ready, has_more))
+    }
+}
+
 #[derive(serde::Serialize)]
 struct ActionLogsChangedPayload {
     ids: Vec<uuid::Uuid>,
@@ -55,6 +118,8 @@ pub async fn events_stream(
         let mut instances_initialized = false;
         let mut instance_sig: std::collections::HashMap<uuid::Uuid, String> =
             std::collections::HashMap::new();
+        let mut coalescer =
+            InstanceChangeCoalescer::new(instance_events_debounce(), instance_events_max_ids());
 
         let mut last_actions_ts = chrono::Utc::now();
         let mut interval = tokio::time::interval(std::time::Duration::from_secs(2));
@@ -111,7 +176,7 @@ pub async fn events_stream(
                     instances_initialized = true;
                 } else {
                     let mut seen = std::collections::HashSet::with_capacity(rows.len());
-                    let mut changed: Vec<uuid::Uuid> = Vec::new();
+                    let now = std::time::Instant::now();
 
                     for r in rows {
                         seen.insert(r.id);
@@ -119,7 +184,7 @@ pub async fn events_stream(
                             Some(prev) if prev == &r.sig => {}
                             _ => {
                                 instance_sig.insert(r.id, r.sig);
-                                changed.push(r.id);
+                                coalescer.record(r.id, now);
                             }
                         }
                     }
@@ -127,20 +192,17 @@ pub async fn events_stream(
                     // Remove signatures for deleted instances
                     instance_sig.retain(|id, _| seen.contains(id));
 
-                    if !changed.is_empty() {
-                        // Keep payload size reasonable; send in chunks.
-                        for chunk in changed.chunks(200) {
-                            let payload = InstancesChangedPayload {
-                                ids: chunk.to_vec(),
-                                emitted_at: chrono::Utc::now(),
-                            };
-                            let ev = Event::default().event("instance.updated").data(
-                                serde_json::to_string(&payload)
-                                    .unwrap_or_else(|_| "{}".to_string()),
-                            );
-                            if tx.send(Ok(ev)).await.is_err() {
-                                return;
-                            }
+                    if let Some((ids, has_more)) = coalescer.drain_ready(now) {
+                        let payload = InstancesChangedPayload {
+                            ids,
+                            has_more,
+                            emitted_at: chrono::Utc::now(),
+                        };
+                        let ev = Event::default().event("instance.updated").data(
+                            serde_json::to_string(&payload).unwrap_or_else(|_| "{}".to_string()),
+                        );
+                        if tx.send(Ok(ev)).await.is_err() {
+                            return;
                         }
                     }
                 }
@@ -210,3 +272,40 @@ pub async fn events_stream(
             .text("keepalive"),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn repeated_changes_to_one_instance_coalesce_into_one_emission() {
+        let mut c = InstanceChangeCoalescer::new(Duration::from_secs(3), 200);
+        let id = uuid::Uuid::new_v4();
+        let t0 = Instant::now();
+        for i in 0..5 {
+            c.record(id, t0 + Duration::from_millis(400 * i));
+            assert_eq!(c.drain_ready(t0 + Duration::from_millis(400 * i)), None);
+        }
+
+        assert_eq!(
+            c.drain_ready(t0 + Duration::from_secs(3)),
+            Some((vec![id], false))
+        );
+        assert_eq!(c.drain_ready(t0 + Duration::from_secs(6)), None);
+    }
+
+    #[test]
+    fn emission_is_capped_with_has_more_flag() {
+        let mut c = InstanceChangeCoalescer::new(Duration::ZERO, 2);
+        let now = Instant::now();
+        for _ in 0..5 {
+            c.record(uuid::Uuid::new_v4(), now);
+        }
+
+        let (ids, has_more) = c.drain_ready(now).unwrap();
+        assert_eq!(ids.len(), 2);
+        assert!(has_more);
+        assert_eq!(c.drain_ready(now), None);
+    }
+}
@@ -12,6 +12,8 @@ use utoipa::IntoParams;
 use crate::app::state::AppState;
 use crate::progress;
 use crate::simple_logger;
+use crate::sort;
+use inventiv_common::error_code::ErrorCode;
 use redis::AsyncCommands;
 
 #[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
@@ -35,6 +37,9 @@ pub struct InstanceResponse {
     pub worker_last_heartbeat: Option<chrono::DateTime<chrono::Utc>>,
     #[sqlx(default)]
     pub worker_model_id: Option<String>,
+    /// Model revision (HF commit) reported by the worker heartbeat.
+    #[sqlx(default)]
+    pub worker_model_revision: Option<String>,
     #[sqlx(default)]
     pub worker_queue_depth: Option<i32>,
     #[sqlx(default)]
@@ -43,6 +48,11 @@ pub struct InstanceResponse {
     pub worker_health_port: Option<i32>,
     #[sqlx(default)]
     pub worker_vllm_port: Option<i32>,
+    /// Worker front port (HAProxy) used for routing when reported.
+    #[sqlx(default)]
+    pub worker_proxy_port: Option<i32>,
+    /// False when an operator paused OpenAI routing to this instance (worker keeps running).
+    pub routing_enabled: bool,
     #[sqlx(default)]
     pub worker_metadata: Option<serde_json::Value>,
     pub created_at: chrono::DateTime<chrono::Utc>,
@@ -51,8 +61,14 @@ pub struct InstanceResponse {
     pub last_reconciliation: Option<chrono::DateTime<chrono::Utc>>,
     pub health_check_failures: Option<i32>,
     pub deletion_reason: Option<String>,
+    /// Deployment TTL: the orchestrator terminates the instance once this time has passed.
+    #[sqlx(default)]
+    pub auto_terminate_at: Option<chrono::DateTime<chrono::Utc>>,
     pub error_code: Option<String>,
     pub error_message: Option<String>,
+    /// Provider's view of the running server (commercial type, zone, hypervisor), synced once running.
+    #[sqlx(default)]
+    pub provider_metadata: Option<serde_json::Value>,
     /// Count of attached block volumes (not deleted) tracked in DB.
     pub storage_count: i64,
     /// Attached block volume sizes in GB (not deleted) tracked in DB.
@@ -65,16 +81,35 @@ pub struct InstanceResponse {
     pub instance_type: String,
     pub cpu_count: Option<i32>,
     pub ram_gb: Option<i32>,
+    /// Network bandwidth of the instance type (bits per second).
+    pub bandwidth_bps: Option<i64>,
     pub gpu_vram: Option<i32>,
     pub gpu_count: Option<i32>, // NEW: Distinct GPU count
     pub cost_per_hour: Option<f64>,
+    /// Seconds of compute billed so far (provider resource allocated, until terminated).
+    #[sqlx(default)]
+    pub billable_seconds: Option<i64>,
+    /// Compute cost so far: billable_seconds at the effective hourly price (same math as FinOps).
     pub total_cost: Option<f64>,
+    /// Requests the OpenAI proxy routed to this instance (detail endpoint only).
+    #[sqlx(default)]
+    pub requests_served: Option<i64>,
+    /// Of `requests_served`, those that failed (upstream error status or unreachable worker).
+    #[sqlx(default)]
+    pub requests_failed: Option<i64>,
+    /// Last request routed to this instance (detail endpoint only).
+    #[sqlx(default)]
+    pub last_request_at: Option<chrono::DateTime<chrono::Utc>>,
     pub is_archived: bool,
     pub deleted_by_provider: Option<bool>,
     /// Progress percentage (0-100) towards operational state (calculated, not from DB)
     #[serde(skip_serializing_if = "Option::is_none")]
     #[sqlx(skip)]
     pub progress_percent: Option<u8>,
+    /// Where a failed instance stopped: "provisioning" (provider never delivered a running server),
+    /// "startup" (server up, worker never healthy) or "runtime". Null unless failed.
+    #[sqlx(skip)]
+    pub failure_stage: Option<String>,
 }
 
 #[derive(Serialize, utoipa::ToSchema)]
@@ -114,12 +149,19 @@ pub struct ListInstanceParams {
     pub archived: Option<bool>,
 }
 
+#[derive(Deserialize, IntoParams)]
+pub struct ModelInstancesParams {
+    /// Only instances in this status (e.g. "ready"). Default: every status, archived included.
+    pub status: Option<String>,
+}
+
 #[derive(Deserialize, IntoParams, utoipa::ToSchema)]
 pub struct SearchInstancesParams {
     pub archived: Option<bool>,
     pub offset: Option<i64>,
     pub limit: Option<i64>,
-    /// Sort field allowlist: created_at|status|provider|region|zone|type|cost_per_hour|total_cost
+    /// Sort field allowlist: created_at|status|provider|region|zone|type|model|gpu_count|cost_per_hour|total_cost
+    /// (default: created_at). Unknown fields are rejected with 400.
     pub sort_by: Option<String>,
     /// "asc" | "desc"
     pub sort_dir: Option<String>,
@@ -134,6 +176,75 @@ pub struct SearchInstancesResponse {
     pub rows: Vec<InstanceResponse>,
 }
 
+/// Columns and joins of the instance list (`InstanceResponse`), shared by the list endpoints.
+const INSTANCE_LIST_SELECT: &str = r#"
+    SELECT
+        i.id, i.provider_id, i.zone_id, i.instance_type_id,
+        i.model_id,
+        m.name as model_name,
+        m.model_id as model_code,
+        i.provider_instance_id::text as provider_instance_id,
+        i.status::text as status,
+        i.ip_address::text as ip_address,
+        i.worker_status,
+        i.worker_last_heartbeat,
+        i.worker_model_id,
+        i.worker_model_revision,
+        i.worker_queue_depth,
+        i.worker_gpu_utilization,
+        i.worker_health_port,
+        i.worker_vllm_port,
+        i.worker_proxy_port,
+        i.routing_enabled,
+        i.worker_metadata,
+        i.created_at,
+        i.terminated_at,
+        i.last_health_check,
+        i.last_reconciliation,
+        i.health_check_failures,
+        i.deletion_reason,
+        i.auto_terminate_at,
+        i.error_code,
+        i.error_message,
+        COALESCE((SELECT COUNT(*) FROM instance_volumes iv WHERE iv.instance_id = i.id AND iv.deleted_at IS NULL), 0)::bigint as storage_count,
+        COALESCE(
+          (SELECT ARRAY_AGG(
+                    (CASE
+                      WHEN iv.size_bytes < 1000000000 THEN iv.size_bytes
+                      ELSE ROUND(iv.size_bytes / 1000000000.0)
+                     END)::int
+                     ORDER BY
+                     (CASE
+                      WHEN iv.size_bytes < 1000000000 THEN iv.size_bytes
+                      ELSE ROUND(iv.size_bytes / 1000000000.0)
+                     END)::int
+                  )
+             FROM instance_volumes iv
+            WHERE iv.instance_id = i.id AND iv.deleted_at IS NULL AND iv.size_bytes > 0),
+          ARRAY[]::int[]
+        ) as storage_sizes_gb,
+        i.is_archived,
+        i.deleted_by_provider,
+        COALESCE(p.name, 'Unknown Provider') as provider_name,
+        COALESCE(z.name, 'Unknown Zone') as zone,
+        COALESCE(r.name, 'Unknown Region') as region,
+        COALESCE(it.name, 'Unknown Type') as instance_type,
+        it.cpu_count as cpu_count,
+        it.ram_gb as ram_gb,
+        it.bandwidth_bps as bandwidth_bps,
+        it.vram_per_gpu_gb as gpu_vram,
+        it.gpu_count as gpu_count,
+        cast(it.cost_per_hour as float8) as cost_per_hour,
+        public.instance_billable_seconds(i, NOW())::bigint as billable_seconds,
+        public.instance_total_cost(i, NOW()) as total_cost
+    FROM instances i
+    LEFT JOIN providers p ON i.provider_id = p.id
+    LEFT JOIN zones z ON i.zone_id = z.id
+    LEFT JOIN regions r ON z.region_id = r.id
+    LEFT JOIN instance_types it ON i.instance_type_id = it.id
+    LEFT JOIN models m ON m.id = i.model_id
+"#;
+
 #[utoipa::path(
     get,
     path = "/instances",
@@ -148,71 +259,12 @@ pub async fn list_instances(
 ) -> Json<Vec<InstanceResponse>> {
     let show_archived = params.archived.unwrap_or(false);
 
-    let instances = sqlx::query_as::<Postgres, InstanceResponse>(
-        r#"
-        SELECT 
-            i.id, i.provider_id, i.zone_id, i.instance_type_id,
-            i.model_id,
-            m.name as model_name,
-            m.model_id as model_code,
-            i.provider_instance_id::text as provider_instance_id,
-            i.status::text as status, 
-            i.ip_address::text as ip_address,
-            i.worker_status,
-            i.worker_last_heartbeat,
-            i.worker_model_id,
-            i.worker_queue_depth,
-            i.worker_gpu_utilization,
-            i.worker_health_port,
-            i.worker_vllm_port,
-            i.worker_metadata,
-            i.created_at,
-            i.terminated_at,
-            i.last_health_check,
-            (i.last_reconciliation AT TIME ZONE 'UTC') as last_reconciliation,
-            i.health_check_failures,
-            i.deletion_reason,
-            i.error_code,
-            i.error_message,
-            COALESCE((SELECT COUNT(*) FROM instance_volumes iv WHERE iv.instance_id = i.id AND iv.deleted_at IS NULL), 0)::bigint as storage_count,
-            COALESCE(
-              (SELECT ARRAY_AGG(
-                        (CASE
-                          WHEN iv.size_bytes < 1000000000 THEN iv.size_bytes
-                          ELSE ROUND(iv.size_bytes / 1000000000.0)
-                         END)::int
-                         ORDER BY
-                         (CASE
-                          WHEN iv.size_bytes < 1000000000 THEN iv.size_bytes
-                          ELSE ROUND(iv.size_bytes / 1000000000.0)
-                         END)::int
-                      )
-                 FROM instance_volumes iv
-                WHERE iv.instance_id = i.id AND iv.deleted_at IS NULL AND iv.size_bytes > 0),
-              ARRAY[]::int[]
-            ) as storage_sizes_gb,
-            i.is_archived,
-            i.deleted_by_provider,
-            COALESCE(p.name, 'Unknown Provider') as provider_name,
-            COALESCE(z.name, 'Unknown Zone') as zone,
-            COALESCE(r.name, 'Unknown Region') as region,
-            COALESCE(it.name, 'Unknown Type') as instance_type,
-            it.cpu_count as cpu_count,
-            it.ram_gb as ram_gb,
-            it.vram_per_gpu_gb as gpu_vram,
-            it.gpu_count as gpu_count,
-            cast(it.cost_per_hour as float8) as cost_per_hour,
-            (EXTRACT(EPOCH FROM (COALESCE(i.terminated_at, NOW()) - i.created_at)) / 3600.0) * cast(it.cost_per_hour as float8) as total_cost
-        FROM instances i
-        LEFT JOIN providers p ON i.provider_id = p.id
-        LEFT JOIN zones z ON i.zone_id = z.id
-        LEFT JOIN regions r ON z.region_id = r.id
-        LEFT JOIN instance_types it ON i.instance_type_id = it.id
-        LEFT JOIN models m ON m.id = i.model_id
+    let instances = sqlx::query_as::<Postgres, InstanceResponse>(&format!(
+        "{}
         WHERE i.is_archived = $1
-        ORDER BY i.created_at DESC
-        "#
-    )
+        ORDER BY i.created_at DESC",
+        INSTANCE_LIST_SELECT
+    ))
     .bind(show_archived)
     .fetch_all(&state.db)
     .await
@@ -225,16 +277,86 @@ pub async fn list_instances(
     Json(instances_vec)
 }
 
+#[utoipa::path(
+    get,
+    path = "/models/{id}/instances",
+    params(
+        ("id" = uuid::Uuid, Path, description = "Model ID"),
+        ModelInstancesParams
+    ),
+    responses(
+        (status = 200, description = "Instances provisioned for the model", body = Vec<InstanceResponse>),
+        (status = 404, description = "Model not found")
+    )
+)]
+pub async fn list_model_instances(
+    State(state): State<Arc<AppState>>,
+    Path(model_id): Path<uuid::Uuid>,
+    axum::extract::Query(params): axum::extract::Query<ModelInstancesParams>,
+) -> impl IntoResponse {
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM models WHERE id = $1)")
+        .bind(model_id)
+        .fetch_one(&state.db)
+        .await
+        .unwrap_or(false);
+    if !exists {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "not_found", "message": "model_not_found"})),
+        )
+            .into_response();
+    }
+    let status = params
+        .status
+        .map(|s| s.trim().to_ascii_lowercase())
+        .filter(|s| !s.is_empty());
+
+    let instances = sqlx::query_as::<Postgres, InstanceResponse>(&format!(
+        "{}
+        WHERE i.model_id = $1
+          AND ($2::text IS NULL OR i.status::text = $2)
+        ORDER BY i.created_at DESC",
+        INSTANCE_LIST_SELECT
+    ))
+    .bind(model_id)
+    .bind(status)
+    .fetch_all(&state.db)
+    .await;
+    match instances {
+        Ok(mut instances) => {
+            progress::enrich_instances_with_progress(&state.db, &mut instances).await;
+            Json(instances).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": "db_error", "message": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
 #[utoipa::path(
     get,
     path = "/instances/search",
     params(SearchInstancesParams),
-    responses((status = 200, description = "Paged search instances (virtualized UI)", body = SearchInstancesResponse))
+    responses(
+        (status = 200, description = "Paged search instances (virtualized UI)", body = SearchInstancesResponse),
+        (status = 400, description = "Unknown sort field")
+    )
 )]
 pub async fn search_instances(
     State(state): State<Arc<AppState>>,
     axum::extract::Query(params): axum::extract::Query<SearchInstancesParams>,
-) -> Json<SearchInstancesResponse> {
+) -> impl IntoResponse {
+    let order_by = match sort::resolve(
+        "sort_by",
+        params.sort_by.as_deref(),
+        sort::INSTANCE_SEARCH_SORT,
+        sort::INSTANCE_SEARCH_SORT_DEFAULT,
+    ) {
+        Ok(expr) => expr,
+        Err(e) => return e.into_response(),
+    };
     let show_archived = params.archived.unwrap_or(false);
     let offset = params.offset.unwrap_or(0).max(0);
     let limit = params.limit.unwrap_or(200).clamp(1, 500);
@@ -260,18 +382,6 @@ pub async fn search_instances(
             .await
             .unwrap_or(0);
 
-    let total_cost_expr = "(EXTRACT(EPOCH FROM (COALESCE(i.terminated_at, NOW()) - i.created_at)) / 3600.0) * cast(it.cost_per_hour as float8)";
-    let order_by = match params.sort_by.as_deref() {
-        Some("status") => "i.status",
-        Some("provider") => "p.name",
-        Some("region") => "r.name",
-        Some("zone") => "z.name",
-        Some("type") => "it.name",
-        Some("cost_per_hour") => "it.cost_per_hour",
-        Some("total_cost") => total_cost_expr,
-        _ => "i.created_at",
-    };
-
     let sql = format!(
         r#"
         SELECT 
@@ -285,17 +395,21 @@ pub async fn search_instances(
             i.worker_status,
             i.worker_last_heartbeat,
             i.worker_model_id,
+            i.worker_model_revision,
             i.worker_queue_depth,
             i.worker_gpu_utilization,
             i.worker_health_port,
             i.worker_vllm_port,
+            i.worker_proxy_port,
+            i.routing_enabled,
             i.worker_metadata,
             i.created_at,
             i.terminated_at,
             i.last_health_check,
-            (i.last_reconciliation AT TIME ZONE 'UTC') as last_reconciliation,
+            i.last_reconciliation,
             i.health_check_failures,
             i.deletion_reason,
+            i.auto_terminate_at,
             i.error_code,
             i.error_message,
             COALESCE((SELECT COUNT(*) FROM instance_volumes iv WHERE iv.instance_id = i.id AND iv.deleted_at IS NULL), 0)::bigint as storage_count,
@@ -323,10 +437,12 @@ pub async fn search_instances(
             COALESCE(it.name, 'Unknown Type') as instance_type,
             it.cpu_count as cpu_count,
             it.ram_gb as ram_gb,
+            it.bandwidth_bps as bandwidth_bps,
             it.vram_per_gpu_gb as gpu_vram,
             it.gpu_count as gpu_count,
             cast(it.cost_per_hour as float8) as cost_per_hour,
-            {total_cost_expr} as total_cost
+            public.instance_billable_seconds(i, NOW())::bigint as billable_seconds,
+            public.instance_total_cost(i, NOW()) as total_cost
         FROM instances i
         LEFT JOIN providers p ON i.provider_id = p.id
         LEFT JOIN zones z ON i.zone_id = z.id
@@ -357,6 +473,7 @@ pub async fn search_instances(
         filtered_count,
         rows,
     })
+    .into_response()
 }
 
 #[utoipa::path(
@@ -387,19 +504,24 @@ pub async fn get_instance(
             i.worker_status,
             i.worker_last_heartbeat,
             i.worker_model_id,
+            i.worker_model_revision,
             i.worker_queue_depth,
             i.worker_gpu_utilization,
             i.worker_health_port,
             i.worker_vllm_port,
+            i.worker_proxy_port,
+            i.routing_enabled,
             i.worker_metadata,
             i.created_at,
             i.terminated_at,
             i.last_health_check,
-            (i.last_reconciliation AT TIME ZONE 'UTC') as last_reconciliation,
+            i.last_reconciliation,
             i.health_check_failures,
             i.deletion_reason,
+            i.auto_terminate_at,
             i.error_code,
             i.error_message,
+            i.provider_metadata,
             COALESCE((SELECT COUNT(*) FROM instance_volumes iv WHERE iv.instance_id = i.id AND iv.deleted_at IS NULL), 0)::bigint as storage_count,
             COALESCE(
               (SELECT ARRAY_AGG(
@@ -425,16 +547,22 @@ pub async fn get_instance(
             COALESCE(it.name, 'Unknown Type') as instance_type,
             it.cpu_count as cpu_count,
             it.ram_gb as ram_gb,
+            it.bandwidth_bps as bandwidth_bps,
             it.vram_per_gpu_gb as gpu_vram,
             it.gpu_count as gpu_count,
             cast(it.cost_per_hour as float8) as cost_per_hour,
-            (EXTRACT(EPOCH FROM (COALESCE(i.terminated_at, NOW()) - i.created_at)) / 3600.0) * cast(it.cost_per_hour as float8) as total_cost
+            public.instance_billable_seconds(i, NOW())::bigint as billable_seconds,
+            public.instance_total_cost(i, NOW()) as total_cost,
+            COALESCE(irm.total_requests, 0) as requests_served,
+            COALESCE(irm.failed_requests, 0) as requests_failed,
+            irm.last_request_at
         FROM instances i
         LEFT JOIN providers p ON i.provider_id = p.id
         LEFT JOIN zones z ON i.zone_id = z.id
         LEFT JOIN regions r ON z.region_id = r.id
         LEFT JOIN instance_types it ON i.instance_type_id = it.id
         LEFT JOIN models m ON m.id = i.model_id
+        LEFT JOIN instance_request_metrics irm ON irm.instance_id = i.id
         WHERE i.id = $1
         LIMIT 1
         "#
@@ -554,6 +682,26 @@ pub async fn get_instance(
     }
 }
 
+/// Terminal statuses an instance can be archived from (terminated or failed for good).
+/// `startup_failed` is excluded: a late worker heartbeat can still recover it.
+const ARCHIVABLE_STATUSES: &[&str] = &["terminated", "archived", "failed", "provisioning_failed"];
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct BulkArchiveRequest {
+    /// Explicit instance ids (non-terminal instances are skipped).
+    pub ids: Option<Vec<uuid::Uuid>>,
+    /// Archive every instance in this terminal status.
+    pub status: Option<String>,
+    /// Only instances that ended (terminated/failed, else created) before this time.
+    pub older_than: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct BulkArchiveResponse {
+    pub archived_count: usize,
+    pub archived_ids: Vec<uuid::Uuid>,
+}
+
 // Archive endpoint (logged version below)
 // COMMAND : ARCHIVE INSTANCE
 #[utoipa::path(
@@ -584,9 +732,10 @@ pub async fn archive_instance(
          SET is_archived = true,
              status = 'archived'
          WHERE id = $1
-           AND status IN ('terminated', 'archived')",
+           AND status::text = ANY($2)",
     )
     .bind(id)
+    .bind(ARCHIVABLE_STATUSES)
     .execute(&state.db)
     .await;
 
@@ -594,7 +743,7 @@ pub async fn archive_instance(
         Ok(r) if r.rows_affected() > 0 => (StatusCode::OK, "Instance Archived"),
         Ok(_) => (
             StatusCode::BAD_REQUEST,
-            "Instance not found or not terminated",
+            "Instance not found or not in a terminal status",
         ),
         Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Database Error"),
     };
@@ -619,6 +768,272 @@ pub async fn archive_instance(
     response.into_response()
 }
 
+// COMMAND : BULK ARCHIVE INSTANCES
+#[utoipa::path(
+    post,
+    path = "/instances/bulk/archive",
+    request_body = BulkArchiveRequest,
+    responses(
+        (status = 200, description = "Instances archived", body = BulkArchiveResponse),
+        (status = 400, description = "Missing filter or non-terminal status"),
+        (status = 500, description = "Server Error")
+    )
+)]
+pub async fn bulk_archive_instances(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<BulkArchiveRequest>,
+) -> impl IntoResponse {
+    let status = req
+        .status
+        .as_deref()
+        .map(|s| s.trim().to_ascii_lowercase())
+        .filter(|s| !s.is_empty());
+    if req.ids.as_ref().is_none_or(|ids| ids.is_empty()) && status.is_none() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": "missing_filter",
+                "message": "provide ids and/or status"
+            })),
+        )
+            .into_response();
+    }
+    if let Some(s) = status.as_deref() {
+        if !ARCHIVABLE_STATUSES.contains(&s) {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({
+                    "error": "invalid_status",
+                    "message": format!("status must be one of: {}", ARCHIVABLE_STATUSES.join(", "))
+                })),
+            )
+                .into_response();
+        }
+    }
+
+    let archived: Result<Vec<uuid::Uuid>, sqlx::Error> = sqlx::query_scalar(
+        "UPDATE instances
+         SET is_archived = true,
+             status = 'archived'
+         WHERE status::text = ANY($1)
+           AND status::text <> 'archived'
+           AND ($2::uuid[] IS NULL OR id = ANY($2))
+           AND ($3::text IS NULL OR status::text = $3)
+           AND ($4::timestamptz IS NULL OR COALESCE(terminated_at, failed_at, created_at) < $4)
+         RETURNING id",
+    )
+    .bind(ARCHIVABLE_STATUSES)
+    .bind(req.ids.as_deref())
+    .bind(status.as_deref())
+    .bind(req.older_than)
+    .fetch_all(&state.db)
+    .await;
+
+    let archived = match archived {
+        Ok(ids) => ids,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "db_error", "message": e.to_string()})),
+            )
+                .into_response();
+        }
+    };
+
+    for id in &archived {
+        let _ = simple_logger::log_action_with_metadata(
+            &state.db,
+            "ARCHIVE_INSTANCE",
+            "success",
+            Some(*id),
+            None,
+            Some(serde_json::json!({"bulk": true, "status_filter": status})),
+        )
+        .await;
+    }
+
+    Json(BulkArchiveResponse {
+        archived_count: archived.len(),
+        archived_ids: archived,
+    })
+    .into_response()
+}
+
+/// What `terminate_instance` does for an instance, given its current state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TerminationDecision {
+    /// Marked `terminating`; the orchestrator deletes the provider resource.
+    ProviderCall,
+    /// No provider resource: marked `terminated` immediately (DB only).
+    ImmediateNoResource,
+    /// Provider resource without a zone: kept `terminating` (MISSING_ZONE) for manual recovery.
+    BlockedMissingZone,
+    /// Nothing to do.
+    AlreadyTerminated,
+}
+
+impl TerminationDecision {
+    pub fn decide(status: &str, provider_instance_id: Option<&str>, has_zone: bool) -> Self {
+        if status == "terminated" {
+            TerminationDecision::AlreadyTerminated
+        } else if provider_instance_id.unwrap_or("").is_empty() {
+            // Failed/invalid provisioning requests must not stay "terminating" forever.
+            TerminationDecision::ImmediateNoResource
+        } else if !has_zone {
+            // Can't safely call the provider API without a zone (risk of leaking resources).
+            TerminationDecision::BlockedMissingZone
+        } else {
+            TerminationDecision::ProviderCall
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct TerminationTarget {
+    id: uuid::Uuid,
+    provider_instance_id: Option<String>,
+    zone_id: Option<uuid::Uuid>,
+    status: String,
+}
+
+impl TerminationTarget {
+    fn decision(&self) -> TerminationDecision {
+        TerminationDecision::decide(
+            &self.status,
+            self.provider_instance_id.as_deref(),
+            self.zone_id.is_some(),
+        )
+    }
+
+    fn plan(&self) -> TerminationPlan {
+        TerminationPlan {
+            instance_id: self.id,
+            status: self.status.clone(),
+            decision: self.decision(),
+            provider_instance_id_present: self.provider_instance_id.is_some(),
+            zone_id_present: self.zone_id.is_some(),
+        }
+    }
+}
+
+async fn fetch_termination_targets(
+    db: &sqlx::Pool<Postgres>,
+    ids: &[uuid::Uuid],
+) -> Result<Vec<TerminationTarget>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT id, provider_instance_id::text AS provider_instance_id, zone_id, status::text AS status
+         FROM instances WHERE id = ANY($1)",
+    )
+    .bind(ids)
+    .fetch_all(db)
+    .await
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct TerminationPlan {
+    pub instance_id: uuid::Uuid,
+    pub status: String,
+    pub decision: TerminationDecision,
+    pub provider_instance_id_present: bool,
+    pub zone_id_present: bool,
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct BulkTerminationPlanRequest {
+    pub ids: Vec<uuid::Uuid>,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct BulkTerminationPlanResponse {
+    pub plans: Vec<TerminationPlan>,
+    /// Requested ids with no matching instance.
+    pub not_found: Vec<uuid::Uuid>,
+}
+
+// QUERY : TERMINATION PLAN (dry-run, no state change, nothing published)
+#[utoipa::path(
+    post,
+    path = "/instances/{id}/terminate/plan",
+    params(
+        ("id" = uuid::Uuid, Path, description = "Instance Database UUID")
+    ),
+    responses(
+        (status = 200, description = "What terminate would do", body = TerminationPlan),
+        (status = 404, description = "Instance not found"),
+        (status = 500, description = "Server Error")
+    )
+)]
+pub async fn plan_terminate_instance(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<uuid::Uuid>,
+) -> impl IntoResponse {
+    match fetch_termination_targets(&state.db, &[id]).await {
+        Ok(targets) => match targets.first() {
+            Some(t) => (StatusCode::OK, Json(t.plan())).into_response(),
+            None => (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "not_found"})),
+            )
+                .into_response(),
+        },
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": "db_error", "message": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
+// QUERY : BULK TERMINATION PLAN
+#[utoipa::path(
+    post,
+    path = "/instances/bulk/terminate/plan",
+    request_body = BulkTerminationPlanRequest,
+    responses(
+        (status = 200, description = "What terminate would do per instance", body = BulkTerminationPlanResponse),
+        (status = 400, description = "No ids"),
+        (status = 500, description = "Server Error")
+    )
+)]
+pub async fn bulk_plan_terminate_instances(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<BulkTerminationPlanRequest>,
+) -> impl IntoResponse {
+    if req.ids.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": "missing_filter",
+                "message": "provide ids"
+            })),
+        )
+            .into_response();
+    }
+    let targets = match fetch_termination_targets(&state.db, &req.ids).await {
+        Ok(t) => t,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "db_error", "message": e.to_string()})),
+            )
+                .into_response();
+        }
+    };
+
+    // Keep the request order; duplicates are reported once.
+    let mut plans = Vec::new();
+    let mut not_found = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for id in req.ids.iter().filter(|id| seen.insert(**id)) {
+        match targets.iter().find(|t| t.id == *id) {
+            Some(t) => plans.push(t.plan()),
+            None => not_found.push(*id),
+        }
+    }
+    Json(BulkTerminationPlanResponse { plans, not_found }).into_response()
+}
+
 // COMMAND : TERMINATE INSTANCE
 #[utoipa::path(
     delete,
@@ -652,16 +1067,12 @@ pub async fn terminate_instance(
     println!("🗑️ Termination Request: {}", id);
 
     // 1. Fetch instance so we can handle edge-cases safely (no provider resource, missing zone, etc.)
-    let instance_row: Option<(Option<String>, Option<uuid::Uuid>, String)> = sqlx::query_as(
-        "SELECT provider_instance_id::text, zone_id, status::text FROM instances WHERE id = $1",
-    )
-    .bind(id)
-    .fetch_optional(&state.db)
-    .await
-    .ok()
-    .flatten();
+    let target = fetch_termination_targets(&state.db, &[id])
+        .await
+        .ok()
+        .and_then(|t| t.into_iter().next());
 
-    let Some((provider_instance_id_opt, zone_id_opt, status)) = instance_row else {
+    let Some(target) = target else {
         println!("⚠️  Instance {} not found for termination", id);
         if let Some(log_id) = log_id {
             let duration = start.elapsed().as_millis() as i32;
@@ -678,99 +1089,103 @@ pub async fn terminate_instance(
         return (StatusCode::NOT_FOUND, "Instance not found").into_response();
     };
 
-    if status == "terminated" {
-        if let Some(log_id) = log_id {
-            let duration = start.elapsed().as_millis() as i32;
-            simple_logger::log_action_complete_with_metadata(
-                &state.db,
-                log_id,
-                "success",
-                duration,
-                None,
-                Some(serde_json::json!({"already_terminated": true})),
-            )
-            .await
-            .ok();
-        }
-        return (StatusCode::OK, "Already terminated").into_response();
-    }
+    let provider_instance_id_opt = target.provider_instance_id.as_deref();
+    let zone_id_opt = target.zone_id;
 
-    // If there is no provider resource to delete (provider_instance_id missing), we terminate immediately.
-    // This prevents "terminating forever" for failed/invalid provisioning requests.
+    // Branching shared with the dry-run plan endpoints (see TerminationDecision).
     //
     // IMPORTANT: if provider_instance_id exists but zone_id is missing, we must NOT mark terminated:
     // we can't safely call the provider API and risk leaking resources. We keep 'terminating' and let
     // admin/operator handle the missing catalog linkage.
-    if provider_instance_id_opt.as_deref().unwrap_or("").is_empty() {
-        let _ = sqlx::query(
-            "UPDATE instances
-             SET status='terminated',
-                 terminated_at = COALESCE(terminated_at, NOW()),
-                 deletion_reason = COALESCE(deletion_reason, 'no_provider_resource')
-             WHERE id=$1 AND status != 'terminated'",
-        )
-        .bind(id)
-        .execute(&state.db)
-        .await;
-
-        if let Some(log_id) = log_id {
-            let duration = start.elapsed().as_millis() as i32;
-            simple_logger::log_action_complete_with_metadata(
-                &state.db,
-                log_id,
-                "success",
-                duration,
-                None,
-                Some(serde_json::json!({
-                    "immediate": true,
-                    "reason": "no_provider_resource",
-                    "provider_instance_id_present": provider_instance_id_opt.is_some(),
-                    "zone_id_present": zone_id_opt.is_some(),
-                })),
-            )
-            .await
-            .ok();
+    match target.decision() {
+        TerminationDecision::AlreadyTerminated => {
+            if let Some(log_id) = log_id {
+                let duration = start.elapsed().as_millis() as i32;
+                simple_logger::log_action_complete_with_metadata(
+                    &state.db,
+                    log_id,
+                    "success",
+                    duration,
+                    None,
+                    Some(serde_json::json!({"already_terminated": true})),
+                )
+                .await
+                .ok();
+            }
+            return (StatusCode::OK, "Already terminated").into_response();
         }
+        TerminationDecision::ImmediateNoResource => {
+            let _ = sqlx::query(
+                "UPDATE instances
+                 SET status='terminated',
+                     terminated_at = COALESCE(terminated_at, NOW()),
+                     deletion_reason = COALESCE(deletion_reason, 'no_provider_resource')
+                 WHERE id=$1 AND status != 'terminated'",
+            )
+            .bind(id)
+            .execute(&state.db)
+            .await;
 
-        return (StatusCode::OK, "Terminated (no provider resource)").into_response();
-    }
-
-    if zone_id_opt.is_none() {
-        // Can't safely terminate on provider without a zone -> keep terminating and surface an error.
-        let _ = sqlx::query(
-            "UPDATE instances
-             SET status='terminating',
-                 error_code = COALESCE(error_code, 'MISSING_ZONE'),
-                 error_message = COALESCE(error_message, 'Missing zone for termination'),
-                 last_reconciliation = NULL
-             WHERE id=$1 AND status != 'terminated'",
-        )
-        .bind(id)
-        .execute(&state.db)
-        .await;
+            if let Some(log_id) = log_id {
+                let duration = start.elapsed().as_millis() as i32;
+                simple_logger::log_action_complete_with_metadata(
+                    &state.db,
+                    log_id,
+                    "success",
+                    duration,
+                    None,
+                    Some(serde_json::json!({
+                        "immediate": true,
+                        "reason": "no_provider_resource",
+                        "provider_instance_id_present": provider_instance_id_opt.is_some(),
+                        "zone_id_present": zone_id_opt.is_some(),
+                    })),
+                )
+                .await
+                .ok();
+            }
 
-        if let Some(log_id) = log_id {
-            let duration = start.elapsed().as_millis() as i32;
-            simple_logger::log_action_complete_with_metadata(
-                &state.db,
-                log_id,
-                "success",
-                duration,
-                Some("Missing zone: kept terminating for manual recovery"),
-                Some(serde_json::json!({
-                    "immediate": false,
-                    "reason": "missing_zone",
-                    "provider_instance_id_present": provider_instance_id_opt.is_some(),
-                    "zone_id_present": zone_id_opt.is_some(),
-                })),
-            )
-            .await
-            .ok();
+            return (StatusCode::OK, "Terminated (no provider resource)").into_response();
         }
+        TerminationDecision::BlockedMissingZone => {
+            // Can't safely terminate on provider without a zone -> keep terminating and surface an error.
+            let _ = sqlx::query(
+                "UPDATE instances
+                 SET status='terminating',
+                     error_code = COALESCE(error_code, $2),
+                     error_message = COALESCE(error_message, 'Missing zone for termination'),
+                     last_reconciliation = NULL
+                 WHERE id=$1 AND status != 'terminated'",
+            )
+            .bind(id)
+            .bind(ErrorCode::MissingZone.as_str())
+            .execute(&state.db)
+            .await;
 
-        // Still publish CMD:TERMINATE (best effort) in case orchestrator can reconcile other metadata,
-        // but the terminator job will also pick it up via status='terminating'.
-        // (We don't early-return here; continue to publish.)
+            if let Some(log_id) = log_id {
+                let duration = start.elapsed().as_millis() as i32;
+                simple_logger::log_action_complete_with_metadata(
+                    &state.db,
+                    log_id,
+                    "success",
+                    duration,
+                    Some("Missing zone: kept terminating for manual recovery"),
+                    Some(serde_json::json!({
+                        "immediate": false,
+                        "reason": "missing_zone",
+                        "provider_instance_id_present": provider_instance_id_opt.is_some(),
+                        "zone_id_present": zone_id_opt.is_some(),
+                    })),
+                )
+                .await
+                .ok();
+            }
+
+            // Still publish CMD:TERMINATE (best effort) in case orchestrator can reconcile other metadata,
+            // but the terminator job will also pick it up via status='terminating'.
+            // (We don't early-return here; continue to publish.)
+        }
+        TerminationDecision::ProviderCall => {}
     }
 
     // 2. Update status to 'terminating' in DB (provider resource exists, orchestrator will delete it)
@@ -1105,3 +1520,430 @@ pub async fn reinstall_instance(
         }
     }
 }
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct InstanceResizeRequest {
+    /// Target instance type (same provider, available in the instance's zone).
+    pub instance_type_id: uuid::Uuid,
+}
+
+/// (status, provider_instance_id, provider_id, zone_id, instance_type_id, model_id)
+type ResizeSourceRow = (
+    String,
+    Option<String>,
+    uuid::Uuid,
+    Option<uuid::Uuid>,
+    Option<uuid::Uuid>,
+    Option<uuid::Uuid>,
+);
+
+/// Check that `instance_type_id` is a valid resize target for instance `id`.
+async fn validate_resize_target(
+    db: &sqlx::Pool<Postgres>,
+    id: uuid::Uuid,
+    instance_type_id: uuid::Uuid,
+) -> Result<(), (StatusCode, &'static str)> {
+    let row: Option<ResizeSourceRow> = sqlx::query_as(
+        "SELECT status::text, provider_instance_id::text, provider_id, zone_id, instance_type_id, model_id
+         FROM instances WHERE id = $1",
+    )
+    .bind(id)
+    .fetch_optional(db)
+    .await
+    .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "Database Error"))?;
+    let Some((status, provider_instance_id, provider_id, zone_id, current_type_id, model_id)) = row
+    else {
+        return Err((StatusCode::NOT_FOUND, "Instance not found"));
+    };
+
+    match status.as_str() {
+        "terminated" => return Err((StatusCode::BAD_REQUEST, "Instance is terminated")),
+        "terminating" => return Err((StatusCode::CONFLICT, "Instance is terminating")),
+        _ => {}
+    }
+    if provider_instance_id.as_deref().unwrap_or("").is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Instance not provisioned (missing provider_instance_id)",
+        ));
+    }
+    if current_type_id == Some(instance_type_id) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Instance already uses this instance type",
+        ));
+    }
+
+    let type_ok: bool = sqlx::query_scalar(
+        "SELECT EXISTS (
+           SELECT 1 FROM instance_types it
+           JOIN instance_type_zones itz ON itz.instance_type_id = it.id
+           WHERE it.id = $1
+             AND it.provider_id = $2
+             AND it.is_active = true
+             AND itz.zone_id = $3
+             AND itz.is_available = true
+         )",
+    )
+    .bind(instance_type_id)
+    .bind(provider_id)
+    .bind(zone_id)
+    .fetch_one(db)
+    .await
+    .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "Database Error"))?;
+    if !type_ok {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Invalid instance type (inactive, other provider or not available in the instance zone)",
+        ));
+    }
+
+    if let Some(model_id) = model_id {
+        let compatible: bool =
+            sqlx::query_scalar("SELECT check_model_instance_compatibility($1, $2)")
+                .bind(model_id)
+                .bind(instance_type_id)
+                .fetch_one(db)
+                .await
+                .unwrap_or(false);
+        if !compatible {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "Model is not compatible with selected instance type (VRAM requirement exceeds available GPU memory)",
+            ));
+        }
+    }
+    Ok(())
+}
+
+// COMMAND : RESIZE INSTANCE (vertical scaling, data volume preserved)
+#[utoipa::path(
+    post,
+    path = "/instances/{id}/resize",
+    params(
+        ("id" = uuid::Uuid, Path, description = "Instance Database UUID")
+    ),
+    request_body = InstanceResizeRequest,
+    responses(
+        (status = 202, description = "Resize Accepted"),
+        (status = 400, description = "Invalid target instance type"),
+        (status = 404, description = "Instance not found"),
+        (status = 409, description = "Instance is not ready (terminating, or already resizing)")
+    )
+)]
+pub async fn resize_instance(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<uuid::Uuid>,
+    Json(req): Json<InstanceResizeRequest>,
+) -> impl IntoResponse {
+    let start = std::time::Instant::now();
+    let log_id = simple_logger::log_action_with_metadata(
+        &state.db,
+        "REQUEST_RESIZE",
+        "in_progress",
+        Some(id),
+        None,
+        Some(serde_json::json!({
+            "instance_id": id.to_string(),
+            "instance_type_id": req.instance_type_id.to_string(),
+        })),
+    )
+    .await
+    .ok();
+
+    if let Err((status, msg)) = validate_resize_target(&state.db, id, req.instance_type_id).await {
+        if let Some(log_id) = log_id {
+            let duration = start.elapsed().as_millis() as i32;
+            simple_logger::log_action_complete(&state.db, log_id, "failed", duration, Some(msg))
+                .await
+                .ok();
+        }
+        return (status, msg).into_response();
+    }
+
+    // Out of routing for the whole power cycle; the guard also rejects a second concurrent resize.
+    let claimed =
+        sqlx::query("UPDATE instances SET status = 'resizing' WHERE id = $1 AND status = 'ready'")
+            .bind(id)
+            .execute(&state.db)
+            .await
+            .map(|r| r.rows_affected() > 0)
+            .unwrap_or(false);
+    if !claimed {
+        let msg = "Only ready instances can be resized (resize already in progress?)";
+        if let Some(log_id) = log_id {
+            let duration = start.elapsed().as_millis() as i32;
+            simple_logger::log_action_complete(&state.db, log_id, "failed", duration, Some(msg))
+                .await
+                .ok();
+        }
+        return (StatusCode::CONFLICT, msg).into_response();
+    }
+
+    // Publish resize command to orchestrator (instance_type_id is updated once the provider confirms).
+    let event = serde_json::json!({
+        "type": "CMD:RESIZE",
+        "instance_id": id.to_string(),
+        "instance_type_id": req.instance_type_id.to_string(),
+        "correlation_id": log_id.map(|id| id.to_string()),
+    })
+    .to_string();
+
+    let published = match state.redis_client.get_multiplexed_async_connection().await {
+        Ok(mut conn) => conn
+            .publish::<_, _, ()>("orchestrator_events", &event)
+            .await
+            .map_err(|e| format!("Failed to publish to Redis: {:?}", e)),
+        Err(e) => Err(format!("Failed to connect to Redis: {:?}", e)),
+    };
+
+    if let Some(log_id) = log_id {
+        let duration = start.elapsed().as_millis() as i32;
+        let (status_str, err) = match &published {
+            Ok(()) => ("success", None),
+            Err(e) => ("failed", Some(e.as_str())),
+        };
+        simple_logger::log_action_complete_with_metadata(
+            &state.db,
+            log_id,
+            status_str,
+            duration,
+            err,
+            Some(serde_json::json!({"redis_published": published.is_ok(), "event_type": "CMD:RESIZE"})),
+        )
+        .await
+        .ok();
+    }
+
+    match published {
+        Ok(()) => (StatusCode::ACCEPTED, "Resize initiated").into_response(),
+        Err(_) => {
+            // Nothing will pick the resize up: put the instance back into routing.
+            let _ = sqlx::query(
+                "UPDATE instances SET status = 'ready' WHERE id = $1 AND status = 'resizing'",
+            )
+            .bind(id)
+            .execute(&state.db)
+            .await;
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to queue resize").into_response()
+        }
+    }
+}
+
+/// Failed statuses a retry may restart from (same instance id, zone, type and model).
+const RETRYABLE_STATUSES: &[&str] = &["provisioning_failed", "startup_failed"];
+
+// COMMAND : RETRY PROVISIONING (same instance id, after a failed create/boot)
+#[utoipa::path(
+    post,
+    path = "/instances/{id}/retry",
+    params(
+        ("id" = uuid::Uuid, Path, description = "Instance Database UUID")
+    ),
+    responses(
+        (status = 202, description = "Retry Accepted (instance back to provisioning)"),
+        (status = 404, description = "Instance not found"),
+        (status = 409, description = "Instance is not in a failed provisioning/startup state, or its server still exists at the provider")
+    )
+)]
+pub async fn retry_instance(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<uuid::Uuid>,
+) -> impl IntoResponse {
+    let start = std::time::Instant::now();
+    let log_id = simple_logger::log_action_with_metadata(
+        &state.db,
+        "REQUEST_RETRY_PROVISION",
+        "in_progress",
+        Some(id),
+        None,
+        Some(serde_json::json!({
+            "instance_id": id.to_string(),
+        })),
+    )
+    .await
+    .ok();
+    let fail = |status: StatusCode, msg: &'static str| {
+        let db = state.db.clone();
+        async move {
+            if let Some(log_id) = log_id {
+                let duration = start.elapsed().as_millis() as i32;
+                simple_logger::log_action_complete(&db, log_id, "failed", duration, Some(msg))
+                    .await
+                    .ok();
+            }
+            (status, msg).into_response()
+        }
+    };
+
+    let row: Option<(String, Option<String>)> =
+        sqlx::query_as("SELECT status::text, provider_instance_id FROM instances WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await
+            .ok()
+            .flatten();
+    let Some((status, provider_instance_id)) = row else {
+        return fail(StatusCode::NOT_FOUND, "Instance not found").await;
+    };
+    if !RETRYABLE_STATUSES.contains(&status.as_str()) {
+        return fail(
+            StatusCode::CONFLICT,
+            "Only provisioning_failed or startup_failed instances can be retried",
+        )
+        .await;
+    }
+    // Provisioning skips create when a server is recorded: retrying would never re-create it.
+    if provider_instance_id.is_some() {
+        return fail(
+            StatusCode::CONFLICT,
+            "Server still exists at the provider: reinstall or terminate it instead",
+        )
+        .await;
+    }
+
+    // Back to a fresh `accepted` (queued) row; the guard makes concurrent retries a no-op.
+    let target: Option<(String, String)> = sqlx::query_as(
+        r#"
+        UPDATE instances i
+        SET status = 'accepted',
+            error_code = NULL,
+            error_message = NULL,
+            failed_at = NULL,
+            retry_count = 0,
+            last_reconciliation = NULL,
+            boot_started_at = NULL,
+            health_check_failures = 0
+        FROM zones z, instance_types it
+        WHERE i.id = $1
+          AND z.id = i.zone_id
+          AND it.id = i.instance_type_id
+          AND i.status::text = ANY($2)
+          AND i.provider_instance_id IS NULL
+        RETURNING COALESCE(z.code, z.name), it.code
+        "#,
+    )
+    .bind(id)
+    .bind(RETRYABLE_STATUSES)
+    .fetch_optional(&state.db)
+    .await
+    .ok()
+    .flatten();
+    let Some((zone, instance_type)) = target else {
+        return fail(StatusCode::CONFLICT, "Instance changed state during retry").await;
+    };
+
+    let event = serde_json::json!({
+        "type": "CMD:PROVISION",
+        "instance_id": id.to_string(),
+        "zone": zone,
+        "instance_type": instance_type,
+        "correlation_id": log_id.map(|id| id.to_string()),
+    })
+    .to_string();
+    let published = match state.redis_client.get_multiplexed_async_connection().await {
+        Ok(mut conn) => conn
+            .publish::<_, _, ()>("orchestrator_events", &event)
+            .await
+            .map_err(|e| format!("Failed to publish to Redis: {:?}", e)),
+        Err(e) => Err(format!("Failed to connect to Redis: {:?}", e)),
+    };
+
+    if let Some(log_id) = log_id {
+        let duration = start.elapsed().as_millis() as i32;
+        simple_logger::log_action_complete_with_metadata(
+            &state.db,
+            log_id,
+            "success",
+            duration,
+            None,
+            Some(serde_json::json!({
+                "previous_status": status,
+                "redis_published": published.is_ok(),
+                "event_type": "CMD:PROVISION",
+            })),
+        )
+        .await
+        .ok();
+    }
+
+    // Even if the publish was lost, job-provisioning re-queues stale `provisioning` rows.
+    (StatusCode::ACCEPTED, "Retry initiated").into_response()
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct InstanceRoutingRequest {
+    pub enabled: bool,
+}
+
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct InstanceRoutingResponse {
+    pub id: uuid::Uuid,
+    pub routing_enabled: bool,
+}
+
+// COMMAND : PAUSE / RESUME ROUTING (no provider action)
+#[utoipa::path(
+    post,
+    path = "/instances/{id}/routing",
+    params(
+        ("id" = uuid::Uuid, Path, description = "Instance Database UUID")
+    ),
+    request_body = InstanceRoutingRequest,
+    responses(
+        (status = 200, description = "Routing flag updated", body = InstanceRoutingResponse),
+        (status = 404, description = "Instance not found"),
+        (status = 500, description = "Server Error")
+    )
+)]
+pub async fn set_instance_routing(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<uuid::Uuid>,
+    Json(req): Json<InstanceRoutingRequest>,
+) -> impl IntoResponse {
+    let start = std::time::Instant::now();
+    let log_id = simple_logger::log_action_with_metadata(
+        &state.db,
+        "SET_INSTANCE_ROUTING",
+        "in_progress",
+        Some(id),
+        None,
+        Some(serde_json::json!({ "routing_enabled": req.enabled })),
+    )
+    .await
+    .ok();
+
+    // Status and worker heartbeats are left as-is: only OpenAI routing skips the instance.
+    let result = sqlx::query("UPDATE instances SET routing_enabled = $2 WHERE id = $1")
+        .bind(id)
+        .bind(req.enabled)
+        .execute(&state.db)
+        .await;
+
+    let (status, err_msg) = match result {
+        Ok(r) if r.rows_affected() > 0 => (StatusCode::OK, None),
+        Ok(_) => (StatusCode::NOT_FOUND, Some("Instance not found")),
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, Some("Database Error")),
+    };
+
+    if let Some(lid) = log_id {
+        let duration = start.elapsed().as_millis() as i32;
+        let status_str = if err_msg.is_none() {
+            "success"
+        } else {
+            "failed"
+        };
+        simple_logger::log_action_complete(&state.db, lid, status_str, duration, err_msg)
+            .await
+            .ok();
+    }
+
+    match err_msg {
+        None => Json(InstanceRoutingResponse {
+            id,
+            routing_enabled: req.enabled,
+        })
+        .into_response(),
+        Some(msg) => (status, msg).into_response(),
+    }
+}
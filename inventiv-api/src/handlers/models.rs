@@ -12,11 +12,14 @@ use std::sync::Arc;
 use utoipa::IntoParams;
 
 use crate::app::AppState;
+use crate::sort;
+use crate::worker_routing;
 
 #[derive(Deserialize, IntoParams, utoipa::ToSchema)]
 pub struct ListModelsParams {
     pub active: Option<bool>,
-    /// Optional sort field (allowlist).
+    /// Sort field allowlist: name|model_id|required_vram_gb|context_length|data_volume_gb|is_active|created_at|updated_at
+    /// (default: name). Unknown fields are rejected with 400.
     pub order_by: Option<String>,
     /// "asc" | "desc"
     pub order_dir: Option<String>,
@@ -32,6 +35,20 @@ pub struct CreateModelRequest {
     pub is_active: Option<bool>,
     /// Recommended data volume size (GB) for this model (optional).
     pub data_volume_gb: Option<i64>,
+    /// Worker freshness window override (seconds, 10..=86400). Defaults to the global setting.
+    pub stale_window_seconds: Option<i32>,
+    /// Provider boot image id override (e.g. a specific CUDA image). Defaults to the instance-type image.
+    pub boot_image_id: Option<String>,
+    /// Listed in /v1/models for API-key callers (default true).
+    pub public: Option<bool>,
+    /// Generation defaults applied to chat/completions requests that omit them (JSON object).
+    pub default_params: Option<serde_json::Value>,
+    /// Extra headers sent to workers for this model (JSON object name -> value). Write-only.
+    pub forward_headers: Option<serde_json::Value>,
+    /// Warm pool size kept provisioned by the orchestrator (default 0 = none).
+    pub min_instances: Option<i32>,
+    /// Cap on live instances for this model (default unlimited).
+    pub max_instances: Option<i32>,
     pub metadata: Option<serde_json::Value>,
 }
 
@@ -43,14 +60,129 @@ pub struct UpdateModelRequest {
     pub context_length: Option<i32>,
     pub is_active: Option<bool>,
     pub data_volume_gb: Option<i64>,
+    pub stale_window_seconds: Option<i32>,
+    /// true = remove the stale window override (back to the global default).
+    pub clear_stale_window_seconds: Option<bool>,
+    /// Empty string clears the override.
+    pub boot_image_id: Option<String>,
     pub metadata: Option<serde_json::Value>,
+    /// true = mark deprecated (keeps the original date if already set), false = un-deprecate.
+    pub deprecated: Option<bool>,
+    /// Replacement suggested to clients of a deprecated model (cleared when un-deprecating).
+    pub replacement_model_id: Option<uuid::Uuid>,
+    /// Model served instead when this one has no capacity (opt-in per request / globally).
+    pub fallback_model_id: Option<uuid::Uuid>,
+    /// true = remove the fallback model.
+    pub clear_fallback_model: Option<bool>,
+    /// false = hide from /v1/models for API keys (only keys scoped to it can call it).
+    pub public: Option<bool>,
+    /// Replaces the generation defaults (JSON object).
+    pub default_params: Option<serde_json::Value>,
+    /// true = remove the generation defaults.
+    pub clear_default_params: Option<bool>,
+    /// Replaces the extra worker headers (JSON object name -> value).
+    pub forward_headers: Option<serde_json::Value>,
+    /// true = remove the extra worker headers.
+    pub clear_forward_headers: Option<bool>,
+    /// Warm pool size (0 disables the warm pool).
+    pub min_instances: Option<i32>,
+    pub max_instances: Option<i32>,
+    /// true = remove the instance cap.
+    pub clear_max_instances: Option<bool>,
+}
+
+fn stale_window_seconds_valid(v: Option<i32>) -> bool {
+    v.is_none_or(|s| (10..=24 * 60 * 60).contains(&s))
+}
+
+fn invalid_stale_window_response() -> axum::response::Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": "invalid_stale_window_seconds",
+            "message": "stale_window_seconds must be between 10 and 86400"
+        })),
+    )
+        .into_response()
+}
+
+fn default_params_valid(v: Option<&serde_json::Value>) -> bool {
+    v.is_none_or(|p| p.is_object())
+}
+
+fn invalid_default_params_response() -> axum::response::Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": "invalid_default_params",
+            "message": "default_params must be a JSON object"
+        })),
+    )
+        .into_response()
+}
+
+/// Validation error for `forward_headers`, if any.
+fn forward_headers_error(v: Option<&serde_json::Value>) -> Option<String> {
+    v.and_then(|h| worker_routing::parse_forward_headers(h).err())
+}
+
+fn invalid_forward_headers_response(message: String) -> axum::response::Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": "invalid_forward_headers",
+            "message": message
+        })),
+    )
+        .into_response()
+}
+
+/// `models_instance_bounds_check`: 0 <= min_instances <= max_instances, max_instances > 0.
+fn is_instance_bounds_violation(e: &dyn sqlx::error::DatabaseError) -> bool {
+    e.constraint() == Some("models_instance_bounds_check")
+}
+
+fn invalid_instance_bounds_response() -> axum::response::Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": "invalid_instance_bounds",
+            "message": "min_instances must be >= 0, max_instances > 0 and min_instances <= max_instances"
+        })),
+    )
+        .into_response()
+}
+
+fn invalid_fallback_response() -> axum::response::Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": "invalid_fallback_model",
+            "message": "fallback_model_id must reference another existing model"
+        })),
+    )
+        .into_response()
+}
+
+fn invalid_replacement_response() -> axum::response::Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": "invalid_replacement_model",
+            "message": "replacement_model_id must reference another existing model"
+        })),
+    )
+        .into_response()
 }
 
 #[utoipa::path(
     get,
     path = "/models",
     params(ListModelsParams),
-    responses((status = 200, description = "List models", body = [inventiv_common::LlmModel]))
+    responses(
+        (status = 200, description = "List models", body = [inventiv_common::LlmModel]),
+        (status = 400, description = "Unknown sort field")
+    )
 )]
 pub async fn list_models(
     State(state): State<Arc<AppState>>,
@@ -66,18 +198,17 @@ pub async fn list_models(
         "desc" => "DESC",
         _ => "ASC",
     };
-    let order_by = match params.order_by.as_deref() {
-        Some("model_id") => "model_id",
-        Some("required_vram_gb") => "required_vram_gb",
-        Some("context_length") => "context_length",
-        Some("data_volume_gb") => "data_volume_gb",
-        Some("is_active") => "is_active",
-        Some("created_at") => "created_at",
-        Some("updated_at") => "updated_at",
-        _ => "name",
+    let order_by = match sort::resolve(
+        "order_by",
+        params.order_by.as_deref(),
+        sort::MODELS_SORT,
+        sort::MODELS_SORT_DEFAULT,
+    ) {
+        Ok(expr) => expr,
+        Err(e) => return e.into_response(),
     };
 
-    let base = r#"SELECT id, name, model_id, required_vram_gb, context_length, is_active, data_volume_gb, metadata, created_at, updated_at
+    let base = r#"SELECT id, name, model_id, required_vram_gb, context_length, is_active, data_volume_gb, stale_window_seconds, boot_image_id, deprecated_at, replacement_model_id, fallback_model_id, public, default_params, min_instances, max_instances, metadata, created_at, updated_at
                  FROM models"#;
     let where_clause = if params.active == Some(true) {
         " WHERE is_active = true"
@@ -115,7 +246,7 @@ pub async fn list_compatible_models(
         r#"
         SELECT DISTINCT
             m.id, m.name, m.model_id, m.required_vram_gb, m.context_length,
-            m.is_active, m.data_volume_gb, m.metadata, m.created_at, m.updated_at
+            m.is_active, m.data_volume_gb, m.stale_window_seconds, m.boot_image_id, m.deprecated_at, m.replacement_model_id, m.fallback_model_id, m.public, m.default_params, m.min_instances, m.max_instances, m.metadata, m.created_at, m.updated_at
         FROM models m
         WHERE m.is_active = true
           AND check_model_instance_compatibility(m.id, $1) = true
@@ -143,7 +274,7 @@ pub async fn get_model(
         return (StatusCode::BAD_REQUEST, Json(json!({"error":"invalid_id"}))).into_response();
     };
     let row: Option<LlmModel> = sqlx::query_as(
-        r#"SELECT id, name, model_id, required_vram_gb, context_length, is_active, data_volume_gb, metadata, created_at, updated_at
+        r#"SELECT id, name, model_id, required_vram_gb, context_length, is_active, data_volume_gb, stale_window_seconds, boot_image_id, deprecated_at, replacement_model_id, fallback_model_id, public, default_params, min_instances, max_instances, metadata, created_at, updated_at
            FROM models WHERE id = $1"#,
     )
     .bind(uid)
@@ -166,13 +297,22 @@ pub async fn create_model(
     State(state): State<Arc<AppState>>,
     Json(payload): Json<CreateModelRequest>,
 ) -> impl IntoResponse {
+    if !stale_window_seconds_valid(payload.stale_window_seconds) {
+        return invalid_stale_window_response();
+    }
+    if !default_params_valid(payload.default_params.as_ref()) {
+        return invalid_default_params_response();
+    }
+    if let Some(message) = forward_headers_error(payload.forward_headers.as_ref()) {
+        return invalid_forward_headers_response(message);
+    }
     let id = uuid::Uuid::new_v4();
     let is_active = payload.is_active.unwrap_or(true);
     let metadata = sqlx::types::Json(payload.metadata.unwrap_or_else(|| json!({})));
     let res: Result<LlmModel, sqlx::Error> = sqlx::query_as(
-        r#"INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, data_volume_gb, metadata, created_at, updated_at)
-           VALUES ($1,$2,$3,$4,$5,$6,$7,$8,NOW(),NOW())
-           RETURNING id, name, model_id, required_vram_gb, context_length, is_active, data_volume_gb, metadata, created_at, updated_at"#,
+        r#"INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, data_volume_gb, stale_window_seconds, boot_image_id, metadata, public, default_params, forward_headers, min_instances, max_instances, created_at, updated_at)
+           VALUES ($1,$2,$3,$4,$5,$6,$7,$8,NULLIF(btrim($9), ''),$10,$11,$12,$13,$14,$15,NOW(),NOW())
+           RETURNING id, name, model_id, required_vram_gb, context_length, is_active, data_volume_gb, stale_window_seconds, boot_image_id, deprecated_at, replacement_model_id, fallback_model_id, public, default_params, min_instances, max_instances, metadata, created_at, updated_at"#,
     )
     .bind(id)
     .bind(payload.name)
@@ -181,11 +321,21 @@ pub async fn create_model(
     .bind(payload.context_length)
     .bind(is_active)
     .bind(payload.data_volume_gb)
+    .bind(payload.stale_window_seconds)
+    .bind(payload.boot_image_id)
     .bind(metadata)
+    .bind(payload.public.unwrap_or(true))
+    .bind(payload.default_params)
+    .bind(payload.forward_headers)
+    .bind(payload.min_instances.unwrap_or(0))
+    .bind(payload.max_instances)
     .fetch_one(&state.db)
     .await;
     match res {
         Ok(m) => (StatusCode::CREATED, Json(m)).into_response(),
+        Err(sqlx::Error::Database(e)) if is_instance_bounds_violation(e.as_ref()) => {
+            invalid_instance_bounds_response()
+        }
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({"error":"db_error","message": e.to_string()})),
@@ -208,6 +358,21 @@ pub async fn update_model(
     let Ok(uid) = uuid::Uuid::parse_str(&id) else {
         return (StatusCode::BAD_REQUEST, Json(json!({"error":"invalid_id"}))).into_response();
     };
+    if !stale_window_seconds_valid(payload.stale_window_seconds) {
+        return invalid_stale_window_response();
+    }
+    if payload.replacement_model_id == Some(uid) {
+        return invalid_replacement_response();
+    }
+    if payload.fallback_model_id == Some(uid) {
+        return invalid_fallback_response();
+    }
+    if !default_params_valid(payload.default_params.as_ref()) {
+        return invalid_default_params_response();
+    }
+    if let Some(message) = forward_headers_error(payload.forward_headers.as_ref()) {
+        return invalid_forward_headers_response(message);
+    }
     let metadata = payload.metadata.map(sqlx::types::Json);
     let row: Result<LlmModel, sqlx::Error> = sqlx::query_as(
         r#"UPDATE models
@@ -218,9 +383,41 @@ pub async fn update_model(
                is_active = COALESCE($6, is_active),
                data_volume_gb = COALESCE($7, data_volume_gb),
                metadata = COALESCE($8, metadata),
+               stale_window_seconds = CASE
+                 WHEN COALESCE($23, false) THEN NULL
+                 ELSE COALESCE($9, stale_window_seconds)
+               END,
+               boot_image_id = CASE WHEN $10::text IS NULL THEN boot_image_id ELSE NULLIF(btrim($10), '') END,
+               deprecated_at = CASE
+                 WHEN $11::bool IS NULL THEN deprecated_at
+                 WHEN $11 THEN COALESCE(deprecated_at, NOW())
+                 ELSE NULL
+               END,
+               replacement_model_id = CASE
+                 WHEN $11::bool = false THEN NULL
+                 ELSE COALESCE($12, replacement_model_id)
+               END,
+               fallback_model_id = CASE
+                 WHEN COALESCE($14, false) THEN NULL
+                 ELSE COALESCE($13, fallback_model_id)
+               END,
+               public = COALESCE($15, public),
+               default_params = CASE
+                 WHEN COALESCE($17, false) THEN NULL
+                 ELSE COALESCE($16, default_params)
+               END,
+               forward_headers = CASE
+                 WHEN COALESCE($19, false) THEN NULL
+                 ELSE COALESCE($18, forward_headers)
+               END,
+               min_instances = COALESCE($20, min_instances),
+               max_instances = CASE
+                 WHEN COALESCE($22, false) THEN NULL
+                 ELSE COALESCE($21, max_instances)
+               END,
                updated_at = NOW()
            WHERE id = $1
-           RETURNING id, name, model_id, required_vram_gb, context_length, is_active, data_volume_gb, metadata, created_at, updated_at"#,
+           RETURNING id, name, model_id, required_vram_gb, context_length, is_active, data_volume_gb, stale_window_seconds, boot_image_id, deprecated_at, replacement_model_id, fallback_model_id, public, default_params, min_instances, max_instances, metadata, created_at, updated_at"#,
     )
     .bind(uid)
     .bind(payload.name)
@@ -230,6 +427,21 @@ pub async fn update_model(
     .bind(payload.is_active)
     .bind(payload.data_volume_gb)
     .bind(metadata)
+    .bind(payload.stale_window_seconds)
+    .bind(payload.boot_image_id)
+    .bind(payload.deprecated)
+    .bind(payload.replacement_model_id)
+    .bind(payload.fallback_model_id)
+    .bind(payload.clear_fallback_model)
+    .bind(payload.public)
+    .bind(payload.default_params)
+    .bind(payload.clear_default_params)
+    .bind(payload.forward_headers)
+    .bind(payload.clear_forward_headers)
+    .bind(payload.min_instances)
+    .bind(payload.max_instances)
+    .bind(payload.clear_max_instances)
+    .bind(payload.clear_stale_window_seconds)
     .fetch_one(&state.db)
     .await;
     match row {
@@ -237,6 +449,16 @@ pub async fn update_model(
         Err(sqlx::Error::RowNotFound) => {
             (StatusCode::NOT_FOUND, Json(json!({"error":"not_found"}))).into_response()
         }
+        Err(sqlx::Error::Database(e)) if is_instance_bounds_violation(e.as_ref()) => {
+            invalid_instance_bounds_response()
+        }
+        Err(sqlx::Error::Database(e)) if e.code().as_deref() == Some("23503") => {
+            if e.constraint().is_some_and(|c| c.contains("fallback")) {
+                invalid_fallback_response()
+            } else {
+                invalid_replacement_response()
+            }
+        }
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({"error":"db_error","message": e.to_string()})),
@@ -277,7 +499,7 @@ pub async fn get_recommended_data_volume(
 
     // Get model from DB
     let model: Option<LlmModel> = sqlx::query_as(
-        r#"SELECT id, name, model_id, required_vram_gb, context_length, is_active, data_volume_gb, metadata, created_at, updated_at
+        r#"SELECT id, name, model_id, required_vram_gb, context_length, is_active, data_volume_gb, stale_window_seconds, boot_image_id, deprecated_at, replacement_model_id, fallback_model_id, public, default_params, min_instances, max_instances, metadata, created_at, updated_at
            FROM models WHERE id = $1"#,
     )
     .bind(uid)
@@ -369,3 +591,161 @@ pub async fn delete_model(
         }
     }
 }
+
+#[derive(Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ModelCompatResponse {
+    pub model_id: String,
+    /// tokenizer_config.json ships a `chat_template` (usable with /v1/chat/completions).
+    pub chat_capable: bool,
+    /// config.json describes a bare encoder / embedding architecture (usable with /v1/embeddings).
+    pub embedding_capable: bool,
+    pub warnings: Vec<String>,
+}
+
+const HF_COMPAT_TIMEOUT_MS: u64 = 3000;
+
+fn hf_hub_base_url() -> String {
+    std::env::var("HF_HUB_BASE_URL")
+        .ok()
+        .map(|s| s.trim().trim_end_matches('/').to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "https://huggingface.co".to_string())
+}
+
+fn hf_token() -> Option<String> {
+    ["HF_TOKEN", "WORKER_HF_TOKEN"]
+        .iter()
+        .filter_map(|k| std::env::var(k).ok())
+        .map(|s| s.trim().to_string())
+        .find(|s| !s.is_empty())
+}
+
+/// Best-effort fetch of a JSON file from the model repo (main revision).
+async fn fetch_hf_json(
+    client: &reqwest::Client,
+    repo: &str,
+    file: &str,
+) -> Result<Option<serde_json::Value>, String> {
+    let url = format!("{}/{}/resolve/main/{}", hf_hub_base_url(), repo, file);
+    let mut req = client.get(&url);
+    if let Some(token) = hf_token() {
+        req = req.bearer_auth(token);
+    }
+    let resp = req.send().await.map_err(|e| e.to_string())?;
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !resp.status().is_success() {
+        return Err(format!("HTTP {}", resp.status()));
+    }
+    resp.json().await.map(Some).map_err(|e| e.to_string())
+}
+
+fn looks_like_embedding_model(config: &serde_json::Value) -> bool {
+    let archs = config
+        .get("architectures")
+        .and_then(|a| a.as_array())
+        .map(|a| a.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>())
+        .unwrap_or_default();
+    if archs.iter().any(|a| {
+        let a = a.to_ascii_lowercase();
+        a.contains("embedding") || (a.ends_with("model") && !a.ends_with("lmheadmodel"))
+    }) {
+        return true;
+    }
+    matches!(
+        config.get("model_type").and_then(|t| t.as_str()),
+        Some("bert" | "xlm-roberta" | "roberta" | "nomic_bert" | "mpnet")
+    )
+}
+
+#[utoipa::path(
+    post,
+    path = "/models/{id}/check-compat",
+    params(("id" = uuid::Uuid, Path, description = "Model UUID")),
+    responses(
+        (status = 200, description = "Chat/embedding compatibility (best-effort)", body = ModelCompatResponse),
+        (status = 404, description = "Model not found")
+    )
+)]
+pub async fn check_model_compat(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let Ok(uid) = uuid::Uuid::parse_str(&id) else {
+        return (StatusCode::BAD_REQUEST, Json(json!({"error":"invalid_id"}))).into_response();
+    };
+    let repo: Option<String> = sqlx::query_scalar("SELECT model_id FROM models WHERE id = $1")
+        .bind(uid)
+        .fetch_optional(&state.db)
+        .await
+        .unwrap_or(None);
+    let Some(repo) = repo else {
+        return (StatusCode::NOT_FOUND, Json(json!({"error":"not_found"}))).into_response();
+    };
+
+    let client = match reqwest::Client::builder()
+        .timeout(std::time::Duration::from_millis(HF_COMPAT_TIMEOUT_MS))
+        .build()
+    {
+        Ok(c) => c,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error":"http_client_error","message": e.to_string()})),
+            )
+                .into_response()
+        }
+    };
+
+    let mut warnings = Vec::new();
+    let (tokenizer_config, config) = tokio::join!(
+        fetch_hf_json(&client, &repo, "tokenizer_config.json"),
+        fetch_hf_json(&client, &repo, "config.json"),
+    );
+
+    let chat_capable = match tokenizer_config {
+        Ok(Some(tc)) => {
+            let has_template = tc
+                .get("chat_template")
+                .is_some_and(|t| !t.is_null() && t.as_str() != Some(""));
+            if !has_template {
+                warnings.push(
+                    "tokenizer_config.json has no chat_template; /v1/chat/completions will fail unless the worker supplies one".to_string(),
+                );
+            }
+            has_template
+        }
+        Ok(None) => {
+            warnings.push("tokenizer_config.json not found in model repo".to_string());
+            false
+        }
+        Err(e) => {
+            warnings.push(format!("could not fetch tokenizer_config.json: {}", e));
+            false
+        }
+    };
+
+    let embedding_capable = match config {
+        Ok(Some(cfg)) => looks_like_embedding_model(&cfg),
+        Ok(None) => {
+            warnings.push("config.json not found in model repo".to_string());
+            false
+        }
+        Err(e) => {
+            warnings.push(format!("could not fetch config.json: {}", e));
+            false
+        }
+    };
+
+    (
+        StatusCode::OK,
+        Json(ModelCompatResponse {
+            model_id: repo,
+            chat_capable,
+            embedding_capable,
+            warnings,
+        }),
+    )
+        .into_response()
+}
@@ -10,6 +10,7 @@ use utoipa::IntoParams;
 
 use crate::app::AppState;
 use crate::handlers::openai::openai_worker_stale_seconds_db;
+use crate::worker_routing;
 
 #[derive(Serialize, sqlx::FromRow, utoipa::ToSchema)]
 pub struct RuntimeModelRow {
@@ -19,40 +20,83 @@ pub struct RuntimeModelRow {
     instances_available: i64,
     gpus_available: i64,
     vram_total_gb: i64,
+    /// Distinct model revisions reported by live workers (more than one = inconsistent outputs).
+    revisions: Vec<String>,
     total_requests: i64,
     failed_requests: i64,
 }
 
+#[derive(Deserialize, IntoParams)]
+pub struct RuntimeModelsParams {
+    /// Count requests from this instant (RFC 3339). When `since`/`until` are both absent,
+    /// `total_requests`/`failed_requests` are lifetime totals.
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// Count requests up to this instant (RFC 3339). Defaults to now when only `since` is set.
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 #[utoipa::path(
     get,
     path = "/runtime/models",
-    responses((status = 200, description = "Runtime models (live capacity + history + counters)", body = Vec<RuntimeModelRow>))
+    params(RuntimeModelsParams),
+    responses(
+        (status = 200, description = "Runtime models (live capacity + history + counters)", body = Vec<RuntimeModelRow>),
+        (status = 400, description = "Invalid window (since > until)")
+    )
 )]
-pub async fn list_runtime_models(State(state): State<Arc<AppState>>) -> Json<Vec<RuntimeModelRow>> {
+pub async fn list_runtime_models(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<RuntimeModelsParams>,
+) -> impl IntoResponse {
+    if let (Some(since), Some(until)) = (params.since, params.until) {
+        if since > until {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({
+                    "error": "invalid_window",
+                    "message": "since must be before until"
+                })),
+            )
+                .into_response();
+        }
+    }
+    let windowed = params.since.is_some() || params.until.is_some();
+
     let stale = openai_worker_stale_seconds_db(&state.db).await;
 
     // Live capacity aggregation (only "ready" + recent heartbeats).
     // Note: instance_types may be null in some edge cases; we treat missing as 0.
-    let rows = sqlx::query_as::<Postgres, RuntimeModelRow>(
+    let rows = sqlx::query_as::<Postgres, RuntimeModelRow>(&format!(
         r#"
         WITH live AS (
           SELECT
             i.worker_model_id AS model_id,
             COUNT(*)::bigint AS instances_available,
             COALESCE(SUM(COALESCE(it.gpu_count, 0))::bigint, 0) AS gpus_available,
-            COALESCE(SUM(COALESCE(it.gpu_count, 0) * COALESCE(it.vram_per_gpu_gb, 0))::bigint, 0) AS vram_total_gb
+            COALESCE(SUM(COALESCE(it.gpu_count, 0) * COALESCE(it.vram_per_gpu_gb, 0))::bigint, 0) AS vram_total_gb,
+            ARRAY_REMOVE(ARRAY_AGG(DISTINCT i.worker_model_revision), NULL) AS revisions
           FROM instances i
           LEFT JOIN instance_types it ON it.id = i.instance_type_id
-          WHERE i.status::text = 'ready'
-            AND i.ip_address IS NOT NULL
+          LEFT JOIN models m ON m.model_id = i.worker_model_id
+          WHERE {routable}
             AND i.worker_model_id IS NOT NULL
-            AND (i.worker_status = 'ready' OR i.worker_status IS NULL)
             AND GREATEST(
               COALESCE(i.worker_last_heartbeat, 'epoch'::timestamptz),
               COALESCE(i.last_health_check, 'epoch'::timestamptz),
-              COALESCE((i.last_reconciliation AT TIME ZONE 'UTC'), 'epoch'::timestamptz)
-            ) > NOW() - ($1::bigint * INTERVAL '1 second')
+              COALESCE(i.last_reconciliation, 'epoch'::timestamptz)
+            ) > NOW() - (COALESCE(m.stale_window_seconds::bigint, $1::bigint) * INTERVAL '1 second')
           GROUP BY i.worker_model_id
+        ),
+        win AS (
+          SELECT
+            c.model_id,
+            SUM(c.requests)::bigint AS requests,
+            SUM(c.failed_requests)::bigint AS failed_requests
+          FROM runtime_model_counters_minute c
+          WHERE $2::bool
+            AND ($3::timestamptz IS NULL OR c.bucket_minute >= date_trunc('minute', $3::timestamptz, 'UTC'))
+            AND ($4::timestamptz IS NULL OR c.bucket_minute <= $4::timestamptz)
+          GROUP BY c.model_id
         )
         SELECT
           rm.model_id,
@@ -61,19 +105,25 @@ pub async fn list_runtime_models(State(state): State<Arc<AppState>>) -> Json<Vec
           COALESCE(l.instances_available, 0) AS instances_available,
           COALESCE(l.gpus_available, 0) AS gpus_available,
           COALESCE(l.vram_total_gb, 0) AS vram_total_gb,
-          rm.total_requests,
-          rm.failed_requests
+          COALESCE(l.revisions, ARRAY[]::text[]) AS revisions,
+          CASE WHEN $2::bool THEN COALESCE(w.requests, 0) ELSE rm.total_requests END AS total_requests,
+          CASE WHEN $2::bool THEN COALESCE(w.failed_requests, 0) ELSE rm.failed_requests END AS failed_requests
         FROM runtime_models rm
         LEFT JOIN live l ON l.model_id = rm.model_id
+        LEFT JOIN win w ON w.model_id = rm.model_id
         ORDER BY COALESCE(l.instances_available, 0) DESC, rm.last_seen_at DESC
         "#,
-    )
+        routable = worker_routing::ROUTABLE_INSTANCE_SQL
+    ))
     .bind(stale)
+    .bind(windowed)
+    .bind(params.since)
+    .bind(params.until)
     .fetch_all(&state.db)
     .await
     .unwrap_or_default();
 
-    Json(rows)
+    Json(rows).into_response()
 }
 
 #[derive(Deserialize, IntoParams)]
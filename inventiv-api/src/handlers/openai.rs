@@ -1,88 +1,230 @@
 // OpenAI proxy handlers
 use axum::body::Bytes;
-use axum::extract::State;
-use axum::http::HeaderMap;
-use axum::response::Response;
+use axum::extract::{Path, RawQuery, State};
+use axum::http::{HeaderMap, Method, StatusCode};
+use axum::response::{IntoResponse, Response};
+use inventiv_common::net;
 use std::sync::Arc;
 
 use crate::app::AppState;
 use crate::auth;
 use crate::openai_proxy;
+use crate::worker_routing;
 
+// --- OpenAPI schemas ---
+// Proxy bodies are forwarded to workers as-is; these describe the commonly used subset
+// (unknown fields are passed through untouched).
+
+#[derive(serde::Serialize, utoipa::ToSchema)]
+pub struct OpenAiModel {
+    pub id: String,
+    pub object: String,
+    /// Unix timestamp of the last READY worker heartbeat for this model
+    pub created: i64,
+    pub owned_by: String,
+}
+
+#[derive(serde::Serialize, utoipa::ToSchema)]
+pub struct OpenAiModelList {
+    pub object: String,
+    pub data: Vec<OpenAiModel>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
+pub struct OpenAiChatMessage {
+    /// system | user | assistant | tool
+    pub role: String,
+    pub content: String,
+}
+
+// Schema only: the proxy forwards the raw body, so the fields are never read.
+#[allow(dead_code)]
+#[derive(serde::Deserialize, utoipa::ToSchema)]
+pub struct OpenAiChatCompletionRequest {
+    /// Model id as listed by /v1/models
+    pub model: String,
+    pub messages: Vec<OpenAiChatMessage>,
+    /// When true, the response is streamed as server-sent events (text/event-stream)
+    pub stream: Option<bool>,
+    pub temperature: Option<f64>,
+    pub max_tokens: Option<u32>,
+}
+
+#[derive(serde::Serialize, utoipa::ToSchema)]
+pub struct OpenAiUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+}
+
+#[derive(serde::Serialize, utoipa::ToSchema)]
+pub struct OpenAiChatCompletionChoice {
+    pub index: u32,
+    pub message: OpenAiChatMessage,
+    pub finish_reason: Option<String>,
+}
+
+#[derive(serde::Serialize, utoipa::ToSchema)]
+pub struct OpenAiChatCompletionResponse {
+    pub id: String,
+    pub object: String,
+    pub created: i64,
+    pub model: String,
+    pub choices: Vec<OpenAiChatCompletionChoice>,
+    pub usage: Option<OpenAiUsage>,
+}
+
+// Schema only: the proxy forwards the raw body, so the fields are never read.
+#[allow(dead_code)]
+#[derive(serde::Deserialize, utoipa::ToSchema)]
+pub struct OpenAiCompletionRequest {
+    pub model: String,
+    /// Prompt text (arrays of prompts are forwarded as-is)
+    pub prompt: String,
+    pub stream: Option<bool>,
+    pub temperature: Option<f64>,
+    pub max_tokens: Option<u32>,
+}
+
+#[derive(serde::Serialize, utoipa::ToSchema)]
+pub struct OpenAiCompletionChoice {
+    pub index: u32,
+    pub text: String,
+    pub finish_reason: Option<String>,
+}
+
+#[derive(serde::Serialize, utoipa::ToSchema)]
+pub struct OpenAiCompletionResponse {
+    pub id: String,
+    pub object: String,
+    pub created: i64,
+    pub model: String,
+    pub choices: Vec<OpenAiCompletionChoice>,
+    pub usage: Option<OpenAiUsage>,
+}
+
+// Schema only: the proxy forwards the raw body, so the fields are never read.
+#[allow(dead_code)]
+#[derive(serde::Deserialize, utoipa::ToSchema)]
+pub struct OpenAiEmbeddingRequest {
+    pub model: String,
+    /// Input text (arrays of inputs are forwarded as-is)
+    pub input: String,
+}
+
+#[derive(serde::Serialize, utoipa::ToSchema)]
+pub struct OpenAiEmbedding {
+    pub index: u32,
+    pub object: String,
+    pub embedding: Vec<f32>,
+}
+
+#[derive(serde::Serialize, utoipa::ToSchema)]
+pub struct OpenAiEmbeddingResponse {
+    pub object: String,
+    pub model: String,
+    pub data: Vec<OpenAiEmbedding>,
+    pub usage: Option<OpenAiUsage>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/models",
+    responses((status = 200, description = "Models currently served by READY workers (non-public models are hidden from API keys)", body = OpenAiModelList))
+)]
 pub async fn openai_list_models(
     State(state): State<Arc<AppState>>,
+    api_key: Option<axum::extract::Extension<auth::ApiKeyPrincipal>>,
 ) -> impl axum::response::IntoResponse {
     // Return *live* models based on worker heartbeats:
     // - if at least 1 READY worker serves model_id and heartbeat is recent -> exposed in /v1/models
     // - if no workers for a model for a while -> disappears (staleness window)
+    // - non-public models are only listed for user sessions (API keys can still call them if scoped)
     #[derive(serde::Serialize, sqlx::FromRow)]
     struct Row {
         model_id: String,
+        ip_address: String,
         last_seen: chrono::DateTime<chrono::Utc>,
     }
-    #[derive(serde::Serialize)]
-    struct ModelObj {
-        id: String,
-        object: &'static str,
-        created: i64,
-        owned_by: &'static str,
-    }
-    #[derive(serde::Serialize)]
-    struct Resp {
-        object: &'static str,
-        data: Vec<ModelObj>,
-    }
 
     let stale = openai_worker_stale_seconds_db(&state.db).await;
-    let rows = sqlx::query_as::<sqlx::Postgres, Row>(
+    let rows = sqlx::query_as::<sqlx::Postgres, Row>(&format!(
         r#"
         SELECT
-          worker_model_id as model_id,
-          MAX(
-            GREATEST(
-              COALESCE(worker_last_heartbeat, 'epoch'::timestamptz),
-              COALESCE(last_health_check, 'epoch'::timestamptz),
-              COALESCE((last_reconciliation AT TIME ZONE 'UTC'), 'epoch'::timestamptz)
-            )
+          i.worker_model_id as model_id,
+          i.ip_address::text as ip_address,
+          GREATEST(
+            COALESCE(i.worker_last_heartbeat, 'epoch'::timestamptz),
+            COALESCE(i.last_health_check, 'epoch'::timestamptz),
+            COALESCE(i.last_reconciliation, 'epoch'::timestamptz)
           ) as last_seen
-        FROM instances
-        WHERE status::text = 'ready'
-          AND ip_address IS NOT NULL
-          AND (worker_status = 'ready' OR worker_status IS NULL)
-          AND worker_model_id IS NOT NULL
+        FROM instances i
+        LEFT JOIN models m ON m.model_id = i.worker_model_id
+        WHERE {routable}
+          AND i.worker_model_id IS NOT NULL
           AND GREATEST(
-              COALESCE(worker_last_heartbeat, 'epoch'::timestamptz),
-              COALESCE(last_health_check, 'epoch'::timestamptz),
-              COALESCE((last_reconciliation AT TIME ZONE 'UTC'), 'epoch'::timestamptz)
-            ) > NOW() - ($1::bigint * INTERVAL '1 second')
-        GROUP BY worker_model_id
-        ORDER BY worker_model_id
+              COALESCE(i.worker_last_heartbeat, 'epoch'::timestamptz),
+              COALESCE(i.last_health_check, 'epoch'::timestamptz),
+              COALESCE(i.last_reconciliation, 'epoch'::timestamptz)
+            ) > NOW() - (COALESCE(m.stale_window_seconds::bigint, $1::bigint) * INTERVAL '1 second')
+          AND ($2::bool OR m.public IS NOT FALSE)
+        ORDER BY i.worker_model_id
         "#,
-    )
+        routable = worker_routing::ROUTABLE_INSTANCE_SQL
+    ))
     .bind(stale)
+    .bind(api_key.is_none())
     .fetch_all(&state.db)
     .await
     .unwrap_or_default();
 
-    let data = rows
+    // Only advertise models that have at least one routable worker (usable IP).
+    let mut last_seen_by_model: std::collections::BTreeMap<String, chrono::DateTime<chrono::Utc>> =
+        std::collections::BTreeMap::new();
+    for r in rows {
+        if net::normalize_instance_ip(&r.ip_address).is_none() {
+            continue;
+        }
+        let seen = last_seen_by_model.entry(r.model_id).or_insert(r.last_seen);
+        if r.last_seen > *seen {
+            *seen = r.last_seen;
+        }
+    }
+
+    let data = last_seen_by_model
         .into_iter()
-        .map(|r| ModelObj {
-            id: r.model_id,
-            object: "model",
-            created: r.last_seen.timestamp(),
-            owned_by: "inventiv",
+        .map(|(model_id, last_seen)| OpenAiModel {
+            id: model_id,
+            object: "model".to_string(),
+            created: last_seen.timestamp(),
+            owned_by: "inventiv".to_string(),
         })
         .collect();
 
-    axum::Json(Resp {
-        object: "list",
+    axum::Json(OpenAiModelList {
+        object: "list".to_string(),
         data,
     })
 }
 
+#[utoipa::path(
+    post,
+    path = "/v1/chat/completions",
+    request_body = OpenAiChatCompletionRequest,
+    responses(
+        (status = 200, description = "Chat completion (SSE stream when stream=true)", body = OpenAiChatCompletionResponse),
+        (status = 400, description = "Invalid JSON body"),
+        (status = 401, description = "Missing or invalid session / API key"),
+        (status = 422, description = "Prompt blocked by moderation (when enabled)"),
+        (status = 429, description = "Low-priority request deferred (workers at soft concurrency cap)"),
+        (status = 502, description = "Worker unreachable"),
+        (status = 503, description = "No READY worker available for the model (or maintenance mode)")
+    )
+)]
 pub async fn openai_proxy_chat_completions(
     State(state): State<Arc<AppState>>,
     user: Option<axum::extract::Extension<auth::AuthUser>>,
+    api_key: Option<axum::extract::Extension<auth::ApiKeyPrincipal>>,
     headers: HeaderMap,
     body: Bytes,
 ) -> Response {
@@ -92,26 +234,145 @@ pub async fn openai_proxy_chat_completions(
         headers,
         body,
         user.map(|u| u.0),
+        api_key.map(|k| k.0),
     )
     .await
 }
 
+#[utoipa::path(
+    post,
+    path = "/v1/completions",
+    request_body = OpenAiCompletionRequest,
+    responses(
+        (status = 200, description = "Text completion (SSE stream when stream=true)", body = OpenAiCompletionResponse),
+        (status = 400, description = "Invalid JSON body"),
+        (status = 401, description = "Missing or invalid session / API key"),
+        (status = 422, description = "Prompt blocked by moderation (when enabled)"),
+        (status = 429, description = "Low-priority request deferred (workers at soft concurrency cap)"),
+        (status = 502, description = "Worker unreachable"),
+        (status = 503, description = "No READY worker available for the model (or maintenance mode)")
+    )
+)]
 pub async fn openai_proxy_completions(
     State(state): State<Arc<AppState>>,
     user: Option<axum::extract::Extension<auth::AuthUser>>,
+    api_key: Option<axum::extract::Extension<auth::ApiKeyPrincipal>>,
     headers: HeaderMap,
     body: Bytes,
 ) -> Response {
-    openai_proxy::proxy_to_worker(&state, "/v1/completions", headers, body, user.map(|u| u.0)).await
+    openai_proxy::proxy_to_worker(
+        &state,
+        "/v1/completions",
+        headers,
+        body,
+        user.map(|u| u.0),
+        api_key.map(|k| k.0),
+    )
+    .await
 }
 
+#[utoipa::path(
+    post,
+    path = "/v1/embeddings",
+    request_body = OpenAiEmbeddingRequest,
+    responses(
+        (status = 200, description = "Embeddings", body = OpenAiEmbeddingResponse),
+        (status = 400, description = "Invalid JSON body"),
+        (status = 401, description = "Missing or invalid session / API key"),
+        (status = 502, description = "Worker unreachable"),
+        (status = 503, description = "No READY worker available for the model (or maintenance mode)")
+    )
+)]
 pub async fn openai_proxy_embeddings(
     State(state): State<Arc<AppState>>,
     user: Option<axum::extract::Extension<auth::AuthUser>>,
+    api_key: Option<axum::extract::Extension<auth::ApiKeyPrincipal>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    openai_proxy::proxy_to_worker(
+        &state,
+        "/v1/embeddings",
+        headers,
+        body,
+        user.map(|u| u.0),
+        api_key.map(|k| k.0),
+    )
+    .await
+}
+
+/// Allowlisted passthrough to vLLM endpoints without a dedicated route. Requests whose JSON body
+/// names a `model` go through the regular proxy pipeline; others are sent to any READY worker
+/// serving a model the caller may use.
+#[utoipa::path(
+    method(get, post),
+    path = "/v1/{path}",
+    params(("path" = String, Path, description = "vLLM endpoint, e.g. `score` or `tokenize`")),
+    request_body(content = Option<serde_json::Value>, description = "Forwarded to the worker as is"),
+    responses(
+        (status = 200, description = "Worker response, relayed as is"),
+        (status = 401, description = "Missing or invalid session / API key"),
+        (status = 404, description = "Path not in OPENAI_PROXY_PASSTHROUGH_PATHS"),
+        (status = 502, description = "Worker unreachable"),
+        (status = 503, description = "No READY worker available (or maintenance mode)")
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+pub async fn openai_proxy_passthrough(
+    State(state): State<Arc<AppState>>,
+    user: Option<axum::extract::Extension<auth::AuthUser>>,
+    api_key: Option<axum::extract::Extension<auth::ApiKeyPrincipal>>,
+    method: Method,
+    Path(rest): Path<String>,
+    RawQuery(query): RawQuery,
     headers: HeaderMap,
     body: Bytes,
 ) -> Response {
-    openai_proxy::proxy_to_worker(&state, "/v1/embeddings", headers, body, user.map(|u| u.0)).await
+    let allowlist = match state.settings.passthrough_paths.get() {
+        Some(v) => v,
+        None => {
+            let v = worker_routing::passthrough_allowlist(&state.db).await;
+            state.settings.passthrough_paths.set(v.clone());
+            v
+        }
+    };
+    let Some(mut target) = worker_routing::passthrough_target(&allowlist, &rest) else {
+        return (
+            StatusCode::NOT_FOUND,
+            axum::Json(serde_json::json!({"error":"not_found","path": format!("/v1/{}", rest)})),
+        )
+            .into_response();
+    };
+    if let Some(q) = query.filter(|q| !q.is_empty()) {
+        target = format!("{}?{}", target, q);
+    }
+
+    let names_model = method == Method::POST
+        && serde_json::from_slice::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|v| v.get("model").and_then(|m| m.as_str()).map(str::to_string))
+            .is_some_and(|m| !m.trim().is_empty());
+    if names_model {
+        openai_proxy::proxy_to_worker(
+            &state,
+            &target,
+            headers,
+            body,
+            user.map(|u| u.0),
+            api_key.map(|k| k.0),
+        )
+        .await
+    } else {
+        openai_proxy::passthrough_to_any_worker(
+            &state,
+            method,
+            &target,
+            &headers,
+            body,
+            api_key.as_ref().map(|k| &k.0),
+        )
+        .await
+    }
 }
 
 // Helper function for OpenAI worker stale seconds
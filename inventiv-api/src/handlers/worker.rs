@@ -1,84 +1,137 @@
 // Worker internal route handlers
 use axum::body::{Body, Bytes};
-use axum::extract::State;
+use axum::extract::{Query, State};
 use axum::http::HeaderMap;
 use axum::response::{IntoResponse, Response};
 use axum::Json;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
 
 use crate::app::AppState;
+use inventiv_common::worker_auth::{self, WorkerAuthResult};
 
 #[derive(Deserialize)]
 struct WorkerInstanceIdPayload {
     instance_id: uuid::Uuid,
 }
 
-fn extract_bearer(headers: &HeaderMap) -> Option<String> {
-    let Some(auth) = headers.get(axum::http::header::AUTHORIZATION) else {
-        return None;
-    };
-    let Ok(auth) = auth.to_str() else {
-        return None;
-    };
-    auth.strip_prefix("Bearer ").map(|s| s.to_string())
+// --- OpenAPI schemas (payloads are validated and handled by the orchestrator) ---
+
+#[derive(Deserialize, Serialize, utoipa::ToSchema)]
+pub struct WorkerRegisterRequest {
+    pub instance_id: uuid::Uuid,
+    pub worker_id: Option<uuid::Uuid>,
+    pub model_id: Option<String>,
+    pub vllm_port: Option<i32>,
+    /// Front port (e.g. HAProxy in multi-vLLM mode); preferred over vllm_port for routing.
+    #[serde(alias = "worker_proxy_port")]
+    pub proxy_port: Option<i32>,
+    pub health_port: Option<i32>,
+    /// Worker-reported reachable IP (optional)
+    pub ip_address: Option<String>,
+    pub metadata: Option<serde_json::Value>,
 }
 
-async fn verify_worker_token_db(
-    db: &sqlx::Pool<sqlx::Postgres>,
-    instance_id: uuid::Uuid,
-    token: &str,
-) -> bool {
-    // Compare hash in DB using pgcrypto digest; avoids adding crypto deps in Rust.
-    let ok: bool = sqlx::query_scalar(
-        r#"
-        SELECT EXISTS(
-          SELECT 1
-          FROM worker_auth_tokens
-          WHERE instance_id = $1
-            AND revoked_at IS NULL
-            AND token_hash = encode(digest($2::text, 'sha256'), 'hex')
-        )
-        "#,
-    )
-    .bind(instance_id)
-    .bind(token)
-    .fetch_one(db)
-    .await
-    .unwrap_or(false);
+#[derive(Deserialize, Serialize, utoipa::ToSchema)]
+pub struct WorkerAgentInfo {
+    pub version: Option<String>,
+    pub build_date: Option<String>,
+    pub checksum: Option<String>,
+}
 
-    if ok {
-        let _ = sqlx::query(
-            "UPDATE worker_auth_tokens SET last_seen_at = NOW() WHERE instance_id = $1",
-        )
-        .bind(instance_id)
-        .execute(db)
-        .await;
-    }
+#[derive(Deserialize, Serialize, utoipa::ToSchema)]
+pub struct WorkerHeartbeatRequest {
+    pub instance_id: uuid::Uuid,
+    pub worker_id: Option<uuid::Uuid>,
+    pub status: inventiv_common::WorkerStatus,
+    pub model_id: Option<String>,
+    /// Model revision (HF commit sha) actually loaded by vLLM
+    #[serde(alias = "worker_model_revision")]
+    pub model_revision: Option<String>,
+    pub queue_depth: Option<i32>,
+    pub gpu_utilization: Option<f64>,
+    pub gpu_mem_used_mb: Option<f64>,
+    /// Worker-reported reachable IP (optional)
+    pub ip_address: Option<String>,
+    pub agent_info: Option<WorkerAgentInfo>,
+    pub metadata: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize, Serialize, utoipa::ToSchema)]
+pub struct WorkerDeregisterRequest {
+    pub instance_id: uuid::Uuid,
+    pub worker_id: Option<uuid::Uuid>,
+    /// Why the worker is leaving (e.g. "maintenance"); recorded in the action log
+    pub reason: Option<String>,
+}
+
+#[derive(Deserialize, utoipa::IntoParams)]
+pub struct WorkerConfigParams {
+    pub instance_id: uuid::Uuid,
+}
+
+#[derive(Deserialize, Serialize, utoipa::ToSchema)]
+pub struct WorkerConfigResponse {
+    pub instance_id: uuid::Uuid,
+    /// Target HF model id (None = keep the current one)
+    pub model_id: Option<String>,
+    /// Extra vLLM CLI args
+    pub vllm_args: Vec<String>,
+    /// HF token source on the control plane (e.g. `env:WORKER_HF_TOKEN`), never the token itself
+    pub hf_token_ref: Option<String>,
+    /// Bumped on every model/args change; re-apply when it differs from the last applied value
+    pub generation: i64,
+}
 
-    ok
+#[derive(Serialize, utoipa::ToSchema)]
+pub struct WorkerAckResponse {
+    /// Always "ok"
+    pub status: String,
+    /// Register only: token issued on first (bootstrap) registration
+    pub bootstrap_token: Option<String>,
+    pub bootstrap_token_prefix: Option<String>,
 }
 
 async fn verify_worker_auth_api(
     db: &sqlx::Pool<sqlx::Postgres>,
     headers: &HeaderMap,
     instance_id: uuid::Uuid,
-) -> bool {
+) -> WorkerAuthResult {
+    // A non-UTF8 Authorization header counts as malformed, not missing.
+    let header = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .map(|v| v.to_str().unwrap_or_default());
+    let token = match worker_auth::parse_bearer(header) {
+        Ok(t) => t,
+        Err(result) => return result,
+    };
+
     // Backward-compat: allow a global token (useful for early bringup).
     let expected = std::env::var("WORKER_AUTH_TOKEN").unwrap_or_default();
-    if !expected.trim().is_empty() {
-        if let Some(tok) = extract_bearer(headers) {
-            if tok.trim() == expected.trim() {
-                return true;
-            }
-        }
+    if !expected.trim().is_empty() && token == expected.trim() {
+        return WorkerAuthResult::Ok;
     }
 
-    let Some(tok) = extract_bearer(headers) else {
-        return false;
-    };
-    verify_worker_token_db(db, instance_id, &tok).await
+    let result = worker_auth::check_worker_token(db, instance_id, &token).await;
+    if result.is_ok() {
+        let _ = sqlx::query(
+            "UPDATE worker_auth_tokens SET last_seen_at = NOW() WHERE instance_id = $1",
+        )
+        .bind(instance_id)
+        .execute(db)
+        .await;
+    }
+    result
+}
+
+/// 401 with a `reason` telling the worker what to fix.
+fn worker_auth_error(result: WorkerAuthResult) -> Response {
+    (
+        axum::http::StatusCode::UNAUTHORIZED,
+        Json(json!({"error": "unauthorized", "reason": result.reason()})),
+    )
+        .into_response()
 }
 
 fn orchestrator_internal_url() -> String {
@@ -87,10 +140,22 @@ fn orchestrator_internal_url() -> String {
 }
 
 async fn proxy_post_to_orchestrator(path: &str, headers: HeaderMap, body: Bytes) -> Response {
+    proxy_to_orchestrator(reqwest::Method::POST, path, headers, Some(body)).await
+}
+
+async fn proxy_to_orchestrator(
+    method: reqwest::Method,
+    path: &str,
+    headers: HeaderMap,
+    body: Option<Bytes>,
+) -> Response {
     let base = orchestrator_internal_url();
     let url = format!("{}/{}", base, path.trim_start_matches('/'));
 
-    let mut req = reqwest::Client::new().post(url).body(body.to_vec());
+    let mut req = reqwest::Client::new().request(method, url);
+    if let Some(body) = body {
+        req = req.body(body.to_vec());
+    }
     // Forward Authorization header (worker auth token)
     if let Some(auth) = headers.get(axum::http::header::AUTHORIZATION) {
         if let Ok(s) = auth.to_str() {
@@ -134,6 +199,17 @@ async fn proxy_post_to_orchestrator(path: &str, headers: HeaderMap, body: Bytes)
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/internal/worker/register",
+    request_body = WorkerRegisterRequest,
+    responses(
+        (status = 200, description = "Worker registered", body = WorkerAckResponse),
+        (status = 400, description = "Invalid body"),
+        (status = 401, description = "Missing, malformed, revoked or unknown worker token"),
+        (status = 502, description = "Orchestrator unreachable")
+    )
+)]
 pub async fn proxy_worker_register(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
@@ -141,7 +217,10 @@ pub async fn proxy_worker_register(
 ) -> Response {
     // Bootstrap flow: allow missing token on register (orchestrator will check IP + token existence).
     // If a token IS present, we verify it here too (defense-in-depth).
-    if extract_bearer(&headers).is_some() {
+    let header = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    if worker_auth::parse_bearer(header).is_ok() {
         let parsed: WorkerInstanceIdPayload = match serde_json::from_slice(&body) {
             Ok(p) => p,
             Err(_) => {
@@ -154,18 +233,26 @@ pub async fn proxy_worker_register(
                     .into_response();
             }
         };
-        if !verify_worker_auth_api(&state.db, &headers, parsed.instance_id).await {
-            return (
-                axum::http::StatusCode::UNAUTHORIZED,
-                Json(json!({"error":"unauthorized"})),
-            )
-                .into_response();
+        let auth = verify_worker_auth_api(&state.db, &headers, parsed.instance_id).await;
+        if !auth.is_ok() {
+            return worker_auth_error(auth);
         }
     }
 
     proxy_post_to_orchestrator("/internal/worker/register", headers, body).await
 }
 
+#[utoipa::path(
+    post,
+    path = "/internal/worker/heartbeat",
+    request_body = WorkerHeartbeatRequest,
+    responses(
+        (status = 200, description = "Heartbeat recorded", body = WorkerAckResponse),
+        (status = 400, description = "Invalid body or unknown worker status"),
+        (status = 401, description = "Missing, malformed, revoked or unknown worker token"),
+        (status = 502, description = "Orchestrator unreachable")
+    )
+)]
 pub async fn proxy_worker_heartbeat(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
@@ -182,13 +269,70 @@ pub async fn proxy_worker_heartbeat(
                 .into_response();
         }
     };
-    if !verify_worker_auth_api(&state.db, &headers, parsed.instance_id).await {
-        return (
-            axum::http::StatusCode::UNAUTHORIZED,
-            Json(json!({"error":"unauthorized"})),
-        )
-            .into_response();
+    let auth = verify_worker_auth_api(&state.db, &headers, parsed.instance_id).await;
+    if !auth.is_ok() {
+        return worker_auth_error(auth);
     }
 
     proxy_post_to_orchestrator("/internal/worker/heartbeat", headers, body).await
 }
+
+#[utoipa::path(
+    post,
+    path = "/internal/worker/deregister",
+    request_body = WorkerDeregisterRequest,
+    responses(
+        (status = 200, description = "Worker marked draining (no longer routed to)", body = WorkerAckResponse),
+        (status = 400, description = "Invalid body"),
+        (status = 401, description = "Missing, malformed, revoked or unknown worker token"),
+        (status = 404, description = "Instance not found"),
+        (status = 502, description = "Orchestrator unreachable")
+    )
+)]
+pub async fn proxy_worker_deregister(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let parsed: WorkerInstanceIdPayload = match serde_json::from_slice(&body) {
+        Ok(p) => p,
+        Err(_) => {
+            return (
+                axum::http::StatusCode::BAD_REQUEST,
+                Json(json!({"error":"invalid_body","message":"missing_or_invalid_instance_id"})),
+            )
+                .into_response();
+        }
+    };
+    let auth = verify_worker_auth_api(&state.db, &headers, parsed.instance_id).await;
+    if !auth.is_ok() {
+        return worker_auth_error(auth);
+    }
+
+    proxy_post_to_orchestrator("/internal/worker/deregister", headers, body).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/internal/worker/config",
+    params(WorkerConfigParams),
+    responses(
+        (status = 200, description = "Desired worker config", body = WorkerConfigResponse),
+        (status = 401, description = "Missing, malformed, revoked or unknown worker token"),
+        (status = 404, description = "Instance not found"),
+        (status = 502, description = "Orchestrator unreachable")
+    )
+)]
+pub async fn proxy_worker_config(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(params): Query<WorkerConfigParams>,
+) -> Response {
+    let auth = verify_worker_auth_api(&state.db, &headers, params.instance_id).await;
+    if !auth.is_ok() {
+        return worker_auth_error(auth);
+    }
+
+    let path = format!("/internal/worker/config?instance_id={}", params.instance_id);
+    proxy_to_orchestrator(reqwest::Method::GET, &path, headers, None).await
+}
@@ -7,6 +7,8 @@ use axum::{
 };
 use inventiv_common::InstanceType;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -175,6 +177,207 @@ pub async fn associate_zones_to_instance_type(
     (StatusCode::OK, "OK".to_string())
 }
 
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct ZoneAvailability {
+    pub zone_id: Uuid,
+    pub is_available: bool,
+}
+
+/// Full desired set of zone associations: zones left out are removed.
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct BulkZoneAvailabilityRequest {
+    pub zones: Vec<ZoneAvailability>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ZoneAvailabilityAction {
+    Inserted,
+    Updated,
+    Removed,
+}
+
+#[derive(Serialize, Deserialize, Debug, utoipa::ToSchema)]
+pub struct ZoneAvailabilityChange {
+    pub zone_id: Uuid,
+    pub action: ZoneAvailabilityAction,
+    /// New availability (None for removed associations).
+    pub is_available: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BulkZoneAvailabilityResponse {
+    pub instance_type_id: Uuid,
+    /// Only the associations that actually changed.
+    pub changes: Vec<ZoneAvailabilityChange>,
+}
+
+fn bad_request(error: &str, message: &str) -> axum::response::Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({"error": error, "message": message})),
+    )
+        .into_response()
+}
+
+fn db_error(e: sqlx::Error) -> axum::response::Response {
+    eprintln!("Error applying instance_type_zones availability: {:?}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({"error": "db_error", "message": "Failed to update associations"})),
+    )
+        .into_response()
+}
+
+// Replace an instance type's zone availability with a desired set (diffed in one transaction)
+#[utoipa::path(
+    put,
+    path = "/instance_types/{id}/zones/availability",
+    tag = "Settings",
+    params(
+        ("id" = Uuid, Path, description = "Instance Type ID")
+    ),
+    request_body = BulkZoneAvailabilityRequest,
+    responses(
+        (status = 200, description = "Applied changes", body = BulkZoneAvailabilityResponse),
+        (status = 400, description = "Duplicate zones or zones from another provider"),
+        (status = 404, description = "Instance type not found")
+    )
+)]
+pub async fn set_instance_type_zone_availability(
+    State(state): State<Arc<AppState>>,
+    Path(instance_type_id): Path<Uuid>,
+    Json(req): Json<BulkZoneAvailabilityRequest>,
+) -> impl IntoResponse {
+    let mut seen = HashSet::new();
+    if !req.zones.iter().all(|z| seen.insert(z.zone_id)) {
+        return bad_request("duplicate_zone", "Each zone_id may appear only once");
+    }
+
+    let mut tx = match state.db.begin().await {
+        Ok(t) => t,
+        Err(e) => return db_error(e),
+    };
+
+    // Locking the instance type serializes concurrent edits of its associations.
+    let it_provider: Option<Uuid> =
+        match sqlx::query_scalar("SELECT provider_id FROM instance_types WHERE id = $1 FOR UPDATE")
+            .bind(instance_type_id)
+            .fetch_optional(&mut *tx)
+            .await
+        {
+            Ok(v) => v,
+            Err(e) => return db_error(e),
+        };
+    let Some(it_provider) = it_provider else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "instance_type_not_found", "message": "Instance type not found"})),
+        )
+            .into_response();
+    };
+
+    let zone_ids: Vec<Uuid> = req.zones.iter().map(|z| z.zone_id).collect();
+    if !zone_ids.is_empty() {
+        let ok_count: i64 = match sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM zones z
+            JOIN regions r ON r.id = z.region_id
+            WHERE z.id = ANY($1)
+              AND r.provider_id = $2
+            "#,
+        )
+        .bind(&zone_ids)
+        .bind(it_provider)
+        .fetch_one(&mut *tx)
+        .await
+        {
+            Ok(v) => v,
+            Err(e) => return db_error(e),
+        };
+        if ok_count != zone_ids.len() as i64 {
+            return bad_request(
+                "invalid_zone_ids",
+                "Zones must belong to the same provider as the instance type",
+            );
+        }
+    }
+
+    let current: HashMap<Uuid, bool> = match sqlx::query_as::<_, (Uuid, bool)>(
+        "SELECT zone_id, COALESCE(is_available, true) FROM instance_type_zones WHERE instance_type_id = $1",
+    )
+    .bind(instance_type_id)
+    .fetch_all(&mut *tx)
+    .await
+    {
+        Ok(rows) => rows.into_iter().collect(),
+        Err(e) => return db_error(e),
+    };
+
+    let mut changes = Vec::new();
+    for z in &req.zones {
+        let action = match current.get(&z.zone_id) {
+            None => ZoneAvailabilityAction::Inserted,
+            Some(&was) if was != z.is_available => ZoneAvailabilityAction::Updated,
+            Some(_) => continue,
+        };
+        let res = sqlx::query(
+            r#"
+            INSERT INTO instance_type_zones (instance_type_id, zone_id, is_available)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (instance_type_id, zone_id) DO UPDATE SET is_available = EXCLUDED.is_available
+            "#,
+        )
+        .bind(instance_type_id)
+        .bind(z.zone_id)
+        .bind(z.is_available)
+        .execute(&mut *tx)
+        .await;
+        if let Err(e) = res {
+            return db_error(e);
+        }
+        changes.push(ZoneAvailabilityChange {
+            zone_id: z.zone_id,
+            action,
+            is_available: Some(z.is_available),
+        });
+    }
+
+    let removed: Vec<Uuid> = current
+        .keys()
+        .filter(|id| !seen.contains(*id))
+        .copied()
+        .collect();
+    if !removed.is_empty() {
+        let res = sqlx::query(
+            "DELETE FROM instance_type_zones WHERE instance_type_id = $1 AND zone_id = ANY($2)",
+        )
+        .bind(instance_type_id)
+        .bind(&removed)
+        .execute(&mut *tx)
+        .await;
+        if let Err(e) = res {
+            return db_error(e);
+        }
+        changes.extend(removed.into_iter().map(|zone_id| ZoneAvailabilityChange {
+            zone_id,
+            action: ZoneAvailabilityAction::Removed,
+            is_available: None,
+        }));
+    }
+
+    if let Err(e) = tx.commit().await {
+        return db_error(e);
+    }
+
+    Json(BulkZoneAvailabilityResponse {
+        instance_type_id,
+        changes,
+    })
+    .into_response()
+}
+
 // Get instance types available in a specific zone (for dashboard filtering)
 #[utoipa::path(
     get,
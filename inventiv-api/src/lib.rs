@@ -8,26 +8,40 @@ pub mod app;
 pub mod auth;
 pub mod auth_endpoints;
 pub mod bootstrap_admin;
+pub mod catalog_transfer;
 pub mod chat;
 pub mod config;
 pub mod email;
 pub mod finops;
 pub mod handlers;
 pub mod instance_type_zones;
+pub mod maintenance;
 pub mod metrics;
+pub mod moderation;
 pub mod openai_proxy;
 pub mod organizations;
 pub mod password_reset;
+pub mod pricing_overrides;
 pub mod progress;
+pub mod provider_cache;
 pub mod provider_settings;
+pub mod proxy_traces;
+pub mod rate_limit;
 pub mod rbac;
+pub mod reconciliation_health;
 pub mod routes;
+pub mod session_affinity;
 pub mod settings;
+pub mod settings_cache;
 pub mod setup;
 pub mod simple_logger;
+pub mod single_flight;
+pub mod sort;
 pub mod users_endpoint;
 pub mod version;
+pub mod volume_drift;
 pub mod workbench;
+pub mod worker_breaker;
 pub mod worker_routing;
 
 // Re-export commonly used types
@@ -16,22 +16,36 @@ mod api_keys;
 mod auth;
 mod auth_endpoints;
 mod bootstrap_admin;
+mod catalog_transfer;
 mod chat;
 mod email;
 mod finops;
 mod instance_type_zones;
+mod maintenance;
 mod metrics;
+mod moderation;
 mod openai_proxy;
 mod organizations;
 mod password_reset;
+mod pricing_overrides;
 mod progress;
+mod provider_cache;
 mod provider_settings;
+mod proxy_traces;
+mod rate_limit;
 mod rbac;
+mod reconciliation_health;
+mod session_affinity;
 mod settings;
+mod settings_cache;
 mod simple_logger;
+mod single_flight;
+mod sort;
 mod users_endpoint;
 mod version;
+mod volume_drift;
 mod workbench;
+mod worker_breaker;
 mod worker_routing;
 
 use app::AppState;
@@ -68,6 +82,9 @@ async fn main() {
     bootstrap_admin::ensure_default_admin(&pool).await;
     bootstrap_admin::ensure_default_organization(&pool).await;
 
+    // Background alert when orchestrator reconciliation stops updating instances
+    tokio::spawn(reconciliation_health::run_monitor(pool.clone()));
+
     // Create application state
     let state = AppState::new(client, pool);
 
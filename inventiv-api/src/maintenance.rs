@@ -0,0 +1,75 @@
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::{header, Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde_json::json;
+use sqlx::{Pool, Postgres};
+use std::sync::Arc;
+
+use crate::AppState;
+
+const DEFAULT_RETRY_AFTER_SECONDS: i64 = 60;
+
+/// Maintenance flag (global_settings.MAINTENANCE_MODE), falling back to env `MAINTENANCE_MODE`.
+/// Toggling takes effect without restart; the middleware caches it briefly (see `SettingsCache`).
+pub async fn maintenance_mode_enabled(db: &Pool<Postgres>) -> bool {
+    let from_db: Option<bool> =
+        sqlx::query_scalar("SELECT value_bool FROM global_settings WHERE key = 'MAINTENANCE_MODE'")
+            .fetch_optional(db)
+            .await
+            .ok()
+            .flatten()
+            .flatten();
+    if let Some(v) = from_db {
+        return v;
+    }
+    std::env::var("MAINTENANCE_MODE")
+        .ok()
+        .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+async fn maintenance_retry_after_seconds(db: &Pool<Postgres>) -> i64 {
+    sqlx::query_scalar::<_, i64>(
+        "SELECT value_int FROM global_settings WHERE key = 'MAINTENANCE_RETRY_AFTER_SECONDS'",
+    )
+    .fetch_optional(db)
+    .await
+    .ok()
+    .flatten()
+    .filter(|v| *v > 0)
+    .unwrap_or(DEFAULT_RETRY_AFTER_SECONDS)
+}
+
+/// Middleware for inference routes (/v1/*): reject new requests while in maintenance.
+/// Requests already being proxied are not affected, so workers drain gracefully.
+pub async fn reject_during_maintenance(
+    State(state): State<Arc<AppState>>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let enabled = match state.settings.maintenance_mode.get() {
+        Some(v) => v,
+        None => {
+            let v = maintenance_mode_enabled(&state.db).await;
+            state.settings.maintenance_mode.set(v);
+            v
+        }
+    };
+    if !enabled {
+        return next.run(req).await;
+    }
+
+    let retry_after = maintenance_retry_after_seconds(&state.db).await;
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        [(header::RETRY_AFTER, retry_after.to_string())],
+        Json(json!({
+            "error": "maintenance",
+            "message": "Inference is temporarily unavailable (maintenance). Retry later."
+        })),
+    )
+        .into_response()
+}
@@ -32,6 +32,29 @@ pub async fn update_instance_request_metrics(
     .await;
 }
 
+/// [`update_instance_request_metrics`] on a spawned task, so the proxied response never waits on it.
+pub fn spawn_instance_request_metrics(
+    db: &Pool<Postgres>,
+    instance_id: Uuid,
+    success: bool,
+    input_tokens: Option<i32>,
+    output_tokens: Option<i32>,
+    total_tokens: Option<i32>,
+) {
+    let db = db.clone();
+    tokio::spawn(async move {
+        update_instance_request_metrics(
+            &db,
+            instance_id,
+            success,
+            input_tokens,
+            output_tokens,
+            total_tokens,
+        )
+        .await;
+    });
+}
+
 /// Extract token usage from OpenAI API response JSON
 pub fn extract_token_usage(
     response_json: &serde_json::Value,
@@ -0,0 +1,181 @@
+use serde_json::{json, Value};
+use std::time::Duration;
+
+const DEFAULT_TIMEOUT_MS: u64 = 2000;
+
+/// Optional pre-forward prompt screening for inference routes (off unless `MODERATION_ENABLED=1`).
+///
+/// The prompt is POSTed as `{"input": "..."}` to `MODERATION_ENDPOINT`; a response with
+/// `flagged: true` (top level or in any `results[]` entry, OpenAI moderation shape) blocks the
+/// request. When the endpoint is unreachable or times out, `MODERATION_FAIL_CLOSED=1` rejects the
+/// request; otherwise it is forwarded (fail-open).
+#[derive(Debug, Clone)]
+pub struct ModerationConfig {
+    pub endpoint: String,
+    pub timeout: Duration,
+    pub fail_closed: bool,
+}
+
+fn env_flag(key: &str) -> bool {
+    std::env::var(key)
+        .ok()
+        .map(|v| {
+            matches!(
+                v.trim().to_ascii_lowercase().as_str(),
+                "1" | "true" | "yes" | "on"
+            )
+        })
+        .unwrap_or(false)
+}
+
+impl ModerationConfig {
+    /// None when moderation is disabled or no endpoint is configured.
+    pub fn from_env() -> Option<Self> {
+        if !env_flag("MODERATION_ENABLED") {
+            return None;
+        }
+        let endpoint = std::env::var("MODERATION_ENDPOINT")
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+        let Some(endpoint) = endpoint else {
+            eprintln!("⚠️ [moderation] MODERATION_ENABLED is set but MODERATION_ENDPOINT is empty; moderation disabled");
+            return None;
+        };
+        let timeout_ms = std::env::var("MODERATION_TIMEOUT_MS")
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(DEFAULT_TIMEOUT_MS);
+        Some(Self {
+            endpoint,
+            timeout: Duration::from_millis(timeout_ms),
+            fail_closed: env_flag("MODERATION_FAIL_CLOSED"),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModerationVerdict {
+    Allowed,
+    Blocked,
+    /// Moderation endpoint failed and the policy is fail-closed.
+    Unavailable,
+}
+
+/// Text to screen: chat `messages[].content` (string or text parts) and completion `prompt`.
+pub fn extract_prompt_text(body: &Value) -> Option<String> {
+    let mut parts: Vec<&str> = Vec::new();
+    if let Some(messages) = body.get("messages").and_then(|m| m.as_array()) {
+        for msg in messages {
+            match msg.get("content") {
+                Some(Value::String(s)) => parts.push(s),
+                Some(Value::Array(items)) => parts.extend(
+                    items
+                        .iter()
+                        .filter_map(|p| p.get("text").and_then(|t| t.as_str())),
+                ),
+                _ => {}
+            }
+        }
+    }
+    match body.get("prompt") {
+        Some(Value::String(s)) => parts.push(s),
+        Some(Value::Array(items)) => parts.extend(items.iter().filter_map(|p| p.as_str())),
+        _ => {}
+    }
+    let text = parts
+        .into_iter()
+        .filter(|s| !s.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    (!text.is_empty()).then_some(text)
+}
+
+fn response_is_flagged(v: &Value) -> bool {
+    v.get("flagged").and_then(|f| f.as_bool()).unwrap_or(false)
+        || v.get("results")
+            .and_then(|r| r.as_array())
+            .is_some_and(|results| {
+                results
+                    .iter()
+                    .any(|r| r.get("flagged").and_then(|f| f.as_bool()).unwrap_or(false))
+            })
+}
+
+async fn query_endpoint(
+    client: &reqwest::Client,
+    config: &ModerationConfig,
+    text: &str,
+) -> Result<bool, String> {
+    let resp = client
+        .post(&config.endpoint)
+        .timeout(config.timeout)
+        .json(&json!({ "input": text }))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        return Err(format!("moderation endpoint returned {}", resp.status()));
+    }
+    let v: Value = resp.json().await.map_err(|e| e.to_string())?;
+    Ok(response_is_flagged(&v))
+}
+
+/// Screen a request body. Bodies without prompt text are always allowed.
+pub async fn moderate_request(
+    client: &reqwest::Client,
+    config: &ModerationConfig,
+    body: &Value,
+) -> ModerationVerdict {
+    let Some(text) = extract_prompt_text(body) else {
+        return ModerationVerdict::Allowed;
+    };
+    match query_endpoint(client, config, &text).await {
+        Ok(true) => ModerationVerdict::Blocked,
+        Ok(false) => ModerationVerdict::Allowed,
+        Err(e) if config.fail_closed => {
+            eprintln!("❌ [moderation] check failed (fail-closed): {}", e);
+            ModerationVerdict::Unavailable
+        }
+        Err(e) => {
+            eprintln!("⚠️ [moderation] check failed (fail-open): {}", e);
+            ModerationVerdict::Allowed
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prompt_text_is_collected_from_messages_and_prompt() {
+        let chat = json!({"messages": [
+            {"role": "system", "content": "be nice"},
+            {"role": "user", "content": [{"type": "text", "text": "hello"}, {"type": "image_url"}]}
+        ]});
+        assert_eq!(
+            extract_prompt_text(&chat).as_deref(),
+            Some("be nice\nhello")
+        );
+        assert_eq!(
+            extract_prompt_text(&json!({"prompt": ["a", "b"]})).as_deref(),
+            Some("a\nb")
+        );
+        assert_eq!(extract_prompt_text(&json!({"messages": []})), None);
+        assert_eq!(extract_prompt_text(&json!({"input": "embed me"})), None);
+    }
+
+    #[test]
+    fn flagged_responses_are_detected() {
+        assert!(response_is_flagged(&json!({"flagged": true})));
+        assert!(response_is_flagged(
+            &json!({"results": [{"flagged": false}, {"flagged": true}]})
+        ));
+        assert!(!response_is_flagged(
+            &json!({"results": [{"flagged": false}]})
+        ));
+        assert!(!response_is_flagged(&json!({})));
+    }
+}
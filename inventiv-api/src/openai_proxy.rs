@@ -10,10 +10,344 @@ use uuid::Uuid;
 
 use crate::auth;
 use crate::metrics;
+use crate::moderation;
+use crate::proxy_traces;
+use crate::rate_limit;
+use crate::session_affinity::AffinityTracker;
 use crate::simple_logger;
+use crate::single_flight::SingleFlight;
+use crate::worker_breaker::WorkerBreaker;
 use crate::worker_routing;
 use crate::AppState;
 
+/// Upstream clients shared by all proxied requests, so connections (and keep-alive) are reused
+/// instead of paying a new TCP handshake per request. Total timeouts are set per request; the
+/// connect timeout is a client setting, so the pools are rebuilt when it changes.
+#[derive(Clone)]
+pub struct ProxyClients {
+    inner: Arc<std::sync::RwLock<ClientPair>>,
+}
+
+struct ClientPair {
+    connect_timeout: std::time::Duration,
+    streaming: reqwest::Client,
+    buffered: reqwest::Client,
+}
+
+impl ClientPair {
+    fn build(connect_timeout: std::time::Duration) -> Self {
+        let builder = || {
+            reqwest::Client::builder()
+                .connect_timeout(connect_timeout)
+                .tcp_keepalive(std::time::Duration::from_secs(60))
+                .pool_idle_timeout(std::time::Duration::from_secs(90))
+                .read_timeout(std::time::Duration::from_secs(300)) // 5 minutes for reading
+                // Advertise Accept-Encoding: gzip to workers; responses are decoded transparently.
+                .gzip(true)
+        };
+        Self {
+            connect_timeout,
+            streaming: builder()
+                .build()
+                .expect("failed to build streaming proxy client"),
+            buffered: builder().build().expect("failed to build proxy client"),
+        }
+    }
+}
+
+impl ProxyClients {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(std::sync::RwLock::new(ClientPair::build(
+                connect_timeout_env().unwrap_or(DEFAULT_CONNECT_TIMEOUT),
+            ))),
+        }
+    }
+
+    /// Client for a request (SSE streams and buffered requests use separate pools).
+    pub fn client(&self, stream: bool) -> reqwest::Client {
+        let pair = self.inner.read().unwrap_or_else(|e| e.into_inner());
+        if stream {
+            pair.streaming.clone()
+        } else {
+            pair.buffered.clone()
+        }
+    }
+
+    /// Client for a request with `connect_timeout` applied (pools are rebuilt only on change).
+    pub fn client_with_connect_timeout(
+        &self,
+        stream: bool,
+        connect_timeout: std::time::Duration,
+    ) -> reqwest::Client {
+        let current = self
+            .inner
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .connect_timeout;
+        if current != connect_timeout {
+            let mut pair = self.inner.write().unwrap_or_else(|e| e.into_inner());
+            if pair.connect_timeout != connect_timeout {
+                *pair = ClientPair::build(connect_timeout);
+            }
+        }
+        self.client(stream)
+    }
+
+    /// Total request timeout applied at request level. Buffered embeddings get a short timeout
+    /// (`OPENAI_EMBEDDINGS_TIMEOUT_SECONDS`, default 10s) so a stuck worker surfaces quickly; other
+    /// buffered requests use `OPENAI_COMPLETIONS_TIMEOUT_SECONDS` (default 60s).
+    pub fn request_timeout(path: &str, stream: bool) -> std::time::Duration {
+        if stream {
+            return std::time::Duration::from_secs(3600);
+        }
+        let (key, default) = if path.trim_end_matches('/') == "/v1/embeddings" {
+            (
+                "OPENAI_EMBEDDINGS_TIMEOUT_SECONDS",
+                DEFAULT_EMBEDDINGS_TIMEOUT_SECONDS,
+            )
+        } else {
+            (
+                "OPENAI_COMPLETIONS_TIMEOUT_SECONDS",
+                DEFAULT_COMPLETIONS_TIMEOUT_SECONDS,
+            )
+        };
+        let secs = std::env::var(key)
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(default);
+        std::time::Duration::from_secs(secs)
+    }
+}
+
+const DEFAULT_EMBEDDINGS_TIMEOUT_SECONDS: u64 = 10;
+const DEFAULT_COMPLETIONS_TIMEOUT_SECONDS: u64 = 60;
+
+const DEFAULT_CONNECT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Failed-over attempts after a connect failure, on top of the first worker.
+const MAX_CONNECT_FAILOVERS: usize = 2;
+
+fn connect_timeout_env() -> Option<std::time::Duration> {
+    std::env::var("OPENAI_PROXY_CONNECT_TIMEOUT_MS")
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .filter(|v| *v > 0)
+        .map(std::time::Duration::from_millis)
+}
+
+/// Upstream connect timeout: global setting `OPENAI_PROXY_CONNECT_TIMEOUT_MS` -> env -> 30s.
+async fn connect_timeout_db(db: &sqlx::Pool<sqlx::Postgres>) -> std::time::Duration {
+    let from_db: Option<i64> = sqlx::query_scalar(
+        "SELECT value_int FROM global_settings WHERE key = 'OPENAI_PROXY_CONNECT_TIMEOUT_MS'",
+    )
+    .fetch_optional(db)
+    .await
+    .ok()
+    .flatten();
+    if let Some(ms) = from_db.filter(|v| *v > 0) {
+        return std::time::Duration::from_millis(ms.clamp(50, 60_000) as u64);
+    }
+    connect_timeout_env().unwrap_or(DEFAULT_CONNECT_TIMEOUT)
+}
+
+/// `connect_timeout_db`, served from the settings cache on the hot path.
+async fn connect_timeout(state: &AppState) -> std::time::Duration {
+    if let Some(v) = state.settings.connect_timeout.get() {
+        return v;
+    }
+    let v = connect_timeout_db(&state.db).await;
+    state.settings.connect_timeout.set(v);
+    v
+}
+
+impl Default for ProxyClients {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decoded request bodies larger than this are rejected (guards against gzip bombs).
+const DEFAULT_MAX_DECOMPRESSED_BODY_BYTES: usize = 64 * 1024 * 1024;
+
+fn max_decompressed_body_bytes() -> usize {
+    std::env::var("OPENAI_PROXY_MAX_DECOMPRESSED_BYTES")
+        .ok()
+        .and_then(|s| s.trim().parse::<usize>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_MAX_DECOMPRESSED_BODY_BYTES)
+}
+
+/// When true, gzip request bodies are forwarded to the worker as received (with
+/// `Content-Encoding: gzip`); otherwise they are re-forwarded decoded (default).
+fn forward_gzip_to_worker() -> bool {
+    std::env::var("OPENAI_PROXY_FORWARD_GZIP")
+        .ok()
+        .map(|v| {
+            matches!(
+                v.trim().to_ascii_lowercase().as_str(),
+                "1" | "true" | "yes" | "on"
+            )
+        })
+        .unwrap_or(false)
+}
+
+/// When true, proxied responses carry `X-Served-By: <instance_id>` (debugging / client-side
+/// stickiness). Off by default so deployments don't expose instance ids to clients.
+fn served_by_header_enabled() -> bool {
+    std::env::var("OPENAI_PROXY_SERVED_BY_HEADER")
+        .ok()
+        .map(|v| {
+            matches!(
+                v.trim().to_ascii_lowercase().as_str(),
+                "1" | "true" | "yes" | "on"
+            )
+        })
+        .unwrap_or(false)
+}
+
+/// Opt-in response sanitizer (`OPENAI_PROXY_SANITIZE_RESPONSES`): keeps worker-internal details
+/// (local model paths, routing headers) away from clients.
+struct ResponseSanitizer {
+    /// Dropped from relayed responses (`OPENAI_PROXY_STRIP_RESPONSE_HEADERS`, comma-separated).
+    strip_headers: Vec<axum::http::HeaderName>,
+}
+
+impl ResponseSanitizer {
+    fn from_env() -> Option<Self> {
+        let enabled = std::env::var("OPENAI_PROXY_SANITIZE_RESPONSES")
+            .ok()
+            .map(|v| {
+                matches!(
+                    v.trim().to_ascii_lowercase().as_str(),
+                    "1" | "true" | "yes" | "on"
+                )
+            })
+            .unwrap_or(false);
+        if !enabled {
+            return None;
+        }
+        let strip_headers = std::env::var("OPENAI_PROXY_STRIP_RESPONSE_HEADERS")
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|h| axum::http::HeaderName::try_from(h.trim()).ok())
+            .collect();
+        Some(Self { strip_headers })
+    }
+
+    fn strip(&self, headers: &mut axum::http::HeaderMap) {
+        for h in &self.strip_headers {
+            headers.remove(h);
+        }
+    }
+}
+
+/// Rewrite a JSON response's top-level `model` (e.g. a worker-local path) to the client-facing id.
+/// Returns `None` when the body is not a JSON object with a different `model`.
+fn rewrite_response_model(body: &[u8], model: &str) -> Option<Bytes> {
+    let mut v: serde_json::Value = serde_json::from_slice(body).ok()?;
+    let current = v.get("model")?.as_str()?;
+    if current == model {
+        return None;
+    }
+    v["model"] = json!(model);
+    serde_json::to_vec(&v).ok().map(Bytes::from)
+}
+
+/// Whether the request body is gzip-encoded. Other (non-identity) encodings are rejected.
+fn request_is_gzip(headers: &HeaderMap) -> Result<bool, (StatusCode, Json<serde_json::Value>)> {
+    let Some(raw) = headers
+        .get(axum::http::header::CONTENT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+    else {
+        return Ok(false);
+    };
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "" | "identity" => Ok(false),
+        "gzip" | "x-gzip" => Ok(true),
+        other => Err((
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Json(json!({
+                "error": "unsupported_content_encoding",
+                "message": format!("unsupported Content-Encoding '{}' (expected gzip)", other)
+            })),
+        )),
+    }
+}
+
+/// Fraction of successful proxied requests recorded in `action_logs` when
+/// `PROXY_LOG_SUCCESS_SAMPLE_RATE` is unset: every request, sampling is opt-in.
+const DEFAULT_SUCCESS_LOG_SAMPLE_RATE: f64 = 1.0;
+
+fn success_log_sample_rate() -> f64 {
+    std::env::var("PROXY_LOG_SUCCESS_SAMPLE_RATE")
+        .ok()
+        .and_then(|s| s.trim().parse::<f64>().ok())
+        .filter(|v| v.is_finite())
+        .map(|v| v.clamp(0.0, 1.0))
+        .unwrap_or(DEFAULT_SUCCESS_LOG_SAMPLE_RATE)
+}
+
+const DEFAULT_LOW_PRIORITY_MAX_WAIT_MS: u64 = 2000;
+
+/// How long a low-priority request waits for a worker below its soft cap before a 429.
+fn low_priority_max_wait() -> std::time::Duration {
+    let ms = std::env::var("PROXY_LOW_PRIORITY_MAX_WAIT_MS")
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_LOW_PRIORITY_MAX_WAIT_MS);
+    std::time::Duration::from_millis(ms)
+}
+
+/// Record a proxied request in `action_logs`.
+/// Failures are always logged; successes can be sampled (`PROXY_LOG_SUCCESS_SAMPLE_RATE`) so high
+/// QPS does not flood the table (runtime counters and instance metrics are still updated for every
+/// request).
+async fn log_proxy_request(
+    db: &sqlx::Pool<sqlx::Postgres>,
+    instance_id: Option<Uuid>,
+    error_code: Option<&str>,
+    metadata: serde_json::Value,
+) {
+    if error_code.is_none() {
+        let rate = success_log_sample_rate();
+        if rate <= 0.0 || (rate < 1.0 && rand::random::<f64>() >= rate) {
+            return;
+        }
+    }
+    let status = if error_code.is_some() {
+        "failed"
+    } else {
+        "success"
+    };
+    let _ = simple_logger::log_action_with_metadata(
+        db,
+        "OPENAI_PROXY",
+        status,
+        instance_id,
+        error_code,
+        Some(metadata),
+    )
+    .await;
+}
+
+/// Decode a gzip body, failing once the output exceeds `limit` bytes.
+pub fn gunzip_body(body: &[u8], limit: usize) -> std::io::Result<Bytes> {
+    use std::io::Read;
+    let mut out = Vec::new();
+    flate2::read::GzDecoder::new(body)
+        .take(limit as u64 + 1)
+        .read_to_end(&mut out)?;
+    if out.len() > limit {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "decompressed body exceeds limit",
+        ));
+    }
+    Ok(Bytes::from(out))
+}
+
 /// Proxy OpenAI-compatible requests to workers
 pub async fn proxy_to_worker(
     state: &Arc<AppState>,
@@ -21,6 +355,7 @@ pub async fn proxy_to_worker(
     headers: HeaderMap,
     body: Bytes,
     user: Option<auth::AuthUser>,
+    api_key: Option<auth::ApiKeyPrincipal>,
 ) -> Response {
     // Generate correlation ID for end-to-end tracing
     let correlation_id = uuid::Uuid::new_v4().to_string();
@@ -31,7 +366,42 @@ pub async fn proxy_to_worker(
         body.len()
     );
 
-    let v: serde_json::Value = match serde_json::from_slice(&body) {
+    // gzip bodies are decoded for routing (model/stream); the worker gets the decoded body unless
+    // OPENAI_PROXY_FORWARD_GZIP is set.
+    let gzip = match request_is_gzip(&headers) {
+        Ok(g) => g,
+        Err(e) => return e.into_response(),
+    };
+    let decoded = if gzip {
+        match gunzip_body(&body, max_decompressed_body_bytes()) {
+            Ok(d) => {
+                eprintln!(
+                    "[OPENAI_PROXY] [{}] GZIP_BODY: compressed={}, decoded={}",
+                    correlation_id,
+                    body.len(),
+                    d.len()
+                );
+                d
+            }
+            Err(e) => {
+                eprintln!(
+                    "[OPENAI_PROXY] [{}] ERROR: Invalid gzip body: {}",
+                    correlation_id, e
+                );
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({"error":"invalid_gzip_body","message": e.to_string()})),
+                )
+                    .into_response();
+            }
+        }
+    } else {
+        body.clone()
+    };
+    let mut forward_gzip = gzip && forward_gzip_to_worker();
+    let mut body = if forward_gzip { body } else { decoded.clone() };
+
+    let mut v: serde_json::Value = match serde_json::from_slice(&decoded) {
         Ok(v) => v,
         Err(e) => {
             eprintln!(
@@ -45,223 +415,907 @@ pub async fn proxy_to_worker(
                 .into_response();
         }
     };
-    let requested_model = v.get("model").and_then(|m| m.as_str());
+    let requested_model = v.get("model").and_then(|m| m.as_str()).map(str::to_string);
     eprintln!(
         "[OPENAI_PROXY] [{}] REQUEST: model={:?}, stream={}",
         correlation_id,
-        requested_model,
+        requested_model.as_deref(),
         v.get("stream").and_then(|b| b.as_bool()).unwrap_or(false)
     );
 
-    let model_id =
-        match worker_routing::resolve_openai_model_id(&state.db, requested_model, user.as_ref())
-            .await
-        {
-            Ok(m) => {
+    // Optional policy screening of the prompt, before any routing or worker call.
+    if let Some(config) = moderation::ModerationConfig::from_env() {
+        let client = state.proxy_clients.client(false);
+        match moderation::moderate_request(&client, &config, &v).await {
+            moderation::ModerationVerdict::Allowed => {}
+            moderation::ModerationVerdict::Blocked => {
                 eprintln!(
-                    "[OPENAI_PROXY] [{}] MODEL_RESOLVED: model_id={}",
-                    correlation_id, m
+                    "[OPENAI_PROXY] [{}] BLOCKED: prompt flagged by moderation",
+                    correlation_id
                 );
-                m
+                log_proxy_request(
+                    &state.db,
+                    None,
+                    Some("content_blocked"),
+                    json!({"model": requested_model, "correlation_id": correlation_id}),
+                )
+                .await;
+                return (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    Json(json!({
+                        "error": "content_blocked",
+                        "message": "Prompt rejected by the moderation policy"
+                    })),
+                )
+                    .into_response();
             }
-            Err(e) => {
+            moderation::ModerationVerdict::Unavailable => {
+                log_proxy_request(
+                    &state.db,
+                    None,
+                    Some("moderation_unavailable"),
+                    json!({"model": requested_model, "correlation_id": correlation_id}),
+                )
+                .await;
+                return (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    Json(json!({
+                        "error": "moderation_unavailable",
+                        "message": "Moderation check failed"
+                    })),
+                )
+                    .into_response();
+            }
+        }
+    }
+
+    // Denylisted request fields (global setting): stripped before forwarding, or rejected.
+    if let Some(filter) = worker_routing::param_filter(&state.db).await {
+        match filter.apply(&mut v) {
+            Ok(false) => {}
+            Ok(true) => {
                 eprintln!(
-                    "[OPENAI_PROXY] [{}] ERROR: Model resolution failed",
-                    correlation_id
+                    "[OPENAI_PROXY] [{}] PARAMS_STRIPPED: denylist={:?}",
+                    correlation_id, filter.denylist
                 );
-                return e.into_response();
+                body = Bytes::from(serde_json::to_vec(&v).unwrap_or_default());
+                forward_gzip = false;
             }
-        };
+            Err(field) => {
+                eprintln!(
+                    "[OPENAI_PROXY] [{}] PARAM_REJECTED: field={}",
+                    correlation_id, field
+                );
+                return (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    Json(json!({
+                        "error": "unsupported_parameter",
+                        "message": format!("Request parameter '{}' is not supported", field),
+                        "param": field
+                    })),
+                )
+                    .into_response();
+            }
+        }
+    }
+
+    let mut model_id = match worker_routing::resolve_openai_model_id(
+        &state.db,
+        requested_model.as_deref(),
+        user.as_ref(),
+    )
+    .await
+    {
+        Ok(m) => {
+            eprintln!(
+                "[OPENAI_PROXY] [{}] MODEL_RESOLVED: model_id={}",
+                correlation_id, m
+            );
+            m
+        }
+        Err(e) => {
+            eprintln!(
+                "[OPENAI_PROXY] [{}] ERROR: Model resolution failed",
+                correlation_id
+            );
+            return e.into_response();
+        }
+    };
+    // Non-public models are only reachable by API keys scoped to them (not found otherwise).
+    if !worker_routing::model_access_allowed(&state.db, &model_id, api_key.as_ref()).await {
+        eprintln!(
+            "[OPENAI_PROXY] [{}] ERROR: Model not allowed for API key, model_id={}",
+            correlation_id, model_id
+        );
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error":"model_not_found", "model": model_id})),
+        )
+            .into_response();
+    }
     let stream = v.get("stream").and_then(|b| b.as_bool()).unwrap_or(false);
 
-    // Sticky key: user-provided; forwarded to worker-local HAProxy to keep affinity in multi-vLLM mode.
-    let sticky = worker_routing::header_value(&headers, "X-Inventiv-Session");
+    // Deprecated models keep being served; clients are warned via header (+ error metadata).
+    let deprecation = worker_routing::model_deprecation(&state.db, &model_id).await;
+    if let Some(d) = deprecation.as_ref() {
+        eprintln!(
+            "[OPENAI_PROXY] [{}] MODEL_DEPRECATED: model_id={}, replacement={:?}",
+            correlation_id, model_id, d.replacement
+        );
+    }
+
+    // Open streams per API key (api_keys.max_concurrent_streams); the slot is released when the
+    // response body is dropped, i.e. on completion or client disconnect.
+    let stream_limit = api_key.as_ref().filter(|_| stream).and_then(|k| {
+        k.max_concurrent_streams
+            .and_then(|l| u32::try_from(l).ok())
+            .filter(|l| *l > 0)
+            .map(|l| (k.api_key_id, l))
+    });
+    let stream_slot = match stream_limit {
+        Some((api_key_id, limit)) => {
+            match rate_limit::acquire_stream_slot(&state.redis_client, api_key_id, limit).await {
+                Ok(slot) => slot,
+                Err(e) => {
+                    eprintln!(
+                        "[OPENAI_PROXY] [{}] ERROR: Too many concurrent streams for api_key_id={}, limit={}",
+                        correlation_id, api_key_id, limit
+                    );
+                    return with_deprecation_header(e.into_response(), deprecation.as_ref());
+                }
+            }
+        }
+        None => None,
+    };
+
+    // Sticky key: user-provided, otherwise generated (stable per API key / user session).
+    // Used for instance selection, forwarded to worker-local HAProxy to keep affinity in multi-vLLM
+    // mode, and echoed back so cooperative clients can reuse it. Anonymous callers without the
+    // header get a random key that does not pin: the least-loaded worker is used.
+    let (sticky, sticky_generated) =
+        worker_routing::resolve_sticky_session(&headers, api_key.as_ref(), user.as_ref());
+    if sticky_generated {
+        eprintln!(
+            "[OPENAI_PROXY] [{}] STICKY_SESSION_GENERATED: {}",
+            correlation_id, sticky
+        );
+    }
+    let affinity_key = worker_routing::session_affinity_key(
+        &sticky,
+        sticky_generated,
+        api_key.as_ref(),
+        user.as_ref(),
+    );
 
-    let Some((instance_id, base_url)) =
-        worker_routing::select_ready_worker_for_model(&state.db, &model_id, sticky.as_deref())
+    // Priority routing: high prefers the least-loaded worker, low respects the soft cap.
+    let routing = worker_routing::PriorityRouting {
+        redis: &state.redis_client,
+        priority: worker_routing::RequestPriority::from_headers(&headers),
+        soft_cap: worker_routing::worker_soft_concurrency_cap(),
+    };
+    // Workers whose breaker is open (recent connect failure) are skipped.
+    let tripped = state.worker_breaker.open_instances();
+    let mut selected = worker_routing::select_ready_worker_for_model_excluding(
+        &state.db,
+        &model_id,
+        affinity_key.as_deref(),
+        Some(&routing),
+        &tripped,
+    )
+    .await;
+    let mut deferred = false;
+    if selected.is_none()
+        && routing.priority == worker_routing::RequestPriority::Low
+        && worker_routing::select_ready_worker_for_model(&state.db, &model_id, None, None)
             .await
-    else {
+            .is_some()
+    {
+        // Workers exist but are at their soft cap: queue briefly, then give up.
+        eprintln!(
+            "[OPENAI_PROXY] [{}] LOW_PRIORITY_DEFERRED: model_id={}",
+            correlation_id, model_id
+        );
+        let deadline = std::time::Instant::now() + low_priority_max_wait();
+        while selected.is_none() && std::time::Instant::now() < deadline {
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            selected = worker_routing::select_ready_worker_for_model_excluding(
+                &state.db,
+                &model_id,
+                affinity_key.as_deref(),
+                Some(&routing),
+                &tripped,
+            )
+            .await;
+        }
+        deferred = selected.is_none();
+    }
+    if deferred {
+        log_proxy_request(
+            &state.db,
+            None,
+            Some("worker_busy"),
+            json!({"model": model_id, "priority": routing.priority.as_str(), "correlation_id": correlation_id}),
+        )
+        .await;
+        let mut resp = error_response(
+            StatusCode::TOO_MANY_REQUESTS,
+            json!({
+                "error": "worker_busy",
+                "message": "Workers are at their soft concurrency cap; low-priority request deferred",
+                "model": model_id
+            }),
+            deprecation.as_ref(),
+        );
+        resp.headers_mut().insert(
+            axum::http::header::RETRY_AFTER,
+            axum::http::HeaderValue::from_static("1"),
+        );
+        return resp;
+    }
+
+    // Opt-in: no capacity for the requested model -> route to its configured fallback model.
+    let mut served_fallback = false;
+    if selected.is_none() && worker_routing::model_fallback_allowed(&state.db, &headers).await {
+        if let Some(fallback) = worker_routing::fallback_model(&state.db, &model_id).await {
+            // A scoped / non-public fallback is only used if the caller may call it directly.
+            if worker_routing::model_access_allowed(&state.db, &fallback, api_key.as_ref()).await {
+                selected = worker_routing::select_ready_worker_for_model_excluding(
+                    &state.db,
+                    &fallback,
+                    affinity_key.as_deref(),
+                    Some(&routing),
+                    &tripped,
+                )
+                .await;
+            }
+            if selected.is_some() {
+                eprintln!(
+                    "[OPENAI_PROXY] [{}] MODEL_FALLBACK: requested={}, served={}",
+                    correlation_id, model_id, fallback
+                );
+                // The worker serves the fallback under its own name: rewrite the body.
+                v["model"] = json!(fallback);
+                body = Bytes::from(serde_json::to_vec(&v).unwrap_or_default());
+                forward_gzip = false;
+                model_id = fallback;
+                served_fallback = true;
+            }
+        }
+    }
+
+    let Some((instance_id, base_url)) = selected else {
         eprintln!(
             "[OPENAI_PROXY] [{}] ERROR: No ready worker found for model_id={}",
             correlation_id, model_id
         );
         worker_routing::bump_runtime_model_counters(&state.db, &model_id, false).await;
-        return (
+        log_proxy_request(
+            &state.db,
+            None,
+            Some("no_ready_worker"),
+            json!({"model": model_id, "correlation_id": correlation_id}),
+        )
+        .await;
+        return error_response(
             StatusCode::SERVICE_UNAVAILABLE,
-            Json(json!({
+            json!({
                 "error":"no_ready_worker",
                 "message":"No READY worker found for requested model",
                 "model": model_id
-            })),
-        )
-            .into_response();
+            }),
+            deprecation.as_ref(),
+        );
     };
 
-    let target = format!("{}{}", base_url.trim_end_matches('/'), path);
-    eprintln!(
-        "[OPENAI_PROXY] [{}] WORKER_SELECTED: instance_id={}, target={}, stream={}",
-        correlation_id, instance_id, target, stream
-    );
-
-    // Build HTTP client with appropriate timeouts
-    let mut client_builder = reqwest::Client::builder()
-        .connect_timeout(std::time::Duration::from_secs(30))
-        .tcp_keepalive(std::time::Duration::from_secs(60))
-        .read_timeout(std::time::Duration::from_secs(300)); // 5 minutes for reading
-
-    if stream {
-        client_builder = client_builder.timeout(std::time::Duration::from_secs(3600));
-    } else {
-        client_builder = client_builder.timeout(std::time::Duration::from_secs(60));
+    // Opt-in per model: generation defaults for params the client omitted (completion paths only).
+    if matches!(path, "/v1/chat/completions" | "/v1/completions") {
+        if let Some(defaults) = worker_routing::model_default_params(&state.db, &model_id).await {
+            if worker_routing::apply_default_params(&mut v, &defaults) {
+                body = Bytes::from(serde_json::to_vec(&v).unwrap_or_default());
+                forward_gzip = false;
+            }
+        }
     }
 
-    let client = match client_builder.build() {
-        Ok(c) => c,
-        Err(_) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({"error":"http_client_build_failed"})),
-            )
-                .into_response();
+    // Client-facing model name: what the client asked for, or the fallback actually served.
+    let sanitizer = ResponseSanitizer::from_env();
+    let response_model = sanitizer.as_ref().map(|_| {
+        if served_fallback {
+            model_id.clone()
+        } else {
+            requested_model.clone().unwrap_or_else(|| model_id.clone())
         }
-    };
+    });
 
-    // Prepare headers for upstream request
-    let mut out_headers = reqwest::header::HeaderMap::new();
-    if let Some(ct) = headers.get(axum::http::header::CONTENT_TYPE) {
-        out_headers.insert(reqwest::header::CONTENT_TYPE, ct.clone());
+    // Identical concurrent non-streaming requests from the same caller can share one upstream
+    // call (opt-in). Usage is recorded once per flight, so callers are never coalesced together.
+    let single_flight = if stream {
+        None
     } else {
-        out_headers.insert(
-            reqwest::header::CONTENT_TYPE,
-            reqwest::header::HeaderValue::from_static("application/json"),
-        );
-    }
-    if let Some(acc) = headers.get(axum::http::header::ACCEPT) {
-        out_headers.insert(reqwest::header::ACCEPT, acc.clone());
+        SingleFlight::window_from_env().map(|w| {
+            let principal = match (&api_key, &user) {
+                (Some(k), _) => format!("key:{}", k.api_key_id),
+                (None, Some(u)) => format!(
+                    "user:{}:{}",
+                    u.user_id,
+                    u.current_organization_id
+                        .map(|o| o.to_string())
+                        .unwrap_or_default()
+                ),
+                (None, None) => "anonymous".to_string(),
+            };
+            (w, SingleFlight::key(path, &principal, &model_id, &body))
+        })
+    };
+
+    // Sampled request/response capture for debugging (opt-in, non-streaming only).
+    let trace = if !stream && proxy_traces::should_capture(&state.db).await {
+        Some(proxy_traces::PendingTrace {
+            path: path.to_string(),
+            request_headers: proxy_traces::redact_headers(&headers),
+            request_body: v.clone(),
+        })
     } else {
-        out_headers.insert(
-            reqwest::header::ACCEPT,
-            reqwest::header::HeaderValue::from_static("application/json"),
-        );
-    }
-    if let Some(sid) = sticky.as_deref() {
-        if let Ok(val) = reqwest::header::HeaderValue::from_str(sid) {
+        None
+    };
+
+    // Per-model worker headers; client credentials (Authorization) are never forwarded.
+    let model_headers = worker_routing::model_forward_headers(&state.db, &model_id).await;
+
+    let forward = async {
+        // Shared pooled client; the total timeout is per request.
+        let connect_timeout = connect_timeout(state).await;
+        let client = state
+            .proxy_clients
+            .client_with_connect_timeout(stream, connect_timeout);
+
+        // Prepare headers for upstream request
+        let mut out_headers = reqwest::header::HeaderMap::new();
+        if let Some(ct) = headers.get(axum::http::header::CONTENT_TYPE) {
+            out_headers.insert(reqwest::header::CONTENT_TYPE, ct.clone());
+        } else {
+            out_headers.insert(
+                reqwest::header::CONTENT_TYPE,
+                reqwest::header::HeaderValue::from_static("application/json"),
+            );
+        }
+        if let Some(acc) = headers.get(axum::http::header::ACCEPT) {
+            out_headers.insert(reqwest::header::ACCEPT, acc.clone());
+        } else {
+            out_headers.insert(
+                reqwest::header::ACCEPT,
+                reqwest::header::HeaderValue::from_static("application/json"),
+            );
+        }
+        if forward_gzip {
+            out_headers.insert(
+                reqwest::header::CONTENT_ENCODING,
+                reqwest::header::HeaderValue::from_static("gzip"),
+            );
+        }
+        for (name, value) in model_headers {
+            out_headers.insert(name, value);
+        }
+        if let Ok(val) = reqwest::header::HeaderValue::from_str(&sticky) {
             out_headers.insert(
                 reqwest::header::HeaderName::from_static("x-inventiv-session"),
                 val,
             );
         }
+
+        // A worker that can't be connected to is most likely dead: trip its breaker and fail over
+        // to the next candidate. Any other failure (read timeout included) is surfaced as is.
+        let (mut instance_id, mut base_url) = (instance_id, base_url);
+        let mut tried: Vec<Uuid> = Vec::new();
+        let (upstream, slot) = loop {
+            // Counted against the worker's soft cap until the response is fully relayed.
+            let slot = worker_routing::acquire_worker_slot(&state.redis_client, instance_id).await;
+
+            let target = format!("{}{}", base_url.trim_end_matches('/'), path);
+            eprintln!(
+                "[OPENAI_PROXY] [{}] WORKER_SELECTED: instance_id={}, target={}, stream={}",
+                correlation_id, instance_id, target, stream
+            );
+
+            // Send request to worker
+            eprintln!(
+                "[OPENAI_PROXY] [{}] UPSTREAM_REQUEST: sending POST to {}",
+                correlation_id, target
+            );
+            let start_time = std::time::Instant::now();
+            match client
+                .post(&target)
+                .timeout(ProxyClients::request_timeout(path, stream))
+                .headers(out_headers.clone())
+                .body(body.clone())
+                .send()
+                .await
+            {
+                Ok(r) => {
+                    let elapsed = start_time.elapsed();
+                    eprintln!(
+                        "[OPENAI_PROXY] [{}] UPSTREAM_RESPONSE: status={}, elapsed_ms={}",
+                        correlation_id,
+                        r.status(),
+                        elapsed.as_millis()
+                    );
+                    break (r, slot);
+                }
+                Err(e) => {
+                    let elapsed = start_time.elapsed();
+                    eprintln!("[OPENAI_PROXY] [{}] UPSTREAM_ERROR: elapsed_ms={}, error={}, is_timeout={}, is_connect={}", 
+                        correlation_id, elapsed.as_millis(), e, e.is_timeout(), e.is_connect());
+                    log_proxy_request(
+                        &state.db,
+                        Some(instance_id),
+                        Some("upstream_request_failed"),
+                        json!({"model": model_id, "target": target, "error": e.to_string(), "connect_failure": e.is_connect(), "correlation_id": correlation_id}),
+                    )
+                    .await;
+
+                    // Checked first: a connect timeout is both a connect and a timeout error.
+                    if e.is_connect() {
+                        state
+                            .worker_breaker
+                            .trip(instance_id, WorkerBreaker::cooldown_from_env());
+                        tried.push(instance_id);
+                        if tried.len() <= MAX_CONNECT_FAILOVERS {
+                            let mut exclude = state.worker_breaker.open_instances();
+                            exclude.extend(&tried);
+                            if let Some(next) =
+                                worker_routing::select_ready_worker_for_model_excluding(
+                                    &state.db,
+                                    &model_id,
+                                    affinity_key.as_deref(),
+                                    Some(&routing),
+                                    &exclude,
+                                )
+                                .await
+                            {
+                                // Not a failed request (yet): counted as a failover only, the
+                                // request itself is counted once, by its final outcome.
+                                state.worker_breaker.record_failover();
+                                eprintln!(
+                                    "[OPENAI_PROXY] [{}] WORKER_FAILOVER: from={}, to={}, elapsed_ms={}, failovers_total={}",
+                                    correlation_id,
+                                    instance_id,
+                                    next.0,
+                                    elapsed.as_millis(),
+                                    state.worker_breaker.failovers()
+                                );
+                                (instance_id, base_url) = next;
+                                continue;
+                            }
+                        }
+                    }
+                    worker_routing::bump_runtime_model_counters(&state.db, &model_id, false).await;
+                    metrics::spawn_instance_request_metrics(
+                        &state.db,
+                        instance_id,
+                        false,
+                        None,
+                        None,
+                        None,
+                    );
+                    if e.is_connect() {
+                        return error_response(
+                            StatusCode::BAD_GATEWAY,
+                            json!({"error":"upstream_unreachable","message":"Cannot connect to worker"}),
+                            deprecation.as_ref(),
+                        );
+                    }
+                    if e.is_timeout() {
+                        return error_response(
+                            StatusCode::GATEWAY_TIMEOUT,
+                            json!({"error":"upstream_timeout","message":"Worker request timeout"}),
+                            deprecation.as_ref(),
+                        );
+                    }
+                    return error_response(
+                        StatusCode::BAD_GATEWAY,
+                        json!({"error":"upstream_unreachable","message":"Worker request failed"}),
+                        deprecation.as_ref(),
+                    );
+                }
+            }
+        };
+
+        let status = upstream.status();
+        // Worker-local stickiness check: the worker echoes which vLLM backend served the session.
+        if let Some(header) = AffinityTracker::backend_header_from_env() {
+            if let Some(backend) = upstream
+                .headers()
+                .get(header.as_str())
+                .and_then(|v| v.to_str().ok())
+            {
+                if let Some(previous) = state.affinity.observe(instance_id, &sticky, backend) {
+                    eprintln!(
+                        "[OPENAI_PROXY] [{}] AFFINITY_BROKEN: session={}, instance_id={}, previous_backend={}, backend={}, total_breaks={}",
+                        correlation_id,
+                        sticky,
+                        instance_id,
+                        previous,
+                        backend,
+                        state.affinity.breaks()
+                    );
+                }
+            }
+        }
+        let mut resp_headers = axum::http::HeaderMap::new();
+        // Preserve content-type for SSE streaming.
+        if let Some(ct) = upstream.headers().get(reqwest::header::CONTENT_TYPE) {
+            if let Ok(cts) = ct.to_str() {
+                if let Ok(v) = axum::http::HeaderValue::from_str(cts) {
+                    resp_headers.insert(axum::http::header::CONTENT_TYPE, v);
+                }
+            }
+        }
+        if let Ok(v) = axum::http::HeaderValue::from_str(&sticky) {
+            resp_headers.insert(axum::http::HeaderName::from_static("x-inventiv-session"), v);
+        }
+
+        if served_by_header_enabled() {
+            if let Ok(v) = axum::http::HeaderValue::from_str(&instance_id.to_string()) {
+                resp_headers.insert(axum::http::HeaderName::from_static("x-served-by"), v);
+            }
+        }
+        if served_fallback {
+            if let Ok(v) = axum::http::HeaderValue::from_str(&model_id) {
+                resp_headers.insert(axum::http::HeaderName::from_static("x-served-model"), v);
+            }
+        }
+
+        let ctx = UpstreamContext {
+            instance_id,
+            model_id: &model_id,
+            correlation_id: &correlation_id,
+            user: user.as_ref(),
+            api_key_id: api_key.as_ref().map(|k| k.api_key_id),
+            slot,
+            stream_slot,
+        };
+        if stream && !status.is_success() && !is_event_stream(upstream.headers()) {
+            handle_streaming_error_response(
+                state,
+                upstream,
+                status,
+                resp_headers,
+                instance_id,
+                &model_id,
+                &correlation_id,
+            )
+            .await
+        } else if stream {
+            handle_streaming_response(state, upstream, status, resp_headers, ctx).await
+        } else {
+            handle_non_streaming_response(
+                state,
+                upstream,
+                status,
+                resp_headers,
+                ctx,
+                response_model.as_deref(),
+                trace,
+            )
+            .await
+        }
+    };
+    let mut resp = match single_flight {
+        Some((window, key)) => {
+            let (shared, coalesced) = state.inflight.run(key, window, forward).await;
+            let mut resp = shared.into_response();
+            if coalesced {
+                eprintln!(
+                    "[OPENAI_PROXY] [{}] COALESCED: shared an in-flight upstream call, model_id={}",
+                    correlation_id, model_id
+                );
+                if let Ok(v) = axum::http::HeaderValue::from_str(&sticky) {
+                    resp.headers_mut()
+                        .insert(axum::http::HeaderName::from_static("x-inventiv-session"), v);
+                }
+            }
+            resp
+        }
+        None => forward.await,
+    };
+    if let Some(sanitizer) = sanitizer.as_ref() {
+        sanitizer.strip(resp.headers_mut());
     }
+    with_deprecation_header(resp, deprecation.as_ref())
+}
 
-    // Send request to worker
+/// Forward an allowlisted passthrough request that names no model to any READY worker serving a
+/// model the caller may use (see `model_access_allowed`), and relay the worker's response as is
+/// (status, content type, body).
+pub async fn passthrough_to_any_worker(
+    state: &Arc<AppState>,
+    method: axum::http::Method,
+    path: &str,
+    headers: &HeaderMap,
+    body: Bytes,
+    api_key: Option<&auth::ApiKeyPrincipal>,
+) -> Response {
+    let correlation_id = uuid::Uuid::new_v4().to_string();
+    let mut excluded = state.worker_breaker.open_instances();
+    let selected = loop {
+        let Some((instance_id, base_url)) =
+            worker_routing::select_ready_worker_for_model_excluding(
+                &state.db, "", None, None, &excluded,
+            )
+            .await
+        else {
+            break None;
+        };
+        let served_model: Option<String> =
+            sqlx::query_scalar("SELECT worker_model_id FROM instances WHERE id = $1")
+                .bind(instance_id)
+                .fetch_optional(&state.db)
+                .await
+                .ok()
+                .flatten()
+                .flatten();
+        if let Some(model) = served_model {
+            if worker_routing::model_access_allowed(&state.db, &model, api_key).await {
+                break Some((instance_id, base_url));
+            }
+        }
+        excluded.push(instance_id);
+    };
+    let Some((instance_id, base_url)) = selected else {
+        log_proxy_request(
+            &state.db,
+            None,
+            Some("no_ready_worker"),
+            json!({"path": path, "correlation_id": correlation_id}),
+        )
+        .await;
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({"error":"no_ready_worker","message":"No READY worker available"})),
+        )
+            .into_response();
+    };
+
+    let target = format!("{}{}", base_url.trim_end_matches('/'), path);
     eprintln!(
-        "[OPENAI_PROXY] [{}] UPSTREAM_REQUEST: sending POST to {}",
-        correlation_id, target
+        "[OPENAI_PROXY] [{}] PASSTHROUGH: method={}, instance_id={}, target={}",
+        correlation_id, method, instance_id, target
     );
-    let start_time = std::time::Instant::now();
-    let upstream = match client
-        .post(&target)
-        .headers(out_headers)
-        .body(body)
-        .send()
-        .await
-    {
-        Ok(r) => {
-            let elapsed = start_time.elapsed();
+    let connect_timeout = connect_timeout(state).await;
+    let client = state
+        .proxy_clients
+        .client_with_connect_timeout(false, connect_timeout);
+    let mut request = client
+        .request(method, &target)
+        .timeout(ProxyClients::request_timeout(path, false));
+    for name in [
+        axum::http::header::CONTENT_TYPE,
+        axum::http::header::CONTENT_ENCODING,
+        axum::http::header::ACCEPT,
+    ] {
+        if let Some(v) = headers.get(&name) {
+            request = request.header(name, v.clone());
+        }
+    }
+    if !body.is_empty() {
+        request = request.body(body);
+    }
+
+    let upstream = match request.send().await {
+        Ok(r) => r,
+        Err(e) => {
             eprintln!(
-                "[OPENAI_PROXY] [{}] UPSTREAM_RESPONSE: status={}, elapsed_ms={}",
+                "[OPENAI_PROXY] [{}] PASSTHROUGH_ERROR: error={}, is_timeout={}, is_connect={}",
                 correlation_id,
-                r.status(),
-                elapsed.as_millis()
+                e,
+                e.is_timeout(),
+                e.is_connect()
             );
-            r
-        }
-        Err(e) => {
-            let elapsed = start_time.elapsed();
-            eprintln!("[OPENAI_PROXY] [{}] UPSTREAM_ERROR: elapsed_ms={}, error={}, is_timeout={}, is_connect={}", 
-                correlation_id, elapsed.as_millis(), e, e.is_timeout(), e.is_connect());
-            worker_routing::bump_runtime_model_counters(&state.db, &model_id, false).await;
-            metrics::update_instance_request_metrics(
+            if e.is_connect() {
+                state
+                    .worker_breaker
+                    .trip(instance_id, WorkerBreaker::cooldown_from_env());
+            }
+            metrics::spawn_instance_request_metrics(
                 &state.db,
                 instance_id,
                 false,
                 None,
                 None,
                 None,
-            )
-            .await;
-            let error_msg = if e.is_timeout() {
-                "Worker request timeout"
-            } else if e.is_connect() {
-                "Cannot connect to worker"
-            } else {
-                "Worker request failed"
-            };
-            let _ = simple_logger::log_action_with_metadata(
+            );
+            log_proxy_request(
                 &state.db,
-                "OPENAI_PROXY",
-                "failed",
                 Some(instance_id),
                 Some("upstream_request_failed"),
-                Some(json!({"target": target, "error": e.to_string(), "correlation_id": correlation_id})),
+                json!({"path": path, "target": target, "error": e.to_string(), "correlation_id": correlation_id}),
             )
             .await;
+            let (status, error, message) = if e.is_timeout() && !e.is_connect() {
+                (
+                    StatusCode::GATEWAY_TIMEOUT,
+                    "upstream_timeout",
+                    "Worker request timeout",
+                )
+            } else {
+                (
+                    StatusCode::BAD_GATEWAY,
+                    "upstream_unreachable",
+                    "Worker request failed",
+                )
+            };
+            return (status, Json(json!({"error": error, "message": message}))).into_response();
+        }
+    };
+
+    let status = upstream.status();
+    let content_type = upstream
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .cloned();
+    let bytes = match upstream.bytes().await {
+        Ok(b) => b,
+        Err(e) => {
+            metrics::spawn_instance_request_metrics(
+                &state.db,
+                instance_id,
+                false,
+                None,
+                None,
+                None,
+            );
             return (
                 StatusCode::BAD_GATEWAY,
-                Json(json!({"error":"upstream_unreachable","message":error_msg})),
+                Json(json!({"error":"upstream_read_failed","message":e.to_string()})),
             )
                 .into_response();
         }
     };
+    metrics::spawn_instance_request_metrics(
+        &state.db,
+        instance_id,
+        status.is_success(),
+        None,
+        None,
+        None,
+    );
 
-    let status = upstream.status();
-    let mut resp_headers = axum::http::HeaderMap::new();
-    // Preserve content-type for SSE streaming.
-    if let Some(ct) = upstream.headers().get(reqwest::header::CONTENT_TYPE) {
-        if let Ok(cts) = ct.to_str() {
-            if let Ok(v) = axum::http::HeaderValue::from_str(cts) {
-                resp_headers.insert(axum::http::header::CONTENT_TYPE, v);
-            }
-        }
+    let mut resp = Response::new(Body::from(bytes));
+    *resp.status_mut() = status;
+    if let Some(ct) = content_type {
+        resp.headers_mut()
+            .insert(axum::http::header::CONTENT_TYPE, ct);
     }
+    resp
+}
 
-    if stream {
-        handle_streaming_response(
-            state,
-            upstream,
-            status,
-            resp_headers,
-            instance_id,
-            &model_id,
-            &correlation_id,
-            user.as_ref(),
-        )
-        .await
-    } else {
-        handle_non_streaming_response(
-            state,
-            upstream,
-            status,
-            resp_headers,
-            instance_id,
-            &model_id,
-            &correlation_id,
-            user.as_ref(),
-        )
-        .await
+/// JSON error envelope; for deprecated models adds `metadata.warning` and the deprecation header.
+fn error_response(
+    status: StatusCode,
+    mut body: serde_json::Value,
+    deprecation: Option<&worker_routing::ModelDeprecation>,
+) -> Response {
+    if let Some(d) = deprecation {
+        body["metadata"] = json!({ "warning": d.warning() });
     }
+    with_deprecation_header((status, Json(body)).into_response(), deprecation)
 }
 
-async fn handle_streaming_response(
+fn with_deprecation_header(
+    mut resp: Response,
+    deprecation: Option<&worker_routing::ModelDeprecation>,
+) -> Response {
+    if let Some(v) =
+        deprecation.and_then(|d| axum::http::HeaderValue::from_str(&d.header_value()).ok())
+    {
+        resp.headers_mut()
+            .insert(axum::http::HeaderName::from_static("x-model-deprecated"), v);
+    }
+    resp
+}
+
+fn is_event_stream(headers: &reqwest::header::HeaderMap) -> bool {
+    headers
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.trim_start().starts_with("text/event-stream"))
+}
+
+/// Single terminal SSE `error` event carrying an upstream error body. OpenAI-style
+/// `{"error": ...}` objects pass through; anything else (e.g. vLLM's flat error object, plain
+/// text) is wrapped under `error`.
+fn sse_error_event(status: StatusCode, body: &[u8]) -> Bytes {
+    let payload = match serde_json::from_slice::<serde_json::Value>(body) {
+        Ok(v) if v.get("error").is_some() => v,
+        Ok(v) if v.is_object() => json!({ "error": v }),
+        _ => json!({
+            "error": {
+                "message": String::from_utf8_lossy(body).trim(),
+                "type": "upstream_error",
+                "code": status.as_u16(),
+            }
+        }),
+    };
+    Bytes::from(format!("event: error\ndata: {}\n\n", payload))
+}
+
+/// `stream: true` request answered with a non-SSE error (the worker failed before streaming
+/// started): relay it as one SSE error event so SSE clients can parse it.
+async fn handle_streaming_error_response(
     state: &Arc<AppState>,
     upstream: reqwest::Response,
     status: StatusCode,
-    resp_headers: axum::http::HeaderMap,
+    mut resp_headers: axum::http::HeaderMap,
     instance_id: Uuid,
     model_id: &str,
     correlation_id: &str,
-    user: Option<&auth::AuthUser>,
 ) -> Response {
+    let body = upstream.bytes().await.unwrap_or_default();
+    eprintln!(
+        "[OPENAI_PROXY] [{}] STREAMING_ERROR_AS_SSE: status={}, content_type={:?}, body_size={}",
+        correlation_id,
+        status,
+        resp_headers
+            .get(axum::http::header::CONTENT_TYPE)
+            .and_then(|h| h.to_str().ok()),
+        body.len()
+    );
+
+    worker_routing::bump_runtime_model_counters(&state.db, model_id, false).await;
+    metrics::spawn_instance_request_metrics(&state.db, instance_id, false, None, None, None);
+    log_proxy_request(
+        &state.db,
+        Some(instance_id),
+        Some("upstream_error_status"),
+        json!({
+            "model": model_id,
+            "stream": true,
+            "upstream_status": status.as_u16(),
+            "correlation_id": correlation_id
+        }),
+    )
+    .await;
+
+    resp_headers.insert(
+        axum::http::header::CONTENT_TYPE,
+        axum::http::HeaderValue::from_static("text/event-stream"),
+    );
+    resp_headers.insert(
+        axum::http::header::CACHE_CONTROL,
+        axum::http::HeaderValue::from_static("no-cache"),
+    );
+    (status, resp_headers, sse_error_event(status, &body)).into_response()
+}
+
+/// Per-request state handed to the upstream response handlers.
+struct UpstreamContext<'a> {
+    instance_id: Uuid,
+    model_id: &'a str,
+    correlation_id: &'a str,
+    user: Option<&'a auth::AuthUser>,
+    api_key_id: Option<Uuid>,
+    /// Worker concurrency slot, released once the upstream response is fully read.
+    slot: Option<worker_routing::WorkerSlot>,
+    /// API key stream slot, released when the client body is dropped.
+    stream_slot: Option<rate_limit::StreamSlot>,
+}
+
+async fn handle_streaming_response(
+    state: &Arc<AppState>,
+    upstream: reqwest::Response,
+    status: StatusCode,
+    resp_headers: axum::http::HeaderMap,
+    ctx: UpstreamContext<'_>,
+) -> Response {
+    let UpstreamContext {
+        instance_id,
+        model_id,
+        correlation_id,
+        user,
+        api_key_id,
+        slot,
+        stream_slot,
+    } = ctx;
     eprintln!(
         "[OPENAI_PROXY] [{}] STREAMING_START: status={}, content_type={:?}",
         correlation_id,
@@ -273,8 +1327,18 @@ async fn handle_streaming_response(
 
     let success = status.is_success();
     worker_routing::bump_runtime_model_counters(&state.db, model_id, success).await;
-    metrics::update_instance_request_metrics(&state.db, instance_id, success, None, None, None)
-        .await;
+    // Don't hold the stream back on the action log insert.
+    let db_for_log = state.db.clone();
+    let log_metadata = json!({
+        "model": model_id,
+        "stream": true,
+        "upstream_status": status.as_u16(),
+        "correlation_id": correlation_id
+    });
+    tokio::spawn(async move {
+        let error_code = (!success).then_some("upstream_error_status");
+        log_proxy_request(&db_for_log, Some(instance_id), error_code, log_metadata).await;
+    });
 
     let correlation_id_for_stream = correlation_id.to_string();
     let correlation_id_for_tokens = correlation_id.to_string();
@@ -295,6 +1359,8 @@ async fn handle_streaming_response(
         while let Some(chunk) = rx.recv().await {
             buffer.extend_from_slice(&chunk);
         }
+        // Stream finished: the worker slot is free again.
+        drop(slot);
 
         // Stream completed (channel closed), extract tokens
         let text = String::from_utf8_lossy(&buffer);
@@ -313,23 +1379,23 @@ async fn handle_streaming_response(
         let (input_tokens, output_tokens, total_tokens) =
             metrics::parse_tokens_from_sse_stream(&text);
 
+        // Count the request once the stream is over (with its tokens, if the worker reported usage).
+        metrics::update_instance_request_metrics(
+            &db_for_tokens,
+            instance_id_for_tokens,
+            success,
+            input_tokens,
+            output_tokens,
+            total_tokens,
+        )
+        .await;
+
         if input_tokens.is_some() || output_tokens.is_some() || total_tokens.is_some() {
             eprintln!(
                 "[OPENAI_PROXY] [{}] STREAM_TOKENS_EXTRACTED: input={:?}, output={:?}, total={:?}",
                 correlation_id_for_tokens, input_tokens, output_tokens, total_tokens
             );
 
-            // Update instance metrics with tokens
-            metrics::update_instance_request_metrics(
-                &db_for_tokens,
-                instance_id_for_tokens,
-                success,
-                input_tokens,
-                output_tokens,
-                total_tokens,
-            )
-            .await;
-
             // Store inference usage
             if let Some(model_uuid) =
                 metrics::resolve_model_uuid(&db_for_tokens, &model_id_for_tokens).await
@@ -341,7 +1407,7 @@ async fn handle_streaming_response(
                     input_tokens,
                     output_tokens,
                     total_tokens,
-                    None,
+                    api_key_id,
                     user_for_tokens.as_ref(),
                 )
                 .await;
@@ -412,7 +1478,12 @@ async fn handle_streaming_response(
         // Return results as a stream
         futures_util::stream::iter(results)
     })
-    .flatten();
+    .flatten()
+    // Holds the API key's stream slot for as long as the client body is alive.
+    .map(move |chunk| {
+        let _ = &stream_slot;
+        chunk
+    });
 
     eprintln!(
         "[OPENAI_PROXY] [{}] STREAMING_RETURN: returning stream to client",
@@ -426,11 +1497,20 @@ async fn handle_non_streaming_response(
     upstream: reqwest::Response,
     status: StatusCode,
     resp_headers: axum::http::HeaderMap,
-    instance_id: Uuid,
-    model_id: &str,
-    correlation_id: &str,
-    user: Option<&auth::AuthUser>,
+    ctx: UpstreamContext<'_>,
+    response_model: Option<&str>,
+    trace: Option<proxy_traces::PendingTrace>,
 ) -> Response {
+    // The worker slot (and the unused stream slot) are dropped once the response is built.
+    let UpstreamContext {
+        instance_id,
+        model_id,
+        correlation_id,
+        user,
+        api_key_id,
+        slot: _slot,
+        stream_slot: _stream_slot,
+    } = ctx;
     eprintln!(
         "[OPENAI_PROXY] [{}] NON_STREAMING: reading response body",
         correlation_id
@@ -451,13 +1531,19 @@ async fn handle_non_streaming_response(
             );
             let success = false;
             worker_routing::bump_runtime_model_counters(&state.db, model_id, success).await;
-            metrics::update_instance_request_metrics(
+            metrics::spawn_instance_request_metrics(
                 &state.db,
                 instance_id,
                 success,
                 None,
                 None,
                 None,
+            );
+            log_proxy_request(
+                &state.db,
+                Some(instance_id),
+                Some("upstream_read_failed"),
+                json!({"model": model_id, "error": e.to_string(), "correlation_id": correlation_id}),
             )
             .await;
             return (
@@ -495,15 +1581,14 @@ async fn handle_non_streaming_response(
     };
 
     // Update instance metrics with tokens
-    metrics::update_instance_request_metrics(
+    metrics::spawn_instance_request_metrics(
         &state.db,
         instance_id,
         success,
         input_tokens,
         output_tokens,
         total_tokens,
-    )
-    .await;
+    );
 
     // Store inference usage if we have tokens
     if success && (input_tokens.is_some() || output_tokens.is_some() || total_tokens.is_some()) {
@@ -515,12 +1600,43 @@ async fn handle_non_streaming_response(
                 input_tokens,
                 output_tokens,
                 total_tokens,
-                None, // API key ID - can be enhanced later
+                api_key_id,
                 user,
             )
             .await;
         }
     }
 
+    log_proxy_request(
+        &state.db,
+        Some(instance_id),
+        (!success).then_some("upstream_error_status"),
+        json!({
+            "model": model_id,
+            "stream": false,
+            "upstream_status": status.as_u16(),
+            "correlation_id": correlation_id
+        }),
+    )
+    .await;
+
+    if let Some(trace) = trace {
+        proxy_traces::record_trace(
+            &state.db,
+            trace,
+            model_id,
+            Some(instance_id),
+            correlation_id,
+            Some(status.as_u16()),
+            &bytes,
+        )
+        .await;
+    }
+
+    let bytes = match response_model.filter(|_| success) {
+        Some(m) => rewrite_response_model(&bytes, m).unwrap_or(bytes),
+        None => bytes,
+    };
+
     (status, resp_headers, bytes).into_response()
 }
@@ -1639,10 +1639,10 @@ mod tests {
         assert!(roles.iter().all(|(_, r)| r == "owner"));
 
         // Also verify the handler would list both orgs for admin (because membership exists).
-        let state = Arc::new(crate::AppState {
-            redis_client: redis::Client::open("redis://127.0.0.1/").unwrap(),
-            db: pool.clone(),
-        });
+        let state = crate::AppState::new(
+            redis::Client::open("redis://127.0.0.1/").unwrap(),
+            pool.clone(),
+        );
         let auth_user = auth::AuthUser {
             user_id: admin_id,
             email: "admin@inventiv.local".to_string(),
@@ -1741,10 +1741,10 @@ mod tests {
         .execute(&pool)
         .await;
 
-        let state = Arc::new(crate::AppState {
-            redis_client: redis::Client::open("redis://127.0.0.1/").unwrap(),
-            db: pool.clone(),
-        });
+        let state = crate::AppState::new(
+            redis::Client::open("redis://127.0.0.1/").unwrap(),
+            pool.clone(),
+        );
         let auth_user = auth::AuthUser {
             user_id: owner_id,
             email: "owner1@inventiv.local".to_string(),
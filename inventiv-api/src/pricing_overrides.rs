@@ -0,0 +1,263 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::FromRow;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::AppState;
+
+/// Effective hourly price override (reserved / committed-use discount) for an instance type.
+/// FinOps uses the override valid at a given time before falling back to `instance_types.cost_per_hour`.
+#[derive(Debug, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct PricingOverrideRow {
+    pub id: Uuid,
+    pub provider_id: Uuid,
+    pub instance_type_id: Uuid,
+    pub instance_type_code: Option<String>,
+    pub cost_per_hour: f64,
+    pub valid_from: DateTime<Utc>,
+    pub valid_to: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct ListPricingOverridesParams {
+    pub provider_id: Option<Uuid>,
+    pub instance_type_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct CreatePricingOverrideRequest {
+    pub instance_type_id: Uuid,
+    pub cost_per_hour: f64,
+    /// Defaults to now.
+    pub valid_from: Option<DateTime<Utc>>,
+    /// Open-ended if missing.
+    pub valid_to: Option<DateTime<Utc>>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct UpdatePricingOverrideRequest {
+    pub cost_per_hour: Option<f64>,
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_to: Option<DateTime<Utc>>,
+    /// true = remove the end date (open-ended override).
+    pub clear_valid_to: Option<bool>,
+    pub note: Option<String>,
+}
+
+const SELECT_OVERRIDE: &str = r#"
+    SELECT po.id, po.provider_id, po.instance_type_id, it.code AS instance_type_code,
+           CAST(po.cost_per_hour AS float8) AS cost_per_hour,
+           po.valid_from, po.valid_to, po.note, po.created_at, po.updated_at
+    FROM pricing_overrides po
+    LEFT JOIN instance_types it ON it.id = po.instance_type_id
+"#;
+
+fn bad_request(error: &str, message: &str) -> axum::response::Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({"error": error, "message": message})),
+    )
+        .into_response()
+}
+
+fn db_error(e: sqlx::Error) -> axum::response::Response {
+    if let sqlx::Error::Database(db_err) = &e {
+        // CHECK violation (validity window / negative price)
+        if db_err.code().as_deref() == Some("23514") {
+            return bad_request("invalid_pricing_override", &db_err.to_string());
+        }
+    }
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({"error":"db_error","message": e.to_string()})),
+    )
+        .into_response()
+}
+
+fn cost_valid(v: f64) -> bool {
+    v.is_finite() && v >= 0.0
+}
+
+async fn fetch_override(
+    db: &sqlx::Pool<sqlx::Postgres>,
+    id: Uuid,
+) -> Result<PricingOverrideRow, sqlx::Error> {
+    sqlx::query_as::<_, PricingOverrideRow>(&format!("{} WHERE po.id = $1", SELECT_OVERRIDE))
+        .bind(id)
+        .fetch_one(db)
+        .await
+}
+
+#[utoipa::path(
+    get,
+    path = "/finops/pricing-overrides",
+    tag = "FinOps",
+    params(ListPricingOverridesParams),
+    responses((status = 200, description = "Pricing overrides", body = Vec<PricingOverrideRow>))
+)]
+pub async fn list_pricing_overrides(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<ListPricingOverridesParams>,
+) -> impl IntoResponse {
+    let rows = sqlx::query_as::<_, PricingOverrideRow>(&format!(
+        "{} WHERE ($1::uuid IS NULL OR po.provider_id = $1)
+             AND ($2::uuid IS NULL OR po.instance_type_id = $2)
+           ORDER BY it.code NULLS LAST, po.valid_from DESC",
+        SELECT_OVERRIDE
+    ))
+    .bind(params.provider_id)
+    .bind(params.instance_type_id)
+    .fetch_all(&state.db)
+    .await;
+
+    match rows {
+        Ok(rows) => (StatusCode::OK, Json(rows)).into_response(),
+        Err(e) => db_error(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/finops/pricing-overrides",
+    tag = "FinOps",
+    request_body = CreatePricingOverrideRequest,
+    responses(
+        (status = 201, description = "Pricing override created", body = PricingOverrideRow),
+        (status = 400, description = "Invalid price or validity window"),
+        (status = 422, description = "Instance type not found")
+    )
+)]
+pub async fn create_pricing_override(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<CreatePricingOverrideRequest>,
+) -> impl IntoResponse {
+    if !cost_valid(req.cost_per_hour) {
+        return bad_request("invalid_cost_per_hour", "cost_per_hour must be >= 0");
+    }
+
+    // provider_id is derived from the instance type (no mismatched pairs).
+    let provider_id: Option<Uuid> =
+        sqlx::query_scalar("SELECT provider_id FROM instance_types WHERE id = $1")
+            .bind(req.instance_type_id)
+            .fetch_optional(&state.db)
+            .await
+            .unwrap_or(None);
+    let Some(provider_id) = provider_id else {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({"error":"instance_type_not_found","message":"instance_type_id does not exist"})),
+        )
+            .into_response();
+    };
+
+    let id = Uuid::new_v4();
+    let res = sqlx::query(
+        r#"INSERT INTO pricing_overrides (id, provider_id, instance_type_id, cost_per_hour, valid_from, valid_to, note)
+           VALUES ($1, $2, $3, $4, COALESCE($5, NOW()), $6, NULLIF(btrim($7), ''))"#,
+    )
+    .bind(id)
+    .bind(provider_id)
+    .bind(req.instance_type_id)
+    .bind(req.cost_per_hour)
+    .bind(req.valid_from)
+    .bind(req.valid_to)
+    .bind(req.note)
+    .execute(&state.db)
+    .await;
+    if let Err(e) = res {
+        return db_error(e);
+    }
+
+    match fetch_override(&state.db, id).await {
+        Ok(row) => (StatusCode::CREATED, Json(row)).into_response(),
+        Err(e) => db_error(e),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/finops/pricing-overrides/{id}",
+    tag = "FinOps",
+    request_body = UpdatePricingOverrideRequest,
+    responses(
+        (status = 200, description = "Pricing override updated", body = PricingOverrideRow),
+        (status = 400, description = "Invalid price or validity window"),
+        (status = 404, description = "Not found")
+    )
+)]
+pub async fn update_pricing_override(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<UpdatePricingOverrideRequest>,
+) -> impl IntoResponse {
+    if req.cost_per_hour.is_some_and(|v| !cost_valid(v)) {
+        return bad_request("invalid_cost_per_hour", "cost_per_hour must be >= 0");
+    }
+
+    let res = sqlx::query(
+        r#"UPDATE pricing_overrides
+           SET cost_per_hour = COALESCE($2, cost_per_hour),
+               valid_from = COALESCE($3, valid_from),
+               valid_to = CASE WHEN COALESCE($5, false) THEN NULL ELSE COALESCE($4, valid_to) END,
+               note = CASE WHEN $6::text IS NULL THEN note ELSE NULLIF(btrim($6), '') END,
+               updated_at = NOW()
+           WHERE id = $1"#,
+    )
+    .bind(id)
+    .bind(req.cost_per_hour)
+    .bind(req.valid_from)
+    .bind(req.valid_to)
+    .bind(req.clear_valid_to)
+    .bind(req.note)
+    .execute(&state.db)
+    .await;
+
+    match res {
+        Ok(r) if r.rows_affected() == 0 => {
+            (StatusCode::NOT_FOUND, Json(json!({"error":"not_found"}))).into_response()
+        }
+        Ok(_) => match fetch_override(&state.db, id).await {
+            Ok(row) => (StatusCode::OK, Json(row)).into_response(),
+            Err(e) => db_error(e),
+        },
+        Err(e) => db_error(e),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/finops/pricing-overrides/{id}",
+    tag = "FinOps",
+    responses(
+        (status = 204, description = "Pricing override deleted"),
+        (status = 404, description = "Not found")
+    )
+)]
+pub async fn delete_pricing_override(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match sqlx::query("DELETE FROM pricing_overrides WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await
+    {
+        Ok(r) if r.rows_affected() == 0 => {
+            (StatusCode::NOT_FOUND, Json(json!({"error":"not_found"}))).into_response()
+        }
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => db_error(e),
+    }
+}
@@ -2,12 +2,14 @@ use crate::handlers::instances::InstanceResponse;
 use sqlx::{Pool, Postgres};
 use uuid::Uuid;
 
-/// Calculate and attach progress percentage to instances
+/// Calculate and attach progress percentage (and failure stage) to instances
 pub async fn enrich_instances_with_progress(
     db: &Pool<Postgres>,
     instances: &mut [InstanceResponse],
 ) {
     for instance in instances.iter_mut() {
+        instance.failure_stage =
+            inventiv_common::failure_stage(&instance.status).map(str::to_string);
         match calculate_instance_progress(db, instance.id, &instance.status).await {
             Ok(progress) => {
                 instance.progress_percent = Some(progress);
@@ -73,6 +75,8 @@ pub async fn calculate_instance_progress(
     // Terminal states: no progress or 100%
     match status_lower.as_str() {
         "ready" => return Ok(100),
+        // Queued: the orchestrator has not started provisioning yet.
+        "accepted" => return Ok(0),
         "terminated" | "terminating" | "archived" => return Ok(0),
         "provisioning_failed" | "startup_failed" | "failed" => return Ok(0),
         _ => {}
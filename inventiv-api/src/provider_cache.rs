@@ -0,0 +1,45 @@
+use sqlx::{Pool, Postgres};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use uuid::Uuid;
+
+/// In-process `providers.code -> providers.id` map used by deployment requests.
+///
+/// Only active providers are cached; inactive/unknown codes always go to the DB so the caller
+/// keeps its own "not found or inactive" handling. Cleared on provider create/update.
+#[derive(Default)]
+pub struct ProviderCodeCache {
+    ids: RwLock<HashMap<String, Uuid>>,
+}
+
+impl ProviderCodeCache {
+    /// Resolve a (lowercase) provider code to its id, hitting the DB only on cache misses.
+    pub async fn resolve(&self, db: &Pool<Postgres>, code: &str) -> Option<Uuid> {
+        if let Some(id) = self.ids.read().ok().and_then(|m| m.get(code).copied()) {
+            return Some(id);
+        }
+
+        let row: Option<(Uuid, bool)> = sqlx::query_as(
+            "SELECT id, COALESCE(is_active, false) FROM providers WHERE code = $1 LIMIT 1",
+        )
+        .bind(code)
+        .fetch_optional(db)
+        .await
+        .unwrap_or(None);
+
+        let (id, is_active) = row?;
+        if is_active {
+            if let Ok(mut m) = self.ids.write() {
+                m.insert(code.to_string(), id);
+            }
+        }
+        Some(id)
+    }
+
+    /// Drop all cached entries (call after any provider write).
+    pub fn invalidate(&self) {
+        if let Ok(mut m) = self.ids.write() {
+            m.clear();
+        }
+    }
+}
@@ -2,14 +2,16 @@ use axum::{
     extract::{Path, State},
     http::StatusCode,
     response::IntoResponse,
-    Json,
+    Extension, Json,
 };
+use redis::AsyncCommands;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use sqlx::FromRow;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::AppState;
+use crate::{auth, AppState};
 
 #[derive(Debug, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
 pub struct SettingDefinitionRow {
@@ -162,6 +164,8 @@ pub async fn upsert_global_setting(
             .bind(&key)
             .execute(&state.db)
             .await;
+        state.settings.invalidate();
+        publish_credentials_invalidation(&state, None).await;
         return StatusCode::OK;
     }
 
@@ -186,7 +190,11 @@ pub async fn upsert_global_setting(
     .await;
 
     match res {
-        Ok(_) => StatusCode::OK,
+        Ok(_) => {
+            state.settings.invalidate();
+            publish_credentials_invalidation(&state, None).await;
+            StatusCode::OK
+        }
         Err(_) => StatusCode::BAD_REQUEST,
     }
 }
@@ -504,10 +512,176 @@ pub async fn update_provider_params(
     if tx.commit().await.is_err() {
         return StatusCode::INTERNAL_SERVER_ERROR;
     }
+    publish_credentials_invalidation(&state, None).await;
 
     StatusCode::OK
 }
 
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct UpdateProviderCredentialsRequest {
+    /// Left unchanged when null/missing.
+    pub project_id: Option<String>,
+    /// Stored encrypted (SCALEWAY_SECRET_KEY_ENC). Left unchanged when null/missing.
+    pub secret_key: Option<String>,
+}
+
+/// Same passphrase sources as the orchestrator (secret file first, then env).
+fn provider_settings_passphrase() -> Option<String> {
+    let passphrase_file = std::env::var("PROVIDER_SETTINGS_ENCRYPTION_KEY_FILE")
+        .ok()
+        .or_else(|| std::env::var("PROVIDER_SETTINGS_PASSPHRASE_FILE").ok())
+        .unwrap_or_else(|| "/run/secrets/provider_settings_key".to_string());
+    std::fs::read_to_string(passphrase_file.trim())
+        .ok()
+        .or_else(|| std::env::var("PROVIDER_SETTINGS_ENCRYPTION_KEY").ok())
+        .or_else(|| std::env::var("PROVIDER_SETTINGS_PASSPHRASE").ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn credentials_error(status: StatusCode, code: &str) -> axum::response::Response {
+    (status, Json(json!({"error": code}))).into_response()
+}
+
+#[utoipa::path(
+    put,
+    path = "/providers/{id}/credentials",
+    tag = "Settings",
+    request_body = UpdateProviderCredentialsRequest,
+    responses(
+        (status = 200, description = "Updated; orchestrators drop their cached credentials"),
+        (status = 400, description = "Unsupported provider, empty value or no current organization"),
+        (status = 403, description = "Admin required"),
+        (status = 404, description = "Provider not found")
+    )
+)]
+pub async fn update_provider_credentials(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<auth::AuthUser>,
+    Path(provider_id): Path<Uuid>,
+    Json(req): Json<UpdateProviderCredentialsRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = auth::require_admin(&user) {
+        return e.into_response();
+    }
+    // Credentials are per organization (provider_settings.organization_id).
+    let Some(organization_id) = user.current_organization_id else {
+        return credentials_error(StatusCode::BAD_REQUEST, "organization_required");
+    };
+    let code: Option<String> = sqlx::query_scalar("SELECT code FROM providers WHERE id = $1")
+        .bind(provider_id)
+        .fetch_optional(&state.db)
+        .await
+        .ok()
+        .flatten();
+    match code.as_deref() {
+        None => return credentials_error(StatusCode::NOT_FOUND, "not_found"),
+        Some("scaleway") => {}
+        Some(_) => return credentials_error(StatusCode::BAD_REQUEST, "unsupported_provider"),
+    }
+
+    let project_id = req.project_id.as_deref().map(str::trim);
+    let secret_key = req.secret_key.as_deref().map(str::trim);
+    if project_id.is_some_and(str::is_empty) || secret_key.is_some_and(str::is_empty) {
+        return credentials_error(StatusCode::BAD_REQUEST, "empty_value");
+    }
+    let passphrase = match secret_key {
+        Some(_) => match provider_settings_passphrase() {
+            Some(p) => Some(p),
+            None => {
+                return credentials_error(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "encryption_key_missing",
+                )
+            }
+        },
+        None => None,
+    };
+
+    let mut tx = match state.db.begin().await {
+        Ok(t) => t,
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+    if let Some(v) = project_id {
+        let res = sqlx::query(
+            r#"
+            INSERT INTO provider_settings (provider_id, organization_id, key, value_text, value_int, value_bool, value_json)
+            VALUES ($1, $2, 'SCALEWAY_PROJECT_ID', $3, NULL, NULL, NULL)
+            ON CONFLICT (provider_id, key, organization_id) DO UPDATE SET
+              value_text = EXCLUDED.value_text,
+              value_int = NULL,
+              value_bool = NULL,
+              value_json = NULL
+            "#,
+        )
+        .bind(provider_id)
+        .bind(organization_id)
+        .bind(v)
+        .execute(&mut *tx)
+        .await;
+        if res.is_err() {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    }
+    if let (Some(v), Some(passphrase)) = (secret_key, passphrase) {
+        let res = sqlx::query(
+            r#"
+            INSERT INTO provider_settings (provider_id, organization_id, key, value_text, value_int, value_bool, value_json)
+            VALUES ($1, $2, 'SCALEWAY_SECRET_KEY_ENC', encode(pgp_sym_encrypt($3::text, $4::text), 'base64'), NULL, NULL, NULL)
+            ON CONFLICT (provider_id, key, organization_id) DO UPDATE SET
+              value_text = EXCLUDED.value_text,
+              value_int = NULL,
+              value_bool = NULL,
+              value_json = NULL
+            "#,
+        )
+        .bind(provider_id)
+        .bind(organization_id)
+        .bind(v)
+        .bind(passphrase)
+        .execute(&mut *tx)
+        .await;
+        if res.is_err() {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+        // A legacy plain key would be shadowed by the encrypted one anyway; don't leave it behind.
+        let _ = sqlx::query(
+            "DELETE FROM provider_settings WHERE provider_id = $1 AND organization_id = $2 AND key = 'SCALEWAY_SECRET_KEY'",
+        )
+        .bind(provider_id)
+        .bind(organization_id)
+        .execute(&mut *tx)
+        .await;
+    }
+    if tx.commit().await.is_err() {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+
+    publish_credentials_invalidation(&state, Some(organization_id)).await;
+
+    StatusCode::OK.into_response()
+}
+
+/// Orchestrators cache resolved provider credentials briefly; tell them to re-read now
+/// (one organization, or all of them when `None`).
+async fn publish_credentials_invalidation(state: &AppState, organization_id: Option<Uuid>) {
+    let mut event = json!({"type": "CMD:INVALIDATE_PROVIDER_CREDENTIALS"});
+    if let Some(id) = organization_id {
+        event["organization_id"] = json!(id.to_string());
+    }
+    match state.redis_client.get_multiplexed_async_connection().await {
+        Ok(mut conn) => {
+            if let Err(e) = conn
+                .publish::<_, _, ()>("orchestrator_events", event.to_string())
+                .await
+            {
+                eprintln!("⚠️ Failed to publish credentials invalidation: {:?}", e);
+            }
+        }
+        Err(e) => eprintln!("⚠️ Failed to connect to Redis: {:?}", e),
+    }
+}
+
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct ProviderConfigStatus {
     pub provider_id: Uuid,
@@ -0,0 +1,205 @@
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::{Pool, Postgres};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::auth;
+use crate::AppState;
+
+const DEFAULT_SAMPLE_PER_MILLE: i64 = 1;
+const DEFAULT_RETENTION_HOURS: i64 = 24;
+/// Captured bodies larger than this are stored as a truncated string.
+const MAX_TRACE_BODY_BYTES: usize = 64 * 1024;
+/// Client headers never stored in clear.
+const REDACTED_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "x-api-key",
+];
+
+/// Whether this request is captured: global_settings.PROXY_TRACE_ENABLED (off by default) and a
+/// PROXY_TRACE_SAMPLE_PER_MILLE draw. Only called for non-streaming requests.
+pub async fn should_capture(db: &Pool<Postgres>) -> bool {
+    let rows: Vec<(String, Option<bool>, Option<i64>)> = sqlx::query_as(
+        "SELECT key, value_bool, value_int FROM global_settings
+         WHERE key IN ('PROXY_TRACE_ENABLED', 'PROXY_TRACE_SAMPLE_PER_MILLE')",
+    )
+    .fetch_all(db)
+    .await
+    .unwrap_or_default();
+    let enabled = rows
+        .iter()
+        .any(|(k, b, _)| k == "PROXY_TRACE_ENABLED" && *b == Some(true));
+    if !enabled {
+        return false;
+    }
+    let per_mille = rows
+        .iter()
+        .find(|(k, _, _)| k == "PROXY_TRACE_SAMPLE_PER_MILLE")
+        .and_then(|(_, _, v)| *v)
+        .unwrap_or(DEFAULT_SAMPLE_PER_MILLE)
+        .clamp(0, 1000);
+    per_mille > 0 && (per_mille >= 1000 || rand::random::<u32>() % 1000 < per_mille as u32)
+}
+
+/// Client request headers as a JSON object, with credentials redacted.
+pub fn redact_headers(headers: &HeaderMap) -> serde_json::Value {
+    let mut out = serde_json::Map::new();
+    for (name, value) in headers {
+        let name = name.as_str();
+        let value = if REDACTED_HEADERS.contains(&name) {
+            "[REDACTED]".to_string()
+        } else {
+            String::from_utf8_lossy(value.as_bytes()).into_owned()
+        };
+        out.insert(name.to_string(), serde_json::Value::String(value));
+    }
+    serde_json::Value::Object(out)
+}
+
+/// Response body as JSON when it parses, else as a (possibly truncated) string.
+fn body_value(bytes: &[u8]) -> serde_json::Value {
+    if bytes.len() <= MAX_TRACE_BODY_BYTES {
+        if let Ok(v) = serde_json::from_slice(bytes) {
+            return v;
+        }
+    }
+    let end = bytes.len().min(MAX_TRACE_BODY_BYTES);
+    serde_json::Value::String(String::from_utf8_lossy(&bytes[..end]).into_owned())
+}
+
+/// Request side of a sampled trace, captured before forwarding.
+pub struct PendingTrace {
+    pub path: String,
+    pub request_headers: serde_json::Value,
+    pub request_body: serde_json::Value,
+}
+
+/// Store a captured pair and prune traces past PROXY_TRACE_RETENTION_HOURS. Best-effort.
+pub async fn record_trace(
+    db: &Pool<Postgres>,
+    trace: PendingTrace,
+    model: &str,
+    instance_id: Option<Uuid>,
+    correlation_id: &str,
+    upstream_status: Option<u16>,
+    response_body: &[u8],
+) {
+    let request_body = match serde_json::to_vec(&trace.request_body) {
+        Ok(b) if b.len() > MAX_TRACE_BODY_BYTES => body_value(&b),
+        _ => trace.request_body,
+    };
+    let inserted = sqlx::query(
+        "INSERT INTO proxy_traces
+           (model, instance_id, correlation_id, path, upstream_status, request_headers, request_body, response_body)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+    )
+    .bind(model)
+    .bind(instance_id)
+    .bind(correlation_id)
+    .bind(&trace.path)
+    .bind(upstream_status.map(i32::from))
+    .bind(&trace.request_headers)
+    .bind(&request_body)
+    .bind(body_value(response_body))
+    .execute(db)
+    .await;
+    if let Err(e) = inserted {
+        eprintln!(
+            "[OPENAI_PROXY] [{}] TRACE_STORE_FAILED: {}",
+            correlation_id, e
+        );
+        return;
+    }
+
+    let retention_hours: i64 = sqlx::query_scalar(
+        "SELECT value_int FROM global_settings WHERE key = 'PROXY_TRACE_RETENTION_HOURS'",
+    )
+    .fetch_optional(db)
+    .await
+    .ok()
+    .flatten()
+    .flatten()
+    .filter(|v: &i64| *v > 0)
+    .unwrap_or(DEFAULT_RETENTION_HOURS);
+    let _ = sqlx::query(
+        "DELETE FROM proxy_traces WHERE created_at < NOW() - make_interval(hours => $1::int)",
+    )
+    .bind(retention_hours as i32)
+    .execute(db)
+    .await;
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct ProxyTrace {
+    pub id: Uuid,
+    pub model: String,
+    pub instance_id: Option<Uuid>,
+    pub correlation_id: Option<String>,
+    pub path: String,
+    pub upstream_status: Option<i32>,
+    pub request_headers: serde_json::Value,
+    pub request_body: Option<serde_json::Value>,
+    pub response_body: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct ProxyTracesQuery {
+    pub model: Option<String>,
+    /// Max rows (default 50, max 500).
+    pub limit: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/proxy_traces",
+    tag = "Admin",
+    params(ProxyTracesQuery),
+    responses(
+        (status = 200, description = "Captured proxy traces, newest first", body = Vec<ProxyTrace>),
+        (status = 403, description = "Admin required")
+    )
+)]
+pub async fn list_proxy_traces(
+    State(state): State<Arc<AppState>>,
+    axum::extract::Extension(user): axum::extract::Extension<auth::AuthUser>,
+    Query(params): Query<ProxyTracesQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = auth::require_admin(&user) {
+        return e.into_response();
+    }
+    let limit = params.limit.unwrap_or(50).clamp(1, 500);
+    let model = params
+        .model
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+    let rows = sqlx::query_as::<_, ProxyTrace>(
+        "SELECT id, model, instance_id, correlation_id, path, upstream_status,
+                request_headers, request_body, response_body, created_at
+         FROM proxy_traces
+         WHERE ($1::text IS NULL OR model = $1)
+         ORDER BY created_at DESC
+         LIMIT $2",
+    )
+    .bind(model)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await;
+    match rows {
+        Ok(rows) => Json(rows).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error":"db_error","message": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
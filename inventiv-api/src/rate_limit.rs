@@ -0,0 +1,253 @@
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::{header, HeaderMap, HeaderValue, Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::auth::ApiKeyPrincipal;
+
+/// Sliding window for `api_keys.rate_limit_per_minute`.
+const WINDOW_MS: i64 = 60_000;
+
+/// Result of counting one request against an API key's sliding window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    pub limit: u32,
+    /// Requests still allowed in the current window (this request included).
+    pub remaining: u32,
+    /// Seconds until the oldest request leaves the window (= Retry-After when rejected).
+    pub reset_seconds: u64,
+    pub allowed: bool,
+}
+
+impl RateLimitStatus {
+    fn from_window(limit: u32, count: u64, oldest_ms: i64, now_ms: i64) -> Self {
+        let allowed = count <= u64::from(limit);
+        let until_reset_ms = (oldest_ms + WINDOW_MS - now_ms).max(0) as u64;
+        Self {
+            limit,
+            remaining: if allowed { limit - count as u32 } else { 0 },
+            reset_seconds: until_reset_ms.div_ceil(1000).max(1),
+            allowed,
+        }
+    }
+
+    /// `X-RateLimit-*` headers, plus `Retry-After` on rejected requests.
+    pub fn apply_headers(&self, headers: &mut HeaderMap) {
+        headers.insert("x-ratelimit-limit", HeaderValue::from(self.limit));
+        headers.insert("x-ratelimit-remaining", HeaderValue::from(self.remaining));
+        headers.insert("x-ratelimit-reset", HeaderValue::from(self.reset_seconds));
+        if !self.allowed {
+            headers.insert(header::RETRY_AFTER, HeaderValue::from(self.reset_seconds));
+        }
+    }
+}
+
+fn window_key(api_key_id: Uuid) -> String {
+    format!("inventiv:ratelimit:api_key:{}", api_key_id)
+}
+
+/// Count a request in the key's sliding window (rejected requests are not counted).
+/// None when Redis is unavailable: requests are then let through without headers.
+pub async fn check_api_key_rate_limit(
+    redis: &redis::Client,
+    api_key_id: Uuid,
+    limit: u32,
+) -> Option<RateLimitStatus> {
+    let mut conn = redis.get_multiplexed_async_connection().await.ok()?;
+    let key = window_key(api_key_id);
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let member = format!("{}-{}", now_ms, Uuid::new_v4());
+
+    let (count, oldest): (u64, Vec<(String, f64)>) = redis::pipe()
+        .atomic()
+        .cmd("ZREMRANGEBYSCORE")
+        .arg(&key)
+        .arg("-inf")
+        .arg(now_ms - WINDOW_MS)
+        .ignore()
+        .cmd("ZADD")
+        .arg(&key)
+        .arg(now_ms)
+        .arg(&member)
+        .ignore()
+        .cmd("ZCARD")
+        .arg(&key)
+        .cmd("ZRANGE")
+        .arg(&key)
+        .arg(0)
+        .arg(0)
+        .arg("WITHSCORES")
+        .cmd("PEXPIRE")
+        .arg(&key)
+        .arg(WINDOW_MS)
+        .ignore()
+        .query_async(&mut conn)
+        .await
+        .ok()?;
+
+    let oldest_ms = oldest.first().map(|(_, s)| *s as i64).unwrap_or(now_ms);
+    let status = RateLimitStatus::from_window(limit, count, oldest_ms, now_ms);
+    if !status.allowed {
+        let _ = redis::cmd("ZREM")
+            .arg(&key)
+            .arg(&member)
+            .query_async::<_, i64>(&mut conn)
+            .await;
+    }
+    Some(status)
+}
+
+/// Middleware for /v1/* (runs after auth): enforce the API key's rate limit and expose
+/// `X-RateLimit-*` headers. Session users and keys without a limit pass through without headers.
+pub async fn enforce_api_key_rate_limit(
+    State(redis): State<redis::Client>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let scope = req.extensions().get::<ApiKeyPrincipal>().and_then(|p| {
+        p.rate_limit_per_minute
+            .and_then(|l| u32::try_from(l).ok())
+            .filter(|l| *l > 0)
+            .map(|l| (p.api_key_id, l))
+    });
+    let Some((api_key_id, limit)) = scope else {
+        return next.run(req).await;
+    };
+    let Some(status) = check_api_key_rate_limit(&redis, api_key_id, limit).await else {
+        return next.run(req).await;
+    };
+
+    let mut resp = if status.allowed {
+        next.run(req).await
+    } else {
+        (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(json!({
+                "error": "rate_limited",
+                "message": format!("Rate limit of {} requests per minute exceeded", limit)
+            })),
+        )
+            .into_response()
+    };
+    status.apply_headers(resp.headers_mut());
+    resp
+}
+
+fn streams_key(api_key_id: Uuid) -> String {
+    format!("inventiv:streams:api_key:{}", api_key_id)
+}
+
+/// Safety TTL on open-stream counters, so a crashed API process cannot pin a key at its cap.
+const STREAMS_TTL_SECONDS: i64 = 3600;
+
+/// A key already holds `api_keys.max_concurrent_streams` open streams.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooManyStreams {
+    pub limit: u32,
+}
+
+impl IntoResponse for TooManyStreams {
+    fn into_response(self) -> Response {
+        (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(json!({
+                "error": "too_many_concurrent_streams",
+                "message": format!("At most {} concurrent streams allowed for this API key", self.limit)
+            })),
+        )
+            .into_response()
+    }
+}
+
+/// Open stream counted against an API key's concurrent-stream limit; released (DECR) when dropped.
+pub struct StreamSlot {
+    redis: redis::Client,
+    api_key_id: Uuid,
+}
+
+/// Count a stream against the key's limit. Ok(None) when Redis is unavailable: the stream is
+/// then let through uncounted.
+pub async fn acquire_stream_slot(
+    redis: &redis::Client,
+    api_key_id: Uuid,
+    limit: u32,
+) -> Result<Option<StreamSlot>, TooManyStreams> {
+    let Ok(mut conn) = redis.get_multiplexed_async_connection().await else {
+        return Ok(None);
+    };
+    let key = streams_key(api_key_id);
+    let Ok((open,)): Result<(i64,), _> = redis::pipe()
+        .atomic()
+        .cmd("INCR")
+        .arg(&key)
+        .cmd("EXPIRE")
+        .arg(&key)
+        .arg(STREAMS_TTL_SECONDS)
+        .ignore()
+        .query_async(&mut conn)
+        .await
+    else {
+        return Ok(None);
+    };
+    if open > i64::from(limit) {
+        let _ = redis::cmd("DECR")
+            .arg(&key)
+            .query_async::<_, i64>(&mut conn)
+            .await;
+        return Err(TooManyStreams { limit });
+    }
+    Ok(Some(StreamSlot {
+        redis: redis.clone(),
+        api_key_id,
+    }))
+}
+
+impl Drop for StreamSlot {
+    fn drop(&mut self) {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let redis = self.redis.clone();
+        let key = streams_key(self.api_key_id);
+        handle.spawn(async move {
+            if let Ok(mut conn) = redis.get_multiplexed_async_connection().await {
+                let _ = redis::cmd("DECR")
+                    .arg(&key)
+                    .query_async::<_, i64>(&mut conn)
+                    .await;
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_status_counts_down_then_rejects() {
+        let now = 1_000_000;
+        let first = RateLimitStatus::from_window(3, 1, now, now);
+        assert_eq!(
+            (first.remaining, first.reset_seconds, first.allowed),
+            (2, 60, true)
+        );
+
+        let last = RateLimitStatus::from_window(3, 3, now - 45_500, now);
+        assert_eq!(
+            (last.remaining, last.reset_seconds, last.allowed),
+            (0, 15, true)
+        );
+
+        let over = RateLimitStatus::from_window(3, 4, now - 45_500, now);
+        assert!(!over.allowed);
+        let mut headers = HeaderMap::new();
+        over.apply_headers(&mut headers);
+        assert_eq!(headers["x-ratelimit-remaining"], "0");
+        assert_eq!(headers[header::RETRY_AFTER], headers["x-ratelimit-reset"]);
+    }
+}
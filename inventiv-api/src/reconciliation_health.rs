@@ -0,0 +1,189 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::{Pool, Postgres};
+use std::sync::Arc;
+
+use crate::auth;
+use crate::simple_logger;
+use crate::AppState;
+
+const DEFAULT_STALE_THRESHOLD_SECONDS: i64 = 900;
+const CHECK_INTERVAL_SECONDS: u64 = 60;
+
+/// Max age of the newest `instances.last_reconciliation` before reconciliation is considered
+/// stalled (`RECONCILIATION_STALE_THRESHOLD_SECONDS`, default 15 minutes).
+pub fn stale_threshold_seconds() -> i64 {
+    std::env::var("RECONCILIATION_STALE_THRESHOLD_SECONDS")
+        .ok()
+        .and_then(|s| s.trim().parse::<i64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_STALE_THRESHOLD_SECONDS)
+}
+
+/// Freshness of the orchestrator reconciliation loops, derived from `instances.last_reconciliation`.
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ReconciliationHealth {
+    /// Newest `last_reconciliation` across active instances.
+    pub last_reconciliation_at: Option<DateTime<Utc>>,
+    /// Seconds since the newest reconciliation (or since the oldest active instance was created
+    /// when none was ever reconciled).
+    pub age_seconds: Option<i64>,
+    pub threshold_seconds: i64,
+    pub active_instances: i64,
+    /// True when active instances exist and reconciliation is older than the threshold.
+    pub stale: bool,
+}
+
+/// Connection usage of the API's Postgres pool.
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DatabasePoolStats {
+    /// Configured pool size (`DATABASE_MAX_CONNECTIONS`).
+    pub max_connections: u32,
+    /// Open connections (in use + idle).
+    pub size: u32,
+    pub idle: u32,
+    /// Connections currently checked out.
+    pub in_use: u32,
+}
+
+impl DatabasePoolStats {
+    pub fn of(db: &Pool<Postgres>) -> Self {
+        let size = db.size();
+        let idle = u32::try_from(db.num_idle()).unwrap_or(u32::MAX).min(size);
+        Self {
+            max_connections: db.options().get_max_connections(),
+            size,
+            idle,
+            in_use: size - idle,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct AdminOverview {
+    pub reconciliation: ReconciliationHealth,
+    pub database_pool: DatabasePoolStats,
+}
+
+pub async fn reconciliation_health(
+    db: &Pool<Postgres>,
+    threshold_seconds: i64,
+) -> Result<ReconciliationHealth, sqlx::Error> {
+    let (active_instances, last_reconciliation_at, age_seconds): (
+        i64,
+        Option<DateTime<Utc>>,
+        Option<i64>,
+    ) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*)::bigint,
+               MAX(last_reconciliation),
+               EXTRACT(EPOCH FROM (NOW() - COALESCE(
+                 MAX(last_reconciliation),
+                 MIN(created_at)
+               )))::bigint
+        FROM instances
+        WHERE status NOT IN ('terminated', 'archived', 'provisioning_failed', 'startup_failed', 'failed')
+        "#,
+    )
+    .fetch_one(db)
+    .await?;
+    Ok(ReconciliationHealth {
+        last_reconciliation_at,
+        age_seconds,
+        threshold_seconds,
+        active_instances,
+        stale: active_instances > 0 && age_seconds.is_some_and(|a| a > threshold_seconds),
+    })
+}
+
+/// Emit a `RECONCILIATION_STALE` action log when reconciliation is stale. Logged once per stale
+/// episode: not repeated while no reconciliation happened since the previous alert.
+/// Returns true when an alert was emitted.
+pub async fn check_and_alert(db: &Pool<Postgres>, threshold_seconds: i64) -> bool {
+    let health = match reconciliation_health(db, threshold_seconds).await {
+        Ok(h) => h,
+        Err(e) => {
+            eprintln!("❌ [reconciliation] health check failed: {}", e);
+            return false;
+        }
+    };
+    if !health.stale {
+        return false;
+    }
+
+    let already_alerted: bool = sqlx::query_scalar(
+        r#"
+        SELECT EXISTS (
+          SELECT 1 FROM action_logs
+          WHERE action_type = 'RECONCILIATION_STALE'
+            AND ($1::timestamptz IS NULL OR created_at > $1)
+        )
+        "#,
+    )
+    .bind(health.last_reconciliation_at)
+    .fetch_one(db)
+    .await
+    .unwrap_or(false);
+    if already_alerted {
+        return false;
+    }
+
+    let message = format!(
+        "No instance reconciliation for {}s (threshold {}s)",
+        health.age_seconds.unwrap_or_default(),
+        health.threshold_seconds
+    );
+    eprintln!("⚠️ [reconciliation] {}", message);
+    simple_logger::log_action_with_metadata(
+        db,
+        "RECONCILIATION_STALE",
+        "failed",
+        None,
+        Some(&message),
+        serde_json::to_value(&health).ok(),
+    )
+    .await
+    .is_ok()
+}
+
+/// Background alert loop (spawned at startup).
+pub async fn run_monitor(db: Pool<Postgres>) {
+    let mut interval =
+        tokio::time::interval(std::time::Duration::from_secs(CHECK_INTERVAL_SECONDS));
+    loop {
+        interval.tick().await;
+        check_and_alert(&db, stale_threshold_seconds()).await;
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/overview",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Platform health overview", body = AdminOverview),
+        (status = 403, description = "Admin required")
+    )
+)]
+pub async fn get_admin_overview(
+    State(state): State<Arc<AppState>>,
+    axum::extract::Extension(user): axum::extract::Extension<auth::AuthUser>,
+) -> impl IntoResponse {
+    if let Err(e) = auth::require_admin(&user) {
+        return e.into_response();
+    }
+    match reconciliation_health(&state.db, stale_threshold_seconds()).await {
+        Ok(reconciliation) => Json(AdminOverview {
+            reconciliation,
+            database_pool: DatabasePoolStats::of(&state.db),
+        })
+        .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error":"db_error","message": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
@@ -1,6 +1,8 @@
 // OpenAI-compatible proxy routes (auth = cookie/JWT OR API key)
 use crate::app::AppState;
 use crate::auth;
+use crate::maintenance;
+use crate::rate_limit;
 use axum::middleware;
 use axum::routing::{get, post};
 use axum::Router;
@@ -10,6 +12,7 @@ use crate::handlers::openai::openai_list_models;
 use crate::handlers::openai::openai_proxy_chat_completions;
 use crate::handlers::openai::openai_proxy_completions;
 use crate::handlers::openai::openai_proxy_embeddings;
+use crate::handlers::openai::openai_proxy_passthrough;
 
 /// Create OpenAI proxy routes router
 pub fn create_openai_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
@@ -18,8 +21,23 @@ pub fn create_openai_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
         .route("/v1/chat/completions", post(openai_proxy_chat_completions))
         .route("/v1/completions", post(openai_proxy_completions))
         .route("/v1/embeddings", post(openai_proxy_embeddings))
+        // Allowlisted vLLM endpoints (OPENAI_PROXY_PASSTHROUGH_PATHS); 404 otherwise.
+        .route(
+            "/v1/{*path}",
+            get(openai_proxy_passthrough).post(openai_proxy_passthrough),
+        )
+        // Innermost: needs the API key principal inserted by auth.
+        .route_layer(middleware::from_fn_with_state(
+            state.redis_client.clone(),
+            rate_limit::enforce_api_key_rate_limit,
+        ))
         .route_layer(middleware::from_fn_with_state(
             state.db.clone(),
             auth::require_user_or_api_key,
         ))
+        // Outermost: maintenance mode short-circuits before auth/proxying.
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            maintenance::reject_during_maintenance,
+        ))
 }
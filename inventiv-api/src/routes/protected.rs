@@ -9,27 +9,43 @@ use std::sync::Arc;
 use crate::action_logs_search;
 use crate::api_keys;
 use crate::auth_endpoints;
+use crate::catalog_transfer;
 use crate::chat;
 use crate::finops;
 use crate::instance_type_zones;
 use crate::metrics;
 use crate::organizations;
+use crate::pricing_overrides;
 use crate::provider_settings;
+use crate::proxy_traces;
+use crate::reconciliation_health;
 use crate::settings;
 use crate::users_endpoint;
+use crate::volume_drift;
 
 use crate::handlers::commands::list_action_logs;
 use crate::handlers::commands::list_action_types;
+use crate::handlers::commands::list_error_codes;
 use crate::handlers::commands::manual_catalog_sync_trigger;
+use crate::handlers::commands::manual_provider_catalog_sync_trigger;
 use crate::handlers::commands::manual_reconcile_trigger;
 use crate::handlers::deployments::create_deployment;
+use crate::handlers::deployments::preview_cloud_init;
 use crate::handlers::events::events_stream;
 use crate::handlers::instances::archive_instance;
+use crate::handlers::instances::bulk_archive_instances;
+use crate::handlers::instances::bulk_plan_terminate_instances;
 use crate::handlers::instances::get_instance;
 use crate::handlers::instances::list_instances;
+use crate::handlers::instances::list_model_instances;
+use crate::handlers::instances::plan_terminate_instance;
 use crate::handlers::instances::reinstall_instance;
+use crate::handlers::instances::resize_instance;
+use crate::handlers::instances::retry_instance;
 use crate::handlers::instances::search_instances;
+use crate::handlers::instances::set_instance_routing;
 use crate::handlers::instances::terminate_instance;
+use crate::handlers::models::check_model_compat;
 use crate::handlers::models::create_model;
 use crate::handlers::models::delete_model;
 use crate::handlers::models::get_model;
@@ -54,6 +70,10 @@ pub fn create_protected_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
             "/auth/sessions/{session_id}/revoke",
             post(auth_endpoints::revoke_session_endpoint),
         )
+        .route(
+            "/auth/me/sessions/revoke-others",
+            post(auth_endpoints::revoke_other_sessions),
+        )
         // Chat (UI): list allowed models for current workspace
         .route("/chat/models", get(chat::list_chat_models))
         // Organizations (multi-tenant MVP)
@@ -99,6 +119,7 @@ pub fn create_protected_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
             "/api_keys/{id}",
             put(api_keys::update_api_key).delete(api_keys::revoke_api_key),
         )
+        .route("/api_keys/{id}/usage", get(api_keys::get_api_key_usage))
         // Runtime models (models in service + historical + counters)
         .route("/runtime/models", get(list_runtime_models))
         // GPU activity (nvtop-like)
@@ -106,6 +127,7 @@ pub fn create_protected_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
         // System activity (CPU/Mem/Disk/Network)
         .route("/system/activity", get(list_system_activity))
         .route("/deployments", post(create_deployment))
+        .route("/deployments/cloud-init-preview", post(preview_cloud_init))
         // Realtime (SSE)
         .route("/events/stream", get(events_stream))
         // Models (catalog)
@@ -122,6 +144,8 @@ pub fn create_protected_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
             "/models/{id}/recommended-data-volume",
             get(get_recommended_data_volume),
         )
+        .route("/models/{id}/check-compat", post(check_model_compat))
+        .route("/models/{id}/instances", get(list_model_instances))
         // Instances
         .route("/instances", get(list_instances))
         .route("/instances/search", get(search_instances))
@@ -130,11 +154,24 @@ pub fn create_protected_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
             get(metrics::get_instance_metrics),
         )
         .route("/instances/{id}/archive", put(archive_instance))
+        .route("/instances/bulk/archive", post(bulk_archive_instances))
+        .route(
+            "/instances/bulk/terminate/plan",
+            post(bulk_plan_terminate_instances),
+        )
         .route(
             "/instances/{id}",
             get(get_instance).delete(terminate_instance),
         )
+        .route(
+            "/instances/{id}/terminate/plan",
+            post(plan_terminate_instance),
+        )
         .route("/instances/{id}/reinstall", post(reinstall_instance))
+        .route("/instances/{id}/resize", post(resize_instance))
+        .route("/instances/{id}/retry", post(retry_instance))
+        .route("/instances/{id}/routing", post(set_instance_routing))
+        .route("/instances/{id}/cost", get(finops::get_instance_cost))
         // Action logs
         .route("/action_logs", get(list_action_logs))
         .route(
@@ -142,9 +179,19 @@ pub fn create_protected_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
             get(action_logs_search::search_action_logs),
         )
         .route("/action_types", get(list_action_types))
+        .route("/error_codes", get(list_error_codes))
         // Commands
         .route("/reconcile", post(manual_reconcile_trigger))
         .route("/catalog/sync", post(manual_catalog_sync_trigger))
+        .route(
+            "/catalog/sync/{provider_code}",
+            post(manual_provider_catalog_sync_trigger),
+        )
+        .route("/catalog/export", get(catalog_transfer::get_catalog_export))
+        .route(
+            "/catalog/import",
+            post(catalog_transfer::post_catalog_import),
+        )
         // Settings
         .route(
             "/providers",
@@ -152,6 +199,7 @@ pub fn create_protected_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
         )
         .route("/providers/search", get(settings::search_providers))
         .route("/providers/{id}", put(settings::update_provider))
+        .route("/providers/{id}/tree", get(settings::get_provider_tree))
         .route(
             "/settings/definitions",
             get(provider_settings::list_settings_definitions),
@@ -170,6 +218,10 @@ pub fn create_protected_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
             "/providers/{id}/params",
             put(provider_settings::update_provider_params),
         )
+        .route(
+            "/providers/{id}/credentials",
+            put(provider_settings::update_provider_credentials),
+        )
         .route(
             "/providers/config-status",
             get(provider_settings::list_provider_config_status),
@@ -204,6 +256,10 @@ pub fn create_protected_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
             "/instance_types/{id}/zones",
             put(instance_type_zones::associate_zones_to_instance_type),
         )
+        .route(
+            "/instance_types/{id}/zones/availability",
+            put(instance_type_zones::set_instance_type_zone_availability),
+        )
         .route(
             "/zones/{zone_id}/instance_types",
             get(instance_type_zones::list_instance_types_for_zone),
@@ -222,6 +278,10 @@ pub fn create_protected_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
             "/finops/dashboard/costs/window",
             get(finops::get_costs_dashboard_window),
         )
+        .route(
+            "/finops/dashboard/costs/by-cost-center",
+            get(finops::get_costs_dashboard_by_cost_center),
+        )
         .route(
             "/finops/dashboard/costs/series",
             get(finops::get_costs_dashboard_series),
@@ -238,6 +298,26 @@ pub fn create_protected_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
             "/finops/cost/cumulative/minute",
             get(finops::get_cost_cumulative_series),
         )
+        // Pricing overrides (reserved/committed-use discounts)
+        .route(
+            "/finops/pricing-overrides",
+            get(pricing_overrides::list_pricing_overrides)
+                .post(pricing_overrides::create_pricing_override),
+        )
+        .route(
+            "/finops/pricing-overrides/{id}",
+            put(pricing_overrides::update_pricing_override)
+                .delete(pricing_overrides::delete_pricing_override),
+        )
+        // Provider volume drift (admin, observability)
+        .route("/admin/volumes/drift", get(volume_drift::get_volume_drift))
+        // Platform health overview (admin): reconciliation freshness
+        .route(
+            "/admin/overview",
+            get(reconciliation_health::get_admin_overview),
+        )
+        // Sampled OpenAI proxy request/response captures (admin, debugging)
+        .route("/admin/proxy_traces", get(proxy_traces::list_proxy_traces))
         // Users management
         .route(
             "/users",
@@ -250,6 +330,10 @@ pub fn create_protected_routes(state: Arc<AppState>) -> Router<Arc<AppState>> {
                 .put(users_endpoint::update_user)
                 .delete(users_endpoint::delete_user),
         )
+        .route(
+            "/users/{id}/revoke-sessions",
+            post(users_endpoint::revoke_user_sessions),
+        )
         .route_layer(middleware::from_fn_with_state(
             state.db.clone(),
             auth::require_user,
@@ -1,9 +1,11 @@
 // Worker internal routes (worker auth handled in handler + orchestrator)
 use crate::app::AppState;
-use axum::routing::post;
+use axum::routing::{get, post};
 use axum::Router;
 use std::sync::Arc;
 
+use crate::handlers::worker::proxy_worker_config;
+use crate::handlers::worker::proxy_worker_deregister;
 use crate::handlers::worker::proxy_worker_heartbeat;
 use crate::handlers::worker::proxy_worker_register;
 
@@ -12,4 +14,6 @@ pub fn create_worker_routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/internal/worker/register", post(proxy_worker_register))
         .route("/internal/worker/heartbeat", post(proxy_worker_heartbeat))
+        .route("/internal/worker/deregister", post(proxy_worker_deregister))
+        .route("/internal/worker/config", get(proxy_worker_config))
 }
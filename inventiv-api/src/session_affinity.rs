@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+const DEFAULT_BACKEND_HEADER: &str = "x-worker-backend";
+/// Bound on tracked (instance, session) pairs; the map is reset when exceeded.
+const MAX_TRACKED_SESSIONS: usize = 10_000;
+
+/// Diagnostic check that worker-local sticky routing (HAProxy on `x-inventiv-session`) holds.
+///
+/// Opt-in (`OPENAI_PROXY_AFFINITY_CHECK=1`): the worker echoes the vLLM backend that served a
+/// request in a response header (`OPENAI_PROXY_BACKEND_HEADER`, default `x-worker-backend`).
+/// A session seen on two different backends of the same instance counts as an affinity break.
+#[derive(Default)]
+pub struct AffinityTracker {
+    backends: Mutex<HashMap<(Uuid, String), String>>,
+    breaks: AtomicU64,
+}
+
+impl AffinityTracker {
+    /// Response header carrying the worker backend id, or None when the check is disabled.
+    pub fn backend_header_from_env() -> Option<String> {
+        let enabled = std::env::var("OPENAI_PROXY_AFFINITY_CHECK")
+            .ok()
+            .map(|v| {
+                matches!(
+                    v.trim().to_ascii_lowercase().as_str(),
+                    "1" | "true" | "yes" | "on"
+                )
+            })
+            .unwrap_or(false);
+        if !enabled {
+            return None;
+        }
+        let header = std::env::var("OPENAI_PROXY_BACKEND_HEADER")
+            .ok()
+            .map(|s| s.trim().to_ascii_lowercase())
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| DEFAULT_BACKEND_HEADER.to_string());
+        Some(header)
+    }
+
+    /// Record the backend that served `session` on `instance_id`.
+    /// Returns the previous backend when it differs (broken affinity).
+    pub fn observe(&self, instance_id: Uuid, session: &str, backend: &str) -> Option<String> {
+        let mut backends = self.backends.lock().unwrap_or_else(|e| e.into_inner());
+        if backends.len() >= MAX_TRACKED_SESSIONS {
+            backends.clear();
+        }
+        let previous = backends.insert((instance_id, session.to_string()), backend.to_string());
+        match previous {
+            Some(p) if p != backend => {
+                self.breaks.fetch_add(1, Ordering::Relaxed);
+                Some(p)
+            }
+            _ => None,
+        }
+    }
+
+    /// Number of affinity breaks observed so far.
+    pub fn breaks(&self) -> u64 {
+        self.breaks.load(Ordering::Relaxed)
+    }
+}
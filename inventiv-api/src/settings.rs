@@ -33,6 +33,8 @@ pub struct CreateRegionRequest {
 #[derive(Deserialize, utoipa::ToSchema)]
 pub struct CreateZoneRequest {
     pub region_id: Uuid,
+    /// Optional provider context: when set, the region must belong to this provider.
+    pub provider_id: Option<Uuid>,
     pub name: String,
     pub code: String,
     pub is_active: Option<bool>,
@@ -137,6 +139,8 @@ pub struct UpdateRegionRequest {
 
 #[derive(Deserialize, utoipa::ToSchema)]
 pub struct UpdateZoneRequest {
+    /// Move the zone to another region of the same provider.
+    pub region_id: Option<Uuid>,
     pub code: Option<String>,
     pub name: Option<String>,
     pub is_active: Option<bool>,
@@ -153,6 +157,42 @@ pub struct UpdateInstanceTypeRequest {
     pub allocation_params: Option<serde_json::Value>,
 }
 
+// --- Hierarchy validation (provider -> region -> zone) ---
+
+fn hierarchy_violation(code: &str, message: &str) -> axum::response::Response {
+    (
+        StatusCode::UNPROCESSABLE_ENTITY,
+        Json(serde_json::json!({"error": code, "message": message})),
+    )
+        .into_response()
+}
+
+async fn provider_exists(db: &sqlx::Pool<sqlx::Postgres>, provider_id: Uuid) -> bool {
+    sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM providers WHERE id = $1)")
+        .bind(provider_id)
+        .fetch_one(db)
+        .await
+        .unwrap_or(false)
+}
+
+async fn region_provider_id(db: &sqlx::Pool<sqlx::Postgres>, region_id: Uuid) -> Option<Uuid> {
+    sqlx::query_scalar("SELECT provider_id FROM regions WHERE id = $1")
+        .bind(region_id)
+        .fetch_optional(db)
+        .await
+        .unwrap_or(None)
+}
+
+async fn zone_provider_id(db: &sqlx::Pool<sqlx::Postgres>, zone_id: Uuid) -> Option<Uuid> {
+    sqlx::query_scalar(
+        "SELECT r.provider_id FROM zones z JOIN regions r ON r.id = z.region_id WHERE z.id = $1",
+    )
+    .bind(zone_id)
+    .fetch_optional(db)
+    .await
+    .unwrap_or(None)
+}
+
 // --- Handlers ---
 
 // Regions
@@ -182,13 +222,17 @@ pub async fn list_regions(State(state): State<Arc<AppState>>) -> Json<Vec<Region
     request_body = CreateRegionRequest,
     responses(
         (status = 201, description = "Region created", body = inventiv_common::Region),
-        (status = 409, description = "Conflict")
+        (status = 409, description = "Conflict"),
+        (status = 422, description = "Provider not found")
     )
 )]
 pub async fn create_region(
     State(state): State<Arc<AppState>>,
     Json(req): Json<CreateRegionRequest>,
 ) -> impl IntoResponse {
+    if !provider_exists(&state.db, req.provider_id).await {
+        return hierarchy_violation("provider_not_found", "provider_id does not exist");
+    }
     let id = Uuid::new_v4();
     let is_active = req.is_active.unwrap_or(true);
     let res = sqlx::query_as::<_, Region>(
@@ -380,13 +424,23 @@ pub async fn list_zones(State(state): State<Arc<AppState>>) -> Json<Vec<Zone>> {
     request_body = CreateZoneRequest,
     responses(
         (status = 201, description = "Zone created", body = inventiv_common::Zone),
-        (status = 409, description = "Conflict")
+        (status = 409, description = "Conflict"),
+        (status = 422, description = "Region not found or belongs to another provider")
     )
 )]
 pub async fn create_zone(
     State(state): State<Arc<AppState>>,
     Json(req): Json<CreateZoneRequest>,
 ) -> impl IntoResponse {
+    let Some(region_provider) = region_provider_id(&state.db, req.region_id).await else {
+        return hierarchy_violation("region_not_found", "region_id does not exist");
+    };
+    if req.provider_id.is_some_and(|p| p != region_provider) {
+        return hierarchy_violation(
+            "region_provider_mismatch",
+            "region_id does not belong to provider_id",
+        );
+    }
     let id = Uuid::new_v4();
     let is_active = req.is_active.unwrap_or(true);
     let res = sqlx::query_as::<_, Zone>(
@@ -523,7 +577,8 @@ pub async fn search_zones(
     request_body = UpdateZoneRequest,
     responses(
         (status = 200, description = "Zone updated"),
-        (status = 404, description = "Zone not found")
+        (status = 404, description = "Zone not found"),
+        (status = 422, description = "Region not found or belongs to another provider")
     )
 )]
 pub async fn update_zone(
@@ -531,31 +586,50 @@ pub async fn update_zone(
     Path(id): Path<Uuid>,
     Json(req): Json<UpdateZoneRequest>,
 ) -> impl IntoResponse {
+    if let Some(region_id) = req.region_id {
+        // A zone can only move between regions of its own provider: instance types and
+        // instance_type_zones associations are provider-scoped.
+        let Some(current_provider) = zone_provider_id(&state.db, id).await else {
+            return StatusCode::NOT_FOUND.into_response();
+        };
+        let Some(target_provider) = region_provider_id(&state.db, region_id).await else {
+            return hierarchy_violation("region_not_found", "region_id does not exist");
+        };
+        if target_provider != current_provider {
+            return hierarchy_violation(
+                "region_provider_mismatch",
+                "region_id belongs to another provider than the zone",
+            );
+        }
+    }
+
     let result = sqlx::query(
         "UPDATE zones SET 
             code = COALESCE($1, code), 
             name = COALESCE($2, name), 
-            is_active = COALESCE($3, is_active)
+            is_active = COALESCE($3, is_active),
+            region_id = COALESCE($5, region_id)
          WHERE id = $4",
     )
     .bind(req.code)
     .bind(req.name)
     .bind(req.is_active)
     .bind(id)
+    .bind(req.region_id)
     .execute(&state.db)
     .await;
 
     match result {
         Ok(res) => {
             if res.rows_affected() > 0 {
-                StatusCode::OK
+                StatusCode::OK.into_response()
             } else {
-                StatusCode::NOT_FOUND
+                StatusCode::NOT_FOUND.into_response()
             }
         }
         Err(e) => {
             eprintln!("Error updating zone: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
         }
     }
 }
@@ -593,13 +667,17 @@ pub async fn list_instance_types(State(state): State<Arc<AppState>>) -> Json<Vec
     request_body = CreateInstanceTypeRequest,
     responses(
         (status = 201, description = "Instance type created", body = inventiv_common::InstanceType),
-        (status = 409, description = "Conflict")
+        (status = 409, description = "Conflict"),
+        (status = 422, description = "Provider not found")
     )
 )]
 pub async fn create_instance_type(
     State(state): State<Arc<AppState>>,
     Json(req): Json<CreateInstanceTypeRequest>,
 ) -> impl IntoResponse {
+    if !provider_exists(&state.db, req.provider_id).await {
+        return hierarchy_violation("provider_not_found", "provider_id does not exist");
+    }
     let id = Uuid::new_v4();
     let is_active = req.is_active.unwrap_or(true);
     let cpu_count = req.cpu_count.unwrap_or(0);
@@ -864,7 +942,10 @@ pub async fn create_provider(
     .await;
 
     match res {
-        Ok(row) => (StatusCode::CREATED, Json(row)).into_response(),
+        Ok(row) => {
+            state.provider_codes.invalidate();
+            (StatusCode::CREATED, Json(row)).into_response()
+        }
         Err(e) => {
             let msg = e.to_string();
             let code = if msg.contains("duplicate key") {
@@ -997,6 +1078,7 @@ pub async fn update_provider(
 
     match result {
         Ok(res) => {
+            state.provider_codes.invalidate();
             if res.rows_affected() > 0 {
                 StatusCode::OK
             } else {
@@ -1009,3 +1091,190 @@ pub async fn update_provider(
         }
     }
 }
+
+// Provider tree (provider -> regions -> zones -> instance types)
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ProviderTree {
+    pub id: Uuid,
+    pub name: String,
+    pub code: Option<String>,
+    pub is_active: bool,
+    pub regions: Vec<ProviderTreeRegion>,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ProviderTreeRegion {
+    pub id: Uuid,
+    pub name: String,
+    pub code: Option<String>,
+    pub is_active: bool,
+    pub zones: Vec<ProviderTreeZone>,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ProviderTreeZone {
+    pub id: Uuid,
+    pub name: String,
+    pub code: Option<String>,
+    pub is_active: bool,
+    /// Instance types offered in this zone (`instance_type_zones`).
+    pub instance_types: Vec<ProviderTreeInstanceType>,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ProviderTreeInstanceType {
+    pub id: Uuid,
+    pub name: String,
+    pub code: Option<String>,
+    pub gpu_count: i32,
+    pub vram_per_gpu_gb: i32,
+    pub cpu_count: i32,
+    pub ram_gb: i32,
+    pub bandwidth_bps: i64,
+    pub cost_per_hour: Option<f64>,
+    pub is_active: bool,
+    /// Availability of this type in this zone.
+    pub is_available: bool,
+}
+
+#[derive(FromRow)]
+struct ProviderTreeRow {
+    region_id: Uuid,
+    region_name: String,
+    region_code: Option<String>,
+    region_is_active: bool,
+    zone_id: Option<Uuid>,
+    zone_name: Option<String>,
+    zone_code: Option<String>,
+    zone_is_active: Option<bool>,
+    type_id: Option<Uuid>,
+    type_name: Option<String>,
+    type_code: Option<String>,
+    gpu_count: Option<i32>,
+    vram_per_gpu_gb: Option<i32>,
+    cpu_count: Option<i32>,
+    ram_gb: Option<i32>,
+    bandwidth_bps: Option<i64>,
+    cost_per_hour: Option<f64>,
+    type_is_active: Option<bool>,
+    is_available: Option<bool>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/providers/{id}/tree",
+    tag = "Settings",
+    params(("id" = Uuid, Path, description = "Provider id")),
+    responses(
+        (status = 200, description = "Regions, zones and instance types of the provider", body = ProviderTree),
+        (status = 404, description = "Provider not found")
+    )
+)]
+pub async fn get_provider_tree(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    let provider = match sqlx::query_as::<_, Provider>(
+        "SELECT id, name, code, description, is_active FROM providers WHERE id = $1",
+    )
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await
+    {
+        Ok(Some(p)) => p,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "provider_not_found"})),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            eprintln!("Error fetching provider tree: {:?}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let rows = match sqlx::query_as::<_, ProviderTreeRow>(
+        r#"
+        SELECT
+          r.id AS region_id, r.name AS region_name, r.code AS region_code, r.is_active AS region_is_active,
+          z.id AS zone_id, z.name AS zone_name, z.code AS zone_code, z.is_active AS zone_is_active,
+          it.id AS type_id, it.name AS type_name, it.code AS type_code,
+          it.gpu_count, it.vram_per_gpu_gb, it.cpu_count, it.ram_gb, it.bandwidth_bps, CAST(it.cost_per_hour AS DOUBLE PRECISION) AS cost_per_hour,
+          it.is_active AS type_is_active, COALESCE(itz.is_available, false) AS is_available
+        FROM regions r
+        LEFT JOIN zones z ON z.region_id = r.id
+        LEFT JOIN instance_type_zones itz ON itz.zone_id = z.id
+        LEFT JOIN instance_types it ON it.id = itz.instance_type_id AND it.provider_id = r.provider_id
+        WHERE r.provider_id = $1
+        ORDER BY r.name, r.id, z.name, z.id, it.name, it.id
+        "#,
+    )
+    .bind(id)
+    .fetch_all(&state.db)
+    .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            eprintln!("Error fetching provider tree: {:?}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    // Rows are ordered by region then zone, so each level only needs to look at its last entry.
+    let mut regions: Vec<ProviderTreeRegion> = Vec::new();
+    for row in rows {
+        if regions.last().map(|r| r.id) != Some(row.region_id) {
+            regions.push(ProviderTreeRegion {
+                id: row.region_id,
+                name: row.region_name,
+                code: row.region_code,
+                is_active: row.region_is_active,
+                zones: Vec::new(),
+            });
+        }
+        let zones = &mut regions.last_mut().expect("region pushed above").zones;
+        let (Some(zone_id), Some(zone_name)) = (row.zone_id, row.zone_name) else {
+            continue;
+        };
+        if zones.last().map(|z| z.id) != Some(zone_id) {
+            zones.push(ProviderTreeZone {
+                id: zone_id,
+                name: zone_name,
+                code: row.zone_code,
+                is_active: row.zone_is_active.unwrap_or(false),
+                instance_types: Vec::new(),
+            });
+        }
+        if let (Some(type_id), Some(type_name)) = (row.type_id, row.type_name) {
+            zones
+                .last_mut()
+                .expect("zone pushed above")
+                .instance_types
+                .push(ProviderTreeInstanceType {
+                    id: type_id,
+                    name: type_name,
+                    code: row.type_code,
+                    gpu_count: row.gpu_count.unwrap_or(0),
+                    vram_per_gpu_gb: row.vram_per_gpu_gb.unwrap_or(0),
+                    cpu_count: row.cpu_count.unwrap_or(0),
+                    ram_gb: row.ram_gb.unwrap_or(0),
+                    bandwidth_bps: row.bandwidth_bps.unwrap_or(0),
+                    cost_per_hour: row.cost_per_hour,
+                    is_active: row.type_is_active.unwrap_or(false),
+                    is_available: row.is_available.unwrap_or(false),
+                });
+        }
+    }
+
+    Json(ProviderTree {
+        id: provider.id,
+        name: provider.name,
+        code: provider.code,
+        is_active: provider.is_active,
+        regions,
+    })
+    .into_response()
+}
@@ -0,0 +1,72 @@
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+/// How long a cached global setting is served before re-reading the DB. Writes through
+/// `/settings/global` clear the cache of the API process that handled them right away; other
+/// replicas pick the change up within this window.
+pub const SETTINGS_CACHE_TTL: Duration = Duration::from_secs(2);
+
+/// A single value that expires `ttl` after it was stored.
+pub struct TtlValue<T> {
+    ttl: Duration,
+    slot: RwLock<Option<(Instant, T)>>,
+}
+
+impl<T: Clone> TtlValue<T> {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            slot: RwLock::new(None),
+        }
+    }
+
+    /// The cached value, unless it is missing or expired.
+    pub fn get(&self) -> Option<T> {
+        let slot = self.slot.read().ok()?;
+        match slot.as_ref() {
+            Some((at, v)) if at.elapsed() < self.ttl => Some(v.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn set(&self, value: T) {
+        if let Ok(mut slot) = self.slot.write() {
+            *slot = Some((Instant::now(), value));
+        }
+    }
+
+    pub fn invalidate(&self) {
+        if let Ok(mut slot) = self.slot.write() {
+            *slot = None;
+        }
+    }
+}
+
+/// Global settings read on the inference hot path, cached per API process.
+pub struct SettingsCache {
+    /// MAINTENANCE_MODE, resolved with its env fallback.
+    pub maintenance_mode: TtlValue<bool>,
+    /// OPENAI_PROXY_CONNECT_TIMEOUT_MS, resolved with its env fallback.
+    pub connect_timeout: TtlValue<Duration>,
+    /// OPENAI_PROXY_PASSTHROUGH_PATHS, parsed.
+    pub passthrough_paths: TtlValue<Vec<String>>,
+}
+
+impl Default for SettingsCache {
+    fn default() -> Self {
+        Self {
+            maintenance_mode: TtlValue::new(SETTINGS_CACHE_TTL),
+            connect_timeout: TtlValue::new(SETTINGS_CACHE_TTL),
+            passthrough_paths: TtlValue::new(SETTINGS_CACHE_TTL),
+        }
+    }
+}
+
+impl SettingsCache {
+    /// Drop every cached value (call after any global_settings write).
+    pub fn invalidate(&self) {
+        self.maintenance_mode.invalidate();
+        self.connect_timeout.invalidate();
+        self.passthrough_paths.invalidate();
+    }
+}
@@ -0,0 +1,140 @@
+use axum::body::{Body, Bytes};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::OnceCell;
+
+const DEFAULT_WINDOW_MS: u64 = 5000;
+
+/// Buffered upstream response handed to every caller of a coalesced flight.
+#[derive(Clone)]
+pub struct SharedResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+impl SharedResponse {
+    async fn buffer(resp: Response) -> Self {
+        let (parts, body) = resp.into_parts();
+        let body = axum::body::to_bytes(body, usize::MAX)
+            .await
+            .unwrap_or_default();
+        Self {
+            status: parts.status,
+            headers: parts.headers,
+            body,
+        }
+    }
+}
+
+impl IntoResponse for SharedResponse {
+    fn into_response(self) -> Response {
+        let mut resp = (self.status, Body::from(self.body)).into_response();
+        *resp.headers_mut() = self.headers;
+        resp
+    }
+}
+
+struct Flight {
+    started: Instant,
+    result: Arc<OnceCell<SharedResponse>>,
+}
+
+/// Opt-in coalescing of identical concurrent non-streaming proxy requests
+/// (`OPENAI_PROXY_SINGLE_FLIGHT=1`).
+///
+/// Requests with the same key arriving while a flight is running share its upstream call and
+/// response. Flights are per caller (API key, or user and organization), so every caller is
+/// billed for its own upstream call. A flight only accepts joiners for `OPENAI_PROXY_SINGLE_FLIGHT_WINDOW_MS` after it
+/// started; it is forgotten as soon as it completes, so this never acts as a response cache.
+#[derive(Default)]
+pub struct SingleFlight {
+    inflight: Mutex<HashMap<String, Flight>>,
+}
+
+impl SingleFlight {
+    /// Coalescing window, or None when single-flight is disabled.
+    pub fn window_from_env() -> Option<Duration> {
+        let enabled = std::env::var("OPENAI_PROXY_SINGLE_FLIGHT")
+            .ok()
+            .map(|v| {
+                matches!(
+                    v.trim().to_ascii_lowercase().as_str(),
+                    "1" | "true" | "yes" | "on"
+                )
+            })
+            .unwrap_or(false);
+        if !enabled {
+            return None;
+        }
+        let ms = std::env::var("OPENAI_PROXY_SINGLE_FLIGHT_WINDOW_MS")
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(DEFAULT_WINDOW_MS);
+        Some(Duration::from_millis(ms))
+    }
+
+    /// Flight key for a request: route, calling principal, resolved model and a digest of the
+    /// forwarded body.
+    pub fn key(path: &str, principal: &str, model: &str, body: &[u8]) -> String {
+        format!(
+            "{}|{}|{}|{:x}",
+            path,
+            principal,
+            model,
+            Sha256::digest(body)
+        )
+    }
+
+    /// Run `call` unless an identical flight started within `window` is still running, in which
+    /// case wait for its response instead. Returns the response and whether it was shared.
+    pub async fn run<F>(&self, key: String, window: Duration, call: F) -> (SharedResponse, bool)
+    where
+        F: Future<Output = Response>,
+    {
+        let result = {
+            let mut inflight = self.inflight.lock().unwrap_or_else(|e| e.into_inner());
+            match inflight.get(&key) {
+                Some(f) if f.started.elapsed() <= window => f.result.clone(),
+                _ => {
+                    let result = Arc::new(OnceCell::new());
+                    inflight.insert(
+                        key.clone(),
+                        Flight {
+                            started: Instant::now(),
+                            result: result.clone(),
+                        },
+                    );
+                    result
+                }
+            }
+        };
+
+        // If the caller running the flight goes away, a waiting caller takes over with its own call.
+        let mut ran = false;
+        let shared = result
+            .get_or_init(|| async {
+                ran = true;
+                SharedResponse::buffer(call.await).await
+            })
+            .await
+            .clone();
+
+        if ran {
+            let mut inflight = self.inflight.lock().unwrap_or_else(|e| e.into_inner());
+            if inflight
+                .get(&key)
+                .is_some_and(|f| Arc::ptr_eq(&f.result, &result))
+            {
+                inflight.remove(&key);
+            }
+        }
+        (shared, !ran)
+    }
+}
@@ -0,0 +1,111 @@
+//! Sort allowlists for list/search endpoints.
+//!
+//! Query params only ever pick a key from a static allowlist; the SQL expression comes from the
+//! table, never from the request. An omitted param falls back to the endpoint's documented default,
+//! but an unknown field is rejected with 400 rather than silently ignored (the UI would otherwise
+//! show a "sorted" column that isn't).
+
+use axum::http::StatusCode;
+use axum::Json;
+use serde_json::json;
+
+/// Maps a public sort key to the SQL expression it orders by.
+pub type SortAllowlist = &'static [(&'static str, &'static str)];
+
+/// 400 response for an unknown sort field.
+pub type SortError = (StatusCode, Json<serde_json::Value>);
+
+/// `GET /instances/search?sort_by=` (default: `created_at`).
+pub const INSTANCE_SEARCH_SORT: SortAllowlist = &[
+    ("created_at", "i.created_at"),
+    ("status", "i.status"),
+    ("provider", "p.name"),
+    ("region", "r.name"),
+    ("zone", "z.name"),
+    ("type", "it.name"),
+    ("model", "m.name"),
+    ("gpu_count", "it.gpu_count"),
+    ("cost_per_hour", "it.cost_per_hour"),
+    ("total_cost", "public.instance_total_cost(i, NOW())"),
+];
+pub const INSTANCE_SEARCH_SORT_DEFAULT: &str = "created_at";
+
+/// `GET /models?order_by=` (default: `name`).
+pub const MODELS_SORT: SortAllowlist = &[
+    ("name", "name"),
+    ("model_id", "model_id"),
+    ("required_vram_gb", "required_vram_gb"),
+    ("context_length", "context_length"),
+    ("data_volume_gb", "data_volume_gb"),
+    ("is_active", "is_active"),
+    ("created_at", "created_at"),
+    ("updated_at", "updated_at"),
+];
+pub const MODELS_SORT_DEFAULT: &str = "name";
+
+/// Resolve `requested` (or `default` when omitted) to its SQL expression.
+pub fn resolve(
+    param: &str,
+    requested: Option<&str>,
+    allowlist: SortAllowlist,
+    default: &str,
+) -> Result<&'static str, SortError> {
+    let key = requested.map(str::trim).unwrap_or(default);
+    allowlist
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, expr)| *expr)
+        .ok_or_else(|| {
+            let allowed: Vec<&str> = allowlist.iter().map(|(k, _)| *k).collect();
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid_sort_field",
+                    "param": param,
+                    "message": format!(
+                        "Unknown {param} '{key}'; allowed: {}",
+                        allowed.join(", ")
+                    ),
+                    "allowed": allowed,
+                })),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn omitted_param_uses_default_and_unknown_is_rejected() {
+        assert_eq!(
+            resolve(
+                "sort_by",
+                None,
+                INSTANCE_SEARCH_SORT,
+                INSTANCE_SEARCH_SORT_DEFAULT
+            )
+            .unwrap(),
+            "i.created_at"
+        );
+        assert_eq!(
+            resolve(
+                "sort_by",
+                Some("gpu_count"),
+                INSTANCE_SEARCH_SORT,
+                "created_at"
+            )
+            .unwrap(),
+            "it.gpu_count"
+        );
+        let (status, body) = resolve(
+            "order_by",
+            Some("name; DROP"),
+            MODELS_SORT,
+            MODELS_SORT_DEFAULT,
+        )
+        .unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.0["error"], "invalid_sort_field");
+    }
+}
@@ -11,7 +11,7 @@ use sqlx::{Postgres, QueryBuilder};
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::{auth, AppState};
+use crate::{auth, simple_logger, AppState};
 
 #[derive(Debug, Serialize, sqlx::FromRow, utoipa::ToSchema)]
 pub struct UserResponse {
@@ -483,3 +483,56 @@ pub async fn delete_user(
             .into_response(),
     }
 }
+
+/// Revoke every session of a user (admin), forcing a new login everywhere.
+pub async fn revoke_user_sessions(
+    State(state): State<Arc<AppState>>,
+    axum::extract::Extension(user): axum::extract::Extension<auth::AuthUser>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    if let Err(e) = auth::require_admin(&user) {
+        return e.into_response();
+    }
+
+    let exists: bool = match sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await
+    {
+        Ok(v) => v,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error":"db_error","message": e.to_string()})),
+            )
+                .into_response();
+        }
+    };
+    if !exists {
+        return (StatusCode::NOT_FOUND, Json(json!({"error":"not_found"}))).into_response();
+    }
+
+    match auth::revoke_user_sessions(&state.db, id, None).await {
+        Ok(revoked) => {
+            let _ = simple_logger::log_action_with_metadata(
+                &state.db,
+                "REVOKE_USER_SESSIONS",
+                "success",
+                None,
+                None,
+                Some(json!({
+                    "user_id": id,
+                    "revoked_by": user.user_id,
+                    "revoked": revoked,
+                })),
+            )
+            .await;
+            Json(json!({"status":"ok","revoked": revoked})).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error":"db_error","message": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
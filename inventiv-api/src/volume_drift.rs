@@ -0,0 +1,133 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::FromRow;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::auth;
+use crate::AppState;
+
+/// Discrepancy between provider volumes and tracked `instance_volumes`, recorded by the
+/// orchestrator's job-volume-drift. Observability only: nothing is deleted from this report.
+#[derive(Debug, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct VolumeDriftItem {
+    /// `untracked_provider_volume` | `tracked_missing_on_provider`
+    pub kind: String,
+    pub provider_id: Uuid,
+    pub provider_code: Option<String>,
+    pub organization_id: Uuid,
+    pub zone_code: String,
+    pub provider_volume_id: String,
+    pub instance_id: Option<Uuid>,
+    pub instance_volume_id: Option<Uuid>,
+    pub volume_name: Option<String>,
+    pub volume_type: Option<String>,
+    pub size_bytes: Option<i64>,
+    pub volume_created_at: Option<DateTime<Utc>>,
+    pub first_seen_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    /// Seconds since the volume was created (or first seen in drift when unknown).
+    pub age_seconds: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct VolumeDriftReport {
+    pub untracked_provider_volumes: i64,
+    pub tracked_missing_on_provider: i64,
+    pub last_checked_at: Option<DateTime<Utc>>,
+    pub items: Vec<VolumeDriftItem>,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct VolumeDriftParams {
+    pub provider_id: Option<Uuid>,
+    pub zone_code: Option<String>,
+}
+
+fn db_error(e: sqlx::Error) -> axum::response::Response {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({"error":"db_error","message": e.to_string()})),
+    )
+        .into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/volumes/drift",
+    tag = "Admin",
+    params(VolumeDriftParams),
+    responses(
+        (status = 200, description = "Provider volume drift report", body = VolumeDriftReport),
+        (status = 403, description = "Admin required")
+    )
+)]
+pub async fn get_volume_drift(
+    State(state): State<Arc<AppState>>,
+    axum::extract::Extension(user): axum::extract::Extension<auth::AuthUser>,
+    Query(params): Query<VolumeDriftParams>,
+) -> impl IntoResponse {
+    if let Err(e) = auth::require_admin(&user) {
+        return e.into_response();
+    }
+    let zone = params
+        .zone_code
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+
+    let items = sqlx::query_as::<_, VolumeDriftItem>(
+        r#"
+        SELECT d.kind, d.provider_id, p.code AS provider_code, d.organization_id, d.zone_code,
+               d.provider_volume_id, d.instance_id, d.instance_volume_id, d.volume_name,
+               d.volume_type, d.size_bytes, d.volume_created_at, d.first_seen_at, d.last_seen_at,
+               EXTRACT(EPOCH FROM (NOW() - COALESCE(d.volume_created_at, d.first_seen_at)))::bigint AS age_seconds
+        FROM provider_volume_drift d
+        LEFT JOIN providers p ON p.id = d.provider_id
+        WHERE ($1::uuid IS NULL OR d.provider_id = $1)
+          AND ($2::text IS NULL OR d.zone_code = $2)
+        ORDER BY d.kind, age_seconds DESC
+        "#,
+    )
+    .bind(params.provider_id)
+    .bind(zone)
+    .fetch_all(&state.db)
+    .await;
+    let items = match items {
+        Ok(items) => items,
+        Err(e) => return db_error(e),
+    };
+
+    let gauges: Result<(i64, i64, Option<DateTime<Utc>>), sqlx::Error> = sqlx::query_as(
+        r#"
+        SELECT COALESCE(SUM(untracked_provider_volumes), 0)::bigint,
+               COALESCE(SUM(tracked_missing_on_provider), 0)::bigint,
+               MAX(checked_at)
+        FROM provider_volume_drift_checks
+        WHERE ($1::uuid IS NULL OR provider_id = $1)
+          AND ($2::text IS NULL OR zone_code = $2)
+        "#,
+    )
+    .bind(params.provider_id)
+    .bind(zone)
+    .fetch_one(&state.db)
+    .await;
+
+    match gauges {
+        Ok((untracked, missing, last_checked_at)) => Json(VolumeDriftReport {
+            untracked_provider_volumes: untracked,
+            tracked_missing_on_provider: missing,
+            last_checked_at,
+            items,
+        })
+        .into_response(),
+        Err(e) => db_error(e),
+    }
+}
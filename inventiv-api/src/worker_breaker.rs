@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+const DEFAULT_COOLDOWN_SECONDS: u64 = 30;
+
+/// Per-process circuit breaker for workers that refuse connections.
+///
+/// A connect failure (refused, unreachable, connect timeout) means the worker is most likely gone
+/// even if its last heartbeat is still fresh: the proxy trips the breaker and routes around the
+/// instance for `OPENAI_PROXY_BREAKER_COOLDOWN_SECONDS` (default 30s) instead of letting every
+/// request pay the connect timeout until the health checks catch up.
+#[derive(Default)]
+pub struct WorkerBreaker {
+    open_until: Mutex<HashMap<Uuid, Instant>>,
+    failovers: AtomicU64,
+}
+
+impl WorkerBreaker {
+    pub fn cooldown_from_env() -> Duration {
+        let secs = std::env::var("OPENAI_PROXY_BREAKER_COOLDOWN_SECONDS")
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_COOLDOWN_SECONDS);
+        Duration::from_secs(secs)
+    }
+
+    /// Stop routing to `instance_id` for `cooldown`.
+    pub fn trip(&self, instance_id: Uuid, cooldown: Duration) {
+        let mut open = self.open_until.lock().unwrap_or_else(|e| e.into_inner());
+        open.insert(instance_id, Instant::now() + cooldown);
+    }
+
+    /// Count a request retried on another worker after a connect failure.
+    pub fn record_failover(&self) {
+        self.failovers.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Number of connect failovers so far (kept apart from the per-model request counters).
+    pub fn failovers(&self) -> u64 {
+        self.failovers.load(Ordering::Relaxed)
+    }
+
+    /// Instances currently excluded from routing (expired entries are dropped).
+    pub fn open_instances(&self) -> Vec<Uuid> {
+        let mut open = self.open_until.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        open.retain(|_, until| *until > now);
+        open.keys().copied().collect()
+    }
+}
@@ -1,4 +1,5 @@
 use axum::http::HeaderMap;
+use inventiv_common::net;
 use sqlx::{Pool, Postgres};
 use uuid::Uuid;
 
@@ -9,6 +10,7 @@ struct ReadyWorkerRow {
     id: Uuid,
     ip_address: String,
     worker_vllm_port: Option<i32>,
+    worker_proxy_port: Option<i32>,
     worker_queue_depth: Option<i32>,
     worker_last_heartbeat: Option<chrono::DateTime<chrono::Utc>>,
 }
@@ -21,6 +23,74 @@ pub fn header_value(headers: &HeaderMap, name: &str) -> Option<String> {
         .map(|s| s.to_string())
 }
 
+/// SQL predicate (instances aliased `i`) for instances allowed to receive inference traffic:
+/// orchestrator status `ready`, a known IP, and a worker that reports `ready` (or has not reported
+/// a status yet). `starting` and `draining` workers are never routable, nor are instances an
+/// operator paused (`routing_enabled = false`). Heartbeat freshness is checked separately since
+/// the staleness window is bound per query.
+pub const ROUTABLE_INSTANCE_SQL: &str = "i.status::text = 'ready' \
+     AND i.ip_address IS NOT NULL \
+     AND i.routing_enabled \
+     AND (i.worker_status = 'ready' OR i.worker_status IS NULL)";
+
+/// Sticky session header used for worker affinity (forwarded to the worker-local HAProxy).
+pub const STICKY_SESSION_HEADER: &str = "X-Inventiv-Session";
+
+/// Resolve the sticky session key for a proxied request.
+///
+/// Order: client-supplied header -> API key (stable across requests) -> user session -> random.
+/// Returns the key and whether it was generated server-side (see `session_affinity_key` for which
+/// keys pin the instance selection).
+pub fn resolve_sticky_session(
+    headers: &HeaderMap,
+    api_key: Option<&auth::ApiKeyPrincipal>,
+    user: Option<&auth::AuthUser>,
+) -> (String, bool) {
+    if let Some(sid) = header_value(headers, STICKY_SESSION_HEADER)
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+    {
+        return (sid, false);
+    }
+    let generated = if let Some(k) = api_key {
+        format!("ak-{}", session_digest(&k.api_key_id.to_string()))
+    } else if let Some(u) = user {
+        format!("us-{}", session_digest(&u.session_id))
+    } else {
+        format!("gen-{}", Uuid::new_v4().simple())
+    };
+    (generated, true)
+}
+
+/// Instance-selection key for a resolved sticky session, scoped per API key.
+///
+/// Client-supplied keys and keys generated from an API key or user session pin the worker, so a
+/// client echoing `X-Inventiv-Session` keeps landing on the same one. Random keys (anonymous
+/// callers without the header) return None: the least-loaded worker is used instead.
+pub fn session_affinity_key(
+    sticky: &str,
+    generated: bool,
+    api_key: Option<&auth::ApiKeyPrincipal>,
+    user: Option<&auth::AuthUser>,
+) -> Option<String> {
+    if generated && api_key.is_none() && user.is_none() {
+        return None;
+    }
+    Some(match api_key {
+        Some(k) => format!("{}:{}", k.api_key_id, sticky),
+        None => sticky.to_string(),
+    })
+}
+
+/// Opaque, stable digest so the echoed header does not leak internal ids.
+fn session_digest(s: &str) -> String {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(b"inventiv-session:");
+    hasher.update(s.as_bytes());
+    format!("{:x}", hasher.finalize())[..24].to_string()
+}
+
 /// Update runtime model counters
 pub async fn bump_runtime_model_counters(db: &Pool<Postgres>, model_id: &str, ok: bool) {
     let mid = model_id.trim();
@@ -41,54 +111,242 @@ pub async fn bump_runtime_model_counters(db: &Pool<Postgres>, model_id: &str, ok
     .bind(ok)
     .execute(db)
     .await;
+
+    // Per-minute bucket (used for windowed counts on /runtime/models).
+    let _ = sqlx::query(
+        r#"
+        INSERT INTO runtime_model_counters_minute (model_id, bucket_minute, requests, failed_requests)
+        VALUES ($1, date_trunc('minute', NOW(), 'UTC'), 1, CASE WHEN $2 THEN 0 ELSE 1 END)
+        ON CONFLICT (model_id, bucket_minute) DO UPDATE
+          SET requests = runtime_model_counters_minute.requests + 1,
+              failed_requests = runtime_model_counters_minute.failed_requests + (CASE WHEN $2 THEN 0 ELSE 1 END)
+        "#,
+    )
+    .bind(mid)
+    .bind(ok)
+    .execute(db)
+    .await;
+}
+
+/// Request priority header (`high` | `normal` | `low`, default `normal`).
+pub const PRIORITY_HEADER: &str = "X-Inventiv-Priority";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RequestPriority {
+    High,
+    #[default]
+    Normal,
+    Low,
 }
 
-/// Select a ready worker for a given model
+impl RequestPriority {
+    /// Priority requested by the client; unknown values fall back to `normal`.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        match header_value(headers, PRIORITY_HEADER)
+            .map(|v| v.trim().to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("high") => Self::High,
+            Some("low") => Self::Low,
+            _ => Self::Normal,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::High => "high",
+            Self::Normal => "normal",
+            Self::Low => "low",
+        }
+    }
+}
+
+const DEFAULT_WORKER_SOFT_CONCURRENCY_CAP: i64 = 16;
+
+/// Soft per-worker in-flight cap (`WORKER_SOFT_CONCURRENCY_CAP`, 0 disables it).
+pub fn worker_soft_concurrency_cap() -> i64 {
+    std::env::var("WORKER_SOFT_CONCURRENCY_CAP")
+        .ok()
+        .and_then(|s| s.trim().parse::<i64>().ok())
+        .filter(|v| *v >= 0)
+        .unwrap_or(DEFAULT_WORKER_SOFT_CONCURRENCY_CAP)
+}
+
+/// Priority-aware routing inputs for `select_ready_worker_for_model`.
+/// In-flight counts are read from Redis (see `acquire_worker_slot`).
+pub struct PriorityRouting<'a> {
+    pub redis: &'a redis::Client,
+    pub priority: RequestPriority,
+    pub soft_cap: i64,
+}
+
+fn inflight_key(instance_id: Uuid) -> String {
+    format!("inventiv:worker_inflight:{}", instance_id)
+}
+
+/// Safety TTL on in-flight counters, so a crashed API process cannot pin a worker at its cap.
+const INFLIGHT_TTL_SECONDS: i64 = 3600;
+
+async fn worker_inflight_counts(redis: &redis::Client, ids: &[Uuid]) -> Vec<i64> {
+    let fallback = vec![0; ids.len()];
+    let Ok(mut conn) = redis.get_multiplexed_async_connection().await else {
+        return fallback;
+    };
+    let keys: Vec<String> = ids.iter().map(|id| inflight_key(*id)).collect();
+    redis::cmd("MGET")
+        .arg(&keys)
+        .query_async::<_, Vec<Option<i64>>>(&mut conn)
+        .await
+        .map(|v| v.into_iter().map(|c| c.unwrap_or(0).max(0)).collect())
+        .unwrap_or(fallback)
+}
+
+/// In-flight request slot on a worker; released (DECR) when dropped.
+pub struct WorkerSlot {
+    redis: redis::Client,
+    instance_id: Uuid,
+}
+
+/// Count a proxied request against the worker's soft concurrency counter (best effort).
+pub async fn acquire_worker_slot(redis: &redis::Client, instance_id: Uuid) -> Option<WorkerSlot> {
+    let mut conn = redis.get_multiplexed_async_connection().await.ok()?;
+    let key = inflight_key(instance_id);
+    redis::pipe()
+        .cmd("INCR")
+        .arg(&key)
+        .ignore()
+        .cmd("EXPIRE")
+        .arg(&key)
+        .arg(INFLIGHT_TTL_SECONDS)
+        .ignore()
+        .query_async::<_, ()>(&mut conn)
+        .await
+        .ok()?;
+    Some(WorkerSlot {
+        redis: redis.clone(),
+        instance_id,
+    })
+}
+
+impl Drop for WorkerSlot {
+    fn drop(&mut self) {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let redis = self.redis.clone();
+        let key = inflight_key(self.instance_id);
+        handle.spawn(async move {
+            if let Ok(mut conn) = redis.get_multiplexed_async_connection().await {
+                let _ = redis::cmd("DECR")
+                    .arg(&key)
+                    .query_async::<_, i64>(&mut conn)
+                    .await;
+            }
+        });
+    }
+}
+
+/// Indices of the candidates (in routing order) a request of `priority` may use.
+///
+/// - high: the least-loaded worker, even above the soft cap
+/// - normal: workers below the cap, or all of them when every worker is at the cap
+/// - low: only workers below the cap (empty = defer)
+fn priority_candidates(inflight: &[i64], priority: RequestPriority, soft_cap: i64) -> Vec<usize> {
+    let below_cap: Vec<usize> = (0..inflight.len())
+        .filter(|&i| soft_cap == 0 || inflight[i] < soft_cap)
+        .collect();
+    match priority {
+        RequestPriority::High => (0..inflight.len())
+            .min_by_key(|&i| inflight[i])
+            .into_iter()
+            .collect(),
+        RequestPriority::Normal if below_cap.is_empty() => (0..inflight.len()).collect(),
+        RequestPriority::Normal | RequestPriority::Low => below_cap,
+    }
+}
+
+/// Select a ready worker for a given model.
+/// With `routing`, the request priority and per-worker in-flight counters are honored; a
+/// low-priority request gets None when every ready worker is at its soft concurrency cap.
 pub async fn select_ready_worker_for_model(
     db: &Pool<Postgres>,
     model: &str,
     sticky_key: Option<&str>,
+    routing: Option<&PriorityRouting<'_>>,
+) -> Option<(Uuid, String)> {
+    select_ready_worker_for_model_excluding(db, model, sticky_key, routing, &[]).await
+}
+
+/// Same as `select_ready_worker_for_model`, never returning one of `exclude` (e.g. workers whose
+/// breaker is open, or already tried for this request).
+pub async fn select_ready_worker_for_model_excluding(
+    db: &Pool<Postgres>,
+    model: &str,
+    sticky_key: Option<&str>,
+    routing: Option<&PriorityRouting<'_>>,
+    exclude: &[Uuid],
 ) -> Option<(Uuid, String)> {
     // `model` here is the vLLM/OpenAI model id (HF repo id).
     // We route based on `instances.worker_model_id` (set by worker heartbeat/register).
     let model = model.trim();
     let stale = openai_worker_stale_seconds_db(db).await;
-    let rows = sqlx::query_as::<Postgres, ReadyWorkerRow>(
+    let rows = sqlx::query_as::<Postgres, ReadyWorkerRow>(&format!(
         r#"
         SELECT
-          id,
-          ip_address::text as ip_address,
-          worker_vllm_port,
-          worker_queue_depth,
-          worker_last_heartbeat
-        FROM instances
-        WHERE status::text = 'ready'
-          AND ip_address IS NOT NULL
-          AND (worker_status = 'ready' OR worker_status IS NULL)
-          AND ($1::text = '' OR worker_model_id = $1)
+          i.id,
+          i.ip_address::text as ip_address,
+          i.worker_vllm_port,
+          i.worker_proxy_port,
+          i.worker_queue_depth,
+          i.worker_last_heartbeat
+        FROM instances i
+        LEFT JOIN models m ON m.model_id = i.worker_model_id
+        WHERE {routable}
+          AND ($1::text = '' OR i.worker_model_id = $1)
           -- Use the same freshness signal as /v1/models + /runtime/models:
           -- allow either worker heartbeat OR orchestrator health timestamps to keep the instance routable.
+          -- The window can be overridden per model (models.stale_window_seconds).
           AND GREATEST(
-              COALESCE(worker_last_heartbeat, 'epoch'::timestamptz),
-              COALESCE(last_health_check, 'epoch'::timestamptz),
-              COALESCE((last_reconciliation AT TIME ZONE 'UTC'), 'epoch'::timestamptz)
-            ) > NOW() - ($2::bigint * INTERVAL '1 second')
-        ORDER BY worker_queue_depth NULLS LAST,
+              COALESCE(i.worker_last_heartbeat, 'epoch'::timestamptz),
+              COALESCE(i.last_health_check, 'epoch'::timestamptz),
+              COALESCE(i.last_reconciliation, 'epoch'::timestamptz)
+            ) > NOW() - (COALESCE(m.stale_window_seconds::bigint, $2::bigint) * INTERVAL '1 second')
+        ORDER BY i.worker_queue_depth NULLS LAST,
                  GREATEST(
-                   COALESCE(worker_last_heartbeat, 'epoch'::timestamptz),
-                   COALESCE(last_health_check, 'epoch'::timestamptz),
-                   COALESCE((last_reconciliation AT TIME ZONE 'UTC'), 'epoch'::timestamptz)
+                   COALESCE(i.worker_last_heartbeat, 'epoch'::timestamptz),
+                   COALESCE(i.last_health_check, 'epoch'::timestamptz),
+                   COALESCE(i.last_reconciliation, 'epoch'::timestamptz)
                  ) DESC,
-                 created_at DESC
+                 i.created_at DESC
         LIMIT 50
         "#,
-    )
+        routable = ROUTABLE_INSTANCE_SQL
+    ))
     .bind(model)
     .bind(stale)
     .fetch_all(db)
     .await
     .ok()?;
 
+    // Never route to an instance whose stored IP can't be turned into a host address.
+    let rows: Vec<ReadyWorkerRow> = rows
+        .into_iter()
+        .filter(|r| net::normalize_instance_ip(&r.ip_address).is_some())
+        .filter(|r| !exclude.contains(&r.id))
+        .collect();
+    if rows.is_empty() {
+        return None;
+    }
+
+    let rows: Vec<ReadyWorkerRow> = match routing {
+        Some(r) => {
+            let ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+            let inflight = worker_inflight_counts(r.redis, &ids).await;
+            let allowed = priority_candidates(&inflight, r.priority, r.soft_cap);
+            allowed.into_iter().map(|i| rows[i].clone()).collect()
+        }
+        None => rows,
+    };
     if rows.is_empty() {
         return None;
     }
@@ -97,20 +355,358 @@ pub async fn select_ready_worker_for_model(
         // Stable-ish affinity to an instance across requests (best effort).
         let mut sorted = rows;
         sorted.sort_by_key(|r| r.id);
-        let idx = (stable_hash_u64(key) as usize) % sorted.len();
+        let idx = sticky_index(key, sorted.len());
         sorted[idx].clone()
     } else {
         rows[0].clone()
     };
 
-    let ip = chosen
-        .ip_address
-        .split('/')
-        .next()
-        .unwrap_or(&chosen.ip_address)
-        .to_string();
-    let port = chosen.worker_vllm_port.unwrap_or(8000).max(1) as i32;
-    Some((chosen.id, format!("http://{}:{}", ip, port)))
+    let default_port = if chosen.worker_proxy_port.is_none() && chosen.worker_vllm_port.is_none() {
+        worker_default_port_db(db).await
+    } else {
+        DEFAULT_WORKER_PORT
+    };
+    let port = worker_routing_port(
+        chosen.worker_proxy_port,
+        chosen.worker_vllm_port,
+        default_port,
+    );
+    let base_url = net::instance_http_base_url(&chosen.ip_address, port)?;
+    Some((chosen.id, base_url))
+}
+
+/// Deprecation notice for a served model (`models.deprecated_at` is set and reached).
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ModelDeprecation {
+    pub deprecated_at: chrono::DateTime<chrono::Utc>,
+    /// HF repo id of the replacement model, if one was configured.
+    pub replacement: Option<String>,
+}
+
+impl ModelDeprecation {
+    /// Value of the `X-Model-Deprecated` response header, e.g.
+    /// `2026-01-09T00:00:00Z; replacement=Qwen/Qwen2.5-7B-Instruct`.
+    pub fn header_value(&self) -> String {
+        let since = self
+            .deprecated_at
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        match self.replacement.as_deref() {
+            Some(r) => format!("{}; replacement={}", since, r),
+            None => since,
+        }
+    }
+
+    /// Warning object surfaced in JSON error envelopes (`metadata.warning`).
+    pub fn warning(&self) -> serde_json::Value {
+        serde_json::json!({
+            "code": "model_deprecated",
+            "deprecated_at": self.deprecated_at,
+            "replacement_model": self.replacement,
+        })
+    }
+}
+
+/// Look up deprecation for a resolved model id (HF repo id). None when the model is not deprecated.
+pub async fn model_deprecation(db: &Pool<Postgres>, model_id: &str) -> Option<ModelDeprecation> {
+    sqlx::query_as::<Postgres, ModelDeprecation>(
+        r#"
+        SELECT m.deprecated_at, r.model_id AS replacement
+        FROM models m
+        LEFT JOIN models r ON r.id = m.replacement_model_id
+        WHERE m.model_id = $1
+          AND m.deprecated_at IS NOT NULL
+          AND m.deprecated_at <= NOW()
+        ORDER BY m.deprecated_at
+        LIMIT 1
+        "#,
+    )
+    .bind(model_id)
+    .fetch_optional(db)
+    .await
+    .ok()
+    .flatten()
+}
+
+/// Per-request opt-in (`true`/`false`) for serving the model's configured fallback.
+pub const ALLOW_FALLBACK_HEADER: &str = "X-Allow-Model-Fallback";
+
+/// Whether a request may be routed to a fallback model: the request header wins, otherwise
+/// global_settings.OPENAI_MODEL_FALLBACK_ENABLED, then env `OPENAI_MODEL_FALLBACK_ENABLED`.
+pub async fn model_fallback_allowed(db: &Pool<Postgres>, headers: &HeaderMap) -> bool {
+    let parse = |v: &str| match v.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    };
+    if let Some(v) = header_value(headers, ALLOW_FALLBACK_HEADER).and_then(|v| parse(&v)) {
+        return v;
+    }
+    let from_db: Option<bool> = sqlx::query_scalar(
+        "SELECT value_bool FROM global_settings WHERE key = 'OPENAI_MODEL_FALLBACK_ENABLED'",
+    )
+    .fetch_optional(db)
+    .await
+    .ok()
+    .flatten()
+    .flatten();
+    if let Some(v) = from_db {
+        return v;
+    }
+    std::env::var("OPENAI_MODEL_FALLBACK_ENABLED")
+        .ok()
+        .and_then(|v| parse(&v))
+        .unwrap_or(false)
+}
+
+/// HF repo id of the (active) fallback configured for a resolved model id.
+pub async fn fallback_model(db: &Pool<Postgres>, model_id: &str) -> Option<String> {
+    sqlx::query_scalar(
+        r#"
+        SELECT f.model_id
+        FROM models m
+        JOIN models f ON f.id = m.fallback_model_id
+        WHERE m.model_id = $1
+          AND f.is_active = true
+        LIMIT 1
+        "#,
+    )
+    .bind(model_id)
+    .fetch_optional(db)
+    .await
+    .ok()
+    .flatten()
+}
+
+/// Generation defaults configured for a resolved model id (HF repo id). None when not opted in.
+pub async fn model_default_params(
+    db: &Pool<Postgres>,
+    model_id: &str,
+) -> Option<serde_json::Map<String, serde_json::Value>> {
+    let params: Option<serde_json::Value> = sqlx::query_scalar(
+        r#"
+        SELECT default_params
+        FROM models
+        WHERE model_id = $1
+          AND default_params IS NOT NULL
+        LIMIT 1
+        "#,
+    )
+    .bind(model_id)
+    .fetch_optional(db)
+    .await
+    .ok()
+    .flatten();
+    match params {
+        Some(serde_json::Value::Object(m)) if !m.is_empty() => Some(m),
+        _ => None,
+    }
+}
+
+/// Fill in default params the client did not send (client values, including explicit nulls,
+/// always win). Returns true when the body was changed.
+pub fn apply_default_params(
+    body: &mut serde_json::Value,
+    defaults: &serde_json::Map<String, serde_json::Value>,
+) -> bool {
+    let Some(obj) = body.as_object_mut() else {
+        return false;
+    };
+    let mut changed = false;
+    for (k, v) in defaults {
+        if k != "model" && !obj.contains_key(k) {
+            obj.insert(k.clone(), v.clone());
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Headers the proxy sets itself; a model's `forward_headers` cannot override them.
+const RESERVED_FORWARD_HEADERS: &[&str] = &[
+    "host",
+    "connection",
+    "content-length",
+    "content-type",
+    "content-encoding",
+    "transfer-encoding",
+    "accept",
+    "x-inventiv-session",
+];
+
+/// Parse a model's `forward_headers` (JSON object of header name -> string value).
+/// Errors name the offending entry; reserved proxy headers are refused.
+pub fn parse_forward_headers(
+    v: &serde_json::Value,
+) -> Result<Vec<(axum::http::HeaderName, axum::http::HeaderValue)>, String> {
+    let obj = v
+        .as_object()
+        .ok_or_else(|| "forward_headers must be a JSON object".to_string())?;
+    let mut out = Vec::with_capacity(obj.len());
+    for (name, value) in obj {
+        let header = axum::http::HeaderName::from_bytes(name.trim().as_bytes())
+            .map_err(|_| format!("invalid header name '{}'", name))?;
+        if RESERVED_FORWARD_HEADERS.contains(&header.as_str()) {
+            return Err(format!("header '{}' is set by the proxy", header));
+        }
+        let value = value
+            .as_str()
+            .and_then(|s| axum::http::HeaderValue::from_str(s).ok())
+            .ok_or_else(|| format!("invalid value for header '{}'", name))?;
+        out.push((header, value));
+    }
+    Ok(out)
+}
+
+/// Extra worker request headers configured for a resolved model id (HF repo id).
+pub async fn model_forward_headers(
+    db: &Pool<Postgres>,
+    model_id: &str,
+) -> Vec<(axum::http::HeaderName, axum::http::HeaderValue)> {
+    let headers: Option<serde_json::Value> = sqlx::query_scalar(
+        r#"
+        SELECT forward_headers
+        FROM models
+        WHERE model_id = $1
+          AND forward_headers IS NOT NULL
+        LIMIT 1
+        "#,
+    )
+    .bind(model_id)
+    .fetch_optional(db)
+    .await
+    .ok()
+    .flatten();
+    headers
+        .and_then(|v| parse_forward_headers(&v).ok())
+        .unwrap_or_default()
+}
+
+/// How denylisted request fields are handled (global_settings.OPENAI_PARAM_FILTER_MODE).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamFilterMode {
+    /// Remove the fields and forward the request (default).
+    Strip,
+    /// Refuse the request (422 naming the field).
+    Reject,
+}
+
+/// Top-level request fields the proxy must not forward (e.g. parameter forms vLLM rejects).
+#[derive(Debug, Clone)]
+pub struct ParamFilter {
+    pub denylist: Vec<String>,
+    pub mode: ParamFilterMode,
+}
+
+impl ParamFilter {
+    /// Apply the filter to a request body. `Ok(true)` when fields were stripped,
+    /// `Err(field)` with the first denylisted field present in reject mode.
+    pub fn apply(&self, body: &mut serde_json::Value) -> Result<bool, String> {
+        let Some(obj) = body.as_object_mut() else {
+            return Ok(false);
+        };
+        let present: Vec<&String> = self
+            .denylist
+            .iter()
+            .filter(|k| k.as_str() != "model" && obj.contains_key(k.as_str()))
+            .collect();
+        match (self.mode, present.first()) {
+            (_, None) => Ok(false),
+            (ParamFilterMode::Reject, Some(field)) => Err(field.to_string()),
+            (ParamFilterMode::Strip, Some(_)) => {
+                for k in present {
+                    obj.remove(k.as_str());
+                }
+                Ok(true)
+            }
+        }
+    }
+}
+
+/// Request parameter filter from global_settings (OPENAI_PARAM_DENYLIST, comma-separated, and
+/// OPENAI_PARAM_FILTER_MODE). None when no denylist is configured.
+pub async fn param_filter(db: &Pool<Postgres>) -> Option<ParamFilter> {
+    let rows: Vec<(String, Option<String>)> = sqlx::query_as(
+        "SELECT key, value_text FROM global_settings
+         WHERE key IN ('OPENAI_PARAM_DENYLIST', 'OPENAI_PARAM_FILTER_MODE')",
+    )
+    .fetch_all(db)
+    .await
+    .unwrap_or_default();
+    let value = |key: &str| {
+        rows.iter()
+            .find(|(k, _)| k == key)
+            .and_then(|(_, v)| v.as_deref())
+    };
+    let denylist: Vec<String> = value("OPENAI_PARAM_DENYLIST")
+        .unwrap_or_default()
+        .split(',')
+        .map(|f| f.trim().to_string())
+        .filter(|f| !f.is_empty())
+        .collect();
+    if denylist.is_empty() {
+        return None;
+    }
+    let mode = match value("OPENAI_PARAM_FILTER_MODE")
+        .map(|m| m.trim().to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("reject") => ParamFilterMode::Reject,
+        _ => ParamFilterMode::Strip,
+    };
+    Some(ParamFilter { denylist, mode })
+}
+
+/// vLLM paths the proxy forwards as-is under `/v1/*` (global_settings.OPENAI_PROXY_PASSTHROUGH_PATHS,
+/// comma-separated). Empty when passthrough is disabled.
+pub async fn passthrough_allowlist(db: &Pool<Postgres>) -> Vec<String> {
+    let raw: Option<String> = sqlx::query_scalar(
+        "SELECT value_text FROM global_settings WHERE key = 'OPENAI_PROXY_PASSTHROUGH_PATHS'",
+    )
+    .fetch_optional(db)
+    .await
+    .ok()
+    .flatten()
+    .flatten();
+    raw.unwrap_or_default()
+        .split(',')
+        .map(|p| format!("/{}", p.trim().trim_start_matches('/')))
+        .filter(|p| p.len() > 1)
+        .collect()
+}
+
+/// Worker path for a `/v1/{rest}` passthrough request: `/v1/{rest}` if allowlisted, else `/{rest}`
+/// (vLLM serves e.g. `/tokenize` and `/version` at the root). None when neither is allowlisted.
+pub fn passthrough_target(allowlist: &[String], rest: &str) -> Option<String> {
+    let rest = rest.trim_start_matches('/');
+    [format!("/v1/{}", rest), format!("/{}", rest)]
+        .into_iter()
+        .find(|p| allowlist.contains(p))
+}
+
+/// Whether the caller may use a resolved model id (HF repo id). User sessions may call any model;
+/// API keys are limited to their `allowed_models` scope when set, otherwise to public models
+/// (models missing from the catalog count as public).
+pub async fn model_access_allowed(
+    db: &Pool<Postgres>,
+    model_id: &str,
+    api_key: Option<&auth::ApiKeyPrincipal>,
+) -> bool {
+    let Some(key) = api_key else {
+        return true;
+    };
+    if let Some(scope) = key.allowed_models.as_ref() {
+        return scope.iter().any(|m| m == model_id);
+    }
+    match sqlx::query_scalar::<Postgres, Option<bool>>(
+        "SELECT bool_or(public) FROM models WHERE model_id = $1",
+    )
+    .bind(model_id)
+    .fetch_one(db)
+    .await
+    {
+        Ok(public) => public.unwrap_or(true),
+        Err(_) => false,
+    }
 }
 
 /// Resolve OpenAI model ID from request
@@ -273,6 +869,41 @@ async fn openai_worker_stale_seconds_db(db: &Pool<Postgres>) -> i64 {
     300 // Hard default: 5 minutes
 }
 
+const DEFAULT_WORKER_PORT: i32 = 8000;
+
+/// Port to route to: the worker's proxy front (HAProxy in multi-vLLM mode) wins over the vLLM
+/// port, then the configured default.
+fn worker_routing_port(proxy_port: Option<i32>, vllm_port: Option<i32>, default_port: i32) -> u16 {
+    proxy_port
+        .or(vllm_port)
+        .unwrap_or(default_port)
+        .clamp(1, u16::MAX as i32) as u16
+}
+
+async fn worker_default_port_db(db: &Pool<Postgres>) -> i32 {
+    // Global settings override (DB) -> env -> hard default.
+    let from_db: Option<i64> = sqlx::query_scalar(
+        "SELECT value_int FROM global_settings WHERE key = 'WORKER_DEFAULT_VLLM_PORT'",
+    )
+    .fetch_optional(db)
+    .await
+    .ok()
+    .flatten();
+    if let Some(v) = from_db {
+        return v.clamp(1, u16::MAX as i64) as i32;
+    }
+    std::env::var("WORKER_DEFAULT_VLLM_PORT")
+        .ok()
+        .and_then(|s| s.trim().parse::<i32>().ok())
+        .filter(|v| *v > 0 && *v <= u16::MAX as i32)
+        .unwrap_or(DEFAULT_WORKER_PORT)
+}
+
+/// Index of the instance (in id-sorted candidates) a sticky key maps to.
+fn sticky_index(key: &str, candidates: usize) -> usize {
+    (stable_hash_u64(key) as usize) % candidates
+}
+
 fn stable_hash_u64(s: &str) -> u64 {
     use std::collections::hash_map::DefaultHasher;
     use std::hash::{Hash, Hasher};
@@ -280,3 +911,145 @@ fn stable_hash_u64(s: &str) -> u64 {
     s.hash(&mut hasher);
     hasher.finish()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_key(id: Uuid) -> auth::ApiKeyPrincipal {
+        auth::ApiKeyPrincipal {
+            api_key_id: id,
+            user_id: Uuid::new_v4(),
+            key_prefix: "sk-test".to_string(),
+            name: "test".to_string(),
+            allowed_models: None,
+            rate_limit_per_minute: None,
+            max_concurrent_streams: None,
+        }
+    }
+
+    #[test]
+    fn passthrough_target_prefers_v1_path_and_rejects_unlisted() {
+        let allowlist = vec!["/v1/score".to_string(), "/tokenize".to_string()];
+        assert_eq!(
+            passthrough_target(&allowlist, "score").as_deref(),
+            Some("/v1/score")
+        );
+        assert_eq!(
+            passthrough_target(&allowlist, "tokenize").as_deref(),
+            Some("/tokenize")
+        );
+        assert_eq!(passthrough_target(&allowlist, "detokenize"), None);
+        assert_eq!(passthrough_target(&[], "score"), None);
+    }
+
+    #[test]
+    fn client_supplied_session_is_honored() {
+        let mut headers = HeaderMap::new();
+        headers.insert(STICKY_SESSION_HEADER, "client-abc".parse().unwrap());
+        let key = api_key(Uuid::new_v4());
+        let (sid, generated) = resolve_sticky_session(&headers, Some(&key), None);
+        assert_eq!(sid, "client-abc");
+        assert!(!generated);
+    }
+
+    #[test]
+    fn generated_session_is_stable_per_api_key_and_reused() {
+        let headers = HeaderMap::new();
+        let key = api_key(Uuid::new_v4());
+        let (first, generated) = resolve_sticky_session(&headers, Some(&key), None);
+        assert!(generated);
+        assert!(first.starts_with("ak-"));
+        assert!(!first.contains(&key.api_key_id.to_string()));
+
+        // A cooperative client echoes the generated session back on its next request.
+        let mut echoed = HeaderMap::new();
+        echoed.insert(STICKY_SESSION_HEADER, first.parse().unwrap());
+        let (second, generated) = resolve_sticky_session(&echoed, Some(&key), None);
+        assert!(!generated);
+        assert_eq!(first, second);
+        assert_eq!(sticky_index(&first, 7), sticky_index(&second, 7));
+
+        // Same API key without the header still maps to the same session.
+        let (again, _) = resolve_sticky_session(&headers, Some(&key), None);
+        assert_eq!(first, again);
+    }
+
+    #[test]
+    fn default_params_only_fill_missing_keys() {
+        let defaults = serde_json::json!({"temperature": 0.2, "top_p": 0.9, "model": "other"});
+        let defaults = defaults.as_object().unwrap();
+        let mut body = serde_json::json!({"model": "m", "top_p": 0.5, "messages": []});
+        assert!(apply_default_params(&mut body, defaults));
+        assert_eq!(
+            body,
+            serde_json::json!({"model": "m", "temperature": 0.2, "top_p": 0.5, "messages": []})
+        );
+        // Nothing left to fill: body untouched.
+        assert!(!apply_default_params(&mut body, defaults));
+    }
+
+    #[test]
+    fn priority_header_is_parsed() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            RequestPriority::from_headers(&headers),
+            RequestPriority::Normal
+        );
+        headers.insert(PRIORITY_HEADER, " HIGH ".parse().unwrap());
+        assert_eq!(
+            RequestPriority::from_headers(&headers),
+            RequestPriority::High
+        );
+        headers.insert(PRIORITY_HEADER, "low".parse().unwrap());
+        assert_eq!(
+            RequestPriority::from_headers(&headers),
+            RequestPriority::Low
+        );
+        headers.insert(PRIORITY_HEADER, "urgent".parse().unwrap());
+        assert_eq!(
+            RequestPriority::from_headers(&headers),
+            RequestPriority::Normal
+        );
+    }
+
+    #[test]
+    fn soft_cap_defers_low_priority_only() {
+        let inflight = [4, 2, 3];
+        // Every worker at the cap: low defers, normal may exceed it, high takes the least loaded.
+        assert!(priority_candidates(&inflight, RequestPriority::Low, 2).is_empty());
+        assert_eq!(
+            priority_candidates(&inflight, RequestPriority::Normal, 2),
+            vec![0, 1, 2]
+        );
+        assert_eq!(
+            priority_candidates(&inflight, RequestPriority::High, 2),
+            vec![1]
+        );
+        // Headroom left on some workers.
+        assert_eq!(
+            priority_candidates(&inflight, RequestPriority::Low, 4),
+            vec![1, 2]
+        );
+        // Cap disabled.
+        assert_eq!(
+            priority_candidates(&inflight, RequestPriority::Low, 0),
+            vec![0, 1, 2]
+        );
+    }
+
+    #[test]
+    fn proxy_port_is_preferred_for_routing() {
+        assert_eq!(worker_routing_port(Some(8080), Some(8001), 8000), 8080);
+        assert_eq!(worker_routing_port(None, Some(8001), 8000), 8001);
+        assert_eq!(worker_routing_port(None, None, 9000), 9000);
+    }
+
+    #[test]
+    fn anonymous_sessions_are_unique() {
+        let headers = HeaderMap::new();
+        let (a, _) = resolve_sticky_session(&headers, None, None);
+        let (b, _) = resolve_sticky_session(&headers, None, None);
+        assert_ne!(a, b);
+    }
+}
@@ -0,0 +1,129 @@
+// Integration tests for catalog export/import (GET /catalog/export, POST /catalog/import).
+
+mod common;
+
+use common::get_test_db_pool;
+use inventiv_api::catalog_transfer::{export_catalog, import_catalog, CatalogImportError};
+
+#[tokio::test]
+async fn test_catalog_round_trip_restores_deleted_row() {
+    let pool = get_test_db_pool().await;
+
+    let suffix = uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
+    let provider_code = format!("export-{}", suffix);
+    let type_code = format!("export-type-{}", suffix);
+    let provider_id: uuid::Uuid = sqlx::query_scalar(
+        "INSERT INTO providers (id, name, code, description, is_active)
+         VALUES (gen_random_uuid(), $1, $1, 'Export test', true) RETURNING id",
+    )
+    .bind(&provider_code)
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to create test provider");
+    let region_id: uuid::Uuid = sqlx::query_scalar(
+        "INSERT INTO regions (id, provider_id, name, code, is_active)
+         VALUES (gen_random_uuid(), $1, 'Region', 'r1', true) RETURNING id",
+    )
+    .bind(provider_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let zone_id: uuid::Uuid = sqlx::query_scalar(
+        "INSERT INTO zones (id, region_id, provider_id, name, code, is_active)
+         VALUES (gen_random_uuid(), $1, $2, 'Zone', 'r1-a', true) RETURNING id",
+    )
+    .bind(region_id)
+    .bind(provider_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let type_id: uuid::Uuid = sqlx::query_scalar(
+        "INSERT INTO instance_types (id, provider_id, name, code, gpu_count, vram_per_gpu_gb, cpu_count, ram_gb, cost_per_hour, is_active, allocation_params)
+         VALUES (gen_random_uuid(), $1, $2, $2, 2, 24, 8, 64, 1.2345, true, '{\"k\":\"v\"}'::jsonb) RETURNING id",
+    )
+    .bind(provider_id)
+    .bind(&type_code)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO instance_type_zones (instance_type_id, zone_id, is_available) VALUES ($1, $2, false)",
+    )
+    .bind(type_id)
+    .bind(zone_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let doc = export_catalog(&pool).await.expect("export failed");
+    let exported = doc
+        .instance_types
+        .iter()
+        .find(|it| it.provider_code == provider_code && it.code == type_code)
+        .expect("instance type missing from export");
+    assert_eq!(exported.zones.len(), 1);
+    assert_eq!(exported.zones[0].zone_code, "r1-a");
+
+    // Wipe the row, then restore it from the document (codes, not UUIDs, carry identity).
+    sqlx::query("DELETE FROM instance_type_zones WHERE instance_type_id = $1")
+        .bind(type_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM instance_types WHERE id = $1")
+        .bind(type_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let summary = import_catalog(&pool, &doc).await.expect("import failed");
+    assert_eq!(summary.instance_types, doc.instance_types.len());
+    // Idempotent: a second import changes nothing.
+    import_catalog(&pool, &doc).await.expect("re-import failed");
+
+    let restored: (uuid::Uuid, i32, i32, f64, serde_json::Value) = sqlx::query_as(
+        "SELECT id, gpu_count, vram_per_gpu_gb, CAST(cost_per_hour AS float8), allocation_params
+         FROM instance_types WHERE provider_id = $1 AND code = $2",
+    )
+    .bind(provider_id)
+    .bind(&type_code)
+    .fetch_one(&pool)
+    .await
+    .expect("instance type not restored");
+    let available: Vec<bool> = sqlx::query_scalar(
+        "SELECT is_available FROM instance_type_zones WHERE instance_type_id = $1",
+    )
+    .bind(restored.0)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+
+    // Dangling references are rejected before anything is written.
+    let mut bad = doc.clone();
+    bad.regions[0].provider_code = format!("missing-{}", suffix);
+    let rejected = import_catalog(&pool, &bad).await;
+
+    sqlx::query("DELETE FROM instance_type_zones WHERE instance_type_id = $1")
+        .bind(restored.0)
+        .execute(&pool)
+        .await
+        .ok();
+    for table in ["instance_types", "zones", "regions"] {
+        sqlx::query(&format!("DELETE FROM {} WHERE provider_id = $1", table))
+            .bind(provider_id)
+            .execute(&pool)
+            .await
+            .ok();
+    }
+    sqlx::query("DELETE FROM providers WHERE id = $1")
+        .bind(provider_id)
+        .execute(&pool)
+        .await
+        .ok();
+
+    assert_eq!((restored.1, restored.2), (2, 24));
+    assert!((restored.3 - 1.2345).abs() < 1e-9);
+    assert_eq!(restored.4, serde_json::json!({"k": "v"}));
+    assert_eq!(available, vec![false]);
+    assert!(matches!(rejected, Err(CatalogImportError::Invalid(_))));
+}
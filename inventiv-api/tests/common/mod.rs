@@ -79,6 +79,11 @@ pub async fn create_test_app() -> Router<Arc<AppState>> {
 /// In axum-test 18, TestServer::new() accepts Router (without state) directly
 /// We create the router without state, then add state via with_state()
 pub async fn create_test_app_service() -> Router {
+    create_test_app_service_with_state().await.0
+}
+
+/// Same as `create_test_app_service`, also returning the shared state (e.g. to clear caches).
+pub async fn create_test_app_service_with_state() -> (Router, Arc<AppState>) {
     let db_pool = get_test_db_pool().await;
     let redis_client = get_test_redis_client().await;
     let state = AppState::new(redis_client, db_pool);
@@ -93,7 +98,7 @@ pub async fn create_test_app_service() -> Router {
 
     // Apply CORS
     let cors = inventiv_api::app::create_cors();
-    app.layer(cors).with_state(state)
+    (app.layer(cors).with_state(state.clone()), state)
 }
 
 /// Clean up test data (optional, can be called between tests)
@@ -258,20 +263,21 @@ pub async fn create_test_organization(
     owner_id: uuid::Uuid,
 ) -> uuid::Uuid {
     let org_id: uuid::Uuid = sqlx::query_scalar(
-        "INSERT INTO organizations (id, name, slug, created_at)
-         VALUES (gen_random_uuid(), $1, $2, NOW())
+        "INSERT INTO organizations (id, name, slug, created_by_user_id, created_at)
+         VALUES (gen_random_uuid(), $1, $2, $3, NOW())
          RETURNING id",
     )
     .bind(name)
     .bind(slug)
+    .bind(owner_id)
     .fetch_one(pool)
     .await
     .expect("Failed to create test organization");
 
     // Add owner membership
     sqlx::query(
-        "INSERT INTO organization_memberships (id, organization_id, user_id, role, created_at)
-         VALUES (gen_random_uuid(), $1, $2, 'owner', NOW())
+        "INSERT INTO organization_memberships (organization_id, user_id, role, created_at)
+         VALUES ($1, $2, 'owner', NOW())
          ON CONFLICT (organization_id, user_id) DO UPDATE SET role = 'owner'",
     )
     .bind(org_id)
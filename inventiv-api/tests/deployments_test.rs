@@ -170,3 +170,733 @@ async fn test_create_deployment_missing_model() {
         .unwrap()
         .contains("Missing model_id"));
 }
+
+#[tokio::test]
+async fn test_provider_code_resolution_is_cached() {
+    use axum::extract::State;
+    use axum::response::IntoResponse;
+    use axum::{Extension, Json};
+    use inventiv_api::auth::AuthUser;
+    use inventiv_api::handlers::deployments::{create_deployment, DeploymentRequest};
+    use inventiv_api::AppState;
+
+    let pool = get_test_db_pool().await;
+    let mock_provider_id = ensure_mock_provider(&pool).await;
+    let state = AppState::new(common::get_test_redis_client().await, pool.clone());
+
+    let suffix = Uuid::new_v4().simple().to_string();
+    let email = format!("deploy_cache_{}@test.com", &suffix[..8]);
+    let user_id = create_test_user(&pool, &email, "password123").await;
+    let org_id = create_test_organization(
+        &pool,
+        "Provider Cache Org",
+        &format!("provider-cache-{}", &suffix[..8]),
+        user_id,
+    )
+    .await;
+    let user = AuthUser {
+        user_id,
+        email,
+        role: "admin".to_string(),
+        session_id: Uuid::new_v4().to_string(),
+        current_organization_id: Some(org_id),
+        current_organization_role: Some("owner".to_string()),
+    };
+    let request = |provider_code: Option<&str>, provider_id: Option<Uuid>| DeploymentRequest {
+        provider_code: provider_code.map(str::to_string),
+        provider_id,
+        zone: "no-such-zone".to_string(),
+        instance_type: "no-such-type".to_string(),
+        model_id: None,
+        max_runtime_hours: None,
+        auto_terminate_on_max_runtime: None,
+        ttl_minutes: None,
+        cost_center: None,
+        reuse_volume_id: None,
+    };
+
+    // Deployments via provider_code are not flagged as deprecated.
+    let mut deprecation_headers = Vec::new();
+    for _ in 0..2 {
+        let resp = create_deployment(
+            State(state.clone()),
+            Extension(user.clone()),
+            Json(request(Some("mock"), None)),
+        )
+        .await
+        .into_response();
+        deprecation_headers.push(resp.headers().get("deprecation").cloned());
+    }
+    assert!(deprecation_headers.iter().all(Option::is_none));
+
+    // The deprecated provider_id path still works but is flagged.
+    let resp = create_deployment(
+        State(state.clone()),
+        Extension(user.clone()),
+        Json(request(None, Some(mock_provider_id))),
+    )
+    .await
+    .into_response();
+    assert_eq!(resp.headers().get("deprecation").unwrap(), "true");
+
+    // Active providers are cached: renaming the code in the DB goes unnoticed until invalidation.
+    let active_code = format!("test-active-{}", &suffix[..8]);
+    let active_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO providers (id, name, code, is_active) VALUES (gen_random_uuid(), $1, $1, true) RETURNING id",
+    )
+    .bind(&active_code)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        state.provider_codes.resolve(&pool, &active_code).await,
+        Some(active_id)
+    );
+    sqlx::query("UPDATE providers SET code = code || '-renamed' WHERE id = $1")
+        .bind(active_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(
+        state.provider_codes.resolve(&pool, &active_code).await,
+        Some(active_id)
+    );
+    state.provider_codes.invalidate();
+    assert_eq!(
+        state.provider_codes.resolve(&pool, &active_code).await,
+        None
+    );
+
+    // Inactive providers resolve (so validation can reject them) but are never cached.
+    let inactive_code = format!("test-inactive-{}", &suffix[..8]);
+    sqlx::query(
+        "INSERT INTO providers (id, name, code, is_active) VALUES (gen_random_uuid(), $1, $1, false)",
+    )
+    .bind(&inactive_code)
+    .execute(&pool)
+    .await
+    .unwrap();
+    assert!(state
+        .provider_codes
+        .resolve(&pool, &inactive_code)
+        .await
+        .is_some());
+    sqlx::query("DELETE FROM providers WHERE code = $1")
+        .bind(&inactive_code)
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(
+        state.provider_codes.resolve(&pool, &inactive_code).await,
+        None
+    );
+
+    sqlx::query("DELETE FROM providers WHERE id = $1")
+        .bind(active_id)
+        .execute(&pool)
+        .await
+        .ok();
+}
+
+#[tokio::test]
+async fn test_failed_validated_update_leaves_no_partial_instance_state() {
+    use axum::extract::State;
+    use axum::response::IntoResponse;
+    use axum::{Extension, Json};
+    use inventiv_api::auth::AuthUser;
+    use inventiv_api::handlers::deployments::{create_deployment, DeploymentRequest};
+    use inventiv_api::AppState;
+
+    let pool = get_test_db_pool().await;
+    ensure_mock_provider(&pool).await;
+    let state = AppState::new(common::get_test_redis_client().await, pool.clone());
+    let zone_id = get_mock_zone_id(&pool)
+        .await
+        .expect("Mock zone should exist");
+    let type_id = get_mock_instance_type_id(&pool)
+        .await
+        .expect("Mock instance type should exist");
+    let zone_code: String = sqlx::query_scalar("SELECT code FROM zones WHERE id = $1")
+        .bind(zone_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let type_code: String = sqlx::query_scalar("SELECT code FROM instance_types WHERE id = $1")
+        .bind(type_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let model_id: Uuid =
+        sqlx::query_scalar("SELECT id FROM models WHERE model_id = 'mock-echo-model'")
+            .fetch_one(&pool)
+            .await
+            .expect("mock echo model");
+
+    let suffix = Uuid::new_v4().simple().to_string();
+    let email = format!("deploy_tx_{}@test.com", &suffix[..8]);
+    let user_id = create_test_user(&pool, &email, "password123").await;
+    let org_id = create_test_organization(
+        &pool,
+        "Deploy Tx Org",
+        &format!("deploy-tx-{}", &suffix[..8]),
+        user_id,
+    )
+    .await;
+    let user = AuthUser {
+        user_id,
+        email,
+        role: "admin".to_string(),
+        session_id: Uuid::new_v4().to_string(),
+        current_organization_id: Some(org_id),
+        current_organization_role: Some("owner".to_string()),
+    };
+
+    // Simulate a failure in the middle of the validated update: this cost_center makes it raise.
+    let poison = format!("tx-fail-{}", &suffix[..8]);
+    let trigger = format!("test_deploy_tx_fail_{}", &suffix[..8]);
+    sqlx::query(&format!(
+        "CREATE FUNCTION {trigger}() RETURNS trigger LANGUAGE plpgsql AS $$
+         BEGIN
+           IF NEW.cost_center = '{poison}' THEN
+             RAISE EXCEPTION 'simulated failure';
+           END IF;
+           RETURN NEW;
+         END $$"
+    ))
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(&format!(
+        "CREATE TRIGGER {trigger} BEFORE UPDATE ON instances FOR EACH ROW EXECUTE FUNCTION {trigger}()"
+    ))
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let resp = create_deployment(
+        State(state),
+        Extension(user),
+        Json(DeploymentRequest {
+            provider_code: Some("mock".to_string()),
+            provider_id: None,
+            zone: zone_code,
+            instance_type: type_code,
+            model_id: Some(model_id),
+            max_runtime_hours: Some(4),
+            auto_terminate_on_max_runtime: None,
+            ttl_minutes: None,
+            cost_center: Some(poison),
+            reuse_volume_id: None,
+        }),
+    )
+    .await
+    .into_response();
+    let status = resp.status();
+    let body: serde_json::Value = serde_json::from_slice(
+        &axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap(),
+    )
+    .unwrap();
+
+    sqlx::query(&format!("DROP TRIGGER {trigger} ON instances"))
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query(&format!("DROP FUNCTION {trigger}()"))
+        .execute(&pool)
+        .await
+        .ok();
+
+    assert_eq!(status, 500);
+    let instance_id: Uuid = body["instance_id"].as_str().unwrap().parse().unwrap();
+    let row: (
+        String,
+        Option<String>,
+        Option<Uuid>,
+        Option<Uuid>,
+        Option<Uuid>,
+        Option<i32>,
+        Option<String>,
+    ) = sqlx::query_as(
+        "SELECT status::text, error_code, zone_id, instance_type_id, model_id, max_runtime_hours, cost_center
+         FROM instances WHERE id = $1",
+    )
+    .bind(instance_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(row.0, "provisioning_failed");
+    assert_eq!(row.1.as_deref(), Some("DB_ERROR"));
+    assert_eq!((row.2, row.3, row.4), (None, None, None));
+    assert_eq!(row.5, None);
+    assert_eq!(row.6, None);
+}
+
+#[tokio::test]
+async fn test_reuse_volume_is_reserved_once_and_rejected_while_in_use() {
+    use axum::extract::State;
+    use axum::response::IntoResponse;
+    use axum::{Extension, Json};
+    use inventiv_api::auth::AuthUser;
+    use inventiv_api::handlers::deployments::{create_deployment, DeploymentRequest};
+    use inventiv_api::AppState;
+
+    let pool = get_test_db_pool().await;
+    let provider_id = ensure_mock_provider(&pool).await;
+    let state = AppState::new(common::get_test_redis_client().await, pool.clone());
+    let zone_id = get_mock_zone_id(&pool)
+        .await
+        .expect("Mock zone should exist");
+    let type_id = get_mock_instance_type_id(&pool)
+        .await
+        .expect("Mock instance type should exist");
+    let zone_code: String = sqlx::query_scalar("SELECT code FROM zones WHERE id = $1")
+        .bind(zone_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let type_code: String = sqlx::query_scalar("SELECT code FROM instance_types WHERE id = $1")
+        .bind(type_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let model_id: Uuid =
+        sqlx::query_scalar("SELECT id FROM models WHERE model_id = 'mock-echo-model'")
+            .fetch_one(&pool)
+            .await
+            .expect("mock echo model");
+
+    let suffix = Uuid::new_v4().simple().to_string();
+    let email = format!("deploy_reuse_{}@test.com", &suffix[..8]);
+    let user_id = create_test_user(&pool, &email, "password123").await;
+    let org_id = create_test_organization(
+        &pool,
+        "Deploy Reuse Org",
+        &format!("deploy-reuse-{}", &suffix[..8]),
+        user_id,
+    )
+    .await;
+    let user = AuthUser {
+        user_id,
+        email,
+        role: "admin".to_string(),
+        session_id: Uuid::new_v4().to_string(),
+        current_organization_id: Some(org_id),
+        current_organization_role: Some("owner".to_string()),
+    };
+
+    // A data volume kept by a terminated instance.
+    let previous_id = Uuid::new_v4();
+    let volume_id = format!("vol-cache-{}", &suffix[..8]);
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, status, created_at, terminated_at, gpu_profile)
+         VALUES ($1, $2, 'terminated', NOW(), NOW(), '{}')",
+    )
+    .bind(previous_id)
+    .bind(provider_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO instance_volumes (id, instance_id, provider_id, zone_code, provider_volume_id, volume_type, size_bytes, delete_on_terminate, status, is_boot)
+         VALUES (gen_random_uuid(), $1, $2, $3, $4, 'sbs_volume', 200000000000, FALSE, 'attached', FALSE)",
+    )
+    .bind(previous_id)
+    .bind(provider_id)
+    .bind(&zone_code)
+    .bind(&volume_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let request = |reuse_volume_id: &str| DeploymentRequest {
+        provider_code: Some("mock".to_string()),
+        provider_id: None,
+        zone: zone_code.clone(),
+        instance_type: type_code.clone(),
+        model_id: Some(model_id),
+        max_runtime_hours: None,
+        auto_terminate_on_max_runtime: None,
+        ttl_minutes: None,
+        cost_center: None,
+        reuse_volume_id: Some(reuse_volume_id.to_string()),
+    };
+    let mut outcomes = Vec::new();
+    for reuse in [volume_id.as_str(), volume_id.as_str(), "vol-unknown"] {
+        let resp = create_deployment(
+            State(state.clone()),
+            Extension(user.clone()),
+            Json(request(reuse)),
+        )
+        .await
+        .into_response();
+        let status = resp.status();
+        let body: serde_json::Value = serde_json::from_slice(
+            &axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        let instance_id: Uuid = body["instance_id"].as_str().unwrap().parse().unwrap();
+        let error_code: Option<String> =
+            sqlx::query_scalar("SELECT error_code FROM instances WHERE id = $1")
+                .bind(instance_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        outcomes.push((instance_id, status.as_u16(), error_code));
+    }
+    let reserved: Vec<(String, String, bool)> = sqlx::query_as(
+        "SELECT provider_volume_id, status, delete_on_terminate FROM instance_volumes WHERE instance_id = $1",
+    )
+    .bind(outcomes[0].0)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+
+    let mut ids: Vec<Uuid> = outcomes.iter().map(|o| o.0).collect();
+    ids.push(previous_id);
+    for table in ["instance_volumes", "action_logs"] {
+        sqlx::query(&format!(
+            "DELETE FROM {} WHERE instance_id = ANY($1)",
+            table
+        ))
+        .bind(&ids)
+        .execute(&pool)
+        .await
+        .ok();
+    }
+    sqlx::query("DELETE FROM instances WHERE id = ANY($1)")
+        .bind(&ids)
+        .execute(&pool)
+        .await
+        .ok();
+
+    assert_eq!(outcomes[0].1, 200);
+    assert_eq!(reserved, vec![(volume_id, "reserved".to_string(), false)]);
+    // The first deployment now holds the volume.
+    assert_eq!(
+        (outcomes[1].1, outcomes[1].2.as_deref()),
+        (409, Some("REUSE_VOLUME_IN_USE"))
+    );
+    assert_eq!(
+        (outcomes[2].1, outcomes[2].2.as_deref()),
+        (400, Some("REUSE_VOLUME_NOT_FOUND"))
+    );
+}
+
+#[tokio::test]
+async fn test_deploy_failure_codes_are_known_error_codes() {
+    use axum::extract::{Query, State};
+    use axum::response::IntoResponse;
+    use axum::{Extension, Json};
+    use inventiv_api::auth::AuthUser;
+    use inventiv_api::handlers::commands::{list_error_codes, ErrorCodesQuery};
+    use inventiv_api::handlers::deployments::{create_deployment, DeploymentRequest};
+    use inventiv_api::AppState;
+    use inventiv_common::error_code::ErrorCode;
+
+    let pool = get_test_db_pool().await;
+    ensure_mock_provider(&pool).await;
+    let state = AppState::new(common::get_test_redis_client().await, pool.clone());
+    let zone_id = get_mock_zone_id(&pool)
+        .await
+        .expect("Mock zone should exist");
+    let zone_code: String = sqlx::query_scalar("SELECT code FROM zones WHERE id = $1")
+        .bind(zone_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+    let type_id = get_mock_instance_type_id(&pool)
+        .await
+        .expect("Mock instance type should exist");
+    let type_code: String = sqlx::query_scalar("SELECT code FROM instance_types WHERE id = $1")
+        .bind(type_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+    let suffix = Uuid::new_v4().simple().to_string();
+    let email = format!("deploy_codes_{}@test.com", &suffix[..8]);
+    let user_id = create_test_user(&pool, &email, "password123").await;
+    let org_id = create_test_organization(
+        &pool,
+        "Deploy Codes Org",
+        &format!("deploy-codes-{}", &suffix[..8]),
+        user_id,
+    )
+    .await;
+    let user = AuthUser {
+        user_id,
+        email,
+        role: "admin".to_string(),
+        session_id: Uuid::new_v4().to_string(),
+        current_organization_id: Some(org_id),
+        current_organization_role: Some("owner".to_string()),
+    };
+
+    let request = |zone: &str, instance_type: &str| DeploymentRequest {
+        provider_code: Some("mock".to_string()),
+        provider_id: None,
+        zone: zone.to_string(),
+        instance_type: instance_type.to_string(),
+        model_id: Some(Uuid::new_v4()),
+        max_runtime_hours: None,
+        auto_terminate_on_max_runtime: None,
+        ttl_minutes: None,
+        cost_center: None,
+        reuse_volume_id: None,
+    };
+    let mut codes = Vec::new();
+    let mut ids = Vec::new();
+    for payload in [
+        request("", ""),
+        // Valid zone and type, unknown model.
+        request(&zone_code, &type_code),
+        request("no-such-zone", "any"),
+        request(&zone_code, "no-such-type"),
+    ] {
+        let resp = create_deployment(State(state.clone()), Extension(user.clone()), Json(payload))
+            .await
+            .into_response();
+        let body: serde_json::Value = serde_json::from_slice(
+            &axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        let instance_id: Uuid = body["instance_id"].as_str().unwrap().parse().unwrap();
+        let stored: Option<String> =
+            sqlx::query_scalar("SELECT error_code FROM instances WHERE id = $1")
+                .bind(instance_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        let logged: Vec<Option<String>> = sqlx::query_scalar(
+            "SELECT metadata->>'error_code' FROM action_logs WHERE instance_id = $1",
+        )
+        .bind(instance_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        codes.push(stored);
+        codes.extend(logged.into_iter().filter(Option::is_some));
+        ids.push(instance_id);
+    }
+
+    let listed = list_error_codes(Query(ErrorCodesQuery {
+        lang: Some("fr".to_string()),
+    }))
+    .await;
+    let listed = serde_json::to_value(&listed.0).unwrap();
+
+    sqlx::query("DELETE FROM action_logs WHERE instance_id = ANY($1)")
+        .bind(&ids)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM instances WHERE id = ANY($1)")
+        .bind(&ids)
+        .execute(&pool)
+        .await
+        .ok();
+
+    // Every failed deployment records a code, in the instance row and in its action log.
+    assert!(codes.len() >= 8, "codes: {:?}", codes);
+    for code in &codes {
+        let code = code
+            .as_deref()
+            .expect("failed deployment without error_code");
+        assert!(
+            ErrorCode::parse(code).is_some(),
+            "unknown error code {}",
+            code
+        );
+    }
+    assert_eq!(listed.as_array().unwrap().len(), ErrorCode::ALL.len());
+    assert!(listed
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|c| c["code"] == "INVALID_ZONE"
+            && c["message"]
+                == ErrorCode::InvalidZone.message(inventiv_common::error_code::Locale::Fr)));
+}
+
+#[tokio::test]
+async fn test_deployment_is_accepted_until_orchestrator_picks_it_up() {
+    use axum::extract::State;
+    use axum::response::IntoResponse;
+    use axum::{Extension, Json};
+    use inventiv_api::auth::AuthUser;
+    use inventiv_api::handlers::deployments::{create_deployment, DeploymentRequest};
+    use inventiv_api::AppState;
+
+    let pool = get_test_db_pool().await;
+    ensure_mock_provider(&pool).await;
+    let state = AppState::new(common::get_test_redis_client().await, pool.clone());
+    let zone_code: String = sqlx::query_scalar("SELECT code FROM zones WHERE id = $1")
+        .bind(
+            get_mock_zone_id(&pool)
+                .await
+                .expect("Mock zone should exist"),
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let type_code: String = sqlx::query_scalar("SELECT code FROM instance_types WHERE id = $1")
+        .bind(
+            get_mock_instance_type_id(&pool)
+                .await
+                .expect("Mock instance type should exist"),
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let model_id: Uuid =
+        sqlx::query_scalar("SELECT id FROM models WHERE model_id = 'mock-echo-model'")
+            .fetch_one(&pool)
+            .await
+            .expect("mock echo model");
+
+    let suffix = Uuid::new_v4().simple().to_string();
+    let email = format!("deploy_accepted_{}@test.com", &suffix[..8]);
+    let user_id = create_test_user(&pool, &email, "password123").await;
+    let org_id = create_test_organization(
+        &pool,
+        "Deploy Accepted Org",
+        &format!("deploy-accepted-{}", &suffix[..8]),
+        user_id,
+    )
+    .await;
+    let user = AuthUser {
+        user_id,
+        email,
+        role: "admin".to_string(),
+        session_id: Uuid::new_v4().to_string(),
+        current_organization_id: Some(org_id),
+        current_organization_role: Some("owner".to_string()),
+    };
+
+    let resp = create_deployment(
+        State(state),
+        Extension(user),
+        Json(DeploymentRequest {
+            provider_code: Some("mock".to_string()),
+            provider_id: None,
+            zone: zone_code,
+            instance_type: type_code,
+            model_id: Some(model_id),
+            max_runtime_hours: None,
+            auto_terminate_on_max_runtime: None,
+            ttl_minutes: None,
+            cost_center: None,
+            reuse_volume_id: None,
+        }),
+    )
+    .await
+    .into_response();
+    let http_status = resp.status();
+    let body: serde_json::Value = serde_json::from_slice(
+        &axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap(),
+    )
+    .unwrap();
+    let instance_id: Uuid = body["instance_id"].as_str().unwrap().parse().unwrap();
+    let status: String = sqlx::query_scalar("SELECT status::text FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+    sqlx::query("DELETE FROM action_logs WHERE instance_id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await
+        .ok();
+
+    assert_eq!(http_status, 200);
+    assert_eq!(body["status"], "accepted");
+    // Queued, not in flight: no orchestrator has started provisioning it.
+    assert_eq!(status, "accepted");
+}
+
+#[tokio::test]
+async fn test_cloud_init_preview_renders_bootstrap_within_size_limit() {
+    use axum::extract::State;
+    use axum::response::IntoResponse;
+    use axum::{Extension, Json};
+    use inventiv_api::auth::AuthUser;
+    use inventiv_api::handlers::deployments::{preview_cloud_init, CloudInitPreviewRequest};
+    use inventiv_api::AppState;
+
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(common::get_test_redis_client().await, pool.clone());
+    let model_id: Uuid =
+        sqlx::query_scalar("SELECT id FROM models WHERE model_id = 'mock-echo-model'")
+            .fetch_one(&pool)
+            .await
+            .expect("mock echo model");
+
+    let admin = AuthUser {
+        user_id: Uuid::new_v4(),
+        email: "cloud_init_preview@test.com".to_string(),
+        role: "admin".to_string(),
+        session_id: Uuid::new_v4().to_string(),
+        current_organization_id: None,
+        current_organization_role: None,
+    };
+    let request = || CloudInitPreviewRequest {
+        model_id: Some(model_id),
+        instance_id: None,
+        control_plane_url: Some("https://cp.example/".to_string()),
+        vllm_image: None,
+        vllm_port: None,
+        worker_health_port: None,
+        ssh_public_key: Some("ssh-ed25519 AAAAPREVIEW test".to_string()),
+    };
+
+    let resp = preview_cloud_init(
+        State(state.clone()),
+        Extension(admin.clone()),
+        Json(request()),
+    )
+    .await
+    .into_response();
+    assert_eq!(resp.status(), 200);
+    let body: serde_json::Value = serde_json::from_slice(
+        &axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap(),
+    )
+    .unwrap();
+    let doc = body["cloud_init"].as_str().unwrap();
+    assert!(doc.starts_with("#cloud-config\n"));
+    assert!(doc.contains("  - ssh-ed25519 AAAAPREVIEW test\n"));
+    assert!(doc.contains("/usr/local/bin/inventiv-worker-bootstrap.sh"));
+    assert!(doc.contains("MODEL_ID=\"mock-echo-model\""));
+    assert!(doc.contains("CONTROL_PLANE_URL=\"https://cp.example\""));
+    assert!(doc.contains("WORKER_AUTH_TOKEN=\"<redacted>\""));
+    assert!(doc.contains("WORKER_HF_TOKEN=\"<redacted>\""));
+    assert_eq!(body["size_bytes"].as_u64().unwrap() as usize, doc.len());
+    assert!(body["size_bytes"].as_u64() <= body["max_bytes"].as_u64());
+    assert_eq!(body["within_limit"], true);
+    assert_eq!(body["worker_client_tls_included"], false);
+
+    let user = AuthUser {
+        role: "user".to_string(),
+        ..admin
+    };
+    let resp = preview_cloud_init(State(state), Extension(user), Json(request()))
+        .await
+        .into_response();
+    assert_eq!(resp.status(), 403);
+}
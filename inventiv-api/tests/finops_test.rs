@@ -0,0 +1,392 @@
+// Integration tests for FinOps query param validation
+
+mod common;
+
+use axum::extract::{Query, State};
+use axum::response::IntoResponse;
+use common::{get_test_db_pool, get_test_redis_client};
+use inventiv_api::finops::{
+    self, BreakdownWindowParams, CostCurrentParams, CostsDashboardSeriesParams, SeriesParams,
+};
+use inventiv_api::AppState;
+
+async fn error_body(response: axum::response::Response) -> serde_json::Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_invalid_granularity_is_rejected() {
+    let state = AppState::new(get_test_redis_client().await, get_test_db_pool().await);
+
+    let response = finops::get_costs_dashboard_series(
+        State(state.clone()),
+        Query(CostsDashboardSeriesParams {
+            window: Some("day".to_string()),
+            granularity: Some("fortnight".to_string()),
+            limit_points: None,
+        }),
+    )
+    .await
+    .into_response();
+    assert_eq!(response.status(), 400);
+    let body = error_body(response).await;
+    assert_eq!(body["error"], "invalid_query_param");
+    assert_eq!(body["param"], "granularity");
+
+    // Omitted params keep their defaults.
+    let response = finops::get_costs_dashboard_series(
+        State(state),
+        Query(CostsDashboardSeriesParams {
+            window: None,
+            granularity: Some("hour".to_string()),
+            limit_points: None,
+        }),
+    )
+    .await
+    .into_response();
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn test_over_range_window_is_rejected() {
+    let state = AppState::new(get_test_redis_client().await, get_test_db_pool().await);
+
+    let response = finops::get_costs_dashboard_window(
+        State(state.clone()),
+        Query(BreakdownWindowParams {
+            window: None,
+            minutes: Some(60 * 24 * 366),
+            limit_instances: None,
+        }),
+    )
+    .await
+    .into_response();
+    assert_eq!(response.status(), 400);
+    assert_eq!(error_body(response).await["param"], "minutes");
+
+    let response = finops::get_costs_dashboard_window(
+        State(state.clone()),
+        Query(BreakdownWindowParams {
+            window: Some("decade".to_string()),
+            minutes: None,
+            limit_instances: None,
+        }),
+    )
+    .await
+    .into_response();
+    assert_eq!(response.status(), 400);
+    assert_eq!(error_body(response).await["param"], "window");
+
+    let response = finops::get_cost_actual_series(
+        State(state),
+        Query(SeriesParams {
+            minutes: Some(0),
+            method: None,
+            provider_id: None,
+            instance_id: None,
+        }),
+    )
+    .await
+    .into_response();
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn test_instance_cost_breakdown_matches_instance_list_total() {
+    use axum::extract::Path;
+    use inventiv_api::handlers::instances::{list_instances, ListInstanceParams};
+
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+    let provider_id = common::ensure_mock_provider(&pool).await;
+
+    let suffix = &uuid::Uuid::new_v4().simple().to_string()[..8];
+    let type_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO instance_types (id, provider_id, name, code, gpu_count, vram_per_gpu_gb, cost_per_hour, is_active)
+         VALUES ($1, $2, $3, $3, 1, 24, 1.80, true)",
+    )
+    .bind(type_id)
+    .bind(provider_id)
+    .bind(format!("test-cost-{}", suffix))
+    .execute(&pool)
+    .await
+    .expect("insert instance type");
+
+    // Terminated after 10.5 minutes: the last minute is partial.
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, instance_type_id, provider_instance_id, status,
+                                created_at, terminated_at, gpu_profile)
+         VALUES ($1, $2, $3, 'srv-cost-test', 'terminated',
+                 date_trunc('minute', NOW()) - interval '20 minutes',
+                 date_trunc('minute', NOW()) - interval '9 minutes 30 seconds', '{}')",
+    )
+    .bind(instance_id)
+    .bind(provider_id)
+    .bind(type_id)
+    .execute(&pool)
+    .await
+    .expect("insert instance");
+
+    // Actual minutes as inventiv-finops computes them.
+    sqlx::query(
+        r#"
+        INSERT INTO finops.cost_actual_minute (bucket_minute, provider_id, instance_id, amount_eur)
+        SELECT b, i.provider_id, i.id,
+               (public.instance_billable_seconds(i, b, b + interval '1 minute') / 3600.0)::numeric
+                 * public.effective_cost_per_hour(i.instance_type_id, b)
+        FROM instances i,
+             generate_series(date_trunc('minute', i.created_at), date_trunc('minute', i.terminated_at), interval '1 minute') b
+        WHERE i.id = $1
+          AND public.instance_billable_seconds(i, b, b + interval '1 minute') > 0
+        "#,
+    )
+    .bind(instance_id)
+    .execute(&pool)
+    .await
+    .expect("insert actual minutes");
+
+    let breakdown = finops::get_instance_cost(State(state.clone()), Path(instance_id))
+        .await
+        .unwrap_or_else(|_| panic!("instance cost not found"))
+        .0;
+    let listed = list_instances(
+        State(state.clone()),
+        Query(ListInstanceParams { archived: None }),
+    )
+    .await
+    .0
+    .into_iter()
+    .find(|i| i.id == instance_id)
+    .expect("instance listed");
+
+    let missing = finops::get_instance_cost(State(state), Path(uuid::Uuid::new_v4())).await;
+
+    sqlx::query("DELETE FROM finops.cost_actual_minute WHERE instance_id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM instance_types WHERE id = $1")
+        .bind(type_id)
+        .execute(&pool)
+        .await
+        .ok();
+
+    let listed_total = listed.total_cost.expect("total_cost");
+    assert!((breakdown.summary.total_eur - listed_total).abs() < 1e-4);
+    assert!((breakdown.summary.total_eur - 0.315).abs() < 1e-4);
+    assert_eq!(breakdown.summary.billable_seconds, 630);
+    assert_eq!(breakdown.summary.hourly_rate_eur, Some(1.8));
+    assert_eq!(breakdown.minutes.len(), 11);
+    assert!(breakdown.minutes[0].bucket_minute < breakdown.minutes[10].bucket_minute);
+    assert!(missing.is_err());
+}
+
+#[tokio::test]
+async fn test_deployment_cost_center_gets_its_own_cost_bucket() {
+    use axum::Extension;
+    use axum::Json;
+    use inventiv_api::auth::AuthUser;
+    use inventiv_api::handlers::deployments::{create_deployment, DeploymentRequest};
+
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+    common::ensure_mock_provider(&pool).await;
+    let zone_id = common::get_mock_zone_id(&pool).await.expect("mock zone");
+    let type_id = common::get_mock_instance_type_id(&pool)
+        .await
+        .expect("mock instance type");
+    let zone_code: String = sqlx::query_scalar("SELECT code FROM zones WHERE id = $1")
+        .bind(zone_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let type_code: String = sqlx::query_scalar("SELECT code FROM instance_types WHERE id = $1")
+        .bind(type_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+    let suffix = &uuid::Uuid::new_v4().simple().to_string()[..8];
+    // Mock instance types only accept the seeded echo model.
+    let model_id: uuid::Uuid =
+        sqlx::query_scalar("SELECT id FROM models WHERE model_id = 'mock-echo-model'")
+            .fetch_one(&pool)
+            .await
+            .expect("mock echo model");
+    let email = format!("cost_center_{}@test.com", suffix);
+    let user_id = common::create_test_user(&pool, &email, "password123").await;
+    let org_id = common::create_test_organization(
+        &pool,
+        "Cost Center Org",
+        &format!("cost-center-{}", suffix),
+        user_id,
+    )
+    .await;
+    let org_default = format!("org-default-{}", suffix);
+    sqlx::query("UPDATE organizations SET default_cost_center = $2 WHERE id = $1")
+        .bind(org_id)
+        .bind(&org_default)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let user = AuthUser {
+        user_id,
+        email,
+        role: "admin".to_string(),
+        session_id: uuid::Uuid::new_v4().to_string(),
+        current_organization_id: Some(org_id),
+        current_organization_role: Some("owner".to_string()),
+    };
+    let request = |cost_center: Option<&str>| DeploymentRequest {
+        provider_code: Some("mock".to_string()),
+        provider_id: None,
+        zone: zone_code.clone(),
+        instance_type: type_code.clone(),
+        model_id: Some(model_id),
+        max_runtime_hours: None,
+        auto_terminate_on_max_runtime: None,
+        ttl_minutes: None,
+        cost_center: cost_center.map(str::to_string),
+        reuse_volume_id: None,
+    };
+    let deploy = |req: DeploymentRequest| {
+        let state = state.clone();
+        let user = user.clone();
+        async move {
+            let resp = create_deployment(State(state), Extension(user), Json(req))
+                .await
+                .into_response();
+            let status = resp.status();
+            (status, error_body(resp).await)
+        }
+    };
+
+    let (status, _) = deploy(request(Some("bad tag!"))).await;
+    assert_eq!(status, 400);
+
+    let explicit = format!("ml-research-{}", suffix);
+    let (_, body) = deploy(request(Some(&explicit))).await;
+    let tagged_id: uuid::Uuid = body["instance_id"].as_str().unwrap().parse().unwrap();
+    let (_, body) = deploy(request(None)).await;
+    let defaulted_id: uuid::Uuid = body["instance_id"].as_str().unwrap().parse().unwrap();
+
+    let stored: Vec<(uuid::Uuid, Option<String>)> =
+        sqlx::query_as("SELECT id, cost_center FROM instances WHERE id = ANY($1)")
+            .bind(vec![tagged_id, defaulted_id])
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    let stored_for = |id| stored.iter().find(|(i, _)| *i == id).unwrap().1.clone();
+    assert_eq!(stored_for(tagged_id), Some(explicit.clone()));
+    assert_eq!(stored_for(defaulted_id), Some(org_default.clone()));
+
+    // Actual cost for the tagged instance at the latest bucket, so it falls in any window.
+    sqlx::query(
+        r#"
+        INSERT INTO finops.cost_actual_minute (bucket_minute, provider_id, instance_id, amount_eur)
+        SELECT COALESCE((SELECT MAX(bucket_minute) FROM finops.cost_actual_minute), date_trunc('minute', NOW())),
+               i.provider_id, i.id, 0.42
+        FROM instances i WHERE i.id = $1
+        "#,
+    )
+    .bind(tagged_id)
+    .execute(&pool)
+    .await
+    .expect("insert actual minute");
+
+    let breakdown = finops::get_costs_dashboard_by_cost_center(
+        State(state.clone()),
+        Query(BreakdownWindowParams {
+            window: Some("1h".to_string()),
+            minutes: None,
+            limit_instances: None,
+        }),
+    )
+    .await
+    .unwrap_or_else(|_| panic!("by-cost-center breakdown failed"))
+    .0;
+
+    sqlx::query("DELETE FROM finops.cost_actual_minute WHERE instance_id = $1")
+        .bind(tagged_id)
+        .execute(&pool)
+        .await
+        .ok();
+
+    let bucket = breakdown
+        .by_cost_center_eur
+        .iter()
+        .find(|r| r.cost_center.as_deref() == Some(explicit.as_str()))
+        .expect("cost_center bucket present");
+    assert_eq!(bucket.instances_count, 1);
+    assert!((bucket.amount_eur - 0.42).abs() < 1e-6);
+}
+
+#[tokio::test]
+async fn test_cost_current_reports_a_single_forecast_method() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+
+    // Both methods stored for the same (latest) bucket, with different burn rates.
+    let provider_id: uuid::Uuid = sqlx::query_scalar("SELECT id FROM providers LIMIT 1")
+        .fetch_one(&pool)
+        .await
+        .expect("a provider");
+    let bucket: chrono::DateTime<chrono::Utc> = "2099-01-01T00:00:00Z".parse().unwrap();
+    for (method, rate) in [("allocation", 0.0), ("blended", 0.5)] {
+        sqlx::query(
+            "INSERT INTO finops.cost_forecast_minute (bucket_minute, provider_id, method, burn_rate_eur_per_hour,
+                 forecast_eur_per_minute, forecast_eur_per_hour, forecast_eur_per_day, forecast_eur_per_month_30d, forecast_eur_per_year_365d)
+             VALUES ($1, $2, $3, $4, 0, $4, 0, 0, 0)",
+        )
+        .bind(bucket)
+        .bind(provider_id)
+        .bind(method)
+        .bind(rate)
+        .execute(&pool)
+        .await
+        .expect("insert forecast row");
+    }
+
+    let current = |method: Option<&str>| {
+        finops::get_cost_current(
+            State(state.clone()),
+            Query(CostCurrentParams {
+                method: method.map(|m| m.to_string()),
+            }),
+        )
+    };
+    let default = current(None).await.map(|r| r.0);
+    let blended = current(Some("blended")).await.map(|r| r.0);
+    let invalid = current(Some("guess")).await.into_response();
+
+    sqlx::query("DELETE FROM finops.cost_forecast_minute WHERE bucket_minute = $1")
+        .bind(bucket)
+        .execute(&pool)
+        .await
+        .ok();
+
+    let default = default.unwrap_or_else(|_| panic!("default forecast failed"));
+    assert_eq!(default.method, "allocation");
+    assert_eq!(default.latest_bucket_minute, Some(bucket));
+    assert!(default.forecast.iter().all(|r| r.method == "allocation"));
+    assert_eq!(default.forecast.len(), 1);
+    assert_eq!(default.forecast[0].burn_rate_eur_per_hour, 0.0);
+
+    let blended = blended.unwrap_or_else(|_| panic!("blended forecast failed"));
+    assert_eq!(blended.method, "blended");
+    assert!(blended.forecast.iter().all(|r| r.method == "blended"));
+    assert_eq!(blended.forecast[0].burn_rate_eur_per_hour, 0.5);
+
+    assert_eq!(invalid.status(), 400);
+}
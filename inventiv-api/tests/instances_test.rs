@@ -3,11 +3,16 @@
 
 mod common;
 
+use axum::extract::{Path, State};
+use axum::response::IntoResponse;
+use axum::Json;
 use axum_test::TestServer;
 use common::{
     create_test_app_service, create_test_session, create_test_user, ensure_mock_provider,
-    get_mock_instance_type_id, get_mock_zone_id, get_test_db_pool,
+    get_mock_instance_type_id, get_mock_zone_id, get_test_db_pool, get_test_redis_client,
 };
+use inventiv_api::handlers::instances;
+use inventiv_api::AppState;
 use serde_json::json;
 use uuid::Uuid;
 
@@ -177,3 +182,557 @@ async fn test_terminate_instance() {
 
     assert_eq!(status, "terminating");
 }
+
+async fn insert_instance_with_status(pool: &sqlx::Pool<sqlx::Postgres>, status: &str) -> Uuid {
+    let mock_provider_id = ensure_mock_provider(pool).await;
+    let mock_zone_id = get_mock_zone_id(pool).await.unwrap();
+    let mock_instance_type_id = get_mock_instance_type_id(pool).await.unwrap();
+    sqlx::query_scalar(
+        "INSERT INTO instances (id, provider_id, zone_id, instance_type_id, status, created_at, failed_at, gpu_profile)
+         VALUES (gen_random_uuid(), $1, $2, $3, $4::instance_status, NOW() - INTERVAL '2 days', NOW() - INTERVAL '2 days', '{}')
+         RETURNING id",
+    )
+    .bind(mock_provider_id)
+    .bind(mock_zone_id)
+    .bind(mock_instance_type_id)
+    .bind(status)
+    .fetch_one(pool)
+    .await
+    .expect("Failed to create test instance")
+}
+
+#[tokio::test]
+async fn test_archive_provisioning_failed_instance() {
+    let app = create_test_app_service().await;
+    let server = TestServer::new(app).unwrap();
+    let pool = get_test_db_pool().await;
+
+    let failed_id = insert_instance_with_status(&pool, "provisioning_failed").await;
+    let ready_id = insert_instance_with_status(&pool, "ready").await;
+
+    let user_id = create_test_user(&pool, "test_user@test.com", "password123").await;
+    let session_token = create_test_session(&pool, user_id, None, None).await;
+
+    let response = server
+        .put(&format!("/instances/{}/archive", failed_id))
+        .add_header("Cookie", format!("inventiv_session={}", session_token))
+        .await;
+    assert_eq!(response.status_code(), 200);
+
+    // Non-terminal instances still cannot be archived.
+    let response = server
+        .put(&format!("/instances/{}/archive", ready_id))
+        .add_header("Cookie", format!("inventiv_session={}", session_token))
+        .await;
+    assert_eq!(response.status_code(), 400);
+
+    let (status, archived): (String, bool) =
+        sqlx::query_as("SELECT status::text, is_archived FROM instances WHERE id = $1")
+            .bind(failed_id)
+            .fetch_one(&pool)
+            .await
+            .expect("Failed to get instance status");
+    assert_eq!(status, "archived");
+    assert!(archived);
+}
+
+#[tokio::test]
+async fn test_resize_rejects_invalid_target_type() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+
+    let instance_id = insert_instance_with_status(&pool, "ready").await;
+    let mock_type_id = get_mock_instance_type_id(&pool).await.unwrap();
+    let scaleway_type_id: Option<Uuid> = sqlx::query_scalar(
+        "SELECT it.id FROM instance_types it JOIN providers p ON p.id = it.provider_id
+         WHERE p.code = 'scaleway' LIMIT 1",
+    )
+    .fetch_optional(&pool)
+    .await
+    .unwrap();
+    let resize = |type_id: Uuid| {
+        instances::resize_instance(
+            State(state.clone()),
+            Path(instance_id),
+            Json(serde_json::from_value(json!({ "instance_type_id": type_id })).unwrap()),
+        )
+    };
+
+    // Not provisioned yet: nothing to resize at the provider.
+    let response = resize(mock_type_id).await.into_response();
+    assert_eq!(response.status(), 400);
+
+    sqlx::query("UPDATE instances SET provider_instance_id = 'srv-resize' WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // Same type as today.
+    let response = resize(mock_type_id).await.into_response();
+    assert_eq!(response.status(), 400);
+
+    // Type from another provider.
+    if let Some(type_id) = scaleway_type_id {
+        let response = resize(type_id).await.into_response();
+        assert_eq!(response.status(), 400);
+    }
+
+    let type_id: Option<Uuid> =
+        sqlx::query_scalar("SELECT instance_type_id FROM instances WHERE id = $1")
+            .bind(instance_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(type_id, Some(mock_type_id));
+}
+
+#[tokio::test]
+async fn test_resize_takes_instance_out_of_routing_and_rejects_a_second_resize() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+
+    let instance_id = insert_instance_with_status(&pool, "ready").await;
+    sqlx::query("UPDATE instances SET provider_instance_id = 'srv-resize-twice' WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let mock_zone_id = get_mock_zone_id(&pool).await.unwrap();
+    let code = format!("mock-resize-{}", &Uuid::new_v4().to_string()[..8]);
+    let target_type: Uuid = sqlx::query_scalar(
+        "INSERT INTO instance_types (id, provider_id, name, code, gpu_count, vram_per_gpu_gb, is_active)
+         SELECT gen_random_uuid(), provider_id, $1, $1, 1, 80, true FROM instances WHERE id = $2
+         RETURNING id",
+    )
+    .bind(&code)
+    .bind(instance_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO instance_type_zones (instance_type_id, zone_id, is_available) VALUES ($1, $2, true)")
+        .bind(target_type)
+        .bind(mock_zone_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let resize = || {
+        instances::resize_instance(
+            State(state.clone()),
+            Path(instance_id),
+            Json(serde_json::from_value(json!({ "instance_type_id": target_type })).unwrap()),
+        )
+    };
+    let first = resize().await.into_response().status();
+    let status: String = sqlx::query_scalar("SELECT status::text FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let second = resize().await.into_response().status();
+
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM instance_type_zones WHERE instance_type_id = $1")
+        .bind(target_type)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM instance_types WHERE id = $1")
+        .bind(target_type)
+        .execute(&pool)
+        .await;
+
+    assert_eq!(first, 202);
+    assert_eq!(status, "resizing");
+    assert_eq!(second, 409);
+}
+
+#[tokio::test]
+async fn test_retry_restarts_failed_provisioning_on_same_instance() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+
+    // A mock provision that failed at create time.
+    let failed_id = insert_instance_with_status(&pool, "provisioning_failed").await;
+    sqlx::query(
+        "UPDATE instances SET error_code = 'PROVIDER_CREATE_FAILED', error_message = 'out of stock', retry_count = 5
+         WHERE id = $1",
+    )
+    .bind(failed_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let ready_id = insert_instance_with_status(&pool, "ready").await;
+    let booted_id = insert_instance_with_status(&pool, "startup_failed").await;
+    sqlx::query("UPDATE instances SET provider_instance_id = 'srv-retry' WHERE id = $1")
+        .bind(booted_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let retry = |id: Uuid| instances::retry_instance(State(state.clone()), Path(id));
+    let accepted = retry(failed_id).await.into_response().status();
+    let again = retry(failed_id).await.into_response().status();
+    let not_failed = retry(ready_id).await.into_response().status();
+    let server_exists = retry(booted_id).await.into_response().status();
+
+    let row: (
+        Uuid,
+        String,
+        Option<String>,
+        Option<String>,
+        bool,
+        Option<i32>,
+    ) = sqlx::query_as(
+        "SELECT id, status::text, error_code, error_message, failed_at IS NULL, retry_count
+         FROM instances WHERE id = $1",
+    )
+    .bind(failed_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let logged: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM action_logs WHERE instance_id = $1 AND action_type = 'REQUEST_RETRY_PROVISION' AND status = 'success'",
+    )
+    .bind(failed_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    for id in [failed_id, ready_id, booted_id] {
+        let _ = sqlx::query("DELETE FROM action_logs WHERE instance_id = $1")
+            .bind(id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(id)
+            .execute(&pool)
+            .await;
+    }
+
+    assert_eq!(accepted, 202);
+    // Same instance id, queued again (accepted) with the failure cleared.
+    assert_eq!(
+        row,
+        (failed_id, "accepted".to_string(), None, None, true, Some(0))
+    );
+    assert_eq!(logged, 1);
+    // Already queued again: nothing to retry.
+    assert_eq!(again, 409);
+    assert_eq!(not_failed, 409);
+    assert_eq!(server_exists, 409);
+}
+
+#[tokio::test]
+async fn test_bulk_archive_by_status_filter() {
+    let app = create_test_app_service().await;
+    let server = TestServer::new(app).unwrap();
+    let pool = get_test_db_pool().await;
+
+    let failed_a = insert_instance_with_status(&pool, "failed").await;
+    let failed_b = insert_instance_with_status(&pool, "failed").await;
+    let terminated = insert_instance_with_status(&pool, "terminated").await;
+
+    let user_id = create_test_user(&pool, "test_user@test.com", "password123").await;
+    let session_token = create_test_session(&pool, user_id, None, None).await;
+
+    let response = server
+        .post("/instances/bulk/archive")
+        .add_header("Cookie", format!("inventiv_session={}", session_token))
+        .json(&json!({"status": "failed", "older_than": chrono::Utc::now() - chrono::Duration::days(1)}))
+        .await;
+    assert_eq!(response.status_code(), 200);
+    let body: serde_json::Value = response.json();
+    let archived: Vec<String> = body["archived_ids"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_str().unwrap().to_string())
+        .collect();
+    assert!(archived.contains(&failed_a.to_string()));
+    assert!(archived.contains(&failed_b.to_string()));
+    assert!(!archived.contains(&terminated.to_string()));
+
+    let response = server
+        .post("/instances/bulk/archive")
+        .add_header("Cookie", format!("inventiv_session={}", session_token))
+        .json(&json!({"status": "ready"}))
+        .await;
+    assert_eq!(response.status_code(), 400);
+}
+
+#[tokio::test]
+async fn test_terminate_plan_reports_each_decision_without_acting() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+
+    let provider_call = insert_instance_with_status(&pool, "ready").await;
+    let no_resource = insert_instance_with_status(&pool, "provisioning_failed").await;
+    let missing_zone = insert_instance_with_status(&pool, "ready").await;
+    let terminated = insert_instance_with_status(&pool, "terminated").await;
+    sqlx::query(
+        "UPDATE instances SET provider_instance_id = 'srv-' || id::text WHERE id = ANY($1)",
+    )
+    .bind(vec![provider_call, missing_zone, terminated])
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query("UPDATE instances SET zone_id = NULL WHERE id = $1")
+        .bind(missing_zone)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let unknown = Uuid::new_v4();
+
+    let response = instances::bulk_plan_terminate_instances(
+        State(state.clone()),
+        Json(
+            serde_json::from_value(json!({
+                "ids": [provider_call, no_resource, missing_zone, terminated, unknown]
+            }))
+            .unwrap(),
+        ),
+    )
+    .await
+    .into_response();
+    assert_eq!(response.status(), 200);
+    let body = json_body(response).await;
+    let decisions: Vec<(String, String)> = body["plans"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| {
+            (
+                p["instance_id"].as_str().unwrap().to_string(),
+                p["decision"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        decisions,
+        vec![
+            (provider_call.to_string(), "provider_call".to_string()),
+            (no_resource.to_string(), "immediate_no_resource".to_string()),
+            (missing_zone.to_string(), "blocked_missing_zone".to_string()),
+            (terminated.to_string(), "already_terminated".to_string()),
+        ]
+    );
+    assert_eq!(body["not_found"], json!([unknown]));
+
+    let response = instances::plan_terminate_instance(State(state.clone()), Path(missing_zone))
+        .await
+        .into_response();
+    assert_eq!(response.status(), 200);
+    let plan = json_body(response).await;
+    assert_eq!(plan["decision"], "blocked_missing_zone");
+    assert_eq!(plan["provider_instance_id_present"], true);
+    assert_eq!(plan["zone_id_present"], false);
+
+    let response = instances::plan_terminate_instance(State(state), Path(unknown))
+        .await
+        .into_response();
+    assert_eq!(response.status(), 404);
+
+    // Dry run: nothing changed, nothing logged.
+    let statuses: Vec<(String, Option<String>)> = sqlx::query_as(
+        "SELECT status::text, error_code FROM instances WHERE id = ANY($1) ORDER BY status::text",
+    )
+    .bind(vec![provider_call, no_resource, missing_zone])
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        statuses,
+        vec![
+            ("provisioning_failed".to_string(), None),
+            ("ready".to_string(), None),
+            ("ready".to_string(), None),
+        ]
+    );
+    let logs: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM action_logs WHERE instance_id = ANY($1) AND action_type = 'REQUEST_TERMINATE'",
+    )
+    .bind(vec![provider_call, no_resource, missing_zone, terminated])
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(logs, 0);
+}
+
+async fn json_body(response: axum::response::Response) -> serde_json::Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_search_sort_accepts_new_fields_and_rejects_unknown() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+    insert_instance_with_status(&pool, "ready").await;
+
+    let params = |v: serde_json::Value| axum::extract::Query(serde_json::from_value(v).unwrap());
+
+    let response = instances::search_instances(
+        State(state.clone()),
+        params(json!({"sort_by": "gpu_count", "sort_dir": "asc", "limit": 500})),
+    )
+    .await
+    .into_response();
+    assert_eq!(response.status(), 200);
+    let body = json_body(response).await;
+    let gpu_counts: Vec<i64> = body["rows"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|r| r["gpu_count"].as_i64())
+        .collect();
+    assert!(!gpu_counts.is_empty());
+    assert!(gpu_counts.windows(2).all(|w| w[0] <= w[1]));
+
+    let response =
+        instances::search_instances(State(state.clone()), params(json!({"sort_by": "bogus"})))
+            .await
+            .into_response();
+    assert_eq!(response.status(), 400);
+    let body = json_body(response).await;
+    assert_eq!(body["error"], "invalid_sort_field");
+    assert_eq!(body["param"], "sort_by");
+}
+
+#[tokio::test]
+async fn test_instance_exposes_instance_type_cpu_ram_and_bandwidth() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+    let provider_id = ensure_mock_provider(&pool).await;
+
+    let type_code = format!("mock-specs-{}", &Uuid::new_v4().simple().to_string()[..8]);
+    let type_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO instance_types (id, provider_id, name, code, gpu_count, vram_per_gpu_gb, cpu_count, ram_gb, bandwidth_bps, is_active)
+         VALUES (gen_random_uuid(), $1, $2, $2, 1, 24, 8, 64, 2500000000, true) RETURNING id",
+    )
+    .bind(provider_id)
+    .bind(&type_code)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let instance_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO instances (id, provider_id, instance_type_id, status, created_at, gpu_profile)
+         VALUES (gen_random_uuid(), $1, $2, 'ready', NOW(), '{}') RETURNING id",
+    )
+    .bind(provider_id)
+    .bind(type_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let response = instances::get_instance(State(state.clone()), Path(instance_id))
+        .await
+        .into_response();
+    let status = response.status();
+    let body = json_body(response).await;
+
+    sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM instance_types WHERE id = $1")
+        .bind(type_id)
+        .execute(&pool)
+        .await
+        .ok();
+
+    assert_eq!(status, 200);
+    assert_eq!(body["cpu_count"], 8);
+    assert_eq!(body["ram_gb"], 64);
+    assert_eq!(body["bandwidth_bps"], 2_500_000_000i64);
+    assert_eq!(body["gpu_count"], 1);
+}
+
+#[tokio::test]
+async fn test_model_instances_lists_every_instance_of_the_model() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+    let provider_id = ensure_mock_provider(&pool).await;
+    let type_id = get_mock_instance_type_id(&pool)
+        .await
+        .expect("Mock instance type should exist");
+
+    let model_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, created_at, updated_at)
+         VALUES (gen_random_uuid(), $1, $1, 1, 2048, true, NOW(), NOW()) RETURNING id",
+    )
+    .bind(format!("test-org/model-instances-{}", Uuid::new_v4()))
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let mut instance_ids = Vec::new();
+    for status in ["ready", "booting"] {
+        let id: Uuid = sqlx::query_scalar(
+            "INSERT INTO instances (id, provider_id, instance_type_id, model_id, status, created_at, gpu_profile)
+             VALUES (gen_random_uuid(), $1, $2, $3, $4::instance_status, NOW(), '{}') RETURNING id",
+        )
+        .bind(provider_id)
+        .bind(type_id)
+        .bind(model_id)
+        .bind(status)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        instance_ids.push(id);
+    }
+
+    let list = |status: Option<&str>| {
+        let params = instances::ModelInstancesParams {
+            status: status.map(str::to_string),
+        };
+        instances::list_model_instances(
+            State(state.clone()),
+            Path(model_id),
+            axum::extract::Query(params),
+        )
+    };
+    let all = json_body(list(None).await.into_response()).await;
+    let ready = json_body(list(Some("Ready")).await.into_response()).await;
+    let missing = instances::list_model_instances(
+        State(state.clone()),
+        Path(Uuid::new_v4()),
+        axum::extract::Query(instances::ModelInstancesParams { status: None }),
+    )
+    .await
+    .into_response()
+    .status();
+
+    sqlx::query("DELETE FROM instances WHERE id = ANY($1)")
+        .bind(&instance_ids)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await
+        .ok();
+
+    let ids = |body: &serde_json::Value| -> Vec<String> {
+        let mut ids: Vec<String> = body
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| i["id"].as_str().unwrap().to_string())
+            .collect();
+        ids.sort();
+        ids
+    };
+    let mut expected: Vec<String> = instance_ids.iter().map(Uuid::to_string).collect();
+    expected.sort();
+    assert_eq!(ids(&all), expected);
+    assert_eq!(ids(&ready), vec![instance_ids[0].to_string()]);
+    assert_eq!(ready[0]["status"], "ready");
+    assert_eq!(missing, 404);
+}
@@ -0,0 +1,123 @@
+// Integration tests for POST /models/{id}/check-compat.
+// Kept in its own test binary: the Hugging Face base URL is configured via process-wide env vars.
+
+mod common;
+
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use common::{get_test_db_pool, get_test_redis_client};
+use inventiv_api::handlers::models;
+use inventiv_api::AppState;
+use serde_json::{json, Value};
+
+/// Stub of the HF `resolve/main/<file>` route for two repos:
+/// - `acme/chat-llm`: causal LM with a chat template (requires a bearer token)
+/// - `acme/embedder`: BERT encoder without a chat template
+async fn spawn_hf_stub() -> u16 {
+    let router = Router::new().route(
+        "/{org}/{repo}/resolve/main/{file}",
+        get(
+            |Path((org, repo, file)): Path<(String, String, String)>, headers: HeaderMap| async move {
+                let authed = headers
+                    .get("authorization")
+                    .and_then(|v| v.to_str().ok())
+                    == Some("Bearer hf-test-token");
+                let body = match (format!("{}/{}", org, repo).as_str(), file.as_str()) {
+                    ("acme/chat-llm", _) if !authed => {
+                        return StatusCode::UNAUTHORIZED.into_response()
+                    }
+                    ("acme/chat-llm", "tokenizer_config.json") => json!({
+                        "chat_template": "{% for m in messages %}{{ m.content }}{% endfor %}"
+                    }),
+                    ("acme/chat-llm", "config.json") => json!({
+                        "architectures": ["LlamaForCausalLM"], "model_type": "llama"
+                    }),
+                    ("acme/embedder", "tokenizer_config.json") => json!({"do_lower_case": true}),
+                    ("acme/embedder", "config.json") => json!({
+                        "architectures": ["BertModel"], "model_type": "bert"
+                    }),
+                    _ => return StatusCode::NOT_FOUND.into_response(),
+                };
+                Json(body).into_response()
+            },
+        ),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, router).await;
+    });
+    port
+}
+
+async fn insert_model(pool: &sqlx::Pool<sqlx::Postgres>, repo: &str) -> uuid::Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, created_at, updated_at)
+         VALUES (gen_random_uuid(), $1, $2, 1, 2048, true, NOW(), NOW())
+         RETURNING id",
+    )
+    .bind(format!("compat-{}", uuid::Uuid::new_v4()))
+    .bind(repo)
+    .fetch_one(pool)
+    .await
+    .expect("Failed to insert test model")
+}
+
+async fn check(state: std::sync::Arc<AppState>, id: uuid::Uuid) -> (StatusCode, Value) {
+    let resp = models::check_model_compat(State(state), Path(id.to_string()))
+        .await
+        .into_response();
+    let status = resp.status();
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_check_compat_reports_chat_and_embedding_capability() {
+    let port = spawn_hf_stub().await;
+    std::env::set_var("HF_HUB_BASE_URL", format!("http://127.0.0.1:{}", port));
+    std::env::set_var("HF_TOKEN", "hf-test-token");
+
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+
+    let chat_id = insert_model(&pool, "acme/chat-llm").await;
+    let embed_id = insert_model(&pool, "acme/embedder").await;
+    let missing_id = insert_model(&pool, "acme/does-not-exist").await;
+
+    let (status, body) = check(state.clone(), chat_id).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["chat_capable"], true);
+    assert_eq!(body["embedding_capable"], false);
+    assert_eq!(body["warnings"], json!([]));
+
+    let (status, body) = check(state.clone(), embed_id).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["chat_capable"], false);
+    assert_eq!(body["embedding_capable"], true);
+    assert!(body["warnings"][0]
+        .as_str()
+        .unwrap()
+        .contains("no chat_template"));
+
+    // Missing repo files are reported as warnings, not errors.
+    let (status, body) = check(state.clone(), missing_id).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["chat_capable"], false);
+    assert_eq!(body["embedding_capable"], false);
+    assert_eq!(body["warnings"].as_array().unwrap().len(), 2);
+
+    let (status, _) = check(state, uuid::Uuid::new_v4()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    sqlx::query("DELETE FROM models WHERE id = ANY($1)")
+        .bind(vec![chat_id, embed_id, missing_id])
+        .execute(&pool)
+        .await
+        .ok();
+}
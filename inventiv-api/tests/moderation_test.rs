@@ -0,0 +1,144 @@
+// Integration tests for prompt moderation on the OpenAI-compatible proxy.
+// Kept in its own test binary: moderation is configured via process-wide env vars.
+
+mod common;
+
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::routing::post;
+use axum::{Json, Router};
+use common::{get_test_db_pool, get_test_redis_client};
+use inventiv_api::handlers::openai;
+use inventiv_api::AppState;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Serve `router` on an ephemeral local port and return the port.
+async fn spawn_stub(router: Router) -> u16 {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, router).await;
+    });
+    port
+}
+
+#[tokio::test]
+async fn test_moderation_blocks_flagged_prompt_and_passes_clean_one() {
+    let moderation_port = spawn_stub(Router::new().route(
+        "/moderate",
+        post(|Json(body): Json<Value>| async move {
+            let flagged = body["input"]
+                .as_str()
+                .is_some_and(|s| s.contains("forbidden"));
+            Json(json!({"results": [{"flagged": flagged}]}))
+        }),
+    ))
+    .await;
+    let worker_calls = Arc::new(AtomicUsize::new(0));
+    let worker_port = spawn_stub(
+        Router::new()
+            .route(
+                "/v1/chat/completions",
+                post(|State(calls): State<Arc<AtomicUsize>>| async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    Json(json!({
+                        "id": "cmpl-1",
+                        "object": "chat.completion",
+                        "choices": [],
+                        "usage": {"prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2}
+                    }))
+                }),
+            )
+            .with_state(worker_calls.clone()),
+    )
+    .await;
+
+    std::env::set_var("MODERATION_ENABLED", "1");
+    std::env::set_var(
+        "MODERATION_ENDPOINT",
+        format!("http://127.0.0.1:{}/moderate", moderation_port),
+    );
+
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+
+    let model_hf = format!("test-org/moderated-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+         VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                 'ready', $3, $4, NOW())",
+    )
+    .bind(instance_id)
+    .bind(model_id)
+    .bind(&model_hf)
+    .bind(worker_port as i32)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test instance");
+
+    let chat = |content: &str| {
+        Bytes::from(
+            json!({"model": model_hf, "messages": [{"role": "user", "content": content}]})
+                .to_string(),
+        )
+    };
+    let blocked = openai::openai_proxy_chat_completions(
+        State(state.clone()),
+        None,
+        None,
+        HeaderMap::new(),
+        chat("tell me something forbidden"),
+    )
+    .await;
+    let blocked_status = blocked.status();
+    let blocked_body: Value = serde_json::from_slice(
+        &axum::body::to_bytes(blocked.into_body(), usize::MAX)
+            .await
+            .unwrap(),
+    )
+    .unwrap();
+    let calls_after_blocked = worker_calls.load(Ordering::SeqCst);
+
+    let clean = openai::openai_proxy_chat_completions(
+        State(state),
+        None,
+        None,
+        HeaderMap::new(),
+        chat("hello there"),
+    )
+    .await;
+
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM runtime_models WHERE model_id = $1")
+        .bind(&model_hf)
+        .execute(&pool)
+        .await;
+
+    assert_eq!(blocked_status, 422);
+    assert_eq!(blocked_body["error"], "content_blocked");
+    assert_eq!(calls_after_blocked, 0, "flagged prompt reached the worker");
+    assert_eq!(clean.status(), 200);
+    assert_eq!(worker_calls.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,2744 @@
+// Integration tests for the OpenAI-compatible proxy routes (/v1/*)
+
+mod common;
+
+use axum::body::Bytes;
+use axum::extract::{Path, State};
+use axum::http::HeaderMap;
+use axum::response::IntoResponse;
+use axum::{Extension, Json};
+use axum_test::TestServer;
+use common::{
+    create_test_app_service_with_state, create_test_user, get_test_db_pool, get_test_redis_client,
+};
+use inventiv_api::api_docs::ApiDoc;
+use inventiv_api::auth::ApiKeyPrincipal;
+use inventiv_api::handlers::{instances, models, openai};
+use inventiv_api::openai_proxy::ProxyClients;
+use inventiv_api::AppState;
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use utoipa::OpenApi;
+
+async fn set_maintenance_mode(state: &AppState, on: bool) {
+    // global_settings has no unique constraint on `key`: replace the row instead of upserting.
+    sqlx::query("DELETE FROM global_settings WHERE key = 'MAINTENANCE_MODE'")
+        .execute(&state.db)
+        .await
+        .expect("Failed to clear MAINTENANCE_MODE");
+    sqlx::query("INSERT INTO global_settings (key, value_bool) VALUES ('MAINTENANCE_MODE', $1)")
+        .bind(on)
+        .execute(&state.db)
+        .await
+        .expect("Failed to toggle MAINTENANCE_MODE");
+    state.settings.invalidate();
+}
+
+/// Bearer token of a fresh admin session, as issued by /auth/login.
+async fn admin_session_token(state: &AppState) -> String {
+    let email = format!("maintenance-admin-{}@test.local", uuid::Uuid::new_v4());
+    let user_id = create_test_user(&state.db, &email, "password").await;
+    let user = inventiv_api::auth::AuthUser {
+        user_id,
+        email,
+        role: "admin".to_string(),
+        session_id: uuid::Uuid::new_v4().to_string(),
+        current_organization_id: None,
+        current_organization_role: None,
+    };
+    let token = inventiv_api::auth::sign_session_jwt(&user).unwrap();
+    inventiv_api::auth::create_session(
+        &state.db,
+        user.session_id.parse().unwrap(),
+        user_id,
+        None,
+        None,
+        None,
+        None,
+        inventiv_api::auth::hash_session_token(&token),
+    )
+    .await
+    .unwrap();
+    token
+}
+
+#[tokio::test]
+async fn test_maintenance_mode_rejects_inference_only() {
+    let (app, state) = create_test_app_service_with_state().await;
+    let server = TestServer::new(app).unwrap();
+    let admin_token = admin_session_token(&state).await;
+
+    set_maintenance_mode(&state, true).await;
+
+    let chat = server
+        .post("/v1/chat/completions")
+        .json(&json!({"model": "test-model", "messages": []}))
+        .await;
+    // Management routes are not gated by maintenance.
+    let instances = server
+        .get("/instances")
+        .authorization_bearer(&admin_token)
+        .await;
+
+    set_maintenance_mode(&state, false).await;
+    let chat_after = server
+        .post("/v1/chat/completions")
+        .json(&json!({"model": "test-model", "messages": []}))
+        .await;
+
+    assert_eq!(chat.status_code(), 503);
+    assert!(chat.headers().get("retry-after").is_some());
+    let body: serde_json::Value = chat.json();
+    assert_eq!(body["error"], "maintenance");
+
+    assert_eq!(instances.status_code(), 200);
+    assert_ne!(chat_after.status_code(), 503);
+}
+
+/// Minimal keep-alive HTTP/1.1 upstream that counts accepted TCP connections.
+async fn spawn_counting_upstream() -> (String, Arc<AtomicUsize>) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let connections = Arc::new(AtomicUsize::new(0));
+    let counter = connections.clone();
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut sock, _)) = listener.accept().await else {
+                return;
+            };
+            counter.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(async move {
+                let mut buf = Vec::new();
+                let mut chunk = [0u8; 1024];
+                loop {
+                    let Ok(n) = sock.read(&mut chunk).await else {
+                        return;
+                    };
+                    if n == 0 {
+                        return;
+                    }
+                    buf.extend_from_slice(&chunk[..n]);
+                    while let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                        buf.drain(..end + 4);
+                        let resp = b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok";
+                        if sock.write_all(resp).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+            });
+        }
+    });
+    (format!("http://{}", addr), connections)
+}
+
+#[tokio::test]
+async fn test_proxy_client_reuses_connections() {
+    let (base_url, connections) = spawn_counting_upstream().await;
+    let clients = ProxyClients::new();
+
+    for _ in 0..5 {
+        let resp = clients
+            .client(false)
+            .get(format!("{}/health", base_url))
+            .timeout(ProxyClients::request_timeout("/health", false))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.text().await.unwrap(), "ok");
+    }
+
+    assert_eq!(
+        connections.load(Ordering::SeqCst),
+        1,
+        "shared proxy client should keep a single pooled connection"
+    );
+}
+
+#[test]
+fn test_openapi_spec_documents_proxy_and_worker_routes() {
+    let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+    let paths = &spec["paths"];
+
+    let chat = &paths["/v1/chat/completions"]["post"];
+    assert!(chat.is_object(), "missing POST /v1/chat/completions");
+    assert_eq!(
+        chat["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/OpenAiChatCompletionRequest"
+    );
+    assert!(paths["/v1/models"]["get"].is_object());
+    assert!(paths["/v1/completions"]["post"].is_object());
+    assert!(paths["/v1/embeddings"]["post"].is_object());
+    assert!(paths["/v1/{path}"]["post"].is_object());
+    assert!(paths["/internal/worker/register"]["post"].is_object());
+    assert!(paths["/internal/worker/heartbeat"]["post"].is_object());
+    assert!(paths["/internal/worker/deregister"]["post"].is_object());
+    assert!(paths["/internal/worker/config"]["get"].is_object());
+}
+
+#[tokio::test]
+async fn test_deprecated_model_is_served_with_warning_header() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+
+    let suffix = uuid::Uuid::new_v4();
+    let old_id = uuid::Uuid::new_v4();
+    let new_id = uuid::Uuid::new_v4();
+    let old_hf = format!("test-org/deprecated-{}", suffix);
+    let new_hf = format!("test-org/replacement-{}", suffix);
+    for (id, hf) in [(old_id, &old_hf), (new_id, &new_hf)] {
+        sqlx::query(
+            "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+             VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+        )
+        .bind(id)
+        .bind(hf)
+        .execute(&pool)
+        .await
+        .expect("Failed to insert test model");
+    }
+
+    let update: models::UpdateModelRequest =
+        serde_json::from_value(json!({"deprecated": true, "replacement_model_id": new_id}))
+            .unwrap();
+    let updated =
+        models::update_model(State(state.clone()), Path(old_id.to_string()), Json(update))
+            .await
+            .into_response();
+    assert_eq!(updated.status(), 200);
+
+    // No READY worker serves the test model: the request is still accepted for routing (not
+    // rejected as retired), and the error envelope carries the deprecation warning.
+    let response = openai::openai_proxy_chat_completions(
+        State(state),
+        None,
+        None,
+        HeaderMap::new(),
+        Bytes::from(json!({"model": old_hf, "messages": []}).to_string()),
+    )
+    .await;
+
+    let status = response.status();
+    let header = response
+        .headers()
+        .get("x-model-deprecated")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+    let _ = sqlx::query("DELETE FROM models WHERE id = ANY($1)")
+        .bind(vec![old_id, new_id])
+        .execute(&pool)
+        .await;
+
+    assert_eq!(status, 503);
+    let header = header.expect("missing X-Model-Deprecated header");
+    assert!(
+        header.ends_with(&format!("replacement={}", new_hf)),
+        "{header}"
+    );
+    assert_eq!(body["error"], "no_ready_worker");
+    assert_eq!(body["metadata"]["warning"]["code"], "model_deprecated");
+    assert_eq!(
+        body["metadata"]["warning"]["replacement_model"],
+        new_hf.as_str()
+    );
+}
+
+/// One-shot upstream that records the request (headers + body) and answers a chat completion.
+async fn spawn_capturing_upstream() -> (u16, tokio::sync::oneshot::Receiver<(String, Vec<u8>)>) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    tokio::spawn(async move {
+        let Ok((mut sock, _)) = listener.accept().await else {
+            return;
+        };
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 4096];
+        let (head, body_start) = loop {
+            let n = sock.read(&mut chunk).await.unwrap_or(0);
+            if n == 0 {
+                return;
+            }
+            buf.extend_from_slice(&chunk[..n]);
+            if let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                break (String::from_utf8_lossy(&buf[..end]).to_string(), end + 4);
+            }
+        };
+        let len = head
+            .lines()
+            .find_map(|l| {
+                let (k, v) = l.split_once(':')?;
+                k.eq_ignore_ascii_case("content-length")
+                    .then(|| v.trim().parse::<usize>().ok())?
+            })
+            .unwrap_or(0);
+        let mut body = buf[body_start..].to_vec();
+        while body.len() < len {
+            let n = sock.read(&mut chunk).await.unwrap_or(0);
+            if n == 0 {
+                break;
+            }
+            body.extend_from_slice(&chunk[..n]);
+        }
+        let payload = r#"{"id":"cmpl-1","object":"chat.completion","choices":[],"usage":{"prompt_tokens":1,"completion_tokens":1,"total_tokens":2}}"#;
+        let resp = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            payload.len(),
+            payload
+        );
+        let _ = sock.write_all(resp.as_bytes()).await;
+        let _ = tx.send((head, body));
+    });
+    (port, rx)
+}
+
+#[tokio::test]
+async fn test_gzip_request_body_is_decoded_for_worker() {
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+    let (port, captured) = spawn_capturing_upstream().await;
+
+    let model_hf = format!("test-org/gzip-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+         VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                 'ready', $3, $4, NOW())",
+    )
+    .bind(instance_id)
+    .bind(model_id)
+    .bind(&model_hf)
+    .bind(port as i32)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test instance");
+
+    let chat = json!({
+        "model": model_hf,
+        "messages": [{"role": "user", "content": "context ".repeat(2048)}]
+    });
+    let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(chat.to_string().as_bytes()).unwrap();
+    let compressed = encoder.finish().unwrap();
+
+    let mut headers = HeaderMap::new();
+    headers.insert("content-type", "application/json".parse().unwrap());
+    headers.insert("content-encoding", "gzip".parse().unwrap());
+    let response = openai::openai_proxy_chat_completions(
+        State(state),
+        None,
+        None,
+        headers,
+        Bytes::from(compressed),
+    )
+    .await;
+    let status = response.status();
+    let received = tokio::time::timeout(std::time::Duration::from_secs(5), captured).await;
+
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+
+    assert_eq!(status, 200);
+    let (head, body) = received
+        .expect("worker was not called")
+        .expect("worker request not captured");
+    assert!(!head.to_ascii_lowercase().contains("content-encoding: gzip"));
+    let forwarded: serde_json::Value =
+        serde_json::from_slice(&body).expect("worker body should be plain JSON");
+    assert_eq!(forwarded, chat);
+}
+
+#[tokio::test]
+async fn test_fallback_model_serves_request_when_opted_in() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+    let (port, captured) = spawn_capturing_upstream().await;
+
+    let suffix = uuid::Uuid::new_v4();
+    let (a_id, b_id) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+    let a_hf = format!("test-org/no-capacity-{}", suffix);
+    let b_hf = format!("test-org/fallback-{}", suffix);
+    for (id, hf) in [(a_id, &a_hf), (b_id, &b_hf)] {
+        sqlx::query(
+            "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+             VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+        )
+        .bind(id)
+        .bind(hf)
+        .execute(&pool)
+        .await
+        .expect("Failed to insert test model");
+    }
+    let update: models::UpdateModelRequest =
+        serde_json::from_value(json!({"fallback_model_id": b_id})).unwrap();
+    let updated = models::update_model(State(state.clone()), Path(a_id.to_string()), Json(update))
+        .await
+        .into_response();
+    assert_eq!(updated.status(), 200);
+
+    // Only model B has a READY worker.
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+         VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                 'ready', $3, $4, NOW())",
+    )
+    .bind(instance_id)
+    .bind(b_id)
+    .bind(&b_hf)
+    .bind(port as i32)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test instance");
+
+    let body = Bytes::from(json!({"model": a_hf, "messages": []}).to_string());
+    let without_opt_in = openai::openai_proxy_chat_completions(
+        State(state.clone()),
+        None,
+        None,
+        HeaderMap::new(),
+        body.clone(),
+    )
+    .await;
+
+    let mut headers = HeaderMap::new();
+    headers.insert("x-allow-model-fallback", "true".parse().unwrap());
+    let response =
+        openai::openai_proxy_chat_completions(State(state), None, None, headers, body).await;
+    let status = response.status();
+    let served = response
+        .headers()
+        .get("x-served-model")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+    let received = tokio::time::timeout(std::time::Duration::from_secs(5), captured).await;
+
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = ANY($1)")
+        .bind(vec![a_id, b_id])
+        .execute(&pool)
+        .await;
+
+    assert_eq!(without_opt_in.status(), 503);
+    assert_eq!(status, 200);
+    assert_eq!(served.as_deref(), Some(b_hf.as_str()));
+    let (_, forwarded) = received
+        .expect("fallback worker was not called")
+        .expect("worker request not captured");
+    let forwarded: serde_json::Value = serde_json::from_slice(&forwarded).unwrap();
+    assert_eq!(forwarded["model"], b_hf.as_str());
+}
+
+#[tokio::test]
+async fn test_model_default_params_fill_omitted_keys() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+    let (port, captured) = spawn_capturing_upstream().await;
+
+    let model_hf = format!("test-org/defaults-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    let update: models::UpdateModelRequest =
+        serde_json::from_value(json!({"default_params": {"temperature": 0.2, "max_tokens": 64}}))
+            .unwrap();
+    let updated = models::update_model(
+        State(state.clone()),
+        Path(model_id.to_string()),
+        Json(update),
+    )
+    .await
+    .into_response();
+    let invalid: models::UpdateModelRequest =
+        serde_json::from_value(json!({"default_params": [1, 2]})).unwrap();
+    let rejected = models::update_model(
+        State(state.clone()),
+        Path(model_id.to_string()),
+        Json(invalid),
+    )
+    .await
+    .into_response();
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+         VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                 'ready', $3, $4, NOW())",
+    )
+    .bind(instance_id)
+    .bind(model_id)
+    .bind(&model_hf)
+    .bind(port as i32)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test instance");
+
+    // No temperature sent; max_tokens set by the client.
+    let body = json!({"model": model_hf, "max_tokens": 8, "messages": []});
+    let response = openai::openai_proxy_chat_completions(
+        State(state),
+        None,
+        None,
+        HeaderMap::new(),
+        Bytes::from(body.to_string()),
+    )
+    .await;
+    let status = response.status();
+    let received = tokio::time::timeout(std::time::Duration::from_secs(5), captured).await;
+
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+
+    assert_eq!(updated.status(), 200);
+    assert_eq!(rejected.status(), 400);
+    assert_eq!(status, 200);
+    let (_, forwarded) = received
+        .expect("worker was not called")
+        .expect("worker request not captured");
+    let forwarded: serde_json::Value = serde_json::from_slice(&forwarded).unwrap();
+    assert_eq!(forwarded["temperature"], 0.2);
+    assert_eq!(forwarded["max_tokens"], 8);
+    assert_eq!(forwarded["model"], model_hf.as_str());
+}
+
+#[tokio::test]
+async fn test_model_forward_headers_reach_worker() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+    let (port, captured) = spawn_capturing_upstream().await;
+
+    let model_hf = format!("test-org/fwd-headers-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    let update: models::UpdateModelRequest = serde_json::from_value(json!({
+        "forward_headers": {"X-Tenant": "acme", "Authorization": "Bearer worker-secret"}
+    }))
+    .unwrap();
+    let updated = models::update_model(
+        State(state.clone()),
+        Path(model_id.to_string()),
+        Json(update),
+    )
+    .await
+    .into_response();
+    let invalid: models::UpdateModelRequest =
+        serde_json::from_value(json!({"forward_headers": {"bad header": "x"}})).unwrap();
+    let rejected = models::update_model(
+        State(state.clone()),
+        Path(model_id.to_string()),
+        Json(invalid),
+    )
+    .await
+    .into_response();
+    let reserved: models::UpdateModelRequest =
+        serde_json::from_value(json!({"forward_headers": {"Host": "evil"}})).unwrap();
+    let rejected_reserved = models::update_model(
+        State(state.clone()),
+        Path(model_id.to_string()),
+        Json(reserved),
+    )
+    .await
+    .into_response();
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+         VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                 'ready', $3, $4, NOW())",
+    )
+    .bind(instance_id)
+    .bind(model_id)
+    .bind(&model_hf)
+    .bind(port as i32)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test instance");
+
+    let mut client_headers = HeaderMap::new();
+    client_headers.insert("authorization", "Bearer client-key".parse().unwrap());
+    let body = json!({"model": model_hf, "messages": []});
+    let response = openai::openai_proxy_chat_completions(
+        State(state),
+        None,
+        None,
+        client_headers,
+        Bytes::from(body.to_string()),
+    )
+    .await;
+    let status = response.status();
+    let received = tokio::time::timeout(std::time::Duration::from_secs(5), captured).await;
+
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+
+    assert_eq!(updated.status(), 200);
+    assert_eq!(rejected.status(), 400);
+    assert_eq!(rejected_reserved.status(), 400);
+    assert_eq!(status, 200);
+    let (head, _) = received
+        .expect("worker was not called")
+        .expect("worker request not captured");
+    let head = head.to_ascii_lowercase();
+    assert!(head.contains("x-tenant: acme"), "{}", head);
+    assert!(
+        head.contains("authorization: bearer worker-secret"),
+        "{}",
+        head
+    );
+    assert!(!head.contains("client-key"), "{}", head);
+}
+
+/// One-shot upstream answering like a worker behind HAProxy: local model path + internal header.
+async fn spawn_leaky_upstream() -> u16 {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let Ok((mut sock, _)) = listener.accept().await else {
+            return;
+        };
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 4096];
+        while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = sock.read(&mut chunk).await.unwrap_or(0);
+            if n == 0 {
+                return;
+            }
+            buf.extend_from_slice(&chunk[..n]);
+        }
+        let payload = r#"{"id":"cmpl-1","object":"chat.completion","model":"/opt/models/local-snapshot","choices":[]}"#;
+        let resp = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nX-Worker-Backend: vllm-0\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            payload.len(),
+            payload
+        );
+        let _ = sock.write_all(resp.as_bytes()).await;
+    });
+    port
+}
+
+#[tokio::test]
+async fn test_sanitized_response_reports_requested_model() {
+    // Other tests never assert the response model or strip-listed headers, so the env is safe here.
+    std::env::set_var("OPENAI_PROXY_SANITIZE_RESPONSES", "true");
+    std::env::set_var(
+        "OPENAI_PROXY_STRIP_RESPONSE_HEADERS",
+        "x-worker-backend, x-inventiv-session",
+    );
+
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+    let port = spawn_leaky_upstream().await;
+
+    let model_hf = format!("test-org/sanitized-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+         VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                 'ready', $3, $4, NOW())",
+    )
+    .bind(instance_id)
+    .bind(model_id)
+    .bind(&model_hf)
+    .bind(port as i32)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test instance");
+
+    let body = json!({"model": model_hf, "messages": []});
+    let response = openai::openai_proxy_chat_completions(
+        State(state),
+        None,
+        None,
+        HeaderMap::new(),
+        Bytes::from(body.to_string()),
+    )
+    .await;
+    let status = response.status();
+    let headers = response.headers().clone();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+
+    assert_eq!(status, 200);
+    let returned: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(returned["model"], model_hf.as_str());
+    assert_eq!(returned["id"], "cmpl-1");
+    assert!(headers.get("x-worker-backend").is_none());
+    assert!(headers.get("x-inventiv-session").is_none());
+}
+
+#[tokio::test]
+async fn test_success_logs_are_sampled_but_failures_always_logged() {
+    // Only this test reads action_logs for its own instance, so the process-wide env is safe here.
+    std::env::set_var("PROXY_LOG_SUCCESS_SAMPLE_RATE", "0");
+
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+    let (port, captured) = spawn_capturing_upstream().await;
+
+    let model_hf = format!("test-org/sampled-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+         VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                 'ready', $3, $4, NOW())",
+    )
+    .bind(instance_id)
+    .bind(model_id)
+    .bind(&model_hf)
+    .bind(port as i32)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test instance");
+
+    let body = Bytes::from(json!({"model": model_hf, "messages": []}).to_string());
+    let ok = openai::openai_proxy_chat_completions(
+        State(state.clone()),
+        None,
+        None,
+        HeaderMap::new(),
+        body.clone(),
+    )
+    .await;
+    let _ = tokio::time::timeout(std::time::Duration::from_secs(5), captured).await;
+    let logs_after_success: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM action_logs WHERE instance_id = $1 AND action_type = 'OPENAI_PROXY'",
+    )
+    .bind(instance_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    // Point the worker at a closed port so the next request fails upstream.
+    let closed_port = {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        listener.local_addr().unwrap().port()
+    };
+    sqlx::query("UPDATE instances SET worker_vllm_port = $2 WHERE id = $1")
+        .bind(instance_id)
+        .bind(closed_port as i32)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let failed =
+        openai::openai_proxy_chat_completions(State(state), None, None, HeaderMap::new(), body)
+            .await;
+    let failed_logs: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM action_logs
+         WHERE instance_id = $1 AND action_type = 'OPENAI_PROXY' AND status = 'failed'",
+    )
+    .bind(instance_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let counters: Option<(i64, i64)> = sqlx::query_as(
+        "SELECT total_requests, failed_requests FROM runtime_models WHERE model_id = $1",
+    )
+    .bind(&model_hf)
+    .fetch_optional(&pool)
+    .await
+    .unwrap();
+
+    let _ = sqlx::query("DELETE FROM action_logs WHERE instance_id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM runtime_models WHERE model_id = $1")
+        .bind(&model_hf)
+        .execute(&pool)
+        .await;
+
+    assert_eq!(ok.status(), 200);
+    assert_eq!(logs_after_success, 0);
+    assert_eq!(failed.status(), 502);
+    assert_eq!(failed_logs, 1);
+    assert_eq!(counters, Some((2, 1)));
+}
+
+#[tokio::test]
+async fn test_non_public_model_hidden_from_list_but_routable_by_scoped_key() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+    let (port, captured) = spawn_capturing_upstream().await;
+
+    let model_hf = format!("test-org/internal-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, public, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, false, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+         VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                 'ready', $3, $4, NOW())",
+    )
+    .bind(instance_id)
+    .bind(model_id)
+    .bind(&model_hf)
+    .bind(port as i32)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test instance");
+
+    let key = |allowed_models: Option<Vec<String>>| {
+        Extension(ApiKeyPrincipal {
+            api_key_id: uuid::Uuid::new_v4(),
+            user_id: uuid::Uuid::new_v4(),
+            key_prefix: "sk-inv-test".to_string(),
+            name: "test-scoped".to_string(),
+            allowed_models,
+            rate_limit_per_minute: None,
+            max_concurrent_streams: None,
+        })
+    };
+    let scoped = key(Some(vec![model_hf.clone()]));
+    let unscoped = key(None);
+
+    let listed = |response: axum::response::Response| async move {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|m| m["id"].as_str().map(str::to_string))
+            .collect::<Vec<_>>()
+    };
+    let for_key = listed(
+        openai::openai_list_models(State(state.clone()), Some(scoped.clone()))
+            .await
+            .into_response(),
+    )
+    .await;
+    let for_session = listed(
+        openai::openai_list_models(State(state.clone()), None)
+            .await
+            .into_response(),
+    )
+    .await;
+
+    let body = Bytes::from(json!({"model": model_hf, "messages": []}).to_string());
+    let denied = openai::openai_proxy_chat_completions(
+        State(state.clone()),
+        None,
+        Some(unscoped),
+        HeaderMap::new(),
+        body.clone(),
+    )
+    .await;
+    let allowed = openai::openai_proxy_chat_completions(
+        State(state),
+        None,
+        Some(scoped),
+        HeaderMap::new(),
+        body,
+    )
+    .await;
+    let received = tokio::time::timeout(std::time::Duration::from_secs(5), captured).await;
+
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+
+    assert!(!for_key.contains(&model_hf), "{for_key:?}");
+    assert!(for_session.contains(&model_hf), "{for_session:?}");
+    assert_eq!(denied.status(), 404);
+    assert_eq!(allowed.status(), 200);
+    received
+        .expect("worker was not called")
+        .expect("worker request not captured");
+}
+
+#[tokio::test]
+async fn test_rate_limit_headers_count_down_under_configured_limit() {
+    use axum::middleware;
+    use axum::routing::get;
+    use inventiv_api::rate_limit::enforce_api_key_rate_limit;
+
+    let redis = get_test_redis_client().await;
+    if redis.get_multiplexed_async_connection().await.is_err() {
+        eprintln!("skipping test: Redis not reachable");
+        return;
+    }
+
+    let principal = |rate_limit_per_minute: Option<i32>| ApiKeyPrincipal {
+        api_key_id: uuid::Uuid::new_v4(),
+        user_id: uuid::Uuid::new_v4(),
+        key_prefix: "sk-inv-test".to_string(),
+        name: "test-rate-limited".to_string(),
+        allowed_models: None,
+        rate_limit_per_minute,
+        max_concurrent_streams: None,
+    };
+    let server_for = |p: ApiKeyPrincipal| {
+        let app: axum::Router = axum::Router::new()
+            .route("/v1/models", get(|| async { "ok" }))
+            .route_layer(middleware::from_fn_with_state(
+                redis.clone(),
+                enforce_api_key_rate_limit,
+            ))
+            .layer(Extension(p));
+        TestServer::new(app).unwrap()
+    };
+
+    let limited = server_for(principal(Some(2)));
+    let mut remaining = Vec::new();
+    for _ in 0..2 {
+        let resp = limited.get("/v1/models").await;
+        assert_eq!(resp.status_code(), 200);
+        assert_eq!(resp.header("x-ratelimit-limit"), "2");
+        remaining.push(resp.header("x-ratelimit-remaining"));
+    }
+    assert_eq!(remaining, vec!["1", "0"]);
+
+    let rejected = limited.get("/v1/models").await;
+    assert_eq!(rejected.status_code(), 429);
+    assert_eq!(rejected.header("x-ratelimit-remaining"), "0");
+    assert_eq!(
+        rejected.header("retry-after"),
+        rejected.header("x-ratelimit-reset")
+    );
+
+    // No configured limit: no headers.
+    let unlimited = server_for(principal(None)).get("/v1/models").await;
+    assert_eq!(unlimited.status_code(), 200);
+    assert!(unlimited.maybe_header("x-ratelimit-limit").is_none());
+}
+
+#[tokio::test]
+async fn test_identical_concurrent_requests_share_one_upstream_call() {
+    // Flights are keyed on the (unique) test model, so enabling this process-wide is safe here.
+    std::env::set_var("OPENAI_PROXY_SINGLE_FLIGHT", "1");
+
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let upstream = axum::Router::new().route(
+        "/v1/chat/completions",
+        axum::routing::post(move || {
+            let counter = counter.clone();
+            async move {
+                let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                // Slow enough for the second request to arrive while the first is in flight.
+                tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+                Json(json!({"id": format!("cmpl-{}", n), "object": "chat.completion", "choices": []}))
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, upstream).await;
+    });
+
+    let model_hf = format!("test-org/single-flight-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+         VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                 'ready', $3, $4, NOW())",
+    )
+    .bind(instance_id)
+    .bind(model_id)
+    .bind(&model_hf)
+    .bind(port as i32)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test instance");
+
+    let send = |body: serde_json::Value| {
+        let state = state.clone();
+        async move {
+            let resp = openai::openai_proxy_chat_completions(
+                State(state),
+                None,
+                None,
+                HeaderMap::new(),
+                Bytes::from(body.to_string()),
+            )
+            .await;
+            let status = resp.status();
+            let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            (status, bytes)
+        }
+    };
+    let body = json!({"model": model_hf, "messages": [{"role": "user", "content": "hi"}]});
+    let (first, second) = tokio::join!(send(body.clone()), send(body.clone()));
+    let shared_calls = calls.load(Ordering::SeqCst);
+    // Once the flight is over, the same prompt goes upstream again (no response caching).
+    let (third, _) = send(body).await;
+
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM runtime_models WHERE model_id = $1")
+        .bind(&model_hf)
+        .execute(&pool)
+        .await;
+
+    assert_eq!(first.0, 200);
+    assert_eq!(second.0, 200);
+    assert_eq!(first.1, second.1);
+    assert_eq!(
+        shared_calls, 1,
+        "identical concurrent requests should share one call"
+    );
+    assert_eq!(third, 200);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_single_flight_never_coalesces_different_callers() {
+    std::env::set_var("OPENAI_PROXY_SINGLE_FLIGHT", "1");
+
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let upstream = axum::Router::new().route(
+        "/v1/chat/completions",
+        axum::routing::post(move || {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+                Json(json!({
+                    "id": "cmpl-shared",
+                    "object": "chat.completion",
+                    "choices": [],
+                    "usage": {"prompt_tokens": 3, "completion_tokens": 2, "total_tokens": 5}
+                }))
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, upstream).await;
+    });
+
+    let model_hf = format!("test-org/single-flight-keys-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+         VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                 'ready', $3, $4, NOW())",
+    )
+    .bind(instance_id)
+    .bind(model_id)
+    .bind(&model_hf)
+    .bind(port as i32)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test instance");
+
+    let key = || ApiKeyPrincipal {
+        api_key_id: uuid::Uuid::new_v4(),
+        user_id: uuid::Uuid::new_v4(),
+        key_prefix: "sk-inv-test".to_string(),
+        name: "test-single-flight".to_string(),
+        allowed_models: None,
+        rate_limit_per_minute: None,
+        max_concurrent_streams: None,
+    };
+    let (key_a, key_b) = (key(), key());
+    let (id_a, id_b) = (key_a.api_key_id, key_b.api_key_id);
+    let send = |principal: ApiKeyPrincipal| {
+        let state = state.clone();
+        let body = json!({"model": model_hf, "messages": [{"role": "user", "content": "hi"}]});
+        async move {
+            openai::openai_proxy_chat_completions(
+                State(state),
+                None,
+                Some(Extension(principal)),
+                HeaderMap::new(),
+                Bytes::from(body.to_string()),
+            )
+            .await
+            .status()
+        }
+    };
+    let (status_a, status_b) = tokio::join!(send(key_a), send(key_b));
+
+    let billed: Vec<(uuid::Uuid, i32)> = sqlx::query_as(
+        "SELECT api_key_id, total_tokens FROM finops.inference_usage
+         WHERE api_key_id IN ($1, $2) ORDER BY api_key_id",
+    )
+    .bind(id_a)
+    .bind(id_b)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+
+    let _ = sqlx::query("DELETE FROM finops.inference_usage WHERE api_key_id IN ($1, $2)")
+        .bind(id_a)
+        .bind(id_b)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM runtime_models WHERE model_id = $1")
+        .bind(&model_hf)
+        .execute(&pool)
+        .await;
+
+    assert_eq!(status_a, 200);
+    assert_eq!(status_b, 200);
+    assert_eq!(
+        calls.load(Ordering::SeqCst),
+        2,
+        "different API keys must not share an upstream call"
+    );
+    let mut expected = vec![(id_a, 5), (id_b, 5)];
+    expected.sort();
+    assert_eq!(billed, expected, "each key is billed for its own call");
+}
+
+async fn set_param_filter(pool: &sqlx::Pool<sqlx::Postgres>, denylist: &str, mode: &str) {
+    sqlx::query(
+        "DELETE FROM global_settings WHERE key IN ('OPENAI_PARAM_DENYLIST', 'OPENAI_PARAM_FILTER_MODE')",
+    )
+    .execute(pool)
+    .await
+    .expect("Failed to reset param filter");
+    sqlx::query(
+        "INSERT INTO global_settings (key, value_text)
+         VALUES ('OPENAI_PARAM_DENYLIST', $1), ('OPENAI_PARAM_FILTER_MODE', $2)",
+    )
+    .bind(denylist)
+    .bind(mode)
+    .execute(pool)
+    .await
+    .expect("Failed to set param filter");
+}
+
+#[tokio::test]
+async fn test_denylisted_params_are_stripped_or_rejected() {
+    // No other test sends logit_bias, so the global denylist doesn't affect them.
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+    let (port, captured) = spawn_capturing_upstream().await;
+
+    let model_hf = format!("test-org/param-filter-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+         VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                 'ready', $3, $4, NOW())",
+    )
+    .bind(instance_id)
+    .bind(model_id)
+    .bind(&model_hf)
+    .bind(port as i32)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test instance");
+
+    let body = Bytes::from(
+        json!({
+            "model": model_hf,
+            "messages": [],
+            "temperature": 0.3,
+            "logit_bias": {"50256": -100}
+        })
+        .to_string(),
+    );
+
+    set_param_filter(&pool, "logit_bias, guided_json", "strip").await;
+    let stripped = openai::openai_proxy_chat_completions(
+        State(state.clone()),
+        None,
+        None,
+        HeaderMap::new(),
+        body.clone(),
+    )
+    .await;
+    let (_, forwarded) = tokio::time::timeout(std::time::Duration::from_secs(5), captured)
+        .await
+        .expect("worker was not called")
+        .unwrap();
+
+    set_param_filter(&pool, "logit_bias", "reject").await;
+    let rejected =
+        openai::openai_proxy_chat_completions(State(state), None, None, HeaderMap::new(), body)
+            .await;
+    let rejected_status = rejected.status();
+    let rejected_body = axum::body::to_bytes(rejected.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    let _ = sqlx::query(
+        "DELETE FROM global_settings WHERE key IN ('OPENAI_PARAM_DENYLIST', 'OPENAI_PARAM_FILTER_MODE')",
+    )
+    .execute(&pool)
+    .await;
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM runtime_models WHERE model_id = $1")
+        .bind(&model_hf)
+        .execute(&pool)
+        .await;
+
+    assert_eq!(stripped.status(), 200);
+    let forwarded: serde_json::Value = serde_json::from_slice(&forwarded).unwrap();
+    assert!(forwarded.get("logit_bias").is_none());
+    assert_eq!(forwarded["temperature"], 0.3);
+
+    assert_eq!(rejected_status, 422);
+    let rejected_body: serde_json::Value = serde_json::from_slice(&rejected_body).unwrap();
+    assert_eq!(rejected_body["error"], "unsupported_parameter");
+    assert_eq!(rejected_body["param"], "logit_bias");
+}
+
+#[tokio::test]
+async fn test_session_affinity_breaks_are_tracked() {
+    // Only this test reads the backend header, so enabling the check process-wide is safe here.
+    std::env::set_var("OPENAI_PROXY_AFFINITY_CHECK", "1");
+
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+
+    // Mock worker: sessions prefixed `sticky-` always land on vllm-0, others alternate backends.
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let upstream = axum::Router::new().route(
+        "/v1/chat/completions",
+        axum::routing::post(move |headers: HeaderMap| {
+            let counter = counter.clone();
+            async move {
+                let n = counter.fetch_add(1, Ordering::SeqCst);
+                let session = headers
+                    .get("x-inventiv-session")
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or_default();
+                let backend = if session.starts_with("sticky-") {
+                    "vllm-0".to_string()
+                } else {
+                    format!("vllm-{}", n % 2)
+                };
+                (
+                    [("x-worker-backend", backend)],
+                    Json(json!({"id": "cmpl-1", "object": "chat.completion", "choices": []})),
+                )
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, upstream).await;
+    });
+
+    let model_hf = format!("test-org/affinity-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+         VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                 'ready', $3, $4, NOW())",
+    )
+    .bind(instance_id)
+    .bind(model_id)
+    .bind(&model_hf)
+    .bind(port as i32)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test instance");
+
+    let send = |session: &'static str, content: &'static str| {
+        let state = state.clone();
+        let body = json!({"model": model_hf, "messages": [{"role": "user", "content": content}]});
+        async move {
+            let mut headers = HeaderMap::new();
+            headers.insert("x-inventiv-session", session.parse().unwrap());
+            openai::openai_proxy_chat_completions(
+                State(state),
+                None,
+                None,
+                headers,
+                Bytes::from(body.to_string()),
+            )
+            .await
+            .status()
+        }
+    };
+    let mut statuses = Vec::new();
+    for content in ["a", "b", "c"] {
+        statuses.push(send("sticky-1", content).await);
+    }
+    let breaks_while_sticky = state.affinity.breaks();
+    for content in ["d", "e"] {
+        statuses.push(send("roaming-1", content).await);
+    }
+
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM runtime_models WHERE model_id = $1")
+        .bind(&model_hf)
+        .execute(&pool)
+        .await;
+
+    assert!(statuses.iter().all(|s| *s == 200), "{:?}", statuses);
+    assert_eq!(calls.load(Ordering::SeqCst), 5);
+    assert_eq!(breaks_while_sticky, 0);
+    assert_eq!(state.affinity.breaks(), 1);
+}
+
+#[tokio::test]
+async fn test_concurrent_streams_per_api_key_are_capped() {
+    let redis = get_test_redis_client().await;
+    if redis.get_multiplexed_async_connection().await.is_err() {
+        eprintln!("skipping test: Redis not reachable");
+        return;
+    }
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(redis, pool.clone());
+
+    let upstream = axum::Router::new().route(
+        "/v1/chat/completions",
+        axum::routing::post(|| async {
+            (
+                [(axum::http::header::CONTENT_TYPE, "text/event-stream")],
+                "data: {\"choices\":[]}\n\ndata: [DONE]\n\n",
+            )
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, upstream).await;
+    });
+
+    let model_hf = format!("test-org/streams-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+         VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                 'ready', $3, $4, NOW())",
+    )
+    .bind(instance_id)
+    .bind(model_id)
+    .bind(&model_hf)
+    .bind(port as i32)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test instance");
+
+    const LIMIT: usize = 2;
+    let principal = ApiKeyPrincipal {
+        api_key_id: uuid::Uuid::new_v4(),
+        user_id: uuid::Uuid::new_v4(),
+        key_prefix: "sk-inv-test".to_string(),
+        name: "test-streams".to_string(),
+        allowed_models: None,
+        rate_limit_per_minute: None,
+        max_concurrent_streams: Some(LIMIT as i32),
+    };
+    let open_stream = || {
+        let state = state.clone();
+        let principal = principal.clone();
+        let body = json!({"model": model_hf, "stream": true, "messages": []});
+        async move {
+            openai::openai_proxy_chat_completions(
+                State(state),
+                None,
+                Some(Extension(principal)),
+                HeaderMap::new(),
+                Bytes::from(body.to_string()),
+            )
+            .await
+        }
+    };
+
+    // Responses are held (bodies not consumed): every stream stays open.
+    let mut open = Vec::new();
+    for _ in 0..LIMIT {
+        open.push(open_stream().await);
+    }
+    let rejected = open_stream().await;
+    let rejected_status = rejected.status();
+    let rejected_body = axum::body::to_bytes(rejected.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    // A disconnected client (dropped body) frees its slot.
+    let statuses: Vec<_> = open.iter().map(|r| r.status()).collect();
+    drop(open.pop());
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let after_disconnect = open_stream().await.status();
+    drop(open);
+
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM runtime_models WHERE model_id = $1")
+        .bind(&model_hf)
+        .execute(&pool)
+        .await;
+
+    assert!(statuses.iter().all(|s| *s == 200), "{:?}", statuses);
+    assert_eq!(rejected_status, 429);
+    let rejected_body: serde_json::Value = serde_json::from_slice(&rejected_body).unwrap();
+    assert_eq!(rejected_body["error"], "too_many_concurrent_streams");
+    assert_eq!(after_disconnect, 200);
+}
+
+#[tokio::test]
+async fn test_sampled_proxy_trace_is_stored_for_non_streaming_request() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+    let port = spawn_leaky_upstream().await;
+
+    let model_hf = format!("test-org/trace-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+         VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                 'ready', $3, $4, NOW())",
+    )
+    .bind(instance_id)
+    .bind(model_id)
+    .bind(&model_hf)
+    .bind(port as i32)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test instance");
+    sqlx::query(
+        "DELETE FROM global_settings WHERE key IN ('PROXY_TRACE_ENABLED', 'PROXY_TRACE_SAMPLE_PER_MILLE')",
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to reset proxy trace settings");
+    sqlx::query(
+        "INSERT INTO global_settings (key, value_bool, value_int)
+         VALUES ('PROXY_TRACE_ENABLED', true, NULL), ('PROXY_TRACE_SAMPLE_PER_MILLE', NULL, 1000)",
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to enable proxy traces");
+
+    let mut client_headers = HeaderMap::new();
+    client_headers.insert("authorization", "Bearer client-key".parse().unwrap());
+    let body = json!({"model": model_hf, "messages": [{"role": "user", "content": "hi"}]});
+    let response = openai::openai_proxy_chat_completions(
+        State(state),
+        None,
+        None,
+        client_headers,
+        Bytes::from(body.to_string()),
+    )
+    .await;
+    let status = response.status();
+
+    let traces: Vec<(
+        Option<uuid::Uuid>,
+        serde_json::Value,
+        Option<serde_json::Value>,
+        Option<serde_json::Value>,
+    )> = sqlx::query_as(
+        "SELECT instance_id, request_headers, request_body, response_body
+             FROM proxy_traces WHERE model = $1",
+    )
+    .bind(&model_hf)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+
+    let _ = sqlx::query(
+        "DELETE FROM global_settings WHERE key IN ('PROXY_TRACE_ENABLED', 'PROXY_TRACE_SAMPLE_PER_MILLE')",
+    )
+    .execute(&pool)
+    .await;
+    let _ = sqlx::query("DELETE FROM proxy_traces WHERE model = $1")
+        .bind(&model_hf)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+
+    assert_eq!(status, 200);
+    assert_eq!(traces.len(), 1);
+    let (trace_instance, headers, request_body, response_body) = &traces[0];
+    assert_eq!(*trace_instance, Some(instance_id));
+    assert_eq!(headers["authorization"], "[REDACTED]");
+    assert_eq!(
+        request_body.as_ref().unwrap()["messages"][0]["content"],
+        "hi"
+    );
+    assert_eq!(response_body.as_ref().unwrap()["id"], "cmpl-1");
+}
+
+/// Fake worker answering every connection with a completion that reports token usage.
+async fn spawn_usage_upstream() -> u16 {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        while let Ok((mut sock, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut buf = Vec::new();
+                let mut chunk = [0u8; 4096];
+                while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+                    let n = sock.read(&mut chunk).await.unwrap_or(0);
+                    if n == 0 {
+                        return;
+                    }
+                    buf.extend_from_slice(&chunk[..n]);
+                }
+                let payload = r#"{"id":"cmpl-u","object":"chat.completion","choices":[],"usage":{"prompt_tokens":7,"completion_tokens":5,"total_tokens":12}}"#;
+                let resp = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    payload.len(),
+                    payload
+                );
+                let _ = sock.write_all(resp.as_bytes()).await;
+            });
+        }
+    });
+    port
+}
+
+#[tokio::test]
+async fn test_api_key_usage_reports_only_that_keys_requests() {
+    use inventiv_api::api_keys;
+    use inventiv_api::auth::AuthUser;
+
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+    let port = spawn_usage_upstream().await;
+
+    let model_hf = format!("test-org/key-usage-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+         VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                 'ready', $3, $4, NOW())",
+    )
+    .bind(instance_id)
+    .bind(model_id)
+    .bind(&model_hf)
+    .bind(port as i32)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test instance");
+
+    let email = format!("key-usage-{}@test.local", uuid::Uuid::new_v4());
+    let owner_id = common::create_test_user(&pool, &email, "password").await;
+    let (key_a, prefix_a) = api_keys::generate_api_key();
+    let row_a = api_keys::insert_api_key(&pool, owner_id, "usage-a", &key_a, &prefix_a, None)
+        .await
+        .unwrap();
+    let (key_b, prefix_b) = api_keys::generate_api_key();
+    let row_b = api_keys::insert_api_key(&pool, owner_id, "usage-b", &key_b, &prefix_b, None)
+        .await
+        .unwrap();
+
+    let principal = ApiKeyPrincipal {
+        api_key_id: row_a.id,
+        user_id: owner_id,
+        key_prefix: row_a.key_prefix.clone(),
+        name: row_a.name.clone(),
+        allowed_models: None,
+        rate_limit_per_minute: None,
+        max_concurrent_streams: None,
+    };
+    let body = json!({"model": model_hf, "messages": [{"role": "user", "content": "hi"}]});
+    let mut statuses = Vec::new();
+    for _ in 0..2 {
+        let response = openai::openai_proxy_chat_completions(
+            State(state.clone()),
+            None,
+            Some(Extension(principal.clone())),
+            HeaderMap::new(),
+            Bytes::from(body.to_string()),
+        )
+        .await;
+        statuses.push(response.status());
+    }
+
+    let as_user = |user_id: uuid::Uuid| AuthUser {
+        user_id,
+        email: email.clone(),
+        role: "user".to_string(),
+        session_id: uuid::Uuid::new_v4().to_string(),
+        current_organization_id: None,
+        current_organization_role: None,
+    };
+    let usage = |user: AuthUser, key_id: uuid::Uuid| {
+        let state = state.clone();
+        async move {
+            let response = api_keys::get_api_key_usage(
+                State(state),
+                Extension(user),
+                Path(key_id),
+                axum::extract::Query(api_keys::ApiKeyUsageQuery {
+                    window: Some("hour".to_string()),
+                }),
+            )
+            .await
+            .into_response();
+            let status = response.status();
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            (
+                status,
+                serde_json::from_slice::<serde_json::Value>(&bytes).unwrap(),
+            )
+        }
+    };
+    let (status_a, report_a) = usage(as_user(owner_id), row_a.id).await;
+    let (status_b, report_b) = usage(as_user(owner_id), row_b.id).await;
+    let (status_stranger, _) = usage(as_user(uuid::Uuid::new_v4()), row_a.id).await;
+
+    let _ = sqlx::query("DELETE FROM finops.inference_usage WHERE api_key_id IN ($1, $2)")
+        .bind(row_a.id)
+        .bind(row_b.id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM api_keys WHERE user_id = $1")
+        .bind(owner_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(owner_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+
+    assert_eq!(statuses, vec![200, 200]);
+    assert_eq!(status_a, 200);
+    assert_eq!(report_a["requests"], 2);
+    assert_eq!(report_a["input_tokens"], 14);
+    assert_eq!(report_a["output_tokens"], 10);
+    assert_eq!(report_a["total_tokens"], 24);
+    assert_eq!(report_a["models"][0]["model"], model_hf);
+    assert_eq!(status_b, 200);
+    assert_eq!(report_b["requests"], 0);
+    assert_eq!(report_b["models"].as_array().unwrap().len(), 0);
+    assert_eq!(status_stranger, 404);
+}
+
+#[tokio::test]
+async fn test_served_by_header_names_selected_instance() {
+    std::env::set_var("OPENAI_PROXY_SERVED_BY_HEADER", "1");
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+
+    let upstream = axum::Router::new().route(
+        "/v1/chat/completions",
+        axum::routing::post(|Json(body): Json<serde_json::Value>| async move {
+            if body["stream"] == json!(true) {
+                (
+                    [(axum::http::header::CONTENT_TYPE, "text/event-stream")],
+                    "data: {\"choices\":[]}\n\ndata: [DONE]\n\n".to_string(),
+                )
+            } else {
+                (
+                    [(axum::http::header::CONTENT_TYPE, "application/json")],
+                    r#"{"id":"cmpl-s","object":"chat.completion","choices":[]}"#.to_string(),
+                )
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, upstream).await;
+    });
+
+    let model_hf = format!("test-org/served-by-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+         VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                 'ready', $3, $4, NOW())",
+    )
+    .bind(instance_id)
+    .bind(model_id)
+    .bind(&model_hf)
+    .bind(port as i32)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test instance");
+
+    let mut served = Vec::new();
+    for stream in [false, true] {
+        let body = json!({"model": model_hf, "stream": stream, "messages": []});
+        let response = openai::openai_proxy_chat_completions(
+            State(state.clone()),
+            None,
+            None,
+            HeaderMap::new(),
+            Bytes::from(body.to_string()),
+        )
+        .await;
+        served.push((
+            response.status(),
+            response
+                .headers()
+                .get("x-served-by")
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string()),
+        ));
+        let _ = axum::body::to_bytes(response.into_body(), usize::MAX).await;
+    }
+
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM runtime_models WHERE model_id = $1")
+        .bind(&model_hf)
+        .execute(&pool)
+        .await;
+
+    let expected = Some(instance_id.to_string());
+    assert_eq!(
+        served,
+        vec![
+            (axum::http::StatusCode::OK, expected.clone()),
+            (axum::http::StatusCode::OK, expected)
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_requests_without_session_header_go_to_least_loaded_worker() {
+    std::env::set_var("OPENAI_PROXY_SERVED_BY_HEADER", "1");
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+
+    let upstream = axum::Router::new().route(
+        "/v1/chat/completions",
+        axum::routing::post(|| async {
+            (
+                [(axum::http::header::CONTENT_TYPE, "application/json")],
+                r#"{"id":"cmpl-l","object":"chat.completion","choices":[]}"#,
+            )
+        }),
+    );
+
+    let model_hf = format!("test-org/least-loaded-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    // Both workers serve the model; only their reported queue depth differs.
+    let mut instance_ids = Vec::new();
+    for queue_depth in [7, 0] {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let app = upstream.clone();
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, app).await;
+        });
+        let instance_id = uuid::Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                    worker_status, worker_model_id, worker_vllm_port, worker_queue_depth, worker_last_heartbeat)
+             VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                     'ready', $3, $4, $5, NOW())",
+        )
+        .bind(instance_id)
+        .bind(model_id)
+        .bind(&model_hf)
+        .bind(port as i32)
+        .bind(queue_depth)
+        .execute(&pool)
+        .await
+        .expect("Failed to insert test instance");
+        instance_ids.push(instance_id);
+    }
+
+    let mut served = Vec::new();
+    for content in ["first", "second"] {
+        let body = json!({"model": model_hf, "messages": [{"role": "user", "content": content}]});
+        let response = openai::openai_proxy_chat_completions(
+            State(state.clone()),
+            None,
+            None,
+            HeaderMap::new(),
+            Bytes::from(body.to_string()),
+        )
+        .await;
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string())
+        };
+        served.push((
+            response.status(),
+            header("x-served-by"),
+            header("x-inventiv-session").is_some(),
+        ));
+        let _ = axum::body::to_bytes(response.into_body(), usize::MAX).await;
+    }
+
+    for id in &instance_ids {
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(id)
+            .execute(&pool)
+            .await;
+    }
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM runtime_models WHERE model_id = $1")
+        .bind(&model_hf)
+        .execute(&pool)
+        .await;
+
+    // Anonymous callers get a random session: echoed, but never pinning the busier worker.
+    let less_loaded = Some(instance_ids[1].to_string());
+    assert_eq!(
+        served,
+        vec![
+            (axum::http::StatusCode::OK, less_loaded.clone(), true),
+            (axum::http::StatusCode::OK, less_loaded, true)
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_echoed_generated_session_keeps_api_key_on_same_worker() {
+    std::env::set_var("OPENAI_PROXY_SERVED_BY_HEADER", "1");
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+
+    let upstream = axum::Router::new().route(
+        "/v1/chat/completions",
+        axum::routing::post(|| async {
+            (
+                [(axum::http::header::CONTENT_TYPE, "application/json")],
+                r#"{"id":"cmpl-p","object":"chat.completion","choices":[]}"#,
+            )
+        }),
+    );
+
+    let model_hf = format!("test-org/pinned-session-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    let mut instance_ids = Vec::new();
+    for _ in 0..2 {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let app = upstream.clone();
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, app).await;
+        });
+        let instance_id = uuid::Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                    worker_status, worker_model_id, worker_vllm_port, worker_queue_depth, worker_last_heartbeat)
+             VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                     'ready', $3, $4, 0, NOW())",
+        )
+        .bind(instance_id)
+        .bind(model_id)
+        .bind(&model_hf)
+        .bind(port as i32)
+        .execute(&pool)
+        .await
+        .expect("Failed to insert test instance");
+        instance_ids.push(instance_id);
+    }
+
+    let principal = ApiKeyPrincipal {
+        api_key_id: uuid::Uuid::new_v4(),
+        user_id: uuid::Uuid::new_v4(),
+        key_prefix: "sk-inv-test".to_string(),
+        name: "test-pinned-session".to_string(),
+        allowed_models: None,
+        rate_limit_per_minute: None,
+        max_concurrent_streams: None,
+    };
+    let body = json!({"model": model_hf, "messages": []}).to_string();
+    let send = |headers: HeaderMap| {
+        openai::openai_proxy_chat_completions(
+            State(state.clone()),
+            None,
+            Some(Extension(principal.clone())),
+            headers,
+            Bytes::from(body.clone()),
+        )
+    };
+    let header = |response: &axum::response::Response, name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string())
+    };
+
+    // No header: the session generated from the API key picks the worker and is echoed.
+    let first = send(HeaderMap::new()).await;
+    let first_status = first.status();
+    let first_served = header(&first, "x-served-by");
+    let session = header(&first, "x-inventiv-session");
+    let _ = axum::body::to_bytes(first.into_body(), usize::MAX).await;
+
+    // Make the pinned worker the busier one: the echoed session still lands on it.
+    if let Some(served) = first_served.as_deref() {
+        sqlx::query("UPDATE instances SET worker_queue_depth = 9 WHERE id = $1")
+            .bind(uuid::Uuid::parse_str(served).unwrap())
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+    let mut headers = HeaderMap::new();
+    if let Some(session) = session.as_deref() {
+        headers.insert("x-inventiv-session", session.parse().unwrap());
+    }
+    let second = send(headers).await;
+    let second_status = second.status();
+    let second_served = header(&second, "x-served-by");
+    let _ = axum::body::to_bytes(second.into_body(), usize::MAX).await;
+
+    for id in &instance_ids {
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(id)
+            .execute(&pool)
+            .await;
+    }
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM runtime_models WHERE model_id = $1")
+        .bind(&model_hf)
+        .execute(&pool)
+        .await;
+
+    assert_eq!(first_status, axum::http::StatusCode::OK);
+    assert_eq!(second_status, axum::http::StatusCode::OK);
+    assert!(session.is_some_and(|s| s.starts_with("ak-")));
+    assert!(first_served.is_some());
+    assert_eq!(second_served, first_served);
+}
+
+#[tokio::test]
+async fn test_streaming_request_json_error_is_delivered_as_sse_event() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+
+    // Worker rejects the request before streaming starts, with a plain JSON 400.
+    let upstream = axum::Router::new().route(
+        "/v1/chat/completions",
+        axum::routing::post(|| async {
+            (
+                axum::http::StatusCode::BAD_REQUEST,
+                [(axum::http::header::CONTENT_TYPE, "application/json")],
+                r#"{"object":"error","message":"max_tokens is too large","type":"BadRequestError","code":400}"#,
+            )
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, upstream).await;
+    });
+
+    let model_hf = format!("test-org/sse-error-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+         VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                 'ready', $3, $4, NOW())",
+    )
+    .bind(instance_id)
+    .bind(model_id)
+    .bind(&model_hf)
+    .bind(port as i32)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test instance");
+
+    let mut results = Vec::new();
+    for stream in [true, false] {
+        let body =
+            json!({"model": model_hf, "stream": stream, "messages": [], "max_tokens": 1_000_000});
+        let response = openai::openai_proxy_chat_completions(
+            State(state.clone()),
+            None,
+            None,
+            HeaderMap::new(),
+            Bytes::from(body.to_string()),
+        )
+        .await;
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(axum::http::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        results.push((
+            status,
+            content_type,
+            String::from_utf8_lossy(&bytes).to_string(),
+        ));
+    }
+
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM runtime_models WHERE model_id = $1")
+        .bind(&model_hf)
+        .execute(&pool)
+        .await;
+
+    let (status, content_type, body) = &results[0];
+    assert_eq!(*status, axum::http::StatusCode::BAD_REQUEST);
+    assert!(
+        content_type.starts_with("text/event-stream"),
+        "{content_type}"
+    );
+    let data = body
+        .strip_prefix("event: error\ndata: ")
+        .and_then(|rest| rest.strip_suffix("\n\n"))
+        .unwrap_or_else(|| panic!("not a single SSE error event: {body:?}"));
+    let event: serde_json::Value = serde_json::from_str(data).unwrap();
+    assert_eq!(event["error"]["message"], "max_tokens is too large");
+    assert_eq!(event["error"]["code"], 400);
+
+    // Non-streaming requests keep the worker's JSON error untouched.
+    let (status, content_type, body) = &results[1];
+    assert_eq!(*status, axum::http::StatusCode::BAD_REQUEST);
+    assert!(
+        content_type.starts_with("application/json"),
+        "{content_type}"
+    );
+    let error: serde_json::Value = serde_json::from_str(body).unwrap();
+    assert_eq!(error["message"], "max_tokens is too large");
+}
+
+#[tokio::test]
+async fn test_unreachable_worker_fails_over_after_short_connect_timeout() {
+    std::env::set_var("OPENAI_PROXY_CONNECT_TIMEOUT_MS", "200");
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+
+    let upstream = axum::Router::new().route(
+        "/v1/chat/completions",
+        axum::routing::post(|| async {
+            (
+                [(axum::http::header::CONTENT_TYPE, "application/json")],
+                r#"{"id":"cmpl-failover","object":"chat.completion","choices":[]}"#,
+            )
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, upstream).await;
+    });
+
+    let model_hf = format!("test-org/connect-failover-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    // Sticky routing orders candidates by id: the dead worker sorts first, the live one last.
+    let dead_id = uuid::Uuid::from_u128(uuid::Uuid::new_v4().as_u128() >> 8);
+    let live_id = uuid::Uuid::from_u128(uuid::Uuid::new_v4().as_u128() | (0xff << 120));
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    // Dead worker: nothing listens on its port anymore, the connect is refused.
+    let dead_port = {
+        let l = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        l.local_addr().unwrap().port()
+    };
+    for (instance_id, worker_port) in [(dead_id, dead_port), (live_id, port)] {
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                    worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+             VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                     'ready', $3, $4, NOW())",
+        )
+        .bind(instance_id)
+        .bind(model_id)
+        .bind(&model_hf)
+        .bind(worker_port as i32)
+        .execute(&pool)
+        .await
+        .expect("Failed to insert test instance");
+    }
+
+    // A session key that picks the first (dead) candidate; mirrors worker_routing::sticky_index.
+    let session = (0..)
+        .map(|n| format!("failover-{}", n))
+        .find(|k| {
+            use std::hash::{Hash, Hasher};
+            let mut h = std::collections::hash_map::DefaultHasher::new();
+            k.hash(&mut h);
+            h.finish().is_multiple_of(2)
+        })
+        .unwrap();
+    let mut headers = HeaderMap::new();
+    headers.insert("x-inventiv-session", session.parse().unwrap());
+
+    let body = json!({"model": model_hf, "stream": false, "messages": []});
+    let started = std::time::Instant::now();
+    let response = openai::openai_proxy_chat_completions(
+        State(state.clone()),
+        None,
+        None,
+        headers,
+        Bytes::from(body.to_string()),
+    )
+    .await;
+    let elapsed = started.elapsed();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let tripped = state.worker_breaker.open_instances();
+    // Counted once, as a success; the failed connect only shows up as a failover.
+    let (total_requests, failed_requests): (i64, i64) = sqlx::query_as(
+        "SELECT total_requests::bigint, failed_requests::bigint FROM runtime_models WHERE model_id = $1",
+    )
+    .bind(&model_hf)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let _ = sqlx::query("DELETE FROM instances WHERE id = ANY($1)")
+        .bind(vec![dead_id, live_id])
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM runtime_models WHERE model_id = $1")
+        .bind(&model_hf)
+        .execute(&pool)
+        .await;
+    std::env::remove_var("OPENAI_PROXY_CONNECT_TIMEOUT_MS");
+
+    assert_eq!(status, axum::http::StatusCode::OK);
+    let served: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(served["id"], "cmpl-failover");
+    assert!(
+        elapsed < std::time::Duration::from_secs(5),
+        "failover took {:?}",
+        elapsed
+    );
+    assert_eq!(tripped, vec![dead_id]);
+    assert_eq!(state.worker_breaker.failovers(), 1);
+    assert_eq!((total_requests, failed_requests), (1, 0));
+}
+
+#[tokio::test]
+async fn test_instance_detail_reports_requests_served_by_that_instance() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+
+    let upstream = axum::Router::new().route(
+        "/v1/chat/completions",
+        axum::routing::post(|Json(body): Json<serde_json::Value>| async move {
+            if body["stream"] == json!(true) {
+                (
+                    [(axum::http::header::CONTENT_TYPE, "text/event-stream")],
+                    "data: {\"choices\":[],\"usage\":{\"prompt_tokens\":3,\"completion_tokens\":4,\"total_tokens\":7}}\n\ndata: [DONE]\n\n".to_string(),
+                )
+            } else {
+                (
+                    [(axum::http::header::CONTENT_TYPE, "application/json")],
+                    r#"{"id":"cmpl-c","object":"chat.completion","choices":[]}"#.to_string(),
+                )
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, upstream).await;
+    });
+
+    let model_hf = format!("test-org/instance-stats-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+         VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                 'ready', $3, $4, NOW())",
+    )
+    .bind(instance_id)
+    .bind(model_id)
+    .bind(&model_hf)
+    .bind(port as i32)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test instance");
+
+    let mut statuses = Vec::new();
+    for stream in [false, true] {
+        let body = json!({"model": model_hf, "stream": stream, "messages": []});
+        let response = openai::openai_proxy_chat_completions(
+            State(state.clone()),
+            None,
+            None,
+            HeaderMap::new(),
+            Bytes::from(body.to_string()),
+        )
+        .await;
+        statuses.push(response.status());
+        let _ = axum::body::to_bytes(response.into_body(), usize::MAX).await;
+    }
+
+    // Counters are updated off the request path: wait for both to land.
+    let mut detail = serde_json::Value::Null;
+    for _ in 0..50 {
+        let response = instances::get_instance(State(state.clone()), Path(instance_id))
+            .await
+            .into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        detail = serde_json::from_slice(&body).unwrap();
+        if detail["requests_served"] == json!(2) {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM runtime_models WHERE model_id = $1")
+        .bind(&model_hf)
+        .execute(&pool)
+        .await;
+
+    assert_eq!(
+        statuses,
+        vec![axum::http::StatusCode::OK, axum::http::StatusCode::OK]
+    );
+    // The streaming request is counted once, even though the worker reported token usage.
+    assert_eq!(detail["requests_served"], json!(2), "{}", detail);
+    assert_eq!(detail["requests_failed"], json!(0), "{}", detail);
+    assert!(detail["last_request_at"].is_string(), "{}", detail);
+}
+
+#[tokio::test]
+async fn test_allowlisted_tokenize_is_passed_through_to_worker() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+
+    let upstream = axum::Router::new().route(
+        "/tokenize",
+        axum::routing::post(|Json(body): Json<serde_json::Value>| async move {
+            Json(json!({"model": body["model"], "count": 3, "tokens": [1, 2, 3]}))
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, upstream).await;
+    });
+
+    let model_hf = format!("test-org/passthrough-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+         VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                 'ready', $3, $4, NOW())",
+    )
+    .bind(instance_id)
+    .bind(model_id)
+    .bind(&model_hf)
+    .bind(port as i32)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test instance");
+    let _ = sqlx::query("DELETE FROM global_settings WHERE key = 'OPENAI_PROXY_PASSTHROUGH_PATHS'")
+        .execute(&pool)
+        .await;
+    sqlx::query(
+        "INSERT INTO global_settings (key, value_text) VALUES ('OPENAI_PROXY_PASSTHROUGH_PATHS', '/v1/score, /tokenize')",
+    )
+    .execute(&pool)
+    .await
+    .expect("Failed to set passthrough allowlist");
+
+    let mut results = Vec::new();
+    for path in ["tokenize", "detokenize"] {
+        let body = json!({"model": model_hf, "prompt": "hello world"});
+        let response = openai::openai_proxy_passthrough(
+            State(state.clone()),
+            None,
+            None,
+            axum::http::Method::POST,
+            Path(path.to_string()),
+            axum::extract::RawQuery(None),
+            HeaderMap::new(),
+            Bytes::from(body.to_string()),
+        )
+        .await;
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap_or_default();
+        results.push((status, body));
+    }
+
+    // No model in the body: only a worker serving a model the caller may use is picked.
+    let modelless = |api_key: Option<ApiKeyPrincipal>| {
+        let state = state.clone();
+        async move {
+            openai::openai_proxy_passthrough(
+                State(state),
+                None,
+                api_key.map(Extension),
+                axum::http::Method::POST,
+                Path("tokenize".to_string()),
+                axum::extract::RawQuery(None),
+                HeaderMap::new(),
+                Bytes::from(json!({"prompt": "hello world"}).to_string()),
+            )
+            .await
+            .status()
+        }
+    };
+    let out_of_scope = modelless(Some(ApiKeyPrincipal {
+        api_key_id: uuid::Uuid::new_v4(),
+        user_id: uuid::Uuid::new_v4(),
+        key_prefix: "sk-inv-test".to_string(),
+        name: "test-passthrough-scoped".to_string(),
+        allowed_models: Some(vec!["test-org/some-other-model".to_string()]),
+        rate_limit_per_minute: None,
+        max_concurrent_streams: None,
+    }))
+    .await;
+
+    let _ = sqlx::query("DELETE FROM global_settings WHERE key = 'OPENAI_PROXY_PASSTHROUGH_PATHS'")
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM runtime_models WHERE model_id = $1")
+        .bind(&model_hf)
+        .execute(&pool)
+        .await;
+
+    assert_eq!(results[0].0, axum::http::StatusCode::OK, "{}", results[0].1);
+    assert_eq!(results[0].1["count"], 3);
+    assert_eq!(results[0].1["model"], json!(model_hf));
+    assert_eq!(results[1].0, axum::http::StatusCode::NOT_FOUND);
+    assert_eq!(results[1].1["error"], "not_found");
+    assert_eq!(out_of_scope, axum::http::StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn test_embeddings_time_out_before_completions_on_slow_worker() {
+    std::env::set_var("OPENAI_EMBEDDINGS_TIMEOUT_SECONDS", "1");
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+
+    let upstream = axum::Router::new().route(
+        "/v1/embeddings",
+        axum::routing::post(|| async {
+            tokio::time::sleep(std::time::Duration::from_secs(4)).await;
+            (
+                [(axum::http::header::CONTENT_TYPE, "application/json")],
+                r#"{"object":"list","data":[]}"#,
+            )
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, upstream).await;
+    });
+
+    let model_hf = format!("test-org/slow-embeddings-{}", uuid::Uuid::new_v4());
+    let model_id = uuid::Uuid::new_v4();
+    let instance_id = uuid::Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+         VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+    )
+    .bind(model_id)
+    .bind(&model_hf)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test model");
+    sqlx::query(
+        "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, ip_address,
+                                worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat)
+         VALUES ($1, (SELECT id FROM providers LIMIT 1), $2, 'ready', NOW(), '{}', '127.0.0.1',
+                 'ready', $3, $4, NOW())",
+    )
+    .bind(instance_id)
+    .bind(model_id)
+    .bind(&model_hf)
+    .bind(port as i32)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test instance");
+
+    let body = json!({"model": model_hf, "input": "hello"});
+    let started = std::time::Instant::now();
+    let response = openai::openai_proxy_embeddings(
+        State(state.clone()),
+        None,
+        None,
+        HeaderMap::new(),
+        Bytes::from(body.to_string()),
+    )
+    .await;
+    let elapsed = started.elapsed();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let embeddings_timeout = ProxyClients::request_timeout("/v1/embeddings", false);
+    let completions_timeout = ProxyClients::request_timeout("/v1/chat/completions", false);
+
+    let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+        .bind(model_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM runtime_models WHERE model_id = $1")
+        .bind(&model_hf)
+        .execute(&pool)
+        .await;
+    std::env::remove_var("OPENAI_EMBEDDINGS_TIMEOUT_SECONDS");
+
+    assert_eq!(status, axum::http::StatusCode::GATEWAY_TIMEOUT);
+    let error: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(error["error"], "upstream_timeout");
+    assert!(
+        elapsed < std::time::Duration::from_secs(3),
+        "embeddings timeout took {:?}",
+        elapsed
+    );
+    assert_eq!(embeddings_timeout, std::time::Duration::from_secs(1));
+    assert!(completions_timeout > embeddings_timeout);
+}
@@ -0,0 +1,106 @@
+// Integration tests for /runtime/models (live capacity + request counters)
+
+mod common;
+
+use axum::extract::{Query, State};
+use axum::response::IntoResponse;
+use common::{get_test_db_pool, get_test_redis_client};
+use inventiv_api::handlers::monitoring::{self, RuntimeModelsParams};
+use inventiv_api::worker_routing;
+use inventiv_api::AppState;
+use uuid::Uuid;
+
+async fn runtime_model_counts(
+    state: std::sync::Arc<AppState>,
+    params: RuntimeModelsParams,
+    model_id: &str,
+) -> (i64, i64) {
+    let response = monitoring::list_runtime_models(State(state), Query(params))
+        .await
+        .into_response();
+    assert_eq!(response.status(), 200);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let rows: Vec<serde_json::Value> = serde_json::from_slice(&bytes).unwrap();
+    let row = rows
+        .iter()
+        .find(|r| r["model_id"] == model_id)
+        .expect("runtime model row missing");
+    (
+        row["total_requests"].as_i64().unwrap(),
+        row["failed_requests"].as_i64().unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn test_runtime_models_windowed_counts() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+    let model_id = format!("test-runtime-window-{}", Uuid::new_v4());
+
+    // Recent traffic: 2 ok + 1 failed.
+    worker_routing::bump_runtime_model_counters(&pool, &model_id, true).await;
+    worker_routing::bump_runtime_model_counters(&pool, &model_id, true).await;
+    worker_routing::bump_runtime_model_counters(&pool, &model_id, false).await;
+
+    // Older traffic (two days ago): 5 requests, 2 failed.
+    sqlx::query(
+        "INSERT INTO runtime_model_counters_minute (model_id, bucket_minute, requests, failed_requests)
+         VALUES ($1, date_trunc('minute', NOW() - INTERVAL '2 days'), 5, 2)",
+    )
+    .bind(&model_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "UPDATE runtime_models SET total_requests = total_requests + 5, failed_requests = failed_requests + 2
+         WHERE model_id = $1",
+    )
+    .bind(&model_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let now = chrono::Utc::now();
+    let lifetime = runtime_model_counts(
+        state.clone(),
+        RuntimeModelsParams {
+            since: None,
+            until: None,
+        },
+        &model_id,
+    )
+    .await;
+    let last_hour = runtime_model_counts(
+        state.clone(),
+        RuntimeModelsParams {
+            since: Some(now - chrono::Duration::hours(1)),
+            until: None,
+        },
+        &model_id,
+    )
+    .await;
+    let last_week_until_yesterday = runtime_model_counts(
+        state,
+        RuntimeModelsParams {
+            since: Some(now - chrono::Duration::days(7)),
+            until: Some(now - chrono::Duration::days(1)),
+        },
+        &model_id,
+    )
+    .await;
+
+    let _ = sqlx::query("DELETE FROM runtime_model_counters_minute WHERE model_id = $1")
+        .bind(&model_id)
+        .execute(&pool)
+        .await;
+    let _ = sqlx::query("DELETE FROM runtime_models WHERE model_id = $1")
+        .bind(&model_id)
+        .execute(&pool)
+        .await;
+
+    assert_eq!(lifetime, (8, 3));
+    assert_eq!(last_hour, (3, 1));
+    assert_eq!(last_week_until_yesterday, (5, 2));
+}
@@ -0,0 +1,267 @@
+// Integration tests for settings handlers (provider/region/zone hierarchy)
+
+mod common;
+
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use common::{get_test_db_pool, get_test_redis_client};
+use inventiv_api::settings::{self, CreateZoneRequest};
+use inventiv_api::AppState;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_create_zone_rejects_nonexistent_region() {
+    let state = AppState::new(get_test_redis_client().await, get_test_db_pool().await);
+
+    let response = settings::create_zone(
+        State(state),
+        Json(CreateZoneRequest {
+            region_id: Uuid::new_v4(),
+            provider_id: None,
+            name: "Orphan zone".to_string(),
+            code: format!("test-orphan-{}", Uuid::new_v4()),
+            is_active: Some(true),
+        }),
+    )
+    .await
+    .into_response();
+
+    assert_eq!(response.status(), 422);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(body["error"], "region_not_found");
+}
+
+#[tokio::test]
+async fn test_bulk_zone_availability_applies_desired_set() {
+    use axum::extract::Path;
+    use inventiv_api::instance_type_zones::{
+        list_instance_type_zones, set_instance_type_zone_availability, BulkZoneAvailabilityRequest,
+        BulkZoneAvailabilityResponse, ZoneAvailability, ZoneAvailabilityAction,
+    };
+
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+    let provider_id = common::ensure_mock_provider(&pool).await;
+
+    let suffix = &Uuid::new_v4().simple().to_string()[..8];
+    let region_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO regions (id, provider_id, name, code) VALUES ($1, $2, $3, $3)")
+        .bind(region_id)
+        .bind(provider_id)
+        .bind(format!("test-bulk-{}", suffix))
+        .execute(&pool)
+        .await
+        .unwrap();
+    let mut zones = Vec::new();
+    for n in 0..4 {
+        let zone_id = Uuid::new_v4();
+        sqlx::query("INSERT INTO zones (id, region_id, name, code) VALUES ($1, $2, $3, $3)")
+            .bind(zone_id)
+            .bind(region_id)
+            .bind(format!("test-bulk-{}-{}", suffix, n))
+            .execute(&pool)
+            .await
+            .unwrap();
+        zones.push(zone_id);
+    }
+    let type_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO instance_types (id, provider_id, name, code, gpu_count, vram_per_gpu_gb, cost_per_hour, is_active)
+         VALUES ($1, $2, $3, $3, 1, 24, 1.0, true)",
+    )
+    .bind(type_id)
+    .bind(provider_id)
+    .bind(format!("test-bulk-{}", suffix))
+    .execute(&pool)
+    .await
+    .unwrap();
+    // Existing: zone 0 available (will be disabled), zone 3 (left out -> removed).
+    sqlx::query(
+        "INSERT INTO instance_type_zones (instance_type_id, zone_id, is_available)
+         VALUES ($1, $2, true), ($1, $3, true)",
+    )
+    .bind(type_id)
+    .bind(zones[0])
+    .bind(zones[3])
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let desired = |available: [bool; 3]| BulkZoneAvailabilityRequest {
+        zones: zones[..3]
+            .iter()
+            .zip(available)
+            .map(|(zone_id, is_available)| ZoneAvailability {
+                zone_id: *zone_id,
+                is_available,
+            })
+            .collect(),
+    };
+    let response = set_instance_type_zone_availability(
+        State(state.clone()),
+        Path(type_id),
+        Json(desired([false, true, false])),
+    )
+    .await
+    .into_response();
+    assert_eq!(response.status(), 200);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let applied: BulkZoneAvailabilityResponse = serde_json::from_slice(&bytes).unwrap();
+
+    // Same set again: nothing to apply.
+    let again = set_instance_type_zone_availability(
+        State(state.clone()),
+        Path(type_id),
+        Json(desired([false, true, false])),
+    )
+    .await
+    .into_response();
+    let bytes = axum::body::to_bytes(again.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let noop: BulkZoneAvailabilityResponse = serde_json::from_slice(&bytes).unwrap();
+
+    let state_after: Vec<(Uuid, bool)> =
+        list_instance_type_zones(State(state.clone()), Path(type_id))
+            .await
+            .0
+            .into_iter()
+            .map(|a| (a.zone_id, a.is_available))
+            .collect();
+
+    sqlx::query("DELETE FROM instance_type_zones WHERE instance_type_id = $1")
+        .bind(type_id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM instance_types WHERE id = $1")
+        .bind(type_id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM regions WHERE id = $1")
+        .bind(region_id)
+        .execute(&pool)
+        .await
+        .ok();
+
+    let action_for = |zone_id: Uuid| {
+        applied
+            .changes
+            .iter()
+            .find(|c| c.zone_id == zone_id)
+            .map(|c| c.action)
+    };
+    assert_eq!(applied.changes.len(), 4);
+    assert_eq!(action_for(zones[0]), Some(ZoneAvailabilityAction::Updated));
+    assert_eq!(action_for(zones[1]), Some(ZoneAvailabilityAction::Inserted));
+    assert_eq!(action_for(zones[2]), Some(ZoneAvailabilityAction::Inserted));
+    assert_eq!(action_for(zones[3]), Some(ZoneAvailabilityAction::Removed));
+    assert!(noop.changes.is_empty());
+
+    let mut expected = vec![(zones[0], false), (zones[1], true), (zones[2], false)];
+    let mut state_after = state_after;
+    expected.sort();
+    state_after.sort();
+    assert_eq!(state_after, expected);
+}
+
+#[tokio::test]
+async fn test_provider_tree_nests_region_zone_and_instance_type() {
+    use axum::extract::Path;
+    use inventiv_api::settings::ProviderTree;
+
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+    let provider_id = common::ensure_mock_provider(&pool).await;
+
+    let suffix = &Uuid::new_v4().simple().to_string()[..8];
+    let region_id = Uuid::new_v4();
+    let zone_id = Uuid::new_v4();
+    let type_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO regions (id, provider_id, name, code) VALUES ($1, $2, $3, $3)")
+        .bind(region_id)
+        .bind(provider_id)
+        .bind(format!("test-tree-{}", suffix))
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO zones (id, region_id, name, code) VALUES ($1, $2, $3, $3)")
+        .bind(zone_id)
+        .bind(region_id)
+        .bind(format!("test-tree-{}-a", suffix))
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO instance_types (id, provider_id, name, code, gpu_count, vram_per_gpu_gb, cost_per_hour, is_active)
+         VALUES ($1, $2, $3, $3, 1, 24, 1.5, true)",
+    )
+    .bind(type_id)
+    .bind(provider_id)
+    .bind(format!("test-tree-{}", suffix))
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO instance_type_zones (instance_type_id, zone_id, is_available) VALUES ($1, $2, true)",
+    )
+    .bind(type_id)
+    .bind(zone_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let response = settings::get_provider_tree(State(state.clone()), Path(provider_id))
+        .await
+        .into_response();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let missing = settings::get_provider_tree(State(state.clone()), Path(Uuid::new_v4()))
+        .await
+        .into_response();
+
+    sqlx::query("DELETE FROM instance_type_zones WHERE instance_type_id = $1")
+        .bind(type_id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM instance_types WHERE id = $1")
+        .bind(type_id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM regions WHERE id = $1")
+        .bind(region_id)
+        .execute(&pool)
+        .await
+        .ok();
+
+    assert_eq!(status, 200);
+    assert_eq!(missing.status(), 404);
+    let tree: ProviderTree = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(tree.id, provider_id);
+    let region = tree
+        .regions
+        .iter()
+        .find(|r| r.id == region_id)
+        .expect("region in tree");
+    assert_eq!(region.zones.len(), 1);
+    let zone = &region.zones[0];
+    assert_eq!(zone.id, zone_id);
+    assert!(zone.is_active);
+    assert_eq!(zone.instance_types.len(), 1);
+    let it = &zone.instance_types[0];
+    assert_eq!(it.id, type_id);
+    assert!(it.is_available);
+    assert_eq!(it.gpu_count, 1);
+    assert_eq!(it.cost_per_hour, Some(1.5));
+}
@@ -0,0 +1,71 @@
+// Integration tests for worker token auth on the internal worker routes (API side).
+// Rejected requests never reach the orchestrator, so no orchestrator is needed here.
+
+mod common;
+
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use common::{get_test_db_pool, get_test_redis_client};
+use inventiv_api::handlers::worker;
+use inventiv_api::AppState;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+async fn heartbeat(
+    state: std::sync::Arc<AppState>,
+    instance_id: Uuid,
+    token: Option<&str>,
+) -> (StatusCode, Value) {
+    let mut headers = HeaderMap::new();
+    if let Some(token) = token {
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+        );
+    }
+    let body = Bytes::from(json!({"instance_id": instance_id, "status": "ready"}).to_string());
+    let resp = worker::proxy_worker_heartbeat(State(state), headers, body).await;
+    let status = resp.status();
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn test_revoked_token_is_distinct_from_missing_token() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+
+    let instance_id = Uuid::new_v4();
+    let token = format!("wk_test_{}", Uuid::new_v4());
+    sqlx::query(
+        "INSERT INTO worker_auth_tokens (instance_id, token_hash, token_prefix, revoked_at)
+         VALUES ($1, encode(digest($2::text, 'sha256'), 'hex'), 'wk_test', NOW())",
+    )
+    .bind(instance_id)
+    .bind(&token)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert revoked worker token");
+
+    let (missing_status, missing_body) = heartbeat(state.clone(), instance_id, None).await;
+    let (revoked_status, revoked_body) = heartbeat(state.clone(), instance_id, Some(&token)).await;
+    let (unknown_status, unknown_body) = heartbeat(state, instance_id, Some("wk_not_issued")).await;
+
+    let _ = sqlx::query("DELETE FROM worker_auth_tokens WHERE instance_id = $1")
+        .bind(instance_id)
+        .execute(&pool)
+        .await;
+
+    assert_eq!(missing_status, StatusCode::UNAUTHORIZED);
+    assert_eq!(missing_body["reason"], "missing_token");
+
+    assert_eq!(revoked_status, StatusCode::UNAUTHORIZED);
+    assert_eq!(revoked_body["error"], "unauthorized");
+    assert_eq!(revoked_body["reason"], "revoked_token");
+
+    assert_eq!(unknown_status, StatusCode::UNAUTHORIZED);
+    assert_eq!(unknown_body["reason"], "unknown_token");
+}
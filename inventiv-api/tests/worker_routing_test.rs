@@ -0,0 +1,418 @@
+// Integration tests for OpenAI worker routing (instance selection + freshness)
+// IMPORTANT: All instances MUST use Mock provider only
+
+mod common;
+
+use axum::extract::{Path, Query, State};
+use axum::response::IntoResponse;
+use axum::Json;
+use common::{ensure_mock_provider, get_test_db_pool, get_test_redis_client};
+use inventiv_api::handlers::instances;
+use inventiv_api::handlers::monitoring::{self, RuntimeModelsParams};
+use inventiv_api::handlers::openai;
+use inventiv_api::worker_routing;
+use inventiv_api::AppState;
+use sqlx::{Pool, Postgres};
+use std::sync::atomic::{AtomicU8, Ordering};
+use uuid::Uuid;
+
+/// Insert a test model, optionally with a per-model stale window override.
+async fn insert_test_model(
+    pool: &Pool<Postgres>,
+    hf_model_id: &str,
+    stale_window_seconds: Option<i32>,
+) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, stale_window_seconds, created_at, updated_at)
+         VALUES (gen_random_uuid(), $1, $1, 1, 2048, true, $2, NOW(), NOW())
+         RETURNING id",
+    )
+    .bind(hf_model_id)
+    .bind(stale_window_seconds)
+    .fetch_one(pool)
+    .await
+    .expect("Failed to create test model")
+}
+
+/// Insert a READY mock instance serving `hf_model_id` whose last heartbeat is `heartbeat_age_s` old.
+///
+/// Each call gets its own IP: (ip_address, worker_vllm_port) is unique among active instances.
+async fn insert_ready_instance(
+    pool: &Pool<Postgres>,
+    provider_id: Uuid,
+    hf_model_id: &str,
+    heartbeat_age_s: i64,
+) -> Uuid {
+    static NEXT_HOST: AtomicU8 = AtomicU8::new(1);
+    let ip = format!("10.99.1.{}", NEXT_HOST.fetch_add(1, Ordering::Relaxed));
+    sqlx::query_scalar(
+        "INSERT INTO instances (id, provider_id, ip_address, status, gpu_profile, worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat, created_at)
+         VALUES (gen_random_uuid(), $1, $4::inet, 'ready', '{}'::jsonb, 'ready', $2, 8000, NOW() - ($3::bigint * INTERVAL '1 second'), NOW())
+         RETURNING id",
+    )
+    .bind(provider_id)
+    .bind(hf_model_id)
+    .bind(heartbeat_age_s)
+    .bind(ip)
+    .fetch_one(pool)
+    .await
+    .expect("Failed to create test instance")
+}
+
+async fn cleanup(pool: &Pool<Postgres>, instance_ids: &[Uuid], model_ids: &[Uuid]) {
+    for id in instance_ids {
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .await;
+    }
+    for id in model_ids {
+        let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .await;
+    }
+}
+
+#[tokio::test]
+async fn test_stale_window_override_per_model() {
+    let pool = get_test_db_pool().await;
+    let provider_id = ensure_mock_provider(&pool).await;
+
+    let suffix = Uuid::new_v4();
+    let slow_model = format!("test-slow-boot-{}", suffix);
+    let default_model = format!("test-default-{}", suffix);
+
+    let slow_model_uuid = insert_test_model(&pool, &slow_model, Some(3600)).await;
+    let default_model_uuid = insert_test_model(&pool, &default_model, None).await;
+
+    // Both workers last heartbeated 20 minutes ago (beyond the 5 minute global default).
+    let slow_instance = insert_ready_instance(&pool, provider_id, &slow_model, 20 * 60).await;
+    let default_instance = insert_ready_instance(&pool, provider_id, &default_model, 20 * 60).await;
+
+    let slow = worker_routing::select_ready_worker_for_model(&pool, &slow_model, None, None).await;
+    let default =
+        worker_routing::select_ready_worker_for_model(&pool, &default_model, None, None).await;
+
+    cleanup(
+        &pool,
+        &[slow_instance, default_instance],
+        &[slow_model_uuid, default_model_uuid],
+    )
+    .await;
+
+    assert_eq!(
+        slow.map(|(id, _)| id),
+        Some(slow_instance),
+        "Model with a long stale window override should stay routable"
+    );
+    assert!(
+        default.is_none(),
+        "Model using the global stale window should be dropped"
+    );
+}
+
+#[tokio::test]
+async fn test_stale_window_override_can_be_cleared() {
+    use axum::extract::{Path, State};
+    use axum::response::IntoResponse;
+    use axum::Json;
+    use inventiv_api::handlers::models;
+    use inventiv_api::AppState;
+
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(common::get_test_redis_client().await, pool.clone());
+    let hf_model = format!("test-clear-window-{}", Uuid::new_v4());
+    let model_uuid = insert_test_model(&pool, &hf_model, Some(3600)).await;
+
+    let update = |body: serde_json::Value| {
+        let state = state.clone();
+        async move {
+            let payload: models::UpdateModelRequest = serde_json::from_value(body).unwrap();
+            models::update_model(State(state), Path(model_uuid.to_string()), Json(payload))
+                .await
+                .into_response()
+                .status()
+        }
+    };
+    let window = || async {
+        sqlx::query_scalar::<_, Option<i32>>(
+            "SELECT stale_window_seconds FROM models WHERE id = $1",
+        )
+        .bind(model_uuid)
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+    };
+
+    // Omitting the field keeps the override; the clear flag drops it.
+    let kept = update(serde_json::json!({"name": hf_model})).await;
+    let after_kept = window().await;
+    let cleared = update(serde_json::json!({"clear_stale_window_seconds": true})).await;
+    let after_clear = window().await;
+
+    cleanup(&pool, &[], &[model_uuid]).await;
+
+    assert_eq!(kept, 200);
+    assert_eq!(after_kept, Some(3600));
+    assert_eq!(cleared, 200);
+    assert_eq!(after_clear, None);
+}
+
+#[tokio::test]
+async fn test_low_priority_deferred_at_soft_cap_while_high_proceeds() {
+    let pool = get_test_db_pool().await;
+    let redis = common::get_test_redis_client().await;
+    let provider_id = ensure_mock_provider(&pool).await;
+
+    let model = format!("test-priority-{}", Uuid::new_v4());
+    let model_uuid = insert_test_model(&pool, &model, None).await;
+    let instance = insert_ready_instance(&pool, provider_id, &model, 0).await;
+
+    // Two requests already in flight on the only worker, soft cap = 2.
+    let held = [
+        worker_routing::acquire_worker_slot(&redis, instance).await,
+        worker_routing::acquire_worker_slot(&redis, instance).await,
+    ];
+    let routing = |priority| worker_routing::PriorityRouting {
+        redis: &redis,
+        priority,
+        soft_cap: 2,
+    };
+    let low = worker_routing::select_ready_worker_for_model(
+        &pool,
+        &model,
+        None,
+        Some(&routing(worker_routing::RequestPriority::Low)),
+    )
+    .await;
+    let high = worker_routing::select_ready_worker_for_model(
+        &pool,
+        &model,
+        None,
+        Some(&routing(worker_routing::RequestPriority::High)),
+    )
+    .await;
+
+    // Once a slot is released, the low-priority request can proceed.
+    drop(held);
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    let low_after = worker_routing::select_ready_worker_for_model(
+        &pool,
+        &model,
+        None,
+        Some(&routing(worker_routing::RequestPriority::Low)),
+    )
+    .await;
+
+    cleanup(&pool, &[instance], &[model_uuid]).await;
+
+    assert!(
+        low.is_none(),
+        "low-priority request should be deferred at the soft cap"
+    );
+    assert_eq!(high.map(|(id, _)| id), Some(instance));
+    assert_eq!(low_after.map(|(id, _)| id), Some(instance));
+}
+
+#[tokio::test]
+async fn test_routing_prefers_registered_proxy_port() {
+    let pool = get_test_db_pool().await;
+    let provider_id = ensure_mock_provider(&pool).await;
+
+    let model = format!("test-proxy-port-{}", Uuid::new_v4());
+    let model_uuid = insert_test_model(&pool, &model, None).await;
+    let instance = insert_ready_instance(&pool, provider_id, &model, 0).await;
+    let ip: String = sqlx::query_scalar("SELECT host(ip_address) FROM instances WHERE id = $1")
+        .bind(instance)
+        .fetch_one(&pool)
+        .await
+        .expect("Failed to read instance ip");
+
+    let before = worker_routing::select_ready_worker_for_model(&pool, &model, None, None).await;
+
+    // What the orchestrator stores when the worker registers with a proxy (HAProxy) port.
+    sqlx::query("UPDATE instances SET worker_proxy_port = 8080 WHERE id = $1")
+        .bind(instance)
+        .execute(&pool)
+        .await
+        .expect("Failed to set worker proxy port");
+    let after = worker_routing::select_ready_worker_for_model(&pool, &model, None, None).await;
+
+    cleanup(&pool, &[instance], &[model_uuid]).await;
+
+    assert_eq!(
+        before.map(|(_, url)| url),
+        Some(format!("http://{}:8000", ip))
+    );
+    assert_eq!(
+        after,
+        Some((instance, format!("http://{}:8080", ip))),
+        "Routing should target the worker proxy port"
+    );
+}
+
+async fn set_worker_status(pool: &Pool<Postgres>, instance_id: Uuid, status: &str) {
+    sqlx::query("UPDATE instances SET worker_status = $2 WHERE id = $1")
+        .bind(instance_id)
+        .bind(status)
+        .execute(pool)
+        .await
+        .expect("Failed to update worker_status");
+}
+
+/// Routing, /v1/models and /runtime/models as seen for `hf_model_id`:
+/// (routable, listed in /v1/models, instances_available in /runtime/models).
+async fn routability_views(
+    state: &std::sync::Arc<AppState>,
+    hf_model_id: &str,
+) -> (bool, bool, i64) {
+    let routed =
+        worker_routing::select_ready_worker_for_model(&state.db, hf_model_id, None, None).await;
+
+    let listed = openai::openai_list_models(State(state.clone()), None)
+        .await
+        .into_response();
+    let listed = axum::body::to_bytes(listed.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let listed: serde_json::Value = serde_json::from_slice(&listed).unwrap();
+    let in_list = listed["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|m| m["id"] == hf_model_id);
+
+    let runtime = monitoring::list_runtime_models(
+        State(state.clone()),
+        Query(RuntimeModelsParams {
+            since: None,
+            until: None,
+        }),
+    )
+    .await
+    .into_response();
+    let runtime = axum::body::to_bytes(runtime.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let runtime: Vec<serde_json::Value> = serde_json::from_slice(&runtime).unwrap();
+    let available = runtime
+        .iter()
+        .find(|r| r["model_id"] == hf_model_id)
+        .and_then(|r| r["instances_available"].as_i64())
+        .unwrap_or(0);
+
+    (routed.is_some(), in_list, available)
+}
+
+#[tokio::test]
+async fn test_draining_and_starting_workers_excluded_until_ready_again() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+    let provider_id = ensure_mock_provider(&pool).await;
+
+    let hf_model_id = format!("test-draining-{}", Uuid::new_v4());
+    let model_uuid = insert_test_model(&pool, &hf_model_id, None).await;
+    // Own address: (ip, port) is unique among active instances and other tests run concurrently.
+    let instance_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO instances (id, provider_id, ip_address, status, gpu_profile, worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat, created_at)
+         VALUES (gen_random_uuid(), $1, '10.99.0.2', 'ready', '{}'::jsonb, 'ready', $2, 8000, NOW(), NOW())
+         RETURNING id",
+    )
+    .bind(provider_id)
+    .bind(&hf_model_id)
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to create test instance");
+    // Registers the model in runtime_models so /runtime/models reports it.
+    worker_routing::bump_runtime_model_counters(&pool, &hf_model_id, true).await;
+
+    let ready = routability_views(&state, &hf_model_id).await;
+    set_worker_status(&pool, instance_id, "draining").await;
+    let draining = routability_views(&state, &hf_model_id).await;
+    set_worker_status(&pool, instance_id, "starting").await;
+    let starting = routability_views(&state, &hf_model_id).await;
+    set_worker_status(&pool, instance_id, "ready").await;
+    let ready_again = routability_views(&state, &hf_model_id).await;
+
+    cleanup(&pool, &[instance_id], &[model_uuid]).await;
+    let _ = sqlx::query("DELETE FROM runtime_models WHERE model_id = $1")
+        .bind(&hf_model_id)
+        .execute(&pool)
+        .await;
+
+    assert_eq!(ready, (true, true, 1));
+    assert_eq!(draining, (false, false, 0));
+    assert_eq!(starting, (false, false, 0));
+    assert_eq!(ready_again, (true, true, 1));
+}
+
+async fn set_routing(state: &std::sync::Arc<AppState>, instance_id: Uuid, enabled: bool) {
+    let resp = instances::set_instance_routing(
+        State(state.clone()),
+        Path(instance_id),
+        Json(instances::InstanceRoutingRequest { enabled }),
+    )
+    .await
+    .into_response();
+    assert_eq!(resp.status(), axum::http::StatusCode::OK);
+}
+
+/// `routing_enabled` as reported by GET /instances/{id}.
+async fn reported_routing_enabled(state: &std::sync::Arc<AppState>, instance_id: Uuid) -> bool {
+    let resp = instances::get_instance(State(state.clone()), Path(instance_id))
+        .await
+        .into_response();
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let v: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    v["routing_enabled"].as_bool().unwrap()
+}
+
+#[tokio::test]
+async fn test_routing_toggle_pauses_and_resumes_selection() {
+    let pool = get_test_db_pool().await;
+    let state = AppState::new(get_test_redis_client().await, pool.clone());
+    let provider_id = ensure_mock_provider(&pool).await;
+
+    let hf_model_id = format!("test-routing-toggle-{}", Uuid::new_v4());
+    let model_uuid = insert_test_model(&pool, &hf_model_id, None).await;
+    let instance_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO instances (id, provider_id, ip_address, status, gpu_profile, worker_status, worker_model_id, worker_vllm_port, worker_last_heartbeat, created_at)
+         VALUES (gen_random_uuid(), $1, '10.99.0.3', 'ready', '{}'::jsonb, 'ready', $2, 8000, NOW(), NOW())
+         RETURNING id",
+    )
+    .bind(provider_id)
+    .bind(&hf_model_id)
+    .fetch_one(&pool)
+    .await
+    .expect("Failed to create test instance");
+
+    let enabled = routability_views(&state, &hf_model_id).await;
+    set_routing(&state, instance_id, false).await;
+    let paused = routability_views(&state, &hf_model_id).await;
+    let paused_reported = reported_routing_enabled(&state, instance_id).await;
+    let (status, worker_status): (String, Option<String>) =
+        sqlx::query_as("SELECT status::text, worker_status FROM instances WHERE id = $1")
+            .bind(instance_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    set_routing(&state, instance_id, true).await;
+    let resumed = routability_views(&state, &hf_model_id).await;
+    let resumed_reported = reported_routing_enabled(&state, instance_id).await;
+
+    cleanup(&pool, &[instance_id], &[model_uuid]).await;
+
+    assert!(enabled.0, "instance should be selectable before pausing");
+    assert!(!paused.0, "paused instance must be skipped for its model");
+    assert!(!paused_reported);
+    assert_eq!(status, "ready");
+    assert_eq!(worker_status.as_deref(), Some("ready"));
+    assert!(
+        resumed.0,
+        "instance should be selectable again once resumed"
+    );
+    assert!(resumed_reported);
+}
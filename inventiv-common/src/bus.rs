@@ -46,6 +46,10 @@ pub enum FinopsEventType {
     InstanceCostStart,
     #[serde(rename = "EVT:INSTANCE_COST_STOP")]
     InstanceCostStop,
+    #[serde(rename = "EVT:INSTANCE_RUNTIME_ALERT")]
+    InstanceRuntimeAlert,
+    #[serde(rename = "EVT:PRICE_CHANGED")]
+    PriceChanged,
 
     // Future-proof catalog (not fully wired yet):
     #[serde(rename = "EVT:TOKENS_CONSUMED")]
@@ -67,6 +71,8 @@ impl FinopsEventType {
         match self {
             FinopsEventType::InstanceCostStart => "EVT:INSTANCE_COST_START",
             FinopsEventType::InstanceCostStop => "EVT:INSTANCE_COST_STOP",
+            FinopsEventType::InstanceRuntimeAlert => "EVT:INSTANCE_RUNTIME_ALERT",
+            FinopsEventType::PriceChanged => "EVT:PRICE_CHANGED",
             FinopsEventType::TokensConsumed => "EVT:TOKENS_CONSUMED",
             FinopsEventType::CreditsAdded => "EVT:CREDITS_ADDED",
             FinopsEventType::CustomerActivated => "EVT:CUSTOMER_ACTIVATED",
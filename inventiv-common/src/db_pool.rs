@@ -0,0 +1,86 @@
+//! Postgres pool sizing shared by the api, orchestrator and finops binaries.
+//!
+//! `DATABASE_MAX_CONNECTIONS` (per-service default), `DATABASE_ACQUIRE_TIMEOUT_SECONDS` (default 10s:
+//! an exhausted pool fails the caller instead of hanging it) and `DATABASE_IDLE_TIMEOUT_SECONDS`
+//! (default 600s, 0 keeps idle connections open).
+
+use std::time::Duration;
+
+use sqlx::postgres::PgPoolOptions;
+
+const DEFAULT_ACQUIRE_TIMEOUT_SECONDS: u64 = 10;
+const DEFAULT_IDLE_TIMEOUT_SECONDS: u64 = 600;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolConfig {
+    pub max_connections: u32,
+    pub acquire_timeout: Duration,
+    /// None: idle connections are never closed.
+    pub idle_timeout: Option<Duration>,
+}
+
+impl PoolConfig {
+    /// Read the pool settings from the environment; `default_max_connections` is the service's
+    /// pool size when `DATABASE_MAX_CONNECTIONS` is unset or invalid.
+    pub fn from_env(default_max_connections: u32) -> Self {
+        Self::from_lookup(default_max_connections, |key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(default_max_connections: u32, lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let read = |key: &str| lookup(key).and_then(|v| v.trim().parse::<u64>().ok());
+        let max_connections = read("DATABASE_MAX_CONNECTIONS")
+            .and_then(|v| u32::try_from(v).ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(default_max_connections);
+        let acquire_timeout = read("DATABASE_ACQUIRE_TIMEOUT_SECONDS")
+            .filter(|v| *v > 0)
+            .unwrap_or(DEFAULT_ACQUIRE_TIMEOUT_SECONDS);
+        let idle_timeout =
+            read("DATABASE_IDLE_TIMEOUT_SECONDS").unwrap_or(DEFAULT_IDLE_TIMEOUT_SECONDS);
+        Self {
+            max_connections,
+            acquire_timeout: Duration::from_secs(acquire_timeout),
+            idle_timeout: (idle_timeout > 0).then(|| Duration::from_secs(idle_timeout)),
+        }
+    }
+
+    pub fn options(&self) -> PgPoolOptions {
+        PgPoolOptions::new()
+            .max_connections(self.max_connections)
+            .acquire_timeout(self.acquire_timeout)
+            .idle_timeout(self.idle_timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configured_values_are_applied_to_pool_options() {
+        let env = |key: &str| match key {
+            "DATABASE_MAX_CONNECTIONS" => Some("32".to_string()),
+            "DATABASE_ACQUIRE_TIMEOUT_SECONDS" => Some("3".to_string()),
+            "DATABASE_IDLE_TIMEOUT_SECONDS" => Some("0".to_string()),
+            _ => None,
+        };
+        let options = PoolConfig::from_lookup(20, env).options();
+        assert_eq!(options.get_max_connections(), 32);
+        assert_eq!(options.get_acquire_timeout(), Duration::from_secs(3));
+        assert_eq!(options.get_idle_timeout(), None);
+    }
+
+    #[test]
+    fn unset_or_invalid_values_use_defaults() {
+        let env = |key: &str| (key == "DATABASE_MAX_CONNECTIONS").then(|| "0".to_string());
+        let config = PoolConfig::from_lookup(20, env);
+        assert_eq!(
+            config,
+            PoolConfig {
+                max_connections: 20,
+                acquire_timeout: Duration::from_secs(10),
+                idle_timeout: Some(Duration::from_secs(600)),
+            }
+        );
+    }
+}
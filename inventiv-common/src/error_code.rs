@@ -0,0 +1,407 @@
+//! Stable failure codes written to `instances.error_code` and to action log metadata.
+//!
+//! The API (deployment validation) and the orchestrator (provisioning, health checks, termination)
+//! share this list so the UI and `GET /error_codes` can explain every code they may read back.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    // Deployment request validation (API).
+    DbError,
+    MissingParams,
+    MissingModel,
+    InvalidMaxRuntime,
+    InvalidCostCenter,
+    InvalidTtl,
+    InvalidProvider,
+    CatalogInconsistent,
+    InvalidZone,
+    InvalidInstanceType,
+    InvalidModel,
+    IncompatibleModelInstance,
+    ReuseVolumeNotFound,
+    ReuseVolumeInUse,
+    // Provisioning (orchestrator).
+    MissingZone,
+    MissingOrganizationId,
+    MissingProviderCredentials,
+    InactiveModel,
+    CatalogLookupFailed,
+    InstanceTypeNotAvailableInZone,
+    InstanceTypeNotSupported,
+    DisklessBootImageRequired,
+    DisklessBootImageResolveFailed,
+    LocalVolumeRemovalFailed,
+    VolumeAttachUnverified,
+    ProviderCreateFailed,
+    ProviderStartFailed,
+    ProviderStartTimeout,
+    ProviderVolumeAttachFailed,
+    ProviderOutOfStock,
+    // Classified provider API failures (`inventiv_providers::ProviderErrorCode`).
+    QuotaExceeded,
+    ImageNotFound,
+    InvalidVolume,
+    RateLimited,
+    NameConflict,
+    OutOfCapacity,
+    // Startup and runtime health.
+    SshNotAccessible,
+    StartupTimeout,
+    WaitingForWorkerHeartbeat,
+    HealthCheckFailed,
+    RecoveryTimeout,
+    // Termination.
+    VolumesDeletePending,
+    TerminatorRetryFailed,
+    TerminatorCheckFailed,
+}
+
+/// Language of the messages returned by `ErrorCode::message`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Fr,
+}
+
+impl Locale {
+    /// `fr`, `fr-FR`, ... select French; anything else falls back to English.
+    pub fn parse(raw: &str) -> Self {
+        let lang = raw.trim().to_ascii_lowercase();
+        if lang == "fr" || lang.starts_with("fr-") || lang.starts_with("fr_") {
+            Locale::Fr
+        } else {
+            Locale::En
+        }
+    }
+}
+
+impl ErrorCode {
+    pub const ALL: &'static [ErrorCode] = &[
+        ErrorCode::DbError,
+        ErrorCode::MissingParams,
+        ErrorCode::MissingModel,
+        ErrorCode::InvalidMaxRuntime,
+        ErrorCode::InvalidCostCenter,
+        ErrorCode::InvalidTtl,
+        ErrorCode::InvalidProvider,
+        ErrorCode::CatalogInconsistent,
+        ErrorCode::InvalidZone,
+        ErrorCode::InvalidInstanceType,
+        ErrorCode::InvalidModel,
+        ErrorCode::IncompatibleModelInstance,
+        ErrorCode::ReuseVolumeNotFound,
+        ErrorCode::ReuseVolumeInUse,
+        ErrorCode::MissingZone,
+        ErrorCode::MissingOrganizationId,
+        ErrorCode::MissingProviderCredentials,
+        ErrorCode::InactiveModel,
+        ErrorCode::CatalogLookupFailed,
+        ErrorCode::InstanceTypeNotAvailableInZone,
+        ErrorCode::InstanceTypeNotSupported,
+        ErrorCode::DisklessBootImageRequired,
+        ErrorCode::DisklessBootImageResolveFailed,
+        ErrorCode::LocalVolumeRemovalFailed,
+        ErrorCode::VolumeAttachUnverified,
+        ErrorCode::ProviderCreateFailed,
+        ErrorCode::ProviderStartFailed,
+        ErrorCode::ProviderStartTimeout,
+        ErrorCode::ProviderVolumeAttachFailed,
+        ErrorCode::ProviderOutOfStock,
+        ErrorCode::QuotaExceeded,
+        ErrorCode::ImageNotFound,
+        ErrorCode::InvalidVolume,
+        ErrorCode::RateLimited,
+        ErrorCode::NameConflict,
+        ErrorCode::OutOfCapacity,
+        ErrorCode::SshNotAccessible,
+        ErrorCode::StartupTimeout,
+        ErrorCode::WaitingForWorkerHeartbeat,
+        ErrorCode::HealthCheckFailed,
+        ErrorCode::RecoveryTimeout,
+        ErrorCode::VolumesDeletePending,
+        ErrorCode::TerminatorRetryFailed,
+        ErrorCode::TerminatorCheckFailed,
+    ];
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::DbError => "DB_ERROR",
+            ErrorCode::MissingParams => "MISSING_PARAMS",
+            ErrorCode::MissingModel => "MISSING_MODEL",
+            ErrorCode::InvalidMaxRuntime => "INVALID_MAX_RUNTIME",
+            ErrorCode::InvalidCostCenter => "INVALID_COST_CENTER",
+            ErrorCode::InvalidTtl => "INVALID_TTL",
+            ErrorCode::InvalidProvider => "INVALID_PROVIDER",
+            ErrorCode::CatalogInconsistent => "CATALOG_INCONSISTENT",
+            ErrorCode::InvalidZone => "INVALID_ZONE",
+            ErrorCode::InvalidInstanceType => "INVALID_INSTANCE_TYPE",
+            ErrorCode::InvalidModel => "INVALID_MODEL",
+            ErrorCode::IncompatibleModelInstance => "INCOMPATIBLE_MODEL_INSTANCE",
+            ErrorCode::ReuseVolumeNotFound => "REUSE_VOLUME_NOT_FOUND",
+            ErrorCode::ReuseVolumeInUse => "REUSE_VOLUME_IN_USE",
+            ErrorCode::MissingZone => "MISSING_ZONE",
+            ErrorCode::MissingOrganizationId => "MISSING_ORGANIZATION_ID",
+            ErrorCode::MissingProviderCredentials => "MISSING_PROVIDER_CREDENTIALS",
+            ErrorCode::InactiveModel => "INACTIVE_MODEL",
+            ErrorCode::CatalogLookupFailed => "CATALOG_LOOKUP_FAILED",
+            ErrorCode::InstanceTypeNotAvailableInZone => "INSTANCE_TYPE_NOT_AVAILABLE_IN_ZONE",
+            ErrorCode::InstanceTypeNotSupported => "INSTANCE_TYPE_NOT_SUPPORTED",
+            ErrorCode::DisklessBootImageRequired => "DISKLESS_BOOT_IMAGE_REQUIRED",
+            ErrorCode::DisklessBootImageResolveFailed => "DISKLESS_BOOT_IMAGE_RESOLVE_FAILED",
+            ErrorCode::LocalVolumeRemovalFailed => "LOCAL_VOLUME_REMOVAL_FAILED",
+            ErrorCode::VolumeAttachUnverified => "VOLUME_ATTACH_UNVERIFIED",
+            ErrorCode::ProviderCreateFailed => "PROVIDER_CREATE_FAILED",
+            ErrorCode::ProviderStartFailed => "PROVIDER_START_FAILED",
+            ErrorCode::ProviderStartTimeout => "PROVIDER_START_TIMEOUT",
+            ErrorCode::ProviderVolumeAttachFailed => "PROVIDER_VOLUME_ATTACH_FAILED",
+            ErrorCode::ProviderOutOfStock => "PROVIDER_OUT_OF_STOCK",
+            ErrorCode::QuotaExceeded => "QUOTA_EXCEEDED",
+            ErrorCode::ImageNotFound => "IMAGE_NOT_FOUND",
+            ErrorCode::InvalidVolume => "INVALID_VOLUME",
+            ErrorCode::RateLimited => "RATE_LIMITED",
+            ErrorCode::NameConflict => "NAME_CONFLICT",
+            ErrorCode::OutOfCapacity => "OUT_OF_CAPACITY",
+            ErrorCode::SshNotAccessible => "SSH_NOT_ACCESSIBLE",
+            ErrorCode::StartupTimeout => "STARTUP_TIMEOUT",
+            ErrorCode::WaitingForWorkerHeartbeat => "WAITING_FOR_WORKER_HEARTBEAT",
+            ErrorCode::HealthCheckFailed => "HEALTH_CHECK_FAILED",
+            ErrorCode::RecoveryTimeout => "RECOVERY_TIMEOUT",
+            ErrorCode::VolumesDeletePending => "VOLUMES_DELETE_PENDING",
+            ErrorCode::TerminatorRetryFailed => "TERMINATOR_RETRY_FAILED",
+            ErrorCode::TerminatorCheckFailed => "TERMINATOR_CHECK_FAILED",
+        }
+    }
+
+    /// Exact (case-sensitive) parse of a stored code; returns None for unknown values.
+    pub fn parse(raw: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|c| c.as_str() == raw.trim())
+    }
+
+    /// Short user-facing explanation of the code.
+    pub fn message(&self, locale: Locale) -> &'static str {
+        let (en, fr) = match self {
+            ErrorCode::DbError => (
+                "Database error while processing the request.",
+                "Erreur de base de données pendant le traitement de la requête.",
+            ),
+            ErrorCode::MissingParams => (
+                "Zone or instance type is missing.",
+                "La zone ou le type d'instance est manquant.",
+            ),
+            ErrorCode::MissingModel => (
+                "No model was selected for the instance.",
+                "Aucun modèle n'a été sélectionné pour l'instance.",
+            ),
+            ErrorCode::InvalidMaxRuntime => (
+                "The maximum runtime is not a valid number of hours.",
+                "La durée maximale d'exécution n'est pas un nombre d'heures valide.",
+            ),
+            ErrorCode::InvalidCostCenter => (
+                "The cost center is not valid.",
+                "Le centre de coût n'est pas valide.",
+            ),
+            ErrorCode::InvalidTtl => (
+                "The time to live is not a valid number of minutes.",
+                "La durée de vie n'est pas un nombre de minutes valide.",
+            ),
+            ErrorCode::InvalidProvider => (
+                "The provider is unknown or inactive.",
+                "Le fournisseur est inconnu ou inactif.",
+            ),
+            ErrorCode::CatalogInconsistent => (
+                "The provider catalog is inconsistent; contact an administrator.",
+                "Le catalogue du fournisseur est incohérent ; contactez un administrateur.",
+            ),
+            ErrorCode::InvalidZone => (
+                "The zone is unknown or inactive for this provider.",
+                "La zone est inconnue ou inactive pour ce fournisseur.",
+            ),
+            ErrorCode::InvalidInstanceType => (
+                "The instance type is unknown, inactive or unavailable in this zone.",
+                "Le type d'instance est inconnu, inactif ou indisponible dans cette zone.",
+            ),
+            ErrorCode::InvalidModel => (
+                "The model is unknown or inactive.",
+                "Le modèle est inconnu ou inactif.",
+            ),
+            ErrorCode::IncompatibleModelInstance => (
+                "The model does not fit on this instance type.",
+                "Le modèle ne tient pas sur ce type d'instance.",
+            ),
+            ErrorCode::ReuseVolumeNotFound => (
+                "The data volume to reuse does not exist in this zone.",
+                "Le volume de données à réutiliser n'existe pas dans cette zone.",
+            ),
+            ErrorCode::ReuseVolumeInUse => (
+                "The data volume to reuse is still attached to another instance.",
+                "Le volume de données à réutiliser est encore attaché à une autre instance.",
+            ),
+            ErrorCode::MissingZone => ("The instance has no zone.", "L'instance n'a pas de zone."),
+            ErrorCode::MissingOrganizationId => (
+                "The instance does not belong to an organization.",
+                "L'instance n'appartient à aucune organisation.",
+            ),
+            ErrorCode::MissingProviderCredentials => (
+                "The organization has no credentials configured for this provider.",
+                "L'organisation n'a pas d'identifiants configurés pour ce fournisseur.",
+            ),
+            ErrorCode::InactiveModel => (
+                "The model was deactivated before the instance was provisioned.",
+                "Le modèle a été désactivé avant le provisionnement de l'instance.",
+            ),
+            ErrorCode::CatalogLookupFailed => (
+                "The instance type or zone could not be looked up in the catalog.",
+                "Le type d'instance ou la zone n'a pas pu être trouvé dans le catalogue.",
+            ),
+            ErrorCode::InstanceTypeNotAvailableInZone => (
+                "The instance type is not available in this zone.",
+                "Le type d'instance n'est pas disponible dans cette zone.",
+            ),
+            ErrorCode::InstanceTypeNotSupported => (
+                "The instance type is not supported for workers.",
+                "Le type d'instance n'est pas pris en charge pour les workers.",
+            ),
+            ErrorCode::DisklessBootImageRequired => (
+                "This instance type needs a boot image and none is configured.",
+                "Ce type d'instance nécessite une image de démarrage et aucune n'est configurée.",
+            ),
+            ErrorCode::DisklessBootImageResolveFailed => (
+                "The boot image could not be resolved at the provider.",
+                "L'image de démarrage n'a pas pu être résolue chez le fournisseur.",
+            ),
+            ErrorCode::LocalVolumeRemovalFailed => (
+                "Local volumes could not be removed before booting.",
+                "Les volumes locaux n'ont pas pu être retirés avant le démarrage.",
+            ),
+            ErrorCode::VolumeAttachUnverified => (
+                "The data volume could not be verified as attached.",
+                "L'attachement du volume de données n'a pas pu être vérifié.",
+            ),
+            ErrorCode::ProviderCreateFailed => (
+                "The provider failed to create the server.",
+                "Le fournisseur n'a pas pu créer le serveur.",
+            ),
+            ErrorCode::ProviderStartFailed => (
+                "The provider failed to start the server.",
+                "Le fournisseur n'a pas pu démarrer le serveur.",
+            ),
+            ErrorCode::ProviderStartTimeout => (
+                "The server did not reach the running state in time.",
+                "Le serveur n'a pas atteint l'état démarré à temps.",
+            ),
+            ErrorCode::ProviderVolumeAttachFailed => (
+                "The provider failed to attach the data volume.",
+                "Le fournisseur n'a pas pu attacher le volume de données.",
+            ),
+            ErrorCode::ProviderOutOfStock => (
+                "The provider has no capacity left for this instance type.",
+                "Le fournisseur n'a plus de capacité pour ce type d'instance.",
+            ),
+            ErrorCode::QuotaExceeded => (
+                "The provider quota is exceeded.",
+                "Le quota du fournisseur est dépassé.",
+            ),
+            ErrorCode::ImageNotFound => (
+                "The provider could not find the boot image.",
+                "Le fournisseur n'a pas trouvé l'image de démarrage.",
+            ),
+            ErrorCode::InvalidVolume => (
+                "The provider rejected the volume configuration.",
+                "Le fournisseur a rejeté la configuration des volumes.",
+            ),
+            ErrorCode::RateLimited => (
+                "The provider rate-limited the request.",
+                "Le fournisseur a limité le débit des requêtes.",
+            ),
+            ErrorCode::NameConflict => (
+                "A provider resource with the same name already exists.",
+                "Une ressource du même nom existe déjà chez le fournisseur.",
+            ),
+            ErrorCode::OutOfCapacity => (
+                "The provider is out of capacity in this zone.",
+                "Le fournisseur n'a plus de capacité dans cette zone.",
+            ),
+            ErrorCode::SshNotAccessible => (
+                "The server never became reachable over SSH.",
+                "Le serveur n'est jamais devenu accessible en SSH.",
+            ),
+            ErrorCode::StartupTimeout => (
+                "The worker did not become healthy within the startup timeout.",
+                "Le worker n'est pas devenu opérationnel dans le délai de démarrage.",
+            ),
+            ErrorCode::WaitingForWorkerHeartbeat => (
+                "Waiting for the first worker heartbeat.",
+                "En attente du premier heartbeat du worker.",
+            ),
+            ErrorCode::HealthCheckFailed => (
+                "The worker failed its health checks.",
+                "Le worker a échoué à ses contrôles de santé.",
+            ),
+            ErrorCode::RecoveryTimeout => (
+                "The instance did not recover in time.",
+                "L'instance n'a pas récupéré à temps.",
+            ),
+            ErrorCode::VolumesDeletePending => (
+                "The server is gone but some volumes are still being deleted.",
+                "Le serveur est supprimé mais des volumes sont encore en cours de suppression.",
+            ),
+            ErrorCode::TerminatorRetryFailed => (
+                "Terminating the server at the provider failed; it will be retried.",
+                "La suppression du serveur chez le fournisseur a échoué ; elle sera retentée.",
+            ),
+            ErrorCode::TerminatorCheckFailed => (
+                "The server's deletion could not be confirmed at the provider.",
+                "La suppression du serveur n'a pas pu être confirmée chez le fournisseur.",
+            ),
+        };
+        match locale {
+            Locale::En => en,
+            Locale::Fr => fr,
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ErrorCode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ErrorCode::parse(s).ok_or_else(|| format!("unknown error code: {}", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_code_round_trips_through_parse_and_serde() {
+        for code in ErrorCode::ALL {
+            assert_eq!(ErrorCode::parse(code.as_str()), Some(*code));
+            assert_eq!(
+                serde_json::to_value(code).unwrap(),
+                serde_json::json!(code.as_str())
+            );
+        }
+        assert_eq!(ErrorCode::parse("NOT_A_CODE"), None);
+    }
+
+    #[test]
+    fn locale_defaults_to_english() {
+        assert_eq!(Locale::parse("fr-FR"), Locale::Fr);
+        assert_eq!(Locale::parse("de"), Locale::En);
+        assert_eq!(
+            ErrorCode::MissingZone.message(Locale::Fr),
+            "L'instance n'a pas de zone."
+        );
+    }
+}
@@ -3,6 +3,13 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 pub mod bus;
+pub mod cloud_init;
+pub mod db_pool;
+pub mod error_code;
+pub mod net;
+pub mod pubsub;
+pub mod utc_buckets;
+pub mod worker_auth;
 pub mod worker_storage;
 pub mod worker_target;
 
@@ -11,19 +18,91 @@ pub mod worker_target;
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, sqlx::Type, utoipa::ToSchema)]
 #[sqlx(type_name = "instance_status", rename_all = "snake_case")]
 pub enum InstanceStatus {
+    Accepted,     // Deployment queued, orchestrator has not picked it up yet
     Provisioning, // Request sent to provider
     Booting,      // Instance en cours de création, pas encore démarrée
     Installing,   // Instance up, mais Worker en cours d'installation
     Starting, // Instance up et running, mais Worker encore en finalisation (download de model, warming, etc.)
     Ready,    // Healthy and serving traffic
+    /// Power-cycled onto another instance type; not routable until back to `ready`.
+    Resizing,
     Draining, // Stopping, finishing current requests
     Terminating, // Termination requested, waiting provider deletion
     Terminated, // Destroyed
     Archived, // Archived (hidden from active list)
     Unavailable, // Instance inaccessible ou indisponible, à reconnecter et diagnostiquer pour repasser en Ready ou à décommissioner
+    /// Provider never delivered a running server (create/start failed). Set only from `provisioning`.
     ProvisioningFailed,
+    /// Server created but the worker never became healthy (SSH, install, health, timeout).
     StartupFailed,
-    Failed, // Error state
+    Failed,  // Error state
+    Stopped, // Compute stopped at the provider (disks kept), not billed for compute
+}
+
+/// Instance statuses that accrue no compute cost (excluded from FinOps allocation and proration).
+/// Storage attached to `stopped` instances would have to be priced separately.
+pub const NON_BILLABLE_COMPUTE_STATUSES: &[&str] = &[
+    "accepted",
+    "terminated",
+    "failed",
+    "provisioning_failed",
+    "startup_failed",
+    "archived",
+    "stopped",
+];
+
+/// Stage at which a failed instance stopped: `provisioning` (no running server was ever
+/// delivered), `startup` (server up, worker never healthy) or `runtime`. None when not failed.
+pub fn failure_stage(status: &str) -> Option<&'static str> {
+    match status {
+        "provisioning_failed" => Some("provisioning"),
+        "startup_failed" => Some("startup"),
+        "failed" => Some("runtime"),
+        _ => None,
+    }
+}
+
+/// Worker agent lifecycle as reported by heartbeats (stored as text in `instances.worker_status`).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkerStatus {
+    Starting, // Agent up, vLLM still loading/warming
+    Ready,    // vLLM serving, routable
+    Draining, // Finishing in-flight requests, not routable
+}
+
+impl WorkerStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkerStatus::Starting => "starting",
+            WorkerStatus::Ready => "ready",
+            WorkerStatus::Draining => "draining",
+        }
+    }
+
+    /// Case-insensitive parse; returns None for unknown values.
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "starting" => Some(WorkerStatus::Starting),
+            "ready" => Some(WorkerStatus::Ready),
+            "draining" => Some(WorkerStatus::Draining),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for WorkerStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for WorkerStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        WorkerStatus::parse(s).ok_or_else(|| format!("unknown worker status: {}", s))
+    }
 }
 
 // --- Entities (SQLx Mapped) ---
@@ -95,6 +174,33 @@ pub struct LlmModel {
     pub is_active: bool,
     #[sqlx(default)]
     pub data_volume_gb: Option<i64>,
+    /// Per-model worker freshness window (seconds). NULL = global OPENAI_WORKER_STALE_SECONDS.
+    #[sqlx(default)]
+    pub stale_window_seconds: Option<i32>,
+    /// Provider boot image override for this model. NULL = instance-type/provider default.
+    #[sqlx(default)]
+    pub boot_image_id: Option<String>,
+    /// Set when the model is deprecated (still served, but clients are warned).
+    #[sqlx(default)]
+    pub deprecated_at: Option<DateTime<Utc>>,
+    /// Suggested replacement for a deprecated model (models.id).
+    #[sqlx(default)]
+    pub replacement_model_id: Option<Uuid>,
+    /// Served instead when this model has no READY worker and the request opted in (models.id).
+    #[sqlx(default)]
+    pub fallback_model_id: Option<Uuid>,
+    /// Listed in /v1/models for API-key callers. Non-public models stay callable by scoped keys.
+    pub public: bool,
+    /// Generation defaults (e.g. temperature, max_tokens) applied to chat/completions requests
+    /// that omit them. NULL = requests are forwarded unchanged.
+    #[sqlx(default)]
+    pub default_params: Option<serde_json::Value>,
+    /// Warm pool: live instances the orchestrator keeps provisioned for this model (0 = none).
+    #[sqlx(default)]
+    pub min_instances: i32,
+    /// Cap on live instances for this model. NULL = unlimited.
+    #[sqlx(default)]
+    pub max_instances: Option<i32>,
     #[sqlx(default)]
     #[serde(skip)]
     pub metadata: sqlx::types::Json<serde_json::Value>,
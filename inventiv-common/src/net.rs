@@ -0,0 +1,136 @@
+/// Instance IP helpers shared across API/Orchestrator.
+///
+/// `instances.ip_address` is a Postgres INET: `ip_address::text` may come back with a CIDR
+/// suffix (e.g. `1.2.3.4/32`). Anything that turns an instance IP into a URL or socket address
+/// must go through these helpers instead of formatting the raw value.
+use std::net::{IpAddr, SocketAddr};
+
+/// Strip an optional CIDR suffix and return the address if it is a usable host address.
+///
+/// Returns None for empty/unparseable values, malformed prefixes, and addresses that cannot
+/// be connected to (unspecified, multicast, IPv4 broadcast).
+pub fn normalize_instance_ip(raw: &str) -> Option<IpAddr> {
+    let raw = raw.trim();
+    let (addr, prefix) = match raw.split_once('/') {
+        Some((a, p)) => (a.trim(), Some(p.trim())),
+        None => (raw, None),
+    };
+
+    let ip: IpAddr = addr.parse().ok()?;
+    if let Some(p) = prefix {
+        let bits: u8 = p.parse().ok()?;
+        let max = if ip.is_ipv4() { 32 } else { 128 };
+        if bits > max {
+            return None;
+        }
+    }
+
+    let unusable = ip.is_unspecified()
+        || ip.is_multicast()
+        || matches!(ip, IpAddr::V4(v4) if v4.is_broadcast());
+    if unusable {
+        return None;
+    }
+    Some(ip)
+}
+
+/// `host:port` socket address for an instance IP (IPv6-safe).
+pub fn instance_socket_addr(raw_ip: &str, port: u16) -> Option<SocketAddr> {
+    normalize_instance_ip(raw_ip).map(|ip| SocketAddr::new(ip, port))
+}
+
+/// `http://host:port` base URL for an instance IP (IPv6 addresses are bracketed).
+pub fn instance_http_base_url(raw_ip: &str, port: u16) -> Option<String> {
+    instance_socket_addr(raw_ip, port).map(|addr| format!("http://{}", addr))
+}
+
+/// Whether `ip` falls inside `cidr` (e.g. `100.64.0.0/10`, `fd00::/8`; a bare address only
+/// matches itself). Malformed CIDRs and mixed address families never match.
+pub fn cidr_contains(cidr: &str, ip: IpAddr) -> bool {
+    let cidr = cidr.trim();
+    let (addr, bits) = match cidr.split_once('/') {
+        Some((a, p)) => match p.trim().parse::<u32>() {
+            Ok(b) => (a.trim(), Some(b)),
+            Err(_) => return false,
+        },
+        None => (cidr, None),
+    };
+    let Ok(net) = addr.parse::<IpAddr>() else {
+        return false;
+    };
+    // IPv4-mapped IPv6 callers (::ffff:a.b.c.d) are compared as IPv4.
+    let (net, width, ip) = match (net, ip.to_canonical()) {
+        (IpAddr::V4(n), IpAddr::V4(i)) => (u32::from(n) as u128, 32, u32::from(i) as u128),
+        (IpAddr::V6(n), IpAddr::V6(i)) => (u128::from(n), 128, u128::from(i)),
+        _ => return false,
+    };
+    let bits = bits.unwrap_or(width);
+    if bits > width {
+        return false;
+    }
+    bits == 0 || (net ^ ip) >> (width - bits) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_ipv4_is_accepted() {
+        assert_eq!(
+            normalize_instance_ip("1.2.3.4"),
+            Some("1.2.3.4".parse().unwrap())
+        );
+        assert_eq!(
+            instance_http_base_url("1.2.3.4", 8000).as_deref(),
+            Some("http://1.2.3.4:8000")
+        );
+    }
+
+    #[test]
+    fn cidr_suffix_is_stripped() {
+        assert_eq!(
+            normalize_instance_ip(" 1.2.3.4/32 "),
+            Some("1.2.3.4".parse().unwrap())
+        );
+        assert_eq!(
+            instance_http_base_url("1.2.3.4/32", 8000).as_deref(),
+            Some("http://1.2.3.4:8000")
+        );
+        assert_eq!(
+            instance_http_base_url("fd00::1/128", 8000).as_deref(),
+            Some("http://[fd00::1]:8000")
+        );
+    }
+
+    #[test]
+    fn invalid_values_are_rejected() {
+        for raw in [
+            "",
+            "not-an-ip",
+            "1.2.3.4/33",
+            "1.2.3.4/abc",
+            "0.0.0.0",
+            "255.255.255.255",
+            "224.0.0.1",
+        ] {
+            assert_eq!(normalize_instance_ip(raw), None, "{raw}");
+            assert_eq!(instance_http_base_url(raw, 8000), None, "{raw}");
+        }
+    }
+
+    #[test]
+    fn cidr_contains_matches_prefix() {
+        let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+        assert!(cidr_contains("100.64.0.0/10", ip("100.100.1.2")));
+        assert!(!cidr_contains("100.64.0.0/10", ip("100.128.0.1")));
+        assert!(cidr_contains(" 10.1.2.3 ", ip("10.1.2.3")));
+        assert!(!cidr_contains("10.1.2.3", ip("10.1.2.4")));
+        assert!(cidr_contains("0.0.0.0/0", ip("8.8.8.8")));
+        assert!(cidr_contains("fd00::/8", ip("fd12::1")));
+        assert!(cidr_contains("10.0.0.0/8", ip("::ffff:10.9.8.7")));
+        for bad in ["10.0.0.0/33", "10.0.0.0/x", "nope", "", "fd00::/8"] {
+            assert!(!cidr_contains(bad, ip("10.0.0.1")), "{bad}");
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use futures_util::StreamExt;
+use std::future::Future;
+use std::time::Duration;
+
+// -----------------------------------------------------------------------------
+// Redis pub/sub consumers that survive connection drops
+// -----------------------------------------------------------------------------
+
+/// Exponential reconnect delay: doubles from `initial` up to `max`, reset once subscribed again.
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    initial: Duration,
+    max: Duration,
+    current: Duration,
+}
+
+impl Backoff {
+    pub fn new(initial: Duration, max: Duration) -> Self {
+        Self {
+            initial,
+            max,
+            current: initial,
+        }
+    }
+
+    /// Delay before the next attempt (the following one will be twice as long, up to `max`).
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = self.current;
+        self.current = (self.current * 2).min(self.max);
+        delay
+    }
+
+    pub fn reset(&mut self) {
+        self.current = self.initial;
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(500), Duration::from_secs(30))
+    }
+}
+
+/// Subscribe to `channel` and hand every payload to `on_message`, forever.
+///
+/// When the connection cannot be established or drops (the message stream ends), the consumer
+/// re-subscribes after a backoff instead of silently stopping. Messages published while
+/// disconnected are lost (pub/sub has no replay); the periodic jobs cover those.
+pub async fn consume_channel<F, Fut>(
+    client: &redis::Client,
+    channel: &str,
+    mut backoff: Backoff,
+    mut on_message: F,
+) where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut subscribed_once = false;
+    loop {
+        match subscribe(client, channel).await {
+            Ok(mut pubsub) => {
+                if subscribed_once {
+                    tracing::warn!("Redis reconnected, resubscribed to '{}'", channel);
+                } else {
+                    tracing::info!("Listening on Redis channel '{}'...", channel);
+                }
+                subscribed_once = true;
+                backoff.reset();
+
+                let mut stream = pubsub.on_message();
+                while let Some(msg) = stream.next().await {
+                    match msg.get_payload::<String>() {
+                        Ok(payload) => on_message(payload).await,
+                        Err(e) => {
+                            tracing::warn!("Ignoring non-text message on '{}': {}", channel, e)
+                        }
+                    }
+                }
+                tracing::warn!("Redis subscription to '{}' lost", channel);
+            }
+            Err(e) => tracing::warn!("Redis subscribe to '{}' failed: {}", channel, e),
+        }
+        let delay = backoff.next_delay();
+        tracing::warn!("Reconnecting to Redis channel '{}' in {:?}", channel, delay);
+        tokio::time::sleep(delay).await;
+    }
+}
+
+async fn subscribe(
+    client: &redis::Client,
+    channel: &str,
+) -> redis::RedisResult<redis::aio::PubSub> {
+    let mut pubsub = client.get_async_pubsub().await?;
+    pubsub.subscribe(channel).await?;
+    Ok(pubsub)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_max_and_resets() {
+        let mut b = Backoff::new(Duration::from_millis(100), Duration::from_millis(350));
+        assert_eq!(b.next_delay(), Duration::from_millis(100));
+        assert_eq!(b.next_delay(), Duration::from_millis(200));
+        assert_eq!(b.next_delay(), Duration::from_millis(350));
+        assert_eq!(b.next_delay(), Duration::from_millis(350));
+        b.reset();
+        assert_eq!(b.next_delay(), Duration::from_millis(100));
+    }
+
+    /// Publish until someone is subscribed and `rx` sees `payload` (or give up after 10s).
+    async fn publish_until_received(
+        conn: &mut redis::aio::MultiplexedConnection,
+        channel: &str,
+        payload: &str,
+        rx: &mut tokio::sync::mpsc::UnboundedReceiver<String>,
+    ) -> bool {
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+        while tokio::time::Instant::now() < deadline {
+            let _: i64 = redis::cmd("PUBLISH")
+                .arg(channel)
+                .arg(payload)
+                .query_async(conn)
+                .await
+                .unwrap_or(0);
+            if let Ok(Some(got)) = tokio::time::timeout(Duration::from_millis(100), rx.recv()).await
+            {
+                if got == payload {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// IDs of the pub/sub connections currently open on the server (`CLIENT LIST TYPE pubsub`).
+    async fn pubsub_client_ids(conn: &mut redis::aio::MultiplexedConnection) -> Vec<i64> {
+        let list: String = redis::cmd("CLIENT")
+            .arg("LIST")
+            .arg("TYPE")
+            .arg("pubsub")
+            .query_async(conn)
+            .await
+            .unwrap();
+        list.lines()
+            .filter_map(|line| {
+                line.split(' ')
+                    .find_map(|field| field.strip_prefix("id="))
+                    .and_then(|id| id.parse().ok())
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn consumer_resubscribes_after_connection_is_killed() {
+        let Some(url) = std::env::var("REDIS_URL")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+        else {
+            eprintln!("skipping integration test: REDIS_URL not set");
+            return;
+        };
+        let client = redis::Client::open(url).unwrap();
+        let channel = format!("test_pubsub_{}", uuid::Uuid::new_v4().simple());
+        let mut conn = client.get_multiplexed_async_connection().await.unwrap();
+        let existing = pubsub_client_ids(&mut conn).await;
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let consumer = {
+            let client = client.clone();
+            let channel = channel.clone();
+            tokio::spawn(async move {
+                let backoff = Backoff::new(Duration::from_millis(50), Duration::from_millis(200));
+                consume_channel(&client, &channel, backoff, |payload| {
+                    let _ = tx.send(payload);
+                    std::future::ready(())
+                })
+                .await;
+            })
+        };
+
+        let before = publish_until_received(&mut conn, &channel, "before", &mut rx).await;
+
+        // Drop the consumer's connection server-side: the one pub/sub client that appeared since
+        // the consumer was spawned. Other clients of a shared Redis are left alone.
+        let ours: Vec<i64> = pubsub_client_ids(&mut conn)
+            .await
+            .into_iter()
+            .filter(|id| !existing.contains(id))
+            .collect();
+        let [consumer_id] = ours[..] else {
+            consumer.abort();
+            eprintln!(
+                "skipping integration test: cannot tell the consumer's connection apart ({} new pub/sub clients)",
+                ours.len()
+            );
+            return;
+        };
+        let killed: i64 = redis::cmd("CLIENT")
+            .arg("KILL")
+            .arg("ID")
+            .arg(consumer_id)
+            .query_async(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(killed, 1, "consumer connection was not killed");
+
+        let after = publish_until_received(&mut conn, &channel, "after", &mut rx).await;
+        consumer.abort();
+
+        assert!(before, "consumer never received the first message");
+        assert!(
+            after,
+            "consumer did not resubscribe after the connection was killed"
+        );
+    }
+}
@@ -0,0 +1,46 @@
+/// UTC time bucketing shared by FinOps (API dashboards and the finops calculator).
+///
+/// Buckets are always floored on the UTC timeline, whatever offset the input carries, so rollups
+/// never shift or collide around DST changes. SQL-side binning must match: `date_bin(.., 'epoch')`
+/// or `date_trunc(.., 'UTC')`, never a session-timezone `date_trunc`.
+use chrono::{DateTime, DurationRound, TimeDelta, TimeZone, Utc};
+
+/// Floor `t` to the start of its UTC minute.
+pub fn minute_bucket<Tz: TimeZone>(t: &DateTime<Tz>) -> DateTime<Utc> {
+    let utc = t.with_timezone(&Utc);
+    utc.duration_trunc(TimeDelta::minutes(1)).unwrap_or(utc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn minute_bucket_floors_seconds_and_nanos() {
+        let t = utc("2026-01-10T12:34:56.789Z");
+        assert_eq!(minute_bucket(&t), utc("2026-01-10T12:34:00Z"));
+        assert_eq!(
+            minute_bucket(&minute_bucket(&t)),
+            utc("2026-01-10T12:34:00Z")
+        );
+    }
+
+    #[test]
+    fn minute_bucket_near_dst_boundary_is_utc() {
+        // Europe/Paris springs forward at 2026-03-29T01:00Z (02:00 CET -> 03:00 CEST).
+        let before = DateTime::parse_from_rfc3339("2026-03-29T01:59:30+01:00").unwrap();
+        let after = DateTime::parse_from_rfc3339("2026-03-29T03:00:30+02:00").unwrap();
+        assert_eq!(minute_bucket(&before), utc("2026-03-29T00:59:00Z"));
+        assert_eq!(minute_bucket(&after), utc("2026-03-29T01:00:00Z"));
+
+        // Falls back at 2026-10-25T01:00Z: the repeated local 02:30 maps to two UTC minutes.
+        let first = DateTime::parse_from_rfc3339("2026-10-25T02:30:10+02:00").unwrap();
+        let second = DateTime::parse_from_rfc3339("2026-10-25T02:30:10+01:00").unwrap();
+        assert_eq!(minute_bucket(&first), utc("2026-10-25T00:30:00Z"));
+        assert_eq!(minute_bucket(&second), utc("2026-10-25T01:30:00Z"));
+    }
+}
@@ -0,0 +1,113 @@
+/// Worker token authentication shared across API/Orchestrator.
+///
+/// Both sides verify `Authorization: Bearer <token>` against `worker_auth_tokens` (hash only,
+/// via pgcrypto). The outcome is typed so callers can tell a missing header from a revoked
+/// token: every failure is a 401, with a distinct `reason` in the body.
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkerAuthResult {
+    Ok,
+    /// No Authorization header.
+    Missing,
+    /// Authorization header present but not `Bearer <token>`.
+    Malformed,
+    /// Token matches but was revoked.
+    Revoked,
+    /// No token on record for this instance, or the token does not match.
+    Unknown,
+}
+
+impl WorkerAuthResult {
+    pub fn is_ok(self) -> bool {
+        self == WorkerAuthResult::Ok
+    }
+
+    /// Stable `reason` value returned in error bodies.
+    pub fn reason(self) -> &'static str {
+        match self {
+            WorkerAuthResult::Ok => "ok",
+            WorkerAuthResult::Missing => "missing_token",
+            WorkerAuthResult::Malformed => "malformed_token",
+            WorkerAuthResult::Revoked => "revoked_token",
+            WorkerAuthResult::Unknown => "unknown_token",
+        }
+    }
+}
+
+/// Extract the bearer token from a raw Authorization header value.
+pub fn parse_bearer(header: Option<&str>) -> Result<String, WorkerAuthResult> {
+    let Some(raw) = header else {
+        return Err(WorkerAuthResult::Missing);
+    };
+    let token = raw
+        .trim()
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .ok_or(WorkerAuthResult::Malformed)?;
+    Ok(token.to_string())
+}
+
+/// Check `token` against the instance's row in `worker_auth_tokens`.
+/// DB errors are reported as `Unknown` (fail closed). Callers own any `last_seen_at` bookkeeping.
+pub async fn check_worker_token(
+    db: &Pool<Postgres>,
+    instance_id: Uuid,
+    token: &str,
+) -> WorkerAuthResult {
+    // Compare hash in DB using pgcrypto digest; avoids adding crypto deps in Rust.
+    let row: Option<(bool, bool)> = sqlx::query_as(
+        r#"
+        SELECT token_hash = encode(digest($2::text, 'sha256'), 'hex'),
+               revoked_at IS NOT NULL
+        FROM worker_auth_tokens
+        WHERE instance_id = $1
+        "#,
+    )
+    .bind(instance_id)
+    .bind(token)
+    .fetch_optional(db)
+    .await
+    .unwrap_or(None);
+
+    match row {
+        Some((true, true)) => WorkerAuthResult::Revoked,
+        Some((true, false)) => WorkerAuthResult::Ok,
+        _ => WorkerAuthResult::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bearer_parsing_distinguishes_missing_and_malformed() {
+        assert_eq!(parse_bearer(None), Err(WorkerAuthResult::Missing));
+        assert_eq!(
+            parse_bearer(Some("Basic abc")),
+            Err(WorkerAuthResult::Malformed)
+        );
+        assert_eq!(
+            parse_bearer(Some("Bearer   ")),
+            Err(WorkerAuthResult::Malformed)
+        );
+        assert_eq!(parse_bearer(Some("Bearer tok")), Ok("tok".to_string()));
+    }
+
+    #[test]
+    fn each_failure_has_its_own_reason() {
+        let reasons: std::collections::HashSet<&str> = [
+            WorkerAuthResult::Missing,
+            WorkerAuthResult::Malformed,
+            WorkerAuthResult::Revoked,
+            WorkerAuthResult::Unknown,
+        ]
+        .iter()
+        .map(|r| r.reason())
+        .collect();
+        assert_eq!(reasons.len(), 4);
+    }
+}
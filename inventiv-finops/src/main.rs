@@ -1,7 +1,8 @@
 use anyhow::Context;
 use bigdecimal::BigDecimal;
-use chrono::{DateTime, Duration, Timelike, Utc};
-use sqlx::{postgres::PgPoolOptions, Pool, Postgres};
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{Pool, Postgres};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tower_http::cors::{Any, CorsLayer};
 use tracing::{error, info};
@@ -23,8 +24,8 @@ async fn main() -> anyhow::Result<()> {
     // may not rebuild if only SQL files changed).
     let database_url = std::env::var("DATABASE_URL").context("DATABASE_URL must be set")?;
     let redis_url = std::env::var("REDIS_URL").context("REDIS_URL must be set")?;
-    let pool = PgPoolOptions::new()
-        .max_connections(5)
+    let pool = PoolConfig::from_env(5)
+        .options()
         .connect(&database_url)
         .await
         .context("Failed to connect to Postgres")?;
@@ -97,10 +98,7 @@ async fn health() -> &'static str {
 
 async fn sleep_to_next_minute() {
     let now = Utc::now();
-    let next = (now + Duration::minutes(1))
-        .with_second(0)
-        .and_then(|d| d.with_nanosecond(0))
-        .unwrap_or(now + Duration::seconds(60));
+    let next = current_minute_bucket(now) + Duration::minutes(1);
     let delta = (next - now)
         .to_std()
         .unwrap_or(std::time::Duration::from_secs(60));
@@ -109,17 +107,12 @@ async fn sleep_to_next_minute() {
 
 fn last_complete_minute(now: DateTime<Utc>) -> DateTime<Utc> {
     // we compute for the last full minute to avoid partial ingestion windows
-    let floored = now
-        .with_second(0)
-        .and_then(|d| d.with_nanosecond(0))
-        .unwrap_or(now);
-    floored - Duration::minutes(1)
+    current_minute_bucket(now) - Duration::minutes(1)
 }
 
+/// Buckets are UTC minutes (see `inventiv_common::utc_buckets`).
 fn current_minute_bucket(now: DateTime<Utc>) -> DateTime<Utc> {
-    now.with_second(0)
-        .and_then(|d| d.with_nanosecond(0))
-        .unwrap_or(now)
+    utc_buckets::minute_bucket(&now)
 }
 
 async fn run_one_minute_tick(db: &Pool<Postgres>) -> anyhow::Result<()> {
@@ -127,7 +120,7 @@ async fn run_one_minute_tick(db: &Pool<Postgres>) -> anyhow::Result<()> {
     let bucket = last_complete_minute(now);
     let bucket_end = bucket + Duration::minutes(1);
 
-    // 1) Forecast/burn-rate: based on active instances allocation
+    // 1) Forecast/burn-rate: based on active instances allocation (+ blended with trailing actuals)
     compute_and_store_forecast(db, bucket).await?;
 
     // 2) Actual minute costs: aggregate provider_costs into per-minute buckets
@@ -137,78 +130,111 @@ async fn run_one_minute_tick(db: &Pool<Postgres>) -> anyhow::Result<()> {
     // 3) Cumulative actual minute: running sum based on previous cumulative + current minute
     compute_and_store_actual_cumulative(db, bucket).await?;
 
+    // 4) Retention: per-minute runtime model counters (written by the API proxy)
+    prune_runtime_model_counters(db, now).await?;
+
     Ok(())
 }
 
+/// Days of `runtime_model_counters_minute` kept for windowed `/runtime/models` counts.
+fn runtime_model_counters_retention_days() -> i64 {
+    std::env::var("RUNTIME_MODEL_COUNTERS_RETENTION_DAYS")
+        .ok()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .unwrap_or(30)
+        .clamp(1, 3650)
+}
+
+async fn prune_runtime_model_counters(
+    db: &Pool<Postgres>,
+    now: DateTime<Utc>,
+) -> anyhow::Result<u64> {
+    let cutoff =
+        current_minute_bucket(now) - Duration::days(runtime_model_counters_retention_days());
+    let deleted = sqlx::query("DELETE FROM runtime_model_counters_minute WHERE bucket_minute < $1")
+        .bind(cutoff)
+        .execute(db)
+        .await?
+        .rows_affected();
+    if deleted > 0 {
+        info!(
+            "pruned {} runtime model counter rows older than {}",
+            deleted, cutoff
+        );
+    }
+    Ok(deleted)
+}
+
 async fn run_cmd_consumer(redis_url: &str, db: &Pool<Postgres>) -> anyhow::Result<()> {
     let client = redis::Client::open(redis_url)?;
-    let mut pubsub = client.get_async_pubsub().await?;
-    pubsub.subscribe("orchestrator_events").await?;
-    info!("FinOps listening on Redis channel 'orchestrator_events'...");
-
-    use futures_util::StreamExt;
-    let mut stream = pubsub.on_message();
-
-    while let Some(msg) = stream.next().await {
-        let payload: String = msg.get_payload()?;
-
-        // Best effort parse; ignore unknown events
-        if let Ok(v) = serde_json::from_str::<serde_json::Value>(&payload) {
-            let event_type = v.get("type").and_then(|t| t.as_str()).unwrap_or("");
-            // For FinOps: we care mostly about provisioning/terminate to refresh allocation burn-rate.
-            if event_type == "CMD:PROVISION" || event_type == "CMD:TERMINATE" {
-                let bucket = current_minute_bucket(Utc::now());
-                if let Err(e) = compute_and_store_forecast(db, bucket).await {
-                    error!("forecast refresh on {} failed: {:?}", event_type, e);
+    pubsub::consume_channel(
+        &client,
+        CHANNEL_ORCHESTRATOR_COMMANDS,
+        pubsub::Backoff::default(),
+        |payload| async move {
+            // Best effort parse; ignore unknown events
+            if let Ok(v) = serde_json::from_str::<serde_json::Value>(&payload) {
+                let event_type = v.get("type").and_then(|t| t.as_str()).unwrap_or("");
+                // For FinOps: we care mostly about provisioning/terminate to refresh allocation burn-rate.
+                if event_type == "CMD:PROVISION" || event_type == "CMD:TERMINATE" {
+                    let bucket = current_minute_bucket(Utc::now());
+                    if let Err(e) = compute_and_store_forecast(db, bucket).await {
+                        error!("forecast refresh on {} failed: {:?}", event_type, e);
+                    }
                 }
             }
-        }
-    }
+        },
+    )
+    .await;
 
     Ok(())
 }
 
-use inventiv_common::bus::{FinopsEventEnvelope, FinopsEventType};
+use inventiv_common::bus::{
+    FinopsEventEnvelope, FinopsEventType, CHANNEL_FINOPS_EVENTS, CHANNEL_ORCHESTRATOR_COMMANDS,
+};
+use inventiv_common::db_pool::PoolConfig;
+use inventiv_common::pubsub;
+use inventiv_common::utc_buckets;
+use inventiv_common::NON_BILLABLE_COMPUTE_STATUSES;
 
 async fn run_finops_events_consumer(redis_url: &str, db: &Pool<Postgres>) -> anyhow::Result<()> {
     let client = redis::Client::open(redis_url)?;
-    let mut pubsub = client.get_async_pubsub().await?;
-    pubsub.subscribe("finops_events").await?;
-    info!("FinOps listening on Redis channel 'finops_events'...");
-
-    use futures_util::StreamExt;
-    let mut stream = pubsub.on_message();
-
-    while let Some(msg) = stream.next().await {
-        let payload: String = msg.get_payload()?;
-        let Ok(evt) = serde_json::from_str::<FinopsEventEnvelope>(&payload) else {
-            continue;
-        };
-
-        // 1) Persist raw event (idempotent)
-        let _ = sqlx::query(
-            r#"
-            INSERT INTO finops.events (event_id, occurred_at, event_type, source, payload)
-            VALUES ($1, $2, $3, $4, $5)
-            ON CONFLICT (event_id) DO NOTHING
-            "#,
-        )
-        .bind(evt.event_id)
-        .bind(evt.occurred_at)
-        .bind(evt.event_type.as_str())
-        .bind(&evt.source)
-        .bind(&evt.payload)
-        .execute(db)
-        .await;
-
-        // 2) React fast for cost start/stop to update the current bucket forecast
-        if evt.event_type == FinopsEventType::InstanceCostStart
-            || evt.event_type == FinopsEventType::InstanceCostStop
-        {
-            let bucket = current_minute_bucket(evt.occurred_at);
-            let _ = compute_and_store_forecast(db, bucket).await;
-        }
-    }
+    pubsub::consume_channel(
+        &client,
+        CHANNEL_FINOPS_EVENTS,
+        pubsub::Backoff::default(),
+        |payload| async move {
+            let Ok(evt) = serde_json::from_str::<FinopsEventEnvelope>(&payload) else {
+                return;
+            };
+
+            // 1) Persist raw event (idempotent)
+            let _ = sqlx::query(
+                r#"
+                INSERT INTO finops.events (event_id, occurred_at, event_type, source, payload)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (event_id) DO NOTHING
+                "#,
+            )
+            .bind(evt.event_id)
+            .bind(evt.occurred_at)
+            .bind(evt.event_type.as_str())
+            .bind(&evt.source)
+            .bind(&evt.payload)
+            .execute(db)
+            .await;
+
+            // 2) React fast for cost start/stop to update the current bucket forecast
+            if evt.event_type == FinopsEventType::InstanceCostStart
+                || evt.event_type == FinopsEventType::InstanceCostStop
+            {
+                let bucket = current_minute_bucket(evt.occurred_at);
+                let _ = compute_and_store_forecast(db, bucket).await;
+            }
+        },
+    )
+    .await;
 
     Ok(())
 }
@@ -217,7 +243,7 @@ async fn compute_and_store_forecast(
     db: &Pool<Postgres>,
     bucket: DateTime<Utc>,
 ) -> anyhow::Result<()> {
-    // Active statuses: anything not terminal/failure/archived.
+    // Active statuses: anything not in NON_BILLABLE_COMPUTE_STATUSES (queued/terminal/failure/archived/stopped).
     // We treat terminating as still allocated (still costing) until terminated_at is set.
     // We only count allocated resources (provider_instance_id present).
     //
@@ -226,18 +252,18 @@ async fn compute_and_store_forecast(
         r#"
         SELECT
           i.provider_id,
-          COALESCE(SUM(COALESCE(it.cost_per_hour, 0)), 0) AS burn_rate_per_hour
+          COALESCE(SUM(public.effective_cost_per_hour(i.instance_type_id, $1)), 0) AS burn_rate_per_hour
         FROM instances i
-        LEFT JOIN instance_types it ON it.id = i.instance_type_id
         WHERE i.is_archived = false
           AND i.provider_instance_id IS NOT NULL
-          AND (i.status::text NOT IN ('terminated','failed','provisioning_failed','startup_failed','archived'))
+          AND NOT (i.status::text = ANY($2))
           AND i.created_at <= $1
           AND (i.terminated_at IS NULL OR i.terminated_at > $1)
         GROUP BY i.provider_id
         "#
     )
     .bind(bucket)
+    .bind(NON_BILLABLE_COMPUTE_STATUSES)
     .fetch_all(db)
     .await
     .unwrap_or_default();
@@ -246,30 +272,137 @@ async fn compute_and_store_forecast(
     let total: (BigDecimal,) = sqlx::query_as(
         r#"
         SELECT
-          COALESCE(SUM(COALESCE(it.cost_per_hour, 0)), 0) AS burn_rate_per_hour
+          COALESCE(SUM(public.effective_cost_per_hour(i.instance_type_id, $1)), 0) AS burn_rate_per_hour
         FROM instances i
-        LEFT JOIN instance_types it ON it.id = i.instance_type_id
         WHERE i.is_archived = false
           AND i.provider_instance_id IS NOT NULL
-          AND (i.status::text NOT IN ('terminated','failed','provisioning_failed','startup_failed','archived'))
+          AND NOT (i.status::text = ANY($2))
           AND i.created_at <= $1
           AND (i.terminated_at IS NULL OR i.terminated_at > $1)
         "#
     )
     .bind(bucket)
+    .bind(NON_BILLABLE_COMPUTE_STATUSES)
     .fetch_one(db)
     .await
     .unwrap_or((BigDecimal::from(0),));
 
     // store per provider (skip NULL provider_id rows defensively)
+    let mut current: HashMap<Option<uuid::Uuid>, BigDecimal> = HashMap::new();
     for (provider_id, burn_rate_per_hour) in rows.into_iter() {
         if let Some(pid) = provider_id {
-            upsert_forecast_row(db, bucket, Some(pid), burn_rate_per_hour).await?;
+            upsert_forecast_row(
+                db,
+                bucket,
+                Some(pid),
+                "allocation",
+                burn_rate_per_hour.clone(),
+            )
+            .await?;
+            current.insert(Some(pid), burn_rate_per_hour);
         }
     }
 
     // store total
-    upsert_forecast_row(db, bucket, None, total.0).await?;
+    upsert_forecast_row(db, bucket, None, "allocation", total.0.clone()).await?;
+    current.insert(None, total.0);
+
+    compute_and_store_blended_forecast(db, bucket, &current).await?;
+
+    Ok(())
+}
+
+fn forecast_history_days() -> i64 {
+    std::env::var("FINOPS_FORECAST_HISTORY_DAYS")
+        .ok()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .unwrap_or(7)
+        .clamp(1, 90)
+}
+
+/// Average hourly actual cost between two cumulative samples (0 if the span is empty).
+fn trailing_rate_per_hour(
+    start_bucket: DateTime<Utc>,
+    start_cumulative: &BigDecimal,
+    end_bucket: DateTime<Utc>,
+    end_cumulative: &BigDecimal,
+) -> BigDecimal {
+    let span_minutes = (end_bucket - start_bucket).num_minutes();
+    if span_minutes <= 0 {
+        return BigDecimal::from(0);
+    }
+    let spent = end_cumulative - start_cumulative;
+    if spent < BigDecimal::from(0) {
+        return BigDecimal::from(0);
+    }
+    spent * BigDecimal::from(60) / BigDecimal::from(span_minutes)
+}
+
+/// Equal-weight blend of the current allocated burn rate and the trailing actual average.
+fn blended_burn_rate(current: &BigDecimal, trailing: &BigDecimal) -> BigDecimal {
+    (current + trailing) / BigDecimal::from(2)
+}
+
+/// (provider_id, start bucket, start cumulative, end bucket, end cumulative)
+type CumulativeSpan = (
+    Option<uuid::Uuid>,
+    DateTime<Utc>,
+    BigDecimal,
+    DateTime<Utc>,
+    BigDecimal,
+);
+
+async fn compute_and_store_blended_forecast(
+    db: &Pool<Postgres>,
+    bucket: DateTime<Utc>,
+    current: &HashMap<Option<uuid::Uuid>, BigDecimal>,
+) -> anyhow::Result<()> {
+    // Trailing window over cumulative actuals, for total (provider_id NULL) and per provider.
+    // If history is shorter than the window, we average over what we have.
+    let history: Vec<CumulativeSpan> = sqlx::query_as(
+            r#"
+            WITH w AS (
+              SELECT provider_id, MIN(bucket_minute) AS start_b, MAX(bucket_minute) AS end_b
+              FROM finops.cost_actual_cumulative_minute
+              WHERE instance_id IS NULL
+                AND bucket_minute > $1 - ($2::bigint * INTERVAL '1 day')
+                AND bucket_minute <= $1
+              GROUP BY provider_id
+            )
+            SELECT w.provider_id, w.start_b, s.cumulative_amount_eur, w.end_b, e.cumulative_amount_eur
+            FROM w
+            JOIN finops.cost_actual_cumulative_minute s
+              ON s.bucket_minute = w.start_b AND s.provider_id IS NOT DISTINCT FROM w.provider_id AND s.instance_id IS NULL
+            JOIN finops.cost_actual_cumulative_minute e
+              ON e.bucket_minute = w.end_b AND e.provider_id IS NOT DISTINCT FROM w.provider_id AND e.instance_id IS NULL
+            "#,
+        )
+        .bind(bucket)
+        .bind(forecast_history_days())
+        .fetch_all(db)
+        .await
+        .unwrap_or_default();
+
+    let mut trailing: HashMap<Option<uuid::Uuid>, BigDecimal> = HashMap::new();
+    for (provider_id, start_b, start_cum, end_b, end_cum) in history {
+        trailing.insert(
+            provider_id,
+            trailing_rate_per_hour(start_b, &start_cum, end_b, &end_cum),
+        );
+    }
+
+    let zero = BigDecimal::from(0);
+    let mut keys: Vec<Option<uuid::Uuid>> =
+        current.keys().chain(trailing.keys()).copied().collect();
+    keys.sort();
+    keys.dedup();
+    for provider_id in keys {
+        let rate = blended_burn_rate(
+            current.get(&provider_id).unwrap_or(&zero),
+            trailing.get(&provider_id).unwrap_or(&zero),
+        );
+        upsert_forecast_row(db, bucket, provider_id, "blended", rate).await?;
+    }
 
     Ok(())
 }
@@ -278,6 +411,7 @@ async fn upsert_forecast_row(
     db: &Pool<Postgres>,
     bucket: DateTime<Utc>,
     provider_id: Option<uuid::Uuid>,
+    method: &str,
     burn_rate_per_hour: BigDecimal,
 ) -> anyhow::Result<()> {
     // projections
@@ -295,12 +429,12 @@ async fn upsert_forecast_row(
     sqlx::query(
         r#"
         INSERT INTO finops.cost_forecast_minute (
-          bucket_minute, provider_id,
+          bucket_minute, provider_id, method,
           burn_rate_eur_per_hour,
           forecast_eur_per_minute, forecast_eur_per_hour, forecast_eur_per_day, forecast_eur_per_month_30d, forecast_eur_per_year_365d
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-        ON CONFLICT (bucket_minute, provider_id_key)
+        VALUES ($1, $2, $9, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (bucket_minute, provider_id_key, method)
         DO UPDATE SET
           burn_rate_eur_per_hour = EXCLUDED.burn_rate_eur_per_hour,
           forecast_eur_per_minute = EXCLUDED.forecast_eur_per_minute,
@@ -318,6 +452,7 @@ async fn upsert_forecast_row(
     .bind(per_day)
     .bind(per_month_30)
     .bind(per_year_365)
+    .bind(method)
     .execute(db)
     .await?;
 
@@ -330,7 +465,12 @@ async fn compute_and_store_actual_minute(
     bucket_end: DateTime<Utc>,
 ) -> anyhow::Result<()> {
     // For dashboard now: compute "actual" from allocated instances and provider catalog pricing.
-    // This is a precise, prorated allocation cost (overlap seconds within the minute) using instance_types.cost_per_hour.
+    // This is a precise, prorated allocation cost (overlap seconds within the minute) using the effective
+    // hourly price (pricing_overrides valid at the bucket start, else instance_types.cost_per_hour).
+    // Billable seconds come from public.instance_billable_seconds, the same rules as the instance
+    // list total_cost (a terminated instance still bills its last partial minute).
+    // On providers with FINOPS_BILLING_INCREMENT_SECONDS, the minute an instance terminates in also
+    // carries the teardown grace (lifetime rounded up to the provider's billing increment).
     //
     // Later we can add a separate pipeline to ingest provider billing lines into finops.provider_costs.
     //
@@ -344,21 +484,18 @@ async fn compute_and_store_actual_minute(
         r#"
         WITH active AS (
           SELECT
-            COALESCE(it.cost_per_hour, 0) AS cost_per_hour,
-            GREATEST(i.created_at, $1) AS start_ts,
-            LEAST(COALESCE(i.terminated_at, $2), $2) AS end_ts
+            public.effective_cost_per_hour(i.instance_type_id, $1) AS cost_per_hour,
+            public.instance_billable_seconds(i, $1, $2)
+              + public.instance_teardown_grace_seconds(i, $1, $2) AS billable_seconds
           FROM instances i
-          LEFT JOIN instance_types it ON it.id = i.instance_type_id
-          WHERE i.is_archived = false
-            AND i.provider_instance_id IS NOT NULL
-            AND (i.status::text NOT IN ('terminated','failed','provisioning_failed','startup_failed','archived'))
+          WHERE i.provider_instance_id IS NOT NULL
             AND i.created_at < $2
             AND (i.terminated_at IS NULL OR i.terminated_at > $1)
         )
-        SELECT COALESCE(SUM((EXTRACT(EPOCH FROM (end_ts - start_ts)) / 3600.0) * cost_per_hour), 0) AS amount
+        SELECT COALESCE(SUM((billable_seconds / 3600.0)::numeric * cost_per_hour), 0) AS amount
         FROM active
-        WHERE end_ts > start_ts
-        "#
+        WHERE billable_seconds > 0
+        "#,
     )
     .bind(bucket)
     .bind(bucket_end)
@@ -374,23 +511,20 @@ async fn compute_and_store_actual_minute(
         WITH active AS (
           SELECT
             i.provider_id,
-            COALESCE(it.cost_per_hour, 0) AS cost_per_hour,
-            GREATEST(i.created_at, $1) AS start_ts,
-            LEAST(COALESCE(i.terminated_at, $2), $2) AS end_ts
+            public.effective_cost_per_hour(i.instance_type_id, $1) AS cost_per_hour,
+            public.instance_billable_seconds(i, $1, $2)
+              + public.instance_teardown_grace_seconds(i, $1, $2) AS billable_seconds
           FROM instances i
-          LEFT JOIN instance_types it ON it.id = i.instance_type_id
-          WHERE i.is_archived = false
-            AND i.provider_instance_id IS NOT NULL
-            AND (i.status::text NOT IN ('terminated','failed','provisioning_failed','startup_failed','archived'))
+          WHERE i.provider_instance_id IS NOT NULL
             AND i.created_at < $2
             AND (i.terminated_at IS NULL OR i.terminated_at > $1)
         )
         SELECT provider_id,
-               COALESCE(SUM((EXTRACT(EPOCH FROM (end_ts - start_ts)) / 3600.0) * cost_per_hour), 0) AS amount
+               COALESCE(SUM((billable_seconds / 3600.0)::numeric * cost_per_hour), 0) AS amount
         FROM active
-        WHERE end_ts > start_ts
+        WHERE billable_seconds > 0
         GROUP BY provider_id
-        "#
+        "#,
     )
     .bind(bucket)
     .bind(bucket_end)
@@ -409,24 +543,21 @@ async fn compute_and_store_actual_minute(
           SELECT
             i.provider_id,
             i.id AS instance_id,
-            COALESCE(it.cost_per_hour, 0) AS cost_per_hour,
-            GREATEST(i.created_at, $1) AS start_ts,
-            LEAST(COALESCE(i.terminated_at, $2), $2) AS end_ts
+            public.effective_cost_per_hour(i.instance_type_id, $1) AS cost_per_hour,
+            public.instance_billable_seconds(i, $1, $2)
+              + public.instance_teardown_grace_seconds(i, $1, $2) AS billable_seconds
           FROM instances i
-          LEFT JOIN instance_types it ON it.id = i.instance_type_id
-          WHERE i.is_archived = false
-            AND i.provider_instance_id IS NOT NULL
-            AND (i.status::text NOT IN ('terminated','failed','provisioning_failed','startup_failed','archived'))
+          WHERE i.provider_instance_id IS NOT NULL
             AND i.created_at < $2
             AND (i.terminated_at IS NULL OR i.terminated_at > $1)
         )
         SELECT provider_id,
                instance_id,
-               COALESCE(SUM((EXTRACT(EPOCH FROM (end_ts - start_ts)) / 3600.0) * cost_per_hour), 0) AS amount
+               COALESCE(SUM((billable_seconds / 3600.0)::numeric * cost_per_hour), 0) AS amount
         FROM active
-        WHERE end_ts > start_ts
+        WHERE billable_seconds > 0
         GROUP BY provider_id, instance_id
-        "#
+        "#,
     )
     .bind(bucket)
     .bind(bucket_end)
@@ -592,3 +723,636 @@ async fn upsert_cumulative_row(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::postgres::PgPoolOptions;
+
+    fn at(minutes_ago: i64) -> DateTime<Utc> {
+        current_minute_bucket(Utc::now()) - Duration::minutes(minutes_ago)
+    }
+
+    #[test]
+    fn blended_forecast_nonzero_when_nothing_allocated() {
+        // Seeded history: 48 EUR spent over the last 2 days => 1 EUR/h trailing average.
+        let trailing = trailing_rate_per_hour(
+            at(2 * 24 * 60),
+            &BigDecimal::from(10),
+            at(0),
+            &BigDecimal::from(58),
+        );
+        assert_eq!(trailing, BigDecimal::from(1));
+
+        let blended = blended_burn_rate(&BigDecimal::from(0), &trailing);
+        assert!(blended > BigDecimal::from(0));
+    }
+
+    #[test]
+    fn trailing_rate_is_zero_without_history() {
+        let t = at(0);
+        let rate = trailing_rate_per_hour(t, &BigDecimal::from(5), t, &BigDecimal::from(5));
+        assert_eq!(rate, BigDecimal::from(0));
+    }
+
+    async fn setup_pool() -> Option<Pool<Postgres>> {
+        let Some(url) = std::env::var("DATABASE_URL")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+        else {
+            eprintln!("skipping integration test: DATABASE_URL not set");
+            return None;
+        };
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&url)
+            .await
+            .ok()?;
+        let _ = sqlx::migrate!("../sqlx-migrations").run(&pool).await;
+        Some(pool)
+    }
+
+    #[tokio::test]
+    async fn forecast_uses_pricing_override() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+
+        // Isolated provider so the per-provider forecast only reflects our instance.
+        let provider_id = uuid::Uuid::new_v4();
+        let instance_type_id = uuid::Uuid::new_v4();
+        let instance_id = uuid::Uuid::new_v4();
+        let override_id = uuid::Uuid::new_v4();
+        let code = format!("t-{}", &provider_id.simple().to_string()[..8]);
+        sqlx::query("INSERT INTO providers (id, name, code, is_active) VALUES ($1, $2, $2, true)")
+            .bind(provider_id)
+            .bind(&code)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO instance_types (id, code, name, provider_id, gpu_count, vram_per_gpu_gb, cost_per_hour)
+             VALUES ($1, $2, $2, $3, 1, 24, 2.0)",
+        )
+        .bind(instance_type_id)
+        .bind(&code)
+        .bind(provider_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, instance_type_id, provider_instance_id, status, created_at, gpu_profile)
+             VALUES ($1, $2, $3, 'srv-pricing-test', 'ready', NOW() - INTERVAL '1 hour', '{}')",
+        )
+        .bind(instance_id)
+        .bind(provider_id)
+        .bind(instance_type_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        // Committed-use discount: 2.0 -> 1.5 EUR/h
+        sqlx::query(
+            "INSERT INTO pricing_overrides (id, provider_id, instance_type_id, cost_per_hour, valid_from)
+             VALUES ($1, $2, $3, 1.5, NOW() - INTERVAL '1 day')",
+        )
+        .bind(override_id)
+        .bind(provider_id)
+        .bind(instance_type_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let bucket = current_minute_bucket(Utc::now());
+        compute_and_store_forecast(&pool, bucket).await.unwrap();
+        let rate: Option<BigDecimal> = sqlx::query_scalar(
+            "SELECT burn_rate_eur_per_hour FROM finops.cost_forecast_minute
+             WHERE bucket_minute = $1 AND provider_id = $2 AND method = 'allocation'",
+        )
+        .bind(bucket)
+        .bind(provider_id)
+        .fetch_optional(&pool)
+        .await
+        .unwrap();
+
+        let _ = sqlx::query("DELETE FROM finops.cost_forecast_minute WHERE provider_id = $1")
+            .bind(provider_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instance_types WHERE id = $1")
+            .bind(instance_type_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM providers WHERE id = $1")
+            .bind(provider_id)
+            .execute(&pool)
+            .await;
+
+        let expected: BigDecimal = "1.5".parse().unwrap();
+        assert_eq!(rate.map(|r| r.normalized()), Some(expected.normalized()));
+    }
+
+    #[tokio::test]
+    async fn blended_forecast_uses_seeded_actuals_when_nothing_allocated() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+
+        // Provider with no running instance, but 48 EUR spent over the last 2 days.
+        let provider_id = uuid::Uuid::new_v4();
+        let code = format!("t-{}", &provider_id.simple().to_string()[..8]);
+        sqlx::query("INSERT INTO providers (id, name, code, is_active) VALUES ($1, $2, $2, true)")
+            .bind(provider_id)
+            .bind(&code)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let bucket = current_minute_bucket(Utc::now());
+        for (minutes_ago, cumulative) in [(2 * 24 * 60, 10), (24 * 60, 30), (0, 58)] {
+            sqlx::query(
+                "INSERT INTO finops.cost_actual_cumulative_minute (bucket_minute, provider_id, instance_id, cumulative_amount_eur)
+                 VALUES ($1, $2, NULL, $3)",
+            )
+            .bind(bucket - Duration::minutes(minutes_ago))
+            .bind(provider_id)
+            .bind(BigDecimal::from(cumulative))
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        compute_and_store_forecast(&pool, bucket).await.unwrap();
+        let rows: Vec<(String, BigDecimal)> = sqlx::query_as(
+            "SELECT method, burn_rate_eur_per_hour FROM finops.cost_forecast_minute
+             WHERE bucket_minute = $1 AND provider_id = $2
+             ORDER BY method",
+        )
+        .bind(bucket)
+        .bind(provider_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+
+        let _ = sqlx::query("DELETE FROM finops.cost_forecast_minute WHERE provider_id = $1")
+            .bind(provider_id)
+            .execute(&pool)
+            .await;
+        let _ =
+            sqlx::query("DELETE FROM finops.cost_actual_cumulative_minute WHERE provider_id = $1")
+                .bind(provider_id)
+                .execute(&pool)
+                .await;
+        let _ = sqlx::query("DELETE FROM providers WHERE id = $1")
+            .bind(provider_id)
+            .execute(&pool)
+            .await;
+
+        // No allocation-based row (nothing allocated); blended = (0 + 1 EUR/h trailing) / 2.
+        let rows: Vec<(String, BigDecimal)> =
+            rows.into_iter().map(|(m, r)| (m, r.normalized())).collect();
+        let expected: BigDecimal = "0.5".parse().unwrap();
+        assert_eq!(rows, vec![("blended".to_string(), expected.normalized())]);
+    }
+
+    #[tokio::test]
+    async fn stopped_instance_has_no_compute_cost() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+
+        let provider_id = uuid::Uuid::new_v4();
+        let instance_type_id = uuid::Uuid::new_v4();
+        let instance_id = uuid::Uuid::new_v4();
+        let code = format!("t-{}", &provider_id.simple().to_string()[..8]);
+        sqlx::query("INSERT INTO providers (id, name, code, is_active) VALUES ($1, $2, $2, true)")
+            .bind(provider_id)
+            .bind(&code)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO instance_types (id, code, name, provider_id, gpu_count, vram_per_gpu_gb, cost_per_hour)
+             VALUES ($1, $2, $2, $3, 1, 24, 2.0)",
+        )
+        .bind(instance_type_id)
+        .bind(&code)
+        .bind(provider_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, instance_type_id, provider_instance_id, status, created_at, gpu_profile)
+             VALUES ($1, $2, $3, 'srv-stopped-test', 'stopped', NOW() - INTERVAL '1 hour', '{}')",
+        )
+        .bind(instance_id)
+        .bind(provider_id)
+        .bind(instance_type_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let bucket = current_minute_bucket(Utc::now()) - Duration::minutes(1);
+        compute_and_store_forecast(&pool, bucket).await.unwrap();
+        compute_and_store_actual_minute(&pool, bucket, bucket + Duration::minutes(1))
+            .await
+            .unwrap();
+        let burn_rate: Option<BigDecimal> = sqlx::query_scalar(
+            "SELECT SUM(burn_rate_eur_per_hour) FROM finops.cost_forecast_minute
+             WHERE bucket_minute = $1 AND provider_id = $2 AND method = 'allocation'",
+        )
+        .bind(bucket)
+        .bind(provider_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let actual: Option<BigDecimal> = sqlx::query_scalar(
+            "SELECT SUM(amount_eur) FROM finops.cost_actual_minute
+             WHERE bucket_minute = $1 AND provider_id = $2",
+        )
+        .bind(bucket)
+        .bind(provider_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let _ = sqlx::query("DELETE FROM finops.cost_forecast_minute WHERE provider_id = $1")
+            .bind(provider_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM finops.cost_actual_minute WHERE provider_id = $1")
+            .bind(provider_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instance_types WHERE id = $1")
+            .bind(instance_type_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM providers WHERE id = $1")
+            .bind(provider_id)
+            .execute(&pool)
+            .await;
+
+        let zero = BigDecimal::from(0);
+        assert_eq!(burn_rate.unwrap_or_else(|| zero.clone()), zero);
+        assert_eq!(actual.unwrap_or_else(|| zero.clone()), zero);
+    }
+
+    #[tokio::test]
+    async fn terminated_instance_total_cost_matches_finops_cumulative() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+
+        let provider_id = uuid::Uuid::new_v4();
+        let instance_type_id = uuid::Uuid::new_v4();
+        let instance_id = uuid::Uuid::new_v4();
+        let code = format!("t-{}", &provider_id.simple().to_string()[..8]);
+        sqlx::query("INSERT INTO providers (id, name, code, is_active) VALUES ($1, $2, $2, true)")
+            .bind(provider_id)
+            .bind(&code)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO instance_types (id, code, name, provider_id, gpu_count, vram_per_gpu_gb, cost_per_hour)
+             VALUES ($1, $2, $2, $3, 1, 24, 2.0)",
+        )
+        .bind(instance_type_id)
+        .bind(&code)
+        .bind(provider_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        // Ran 2m25s across three minute buckets, terminated before FinOps processed those minutes.
+        let start = at(5);
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, instance_type_id, provider_instance_id, status, created_at, terminated_at, gpu_profile)
+             VALUES ($1, $2, $3, 'srv-billable-test', 'terminated', $4 + INTERVAL '15 seconds', $4 + INTERVAL '160 seconds', '{}')",
+        )
+        .bind(instance_id)
+        .bind(provider_id)
+        .bind(instance_type_id)
+        .bind(start)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        for minute in 0..4 {
+            let bucket = start + Duration::minutes(minute);
+            compute_and_store_actual_minute(&pool, bucket, bucket + Duration::minutes(1))
+                .await
+                .unwrap();
+            compute_and_store_actual_cumulative(&pool, bucket)
+                .await
+                .unwrap();
+        }
+        let cumulative: Option<f64> = sqlx::query_scalar(
+            "SELECT cumulative_amount_eur::float8 FROM finops.cost_actual_cumulative_minute
+             WHERE provider_id = $1 AND instance_id = $2
+             ORDER BY bucket_minute DESC LIMIT 1",
+        )
+        .bind(provider_id)
+        .bind(instance_id)
+        .fetch_optional(&pool)
+        .await
+        .unwrap();
+        // Same expression as the instance list / detail `billable_seconds` and `total_cost`.
+        let (billable_seconds, total_cost): (i64, f64) = sqlx::query_as(
+            "SELECT public.instance_billable_seconds(i, NOW())::bigint, public.instance_total_cost(i, NOW())
+             FROM instances i WHERE i.id = $1",
+        )
+        .bind(instance_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        for table in ["cost_actual_minute", "cost_actual_cumulative_minute"] {
+            let _ = sqlx::query(&format!(
+                "DELETE FROM finops.{} WHERE provider_id = $1",
+                table
+            ))
+            .bind(provider_id)
+            .execute(&pool)
+            .await;
+        }
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instance_types WHERE id = $1")
+            .bind(instance_type_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM providers WHERE id = $1")
+            .bind(provider_id)
+            .execute(&pool)
+            .await;
+
+        assert_eq!(billable_seconds, 145);
+        let cumulative = cumulative.expect("no FinOps cumulative row for the instance");
+        assert!(
+            (cumulative - total_cost).abs() < 1e-6,
+            "FinOps cumulative {} != instance total_cost {}",
+            cumulative,
+            total_cost
+        );
+    }
+
+    #[tokio::test]
+    async fn termination_minute_rounds_up_to_provider_billing_increment() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(organization_id): Option<uuid::Uuid> =
+            sqlx::query_scalar("SELECT id FROM organizations LIMIT 1")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: no organization seeded");
+            return;
+        };
+
+        let provider_id = uuid::Uuid::new_v4();
+        let instance_type_id = uuid::Uuid::new_v4();
+        let instance_id = uuid::Uuid::new_v4();
+        let code = format!("t-{}", &provider_id.simple().to_string()[..8]);
+        sqlx::query("INSERT INTO providers (id, name, code, is_active) VALUES ($1, $2, $2, true)")
+            .bind(provider_id)
+            .bind(&code)
+            .execute(&pool)
+            .await
+            .unwrap();
+        // Provider bills per started minute.
+        sqlx::query(
+            "INSERT INTO provider_settings (provider_id, organization_id, key, value_int)
+             VALUES ($1, $2, 'FINOPS_BILLING_INCREMENT_SECONDS', 60)",
+        )
+        .bind(provider_id)
+        .bind(organization_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO instance_types (id, code, name, provider_id, gpu_count, vram_per_gpu_gb, cost_per_hour)
+             VALUES ($1, $2, $2, $3, 1, 24, 3.6)",
+        )
+        .bind(instance_type_id)
+        .bind(&code)
+        .bind(provider_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        // Ran 2m25s (15s..160s): billed 3 full minutes by the provider.
+        let start = at(5);
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, instance_type_id, provider_instance_id, status, created_at, terminated_at, gpu_profile)
+             VALUES ($1, $2, $3, 'srv-increment-test', 'terminated', $4 + INTERVAL '15 seconds', $4 + INTERVAL '160 seconds', '{}')",
+        )
+        .bind(instance_id)
+        .bind(provider_id)
+        .bind(instance_type_id)
+        .bind(start)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        for minute in 0..4 {
+            let bucket = start + Duration::minutes(minute);
+            compute_and_store_actual_minute(&pool, bucket, bucket + Duration::minutes(1))
+                .await
+                .unwrap();
+        }
+        let rows: Vec<(DateTime<Utc>, f64)> = sqlx::query_as(
+            "SELECT bucket_minute, amount_eur::float8 FROM finops.cost_actual_minute
+             WHERE provider_id = $1 AND instance_id = $2 ORDER BY bucket_minute",
+        )
+        .bind(provider_id)
+        .bind(instance_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+
+        let _ = sqlx::query("DELETE FROM finops.cost_actual_minute WHERE provider_id = $1")
+            .bind(provider_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instance_types WHERE id = $1")
+            .bind(instance_type_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM provider_settings WHERE provider_id = $1")
+            .bind(provider_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM providers WHERE id = $1")
+            .bind(provider_id)
+            .execute(&pool)
+            .await;
+
+        // 3.6 EUR/h = 0.001 EUR/s: 45s + 60s prorated, then 40s run + 35s grace in the last minute.
+        assert_eq!(rows.len(), 3, "{:?}", rows);
+        assert!((rows[0].1 - 0.045).abs() < 1e-9, "{:?}", rows);
+        assert!((rows[1].1 - 0.060).abs() < 1e-9, "{:?}", rows);
+        assert_eq!(rows[2].0, start + Duration::minutes(2));
+        assert!((rows[2].1 - 0.075).abs() < 1e-9, "{:?}", rows);
+        let total: f64 = rows.iter().map(|r| r.1).sum();
+        assert!((total - 0.180).abs() < 1e-9, "{:?}", rows);
+    }
+
+    #[tokio::test]
+    async fn actual_minute_near_dst_boundary_lands_in_utc_bucket() {
+        let Some(url) = std::env::var("DATABASE_URL")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+        else {
+            eprintln!("skipping integration test: DATABASE_URL not set");
+            return;
+        };
+        // Session in a DST zone: bucketing must not depend on it.
+        let Ok(pool) = PgPoolOptions::new()
+            .max_connections(2)
+            .after_connect(|conn, _| {
+                Box::pin(async move {
+                    sqlx::query("SET TIME ZONE 'Europe/Paris'")
+                        .execute(conn)
+                        .await?;
+                    Ok(())
+                })
+            })
+            .connect(&url)
+            .await
+        else {
+            return;
+        };
+
+        let provider_id = uuid::Uuid::new_v4();
+        let instance_type_id = uuid::Uuid::new_v4();
+        let instance_id = uuid::Uuid::new_v4();
+        let code = format!("t-{}", &provider_id.simple().to_string()[..8]);
+        sqlx::query("INSERT INTO providers (id, name, code, is_active) VALUES ($1, $2, $2, true)")
+            .bind(provider_id)
+            .bind(&code)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO instance_types (id, code, name, provider_id, gpu_count, vram_per_gpu_gb, cost_per_hour)
+             VALUES ($1, $2, $2, $3, 1, 24, 3.6)",
+        )
+        .bind(instance_type_id)
+        .bind(&code)
+        .bind(provider_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        // Europe/Paris springs forward at 01:00Z: 01:59:30 CET is followed by 03:00:00 CEST.
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, instance_type_id, provider_instance_id, status, created_at, terminated_at, gpu_profile)
+             VALUES ($1, $2, $3, 'srv-dst-test', 'terminated',
+                     '2026-03-29 01:59:30+01', '2026-03-29 03:00:45+02', '{}')",
+        )
+        .bind(instance_id)
+        .bind(provider_id)
+        .bind(instance_type_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let event = DateTime::parse_from_rfc3339("2026-03-29T03:00:30+02:00").unwrap();
+        let bucket = utc_buckets::minute_bucket(&event);
+        for b in [bucket - Duration::minutes(1), bucket] {
+            compute_and_store_actual_minute(&pool, b, b + Duration::minutes(1))
+                .await
+                .unwrap();
+        }
+        let rows: Vec<(DateTime<Utc>, f64)> = sqlx::query_as(
+            "SELECT bucket_minute, amount_eur::float8 FROM finops.cost_actual_minute
+             WHERE provider_id = $1 AND instance_id = $2 ORDER BY bucket_minute",
+        )
+        .bind(provider_id)
+        .bind(instance_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+
+        let _ = sqlx::query("DELETE FROM finops.cost_actual_minute WHERE provider_id = $1")
+            .bind(provider_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instance_types WHERE id = $1")
+            .bind(instance_type_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM providers WHERE id = $1")
+            .bind(provider_id)
+            .execute(&pool)
+            .await;
+
+        let utc = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        assert_eq!(bucket, utc("2026-03-29T01:00:00Z"));
+        // 3.6 EUR/h = 0.001 EUR/s: 30s in the 00:59Z minute, 45s in the 01:00Z minute.
+        assert_eq!(rows.len(), 2, "{:?}", rows);
+        assert_eq!(rows[0].0, utc("2026-03-29T00:59:00Z"));
+        assert!((rows[0].1 - 0.030).abs() < 1e-9, "{:?}", rows);
+        assert_eq!(rows[1].0, utc("2026-03-29T01:00:00Z"));
+        assert!((rows[1].1 - 0.045).abs() < 1e-9, "{:?}", rows);
+    }
+
+    #[tokio::test]
+    async fn runtime_model_counters_past_retention_are_pruned() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+
+        let model_id = format!("test-org/counters-retention-{}", uuid::Uuid::new_v4());
+        let now = Utc::now();
+        let retention = runtime_model_counters_retention_days();
+        for bucket in [
+            current_minute_bucket(now) - Duration::days(retention + 1),
+            current_minute_bucket(now) - Duration::days(1),
+        ] {
+            sqlx::query(
+                "INSERT INTO runtime_model_counters_minute (model_id, bucket_minute, requests, failed_requests)
+                 VALUES ($1, $2, 3, 1)",
+            )
+            .bind(&model_id)
+            .bind(bucket)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let deleted = prune_runtime_model_counters(&pool, now).await.unwrap();
+        let kept: Vec<DateTime<Utc>> = sqlx::query_scalar(
+            "SELECT bucket_minute FROM runtime_model_counters_minute WHERE model_id = $1",
+        )
+        .bind(&model_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+
+        let _ = sqlx::query("DELETE FROM runtime_model_counters_minute WHERE model_id = $1")
+            .bind(&model_id)
+            .execute(&pool)
+            .await;
+
+        assert!(deleted >= 1);
+        assert_eq!(kept, vec![current_minute_bucket(now) - Duration::days(1)]);
+    }
+}
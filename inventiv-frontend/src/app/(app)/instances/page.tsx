@@ -137,6 +137,8 @@ export default function InstancesPage() {
     const stats = {
         total: instances.length,
         active: instances.filter((i) => i.status.toLowerCase() === "ready").length,
+        // Queued (accepted) deployments are not in flight yet: counted separately.
+        queued: instances.filter((i) => i.status.toLowerCase() === "accepted").length,
         provisioning: instances.filter((i) =>
             ["provisioning", "booting"].includes(i.status.toLowerCase())
         ).length,
@@ -220,7 +222,7 @@ export default function InstancesPage() {
                 <IAStatCell
                     title="Provisioning"
                     value={stats.provisioning}
-                    subtitle="Provisioning / booting"
+                    subtitle={`Provisioning / booting · ${stats.queued} queued`}
                     icon={RefreshIcon}
                     accent="cyan"
                 />
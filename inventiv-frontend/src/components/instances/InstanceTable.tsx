@@ -140,10 +140,17 @@ export function InstanceTable({
             variant={
               row.status.toLowerCase() === "ready"
                 ? "default"
-                : row.status.toLowerCase() === "terminated"
+                : row.status.toLowerCase() === "terminated" || row.failure_stage
                   ? "destructive"
                   : "secondary"
             }
+            title={
+              row.failure_stage === "provisioning"
+                ? "Failed during provisioning: the provider never delivered a running server"
+                : row.failure_stage === "startup"
+                  ? "Failed during startup: the server was created but the worker never became healthy"
+                  : undefined
+            }
           >
             {row.status}
           </Badge>
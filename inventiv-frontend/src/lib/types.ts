@@ -27,8 +27,13 @@ export type Instance = {
     gpu_count?: number;
     cpu_count?: number | null;
     ram_gb?: number | null;
+    bandwidth_bps?: number | null;
     cost_per_hour?: number;
     total_cost?: number;
+    billable_seconds?: number;
+    requests_served?: number | null;
+    requests_failed?: number | null;
+    last_request_at?: string | null;
     storage_count?: number;
     storage_sizes_gb?: number[];
     storages?: InstanceStorageInfo[];
@@ -38,10 +43,13 @@ export type Instance = {
     worker_queue_depth?: number | null;
     worker_gpu_utilization?: number | null;
     worker_metadata?: Record<string, unknown> | null;
+    provider_metadata?: Record<string, unknown> | null;
     worker_health_port?: number | null;
     worker_vllm_port?: number | null;
     // Progress percentage (0-100) towards operational state
     progress_percent?: number | null;
+    // Where a failed instance stopped: provisioning (never created) | startup (created, never healthy) | runtime
+    failure_stage?: "provisioning" | "startup" | "runtime" | null;
 };
 
 export type InstanceStorageInfo = {
@@ -153,6 +161,8 @@ export type LlmModel = {
     context_length: number;
     is_active: boolean;
     data_volume_gb?: number | null;
+    public: boolean; // listed in /v1/models for API keys
+    default_params?: Record<string, unknown> | null; // generation defaults for chat/completions
     metadata?: Record<string, unknown> | null;
     created_at: string;
     updated_at: string;
@@ -169,6 +179,8 @@ export type ApiKey = {
     created_at: string;
     last_used_at?: string | null;
     revoked_at?: string | null;
+    allowed_models?: string[] | null; // model scope (HF repo ids), null = any public model
+    rate_limit_per_minute?: number | null; // requests/min on /v1/*, null = unlimited
 };
 
 // -----------------------------
@@ -374,6 +386,7 @@ export type InstanceStats = {
 export type FinopsForecastMinuteRow = {
     bucket_minute: string;
     provider_id: string | null;
+    method: "allocation" | "blended";
 
     burn_rate_eur_per_hour: number;
 
@@ -400,6 +413,7 @@ export type FinopsCumulativeMinuteRow = {
 
 export type FinopsCostCurrentResponse = {
     latest_bucket_minute: string | null;
+    method: "allocation" | "blended";
     forecast: FinopsForecastMinuteRow[];
     cumulative_total: FinopsCumulativeMinuteRow | null;
 };
@@ -548,6 +562,20 @@ export type FinopsCostsDashboardWindowResponse = {
     by_instance_eur: FinopsInstanceCostRow[];
 };
 
+export type FinopsCostCenterCostRow = {
+    cost_center: string | null;
+    instances_count: number;
+    amount_eur: number;
+};
+
+export type FinopsCostsByCostCenterResponse = {
+    window: string;
+    window_minutes: number;
+    bucket_end_minute: string | null;
+    bucket_start_minute: string | null;
+    by_cost_center_eur: FinopsCostCenterCostRow[];
+};
+
 export type FinopsCostsDashboardSeriesPoint = {
     bucket: string;
     amount_eur: number;
@@ -0,0 +1,221 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use inventiv_providers::CloudProvider;
+use serde::Serialize;
+use sqlx::{Pool, Postgres};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::provider_manager::ProviderManager;
+use crate::services;
+use crate::AppState;
+
+/// Upper bound for each remote check, so the endpoint stays cheap enough for a 1-minute monitor.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize)]
+pub struct CheckStatus {
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    pub duration_ms: u64,
+}
+
+impl CheckStatus {
+    fn new(started: Instant, result: Result<Option<String>, String>) -> Self {
+        let duration_ms = started.elapsed().as_millis() as u64;
+        match result {
+            Ok(detail) => Self {
+                ok: true,
+                detail,
+                duration_ms,
+            },
+            Err(e) => Self {
+                ok: false,
+                detail: Some(e),
+                duration_ms,
+            },
+        }
+    }
+}
+
+/// Provision-path prerequisites: provider credentials, worker control-plane reachability and a
+/// non-empty catalog for the checked providers.
+#[derive(Debug, Serialize)]
+pub struct DeepHealth {
+    pub ok: bool,
+    pub providers: BTreeMap<String, CheckStatus>,
+    pub control_plane: CheckStatus,
+    pub catalog: CheckStatus,
+}
+
+/// Provider under check: its code and the instance built for it (or why it could not be built).
+pub type ProviderTarget = (String, Result<Box<dyn CloudProvider>, String>);
+
+async fn check_provider(provider: Result<Box<dyn CloudProvider>, String>) -> CheckStatus {
+    let started = Instant::now();
+    let result = match provider {
+        Ok(p) => match tokio::time::timeout(CHECK_TIMEOUT, p.validate_credentials()).await {
+            Ok(Ok(())) => Ok(None),
+            Ok(Err(e)) => Err(e.to_string()),
+            Err(_) => Err("credential check timed out".to_string()),
+        },
+        Err(e) => Err(e),
+    };
+    CheckStatus::new(started, result)
+}
+
+async fn check_control_plane(url: &str) -> CheckStatus {
+    let started = Instant::now();
+    let url = url.trim();
+    if url.is_empty() {
+        return CheckStatus::new(
+            started,
+            Err("WORKER_CONTROL_PLANE_URL is not configured".to_string()),
+        );
+    }
+    let client = match reqwest::Client::builder().timeout(CHECK_TIMEOUT).build() {
+        Ok(c) => c,
+        Err(e) => return CheckStatus::new(started, Err(e.to_string())),
+    };
+    // Any HTTP answer proves reachability; only transport errors fail the check.
+    let result = client
+        .head(url)
+        .send()
+        .await
+        .map(|r| Some(format!("HTTP {}", r.status().as_u16())))
+        .map_err(|e| e.to_string());
+    CheckStatus::new(started, result)
+}
+
+async fn check_catalog(db: &Pool<Postgres>, provider_codes: &[String]) -> CheckStatus {
+    let started = Instant::now();
+    let result = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)::bigint
+        FROM instance_types it
+        JOIN providers p ON p.id = it.provider_id
+        WHERE it.is_active = true
+          AND p.code = ANY($1)
+        "#,
+    )
+    .bind(provider_codes)
+    .fetch_one(db)
+    .await
+    .map_err(|e| e.to_string())
+    .and_then(|n| {
+        if n > 0 {
+            Ok(Some(format!("{} active instance types", n)))
+        } else {
+            Err("no active instance types in catalog".to_string())
+        }
+    });
+    CheckStatus::new(started, result)
+}
+
+/// Run all checks concurrently.
+pub async fn run_checks(
+    db: &Pool<Postgres>,
+    providers: Vec<ProviderTarget>,
+    control_plane_url: &str,
+) -> DeepHealth {
+    let codes: Vec<String> = providers.iter().map(|(code, _)| code.clone()).collect();
+    let provider_checks = futures_util::future::join_all(
+        providers
+            .into_iter()
+            .map(|(code, p)| async move { (code, check_provider(p).await) }),
+    );
+    let (provider_checks, control_plane, catalog) = tokio::join!(
+        provider_checks,
+        check_control_plane(control_plane_url),
+        check_catalog(db, &codes)
+    );
+    let providers: BTreeMap<String, CheckStatus> = provider_checks.into_iter().collect();
+    let ok =
+        !providers.is_empty() && providers.values().all(|c| c.ok) && control_plane.ok && catalog.ok;
+    DeepHealth {
+        ok,
+        providers,
+        control_plane,
+        catalog,
+    }
+}
+
+/// GET /healthz/deep: 200 when the orchestrator can provision, 503 otherwise.
+pub async fn get_deep_health(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let default_org_id: Option<uuid::Uuid> =
+        sqlx::query_scalar("SELECT id FROM organizations WHERE slug = 'inventiv-it' LIMIT 1")
+            .fetch_optional(&state.db)
+            .await
+            .ok()
+            .flatten();
+    let provider_name = ProviderManager::current_provider_name();
+    let provider = match default_org_id {
+        Some(org_id) => {
+            ProviderManager::get_provider(&provider_name, org_id, state.db.clone()).await
+        }
+        None => Err("Default organization 'inventiv-it' not found".to_string()),
+    };
+
+    let health = run_checks(
+        &state.db,
+        vec![(provider_name, provider)],
+        &services::worker_control_plane_url(),
+    )
+    .await;
+    let status = if health.ok {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(health))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{setup_pool, TestProvider};
+
+    #[tokio::test]
+    async fn deep_health_passes_with_mock_provider() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let mock_types: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM instance_types it JOIN providers p ON p.id = it.provider_id
+             WHERE p.code = 'mock' AND it.is_active = true",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap_or(0);
+        if mock_types == 0 {
+            eprintln!("skipping integration test: mock catalog not seeded");
+            return;
+        }
+
+        // Control plane stub: HEAD is answered like any route (405 still proves reachability).
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let app = axum::Router::new().route("/", axum::routing::get(|| async { "ok" }));
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, app).await;
+        });
+
+        let providers: Vec<ProviderTarget> = vec![(
+            "mock".to_string(),
+            Ok(Box::new(TestProvider::default()) as Box<dyn CloudProvider>),
+        )];
+        let health = run_checks(&pool, providers, &format!("http://127.0.0.1:{}/", port)).await;
+        assert!(health.ok, "{:?}", health);
+        assert!(health.providers["mock"].ok);
+
+        // Unreachable control plane and a provider that could not be built both fail the check.
+        let providers: Vec<ProviderTarget> =
+            vec![("mock".to_string(), Err("missing credentials".to_string()))];
+        let health = run_checks(&pool, providers, "http://127.0.0.1:1/").await;
+        assert!(!health.ok);
+        assert!(!health.providers["mock"].ok);
+        assert!(!health.control_plane.ok);
+        assert!(health.catalog.ok);
+    }
+}
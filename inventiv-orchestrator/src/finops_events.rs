@@ -84,3 +84,51 @@ pub async fn emit_instance_cost_stop(
     );
     publish_finops_event(redis_client, &evt).await
 }
+
+/// Build and publish runtime ALERT event (instance ran past its max_runtime_hours).
+pub async fn emit_instance_runtime_alert(
+    redis_client: &redis::Client,
+    instance_id: Uuid,
+    provider_id: Uuid,
+    max_runtime_hours: i32,
+    runtime_seconds: i64,
+    auto_terminate: bool,
+    source: &str,
+) -> anyhow::Result<()> {
+    let evt = FinopsEventEnvelope::new(
+        FinopsEventType::InstanceRuntimeAlert,
+        serde_json::json!({
+            "instance_id": instance_id.to_string(),
+            "provider_id": provider_id.to_string(),
+            "max_runtime_hours": max_runtime_hours,
+            "runtime_seconds": runtime_seconds,
+            "auto_terminate": auto_terminate,
+        }),
+        source,
+    );
+    publish_finops_event(redis_client, &evt).await
+}
+
+/// Build the PRICE_CHANGED event for a catalog price change (old/new hourly price, % change).
+pub fn price_changed_event(
+    provider_id: Uuid,
+    instance_type_id: Uuid,
+    instance_type_code: &str,
+    old_cost_per_hour: f64,
+    new_cost_per_hour: f64,
+    pct_change: f64,
+    source: &str,
+) -> FinopsEventEnvelope {
+    FinopsEventEnvelope::new(
+        FinopsEventType::PriceChanged,
+        serde_json::json!({
+            "provider_id": provider_id.to_string(),
+            "instance_type_id": instance_type_id.to_string(),
+            "instance_type_code": instance_type_code,
+            "old_cost_per_hour": old_cost_per_hour,
+            "new_cost_per_hour": new_cost_per_hour,
+            "pct_change": pct_change,
+        }),
+        source,
+    )
+}
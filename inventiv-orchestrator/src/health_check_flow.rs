@@ -8,6 +8,8 @@ use tokio::process::Command;
 
 use crate::logger;
 use crate::state_machine;
+use inventiv_common::error_code::ErrorCode;
+use inventiv_common::{net, WorkerStatus};
 use uuid::Uuid;
 
 /// Resolve vLLM Docker image with hierarchy (same logic as in services.rs)
@@ -179,8 +181,10 @@ fn worker_hf_token() -> String {
 }
 
 async fn check_instance_readyz_http(ip: &str, port: u16) -> bool {
-    let clean_ip = ip.split('/').next().unwrap_or(ip);
-    let url = format!("http://{}:{}/readyz", clean_ip, port);
+    let Some(base_url) = net::instance_http_base_url(ip, port) else {
+        return false;
+    };
+    let url = format!("{}/readyz", base_url);
     // Use short timeout to avoid stalling the job loop
     let client = reqwest::Client::builder()
         .connect_timeout(StdDuration::from_secs(2))
@@ -197,8 +201,9 @@ async fn check_instance_readyz_http(ip: &str, port: u16) -> bool {
 
 /// Check agent info endpoint (/info) to verify version and checksum
 async fn check_agent_info(ip: &str, port: u16) -> Result<serde_json::Value, String> {
-    let clean_ip = ip.split('/').next().unwrap_or(ip);
-    let url = format!("http://{}:{}/info", clean_ip, port);
+    let base_url = net::instance_http_base_url(ip, port)
+        .ok_or_else(|| format!("Unusable instance IP: {}", ip))?;
+    let url = format!("{}/info", base_url);
     let client = reqwest::Client::builder()
         .connect_timeout(StdDuration::from_secs(2))
         .timeout(StdDuration::from_secs(3))
@@ -227,8 +232,10 @@ pub(crate) async fn check_vllm_http_models(
     ip: &str,
     port: u16,
 ) -> (bool, Vec<String>, i32, Option<String>) {
-    let clean_ip = ip.split('/').next().unwrap_or(ip);
-    let url = format!("http://{}:{}/v1/models", clean_ip, port);
+    let Some(base_url) = net::instance_http_base_url(ip, port) else {
+        return (false, Vec::new(), 0, Some("invalid_ip".to_string()));
+    };
+    let url = format!("{}/v1/models", base_url);
     let start = std::time::Instant::now();
     let client = reqwest::Client::builder()
         .connect_timeout(StdDuration::from_secs(2))
@@ -278,8 +285,10 @@ async fn check_vllm_warmup_http(
     port: u16,
     model_id: &str,
 ) -> (bool, i32, Option<String>) {
-    let clean_ip = ip.split('/').next().unwrap_or(ip);
-    let url = format!("http://{}:{}/v1/chat/completions", clean_ip, port);
+    let Some(base_url) = net::instance_http_base_url(ip, port) else {
+        return (false, 0, Some("invalid_ip".to_string()));
+    };
+    let url = format!("{}/v1/chat/completions", base_url);
     let start = std::time::Instant::now();
     let client = reqwest::Client::builder()
         .connect_timeout(StdDuration::from_secs(2))
@@ -316,14 +325,11 @@ async fn check_vllm_warmup_http(
 
 /// Check instance health by testing SSH port connectivity.
 async fn check_instance_ssh(ip: &str) -> bool {
-    let clean_ip = ip.split('/').next().unwrap_or(ip);
-    let addr = format!("{}:22", clean_ip);
+    let Some(socket_addr) = net::instance_socket_addr(ip, 22) else {
+        return false;
+    };
 
     tokio::task::spawn_blocking(move || {
-        let socket_addr = match addr.parse() {
-            Ok(a) => a,
-            Err(_) => return false,
-        };
         TcpStream::connect_timeout(&socket_addr, StdDuration::from_secs(3)).is_ok()
     })
     .await
@@ -333,7 +339,7 @@ async fn check_instance_ssh(ip: &str) -> bool {
 /// Check container health via SSH: returns (vllm_running, agent_running, vllm_exit_code, agent_exit_code)
 /// Returns None if SSH check fails.
 async fn check_containers_via_ssh(ip: &str) -> Option<(bool, bool, Option<i32>, Option<i32>)> {
-    let clean_ip = ip.split('/').next().unwrap_or(ip);
+    let clean_ip = net::normalize_instance_ip(ip)?;
     let ssh_key_path =
         std::env::var("SSH_KEY_PATH").unwrap_or_else(|_| "/app/.ssh/llm-studio-key".to_string());
     let ssh_user = std::env::var("SSH_USER").unwrap_or_else(|_| "root".to_string());
@@ -413,8 +419,8 @@ docker inspect -f '{{.State.Running}} {{.State.ExitCode}}' inventiv-agent 2>/dev
 /// Fetch worker logs from the /logs endpoint
 /// Returns None if the request fails
 async fn fetch_worker_logs(ip: &str, port: u16) -> Option<WorkerLogs> {
-    let clean_ip = ip.split('/').next().unwrap_or(ip);
-    let url = format!("http://{}:{}/logs?tail=100", clean_ip, port);
+    let base_url = net::instance_http_base_url(ip, port)?;
+    let url = format!("{}/logs?tail=100", base_url);
     let client = reqwest::Client::builder()
         .connect_timeout(StdDuration::from_secs(2))
         .timeout(StdDuration::from_secs(5))
@@ -689,7 +695,13 @@ async fn maybe_trigger_worker_install_over_ssh(
         .filter(|s| !s.trim().is_empty())
         .unwrap_or_else(|| "/app/.ssh/llm-studio-key".to_string());
 
-    let clean_ip = ip.split('/').next().unwrap_or(ip);
+    let Some(clean_ip) = net::normalize_instance_ip(ip) else {
+        eprintln!(
+            "⚠️ [worker_install] Instance {} has an unusable IP ({}), skipping SSH install",
+            instance_id, ip
+        );
+        return;
+    };
     let target = format!("{}@{}", ssh_user, clean_ip);
 
     // De-dupe / backoff: don't re-run SSH bootstrap in a tight loop.
@@ -1679,7 +1691,7 @@ pub async fn check_and_transition_instance(
         let _ = state_machine::booting_to_startup_failed(
             &db,
             instance_id,
-            "STARTUP_TIMEOUT",
+            ErrorCode::StartupTimeout.as_str(),
             &timeout_msg,
         )
         .await;
@@ -1772,8 +1784,7 @@ pub async fn check_and_transition_instance(
                 // If heartbeat is recent (within 30 seconds) and status is "ready", trust it
                 if age < 30 {
                     if let Some(status) = &info.worker_status {
-                        let status_lower = status.to_ascii_lowercase();
-                        if status_lower == "ready" {
+                        if WorkerStatus::parse(status) == Some(WorkerStatus::Ready) {
                             is_healthy_from_heartbeat = true;
                             println!(
                                 "✅ Instance {} healthy via heartbeat (status={}, age={}s)",
@@ -2534,13 +2545,14 @@ pub async fn check_and_transition_instance(
                         let _ = sqlx::query(
                             "UPDATE instances 
                              SET status='failed', 
-                                 error_code=COALESCE(error_code,'SSH_NOT_ACCESSIBLE'),
+                                 error_code=COALESCE(error_code,$3),
                                  error_message=COALESCE($2,error_message),
                                  failed_at=COALESCE(failed_at,NOW())
                              WHERE id=$1",
                         )
                         .bind(instance_id)
                         .bind(&error_msg)
+                        .bind(ErrorCode::SshNotAccessible.as_str())
                         .execute(&db)
                         .await;
 
@@ -2630,7 +2642,7 @@ pub async fn check_and_transition_instance(
             let _ = state_machine::booting_to_startup_failed(
                 &db,
                 instance_id,
-                "HEALTH_CHECK_FAILED",
+                ErrorCode::HealthCheckFailed.as_str(),
                 "Instance failed health checks after 30 attempts",
             )
             .await;
@@ -1,8 +1,10 @@
+use inventiv_common::error_code::ErrorCode;
 use sqlx::{Pool, Postgres};
 
 use crate::health_check_flow::check_and_transition_instance;
 use crate::logger;
 use crate::provider_manager::ProviderManager;
+use crate::state_machine;
 
 /// job-health-check: processes BOOTING/INSTALLING/STARTING instances and transitions them to READY/STARTUP_FAILED.
 /// Uses SKIP LOCKED claiming so multiple orchestrators can run safely.
@@ -13,6 +15,22 @@ pub async fn run(pool: Pool<Postgres>) {
     loop {
         interval.tick().await;
 
+        // Set-based sweep first: fail every overdue BOOTING/INSTALLING/STARTING instance in one
+        // statement instead of discovering timeouts one claimed row at a time.
+        match state_machine::startup_timeouts_to_failed(
+            &pool,
+            &state_machine::StartupTimeoutPolicy::from_env(),
+        )
+        .await
+        {
+            Ok(failed) if !failed.is_empty() => println!(
+                "⏱️  job-health-check: {} instance(s) exceeded startup timeout -> startup_failed",
+                failed.len()
+            ),
+            Ok(_) => {}
+            Err(e) => eprintln!("❌ job-health-check startup-timeout sweep error: {:?}", e),
+        }
+
         // Claim BOOTING/INSTALLING/STARTING instances even if IP is missing. If IP is missing, we try to fetch it from provider.
         #[allow(clippy::type_complexity)]
         let booting_instances: Result<
@@ -83,7 +101,7 @@ pub async fn run(pool: Pool<Postgres>) {
                             if let Some(pid) = provider_instance_id.as_deref() {
                                 // Get organization_id from instance (required)
                                 let org_id: Option<uuid::Uuid> = sqlx::query_scalar(
-                                    "SELECT organization_id FROM instances WHERE id = $1"
+                                    "SELECT organization_id FROM instances WHERE id = $1",
                                 )
                                 .bind(id)
                                 .fetch_optional(&db_clone)
@@ -92,7 +110,10 @@ pub async fn run(pool: Pool<Postgres>) {
                                 .flatten();
 
                                 let Some(org_id) = org_id else {
-                                    eprintln!("❌ [Health Check] Instance {} missing organization_id", id);
+                                    eprintln!(
+                                        "❌ [Health Check] Instance {} missing organization_id",
+                                        id
+                                    );
                                     return;
                                 };
 
@@ -329,7 +350,7 @@ pub async fn run(pool: Pool<Postgres>) {
                                                     let _ = sqlx::query(
                                                         "UPDATE instances
                                                          SET status = 'terminating',
-                                                             error_code = COALESCE(error_code, 'PROVIDER_OUT_OF_STOCK'),
+                                                             error_code = COALESCE(error_code, $3),
                                                              error_message = COALESCE($2, error_message),
                                                              failed_at = COALESCE(failed_at, NOW()),
                                                              deletion_reason = COALESCE(deletion_reason, 'provider_out_of_stock')
@@ -337,6 +358,7 @@ pub async fn run(pool: Pool<Postgres>) {
                                                     )
                                                     .bind(id)
                                                     .bind(&msg)
+                                                    .bind(ErrorCode::ProviderOutOfStock.as_str())
                                                     .execute(&db_clone)
                                                     .await;
 
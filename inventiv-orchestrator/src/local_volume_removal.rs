@@ -0,0 +1,194 @@
+//! Verified removal of provider local volumes before a diskless boot.
+//!
+//! Diskless instance types (Scaleway L4/L40S/H100) must boot from Block Storage: local volumes the
+//! provider attached at creation are removed before the first start. `remove_local_volumes` can
+//! partially succeed, and an instance booted with leftover local storage defeats the Block Storage
+//! layout, so we re-list the attached volumes after each call, retry a bounded number of times and
+//! fail provisioning with `LOCAL_VOLUME_REMOVAL_FAILED` when they persist.
+
+use std::time::Duration;
+
+use inventiv_common::error_code::ErrorCode;
+use inventiv_providers::CloudProvider;
+
+pub const ERROR_CODE: &str = ErrorCode::LocalVolumeRemovalFailed.as_str();
+
+const DEFAULT_ATTEMPTS: u32 = 3;
+const DEFAULT_INTERVAL_S: u64 = 5;
+
+/// Provider volume types backed by the host's local disks.
+const LOCAL_VOLUME_TYPES: &[&str] = &["l_ssd"];
+
+/// One removal call and the local volumes seen before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub attempt: u32,
+    pub local_volumes: Vec<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Removal {
+    /// No local volume was attached: nothing to remove.
+    NotNeeded,
+    Removed {
+        attempts: Vec<Attempt>,
+    },
+    Persisting {
+        attempts: Vec<Attempt>,
+        remaining: Vec<String>,
+        last_error: Option<String>,
+    },
+}
+
+/// Attempts and delay from `LOCAL_VOLUME_REMOVAL_ATTEMPTS` / `LOCAL_VOLUME_REMOVAL_INTERVAL_S`.
+pub fn retry_policy() -> (u32, Duration) {
+    let read = |key: &str, default: u64| {
+        std::env::var(key)
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(default)
+    };
+    let attempts = read("LOCAL_VOLUME_REMOVAL_ATTEMPTS", DEFAULT_ATTEMPTS as u64).max(1) as u32;
+    let interval_s = read("LOCAL_VOLUME_REMOVAL_INTERVAL_S", DEFAULT_INTERVAL_S);
+    (attempts, Duration::from_secs(interval_s))
+}
+
+async fn attached_local_volumes(
+    provider: &dyn CloudProvider,
+    zone: &str,
+    server_id: &str,
+) -> anyhow::Result<Vec<String>> {
+    Ok(provider
+        .list_attached_volumes(zone, server_id)
+        .await?
+        .into_iter()
+        .filter(|v| LOCAL_VOLUME_TYPES.contains(&v.volume_type.as_str()))
+        .map(|v| v.provider_volume_id)
+        .collect())
+}
+
+/// Remove the server's local volumes, re-checking after each call, at most `attempts` times.
+pub async fn remove_and_verify(
+    provider: &dyn CloudProvider,
+    zone: &str,
+    server_id: &str,
+    instance_type: &str,
+    pre_created_volume_id: Option<&str>,
+    attempts: u32,
+    interval: Duration,
+) -> Removal {
+    let mut done = Vec::new();
+    let mut remaining = Vec::new();
+    let mut last_error = None;
+    for attempt in 0..=attempts {
+        match attached_local_volumes(provider, zone, server_id).await {
+            Ok(local) if local.is_empty() => {
+                return if done.is_empty() {
+                    Removal::NotNeeded
+                } else {
+                    Removal::Removed { attempts: done }
+                };
+            }
+            Ok(local) => remaining = local,
+            Err(e) => last_error = Some(format!("list attached volumes: {}", e)),
+        }
+        if attempt == attempts {
+            break;
+        }
+        let error = provider
+            .remove_local_volumes(zone, server_id, instance_type, pre_created_volume_id)
+            .await
+            .err()
+            .map(|e| e.to_string());
+        if error.is_some() {
+            last_error = error.clone();
+        }
+        done.push(Attempt {
+            attempt: attempt + 1,
+            local_volumes: remaining.clone(),
+            error,
+        });
+        // Give the provider time to apply the detach before checking again.
+        tokio::time::sleep(interval).await;
+    }
+    Removal::Persisting {
+        attempts: done,
+        remaining,
+        last_error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TestProvider;
+    use inventiv_providers::inventory;
+    use std::sync::atomic::Ordering;
+
+    /// A server with a boot SBS volume and a local volume that goes away after `removed_after`
+    /// removal calls (never when `None`).
+    fn provider(removed_after: Option<usize>) -> TestProvider {
+        let volume = |id: &str, volume_type: &str, boot: bool| inventory::AttachedVolume {
+            provider_volume_id: id.to_string(),
+            provider_volume_name: None,
+            volume_type: volume_type.to_string(),
+            size_bytes: None,
+            boot,
+        };
+        TestProvider {
+            attached_volumes: vec![
+                volume("boot-sbs", "sbs_volume", true),
+                volume("local-1", "l_ssd", false),
+            ],
+            local_volumes_removed_after: removed_after,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn local_volume_gone_on_second_check_is_removed() {
+        let p = provider(Some(1));
+        let outcome =
+            remove_and_verify(&p, "fr-par-2", "srv", "L4-1-24G", None, 3, Duration::ZERO).await;
+        assert_eq!(
+            outcome,
+            Removal::Removed {
+                attempts: vec![Attempt {
+                    attempt: 1,
+                    local_volumes: vec!["local-1".to_string()],
+                    error: None,
+                }]
+            }
+        );
+        assert_eq!(p.calls.local_volume_removals.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn persisting_local_volume_fails_after_bounded_attempts() {
+        let p = provider(None);
+        let outcome =
+            remove_and_verify(&p, "fr-par-2", "srv", "L4-1-24G", None, 2, Duration::ZERO).await;
+        let Removal::Persisting {
+            attempts,
+            remaining,
+            last_error,
+        } = outcome
+        else {
+            panic!("expected persisting local volumes, got {:?}", outcome);
+        };
+        assert_eq!(attempts.len(), 2);
+        assert_eq!(remaining, vec!["local-1".to_string()]);
+        assert_eq!(last_error, None);
+        assert_eq!(p.calls.local_volume_removals.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn no_local_volume_needs_no_removal() {
+        let p = provider(Some(0));
+        let outcome =
+            remove_and_verify(&p, "fr-par-2", "srv", "L4-1-24G", None, 3, Duration::ZERO).await;
+        assert_eq!(outcome, Removal::NotNeeded);
+        assert_eq!(p.calls.local_volume_removals.load(Ordering::SeqCst), 0);
+    }
+}
@@ -53,6 +53,32 @@ pub async fn log_event_with_metadata(
     Ok(log_id)
 }
 
+/// Log an event that is not tied to an instance (e.g. catalog sync).
+pub async fn log_global_event_with_metadata(
+    db: &Pool<Postgres>,
+    action_type: &str,
+    status: &str,
+    metadata: Option<serde_json::Value>,
+) -> Result<Uuid, sqlx::Error> {
+    let log_id = Uuid::new_v4();
+    sqlx::query(
+        "INSERT INTO action_logs (id, action_type, component, status, metadata, created_at)
+         VALUES ($1, $2, 'orchestrator', $3, $4, NOW())",
+    )
+    .bind(log_id)
+    .bind(action_type)
+    .bind(status)
+    .bind(metadata)
+    .execute(db)
+    .await?;
+
+    println!(
+        "📝 [Orchestrator] Logged: {} - {} ({})",
+        action_type, status, log_id
+    );
+    Ok(log_id)
+}
+
 /// Log event completion with duration
 pub async fn log_event_complete(
     db: &Pool<Postgres>,
@@ -1,17 +1,23 @@
 use axum::http::{HeaderMap, StatusCode};
 use axum::{
-    extract::{ConnectInfo, State},
+    extract::{ConnectInfo, Query, State},
     response::{IntoResponse, Json},
     routing::{get, post},
     Router,
 };
+use inventiv_common::bus::CHANNEL_ORCHESTRATOR_COMMANDS;
+use inventiv_common::db_pool::PoolConfig;
+use inventiv_common::worker_auth::{self, WorkerAuthResult};
+use inventiv_common::{net, WorkerStatus};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use uuid::Uuid;
+mod deep_health;
 mod finops_events;
 mod health_check_job;
+mod local_volume_removal;
 mod logger;
 mod models;
 mod provider_manager; // NEW
@@ -19,10 +25,13 @@ mod provisioning_job;
 mod recovery_job;
 mod services; // NEW
 mod terminator_job;
+mod volume_drift_job;
 mod volume_reconciliation_job;
+mod volume_verification;
+mod warm_pool_job;
 mod watch_dog_job;
+mod worker_mtls;
 // worker_storage moved to inventiv-common
-use sqlx::postgres::PgPoolOptions;
 use sqlx::{Pool, Postgres};
 use tokio::time::{sleep, Duration};
 
@@ -37,6 +46,9 @@ struct WorkerRegisterRequest {
     worker_id: Option<Uuid>,
     model_id: Option<String>,
     vllm_port: Option<i32>,
+    /// Front port (e.g. HAProxy in multi-vLLM mode); preferred over vllm_port for routing.
+    #[serde(alias = "worker_proxy_port")]
+    proxy_port: Option<i32>,
     health_port: Option<i32>,
     /// Optional: worker-reported reachable IP (useful for local/dev, and for providers where the worker is best source of truth).
     ip_address: Option<String>,
@@ -54,11 +66,17 @@ struct AgentInfo {
 struct WorkerHeartbeatRequest {
     instance_id: Uuid,
     worker_id: Option<Uuid>,
-    status: String, // starting|ready|draining (lowercase)
+    status: String, // starting|ready|draining (validated against WorkerStatus)
     model_id: Option<String>,
+    /// Model revision (HF commit sha) actually loaded by vLLM.
+    #[serde(alias = "worker_model_revision")]
+    model_revision: Option<String>,
     queue_depth: Option<i32>,
     gpu_utilization: Option<f64>,
     gpu_mem_used_mb: Option<f64>,
+    /// Optional: ports re-reported by the worker (e.g. after a restart on other ports).
+    vllm_port: Option<i32>,
+    health_port: Option<i32>,
     /// Optional: worker-reported reachable IP.
     ip_address: Option<String>,
     /// Agent version/checksum information
@@ -66,19 +84,12 @@ struct WorkerHeartbeatRequest {
     metadata: Option<serde_json::Value>,
 }
 
-fn extract_bearer(headers: &HeaderMap) -> Option<String> {
-    let Some(auth) = headers.get(axum::http::header::AUTHORIZATION) else {
-        return None;
-    };
-    let Ok(auth) = auth.to_str() else {
-        return None;
-    };
-    let auth = auth.trim();
-    let prefix = "Bearer ";
-    if auth.len() <= prefix.len() || !auth.starts_with(prefix) {
-        return None;
-    }
-    Some(auth[prefix.len()..].trim().to_string())
+#[derive(Deserialize, Debug)]
+struct WorkerDeregisterRequest {
+    instance_id: Uuid,
+    worker_id: Option<Uuid>,
+    /// Optional: why the worker is leaving (e.g. "maintenance"), recorded in the action log.
+    reason: Option<String>,
 }
 
 fn request_client_ip(headers: &HeaderMap, connect: &SocketAddr) -> String {
@@ -96,78 +107,116 @@ fn request_client_ip(headers: &HeaderMap, connect: &SocketAddr) -> String {
     connect.ip().to_string()
 }
 
-async fn verify_worker_token_db(db: &Pool<Postgres>, instance_id: Uuid, token: &str) -> bool {
-    // Compare hash in DB using pgcrypto digest; avoids adding crypto deps in Rust.
-    let ok: bool = sqlx::query_scalar(
-        r#"
-        SELECT EXISTS(
-          SELECT 1
-          FROM worker_auth_tokens
-          WHERE instance_id = $1
-            AND revoked_at IS NULL
-            AND token_hash = encode(digest($2::text, 'sha256'), 'hex')
-        )
-        "#,
-    )
-    .bind(instance_id)
-    .bind(token)
-    .fetch_one(db)
-    .await
-    .unwrap_or(false);
+async fn verify_worker_auth(
+    db: &Pool<Postgres>,
+    headers: &HeaderMap,
+    instance_id: Uuid,
+) -> WorkerAuthResult {
+    // A non-UTF8 Authorization header counts as malformed, not missing.
+    let header = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .map(|v| v.to_str().unwrap_or_default());
+    let token = match worker_auth::parse_bearer(header) {
+        Ok(t) => t,
+        Err(result) => return result,
+    };
 
-    if ok {
+    // Backward-compat: allow a global token (useful for early bringup).
+    let expected = std::env::var("WORKER_AUTH_TOKEN").unwrap_or_default();
+    if !expected.trim().is_empty() && token == expected.trim() {
+        return WorkerAuthResult::Ok;
+    }
+
+    let result = worker_auth::check_worker_token(db, instance_id, &token).await;
+    if result.is_ok() {
+        // First authenticated use proves the worker received its token: drop the retry copy.
         let _ = sqlx::query(
-            "UPDATE worker_auth_tokens SET last_seen_at = NOW() WHERE instance_id = $1",
+            "UPDATE worker_auth_tokens SET last_seen_at = NOW(), bootstrap_token = NULL WHERE instance_id = $1",
         )
         .bind(instance_id)
         .execute(db)
         .await;
     }
+    result
+}
+
+/// 401 with a `reason` telling the worker what to fix.
+fn worker_auth_error(result: WorkerAuthResult) -> axum::response::Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(json!({"error": "unauthorized", "reason": result.reason()})),
+    )
+        .into_response()
+}
+
+fn worker_bootstrap_window_seconds() -> i64 {
+    std::env::var("WORKER_BOOTSTRAP_WINDOW_SECONDS")
+        .ok()
+        .and_then(|s| s.trim().parse::<i64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(600)
+}
+
+/// Compare the instance IP stored in DB (INET text, may carry a CIDR suffix) with the caller IP.
+fn bootstrap_ip_matches(instance_ip: &str, client_ip: &str) -> bool {
+    match (
+        net::normalize_instance_ip(instance_ip),
+        client_ip.trim().parse::<std::net::IpAddr>(),
+    ) {
+        (Some(expected), Ok(actual)) => expected == actual,
+        _ => false,
+    }
+}
 
-    ok
+/// Rule that let a tokenless worker bootstrap (logged on register).
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BootstrapRule {
+    /// Caller IP equals the instance IP.
+    ExactIp,
+    /// Caller IP inside an allowlisted NAT/egress range (`source` = provider setting or env).
+    Cidr { source: &'static str, cidr: String },
 }
 
-async fn verify_worker_auth(db: &Pool<Postgres>, headers: &HeaderMap, instance_id: Uuid) -> bool {
-    // Backward-compat: allow a global token (useful for early bringup).
-    let expected = std::env::var("WORKER_AUTH_TOKEN").unwrap_or_default();
-    if !expected.trim().is_empty() {
-        if let Some(tok) = extract_bearer(headers) {
-            if tok.trim() == expected.trim() {
-                return true;
-            }
+impl std::fmt::Display for BootstrapRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BootstrapRule::ExactIp => f.write_str("exact_ip"),
+            BootstrapRule::Cidr { source, cidr } => write!(f, "cidr({}:{})", source, cidr),
         }
     }
+}
 
-    let Some(tok) = extract_bearer(headers) else {
-        return false;
-    };
-    verify_worker_token_db(db, instance_id, &tok).await
+/// First CIDR of a comma/whitespace separated allowlist that contains the caller IP.
+fn bootstrap_cidr_match(allowlist: &str, client_ip: &str) -> Option<String> {
+    let ip = client_ip.trim().parse::<std::net::IpAddr>().ok()?;
+    allowlist
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|c| !c.is_empty())
+        .find(|c| net::cidr_contains(c, ip))
+        .map(str::to_string)
 }
 
-async fn instance_bootstrap_allowed(
+/// Global NAT allowlist for worker bootstrap (provider settings can add their own ranges).
+fn worker_bootstrap_allowed_cidrs() -> String {
+    std::env::var("WORKER_BOOTSTRAP_ALLOWED_CIDRS").unwrap_or_default()
+}
+
+async fn instance_bootstrap_ip_allowed(
     db: &Pool<Postgres>,
     instance_id: Uuid,
     client_ip: &str,
-) -> bool {
-    // Allow bootstrap when:
-    // - instance exists
-    // - no token exists yet
-    // - and client_ip matches instance.ip_address
-    let token_exists: bool = sqlx::query_scalar(
-        "SELECT EXISTS(SELECT 1 FROM worker_auth_tokens WHERE instance_id = $1 AND revoked_at IS NULL)",
-    )
-    .bind(instance_id)
-    .fetch_one(db)
-    .await
-    .unwrap_or(false);
-
-    if token_exists {
-        return false;
-    }
-
-    let row: Option<(Option<String>, Option<String>)> = sqlx::query_as(
+) -> Option<BootstrapRule> {
+    // Allow bootstrap when the instance exists and the client IP either:
+    // - matches instance.ip_address, or
+    // - falls in the provider's WORKER_BOOTSTRAP_ALLOWED_CIDRS (instance organization) or the env list.
+    let row: Option<(Option<String>, Option<String>, Option<String>)> = sqlx::query_as(
         r#"
-        SELECT i.ip_address::text as ip, p.code as provider_code
+        SELECT i.ip_address::text as ip, p.code as provider_code,
+               (SELECT NULLIF(btrim(s.value_text), '')
+                FROM provider_settings s
+                WHERE s.provider_id = i.provider_id
+                  AND s.organization_id = i.organization_id
+                  AND s.key = 'WORKER_BOOTSTRAP_ALLOWED_CIDRS') as provider_cidrs
         FROM instances i
         JOIN providers p ON p.id = i.provider_id
         WHERE i.id = $1
@@ -179,16 +228,58 @@ async fn instance_bootstrap_allowed(
     .ok()
     .flatten();
 
-    let Some((ip_opt, provider_code_opt)) = row else {
-        return false;
+    let (ip_opt, provider_code_opt, provider_cidrs) = row?;
+
+    let rule = if ip_opt
+        .as_deref()
+        .is_some_and(|ip| bootstrap_ip_matches(ip, client_ip))
+    {
+        BootstrapRule::ExactIp
+    } else if let Some(cidr) = provider_cidrs
+        .as_deref()
+        .and_then(|list| bootstrap_cidr_match(list, client_ip))
+    {
+        BootstrapRule::Cidr {
+            source: "provider",
+            cidr,
+        }
+    } else {
+        let cidr = bootstrap_cidr_match(&worker_bootstrap_allowed_cidrs(), client_ip)?;
+        BootstrapRule::Cidr {
+            source: "global",
+            cidr,
+        }
     };
+    println!(
+        "🔓 [Worker] BOOTSTRAP allowed: instance_id={} provider={:?} client_ip={} rule={}",
+        instance_id, provider_code_opt, client_ip, rule
+    );
+    Some(rule)
+}
 
-    let _ = provider_code_opt; // keep for future audit logging if needed
-    let Some(ip) = ip_opt else {
-        return false;
-    };
-    let clean_ip = ip.split('/').next().unwrap_or(ip.as_str()).trim();
-    clean_ip == client_ip.trim()
+/// Return the token issued by a previous bootstrap for this instance, if the worker never used it
+/// and we are still inside the bootstrap window (the register response was most likely lost).
+async fn reclaim_bootstrap_token(
+    db: &Pool<Postgres>,
+    instance_id: Uuid,
+) -> Option<(String, String)> {
+    sqlx::query_as::<Postgres, (String, String)>(
+        r#"
+        SELECT bootstrap_token, token_prefix
+        FROM worker_auth_tokens
+        WHERE instance_id = $1
+          AND revoked_at IS NULL
+          AND last_seen_at IS NULL
+          AND bootstrap_token IS NOT NULL
+          AND created_at > NOW() - ($2::bigint * INTERVAL '1 second')
+        "#,
+    )
+    .bind(instance_id)
+    .bind(worker_bootstrap_window_seconds())
+    .fetch_optional(db)
+    .await
+    .ok()
+    .flatten()
 }
 
 async fn issue_worker_token(
@@ -202,8 +293,8 @@ async fn issue_worker_token(
 
     let res = sqlx::query(
         r#"
-        INSERT INTO worker_auth_tokens (instance_id, token_hash, token_prefix, worker_id, metadata)
-        VALUES ($1, encode(digest($2::text, 'sha256'), 'hex'), $3, $4, $5)
+        INSERT INTO worker_auth_tokens (instance_id, token_hash, token_prefix, worker_id, metadata, bootstrap_token)
+        VALUES ($1, encode(digest($2::text, 'sha256'), 'hex'), $3, $4, $5, $2)
         ON CONFLICT (instance_id) DO NOTHING
         "#,
     )
@@ -234,6 +325,161 @@ struct CommandProvision {
 mod health_check_flow;
 mod migrations; // NEW
 mod state_machine;
+#[cfg(test)]
+mod test_support;
+
+/// Dispatch one `orchestrator_events` message (CMD:*) to its background task.
+fn handle_orchestrator_event(state_redis: &Arc<AppState>, payload: String) {
+    println!("📩 Received Event: {}", payload);
+
+    if let Ok(event_json) = serde_json::from_str::<serde_json::Value>(&payload) {
+        let event_type = event_json["type"].as_str().unwrap_or("");
+
+        match event_type {
+            "CMD:PROVISION" => {
+                if let Ok(cmd) = serde_json::from_value::<CommandProvision>(event_json.clone()) {
+                    let instance_id = cmd.instance_id.clone();
+                    eprintln!(
+                        "📥 [Redis] Received CMD:PROVISION for instance {} (zone={}, type={})",
+                        instance_id, cmd.zone, cmd.instance_type
+                    );
+                    let pool = state_redis.db.clone();
+                    let redis_client = state_redis.redis_client.clone();
+                    tokio::spawn(async move {
+                        eprintln!(
+                            "🔵 [Redis] Spawning process_provisioning task for instance {}",
+                            instance_id
+                        );
+                        services::process_provisioning(
+                            pool,
+                            redis_client,
+                            cmd.instance_id,
+                            cmd.zone,
+                            cmd.instance_type,
+                            cmd.correlation_id,
+                        )
+                        .await;
+                        eprintln!(
+                            "🔵 [Redis] process_provisioning task completed for instance {}",
+                            instance_id
+                        );
+                    });
+                } else {
+                    eprintln!(
+                        "⚠️ [Redis] Failed to parse CMD:PROVISION event: {}",
+                        payload
+                    );
+                }
+            }
+            "CMD:TERMINATE" => {
+                if let Ok(cmd) = serde_json::from_value::<CommandTerminate>(event_json.clone()) {
+                    eprintln!(
+                        "📥 [Redis] Received CMD:TERMINATE for instance {}",
+                        cmd.instance_id
+                    );
+                    let pool = state_redis.db.clone();
+                    let redis_client = state_redis.redis_client.clone();
+                    tokio::spawn(async move {
+                        services::process_termination(
+                            pool,
+                            redis_client,
+                            cmd.instance_id,
+                            cmd.correlation_id,
+                        )
+                        .await;
+                    });
+                } else {
+                    eprintln!(
+                        "⚠️ [Redis] Failed to parse CMD:TERMINATE event: {}",
+                        payload
+                    );
+                }
+            }
+            "CMD:REINSTALL" => {
+                if let Ok(cmd) = serde_json::from_value::<CommandReinstall>(event_json.clone()) {
+                    println!("📥 Received Reinstall Command");
+                    let pool = state_redis.db.clone();
+                    let redis_client = state_redis.redis_client.clone();
+                    tokio::spawn(async move {
+                        services::process_reinstall(
+                            pool,
+                            redis_client,
+                            cmd.instance_id,
+                            cmd.correlation_id,
+                        )
+                        .await;
+                    });
+                }
+            }
+            "CMD:RESIZE" => {
+                if let Ok(cmd) = serde_json::from_value::<CommandResize>(event_json.clone()) {
+                    println!("📥 Received Resize Command");
+                    let pool = state_redis.db.clone();
+                    tokio::spawn(async move {
+                        services::process_resize(
+                            pool,
+                            cmd.instance_id,
+                            cmd.instance_type_id,
+                            cmd.correlation_id,
+                        )
+                        .await;
+                    });
+                }
+            }
+            #[cfg(feature = "provider-scaleway")]
+            "CMD:INVALIDATE_PROVIDER_CREDENTIALS" => {
+                // Optional `organization_id`: drop one organization's cached credentials, else all.
+                let organization_id = event_json
+                    .get("organization_id")
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| Uuid::parse_str(s).ok());
+                println!(
+                    "📥 Received Invalidate Provider Credentials Command (organization_id={:?})",
+                    organization_id
+                );
+                provider_manager::ProviderManager::invalidate_credentials(organization_id);
+            }
+            "CMD:SYNC_CATALOG" => {
+                // Optional `provider_code`: sync a single provider instead of everything.
+                let provider_code = event_json
+                    .get("provider_code")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string());
+                println!(
+                    "📥 Received Sync Catalog Command (provider_code={:?})",
+                    provider_code
+                );
+                let pool = state_redis.db.clone();
+                let redis_client = state_redis.redis_client.clone();
+                tokio::spawn(async move {
+                    services::process_catalog_sync(pool, redis_client, provider_code).await;
+                });
+            }
+            "CMD:RECONCILE" => {
+                let field = |k: &str| {
+                    event_json
+                        .get(k)
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                };
+                let scope = services::ReconcileScope {
+                    provider_code: field("provider_code"),
+                    zone: field("zone"),
+                };
+                println!(
+                    "📥 Received Manual Reconciliation Command (provider_code={:?}, zone={:?})",
+                    scope.provider_code, scope.zone
+                );
+                let pool = state_redis.db.clone();
+                tokio::spawn(async move {
+                    services::process_full_reconciliation(pool, scope).await;
+                });
+            }
+            _ => eprintln!("⚠️  Unknown event type: {}", event_type),
+        }
+    }
+}
 
 #[tokio::main]
 async fn main() {
@@ -243,9 +489,9 @@ async fn main() {
     let redis_url = std::env::var("REDIS_URL").expect("REDIS_URL must be set");
     let redis_client = redis::Client::open(redis_url.clone()).unwrap();
 
-    // Connect to Postgres
-    let pool = PgPoolOptions::new()
-        .max_connections(5)
+    // Connect to Postgres (DATABASE_MAX_CONNECTIONS, default 10)
+    let pool = PoolConfig::from_env(10)
+        .options()
         .connect(&database_url)
         .await
         .expect("Failed to connect to Postgres");
@@ -268,9 +514,10 @@ async fn main() {
     // Kick an initial catalog sync shortly after startup so the Settings UI has data
     // (providers/regions/zones/instance types) without manual seeding on staging/prod.
     let db_catalog = state.db.clone();
+    let redis_catalog = state.redis_client.clone();
     tokio::spawn(async move {
         tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-        services::process_catalog_sync(db_catalog).await;
+        services::process_catalog_sync(db_catalog, redis_catalog, None).await;
     });
 
     // 3. Start Scaling Engine Loop (Background Task)
@@ -280,118 +527,19 @@ async fn main() {
     });
 
     // 4. Start Event Listener (Redis Subscriber)
-    // Use dedicated PubSub connection
-    let mut pubsub = redis_client.get_async_pubsub().await.unwrap();
-    pubsub.subscribe("orchestrator_events").await.unwrap();
-    println!("🎧 Orchestrator listening on Redis channel 'orchestrator_events'...");
-
+    // Dedicated PubSub connection, re-subscribed with backoff if Redis drops it.
     let state_redis = state.clone();
     tokio::spawn(async move {
-        use futures_util::StreamExt;
-        let mut stream = pubsub.on_message();
-
-        while let Some(msg) = stream.next().await {
-            let payload: String = msg.get_payload().unwrap();
-            println!("📩 Received Event: {}", payload);
-
-            if let Ok(event_json) = serde_json::from_str::<serde_json::Value>(&payload) {
-                let event_type = event_json["type"].as_str().unwrap_or("");
-
-                match event_type {
-                    "CMD:PROVISION" => {
-                        if let Ok(cmd) =
-                            serde_json::from_value::<CommandProvision>(event_json.clone())
-                        {
-                            let instance_id = cmd.instance_id.clone();
-                            eprintln!("📥 [Redis] Received CMD:PROVISION for instance {} (zone={}, type={})", 
-                                instance_id, cmd.zone, cmd.instance_type);
-                            let pool = state_redis.db.clone();
-                            let redis_client = state_redis.redis_client.clone();
-                            tokio::spawn(async move {
-                                eprintln!(
-                                    "🔵 [Redis] Spawning process_provisioning task for instance {}",
-                                    instance_id
-                                );
-                                services::process_provisioning(
-                                    pool,
-                                    redis_client,
-                                    cmd.instance_id,
-                                    cmd.zone,
-                                    cmd.instance_type,
-                                    cmd.correlation_id,
-                                )
-                                .await;
-                                eprintln!("🔵 [Redis] process_provisioning task completed for instance {}", instance_id);
-                            });
-                        } else {
-                            eprintln!(
-                                "⚠️ [Redis] Failed to parse CMD:PROVISION event: {}",
-                                payload
-                            );
-                        }
-                    }
-                    "CMD:TERMINATE" => {
-                        if let Ok(cmd) =
-                            serde_json::from_value::<CommandTerminate>(event_json.clone())
-                        {
-                            eprintln!(
-                                "📥 [Redis] Received CMD:TERMINATE for instance {}",
-                                cmd.instance_id
-                            );
-                            let pool = state_redis.db.clone();
-                            let redis_client = state_redis.redis_client.clone();
-                            tokio::spawn(async move {
-                                services::process_termination(
-                                    pool,
-                                    redis_client,
-                                    cmd.instance_id,
-                                    cmd.correlation_id,
-                                )
-                                .await;
-                            });
-                        } else {
-                            eprintln!(
-                                "⚠️ [Redis] Failed to parse CMD:TERMINATE event: {}",
-                                payload
-                            );
-                        }
-                    }
-                    "CMD:REINSTALL" => {
-                        if let Ok(cmd) =
-                            serde_json::from_value::<CommandReinstall>(event_json.clone())
-                        {
-                            println!("📥 Received Reinstall Command");
-                            let pool = state_redis.db.clone();
-                            let redis_client = state_redis.redis_client.clone();
-                            tokio::spawn(async move {
-                                services::process_reinstall(
-                                    pool,
-                                    redis_client,
-                                    cmd.instance_id,
-                                    cmd.correlation_id,
-                                )
-                                .await;
-                            });
-                        }
-                    }
-                    "CMD:SYNC_CATALOG" => {
-                        println!("📥 Received Sync Catalog Command");
-                        let pool = state_redis.db.clone();
-                        tokio::spawn(async move {
-                            services::process_catalog_sync(pool).await;
-                        });
-                    }
-                    "CMD:RECONCILE" => {
-                        println!("📥 Received Manual Reconciliation Command");
-                        let pool = state_redis.db.clone();
-                        tokio::spawn(async move {
-                            services::process_full_reconciliation(pool).await;
-                        });
-                    }
-                    _ => eprintln!("⚠️  Unknown event type: {}", event_type),
-                }
-            }
-        }
+        inventiv_common::pubsub::consume_channel(
+            &state_redis.redis_client,
+            CHANNEL_ORCHESTRATOR_COMMANDS,
+            inventiv_common::pubsub::Backoff::default(),
+            |payload| {
+                handle_orchestrator_event(&state_redis, payload);
+                std::future::ready(())
+            },
+        )
+        .await;
     });
 
     // job-watch-dog (READY)
@@ -434,17 +582,53 @@ async fn main() {
         volume_reconciliation_job::run(db_volume_reconciliation).await;
     });
 
+    // job-volume-drift (provider volumes vs tracked instance_volumes, observability only)
+    let db_volume_drift = state.db.clone();
+    tokio::spawn(async move {
+        volume_drift_job::run(db_volume_drift).await;
+    });
+
+    // job-warm-pool (keep models.min_instances live instances per model)
+    let db_warm_pool = state.db.clone();
+    let redis_warm_pool = state.redis_client.clone();
+    tokio::spawn(async move {
+        warm_pool_job::run(db_warm_pool, redis_warm_pool).await;
+    });
+
+    // Optional worker mTLS (WORKER_MTLS_*): unset keeps the plain HTTP behavior.
+    let worker_mtls = worker_mtls::WorkerMtlsConfig::from_env()
+        .unwrap_or_else(|e| panic!("Invalid worker mTLS configuration: {}", e));
+    let mut worker_routes = Router::new()
+        .route("/internal/worker/register", post(worker_register))
+        .route("/internal/worker/heartbeat", post(worker_heartbeat))
+        .route("/internal/worker/deregister", post(worker_deregister))
+        .route("/internal/worker/config", get(worker_config));
+    if worker_mtls.is_some() {
+        worker_routes =
+            worker_routes.route_layer(axum::middleware::from_fn(worker_mtls::require_client_cert));
+    }
+
     // 5. Start HTTP Server (Admin API - Simplified for internal health/debug only)
     let app = Router::new()
         .route("/", get(root))
         .route("/admin/status", get(get_status))
-        .route("/internal/worker/register", post(worker_register))
-        .route("/internal/worker/heartbeat", post(worker_heartbeat))
+        .route("/healthz/deep", get(deep_health::get_deep_health))
+        .merge(worker_routes)
         // NO MORE PUBLIC API FOR INSTANCES
         // .route("/instances", get(list_instances))
         // .route("/instances/:id", axum::routing::delete(delete_instance_handler))
         .with_state(state.clone());
 
+    if let Some(config) = worker_mtls {
+        let acceptor = config
+            .acceptor()
+            .unwrap_or_else(|e| panic!("Invalid worker mTLS configuration: {}", e));
+        let tls_addr = SocketAddr::from(([0, 0, 0, 0], config.port));
+        println!("Orchestrator worker mTLS listening on {}", tls_addr);
+        let tls_listener = tokio::net::TcpListener::bind(tls_addr).await.unwrap();
+        tokio::spawn(worker_mtls::serve(tls_listener, acceptor, app.clone()));
+    }
+
     let addr = SocketAddr::from(([0, 0, 0, 0], 8001));
     println!("Orchestrator listening on {}", addr);
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
@@ -472,6 +656,13 @@ struct CommandReinstall {
     correlation_id: Option<String>,
 }
 
+#[derive(serde::Deserialize, Debug)]
+struct CommandResize {
+    instance_id: String,
+    instance_type_id: String,
+    correlation_id: Option<String>,
+}
+
 // DELETED HANDLERS (Moved to services.rs)
 
 async fn get_status(State(state): State<Arc<AppState>>) -> impl IntoResponse {
@@ -496,20 +687,33 @@ async fn worker_register(
 ) -> impl IntoResponse {
     let client_ip = request_client_ip(&headers, &connect);
 
+    // Reject bad ports before any bootstrap token is issued.
+    if let Err(e) = validate_worker_ports(&[
+        ("vllm_port", payload.vllm_port),
+        ("health_port", payload.health_port),
+        ("proxy_port", payload.proxy_port),
+    ]) {
+        return e.into_response();
+    }
+
     // Either:
     // - authenticated (existing token or global token), OR
-    // - bootstrap (no token yet + IP matches instance/ip) -> issue token and return it.
-    let authed = verify_worker_auth(&state.db, &headers, payload.instance_id).await;
+    // - bootstrap (IP matches instance/ip):
+    //   - no token yet -> issue token and return it
+    //   - token issued but never used, within bootstrap window -> return the same token (retry)
+    let auth = verify_worker_auth(&state.db, &headers, payload.instance_id).await;
     let mut issued_token: Option<(String, String)> = None;
-    if !authed {
+    if matches!(auth, WorkerAuthResult::Revoked) {
+        // A known token that is no longer valid must not fall back to bootstrap.
+        return worker_auth_error(auth);
+    }
+    if !auth.is_ok() {
         let can_bootstrap =
-            instance_bootstrap_allowed(&state.db, payload.instance_id, &client_ip).await;
+            instance_bootstrap_ip_allowed(&state.db, payload.instance_id, &client_ip)
+                .await
+                .is_some();
         if !can_bootstrap {
-            return (
-                StatusCode::UNAUTHORIZED,
-                Json(json!({"error": "unauthorized"})),
-            )
-                .into_response();
+            return worker_auth_error(auth);
         }
         issued_token = issue_worker_token(
             &state.db,
@@ -518,6 +722,15 @@ async fn worker_register(
             payload.metadata.clone(),
         )
         .await;
+        if issued_token.is_none() {
+            issued_token = reclaim_bootstrap_token(&state.db, payload.instance_id).await;
+            if issued_token.is_some() {
+                println!(
+                    "🔁 [Worker] REGISTER retry: returning existing bootstrap token for instance_id={}",
+                    payload.instance_id
+                );
+            }
+        }
         if issued_token.is_none() {
             return (
                 StatusCode::CONFLICT,
@@ -528,8 +741,8 @@ async fn worker_register(
     }
 
     println!(
-        "🧩 [Worker] REGISTER: instance_id={} worker_id={:?} model_id={:?} health_port={:?} vllm_port={:?} ip={:?} client_ip={}",
-        payload.instance_id, payload.worker_id, payload.model_id, payload.health_port, payload.vllm_port, payload.ip_address, client_ip
+        "🧩 [Worker] REGISTER: instance_id={} worker_id={:?} model_id={:?} health_port={:?} vllm_port={:?} proxy_port={:?} ip={:?} client_ip={}",
+        payload.instance_id, payload.worker_id, payload.model_id, payload.health_port, payload.vllm_port, payload.proxy_port, payload.ip_address, client_ip
     );
 
     // Log payload summary for debugging
@@ -539,6 +752,7 @@ async fn worker_register(
         "model_id": payload.model_id,
         "health_port": payload.health_port,
         "vllm_port": payload.vllm_port,
+        "proxy_port": payload.proxy_port,
         "ip_address": payload.ip_address,
         "has_metadata": payload.metadata.is_some()
     });
@@ -554,6 +768,7 @@ async fn worker_register(
             worker_model_id = COALESCE($2, worker_model_id),
             worker_vllm_port = COALESCE($3, worker_vllm_port),
             worker_health_port = COALESCE($4, worker_health_port),
+            worker_proxy_port = COALESCE($7, worker_proxy_port),
             ip_address = CASE
               WHEN ip_address IS NULL AND $5 IS NOT NULL AND btrim($5) <> '' THEN $5::inet
               ELSE ip_address
@@ -587,6 +802,7 @@ async fn worker_register(
     .bind(payload.health_port)
     .bind(payload.ip_address)
     .bind(payload.metadata)
+    .bind(payload.proxy_port)
     .execute(&state.db)
     .await;
 
@@ -615,20 +831,60 @@ async fn worker_register(
     }
 }
 
+/// Heartbeat `status` must be a known worker status; anything else is a client error.
+fn parse_heartbeat_status(
+    raw: &str,
+) -> Result<WorkerStatus, (StatusCode, Json<serde_json::Value>)> {
+    WorkerStatus::parse(raw).ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_worker_status",
+                "message": format!("unknown worker status '{}' (expected starting|ready|draining)", raw.trim())
+            })),
+        )
+    })
+}
+
+/// Worker-reported ports must be valid TCP ports (missing ones keep the stored value), otherwise
+/// routing would build unreachable worker URLs.
+fn validate_worker_ports(
+    ports: &[(&str, Option<i32>)],
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    for (name, port) in ports {
+        if let Some(p) = port.filter(|p| !(1..=65535).contains(p)) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid_worker_port",
+                    "message": format!("{} must be between 1 and 65535 (got {})", name, p)
+                })),
+            ));
+        }
+    }
+    Ok(())
+}
+
 async fn worker_heartbeat(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Json(payload): Json<WorkerHeartbeatRequest>,
 ) -> impl IntoResponse {
-    if !verify_worker_auth(&state.db, &headers, payload.instance_id).await {
-        return (
-            StatusCode::UNAUTHORIZED,
-            Json(json!({"error": "unauthorized"})),
-        )
-            .into_response();
+    let auth = verify_worker_auth(&state.db, &headers, payload.instance_id).await;
+    if !auth.is_ok() {
+        return worker_auth_error(auth);
     }
 
-    let status = payload.status.to_ascii_lowercase();
+    let status = match parse_heartbeat_status(&payload.status) {
+        Ok(s) => s,
+        Err(e) => return e.into_response(),
+    };
+    if let Err(e) = validate_worker_ports(&[
+        ("vllm_port", payload.vllm_port),
+        ("health_port", payload.health_port),
+    ]) {
+        return e.into_response();
+    }
 
     // Log agent info if present
     if let Some(agent_info) = &payload.agent_info {
@@ -650,8 +906,9 @@ async fn worker_heartbeat(
     let payload_summary = json!({
         "instance_id": payload.instance_id,
         "worker_id": payload.worker_id,
-        "status": status,
+        "status": status.as_str(),
         "model_id": payload.model_id,
+        "model_revision": payload.model_revision,
         "gpu_utilization": payload.gpu_utilization,
         "gpu_mem_used_mb": payload.gpu_mem_used_mb,
         "queue_depth": payload.queue_depth,
@@ -677,14 +934,40 @@ async fn worker_heartbeat(
         });
     }
 
+    let model_revision = payload
+        .model_revision
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .map(str::to_string);
+    // Only re-check revision consistency when the reported revision changes.
+    let previous_revision: Option<String> = if model_revision.is_some() {
+        sqlx::query_scalar("SELECT worker_model_revision FROM instances WHERE id = $1")
+            .bind(payload.instance_id)
+            .fetch_optional(&state.db)
+            .await
+            .ok()
+            .flatten()
+            .flatten()
+    } else {
+        None
+    };
+
     let res = sqlx::query(
         r#"
         UPDATE instances
         SET worker_last_heartbeat = NOW(),
             worker_status = $2,
             worker_model_id = COALESCE($3, worker_model_id),
+            -- A model switch invalidates the previous revision.
+            worker_model_revision = CASE
+              WHEN $3 IS NOT NULL AND $3 IS DISTINCT FROM worker_model_id THEN $8
+              ELSE COALESCE($8, worker_model_revision)
+            END,
             worker_queue_depth = COALESCE($4, worker_queue_depth),
             worker_gpu_utilization = COALESCE($5, worker_gpu_utilization),
+            worker_vllm_port = COALESCE($9, worker_vllm_port),
+            worker_health_port = COALESCE($10, worker_health_port),
             ip_address = CASE
               WHEN ip_address IS NULL AND $6 IS NOT NULL AND btrim($6) <> '' THEN $6::inet
               ELSE ip_address
@@ -712,15 +995,26 @@ async fn worker_heartbeat(
         "#,
     )
     .bind(payload.instance_id)
-    .bind(status)
+    .bind(status.as_str())
     .bind(payload.model_id)
     .bind(payload.queue_depth)
     .bind(payload.gpu_utilization)
     .bind(payload.ip_address.clone())
     .bind(meta_clone.clone())
+    .bind(model_revision.as_deref())
+    .bind(payload.vllm_port)
+    .bind(payload.health_port)
     .execute(&state.db)
     .await;
 
+    if let Some(revision) = model_revision.as_deref() {
+        if res.as_ref().is_ok_and(|r| r.rows_affected() > 0)
+            && previous_revision.as_deref() != Some(revision)
+        {
+            warn_on_model_revision_mismatch(&state.db, payload.instance_id, revision).await;
+        }
+    }
+
     // Insert time series GPU samples (nvtop-like dashboard).
     // Prefer per-GPU list in metadata.gpus, fallback to aggregate fields.
     // Best-effort only: do not fail heartbeat on metrics insert.
@@ -963,6 +1257,209 @@ async fn worker_heartbeat(
     }
 }
 
+/// Clean worker shutdown: mark the worker `draining` right away so the API proxy stops routing to
+/// it, instead of waiting for the stale window. A later `ready` heartbeat makes it routable again.
+async fn worker_deregister(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(payload): Json<WorkerDeregisterRequest>,
+) -> impl IntoResponse {
+    let auth = verify_worker_auth(&state.db, &headers, payload.instance_id).await;
+    if !auth.is_ok() {
+        return worker_auth_error(auth);
+    }
+
+    println!(
+        "👋 [Worker] DEREGISTER: instance_id={} worker_id={:?} reason={:?}",
+        payload.instance_id, payload.worker_id, payload.reason
+    );
+
+    let res = sqlx::query("UPDATE instances SET worker_status = $2 WHERE id = $1")
+        .bind(payload.instance_id)
+        .bind(WorkerStatus::Draining.as_str())
+        .execute(&state.db)
+        .await;
+
+    match res {
+        Ok(r) if r.rows_affected() > 0 => {
+            let _ = logger::log_event_with_metadata(
+                &state.db,
+                "WORKER_DEREGISTERED",
+                "success",
+                payload.instance_id,
+                None,
+                Some(json!({
+                    "worker_id": payload.worker_id,
+                    "reason": payload.reason,
+                })),
+            )
+            .await;
+            (StatusCode::OK, Json(json!({"status": "ok"}))).into_response()
+        }
+        Ok(_) => (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "instance_not_found"})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": "db_error", "message": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
+/// Log WORKER_MODEL_REVISION_MISMATCH when other live instances serving the same
+/// `worker_model_id` report a different revision. Returns true when a mismatch was logged.
+async fn warn_on_model_revision_mismatch(
+    db: &Pool<Postgres>,
+    instance_id: Uuid,
+    revision: &str,
+) -> bool {
+    let others: Vec<(Uuid, String, String)> = sqlx::query_as(
+        r#"
+        SELECT o.id, o.worker_model_id, o.worker_model_revision
+        FROM instances i
+        JOIN instances o ON o.worker_model_id = i.worker_model_id AND o.id <> i.id
+        WHERE i.id = $1
+          AND o.worker_model_revision IS NOT NULL
+          AND o.worker_model_revision <> $2
+          AND o.is_archived = false
+          AND o.status::text NOT IN ('terminated', 'terminating', 'archived')
+        ORDER BY o.worker_last_heartbeat DESC NULLS LAST
+        "#,
+    )
+    .bind(instance_id)
+    .bind(revision)
+    .fetch_all(db)
+    .await
+    .unwrap_or_default();
+
+    let Some((_, model_id, _)) = others.first() else {
+        return false;
+    };
+    eprintln!(
+        "⚠️ [Worker] MODEL_REVISION_MISMATCH: instance_id={} model_id={} revision={} other_instances={}",
+        instance_id,
+        model_id,
+        revision,
+        others.len()
+    );
+    let metadata = json!({
+        "model_id": model_id,
+        "revision": revision,
+        "other_instances": others
+            .iter()
+            .map(|(id, _, rev)| json!({"instance_id": id, "revision": rev}))
+            .collect::<Vec<_>>(),
+    });
+    let _ = logger::log_event_with_metadata(
+        db,
+        "WORKER_MODEL_REVISION_MISMATCH",
+        "failed",
+        instance_id,
+        Some("instances serving this model report different revisions"),
+        Some(metadata),
+    )
+    .await;
+    true
+}
+
+#[derive(Deserialize, Debug)]
+struct WorkerConfigQuery {
+    instance_id: Uuid,
+}
+
+/// Desired worker state, polled by the worker agent. `generation` is bumped (DB trigger) whenever
+/// the target model or vLLM args change; the worker re-applies its config when it differs from
+/// the last applied generation.
+#[derive(Serialize, Debug, sqlx::FromRow)]
+struct WorkerConfig {
+    instance_id: Uuid,
+    model_id: Option<String>,
+    vllm_args: Vec<String>,
+    /// Where the worker should read its HF token from (never the token itself).
+    #[sqlx(skip)]
+    hf_token_ref: Option<String>,
+    generation: i64,
+}
+
+/// Reference to the HF token source configured on the control plane (e.g. `env:WORKER_HF_TOKEN`).
+fn worker_hf_token_ref() -> Option<String> {
+    for name in [
+        "WORKER_HF_TOKEN",
+        "HUGGINGFACE_TOKEN",
+        "HUGGING_FACE_HUB_TOKEN",
+        "HUGGINGFACE_HUB_TOKEN",
+        "HF_TOKEN",
+    ] {
+        if std::env::var(name).is_ok_and(|v| !v.trim().is_empty()) {
+            return Some(format!("env:{}", name));
+        }
+    }
+    std::env::var("WORKER_HF_TOKEN_FILE")
+        .ok()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .map(|p| format!("file:{}", p))
+}
+
+async fn load_worker_config(
+    db: &Pool<Postgres>,
+    instance_id: Uuid,
+) -> Result<Option<WorkerConfig>, sqlx::Error> {
+    let row: Option<WorkerConfig> = sqlx::query_as(
+        r#"
+        SELECT i.id AS instance_id,
+               NULLIF(btrim(m.model_id), '') AS model_id,
+               i.worker_vllm_args AS vllm_args,
+               i.worker_config_generation AS generation
+        FROM instances i
+        LEFT JOIN models m ON m.id = i.model_id
+        WHERE i.id = $1
+        "#,
+    )
+    .bind(instance_id)
+    .fetch_optional(db)
+    .await?;
+
+    Ok(row.map(|mut cfg| {
+        // Same fallback as provisioning when the instance has no model assigned.
+        cfg.model_id = cfg.model_id.or_else(|| {
+            std::env::var("WORKER_MODEL_ID")
+                .ok()
+                .filter(|s| !s.trim().is_empty())
+        });
+        cfg.hf_token_ref = worker_hf_token_ref();
+        cfg
+    }))
+}
+
+async fn worker_config(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<WorkerConfigQuery>,
+) -> impl IntoResponse {
+    let auth = verify_worker_auth(&state.db, &headers, query.instance_id).await;
+    if !auth.is_ok() {
+        return worker_auth_error(auth);
+    }
+
+    match load_worker_config(&state.db, query.instance_id).await {
+        Ok(Some(cfg)) => (StatusCode::OK, Json(cfg)).into_response(),
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "instance_not_found"})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": "db_error", "message": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
 async fn scaling_engine_loop(state: Arc<AppState>) {
     println!("Scaling Engine Started");
     loop {
@@ -974,3 +1471,797 @@ async fn scaling_engine_loop(state: Arc<AppState>) {
         println!("Scaler Heartbeat: {} total instances managed.", count);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::setup_pool;
+
+    #[test]
+    fn bootstrap_ip_matches_strips_cidr_suffix() {
+        assert!(bootstrap_ip_matches("10.0.0.5/32", "10.0.0.5"));
+        assert!(bootstrap_ip_matches("10.0.0.5", " 10.0.0.5 "));
+    }
+
+    #[test]
+    fn bootstrap_ip_matches_rejects_other_ip() {
+        // A retried register from another host must not be able to reclaim the bootstrap token.
+        assert!(!bootstrap_ip_matches("10.0.0.5/32", "10.0.0.6"));
+        assert!(!bootstrap_ip_matches("", ""));
+    }
+
+    #[test]
+    fn bootstrap_cidr_match_returns_matching_range() {
+        let list = "203.0.113.0/24, 100.64.0.0/10";
+        assert_eq!(
+            bootstrap_cidr_match(list, "100.70.1.2").as_deref(),
+            Some("100.64.0.0/10")
+        );
+        assert_eq!(bootstrap_cidr_match(list, "198.51.100.7"), None);
+        assert_eq!(bootstrap_cidr_match("", "100.70.1.2"), None);
+        assert_eq!(bootstrap_cidr_match(list, "not-an-ip"), None);
+    }
+
+    #[test]
+    fn heartbeat_status_rejects_unknown_values() {
+        let (code, body) = parse_heartbeat_status("warming").unwrap_err();
+        assert_eq!(code, StatusCode::BAD_REQUEST);
+        assert_eq!(body.0["error"], "invalid_worker_status");
+    }
+
+    #[test]
+    fn heartbeat_status_accepts_known_values() {
+        assert_eq!(
+            parse_heartbeat_status("READY").unwrap(),
+            WorkerStatus::Ready
+        );
+        assert_eq!(
+            parse_heartbeat_status(" draining ").unwrap(),
+            WorkerStatus::Draining
+        );
+    }
+
+    #[test]
+    fn worker_ports_must_be_in_tcp_range() {
+        assert!(validate_worker_ports(&[("vllm_port", Some(8000)), ("health_port", None)]).is_ok());
+        assert!(validate_worker_ports(&[("vllm_port", Some(65535))]).is_ok());
+        for bad in [0, -1, 65536] {
+            let (code, body) = validate_worker_ports(&[("health_port", Some(bad))]).unwrap_err();
+            assert_eq!(code, StatusCode::BAD_REQUEST);
+            assert_eq!(body.0["error"], "invalid_worker_port");
+        }
+    }
+
+    #[tokio::test]
+    async fn worker_config_returns_target_model_and_bumps_generation() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(provider_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers LIMIT 1")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: no provider seeded");
+            return;
+        };
+
+        let suffix = Uuid::new_v4();
+        let first_model = Uuid::new_v4();
+        let second_model = Uuid::new_v4();
+        for (id, name) in [
+            (first_model, format!("test-org/config-a-{}", suffix)),
+            (second_model, format!("test-org/config-b-{}", suffix)),
+        ] {
+            sqlx::query(
+                "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, metadata, created_at, updated_at)
+                 VALUES ($1, $2, $2, 8, 4096, true, '{}'::jsonb, NOW(), NOW())",
+            )
+            .bind(id)
+            .bind(name)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let instance_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, model_id, status, created_at, gpu_profile, worker_vllm_args)
+             VALUES ($1, $2, $3, 'ready', NOW(), '{}', ARRAY['--max-model-len', '4096'])",
+        )
+        .bind(instance_id)
+        .bind(provider_id)
+        .bind(first_model)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let before = load_worker_config(&pool, instance_id)
+            .await
+            .unwrap()
+            .expect("config for mock instance");
+        assert_eq!(
+            before.model_id,
+            Some(format!("test-org/config-a-{}", suffix))
+        );
+        assert_eq!(before.vllm_args, vec!["--max-model-len", "4096"]);
+
+        sqlx::query("UPDATE instances SET model_id = $2 WHERE id = $1")
+            .bind(instance_id)
+            .bind(second_model)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let after = load_worker_config(&pool, instance_id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM models WHERE id = ANY($1)")
+            .bind(vec![first_model, second_model])
+            .execute(&pool)
+            .await;
+
+        assert_eq!(
+            after.model_id,
+            Some(format!("test-org/config-b-{}", suffix))
+        );
+        assert_eq!(after.generation, before.generation + 1);
+        assert!(load_worker_config(&pool, Uuid::new_v4())
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn heartbeat_with_different_revisions_logs_mismatch() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(provider_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers LIMIT 1")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: no provider seeded");
+            return;
+        };
+        let state = Arc::new(AppState {
+            db: pool.clone(),
+            redis_client: redis::Client::open("redis://127.0.0.1/").unwrap(),
+        });
+
+        let model_id = format!("test-org/revision-{}", Uuid::new_v4());
+        let instances = [Uuid::new_v4(), Uuid::new_v4()];
+        let mut statuses = Vec::new();
+        for (instance_id, revision) in instances.iter().zip(["rev-aaa", "rev-bbb"]) {
+            sqlx::query(
+                "INSERT INTO instances (id, provider_id, status, created_at, gpu_profile)
+                 VALUES ($1, $2, 'ready', NOW(), '{}')",
+            )
+            .bind(instance_id)
+            .bind(provider_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+            let (token, _) = issue_worker_token(&pool, *instance_id, None, None)
+                .await
+                .expect("worker token");
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                axum::http::header::AUTHORIZATION,
+                format!("Bearer {}", token).parse().unwrap(),
+            );
+            let payload = WorkerHeartbeatRequest {
+                instance_id: *instance_id,
+                worker_id: None,
+                status: "ready".to_string(),
+                model_id: Some(model_id.clone()),
+                model_revision: Some(revision.to_string()),
+                queue_depth: None,
+                gpu_utilization: None,
+                gpu_mem_used_mb: None,
+                vllm_port: None,
+                health_port: None,
+                ip_address: None,
+                agent_info: None,
+                metadata: None,
+            };
+            let resp = worker_heartbeat(State(state.clone()), headers, Json(payload))
+                .await
+                .into_response();
+            statuses.push(resp.status());
+        }
+
+        let stored: Vec<Option<String>> = sqlx::query_scalar(
+            "SELECT worker_model_revision FROM instances WHERE id = ANY($1) ORDER BY worker_model_revision",
+        )
+        .bind(instances.to_vec())
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        let mismatch_logs: Vec<Uuid> = sqlx::query_scalar(
+            "SELECT instance_id FROM action_logs
+             WHERE action_type = 'WORKER_MODEL_REVISION_MISMATCH' AND instance_id = ANY($1)",
+        )
+        .bind(instances.to_vec())
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+
+        let _ = sqlx::query("DELETE FROM action_logs WHERE instance_id = ANY($1)")
+            .bind(instances.to_vec())
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM worker_auth_tokens WHERE instance_id = ANY($1)")
+            .bind(instances.to_vec())
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM gpu_samples WHERE instance_id = ANY($1)")
+            .bind(instances.to_vec())
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instances WHERE id = ANY($1)")
+            .bind(instances.to_vec())
+            .execute(&pool)
+            .await;
+
+        assert_eq!(statuses, vec![StatusCode::OK, StatusCode::OK]);
+        assert_eq!(
+            stored,
+            vec![Some("rev-aaa".to_string()), Some("rev-bbb".to_string())]
+        );
+        // Only the second heartbeat sees a conflicting revision.
+        assert_eq!(mismatch_logs, vec![instances[1]]);
+    }
+
+    #[tokio::test]
+    async fn heartbeat_with_unknown_status_is_rejected_without_update() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(provider_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers LIMIT 1")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: no provider seeded");
+            return;
+        };
+        let state = Arc::new(AppState {
+            db: pool.clone(),
+            redis_client: redis::Client::open("redis://127.0.0.1/").unwrap(),
+        });
+
+        let instance_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, status, created_at, gpu_profile, worker_status)
+             VALUES ($1, $2, 'ready', NOW(), '{}', 'ready')",
+        )
+        .bind(instance_id)
+        .bind(provider_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let (token, _) = issue_worker_token(&pool, instance_id, None, None)
+            .await
+            .expect("worker token");
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            axum::http::header::AUTHORIZATION,
+            format!("Bearer {}", token).parse().unwrap(),
+        );
+        let payload = WorkerHeartbeatRequest {
+            instance_id,
+            worker_id: None,
+            status: "warming".to_string(),
+            model_id: None,
+            model_revision: None,
+            queue_depth: Some(3),
+            gpu_utilization: None,
+            gpu_mem_used_mb: None,
+            vllm_port: None,
+            health_port: None,
+            ip_address: None,
+            agent_info: None,
+            metadata: None,
+        };
+        let resp = worker_heartbeat(State(state), headers, Json(payload))
+            .await
+            .into_response();
+        let status = resp.status();
+        let body: serde_json::Value = serde_json::from_slice(
+            &axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        let (worker_status, heartbeat, queue_depth): (
+            Option<String>,
+            Option<chrono::DateTime<chrono::Utc>>,
+            Option<i32>,
+        ) = sqlx::query_as(
+            "SELECT worker_status, worker_last_heartbeat, worker_queue_depth FROM instances WHERE id = $1",
+        )
+        .bind(instance_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let _ = sqlx::query("DELETE FROM worker_auth_tokens WHERE instance_id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "invalid_worker_status");
+        assert_eq!(worker_status.as_deref(), Some("ready"));
+        assert_eq!(heartbeat, None);
+        assert_eq!(queue_depth, None);
+    }
+
+    #[tokio::test]
+    async fn register_persists_worker_proxy_port() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(provider_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers LIMIT 1")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: no provider seeded");
+            return;
+        };
+        let state = Arc::new(AppState {
+            db: pool.clone(),
+            redis_client: redis::Client::open("redis://127.0.0.1/").unwrap(),
+        });
+
+        let instance_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, status, created_at, gpu_profile)
+             VALUES ($1, $2, 'booting', NOW(), '{}')",
+        )
+        .bind(instance_id)
+        .bind(provider_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let (token, _) = issue_worker_token(&pool, instance_id, None, None)
+            .await
+            .expect("worker token");
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            axum::http::header::AUTHORIZATION,
+            format!("Bearer {}", token).parse().unwrap(),
+        );
+        let payload: WorkerRegisterRequest = serde_json::from_value(json!({
+            "instance_id": instance_id,
+            "vllm_port": 8001,
+            "worker_proxy_port": 8080,
+        }))
+        .unwrap();
+        let resp = worker_register(
+            State(state),
+            headers,
+            ConnectInfo("127.0.0.1:50000".parse().unwrap()),
+            Json(payload),
+        )
+        .await
+        .into_response();
+
+        let ports: (Option<i32>, Option<i32>) = sqlx::query_as(
+            "SELECT worker_vllm_port, worker_proxy_port FROM instances WHERE id = $1",
+        )
+        .bind(instance_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let _ = sqlx::query("DELETE FROM worker_auth_tokens WHERE instance_id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(ports, (Some(8001), Some(8080)));
+    }
+
+    #[tokio::test]
+    async fn register_rejects_out_of_range_port() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(provider_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers LIMIT 1")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: no provider seeded");
+            return;
+        };
+        let state = Arc::new(AppState {
+            db: pool.clone(),
+            redis_client: redis::Client::open("redis://127.0.0.1/").unwrap(),
+        });
+
+        let instance_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, status, created_at, gpu_profile)
+             VALUES ($1, $2, 'booting', NOW(), '{}')",
+        )
+        .bind(instance_id)
+        .bind(provider_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let (token, _) = issue_worker_token(&pool, instance_id, None, None)
+            .await
+            .expect("worker token");
+
+        let mut statuses = Vec::new();
+        for port in [0, 8000] {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                axum::http::header::AUTHORIZATION,
+                format!("Bearer {}", token).parse().unwrap(),
+            );
+            let payload: WorkerRegisterRequest = serde_json::from_value(json!({
+                "instance_id": instance_id,
+                "vllm_port": port,
+            }))
+            .unwrap();
+            let resp = worker_register(
+                State(state.clone()),
+                headers,
+                ConnectInfo("127.0.0.1:50000".parse().unwrap()),
+                Json(payload),
+            )
+            .await
+            .into_response();
+            let port_after: Option<i32> =
+                sqlx::query_scalar("SELECT worker_vllm_port FROM instances WHERE id = $1")
+                    .bind(instance_id)
+                    .fetch_one(&pool)
+                    .await
+                    .unwrap();
+            statuses.push((resp.status(), port_after));
+        }
+
+        let _ = sqlx::query("DELETE FROM worker_auth_tokens WHERE instance_id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+
+        assert_eq!(
+            statuses,
+            vec![
+                (StatusCode::BAD_REQUEST, None),
+                (StatusCode::OK, Some(8000))
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn deregistered_worker_is_no_longer_routable() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(provider_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers LIMIT 1")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: no provider seeded");
+            return;
+        };
+        let state = Arc::new(AppState {
+            db: pool.clone(),
+            redis_client: redis::Client::open("redis://127.0.0.1/").unwrap(),
+        });
+
+        let instance_id = Uuid::new_v4();
+        let model = format!("test-org/deregister-{}", instance_id);
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, status, created_at, gpu_profile, ip_address)
+             VALUES ($1, $2, 'ready', NOW(), '{}', '127.0.0.1')",
+        )
+        .bind(instance_id)
+        .bind(provider_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let (token, _) = issue_worker_token(&pool, instance_id, None, None)
+            .await
+            .expect("worker token");
+        let auth_headers = || {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                axum::http::header::AUTHORIZATION,
+                format!("Bearer {}", token).parse().unwrap(),
+            );
+            headers
+        };
+
+        let register: WorkerRegisterRequest = serde_json::from_value(json!({
+            "instance_id": instance_id,
+            "model_id": model,
+            "vllm_port": 8000,
+        }))
+        .unwrap();
+        let registered = worker_register(
+            State(state.clone()),
+            auth_headers(),
+            ConnectInfo("127.0.0.1:50000".parse().unwrap()),
+            Json(register),
+        )
+        .await
+        .into_response()
+        .status();
+        let heartbeat: WorkerHeartbeatRequest = serde_json::from_value(json!({
+            "instance_id": instance_id,
+            "status": "ready",
+            "model_id": model,
+        }))
+        .unwrap();
+        let heartbeat = worker_heartbeat(State(state.clone()), auth_headers(), Json(heartbeat))
+            .await
+            .into_response()
+            .status();
+
+        // Same READY-worker filter as the API proxy (worker_routing::select_ready_worker_for_model).
+        let routable = || async {
+            sqlx::query_scalar::<_, i64>(
+                "SELECT COUNT(*) FROM instances
+                 WHERE id = $1 AND status::text = 'ready' AND ip_address IS NOT NULL
+                   AND (worker_status = 'ready' OR worker_status IS NULL)
+                   AND worker_model_id = $2",
+            )
+            .bind(instance_id)
+            .bind(&model)
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+        };
+        let before = routable().await;
+
+        let deregister: WorkerDeregisterRequest = serde_json::from_value(json!({
+            "instance_id": instance_id,
+            "reason": "maintenance",
+        }))
+        .unwrap();
+        let unauthenticated = worker_deregister(
+            State(state.clone()),
+            HeaderMap::new(),
+            Json(serde_json::from_value(json!({"instance_id": instance_id})).unwrap()),
+        )
+        .await
+        .into_response()
+        .status();
+        let deregistered =
+            worker_deregister(State(state.clone()), auth_headers(), Json(deregister))
+                .await
+                .into_response()
+                .status();
+        let after = routable().await;
+        let logged: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM action_logs WHERE instance_id = $1 AND action_type = 'WORKER_DEREGISTERED'",
+        )
+        .bind(instance_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        for sql in [
+            "DELETE FROM action_logs WHERE instance_id = $1",
+            "DELETE FROM worker_auth_tokens WHERE instance_id = $1",
+            "DELETE FROM instances WHERE id = $1",
+        ] {
+            let _ = sqlx::query(sql).bind(instance_id).execute(&pool).await;
+        }
+
+        assert_eq!(registered, StatusCode::OK);
+        assert_eq!(heartbeat, StatusCode::OK);
+        assert_eq!(before, 1);
+        assert_eq!(unauthenticated, StatusCode::UNAUTHORIZED);
+        assert_eq!(deregistered, StatusCode::OK);
+        assert_eq!(after, 0);
+        assert_eq!(logged, 1);
+    }
+
+    #[tokio::test]
+    async fn double_register_returns_the_same_bootstrap_token() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(provider_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers LIMIT 1")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: no provider seeded");
+            return;
+        };
+        let state = Arc::new(AppState {
+            db: pool.clone(),
+            redis_client: redis::Client::open("redis://127.0.0.1/").unwrap(),
+        });
+
+        let instance_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, status, created_at, gpu_profile, ip_address)
+             VALUES ($1, $2, 'booting', NOW(), '{}', '198.18.7.9')",
+        )
+        .bind(instance_id)
+        .bind(provider_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let register = |from: &str, token: Option<&str>| {
+            let state = state.clone();
+            let mut headers = HeaderMap::new();
+            if let Some(token) = token {
+                headers.insert(
+                    axum::http::header::AUTHORIZATION,
+                    format!("Bearer {}", token).parse().unwrap(),
+                );
+            }
+            let connect: SocketAddr = format!("{}:50000", from).parse().unwrap();
+            let payload: WorkerRegisterRequest =
+                serde_json::from_value(json!({ "instance_id": instance_id })).unwrap();
+            async move {
+                let resp =
+                    worker_register(State(state), headers, ConnectInfo(connect), Json(payload))
+                        .await
+                        .into_response();
+                let status = resp.status();
+                let body: serde_json::Value = serde_json::from_slice(
+                    &axum::body::to_bytes(resp.into_body(), usize::MAX)
+                        .await
+                        .unwrap(),
+                )
+                .unwrap();
+                (status, body["bootstrap_token"].as_str().map(str::to_string))
+            }
+        };
+
+        // The first response is lost: the worker registers again from the same instance.
+        let first = register("198.18.7.9", None).await;
+        let retry = register("198.18.7.9", None).await;
+        let other_host = register("198.51.100.7", None).await;
+        let token = first.1.clone().unwrap_or_default();
+        let authenticated = register("198.18.7.9", Some(&token)).await;
+        // Once the token has been used, a tokenless register can no longer reclaim it.
+        let after_use = register("198.18.7.9", None).await;
+        let tokens: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM worker_auth_tokens WHERE instance_id = $1")
+                .bind(instance_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+
+        let _ = sqlx::query("DELETE FROM worker_auth_tokens WHERE instance_id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+
+        assert_eq!(first.0, StatusCode::OK);
+        assert!(first.1.is_some());
+        assert_eq!(retry, first);
+        assert_eq!(other_host, (StatusCode::UNAUTHORIZED, None));
+        assert_eq!(authenticated, (StatusCode::OK, None));
+        assert_eq!(after_use, (StatusCode::CONFLICT, None));
+        assert_eq!(tokens, 1);
+    }
+
+    #[tokio::test]
+    async fn bootstrap_accepts_provider_nat_range_only() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(provider_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers LIMIT 1")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: no provider seeded");
+            return;
+        };
+
+        let (user_id, org_id, instance_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let name = format!("bootstrap-nat-{}", instance_id);
+        sqlx::query(
+            "INSERT INTO users (id, email, username, password_hash) VALUES ($1, $2, $2, 'x')",
+        )
+        .bind(user_id)
+        .bind(format!("{}@test.local", name))
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO organizations (id, name, slug, created_by_user_id) VALUES ($1, $2, $2, $3)",
+        )
+        .bind(org_id)
+        .bind(&name)
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO provider_settings (provider_id, organization_id, key, value_text)
+             VALUES ($1, $2, 'WORKER_BOOTSTRAP_ALLOWED_CIDRS', '100.64.0.0/10')",
+        )
+        .bind(provider_id)
+        .bind(org_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, organization_id, status, created_at, gpu_profile, ip_address)
+             VALUES ($1, $2, $3, 'booting', NOW(), '{}', '51.15.0.10')",
+        )
+        .bind(instance_id)
+        .bind(provider_id)
+        .bind(org_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let exact = instance_bootstrap_ip_allowed(&pool, instance_id, "51.15.0.10").await;
+        let nat = instance_bootstrap_ip_allowed(&pool, instance_id, "100.72.3.4").await;
+        let outside = instance_bootstrap_ip_allowed(&pool, instance_id, "198.51.100.7").await;
+
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM organizations WHERE id = $1")
+            .bind(org_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(user_id)
+            .execute(&pool)
+            .await;
+
+        assert_eq!(exact, Some(BootstrapRule::ExactIp));
+        assert_eq!(
+            nat,
+            Some(BootstrapRule::Cidr {
+                source: "provider",
+                cidr: "100.64.0.0/10".to_string()
+            })
+        );
+        assert_eq!(outside, None);
+    }
+}
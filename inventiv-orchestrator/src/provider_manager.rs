@@ -3,20 +3,65 @@ use inventiv_providers::scaleway::ScalewayProvider;
 use inventiv_providers::CloudProvider;
 // use std::collections::HashMap;
 use sqlx::{Pool, Postgres};
+#[cfg(feature = "provider-scaleway")]
+use std::collections::HashMap;
 use std::env;
+#[cfg(feature = "provider-scaleway")]
 use std::fs;
+#[cfg(feature = "provider-scaleway")]
 use std::path::Path;
+#[cfg(feature = "provider-scaleway")]
+use std::sync::{Mutex, OnceLock};
+#[cfg(feature = "provider-scaleway")]
+use std::time::{Duration, Instant};
 
 #[cfg(feature = "provider-mock")]
 use inventiv_providers::mock::MockProvider;
 
 pub struct ProviderManager;
 
+/// Scaleway credentials resolved for one organization.
+#[cfg(feature = "provider-scaleway")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScalewayCredentials {
+    pub project_id: String,
+    pub secret_key: String,
+    pub ssh_public_key: Option<String>,
+    pub access_key: Option<String>,
+    pub organization_id: Option<String>,
+}
+
+#[cfg(feature = "provider-scaleway")]
+const DEFAULT_CREDENTIALS_CACHE_TTL_S: u64 = 30;
+
+#[cfg(feature = "provider-scaleway")]
+type CredentialsCache = Mutex<HashMap<uuid::Uuid, (Instant, ScalewayCredentials)>>;
+
+/// Resolved credentials per organization, so provider calls don't hit (and decrypt from) the DB
+/// every time. Dropped on CMD:INVALIDATE_PROVIDER_CREDENTIALS or after the TTL.
+#[cfg(feature = "provider-scaleway")]
+static SCALEWAY_CREDENTIALS: OnceLock<CredentialsCache> = OnceLock::new();
+
+#[cfg(feature = "provider-scaleway")]
+fn credentials_cache() -> &'static CredentialsCache {
+    SCALEWAY_CREDENTIALS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+#[cfg(feature = "provider-scaleway")]
+fn credentials_cache_ttl() -> Duration {
+    let secs = env::var("PROVIDER_CREDENTIALS_CACHE_TTL_S")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_CREDENTIALS_CACHE_TTL_S);
+    Duration::from_secs(secs)
+}
+
 impl ProviderManager {
     pub fn current_provider_name() -> String {
         env::var("PROVIDER").unwrap_or_else(|_| "scaleway".to_string())
     }
 
+    #[cfg(feature = "provider-scaleway")]
     fn read_secret_file(path: &str) -> Result<String, String> {
         let p = path.trim();
         if p.is_empty() {
@@ -27,6 +72,7 @@ impl ProviderManager {
             .map_err(|e| format!("failed to read secret file '{}': {}", p, e))
     }
 
+    #[cfg(feature = "provider-scaleway")]
     fn provider_settings_passphrase() -> Option<String> {
         // This passphrase MUST come from a secret (never committed).
         // We support both *_FILE (preferred) and direct env value.
@@ -44,10 +90,11 @@ impl ProviderManager {
             .filter(|s| !s.is_empty())
     }
 
+    #[cfg(feature = "provider-scaleway")]
     async fn scaleway_init_from_db(
         db: &Pool<Postgres>,
         organization_id: uuid::Uuid,
-    ) -> Result<Option<(String, String, Option<String>, Option<String>, Option<String>)>, String> {
+    ) -> Result<Option<ScalewayCredentials>, String> {
         // Resolve provider_id by code.
         let provider_id: Option<uuid::Uuid> =
             sqlx::query_scalar("SELECT id FROM providers WHERE code = 'scaleway' LIMIT 1")
@@ -147,9 +194,16 @@ impl ProviderManager {
             .map(|s| s.trim().to_string())
             .filter(|s| !s.is_empty());
 
-        Ok(Some((project_id, secret_key, ssh_public_key, access_key, organization_id_scw)))
+        Ok(Some(ScalewayCredentials {
+            project_id,
+            secret_key,
+            ssh_public_key,
+            access_key,
+            organization_id: organization_id_scw,
+        }))
     }
 
+    #[cfg(feature = "provider-scaleway")]
     fn scaleway_init_from_env() -> Result<(String, String, Option<String>), String> {
         // Project id can come from either SCALEWAY_PROJECT_ID or SCW_PROJECT_ID (common alias).
         let project_id = env::var("SCALEWAY_PROJECT_ID")
@@ -218,37 +272,114 @@ impl ProviderManager {
         Ok((project_id, secret_key, ssh_public_key))
     }
 
+    /// Scaleway credentials for `organization_id`: provider_settings first, then env/secret files
+    /// for the platform organization only (other organizations must configure their own).
+    /// Cached for PROVIDER_CREDENTIALS_CACHE_TTL_S (default 30s), so a rotated secret is picked up
+    /// by new provider calls without a restart.
+    #[cfg(feature = "provider-scaleway")]
+    pub async fn scaleway_credentials(
+        db: &Pool<Postgres>,
+        organization_id: uuid::Uuid,
+    ) -> Result<ScalewayCredentials, String> {
+        let ttl = credentials_cache_ttl();
+        if let Ok(cache) = credentials_cache().lock() {
+            if let Some((at, creds)) = cache.get(&organization_id) {
+                if at.elapsed() < ttl {
+                    return Ok(creds.clone());
+                }
+            }
+        }
+
+        let creds = match Self::scaleway_init_from_db(db, organization_id).await? {
+            Some(creds) => creds,
+            None if !Self::is_platform_organization(db, organization_id).await? => {
+                return Err(format!(
+                    "Missing Scaleway credentials for organization {} (provider_settings not found)",
+                    organization_id
+                ));
+            }
+            None => {
+                let (project_id, secret_key, ssh_public_key) = Self::scaleway_init_from_env()
+                    .map_err(|e| {
+                        format!(
+                            "Missing Scaleway credentials for organization {} (provider_settings not found; {})",
+                            organization_id, e
+                        )
+                    })?;
+                ScalewayCredentials {
+                    project_id,
+                    secret_key,
+                    ssh_public_key,
+                    access_key: env::var("SCALEWAY_ACCESS_KEY")
+                        .ok()
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty()),
+                    organization_id: env::var("SCALEWAY_ORGANIZATION_ID")
+                        .ok()
+                        .or_else(|| env::var("SCW_DEFAULT_ORGANIZATION_ID").ok())
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty()),
+                }
+            }
+        };
+
+        if let Ok(mut cache) = credentials_cache().lock() {
+            cache.insert(organization_id, (Instant::now(), creds.clone()));
+        }
+        Ok(creds)
+    }
+
+    /// The default organization, which may use the deployment's own (env) provider credentials.
+    #[cfg(feature = "provider-scaleway")]
+    async fn is_platform_organization(
+        db: &Pool<Postgres>,
+        organization_id: uuid::Uuid,
+    ) -> Result<bool, String> {
+        sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM organizations WHERE id = $1 AND slug = 'inventiv-it')",
+        )
+        .bind(organization_id)
+        .fetch_one(db)
+        .await
+        .map_err(|e| format!("DB error resolving organization: {}", e))
+    }
+
+    /// Drop cached credentials for one organization (or all of them when `None`).
+    #[cfg(feature = "provider-scaleway")]
+    pub fn invalidate_credentials(organization_id: Option<uuid::Uuid>) {
+        if let Ok(mut cache) = credentials_cache().lock() {
+            match organization_id {
+                Some(id) => {
+                    cache.remove(&id);
+                }
+                None => cache.clear(),
+            }
+        }
+    }
+
     pub async fn get_provider(
         provider_name: &str,
         organization_id: uuid::Uuid,
         db: Pool<Postgres>,
     ) -> Result<Box<dyn CloudProvider>, String> {
+        // Only used by the provider backends compiled in.
+        #[cfg(not(feature = "provider-scaleway"))]
+        let _ = organization_id;
+        #[cfg(not(any(feature = "provider-mock", feature = "provider-scaleway")))]
+        let _ = db;
         match provider_name.to_lowercase().as_str() {
             #[cfg(feature = "provider-scaleway")]
             "scaleway" => {
-                if let Some((project_id, secret_key, ssh_public_key, access_key, org_id_scw)) =
-                    Self::scaleway_init_from_db(&db, organization_id).await?
-                {
-                    let mut provider = ScalewayProvider::new(
-                        project_id,
-                        secret_key,
-                        ssh_public_key,
-                    );
-                    // Set organization_id and access_key from DB if available
-                    if let Some(ak) = access_key {
-                        provider.set_access_key(ak);
-                    }
-                    if let Some(oid) = org_id_scw {
-                        provider.set_organization_id(oid);
-                    }
-                    return Ok(Box::new(provider));
+                let creds = Self::scaleway_credentials(&db, organization_id).await?;
+                let mut provider =
+                    ScalewayProvider::new(creds.project_id, creds.secret_key, creds.ssh_public_key);
+                if let Some(ak) = creds.access_key {
+                    provider.set_access_key(ak);
                 }
-
-                // No credentials in DB for this organization - return error (no fallback)
-                return Err(format!(
-                    "Missing Scaleway credentials for organization {} (provider_settings not found)",
-                    organization_id
-                ));
+                if let Some(oid) = creds.organization_id {
+                    provider.set_organization_id(oid);
+                }
+                Ok(Box::new(provider))
             }
             #[cfg(not(feature = "provider-scaleway"))]
             "scaleway" => Err(
@@ -267,3 +398,85 @@ impl ProviderManager {
         }
     }
 }
+
+#[cfg(all(test, feature = "provider-scaleway"))]
+mod tests {
+    use super::*;
+    use crate::test_support::setup_pool;
+
+    #[tokio::test]
+    async fn rotated_scaleway_secret_is_used_after_invalidation() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(provider_id): Option<uuid::Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers WHERE code = 'scaleway'")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping: scaleway provider not seeded");
+            return;
+        };
+
+        let suffix = uuid::Uuid::new_v4().simple().to_string();
+        let user_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO users (id, email, password_hash, username, created_at)
+             VALUES (gen_random_uuid(), $1, 'x', $2, NOW()) RETURNING id",
+        )
+        .bind(format!("rotate-{}@test.local", suffix))
+        .bind(format!("rotate-{}", suffix))
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let org_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO organizations (name, slug, created_by_user_id) VALUES ($1, $1, $2) RETURNING id",
+        )
+        .bind(format!("rotate-{}", suffix))
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        // Not the platform organization: no fallback to the deployment's env credentials.
+        let unconfigured = ProviderManager::scaleway_credentials(&pool, org_id).await;
+        sqlx::query(
+            "INSERT INTO provider_settings (provider_id, organization_id, key, value_text)
+             VALUES ($1, $2, 'SCALEWAY_PROJECT_ID', 'proj-1'), ($1, $2, 'SCALEWAY_SECRET_KEY', 'old-secret')",
+        )
+        .bind(provider_id)
+        .bind(org_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let before = ProviderManager::scaleway_credentials(&pool, org_id).await;
+        sqlx::query(
+            "UPDATE provider_settings SET value_text = 'new-secret'
+             WHERE provider_id = $1 AND organization_id = $2 AND key = 'SCALEWAY_SECRET_KEY'",
+        )
+        .bind(provider_id)
+        .bind(org_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let cached = ProviderManager::scaleway_credentials(&pool, org_id).await;
+        ProviderManager::invalidate_credentials(Some(org_id));
+        let after = ProviderManager::scaleway_credentials(&pool, org_id).await;
+
+        let _ = sqlx::query("DELETE FROM organizations WHERE id = $1")
+            .bind(org_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(user_id)
+            .execute(&pool)
+            .await;
+
+        assert!(unconfigured.is_err());
+        assert_eq!(before.unwrap().secret_key, "old-secret");
+        assert_eq!(cached.unwrap().secret_key, "old-secret");
+        let after = after.unwrap();
+        assert_eq!(after.secret_key, "new-secret");
+        assert_eq!(after.project_id, "proj-1");
+    }
+}
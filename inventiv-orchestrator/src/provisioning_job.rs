@@ -4,13 +4,13 @@ use uuid::Uuid;
 use crate::logger;
 use crate::services;
 
-/// job-provisioning: re-queues stuck PROVISIONING instances.
+/// job-provisioning: re-queues stuck ACCEPTED/PROVISIONING instances.
 ///
 /// Why: Redis Pub/Sub is not durable. If orchestrator is down during publish, the event is lost,
-/// and the instance can remain in `provisioning` forever with no provider_instance_id.
+/// and the instance can remain `accepted` (or `provisioning`) forever with no provider_instance_id.
 ///
 /// Strategy:
-/// - Claim stale `provisioning` rows (provider_instance_id IS NULL) with SKIP LOCKED
+/// - Claim stale `accepted`/`provisioning` rows (provider_instance_id IS NULL) with SKIP LOCKED
 /// - Bump retry_count and set last_reconciliation as a lease timestamp
 /// - Call process_provisioning again
 pub async fn run(pool: Pool<Postgres>, redis_client: redis::Client) {
@@ -51,7 +51,7 @@ async fn requeue_stale_provisioning(
             FROM instances i
             JOIN zones z ON z.id = i.zone_id
             JOIN instance_types it ON it.id = i.instance_type_id
-            WHERE i.status = 'provisioning'
+            WHERE i.status IN ('accepted', 'provisioning')
               AND i.provider_instance_id IS NULL
               AND i.failed_at IS NULL
               AND i.created_at < NOW() - INTERVAL '30 seconds'
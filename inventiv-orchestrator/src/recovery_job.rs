@@ -1,3 +1,4 @@
+use inventiv_common::error_code::ErrorCode;
 use sqlx::{Pool, Postgres};
 use uuid::Uuid;
 
@@ -153,7 +154,7 @@ async fn recover_stuck_instances(
         let _ = state_machine::booting_to_startup_failed(
             pool,
             instance_id,
-            "RECOVERY_TIMEOUT",
+            ErrorCode::RecoveryTimeout.as_str(),
             "Instance stuck in booting state for too long (recovery job)",
         )
         .await;
@@ -1,9 +1,15 @@
 use crate::finops_events;
 use crate::health_check_flow;
+use crate::local_volume_removal;
 use crate::logger;
 use crate::provider_manager::ProviderManager;
 use crate::state_machine;
+use crate::volume_verification;
+use crate::worker_mtls;
 use bigdecimal::FromPrimitive;
+use inventiv_common::cloud_init;
+use inventiv_common::error_code::ErrorCode;
+use inventiv_common::net;
 use inventiv_common::worker_storage;
 use serde_json::json;
 use sqlx::{Pool, Postgres};
@@ -19,7 +25,7 @@ fn gb_to_bytes(gb: i64) -> i64 {
     gb.saturating_mul(1_000_000_000)
 }
 
-fn worker_control_plane_url() -> String {
+pub fn worker_control_plane_url() -> String {
     // Priority:
     // 1) WORKER_CONTROL_PLANE_URL (direct)
     // 2) WORKER_CONTROL_PLANE_URL_FILE (read file contents)
@@ -44,14 +50,11 @@ fn worker_control_plane_url() -> String {
 
 /// Check if SSH port 22 is accessible on the given IP address
 async fn check_ssh_accessible(ip: &str) -> bool {
-    let clean_ip = ip.split('/').next().unwrap_or(ip);
-    let addr = format!("{}:22", clean_ip);
+    let Some(socket_addr) = net::instance_socket_addr(ip, 22) else {
+        return false;
+    };
 
     tokio::task::spawn_blocking(move || {
-        let socket_addr = match addr.parse() {
-            Ok(a) => a,
-            Err(_) => return false,
-        };
         TcpStream::connect_timeout(&socket_addr, StdDuration::from_secs(3)).is_ok()
     })
     .await
@@ -231,9 +234,10 @@ pub async fn process_termination(
                 None => {
                     eprintln!("❌ [process_termination] Instance {} missing organization_id", id_uuid);
                     let _ = sqlx::query(
-                        "UPDATE instances SET status='failed', error_code='MISSING_ORGANIZATION_ID', error_message='Instance missing organization_id', failed_at=NOW() WHERE id=$1"
+                        "UPDATE instances SET status='failed', error_code=$2, error_message='Instance missing organization_id', failed_at=NOW() WHERE id=$1"
                     )
                     .bind(id_uuid)
+                    .bind(ErrorCode::MissingOrganizationId.as_str())
                     .execute(&pool)
                     .await;
                     return;
@@ -843,6 +847,53 @@ pub async fn process_reinstall(
         return;
     }
 
+    // A new model may need a bigger data volume than the one provisioned for the previous model:
+    // grow it before re-bootstrapping so the download doesn't run out of space.
+    let target: Option<(Option<String>, Option<Uuid>, String)> = sqlx::query_as(
+        "SELECT z.code, i.organization_id, p.code
+         FROM instances i
+         JOIN providers p ON p.id = i.provider_id
+         LEFT JOIN zones z ON z.id = i.zone_id
+         WHERE i.id = $1",
+    )
+    .bind(id_uuid)
+    .fetch_optional(&pool)
+    .await
+    .ok()
+    .flatten();
+    if let Some((Some(zone), Some(organization_id), provider_code)) = target {
+        match ProviderManager::get_provider(&provider_code, organization_id, pool.clone()).await {
+            Ok(provider) => {
+                if let Err(e) = grow_data_volume_for_model(
+                    &pool,
+                    provider.as_ref(),
+                    id_uuid,
+                    &zone,
+                    correlation_id.as_deref(),
+                )
+                .await
+                {
+                    eprintln!(
+                        "❌ [process_reinstall] Data volume resize for {} failed: {}",
+                        id_uuid, e
+                    );
+                    if let Some(lid) = log_id_execute {
+                        let dur = start.elapsed().as_millis() as i32;
+                        let msg = format!("Data volume resize failed: {}", e);
+                        logger::log_event_complete(&pool, lid, "failed", dur, Some(&msg))
+                            .await
+                            .ok();
+                    }
+                    return;
+                }
+            }
+            Err(e) => eprintln!(
+                "⚠️ [process_reinstall] Provider unavailable, data volume size not checked for {}: {}",
+                id_uuid, e
+            ),
+        }
+    }
+
     // Move instance back to booting so health checks can converge to READY again.
     let _ = sqlx::query(
         "UPDATE instances
@@ -877,6 +928,287 @@ pub async fn process_reinstall(
     }
 }
 
+/// Grow the instance's Block Storage data volume to the model's `data_volume_gb` (never shrinks).
+/// Returns the `(from, to)` sizes in GB when a resize was applied.
+async fn grow_data_volume_for_model(
+    pool: &Pool<Postgres>,
+    provider: &dyn inventiv_providers::CloudProvider,
+    instance_id: Uuid,
+    zone: &str,
+    correlation_id: Option<&str>,
+) -> Result<Option<(u64, u64)>, String> {
+    let (_, data_volume_gb) = resolve_instance_model_and_volume(pool, instance_id).await;
+    let Some(target_gb) = data_volume_gb.filter(|gb| *gb > 0).map(|gb| gb as u64) else {
+        return Ok(None);
+    };
+
+    // Model data lives on the data volume when there is one, else on the (diskless) boot volume.
+    let volume: Option<(String, i64)> = sqlx::query_as(
+        "SELECT provider_volume_id, size_bytes
+         FROM instance_volumes
+         WHERE instance_id = $1
+           AND deleted_at IS NULL
+           AND volume_type = 'sbs_volume'
+         ORDER BY is_boot ASC, created_at ASC
+         LIMIT 1",
+    )
+    .bind(instance_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    let Some((volume_id, tracked_bytes)) = volume else {
+        return Ok(None);
+    };
+
+    let current_bytes = match provider.get_block_storage_size(zone, &volume_id).await {
+        Ok(Some(bytes)) => bytes,
+        _ => tracked_bytes.max(0) as u64,
+    };
+    let current_gb = current_bytes / 1_000_000_000;
+    if current_gb >= target_gb {
+        return Ok(None);
+    }
+
+    eprintln!(
+        "🔵 [process_reinstall] Growing Block Storage {} from {}GB to {}GB for instance {}",
+        volume_id, current_gb, target_gb, instance_id
+    );
+    let resize_start = Instant::now();
+    let resize_log = logger::log_event_with_metadata(
+        pool,
+        "PROVIDER_VOLUME_RESIZE",
+        "in_progress",
+        instance_id,
+        None,
+        Some(json!({
+            "zone": zone,
+            "volume_id": volume_id,
+            "current_size_gb": current_gb,
+            "target_size_gb": target_gb,
+            "reason": "reinstall",
+            "correlation_id": correlation_id,
+        })),
+    )
+    .await
+    .ok();
+
+    let result = match provider
+        .resize_block_storage(zone, &volume_id, target_gb)
+        .await
+    {
+        Ok(true) => Ok(()),
+        Ok(false) => Err("Provider does not support Block Storage resize".to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    if let Some(lid) = resize_log {
+        let dur = resize_start.elapsed().as_millis() as i32;
+        let (status, msg) = match &result {
+            Ok(()) => ("success", None),
+            Err(e) => ("failed", Some(e.as_str())),
+        };
+        logger::log_event_complete(pool, lid, status, dur, msg)
+            .await
+            .ok();
+    }
+    result?;
+
+    let _ = sqlx::query(
+        "UPDATE instance_volumes
+         SET size_bytes = $3
+         WHERE instance_id = $1 AND provider_volume_id = $2 AND deleted_at IS NULL",
+    )
+    .bind(instance_id)
+    .bind(&volume_id)
+    .bind((target_gb * 1_000_000_000) as i64)
+    .execute(pool)
+    .await;
+    Ok(Some((current_gb, target_gb)))
+}
+
+/// (provider_instance_id, zone code, organization_id, provider code, new instance type code)
+type ResizeTargetRow = (
+    Option<String>,
+    Option<String>,
+    Option<Uuid>,
+    String,
+    Option<String>,
+);
+
+pub async fn process_resize(
+    pool: Pool<Postgres>,
+    instance_id: String,
+    instance_type_id: String,
+    correlation_id: Option<String>,
+) {
+    let start = Instant::now();
+    let (Ok(id_uuid), Ok(type_uuid)) = (
+        Uuid::parse_str(&instance_id),
+        Uuid::parse_str(&instance_type_id),
+    ) else {
+        println!(
+            "❌ Invalid ids for resize (instance_id='{}', instance_type_id='{}')",
+            instance_id, instance_type_id
+        );
+        return;
+    };
+
+    println!("🔧 Processing Resize Async: {} -> {}", id_uuid, type_uuid);
+
+    let log_id_execute = logger::log_event_with_metadata(
+        &pool,
+        "EXECUTE_RESIZE",
+        "in_progress",
+        id_uuid,
+        None,
+        Some(json!({
+            "correlation_id": correlation_id,
+            "instance_type_id": type_uuid.to_string(),
+        })),
+    )
+    .await
+    .ok();
+
+    let row: Option<ResizeTargetRow> = sqlx::query_as(
+        "SELECT i.provider_instance_id::text, z.code, i.organization_id, p.code,
+                (SELECT it.code FROM instance_types it WHERE it.id = $2)
+         FROM instances i
+         JOIN providers p ON p.id = i.provider_id
+         LEFT JOIN zones z ON z.id = i.zone_id
+         WHERE i.id = $1",
+    )
+    .bind(id_uuid)
+    .bind(type_uuid)
+    .fetch_optional(&pool)
+    .await
+    .ok()
+    .flatten();
+
+    let result = match row {
+        None => Err("Instance not found".to_string()),
+        Some((
+            Some(server_id),
+            Some(zone),
+            Some(organization_id),
+            provider_code,
+            Some(type_code),
+        )) => {
+            match ProviderManager::get_provider(&provider_code, organization_id, pool.clone()).await
+            {
+                Ok(provider) => {
+                    resize_with_provider(
+                        &pool,
+                        provider.as_ref(),
+                        id_uuid,
+                        &zone,
+                        &server_id,
+                        type_uuid,
+                        &type_code,
+                    )
+                    .await
+                }
+                Err(e) => Err(format!("Provider unavailable: {}", e)),
+            }
+        }
+        Some(_) => {
+            Err("Missing provider_instance_id, zone, organization_id or instance type".to_string())
+        }
+    };
+
+    if let Err(e) = &result {
+        eprintln!("❌ [process_resize] Resize of {} failed: {}", id_uuid, e);
+        // The server may have been power-cycled: let health checks bring it back to READY.
+        let _ = sqlx::query(
+            "UPDATE instances
+             SET status = 'booting',
+                 boot_started_at = NOW(),
+                 last_health_check = NULL,
+                 health_check_failures = 0
+             WHERE id = $1 AND status = 'resizing'",
+        )
+        .bind(id_uuid)
+        .execute(&pool)
+        .await;
+    }
+    if let Some(lid) = log_id_execute {
+        let dur = start.elapsed().as_millis() as i32;
+        let (status, msg) = match &result {
+            Ok(()) => ("success", None),
+            Err(e) => ("failed", Some(e.as_str())),
+        };
+        logger::log_event_complete(&pool, lid, status, dur, msg)
+            .await
+            .ok();
+    }
+}
+
+/// Resize the server at the provider, then record the new type on the instance.
+/// The instance is kept out of routing (RESIZING) for the whole power cycle; only a READY (or
+/// already claimed) instance is resized. Attached volumes are left as-is (the data volume survives
+/// the stop/start).
+async fn resize_with_provider(
+    pool: &Pool<Postgres>,
+    provider: &dyn inventiv_providers::CloudProvider,
+    instance_id: Uuid,
+    zone: &str,
+    server_id: &str,
+    instance_type_id: Uuid,
+    instance_type_code: &str,
+) -> Result<(), String> {
+    let claimed = sqlx::query(
+        "UPDATE instances SET status = 'resizing' WHERE id = $1 AND status IN ('ready', 'resizing')",
+    )
+    .bind(instance_id)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?
+    .rows_affected();
+    if claimed == 0 {
+        return Err("Instance is not ready for resize".to_string());
+    }
+
+    // Stop + type change + start: allow for a slow power cycle.
+    let resized = tokio::time::timeout(
+        Duration::from_secs(300),
+        provider.resize_instance(zone, server_id, instance_type_code),
+    )
+    .await
+    .map_err(|_| "Provider resize timed out".to_string())?
+    .map_err(|e| e.to_string())?;
+    if !resized {
+        return Err("Provider does not support resize".to_string());
+    }
+
+    // Back to booting so health checks converge to READY on the new hardware.
+    sqlx::query(
+        "UPDATE instances
+         SET instance_type_id = $2,
+             status = 'booting',
+             boot_started_at = NOW(),
+             last_health_check = NULL,
+             health_check_failures = 0,
+             failed_at = NULL,
+             error_code = NULL,
+             error_message = NULL
+         WHERE id = $1
+           AND status NOT IN ('terminated', 'terminating')",
+    )
+    .bind(instance_id)
+    .bind(instance_type_id)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    // The public IP may change across the power cycle (best effort).
+    if let Ok(Some(ip)) = provider.get_instance_ip(zone, server_id).await {
+        let _ = sqlx::query("UPDATE instances SET ip_address = $2::inet WHERE id = $1")
+            .bind(instance_id)
+            .bind(ip)
+            .execute(pool)
+            .await;
+    }
+    Ok(())
+}
+
 pub async fn process_provisioning(
     pool: Pool<Postgres>,
     redis_client: redis::Client,
@@ -902,6 +1234,14 @@ pub async fn process_provisioning(
         instance_uuid, zone, instance_type, correlation_id_meta
     );
 
+    // Queued -> in flight. No-op for rows already past `accepted` (requeues, warm pool).
+    let _ = state_machine::accepted_to_provisioning(
+        &pool,
+        instance_uuid,
+        "Orchestrator started provisioning",
+    )
+    .await;
+
     // 0. Resolve provider from the instance row (supports multiple providers)
     // No hardcoded UUID fallbacks: the DB catalog must contain the provider referenced by the instance.
     let provider_id: Uuid =
@@ -982,13 +1322,14 @@ pub async fn process_provisioning(
         sqlx::query(
             "UPDATE instances 
              SET status = 'failed',
-                 error_code = COALESCE(error_code, 'CATALOG_LOOKUP_FAILED'),
+                 error_code = COALESCE(error_code, $3),
                  error_message = COALESCE($2, error_message),
                  failed_at = COALESCE(failed_at, NOW())
              WHERE id = $1",
         )
         .bind(instance_uuid)
         .bind(&msg)
+        .bind(ErrorCode::CatalogLookupFailed.as_str())
         .execute(&pool)
         .await
         .ok();
@@ -1032,13 +1373,14 @@ pub async fn process_provisioning(
             let _ = sqlx::query(
                 "UPDATE instances
                  SET status='failed',
-                     error_code=COALESCE(error_code,'INSTANCE_TYPE_NOT_AVAILABLE_IN_ZONE'),
+                     error_code=COALESCE(error_code,$3),
                      error_message=COALESCE($2,error_message),
                      failed_at=COALESCE(failed_at,NOW())
                  WHERE id=$1",
             )
             .bind(instance_uuid)
             .bind(&msg)
+            .bind(ErrorCode::InstanceTypeNotAvailableInZone.as_str())
             .execute(&pool)
             .await;
             eprintln!("❌ {}", msg);
@@ -1062,13 +1404,14 @@ pub async fn process_provisioning(
             let _ = sqlx::query(
                 "UPDATE instances
                  SET status='failed',
-                     error_code=COALESCE(error_code,'INSTANCE_TYPE_NOT_SUPPORTED'),
+                     error_code=COALESCE(error_code,$3),
                      error_message=COALESCE($2,error_message),
                      failed_at=COALESCE(failed_at,NOW())
                  WHERE id=$1",
             )
             .bind(instance_uuid)
             .bind(&msg)
+            .bind(ErrorCode::InstanceTypeNotSupported.as_str())
             .execute(&pool)
             .await;
             eprintln!("❌ {}", msg);
@@ -1095,13 +1438,14 @@ pub async fn process_provisioning(
         let _ = sqlx::query(
             "UPDATE instances
              SET status='failed',
-                 error_code='MISSING_ORGANIZATION_ID',
+                 error_code=$3,
                  error_message=$2,
                  failed_at=NOW()
              WHERE id=$1",
         )
         .bind(instance_uuid)
         .bind(msg)
+        .bind(ErrorCode::MissingOrganizationId.as_str())
         .execute(&pool)
         .await;
         return;
@@ -1151,13 +1495,14 @@ pub async fn process_provisioning(
         let _ = sqlx::query(
             "UPDATE instances
              SET status='failed',
-                 error_code=COALESCE(error_code,'MISSING_MODEL'),
+                 error_code=COALESCE(error_code,$3),
                  error_message=COALESCE($2,error_message),
                  failed_at=COALESCE(failed_at,NOW())
              WHERE id=$1",
         )
         .bind(instance_uuid)
         .bind(msg)
+        .bind(ErrorCode::MissingModel.as_str())
         .execute(&pool)
         .await;
         if let Some(log_id) = log_id_execute {
@@ -1169,6 +1514,37 @@ pub async fn process_provisioning(
         return;
     }
 
+    // The model may have been deactivated between request acceptance and execution:
+    // never allocate a GPU for a disabled model.
+    let model_active: Option<bool> = sqlx::query_scalar(
+        "SELECT COALESCE(m.is_active, false)
+         FROM instances i
+         JOIN models m ON m.id = i.model_id
+         WHERE i.id = $1",
+    )
+    .bind(instance_uuid)
+    .fetch_optional(&pool)
+    .await
+    .unwrap_or(None);
+    if model_active == Some(false) {
+        let msg = "Model was deactivated before provisioning started";
+        eprintln!("❌ {} (instance {})", msg, instance_uuid);
+        let _ = state_machine::provisioning_to_provisioning_failed(
+            &pool,
+            instance_uuid,
+            ErrorCode::InactiveModel.as_str(),
+            msg,
+        )
+        .await;
+        if let Some(log_id) = log_id_execute {
+            let duration = start.elapsed().as_millis() as i32;
+            logger::log_event_complete(&pool, log_id, "failed", duration, Some(msg))
+                .await
+                .ok();
+        }
+        return;
+    }
+
     // 0. Get organization_id from instance (required - no fallback)
     let organization_id: Option<uuid::Uuid> = sqlx::query_scalar(
         "SELECT organization_id FROM instances WHERE id = $1"
@@ -1193,13 +1569,14 @@ pub async fn process_provisioning(
             let _ = sqlx::query(
                 "UPDATE instances
                  SET status = 'failed',
-                     error_code = COALESCE(error_code, 'MISSING_ORGANIZATION_ID'),
+                     error_code = COALESCE(error_code, $3),
                      error_message = COALESCE($2, error_message),
                      failed_at = COALESCE(failed_at, NOW())
                  WHERE id = $1",
             )
             .bind(instance_uuid)
             .bind(msg)
+            .bind(ErrorCode::MissingOrganizationId.as_str())
             .execute(&pool)
             .await;
             return;
@@ -1221,19 +1598,71 @@ pub async fn process_provisioning(
             let _ = sqlx::query(
                 "UPDATE instances
                  SET status = 'failed',
-                     error_code = COALESCE(error_code, 'MISSING_PROVIDER_CREDENTIALS'),
+                     error_code = COALESCE(error_code, $3),
                      error_message = COALESCE($2, error_message),
                      failed_at = COALESCE(failed_at, NOW())
                  WHERE id = $1",
             )
             .bind(instance_uuid)
             .bind(&msg)
+            .bind(ErrorCode::MissingProviderCredentials.as_str())
             .execute(&pool)
             .await;
             return;
         }
     };
 
+    provision_with_provider(
+        ProvisioningRun {
+            pool,
+            redis_client,
+            instance_uuid,
+            zone,
+            instance_type,
+            type_id,
+            provider_name,
+            correlation_id_meta,
+            log_id_execute,
+            start,
+        },
+        provider,
+    )
+    .await;
+}
+
+/// Context resolved by `process_provisioning` before the provider-side steps run.
+struct ProvisioningRun {
+    pool: Pool<Postgres>,
+    redis_client: redis::Client,
+    instance_uuid: Uuid,
+    zone: String,
+    instance_type: String,
+    type_id: Uuid,
+    provider_name: String,
+    correlation_id_meta: Option<String>,
+    log_id_execute: Option<Uuid>,
+    start: Instant,
+}
+
+/// Provider-side provisioning (create, storage, start, IP, SSH), split from `process_provisioning`
+/// so it can be driven with any `CloudProvider`.
+async fn provision_with_provider(
+    run: ProvisioningRun,
+    provider: Box<dyn inventiv_providers::CloudProvider>,
+) {
+    let ProvisioningRun {
+        pool,
+        redis_client,
+        instance_uuid,
+        zone,
+        instance_type,
+        type_id,
+        provider_name,
+        correlation_id_meta,
+        log_id_execute,
+        start,
+    } = run;
+
     // 1.5 Idempotence guard: if provider_instance_id already exists, don't create a second server
     let existing: Option<(Option<String>, Option<String>)> =
         sqlx::query_as("SELECT provider_instance_id, status::text FROM instances WHERE id = $1")
@@ -1388,13 +1817,14 @@ pub async fn process_provisioning(
                     let _ = sqlx::query(
                         "UPDATE instances
                          SET status = 'failed',
-                             error_code = COALESCE(error_code, 'DISKLESS_BOOT_IMAGE_REQUIRED'),
+                             error_code = COALESCE(error_code, $3),
                              error_message = COALESCE($2, error_message),
                              failed_at = COALESCE(failed_at, NOW())
                          WHERE id = $1",
                     )
                     .bind(instance_uuid)
                     .bind(&msg)
+                    .bind(ErrorCode::DisklessBootImageRequired.as_str())
                     .execute(&pool)
                     .await;
                     return;
@@ -1411,13 +1841,14 @@ pub async fn process_provisioning(
                     let _ = sqlx::query(
                         "UPDATE instances
                          SET status = 'failed',
-                             error_code = COALESCE(error_code, 'DISKLESS_BOOT_IMAGE_RESOLVE_FAILED'),
+                             error_code = COALESCE(error_code, $3),
                              error_message = COALESCE($2, error_message),
                              failed_at = COALESCE(failed_at, NOW())
                          WHERE id = $1",
                     )
                     .bind(instance_uuid)
                     .bind(&msg)
+                    .bind(ErrorCode::DisklessBootImageResolveFailed.as_str())
                     .execute(&pool)
                     .await;
                     return;
@@ -1426,6 +1857,29 @@ pub async fn process_provisioning(
         }
     }
 
+    // Per-model boot image override (e.g. a model needing a specific CUDA image).
+    let model_boot_image: Option<String> = sqlx::query_scalar(
+        r#"
+        SELECT NULLIF(TRIM(m.boot_image_id), '')
+        FROM instances i
+        JOIN models m ON m.id = i.model_id
+        WHERE i.id = $1
+        "#,
+    )
+    .bind(instance_uuid)
+    .fetch_optional(&pool)
+    .await
+    .unwrap_or(None)
+    .flatten();
+    image_id = apply_model_boot_image(
+        provider.as_ref(),
+        &zone,
+        image_id,
+        model_boot_image,
+        provider.requires_diskless_boot(&instance_type),
+    )
+    .await;
+
     // Optional: configure worker auto-install at boot (cloud-init) for Scaleway.
     //
     // Controlled by orchestrator env vars:
@@ -1473,7 +1927,7 @@ pub async fn process_provisioning(
             if ssh_pub.trim().is_empty() {
                 None
             } else {
-                Some(build_ssh_key_cloud_init(&ssh_pub))
+                Some(cloud_init::build_ssh_key_cloud_init(&ssh_pub))
             }
         } else {
             let (model_from_db, _vol_from_db) =
@@ -1544,14 +1998,14 @@ pub async fn process_provisioning(
             };
 
             let agent_url = std::env::var("WORKER_AGENT_SOURCE_URL")
-                    .ok()
-                    .filter(|s| !s.trim().is_empty())
-                    .unwrap_or_else(|| "https://raw.githubusercontent.com/Inventiv-IT-for-AI/inventiv-agents/main/inventiv-worker/agent.py".to_string());
+                .ok()
+                .filter(|s| !s.trim().is_empty())
+                .unwrap_or_else(|| cloud_init::DEFAULT_AGENT_SOURCE_URL.to_string());
 
             let worker_auth_token = std::env::var("WORKER_AUTH_TOKEN").unwrap_or_default();
             let worker_hf_token = worker_hf_token();
 
-            Some(build_worker_cloud_init(
+            Some(cloud_init::build_worker_cloud_init(
                 &ssh_pub,
                 &instance_uuid.to_string(),
                 &cp_url,
@@ -1562,10 +2016,11 @@ pub async fn process_provisioning(
                 &agent_url,
                 &worker_auth_token,
                 &worker_hf_token,
+                worker_mtls::worker_client_tls().as_ref(),
             ))
         }
     } else if !ssh_pub.trim().is_empty() {
-        Some(build_ssh_key_cloud_init(&ssh_pub))
+        Some(cloud_init::build_ssh_key_cloud_init(&ssh_pub))
     } else {
         None
     };
@@ -1637,10 +2092,31 @@ pub async fn process_provisioning(
         }
     }
 
-    // Provider-specific volume creation strategy: some providers require volumes to be created BEFORE instance
-    // This is determined by provider.should_pre_create_data_volume() abstraction
-    let mut pre_created_volume_id: Option<String> = None;
-    if is_worker_target && provider.should_pre_create_data_volume(&instance_type) {
+    // Provider-specific volume creation strategy: pre-create, post-attach, or skip (local storage).
+    let storage_strategy = data_volume_strategy(provider.as_ref(), &instance_type);
+    if is_worker_target {
+        eprintln!(
+            "ℹ️ [process_create] Data volume strategy for instance {} (type {}): {} (volume_type={})",
+            instance_uuid,
+            instance_type,
+            storage_strategy.as_str(),
+            storage_strategy.volume_type()
+        );
+    }
+    // A data volume reused from a previous instance stands in for the pre-created one.
+    let reused_volume = reserved_data_volume(&pool, instance_uuid).await;
+    if let Some((vol_id, gb)) = &reused_volume {
+        eprintln!(
+            "♻️ [process_create] Reusing data volume {} ({}GB) for instance {}",
+            vol_id, gb, instance_uuid
+        );
+    }
+    let mut pre_created_volume_id: Option<String> =
+        reused_volume.as_ref().map(|(id, _)| id.clone());
+    if reused_volume.is_none()
+        && is_worker_target
+        && matches!(storage_strategy, DataVolumeStrategy::PreCreate { .. })
+    {
         if let Some((gb, perf_iops, delete_on_terminate)) = data_conf {
             if gb > 0 {
                 let vol_name = format!("inventiv-data-{}", instance_uuid);
@@ -1660,9 +2136,9 @@ pub async fn process_provisioning(
                 .await
                 .ok();
                 let vol_start = Instant::now();
-                let volume_type = provider.get_data_volume_type(&instance_type);
+                let volume_type = storage_strategy.volume_type();
                 let created = provider
-                    .create_volume(&zone, &vol_name, gb_to_bytes(gb), &volume_type, perf_iops)
+                    .create_volume(&zone, &vol_name, gb_to_bytes(gb), volume_type, perf_iops)
                     .await;
                 match created {
                     Ok(Some(vol_id)) => {
@@ -1693,7 +2169,7 @@ pub async fn process_provisioning(
                                 r#"
                                 INSERT INTO instance_volumes 
                                 (id, instance_id, provider_id, zone_code, provider_volume_id, provider_volume_name, volume_type, size_bytes, delete_on_terminate, status, attached_at, is_boot)
-                                VALUES ($1, $2, $3, $4, $5, $6, 'sbs_volume', $7, $8, 'created', NULL, FALSE)
+                                VALUES ($1, $2, $3, $4, $5, $6, $9, $7, $8, 'created', NULL, FALSE)
                                 ON CONFLICT (instance_id, provider_volume_id) DO UPDATE
                                 SET status = 'created', deleted_at = NULL
                                 WHERE instance_volumes.deleted_at IS NOT NULL
@@ -1707,6 +2183,7 @@ pub async fn process_provisioning(
                             .bind(&vol_name)
                             .bind(gb_to_bytes(gb))
                             .bind(delete_on_terminate)
+                            .bind(volume_type)
                             .execute(&pool)
                             .await;
                         }
@@ -1737,6 +2214,26 @@ pub async fn process_provisioning(
         }
     }
 
+    log_provisioning_step(
+        &pool,
+        instance_uuid,
+        "create_server",
+        start,
+        correlation_id_meta.as_deref(),
+    )
+    .await;
+
+    // Cost attribution: propagate the instance cost_center to provider billing tags.
+    let cost_center: Option<String> =
+        sqlx::query_scalar("SELECT cost_center FROM instances WHERE id = $1")
+            .bind(instance_uuid)
+            .fetch_optional(&pool)
+            .await
+            .ok()
+            .flatten()
+            .flatten();
+    let billing_tags = provider_billing_tags(cost_center.as_deref());
+
     // LOG 3: PROVIDER_CREATE (API call)
     let api_start = Instant::now();
     let log_id_provider = logger::log_event_with_metadata(
@@ -1753,7 +2250,11 @@ pub async fn process_provisioning(
             "provider": provider_name,
             "has_cloud_init": cloud_init_for_create.is_some(),
             "cloud_init_length": cloud_init_for_create.as_ref().map(|ci| ci.len()).unwrap_or(0),
-            "pre_created_volume_id": pre_created_volume_id.as_deref()
+            "pre_created_volume_id": pre_created_volume_id.as_deref(),
+            "reused_volume": reused_volume.is_some(),
+            "storage_strategy": is_worker_target.then(|| storage_strategy.as_str()),
+            "data_volume_type": is_worker_target.then(|| storage_strategy.volume_type()),
+            "cost_center": cost_center
         })),
     )
     .await
@@ -1765,15 +2266,87 @@ pub async fn process_provisioning(
         pre_created_volume_id.as_ref().map(|vid| vec![vid.clone()]);
     let volumes_ref: Option<&[String]> = volumes_for_create.as_deref();
 
-    let server_id_result = provider
-        .create_instance(
+    // Retryable failures (capacity, rate limit) re-queue the create with backoff; anything else
+    // fails the provisioning right away.
+    let retry_policy = ProvisionRetryPolicy::from_env();
+    let mut retry_attempts: Vec<serde_json::Value> = Vec::new();
+    let mut collided_names = Vec::new();
+    let server_id_result = loop {
+        let (res, collided) = create_instance_with_name_retry(
+            provider.as_ref(),
+            instance_uuid,
             &zone,
             &instance_type,
             &image_id,
             cloud_init_for_create.as_deref(),
             volumes_ref,
+            &billing_tags,
         )
         .await;
+        collided_names.extend(collided);
+        let retryable = match &res {
+            Err(e) => inventiv_providers::provider_error_code(e)
+                .filter(|c| c.is_retryable())
+                .map(|c| (c, e.to_string())),
+            Ok(_) => None,
+        };
+        let Some((code, error)) = retryable else {
+            break res;
+        };
+        if retry_attempts.len() >= retry_policy.max_retries {
+            break res;
+        }
+        let backoff = retry_policy.backoff(retry_attempts.len());
+        let attempt = json!({
+            "attempt": retry_attempts.len() + 1,
+            "error_code": code.as_str(),
+            "error": error,
+            "backoff_ms": backoff.as_millis() as u64,
+        });
+        eprintln!(
+            "⚠️ [process_provisioning] Retryable provider failure ({}) for instance {}, retry {}/{} in {}ms",
+            code.as_str(),
+            instance_uuid,
+            retry_attempts.len() + 1,
+            retry_policy.max_retries,
+            backoff.as_millis()
+        );
+        logger::log_event_with_metadata(
+            &pool,
+            "PROVISION_RETRY",
+            "failed",
+            instance_uuid,
+            Some(&error),
+            Some(json!({
+                "retry": attempt,
+                "zone": zone,
+                "provider": provider_name,
+                "correlation_id": correlation_id_meta
+            })),
+        )
+        .await
+        .ok();
+        retry_attempts.push(attempt);
+        sleep(backoff).await;
+    };
+    for (idx, name) in collided_names.iter().enumerate() {
+        logger::log_event_with_metadata(
+            &pool,
+            "NAME_COLLISION_RETRY",
+            "success",
+            instance_uuid,
+            None,
+            Some(json!({
+                "rejected_name": name,
+                "next_name": instance_server_name(instance_uuid, idx + 1),
+                "zone": zone,
+                "provider": provider_name,
+                "correlation_id": correlation_id_meta
+            })),
+        )
+        .await
+        .ok();
+    }
 
     match server_id_result {
         Ok(server_id) => {
@@ -1787,7 +2360,8 @@ pub async fn process_provisioning(
                     "correlation_id": correlation_id_meta,
                     "instance_type": instance_type,
                     "image_id": image_id,
-                    "provider": provider_name
+                    "provider": provider_name,
+                    "retry_attempts": retry_attempts
                 });
                 logger::log_event_complete_with_metadata(
                     &pool,
@@ -1860,24 +2434,112 @@ pub async fn process_provisioning(
             // Check if this instance requires diskless boot (needed for volume discovery and resize)
             let requires_diskless = provider.requires_diskless_boot(&instance_type);
 
-            // Discover and track all volumes attached to the instance (including auto-created boot volumes)
-            // This ensures storage_count and storage_sizes_gb are populated immediately
-            // Track ALL volumes (boot + data) so they can be displayed and cleaned up on termination
-            // For Scaleway L4-1-24G: Scaleway creates a Block Storage (sbs_volume) automatically from image snapshot (20GB bootable)
-            let mut auto_created_boot_volume_id: Option<String> = None;
-            let mut auto_created_boot_volume_size_gb: Option<u64> = None;
-
-            if let Ok(attached_volumes) = provider.list_attached_volumes(&zone, &server_id).await {
-                if !attached_volumes.is_empty() {
-                    eprintln!(
-                        "🔍 [process_create] Discovering volumes for instance {} (found {} volumes)",
-                        instance_uuid, attached_volumes.len()
-                    );
-
-                    for av in attached_volumes {
-                        // For Scaleway L4-1-24G: Scaleway creates a Block Storage automatically (20GB bootable)
-                        // We need to resize it to target size before starting the instance
-                        // NOTE: Scaleway API may not mark the volume as boot=true, so for diskless instances,
+            // Diskless boot: local volumes must be gone before the first start.
+            if requires_diskless {
+                let (attempts, interval) = local_volume_removal::retry_policy();
+                let removal = local_volume_removal::remove_and_verify(
+                    provider.as_ref(),
+                    &zone,
+                    &server_id,
+                    &instance_type,
+                    pre_created_volume_id.as_deref(),
+                    attempts,
+                    interval,
+                )
+                .await;
+                let attempts_done = match &removal {
+                    local_volume_removal::Removal::NotNeeded => &[][..],
+                    local_volume_removal::Removal::Removed { attempts } => &attempts[..],
+                    local_volume_removal::Removal::Persisting { attempts, .. } => &attempts[..],
+                };
+                for a in attempts_done {
+                    logger::log_event_with_metadata(
+                        &pool,
+                        "LOCAL_VOLUME_REMOVAL",
+                        if a.error.is_some() {
+                            "failed"
+                        } else {
+                            "success"
+                        },
+                        instance_uuid,
+                        a.error.as_deref(),
+                        Some(json!({
+                            "attempt": a.attempt,
+                            "max_attempts": attempts,
+                            "local_volumes": a.local_volumes,
+                            "server_id": server_id,
+                            "zone": zone,
+                            "correlation_id": correlation_id_meta
+                        })),
+                    )
+                    .await
+                    .ok();
+                }
+                if let local_volume_removal::Removal::Persisting {
+                    remaining,
+                    last_error,
+                    ..
+                } = &removal
+                {
+                    let msg = format!(
+                        "Local volumes still attached to server {} after {} removal attempts: [{}]{}",
+                        server_id,
+                        attempts,
+                        remaining.join(", "),
+                        last_error
+                            .as_deref()
+                            .map(|e| format!(" (last error: {})", e))
+                            .unwrap_or_default()
+                    );
+                    eprintln!("❌ [process_create] {}", msg);
+                    if let Some(log_id) = log_id_execute {
+                        let duration = start.elapsed().as_millis() as i32;
+                        logger::log_event_complete(&pool, log_id, "failed", duration, Some(&msg))
+                            .await
+                            .ok();
+                    }
+                    cleanup_failed_create(
+                        &pool,
+                        provider.as_ref(),
+                        instance_uuid,
+                        &zone,
+                        &server_id,
+                        local_volume_removal::ERROR_CODE,
+                        &msg,
+                        correlation_id_meta.as_deref(),
+                    )
+                    .await;
+                    return;
+                }
+            }
+
+            log_provisioning_step(
+                &pool,
+                instance_uuid,
+                "discover_volumes",
+                start,
+                correlation_id_meta.as_deref(),
+            )
+            .await;
+
+            // Discover and track all volumes attached to the instance (including auto-created boot volumes)
+            // This ensures storage_count and storage_sizes_gb are populated immediately
+            // Track ALL volumes (boot + data) so they can be displayed and cleaned up on termination
+            // For Scaleway L4-1-24G: Scaleway creates a Block Storage (sbs_volume) automatically from image snapshot (20GB bootable)
+            let mut auto_created_boot_volume_id: Option<String> = None;
+            let mut auto_created_boot_volume_size_gb: Option<u64> = None;
+
+            if let Ok(attached_volumes) = provider.list_attached_volumes(&zone, &server_id).await {
+                if !attached_volumes.is_empty() {
+                    eprintln!(
+                        "🔍 [process_create] Discovering volumes for instance {} (found {} volumes)",
+                        instance_uuid, attached_volumes.len()
+                    );
+
+                    for av in attached_volumes {
+                        // For Scaleway L4-1-24G: Scaleway creates a Block Storage automatically (20GB bootable)
+                        // We need to resize it to target size before starting the instance
+                        // NOTE: Scaleway API may not mark the volume as boot=true, so for diskless instances,
                         // we consider the first sbs_volume as the bootable volume
                         if requires_diskless
                             && av.volume_type == "sbs_volume"
@@ -2018,6 +2680,15 @@ pub async fn process_provisioning(
             // This ensures we have the IP address before configuring firewall rules
             // The old code that checked WORKER_EXPOSE_PORTS here has been moved to after IP retrieval
 
+            log_provisioning_step(
+                &pool,
+                instance_uuid,
+                "attach_storage",
+                start,
+                correlation_id_meta.as_deref(),
+            )
+            .await;
+
             // Optional: create + attach a data volume (SBS) based on instance type allocation params.
             // allocation_params shape:
             // {
@@ -2094,11 +2765,18 @@ pub async fn process_provisioning(
                 }
             }
 
+            // A reused volume is attached as is and must outlive this instance.
+            if let Some((_, reused_gb)) = &reused_volume {
+                let gb = data_conf.map_or(*reused_gb, |(gb, _, _)| gb).max(1);
+                data_conf = Some((gb, None, false));
+            }
+
             if let Some((gb, perf_iops, delete_on_terminate)) = data_conf {
                 if gb > 0 {
                     // Some instance types have auto-created storage (e.g., Scaleway RENDER-S with Local Storage)
                     // Check via provider abstraction if we should skip data volume creation
-                    if is_worker_target && provider.should_skip_data_volume_creation(&instance_type)
+                    if is_worker_target
+                        && matches!(storage_strategy, DataVolumeStrategy::Skip { .. })
                     {
                         eprintln!(
                             "ℹ️ [process_create] Skipping data volume creation for instance {} (type {}) - using auto-created storage",
@@ -2242,13 +2920,15 @@ pub async fn process_provisioning(
                     // NOTE: requires_diskless is already defined above (after instance creation)
                     // NOTE: For diskless boot instances, skip attachment here - attach_block_storage_after_boot will handle it AFTER startup and SSH
                     // If pre_created_volume_id exists, use it (volume created BEFORE instance)
-                    // Otherwise, create volume now (fallback path - should not happen in normal flow)
+                    // Otherwise, create volume now (post_attach strategy, or pre_create fallback)
                     if requires_diskless {
                         eprintln!(
                             "⏭️ [process_create] Skipping Block Storage attachment for diskless instance {} - attach_block_storage_after_boot will handle it AFTER startup and SSH",
                             server_id
                         );
-                    } else if !provider.should_skip_data_volume_creation(&instance_type) {
+                    } else if reused_volume.is_some()
+                        || !matches!(storage_strategy, DataVolumeStrategy::Skip { .. })
+                    {
                         let vol_id_to_attach_opt: Option<String> = if let Some(pre_vol_id) =
                             &pre_created_volume_id
                         {
@@ -2259,9 +2939,10 @@ pub async fn process_provisioning(
                             );
                             Some(pre_vol_id.clone())
                         } else {
-                            // Fallback: create volume now (should not happen in normal flow)
+                            // post_attach: create volume now (also the fallback when pre-creation failed)
                             eprintln!(
-                                "⚠️ [process_create] No pre-created volume found - creating data volume AFTER instance creation (fallback): size={}GB",
+                                "ℹ️ [process_create] Creating data volume AFTER instance creation (strategy={}): size={}GB",
+                                storage_strategy.as_str(),
                                 gb
                             );
                             let vol_name = format!("inventiv-data-{}", instance_uuid);
@@ -2276,13 +2957,13 @@ pub async fn process_provisioning(
                     .await
                     .ok();
                             let vol_start = Instant::now();
-                            let volume_type = provider.get_data_volume_type(&instance_type);
+                            let volume_type = storage_strategy.volume_type();
                             let created = provider
                                 .create_volume(
                                     &zone,
                                     &vol_name,
                                     gb_to_bytes(gb),
-                                    &volume_type,
+                                    volume_type,
                                     perf_iops,
                                 )
                                 .await;
@@ -2312,7 +2993,7 @@ pub async fn process_provisioning(
                                             r#"
                                             INSERT INTO instance_volumes 
                                             (id, instance_id, provider_id, zone_code, provider_volume_id, provider_volume_name, volume_type, size_bytes, delete_on_terminate, status, attached_at, is_boot)
-                                            VALUES ($1, $2, $3, $4, $5, $6, 'sbs_volume', $7, $8, 'created', NULL, FALSE)
+                                            VALUES ($1, $2, $3, $4, $5, $6, $9, $7, $8, 'created', NULL, FALSE)
                                             ON CONFLICT (instance_id, provider_volume_id) DO UPDATE
                                             SET status = 'created', deleted_at = NULL
                                             WHERE instance_volumes.deleted_at IS NOT NULL
@@ -2326,6 +3007,7 @@ pub async fn process_provisioning(
                                         .bind(&vol_name)
                                         .bind(gb_to_bytes(gb))
                                         .bind(delete_on_terminate)
+                                        .bind(volume_type)
                             .execute(&pool)
                             .await;
                                     }
@@ -2449,26 +3131,30 @@ pub async fn process_provisioning(
                                 .bind(&msg)
                                 .execute(&pool)
                                 .await;
-                                // Best-effort cleanup of the created volume to avoid cost leak.
-                                let _ = provider.delete_volume(&zone, &vol_id_to_attach).await;
-                                let _ = sqlx::query(
-                                    r#"
-                                UPDATE instance_volumes 
-                                SET status='deleted', deleted_at=NOW() 
-                                WHERE instance_id=$1 AND provider_volume_id=$2
-                                "#,
-                                )
-                                .bind(instance_uuid)
-                                .bind(&vol_id_to_attach)
-                                .execute(&pool)
-                                .await;
+                                // Best-effort cleanup of the created volume to avoid cost leak
+                                // (a reused volume belongs to the user and is kept).
+                                if reused_volume.is_none() {
+                                    let _ = provider.delete_volume(&zone, &vol_id_to_attach).await;
+                                    let _ = sqlx::query(
+                                        r#"
+                                    UPDATE instance_volumes 
+                                    SET status='deleted', deleted_at=NOW() 
+                                    WHERE instance_id=$1 AND provider_volume_id=$2
+                                    "#,
+                                    )
+                                    .bind(instance_uuid)
+                                    .bind(&vol_id_to_attach)
+                                    .execute(&pool)
+                                    .await;
+                                }
                                 // Cleanup server to avoid leak
                                 let _ = provider.terminate_instance(&zone, &server_id).await;
                                 let _ = sqlx::query(
-                                "UPDATE instances SET status='failed', error_code=COALESCE(error_code,'PROVIDER_VOLUME_ATTACH_FAILED'), error_message=COALESCE($2,error_message), failed_at=COALESCE(failed_at,NOW()) WHERE id=$1"
+                                "UPDATE instances SET status='failed', error_code=COALESCE(error_code,$3), error_message=COALESCE($2,error_message), failed_at=COALESCE(failed_at,NOW()) WHERE id=$1"
                             )
                             .bind(instance_uuid)
                             .bind(&msg)
+                            .bind(ErrorCode::ProviderVolumeAttachFailed.as_str())
                             .execute(&pool)
                             .await;
                                 if let Some(log_id) = log_id_execute {
@@ -2492,6 +3178,15 @@ pub async fn process_provisioning(
                 }
             }
 
+            log_provisioning_step(
+                &pool,
+                instance_uuid,
+                "start_server",
+                start,
+                correlation_id_meta.as_deref(),
+            )
+            .await;
+
             // LOG 3.1: PROVIDER_START (API call)
             let start_api = Instant::now();
             let log_id_start = logger::log_event_with_metadata(
@@ -2881,7 +3576,7 @@ pub async fn process_provisioning(
                                     let _ = sqlx::query(
                                     "UPDATE instances
                                      SET status = $2::instance_status,
-                                         error_code = COALESCE(error_code, 'PROVIDER_START_FAILED'),
+                                         error_code = COALESCE(error_code, $4),
                                          error_message = COALESCE($3, error_message),
                                          failed_at = COALESCE(failed_at, NOW()),
                                          deletion_reason = COALESCE(deletion_reason, 'provider_start_failed_cleanup')
@@ -2890,6 +3585,7 @@ pub async fn process_provisioning(
                                 .bind(instance_uuid)
                                 .bind(next_status)
                                 .bind(&msg)
+                                .bind(ErrorCode::ProviderStartFailed.as_str())
                                 .execute(&pool)
                                 .await;
                                     return;
@@ -3072,7 +3768,7 @@ pub async fn process_provisioning(
                         let _ = sqlx::query(
                             "UPDATE instances
                              SET status = $2::instance_status,
-                                 error_code = COALESCE(error_code, 'PROVIDER_START_FAILED'),
+                                 error_code = COALESCE(error_code, $4),
                                  error_message = COALESCE($3, error_message),
                                  failed_at = COALESCE(failed_at, NOW()),
                                  deletion_reason = COALESCE(deletion_reason, 'provider_start_failed_cleanup')
@@ -3081,6 +3777,7 @@ pub async fn process_provisioning(
                         .bind(instance_uuid)
                         .bind(next_status)
                         .bind(&msg)
+                        .bind(ErrorCode::ProviderStartFailed.as_str())
                         .execute(&pool)
                         .await;
                         return;
@@ -3184,7 +3881,7 @@ pub async fn process_provisioning(
                 let _ = sqlx::query(
                     "UPDATE instances
                      SET status = $2::instance_status,
-                         error_code = COALESCE(error_code, 'PROVIDER_START_TIMEOUT'),
+                         error_code = COALESCE(error_code, $4),
                          error_message = COALESCE($3, error_message),
                          failed_at = COALESCE(failed_at, NOW()),
                          deletion_reason = COALESCE(deletion_reason, 'provider_start_timeout_cleanup')
@@ -3193,6 +3890,7 @@ pub async fn process_provisioning(
                 .bind(instance_uuid)
                 .bind(next_status)
                 .bind(&msg)
+                .bind(ErrorCode::ProviderStartTimeout.as_str())
                 .execute(&pool)
                 .await;
                 return;
@@ -3223,6 +3921,15 @@ pub async fn process_provisioning(
             .execute(&pool)
             .await;
 
+            log_provisioning_step(
+                &pool,
+                instance_uuid,
+                "resolve_ip",
+                start,
+                correlation_id_meta.as_deref(),
+            )
+            .await;
+
             // 3.5. Wait for server to be running, then retrieve IP
             // Scaleway assigns IP dynamically only after the server reaches "running" state.
             // This matches the behavior in scw_instance_provision.sh which waits for "running" before checking IP.
@@ -3385,6 +4092,12 @@ pub async fn process_provisioning(
                 }
             }
 
+            // Provider view of the running server (commercial type, zone, hypervisor), for debugging.
+            if server_running {
+                sync_provider_metadata(&pool, provider.as_ref(), instance_uuid, &zone, &server_id)
+                    .await;
+            }
+
             // Configure Security Groups (AFTER IP retrieval, BEFORE SSH check)
             // For Scaleway: Open ports SSH (22), worker HTTP (8000), worker metrics (8080)
             if ip_address.is_some() && auto_install && is_worker_target {
@@ -3540,6 +4253,14 @@ pub async fn process_provisioning(
                 .as_ref()
                 .filter(|_| auto_install && is_worker_target)
             {
+                log_provisioning_step(
+                    &pool,
+                    instance_uuid,
+                    "await_ssh",
+                    start,
+                    correlation_id_meta.as_deref(),
+                )
+                .await;
                 eprintln!("⏳ [process_create] Waiting for SSH to become accessible on {} (max 3 minutes)...", ip_for_ssh);
 
                 let ssh_check_log = logger::log_event_with_metadata(
@@ -3591,6 +4312,42 @@ pub async fn process_provisioning(
                             .ok();
                         }
 
+                        // The provider said the data volume is attached; make sure the guest sees it
+                        // before installing (otherwise the model download fails much later).
+                        if let Some(probe) =
+                            volume_verification::SshBlockDeviceProbe::new(ip_for_ssh)
+                        {
+                            if let Err(msg) = verify_data_volumes_visible(
+                                &pool,
+                                instance_uuid,
+                                &probe,
+                                correlation_id_meta.as_deref(),
+                            )
+                            .await
+                            {
+                                let _ = state_machine::booting_to_startup_failed(
+                                    &pool,
+                                    instance_uuid,
+                                    volume_verification::ERROR_CODE,
+                                    &msg,
+                                )
+                                .await;
+                                if let Some(log_id) = log_id_execute {
+                                    let duration = start.elapsed().as_millis() as i32;
+                                    logger::log_event_complete(
+                                        &pool,
+                                        log_id,
+                                        "failed",
+                                        duration,
+                                        Some(&msg),
+                                    )
+                                    .await
+                                    .ok();
+                                }
+                                return;
+                            }
+                        }
+
                         // Transition to "installing" status when SSH becomes accessible
                         // This indicates we're ready to start worker installation
                         if is_worker_target {
@@ -3684,26 +4441,30 @@ pub async fn process_provisioning(
 
                     // Mark instance as failed if SSH is not accessible after 3 minutes
                     // This prevents indefinite waiting
-                    let _ = sqlx::query(
-                        "UPDATE instances
-                         SET status = 'startup_failed'::instance_status,
-                             error_code = COALESCE(error_code, 'SSH_NOT_ACCESSIBLE'),
-                             error_message = COALESCE($2, error_message),
-                             failed_at = COALESCE(failed_at, NOW())
-                         WHERE id = $1",
+                    let _ = state_machine::booting_to_startup_failed(
+                        &pool,
+                        instance_uuid,
+                        ErrorCode::SshNotAccessible.as_str(),
+                        &format!(
+                            "SSH not accessible after {} seconds on {}",
+                            elapsed_seconds, ip_for_ssh
+                        ),
                     )
-                    .bind(instance_uuid)
-                    .bind(format!(
-                        "SSH not accessible after {} seconds on {}",
-                        elapsed_seconds, ip_for_ssh
-                    ))
-                    .execute(&pool)
                     .await;
 
                     // Don't return - let the function complete normally so cleanup can happen
                 }
             }
 
+            log_provisioning_step(
+                &pool,
+                instance_uuid,
+                "finalize",
+                start,
+                correlation_id_meta.as_deref(),
+            )
+            .await;
+
             // LOG 4: INSTANCE_CREATED
             let db_start = Instant::now();
             let log_id_created = logger::log_event_with_metadata(
@@ -3775,11 +4536,24 @@ pub async fn process_provisioning(
         }
         Err(e) => {
             let msg = format!("Failed to create instance: {:?}", e);
+            // Known provider failures get a stable code; anything else stays PROVIDER_CREATE_FAILED.
+            let classified_code =
+                inventiv_providers::provider_error_code(&e).and_then(classified_error_code);
             if let Some(log_id) = log_id_provider {
                 let api_duration = api_start.elapsed().as_millis() as i32;
-                logger::log_event_complete(&pool, log_id, "failed", api_duration, Some(&msg))
-                    .await
-                    .ok();
+                logger::log_event_complete_with_metadata(
+                    &pool,
+                    log_id,
+                    "failed",
+                    api_duration,
+                    Some(&msg),
+                    Some(json!({
+                        "error_code": classified_code,
+                        "retry_attempts": retry_attempts
+                    })),
+                )
+                .await
+                .ok();
             }
             if let Some(log_id) = log_id_execute {
                 let duration = start.elapsed().as_millis() as i32;
@@ -3787,68 +4561,489 @@ pub async fn process_provisioning(
                     .await
                     .ok();
             }
-            let _ = sqlx::query(
-                "UPDATE instances
-                  SET status = 'failed',
-                      error_code = COALESCE(error_code, 'PROVIDER_CREATE_FAILED'),
-                      error_message = COALESCE($2, error_message),
-                      failed_at = COALESCE(failed_at, NOW())
-                  WHERE id = $1",
+            let _ = state_machine::provisioning_to_provisioning_failed(
+                &pool,
+                instance_uuid,
+                classified_code
+                    .unwrap_or(ErrorCode::ProviderCreateFailed)
+                    .as_str(),
+                &msg,
             )
-            .bind(instance_uuid)
-            .bind(&msg)
-            .execute(&pool)
             .await;
         }
     }
 }
 
-/// Resolve vLLM Docker image with hierarchy:
-/// 1. instance_types.allocation_params.vllm_image (instance-type specific)
-/// 2. provider_settings.WORKER_VLLM_IMAGE_<INSTANCE_TYPE_CODE> (per instance type)
-/// 3. provider_settings.WORKER_VLLM_IMAGE (provider default)
-/// 4. WORKER_VLLM_IMAGE (env var)
-/// 5. Hardcoded default (stable version, not "latest")
-pub async fn resolve_vllm_image(
+/// Instance error code for a classified provider failure (None for unclassified errors).
+fn classified_error_code(code: inventiv_providers::ProviderErrorCode) -> Option<ErrorCode> {
+    use inventiv_providers::ProviderErrorCode as P;
+    match code {
+        P::QuotaExceeded => Some(ErrorCode::QuotaExceeded),
+        P::ImageNotFound => Some(ErrorCode::ImageNotFound),
+        P::InvalidVolume => Some(ErrorCode::InvalidVolume),
+        P::RateLimited => Some(ErrorCode::RateLimited),
+        P::NameConflict => Some(ErrorCode::NameConflict),
+        P::OutOfCapacity => Some(ErrorCode::OutOfCapacity),
+        P::Unknown => None,
+    }
+}
+
+/// Best-effort teardown of a server whose provisioning failed before it was started: terminate it,
+/// delete the volumes created for it and record the failure (`terminating` once the provider
+/// accepted the deletion, else `provisioning_failed`).
+#[allow(clippy::too_many_arguments)]
+async fn cleanup_failed_create(
     pool: &Pool<Postgres>,
-    instance_type_id: Option<Uuid>,
-    provider_id: Option<Uuid>,
-    instance_type_code: &str,
-) -> String {
-    // 1. Check instance_types.allocation_params.vllm_image (instance-type specific)
-    if let Some(type_id) = instance_type_id {
-        if let Ok(Some(vllm_image)) = sqlx::query_scalar::<_, Option<String>>(
-            "SELECT NULLIF(TRIM(allocation_params->>'vllm_image'), '') FROM instance_types WHERE id = $1"
-        )
-        .bind(type_id)
-        .fetch_optional(pool)
-        .await
-        {
-            if let Some(img) = vllm_image.filter(|img| !img.trim().is_empty()) {
-                eprintln!("✅ [resolve_vllm_image] Using instance-type specific image: {} (from allocation_params)", img);
-                return img;
-            }
+    provider: &dyn inventiv_providers::CloudProvider,
+    instance_id: Uuid,
+    zone: &str,
+    server_id: &str,
+    error_code: &str,
+    msg: &str,
+    correlation_id: Option<&str>,
+) {
+    let terminate_log = logger::log_event_with_metadata(
+        pool,
+        "PROVIDER_TERMINATE",
+        "in_progress",
+        instance_id,
+        None,
+        Some(json!({"zone": zone, "server_id": server_id, "correlation_id": correlation_id, "reason": error_code})),
+    )
+    .await
+    .ok();
+    let terminate_start = Instant::now();
+    let terminate_res = provider.terminate_instance(zone, server_id).await;
+    if let Some(lid) = terminate_log {
+        let dur = terminate_start.elapsed().as_millis() as i32;
+        let (status, error) = match &terminate_res {
+            Ok(true) => ("success", None),
+            Ok(false) => (
+                "failed",
+                Some("Provider terminate returned false".to_string()),
+            ),
+            Err(err) => ("failed", Some(err.to_string())),
+        };
+        logger::log_event_complete(pool, lid, status, dur, error.as_deref())
+            .await
+            .ok();
+    }
+
+    let vols: Vec<(Uuid, String, bool)> = sqlx::query_as(
+        "SELECT id, provider_volume_id, delete_on_terminate
+         FROM instance_volumes
+         WHERE instance_id = $1 AND deleted_at IS NULL",
+    )
+    .bind(instance_id)
+    .fetch_all(pool)
+    .await
+    .unwrap_or_default();
+    for (vol_row_id, provider_volume_id, delete_on_terminate) in vols {
+        if !delete_on_terminate {
+            continue;
         }
+        let _ = provider.delete_volume(zone, &provider_volume_id).await;
+        let _ = sqlx::query(
+            "UPDATE instance_volumes SET status='deleted', deleted_at=NOW() WHERE id=$1",
+        )
+        .bind(vol_row_id)
+        .execute(pool)
+        .await;
     }
 
-    // 2. Check provider_settings.WORKER_VLLM_IMAGE_<INSTANCE_TYPE_CODE> (per instance type)
-    if let Some(pid) = provider_id {
-        let setting_key = format!(
-            "WORKER_VLLM_IMAGE_{}",
-            instance_type_code.replace("-", "_").to_uppercase()
+    let next_status = match terminate_res {
+        Ok(true) => "terminating",
+        _ => "provisioning_failed",
+    };
+    let _ = sqlx::query(
+        "UPDATE instances
+         SET status = $2::instance_status,
+             error_code = COALESCE(error_code, $3),
+             error_message = COALESCE($4, error_message),
+             failed_at = COALESCE(failed_at, NOW()),
+             deletion_reason = COALESCE(deletion_reason, $5)
+         WHERE id = $1",
+    )
+    .bind(instance_id)
+    .bind(next_status)
+    .bind(error_code)
+    .bind(msg)
+    .bind(format!("{}_cleanup", error_code.to_ascii_lowercase()))
+    .execute(pool)
+    .await;
+}
+
+/// Data volume the deployment asked to reuse (`reuse_volume_id`): reserved by the API as an
+/// `instance_volumes` row (status 'reserved', kept on terminate). Returns its id and size in GB.
+async fn reserved_data_volume(pool: &Pool<Postgres>, instance_id: Uuid) -> Option<(String, i64)> {
+    let row: Option<(String, i64)> = sqlx::query_as(
+        "SELECT provider_volume_id, size_bytes FROM instance_volumes
+         WHERE instance_id = $1 AND status = 'reserved' AND deleted_at IS NULL
+         ORDER BY created_at
+         LIMIT 1",
+    )
+    .bind(instance_id)
+    .fetch_optional(pool)
+    .await
+    .ok()
+    .flatten();
+    row.map(|(id, size_bytes)| (id, size_bytes / 1_000_000_000))
+}
+
+/// Records a `PROVISIONING_STEP` action log (step name + elapsed time since provisioning started)
+/// so the instance detail page can render a progress timeline.
+async fn log_provisioning_step(
+    pool: &Pool<Postgres>,
+    instance_id: Uuid,
+    step: &str,
+    start: Instant,
+    correlation_id: Option<&str>,
+) {
+    logger::log_event_with_metadata(
+        pool,
+        "PROVISIONING_STEP",
+        "success",
+        instance_id,
+        None,
+        Some(json!({
+            "step": step,
+            "elapsed_ms": start.elapsed().as_millis() as i64,
+            "correlation_id": correlation_id
+        })),
+    )
+    .await
+    .ok();
+}
+
+/// Confirm every attached data volume shows up on the instance (see `volume_verification`).
+/// Returns the failure message for the first volume that does not appear in time.
+async fn verify_data_volumes_visible(
+    pool: &Pool<Postgres>,
+    instance_id: Uuid,
+    probe: &dyn volume_verification::BlockDeviceProbe,
+    correlation_id: Option<&str>,
+) -> Result<(), String> {
+    let Some((attempts, interval)) = volume_verification::verify_window() else {
+        return Ok(());
+    };
+    for volume in volume_verification::attached_data_volumes(pool, instance_id).await {
+        let started = Instant::now();
+        let outcome =
+            volume_verification::verify_attached(probe, &volume, attempts, interval).await;
+        let elapsed_ms = started.elapsed().as_millis() as i64;
+        let (status, polls, error) = match &outcome {
+            volume_verification::Verification::Verified { attempts } => {
+                ("success", *attempts, None)
+            }
+            volume_verification::Verification::Unverified {
+                attempts,
+                last_error,
+            } => ("failed", *attempts, last_error.clone()),
+        };
+        eprintln!(
+            "{} [process_create] Volume {} visibility on instance {}: {} after {} poll(s) ({}ms){}",
+            if status == "success" { "✅" } else { "❌" },
+            volume.provider_volume_id,
+            instance_id,
+            status,
+            polls,
+            elapsed_ms,
+            error
+                .as_deref()
+                .map(|e| format!(", last_error={}", e))
+                .unwrap_or_default()
         );
-        if let Ok(Some(img)) = sqlx::query_scalar::<_, Option<String>>(
-            "SELECT NULLIF(TRIM(value_text), '') FROM provider_settings WHERE provider_id = $1 AND key = $2"
+        logger::log_event_with_metadata(
+            pool,
+            "VOLUME_ATTACH_VERIFY",
+            status,
+            instance_id,
+            error.as_deref(),
+            Some(json!({
+                "volume_id": volume.provider_volume_id,
+                "size_bytes": volume.size_bytes,
+                "polls": polls,
+                "elapsed_ms": elapsed_ms,
+                "correlation_id": correlation_id
+            })),
         )
-        .bind(pid)
-        .bind(&setting_key)
-        .fetch_optional(pool)
         .await
-        {
-            if let Some(img) = img.filter(|img| !img.trim().is_empty()) {
-                eprintln!("✅ [resolve_vllm_image] Using provider setting for {}: {}", instance_type_code, img);
-                return img;
-            }
+        .ok();
+        if status != "success" {
+            return Err(format!(
+                "Volume {} is attached at the provider but no block device appeared on the instance after {} poll(s)",
+                volume.provider_volume_id, polls
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Provider create attempts when the server name collides (initial name + retries).
+const MAX_NAME_COLLISION_ATTEMPTS: usize = 3;
+
+/// Re-queue policy for retryable provider create failures (capacity, rate limit):
+/// `PROVISION_RETRY_MAX` extra attempts (default 3), exponential backoff starting at
+/// `PROVISION_RETRY_BACKOFF_MS` (default 5000).
+struct ProvisionRetryPolicy {
+    max_retries: usize,
+    base_backoff: Duration,
+}
+
+impl ProvisionRetryPolicy {
+    fn from_env() -> Self {
+        let max_retries = std::env::var("PROVISION_RETRY_MAX")
+            .ok()
+            .and_then(|s| s.trim().parse::<usize>().ok())
+            .unwrap_or(3);
+        let backoff_ms = std::env::var("PROVISION_RETRY_BACKOFF_MS")
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .unwrap_or(5000);
+        Self {
+            max_retries,
+            base_backoff: Duration::from_millis(backoff_ms),
+        }
+    }
+
+    /// Delay before retry number `retry` (0-based), capped at 5 minutes.
+    fn backoff(&self, retry: usize) -> Duration {
+        let factor = 1u32 << retry.min(10);
+        self.base_backoff
+            .saturating_mul(factor)
+            .min(Duration::from_secs(300))
+    }
+}
+
+/// Deterministic server name for an instance: `inventiv-worker-<id>`, then `-r1`, `-r2`... on retries.
+fn instance_server_name(instance_id: Uuid, attempt: usize) -> String {
+    if attempt == 0 {
+        format!("inventiv-worker-{}", instance_id)
+    } else {
+        format!("inventiv-worker-{}-r{}", instance_id, attempt)
+    }
+}
+
+/// How the data volume of a worker instance is provisioned, decided by the provider hooks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DataVolumeStrategy {
+    /// The instance type ships with its own storage (e.g. Scaleway RENDER-S Local Storage).
+    Skip { volume_type: String },
+    /// Create the volume before the server and pass it to `create_instance`.
+    PreCreate { volume_type: String },
+    /// Create and attach the volume once the server exists.
+    PostAttach { volume_type: String },
+}
+
+impl DataVolumeStrategy {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Skip { .. } => "skip",
+            Self::PreCreate { .. } => "pre_create",
+            Self::PostAttach { .. } => "post_attach",
+        }
+    }
+
+    fn volume_type(&self) -> &str {
+        match self {
+            Self::Skip { volume_type }
+            | Self::PreCreate { volume_type }
+            | Self::PostAttach { volume_type } => volume_type,
+        }
+    }
+}
+
+/// Resolve the data-volume strategy for an instance type. Skipping wins over pre-creation.
+fn data_volume_strategy(
+    provider: &dyn inventiv_providers::CloudProvider,
+    instance_type: &str,
+) -> DataVolumeStrategy {
+    let volume_type = provider.get_data_volume_type(instance_type);
+    if provider.should_skip_data_volume_creation(instance_type) {
+        DataVolumeStrategy::Skip { volume_type }
+    } else if provider.should_pre_create_data_volume(instance_type) {
+        DataVolumeStrategy::PreCreate { volume_type }
+    } else {
+        DataVolumeStrategy::PostAttach { volume_type }
+    }
+}
+
+/// Store the provider's normalized instance metadata in `instances.provider_metadata`.
+/// Best-effort: provisioning never fails on it. Returns whether metadata was stored.
+async fn sync_provider_metadata(
+    pool: &Pool<Postgres>,
+    provider: &dyn inventiv_providers::CloudProvider,
+    instance_id: Uuid,
+    zone: &str,
+    server_id: &str,
+) -> bool {
+    let details = match provider.get_instance_details(zone, server_id).await {
+        Ok(Some(details)) => details,
+        Ok(None) => return false,
+        Err(e) => {
+            eprintln!(
+                "⚠️ [process_create] Could not fetch provider metadata for {}: {}",
+                server_id, e
+            );
+            return false;
+        }
+    };
+    sqlx::query("UPDATE instances SET provider_metadata = $2 WHERE id = $1")
+        .bind(instance_id)
+        .bind(details.provider_metadata())
+        .execute(pool)
+        .await
+        .is_ok()
+}
+
+/// Provider tags derived from the instance's cost attribution (empty when none is set).
+fn provider_billing_tags(cost_center: Option<&str>) -> Vec<String> {
+    cost_center
+        .map(|c| vec![format!("cost_center={}", c)])
+        .unwrap_or_default()
+}
+
+/// Create the server, retrying with a disambiguated name when the provider reports a name
+/// collision. Returns the create result and the names rejected as collisions (for logging).
+#[allow(clippy::too_many_arguments)]
+async fn create_instance_with_name_retry(
+    provider: &dyn inventiv_providers::CloudProvider,
+    instance_id: Uuid,
+    zone: &str,
+    instance_type: &str,
+    image_id: &str,
+    cloud_init: Option<&str>,
+    volumes: Option<&[String]>,
+    tags: &[String],
+) -> (anyhow::Result<String>, Vec<String>) {
+    let mut collided = Vec::new();
+    let mut attempt = 0;
+    loop {
+        let name = instance_server_name(instance_id, attempt);
+        let res = provider
+            .create_instance_tagged(
+                &name,
+                zone,
+                instance_type,
+                image_id,
+                cloud_init,
+                volumes,
+                tags,
+            )
+            .await;
+        let is_collision = res
+            .as_ref()
+            .err()
+            .and_then(inventiv_providers::provider_error_code)
+            == Some(inventiv_providers::ProviderErrorCode::NameConflict);
+        attempt += 1;
+        if !is_collision || attempt >= MAX_NAME_COLLISION_ATTEMPTS {
+            return (res, collided);
+        }
+        eprintln!(
+            "⚠️ [process_create] Server name '{}' already exists on provider, retrying with a new name",
+            name
+        );
+        collided.push(name);
+    }
+}
+
+/// Use the model's boot image override when the provider can see it; otherwise keep `resolved`.
+/// For `diskless` types the override must also be able to boot from Block Storage, so it never
+/// replaces the validated diskless image with one that would recreate local volumes.
+async fn apply_model_boot_image(
+    provider: &dyn inventiv_providers::CloudProvider,
+    zone: &str,
+    resolved: String,
+    model_image: Option<String>,
+    diskless: bool,
+) -> String {
+    let Some(img) = model_image else {
+        return resolved;
+    };
+    let usable = if diskless {
+        provider.check_diskless_boot_image(zone, &img).await
+    } else {
+        provider.check_image_exists(zone, &img).await
+    };
+    match usable {
+        Ok(true) => {
+            println!(
+                "ℹ️ Using model boot image override '{}' (instead of '{}')",
+                img, resolved
+            );
+            img
+        }
+        Ok(false) if diskless => {
+            eprintln!(
+                "⚠️ Model boot image '{}' is missing or not diskless-bootable in zone '{}'; falling back to '{}'",
+                img, zone, resolved
+            );
+            resolved
+        }
+        Ok(false) => {
+            eprintln!(
+                "⚠️ Model boot image '{}' not found in zone '{}'; falling back to '{}'",
+                img, zone, resolved
+            );
+            resolved
+        }
+        Err(e) => {
+            eprintln!(
+                "⚠️ Could not verify model boot image '{}' in zone '{}' ({}); falling back to '{}'",
+                img, zone, e, resolved
+            );
+            resolved
+        }
+    }
+}
+
+/// Resolve vLLM Docker image with hierarchy:
+/// 1. instance_types.allocation_params.vllm_image (instance-type specific)
+/// 2. provider_settings.WORKER_VLLM_IMAGE_<INSTANCE_TYPE_CODE> (per instance type)
+/// 3. provider_settings.WORKER_VLLM_IMAGE (provider default)
+/// 4. WORKER_VLLM_IMAGE (env var)
+/// 5. Hardcoded default (stable version, not "latest")
+pub async fn resolve_vllm_image(
+    pool: &Pool<Postgres>,
+    instance_type_id: Option<Uuid>,
+    provider_id: Option<Uuid>,
+    instance_type_code: &str,
+) -> String {
+    // 1. Check instance_types.allocation_params.vllm_image (instance-type specific)
+    if let Some(type_id) = instance_type_id {
+        if let Ok(Some(vllm_image)) = sqlx::query_scalar::<_, Option<String>>(
+            "SELECT NULLIF(TRIM(allocation_params->>'vllm_image'), '') FROM instance_types WHERE id = $1"
+        )
+        .bind(type_id)
+        .fetch_optional(pool)
+        .await
+        {
+            if let Some(img) = vllm_image.filter(|img| !img.trim().is_empty()) {
+                eprintln!("✅ [resolve_vllm_image] Using instance-type specific image: {} (from allocation_params)", img);
+                return img;
+            }
+        }
+    }
+
+    // 2. Check provider_settings.WORKER_VLLM_IMAGE_<INSTANCE_TYPE_CODE> (per instance type)
+    if let Some(pid) = provider_id {
+        let setting_key = format!(
+            "WORKER_VLLM_IMAGE_{}",
+            instance_type_code.replace("-", "_").to_uppercase()
+        );
+        if let Ok(Some(img)) = sqlx::query_scalar::<_, Option<String>>(
+            "SELECT NULLIF(TRIM(value_text), '') FROM provider_settings WHERE provider_id = $1 AND key = $2"
+        )
+        .bind(pid)
+        .bind(&setting_key)
+        .fetch_optional(pool)
+        .await
+        {
+            if let Some(img) = img.filter(|img| !img.trim().is_empty()) {
+                eprintln!("✅ [resolve_vllm_image] Using provider setting for {}: {}", instance_type_code, img);
+                return img;
+            }
         }
     }
 
@@ -3883,153 +5078,29 @@ pub async fn resolve_vllm_image(
     // Default: v0.13.0 is a stable version available on Docker Hub
     // Note: For P100 (RENDER-S), this may need to be a version compiled with sm_60 support
     // For L4/L40S, this version should work fine
-    let default_image = "vllm/vllm-openai:v0.13.0".to_string();
+    let default_image = cloud_init::DEFAULT_VLLM_IMAGE.to_string();
     eprintln!("ℹ️ [resolve_vllm_image] Using hardcoded default: {} (consider configuring instance_types.allocation_params.vllm_image)", default_image);
     default_image
 }
 
-#[allow(clippy::too_many_arguments)]
-fn build_worker_cloud_init(
-    ssh_pub: &str,
-    instance_id: &str,
-    control_plane_url: &str,
-    model_id: &str,
-    vllm_image: &str,
-    vllm_port: u16,
-    worker_health_port: u16,
-    agent_source_url: &str,
-    worker_auth_token: &str,
-    worker_hf_token: &str,
-) -> String {
-    // Keep it simple for initial DEV->Scaleway validation:
-    // - Run vLLM from upstream image
-    // - Run agent from python image (mount agent.py downloaded at boot)
-    // - Use host network for agent so it can talk to vLLM at 127.0.0.1
-    let mut cloud = String::new();
-    cloud.push_str("#cloud-config\n");
-    if !ssh_pub.trim().is_empty() {
-        cloud.push_str("ssh_authorized_keys:\n");
-        cloud.push_str(&format!("  - {}\n", ssh_pub.trim()));
-    }
-    cloud.push_str("\nwrite_files:\n");
-    cloud.push_str("  - path: /usr/local/bin/inventiv-worker-bootstrap.sh\n");
-    cloud.push_str("    permissions: '0755'\n");
-    cloud.push_str("    content: |\n");
-    cloud.push_str("      #!/usr/bin/env bash\n");
-    cloud.push_str("      set -euo pipefail\n");
-    cloud.push_str("      echo '[inventiv-worker] bootstrap starting'\n");
-    cloud.push_str(&format!("      INSTANCE_ID=\"{}\"\n", instance_id));
-    cloud.push_str(&format!(
-        "      CONTROL_PLANE_URL=\"{}\"\n",
-        control_plane_url
-    ));
-    cloud.push_str(&format!("      MODEL_ID=\"{}\"\n", model_id));
-    cloud.push_str(&format!("      VLLM_IMAGE=\"{}\"\n", vllm_image));
-    cloud.push_str(&format!("      VLLM_PORT=\"{}\"\n", vllm_port));
-    cloud.push_str(&format!(
-        "      WORKER_HEALTH_PORT=\"{}\"\n",
-        worker_health_port
-    ));
-    cloud.push_str(&format!("      AGENT_URL=\"{}\"\n", agent_source_url));
-    cloud.push_str(&format!(
-        "      WORKER_AUTH_TOKEN=\"{}\"\n",
-        worker_auth_token
-    ));
-    cloud.push_str(&format!("      WORKER_HF_TOKEN=\"{}\"\n", worker_hf_token));
-    cloud.push_str("      export DEBIAN_FRONTEND=noninteractive\n");
-    cloud.push('\n');
-    cloud.push_str("      if ! command -v docker >/dev/null 2>&1; then\n");
-    cloud.push_str("        echo '[inventiv-worker] installing docker'\n");
-    cloud.push_str("        apt-get update -y\n");
-    cloud.push_str("        apt-get install -y ca-certificates curl gnupg\n");
-    cloud.push_str("        curl -fsSL https://get.docker.com | sh\n");
-    cloud.push_str("      fi\n");
-    cloud.push_str("      systemctl enable --now docker || true\n");
-    cloud.push('\n');
-    cloud.push_str("      # Enable NVIDIA runtime for docker (required for --gpus all)\n");
-    cloud.push_str("      if command -v nvidia-smi >/dev/null 2>&1; then\n");
-    cloud.push_str("        echo '[inventiv-worker] installing nvidia-container-toolkit'\n");
-    cloud.push_str("        set +e\n");
-    cloud.push_str("        . /etc/os-release\n");
-    cloud.push_str("        distribution=\"${ID}${VERSION_ID}\"\n");
-    cloud.push_str("        curl -fsSL https://nvidia.github.io/libnvidia-container/gpgkey | gpg --batch --yes --dearmor -o /usr/share/keyrings/nvidia-container-toolkit-keyring.gpg\n");
-    cloud.push_str("        curl -fsSL \"https://nvidia.github.io/libnvidia-container/${distribution}/libnvidia-container.list\" \\\n");
-    cloud.push_str("          | sed 's#deb https://#deb [signed-by=/usr/share/keyrings/nvidia-container-toolkit-keyring.gpg] https://#g' \\\n");
-    cloud.push_str("          > /etc/apt/sources.list.d/nvidia-container-toolkit.list\n");
-    cloud.push_str("        apt-get update -y\n");
-    cloud.push_str("        apt-get install -y nvidia-container-toolkit\n");
-    cloud.push_str("        nvidia-ctk runtime configure --runtime=docker\n");
-    cloud.push_str("        systemctl restart docker\n");
-    cloud.push_str("        echo '[inventiv-worker] nvidia-container-toolkit configured'\n");
-    cloud.push_str("        set -e\n");
-    cloud.push_str("      else\n");
-    cloud.push_str("        echo '[inventiv-worker] nvidia-smi not found; skipping nvidia-container-toolkit'\n");
-    cloud.push_str("      fi\n");
-    cloud.push('\n');
-    cloud.push_str("      mkdir -p /opt/inventiv-worker\n");
-    cloud.push_str("      curl -fsSL \"$AGENT_URL\" -o /opt/inventiv-worker/agent.py\n");
-    cloud.push('\n');
-    cloud.push_str(
-        "      for i in 1 2 3 4 5; do docker pull \"$VLLM_IMAGE\" && break || sleep 5; done\n",
-    );
-    cloud.push_str(
-        "      for i in 1 2 3 4 5; do docker pull python:3.11-slim && break || sleep 5; done\n",
+/// Catalog sync (`CMD:SYNC_CATALOG`). When `provider_code` is set only that provider is synced
+/// (e.g. after changing its credentials); otherwise a full sync runs.
+///
+/// Providers are synced in parallel, at most `CATALOG_SYNC_CONCURRENCY` at a time; a provider that
+/// fails (missing credentials, unknown code) does not stop the others. Significant instance type
+/// price changes are published as `EVT:PRICE_CHANGED` FinOps events.
+pub async fn process_catalog_sync(
+    pool: Pool<Postgres>,
+    redis_client: redis::Client,
+    provider_code: Option<String>,
+) {
+    let providers = catalog_sync_targets(provider_code.as_deref());
+    let concurrency = catalog_sync_concurrency();
+    println!(
+        "🔄 [Catalog Sync] Starting catalog synchronization (providers: {}, concurrency: {})...",
+        providers.join(", "),
+        concurrency
     );
-    cloud.push('\n');
-    cloud.push_str("      docker rm -f vllm >/dev/null 2>&1 || true\n");
-    cloud.push_str("      docker run -d --restart unless-stopped \\\n");
-    cloud.push_str("        --name vllm \\\n");
-    cloud.push_str("        --gpus all \\\n");
-    cloud.push_str(&format!("        -p {0}:{0} \\\n", vllm_port));
-    cloud.push_str("        -e HUGGING_FACE_HUB_TOKEN=\"$WORKER_HF_TOKEN\" \\\n");
-    cloud.push_str("        -e HUGGINGFACE_HUB_TOKEN=\"$WORKER_HF_TOKEN\" \\\n");
-    cloud.push_str("        -e HF_TOKEN=\"$WORKER_HF_TOKEN\" \\\n");
-    cloud.push_str("        -e HF_HOME=/opt/inventiv-worker/hf \\\n");
-    cloud.push_str("        -e TRANSFORMERS_CACHE=/opt/inventiv-worker/hf \\\n");
-    cloud.push_str("        -v /opt/inventiv-worker:/opt/inventiv-worker \\\n");
-    cloud.push_str("        \"$VLLM_IMAGE\" \\\n");
-    cloud.push_str(&format!("        --host 0.0.0.0 --port {} \\\n", vllm_port));
-    cloud.push_str("        --model \"$MODEL_ID\" \\\n");
-    cloud.push_str("        --dtype float16\n");
-    cloud.push('\n');
-    cloud.push_str("      docker rm -f inventiv-agent >/dev/null 2>&1 || true\n");
-    cloud.push_str("      docker run -d --restart unless-stopped \\\n");
-    cloud.push_str("        --name inventiv-agent \\\n");
-    cloud.push_str("        --network host \\\n");
-    cloud.push_str("        -e CONTROL_PLANE_URL=\"$CONTROL_PLANE_URL\" \\\n");
-    cloud.push_str("        -e INSTANCE_ID=\"$INSTANCE_ID\" \\\n");
-    cloud.push_str("        -e MODEL_ID=\"$MODEL_ID\" \\\n");
-    cloud.push_str(&format!(
-        "        -e VLLM_BASE_URL=\"http://127.0.0.1:{}\" \\\n",
-        vllm_port
-    ));
-    cloud.push_str("        -e WORKER_HEALTH_PORT=\"$WORKER_HEALTH_PORT\" \\\n");
-    cloud.push_str("        -e WORKER_VLLM_PORT=\"$VLLM_PORT\" \\\n");
-    cloud.push_str("        -e WORKER_HEARTBEAT_INTERVAL_S=10 \\\n");
-    cloud.push_str("        -e WORKER_AUTH_TOKEN=\"$WORKER_AUTH_TOKEN\" \\\n");
-    cloud.push_str("        -v /opt/inventiv-worker/agent.py:/app/agent.py:ro \\\n");
-    cloud.push_str("        python:3.11-slim \\\n");
-    cloud.push_str("        bash -lc \"pip install --no-cache-dir requests >/dev/null && python /app/agent.py\"\n");
-    cloud.push('\n');
-    cloud.push_str("      echo '[inventiv-worker] bootstrap done'\n");
-    cloud.push('\n');
-    cloud.push_str("runcmd:\n");
-    cloud.push_str("  - [ bash, -lc, /usr/local/bin/inventiv-worker-bootstrap.sh ]\n");
-    cloud
-}
-
-fn build_ssh_key_cloud_init(ssh_pub: &str) -> String {
-    let mut cloud = String::new();
-    cloud.push_str("#cloud-config\n");
-    if !ssh_pub.trim().is_empty() {
-        cloud.push_str("ssh_authorized_keys:\n");
-        cloud.push_str(&format!("  - {}\n", ssh_pub.trim()));
-    }
-    cloud
-}
-
-pub async fn process_catalog_sync(pool: Pool<Postgres>) {
-    println!("🔄 [Catalog Sync] Starting catalog synchronization...");
 
     // Get default organization (for global catalog sync operations)
     let default_org_id: Option<uuid::Uuid> = sqlx::query_scalar(
@@ -4045,198 +5116,532 @@ pub async fn process_catalog_sync(pool: Pool<Postgres>) {
         return;
     };
 
-    // 1. Get Provider (Scaleway)
-    let provider_name = ProviderManager::current_provider_name();
-    if let Ok(provider) = ProviderManager::get_provider(&provider_name, default_org_id, pool.clone()).await {
-        // Ensure the provider exists in DB (required for Settings UI and FK integrity).
-        let provider_uuid: Option<Uuid> = sqlx::query_scalar(
-            r#"
-            INSERT INTO providers (id, name, code, description, is_active)
-            VALUES (gen_random_uuid(), $1, $2, $3, true)
-            ON CONFLICT (code)
-            DO UPDATE SET
-              name = EXCLUDED.name,
-              description = EXCLUDED.description,
-              is_active = true
-            RETURNING id
-            "#,
-        )
-        .bind(match provider_name.as_str() {
-            "scaleway" => "Scaleway",
-            "mock" => "Mock",
-            _ => provider_name.as_str(),
+    let results = sync_provider_catalogs(&pool, providers, concurrency, |provider_name| {
+        let pool = pool.clone();
+        async move { ProviderManager::get_provider(&provider_name, default_org_id, pool).await }
+    })
+    .await;
+
+    let mut summary = Vec::with_capacity(results.len());
+    for result in results {
+        match result.outcome {
+            Ok(changes) => {
+                summary.push(format!(
+                    "{}: ok ({} price changes)",
+                    result.provider,
+                    changes.len()
+                ));
+                for change in changes {
+                    let evt = change.to_event("orchestrator");
+                    if let Err(e) = finops_events::publish_finops_event(&redis_client, &evt).await {
+                        eprintln!(
+                            "❌ [Catalog Sync] Failed to publish PRICE_CHANGED for {}: {:?}",
+                            change.instance_type_code, e
+                        );
+                    }
+                }
+            }
+            Err(e) => {
+                println!(
+                    "❌ [Catalog Sync] Provider '{}' not configured: {}",
+                    result.provider, e
+                );
+                summary.push(format!("{}: failed ({})", result.provider, e));
+            }
+        }
+    }
+    println!("✅ [Catalog Sync] Done: {}", summary.join("; "));
+}
+
+const DEFAULT_CATALOG_SYNC_CONCURRENCY: usize = 4;
+
+fn catalog_sync_concurrency() -> usize {
+    std::env::var("CATALOG_SYNC_CONCURRENCY")
+        .ok()
+        .and_then(|s| s.trim().parse::<usize>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_CATALOG_SYNC_CONCURRENCY)
+}
+
+/// Outcome of one provider's catalog sync: the price changes applied, or why the provider could
+/// not be synced.
+pub struct ProviderSyncResult {
+    pub provider: String,
+    pub outcome: Result<Vec<PriceChange>, String>,
+}
+
+/// Sync each provider returned by `resolve`, running at most `concurrency` syncs at once.
+/// Results come back in completion order, one per provider.
+async fn sync_provider_catalogs<F, Fut>(
+    pool: &Pool<Postgres>,
+    providers: Vec<String>,
+    concurrency: usize,
+    resolve: F,
+) -> Vec<ProviderSyncResult>
+where
+    F: Fn(String) -> Fut,
+    Fut: std::future::Future<Output = Result<Box<dyn inventiv_providers::CloudProvider>, String>>,
+{
+    use futures_util::StreamExt;
+
+    futures_util::stream::iter(providers)
+        .map(|provider_name| {
+            let provider = resolve(provider_name.clone());
+            async move {
+                let outcome = match provider.await {
+                    Ok(provider) => {
+                        Ok(sync_provider_catalog(pool, &provider_name, provider.as_ref()).await)
+                    }
+                    Err(e) => Err(e),
+                };
+                ProviderSyncResult {
+                    provider: provider_name,
+                    outcome,
+                }
+            }
         })
-        .bind(&provider_name)
-        .bind(format!("Auto-managed provider entry for {}", provider_name))
-        .fetch_optional(&pool)
+        .buffer_unordered(concurrency.max(1))
+        .collect()
         .await
-        .unwrap_or(None);
+}
 
-        let Some(provider_uuid) = provider_uuid else {
-            println!(
-                "❌ [Catalog Sync] Could not resolve provider id in DB for code={}",
-                provider_name
-            );
-            return;
-        };
+const DEFAULT_PRICE_CHANGE_THRESHOLD_PCT: f64 = 1.0;
+
+/// An instance type whose hourly price moved past `CATALOG_PRICE_CHANGE_THRESHOLD_PCT` during a
+/// catalog sync.
+#[derive(Debug, Clone)]
+pub struct PriceChange {
+    pub provider_id: Uuid,
+    pub instance_type_id: Uuid,
+    pub instance_type_code: String,
+    pub old_cost_per_hour: f64,
+    pub new_cost_per_hour: f64,
+    pub pct_change: f64,
+}
+
+impl PriceChange {
+    pub fn to_event(&self, source: &str) -> inventiv_common::bus::FinopsEventEnvelope {
+        finops_events::price_changed_event(
+            self.provider_id,
+            self.instance_type_id,
+            &self.instance_type_code,
+            self.old_cost_per_hour,
+            self.new_cost_per_hour,
+            self.pct_change,
+            source,
+        )
+    }
+}
+
+fn price_change_threshold_pct() -> f64 {
+    std::env::var("CATALOG_PRICE_CHANGE_THRESHOLD_PCT")
+        .ok()
+        .and_then(|s| s.trim().parse::<f64>().ok())
+        .filter(|v| v.is_finite() && *v >= 0.0)
+        .unwrap_or(DEFAULT_PRICE_CHANGE_THRESHOLD_PCT)
+}
+
+/// Percent change from `old` to `new` when it is at least `threshold_pct` (either direction).
+/// New types and types without a previous price never count as a change.
+fn significant_price_change(old: f64, new: f64, threshold_pct: f64) -> Option<f64> {
+    if old <= 0.0 || old == new {
+        return None;
+    }
+    let pct = (new - old) / old * 100.0;
+    (pct.abs() >= threshold_pct).then_some(pct)
+}
+
+/// Provider codes covered by a catalog sync: the requested provider, else the full set.
+fn catalog_sync_targets(provider_code: Option<&str>) -> Vec<String> {
+    match provider_code.map(str::trim).filter(|c| !c.is_empty()) {
+        Some(code) => vec![code.to_ascii_lowercase()],
+        None => vec![ProviderManager::current_provider_name()],
+    }
+}
 
-        // Prefer zones configured in DB for this provider; fallback to a sane default list.
-        let zones: Vec<String> = sqlx::query_scalar(
+/// Sync one provider's catalog (regions/zones, instance types, zone availability).
+///
+/// Runs under a per-provider advisory lock so concurrent syncs of the same provider can't
+/// interleave upserts with the soft-delete pass. Returns the significant price changes applied.
+async fn sync_provider_catalog(
+    pool: &Pool<Postgres>,
+    provider_name: &str,
+    provider: &dyn inventiv_providers::CloudProvider,
+) -> Vec<PriceChange> {
+    // Session-level lock: held on this connection for the whole sync.
+    let mut lock_conn = match pool.acquire().await {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("❌ [Catalog Sync] Could not acquire DB connection: {}", e);
+            return Vec::new();
+        }
+    };
+    let locked: bool =
+        sqlx::query_scalar("SELECT pg_try_advisory_lock(hashtext('catalog_sync'), hashtext($1))")
+            .bind(provider_name)
+            .fetch_one(&mut *lock_conn)
+            .await
+            .unwrap_or(false);
+    if !locked {
+        println!(
+            "⏭️ [Catalog Sync] Sync already running for provider {}; skipping",
+            provider_name
+        );
+        return Vec::new();
+    }
+
+    let changes = sync_provider_catalog_locked(pool, provider_name, provider).await;
+    
+    let _ = sqlx::query("SELECT pg_advisory_unlock(hashtext('catalog_sync'), hashtext($1))")
+        .bind(provider_name)
+        .execute(&mut *lock_conn)
+        .await;
+    changes
+}
+    
+/// Upsert the provider catalog, then soft-delete what the provider no longer offers: instance
+/// types missing from every zone get `is_active = false`, types missing from a zone get
+/// `is_available = false` there (rows are kept for FKs; both are restored if they reappear).
+/// Deactivation only runs on complete data: any failed zone fetch or upsert skips the type pass,
+/// and an empty catalog (e.g. the mock provider, whose catalog is seeded in DB) never deactivates.
+/// Price moves past the threshold are logged as `PRICE_CHANGED` and returned.
+async fn sync_provider_catalog_locked(
+    pool: &Pool<Postgres>,
+    provider_name: &str,
+    provider: &dyn inventiv_providers::CloudProvider,
+) -> Vec<PriceChange> {
+    // Ensure the provider exists in DB (required for Settings UI and FK integrity).
+    let provider_uuid: Option<Uuid> = sqlx::query_scalar(
+        r#"
+        INSERT INTO providers (id, name, code, description, is_active)
+        VALUES (gen_random_uuid(), $1, $2, $3, true)
+        ON CONFLICT (code)
+        DO UPDATE SET
+          name = EXCLUDED.name,
+          description = EXCLUDED.description,
+          is_active = true
+        RETURNING id
+        "#,
+    )
+    .bind(match provider_name {
+        "scaleway" => "Scaleway",
+        "mock" => "Mock",
+        _ => provider_name,
+    })
+    .bind(provider_name)
+    .bind(format!("Auto-managed provider entry for {}", provider_name))
+    .fetch_optional(pool)
+    .await
+    .unwrap_or(None);
+
+    let Some(provider_uuid) = provider_uuid else {
+        println!(
+            "❌ [Catalog Sync] Could not resolve provider id in DB for code={}",
+            provider_name
+        );
+        return Vec::new();
+    };
+
+    // Prefer zones configured in DB for this provider; fallback to a sane default list.
+    let zones: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT z.code
+        FROM zones z
+        JOIN regions r ON r.id = z.region_id
+        WHERE z.is_active = true
+          AND r.provider_id = $1
+        ORDER BY z.code
+        "#,
+    )
+    .bind(provider_uuid)
+    .fetch_all(pool)
+    .await
+    .unwrap_or_default();
+
+    let zones: Vec<String> = if zones.is_empty() {
+        vec![
+            // fallback only makes sense for scaleway; mock will typically be in DB
+            "fr-par-1".to_string(),
+            "fr-par-2".to_string(),
+        ]
+    } else {
+        zones
+    };
+
+    let mut complete = true;
+    let mut seen_type_ids: std::collections::HashSet<Uuid> = std::collections::HashSet::new();
+    let threshold_pct = price_change_threshold_pct();
+    let mut price_changes: Vec<PriceChange> = Vec::new();
+
+    for zone in &zones {
+        println!("🔄 [Catalog Sync] Fetching catalog for zone: {}", zone);
+
+        // Ensure region+zone exist (so Settings UI doesn't stay empty).
+        // Region code heuristic: drop the trailing "-<digit>" (e.g., fr-par-2 -> fr-par).
+        let region_code = zone
+            .rsplit_once('-')
+            .map(|(_, r)| r)
+            .unwrap_or(zone)
+            .to_string();
+        let region_name = region_code.clone();
+        let region_id: Option<Uuid> = sqlx::query_scalar(
             r#"
-            SELECT z.code
-            FROM zones z
-            JOIN regions r ON r.id = z.region_id
-            WHERE z.is_active = true
-              AND r.provider_id = $1
-            ORDER BY z.code
-            "#,
+             INSERT INTO regions (id, provider_id, name, code, is_active)
+             VALUES (gen_random_uuid(), $1, $2, $3, true)
+             ON CONFLICT (provider_id, code)
+             DO UPDATE SET
+               name = EXCLUDED.name,
+               is_active = true
+             RETURNING id
+             "#,
         )
         .bind(provider_uuid)
-        .fetch_all(&pool)
+        .bind(&region_name)
+        .bind(&region_code)
+        .fetch_optional(pool)
         .await
-        .unwrap_or_default();
-
-        let zones: Vec<String> = if zones.is_empty() {
-            vec![
-                // fallback only makes sense for scaleway; mock will typically be in DB
-                "fr-par-1".to_string(),
-                "fr-par-2".to_string(),
-            ]
-        } else {
-            zones
-        };
+        .unwrap_or(None);
 
-        for zone in &zones {
-            println!("🔄 [Catalog Sync] Fetching catalog for zone: {}", zone);
-
-            // Ensure region+zone exist (so Settings UI doesn't stay empty).
-            // Region code heuristic: drop the trailing "-<digit>" (e.g., fr-par-2 -> fr-par).
-            let region_code = zone
-                .rsplit_once('-')
-                .map(|(_, r)| r)
-                .unwrap_or(zone)
-                .to_string();
-            let region_name = region_code.clone();
-            let region_id: Option<Uuid> = sqlx::query_scalar(
+        let zone_id: Option<Uuid> = if let Some(rid) = region_id {
+            sqlx::query_scalar(
                 r#"
-                 INSERT INTO regions (id, provider_id, name, code, is_active)
+                 INSERT INTO zones (id, region_id, name, code, is_active)
                  VALUES (gen_random_uuid(), $1, $2, $3, true)
-                 ON CONFLICT (provider_id, code)
+                 ON CONFLICT (region_id, code)
                  DO UPDATE SET
                    name = EXCLUDED.name,
                    is_active = true
                  RETURNING id
                  "#,
             )
-            .bind(provider_uuid)
-            .bind(&region_name)
-            .bind(&region_code)
-            .fetch_optional(&pool)
+            .bind(rid)
+            .bind(zone)
+            .bind(zone)
+            .fetch_optional(pool)
             .await
-            .unwrap_or(None);
+            .unwrap_or(None)
+        } else {
+            None
+        };
 
-            let zone_id: Option<Uuid> = if let Some(rid) = region_id {
-                sqlx::query_scalar(
-                    r#"
-                     INSERT INTO zones (id, region_id, name, code, is_active)
-                     VALUES (gen_random_uuid(), $1, $2, $3, true)
-                     ON CONFLICT (region_id, code)
-                     DO UPDATE SET
-                       name = EXCLUDED.name,
-                       is_active = true
-                     RETURNING id
-                     "#,
-                )
-                .bind(rid)
-                .bind(zone)
-                .bind(zone)
-                .fetch_optional(&pool)
-                .await
-                .unwrap_or(None)
-            } else {
-                None
-            };
-
-            if zone_id.is_none() {
-                println!(
-                    "⚠️ [Catalog Sync] Zone '{}' not found in DB; skipping availability mapping",
-                    zone
-                );
-            }
+        if zone_id.is_none() {
+            complete = false;
+            println!(
+                "⚠️ [Catalog Sync] Zone '{}' not found in DB; skipping availability mapping",
+                zone
+            );
+        }
 
-            match provider.fetch_catalog(zone).await {
-                Ok(items) => {
-                    let mut count = 0;
-                    for item in items {
-                        // Convert f64 to BigDecimal for NUMERIC column
-                        // Using primitive cast via string to avoid precision issues if possible or just use FromPrimitive
-                        // sqlx BigDecimal feature allows direct usage usually if From f64 is implemented.
-                        // But safer to cast in SQL or use bigdecimal crate types.
-                        let hourly_price = bigdecimal::BigDecimal::from_f64(item.cost_per_hour)
-                            .unwrap_or_default();
-
-                        // Upsert instance type and get its id (needed to map availability to zones)
-                        let type_id: Option<Uuid> = sqlx::query_scalar(
-                            "INSERT INTO instance_types (id, provider_id, name, code, is_active, cost_per_hour, cpu_count, ram_gb, gpu_count, vram_per_gpu_gb, bandwidth_bps)
-                             VALUES (gen_random_uuid(), $1, $2, $3, true, $4, $5, $6, $7, $8, $9)
-                             ON CONFLICT (provider_id, code)
-                             DO UPDATE SET
-                                name = EXCLUDED.name,
-                                cost_per_hour = EXCLUDED.cost_per_hour,
-                                cpu_count = EXCLUDED.cpu_count,
-                                ram_gb = EXCLUDED.ram_gb,
-                                gpu_count = EXCLUDED.gpu_count,
-                                vram_per_gpu_gb = EXCLUDED.vram_per_gpu_gb,
-                                bandwidth_bps = EXCLUDED.bandwidth_bps,
-                                is_active = true
-                             RETURNING id"
-                        )
-                        .bind(provider_uuid)
-                        .bind(&item.name)
-                        .bind(&item.code)
-                        .bind(hourly_price)
-                        .bind(item.cpu_count)
-                        .bind(item.ram_gb)
-                        .bind(item.gpu_count)
-                        .bind(item.vram_per_gpu_gb)
-                        .bind(item.bandwidth_bps)
-                        .fetch_optional(&pool)
-                        .await
-                        .unwrap_or(None);
+        match provider.fetch_catalog(zone).await {
+            Ok(items) => {
+                let mut count = 0;
+                let mut zone_complete = true;
+                let mut zone_type_ids: Vec<Uuid> = Vec::new();
+                for item in items {
+                    // Convert f64 to BigDecimal for NUMERIC column
+                    // Using primitive cast via string to avoid precision issues if possible or just use FromPrimitive
+                    // sqlx BigDecimal feature allows direct usage usually if From f64 is implemented.
+                    // But safer to cast in SQL or use bigdecimal crate types.
+                    let hourly_price =
+                        bigdecimal::BigDecimal::from_f64(item.cost_per_hour).unwrap_or_default();
+
+                    // Upsert instance type and get its id (needed to map availability to zones)
+                    // along with the price it had before this sync.
+                    let upserted: Option<(Uuid, Option<f64>)> = sqlx::query_as(
+                        "WITH previous AS (
+                            SELECT cost_per_hour FROM instance_types WHERE provider_id = $1 AND code = $3
+                         )
+                         INSERT INTO instance_types (id, provider_id, name, code, is_active, cost_per_hour, cpu_count, ram_gb, gpu_count, vram_per_gpu_gb, bandwidth_bps)
+                         VALUES (gen_random_uuid(), $1, $2, $3, true, $4, $5, $6, $7, $8, $9)
+                         ON CONFLICT (provider_id, code)
+                         DO UPDATE SET
+                            name = EXCLUDED.name,
+                            cost_per_hour = EXCLUDED.cost_per_hour,
+                            cpu_count = EXCLUDED.cpu_count,
+                            ram_gb = EXCLUDED.ram_gb,
+                            gpu_count = EXCLUDED.gpu_count,
+                            vram_per_gpu_gb = EXCLUDED.vram_per_gpu_gb,
+                            bandwidth_bps = EXCLUDED.bandwidth_bps,
+                            is_active = true
+                         RETURNING id, (SELECT cost_per_hour::float8 FROM previous)"
+                    )
+                    .bind(provider_uuid)
+                    .bind(&item.name)
+                    .bind(&item.code)
+                    .bind(hourly_price)
+                    .bind(item.cpu_count)
+                    .bind(item.ram_gb)
+                    .bind(item.gpu_count)
+                    .bind(item.vram_per_gpu_gb)
+                    .bind(item.bandwidth_bps)
+                    .fetch_optional(pool)
+                    .await
+                    .unwrap_or(None);
+                    let type_id = upserted.map(|(id, _)| id);
 
-                        // Map availability: all items returned by provider for this zone are available.
-                        if let (Some(tid), Some(zid)) = (type_id, zone_id) {
-                            let _ = sqlx::query(
-                                "INSERT INTO instance_type_zones (instance_type_id, zone_id, is_available)
-                                 VALUES ($1, $2, true)
-                                 ON CONFLICT (instance_type_id, zone_id)
-                                 DO UPDATE SET is_available = EXCLUDED.is_available"
+                    if let Some((tid, Some(old))) = upserted {
+                        if let Some(pct) =
+                            significant_price_change(old, item.cost_per_hour, threshold_pct)
+                        {
+                            let change = PriceChange {
+                                provider_id: provider_uuid,
+                                instance_type_id: tid,
+                                instance_type_code: item.code.clone(),
+                                old_cost_per_hour: old,
+                                new_cost_per_hour: item.cost_per_hour,
+                                pct_change: pct,
+                            };
+                            println!(
+                                "💶 [Catalog Sync] {} price changed {:.4} -> {:.4} ({:+.1}%)",
+                                change.instance_type_code, old, item.cost_per_hour, pct
+                            );
+                            logger::log_global_event_with_metadata(
+                                pool,
+                                "PRICE_CHANGED",
+                                "success",
+                                Some(change.to_event("orchestrator").payload),
                             )
-                            .bind(tid)
-                            .bind(zid)
-                            .execute(&pool)
-                            .await;
+                            .await
+                            .ok();
+                            price_changes.push(change);
                         }
-                        count += 1;
                     }
-                    println!(
-                        "✅ [Catalog Sync] Updated {} types for zone {}",
-                        count, zone
-                    );
+
+                    // Map availability: all items returned by provider for this zone are available.
+                    if let (Some(tid), Some(zid)) = (type_id, zone_id) {
+                        let _ = sqlx::query(
+                            "INSERT INTO instance_type_zones (instance_type_id, zone_id, is_available)
+                             VALUES ($1, $2, true)
+                             ON CONFLICT (instance_type_id, zone_id)
+                             DO UPDATE SET is_available = EXCLUDED.is_available"
+                        )
+                        .bind(tid)
+                        .bind(zid)
+                        .execute(pool)
+                        .await;
+                    }
+                    match type_id {
+                        Some(tid) => {
+                            seen_type_ids.insert(tid);
+                            zone_type_ids.push(tid);
+                        }
+                        None => zone_complete = false,
+                    }
+                    count += 1;
+                }
+                println!(
+                    "✅ [Catalog Sync] Updated {} types for zone {}",
+                    count, zone
+                );
+
+                if let Some(zid) = zone_id.filter(|_| zone_complete && !zone_type_ids.is_empty()) {
+                    let removed = sqlx::query(
+                        "UPDATE instance_type_zones SET is_available = false
+                         WHERE zone_id = $1
+                           AND is_available IS DISTINCT FROM false
+                           AND NOT (instance_type_id = ANY($2))",
+                    )
+                    .bind(zid)
+                    .bind(&zone_type_ids)
+                    .execute(pool)
+                    .await
+                    .map(|r| r.rows_affected())
+                    .unwrap_or(0);
+                    if removed > 0 {
+                        println!(
+                            "🗑️ [Catalog Sync] Marked {} types unavailable in zone {}",
+                            removed, zone
+                        );
+                    }
                 }
-                Err(e) => println!("❌ [Catalog Sync] Error for {}: {:?}", zone, e),
+                complete &= zone_complete;
+            }
+            Err(e) => {
+                complete = false;
+                println!("❌ [Catalog Sync] Error for {}: {:?}", zone, e);
             }
         }
-    } else {
-        println!("❌ [Catalog Sync] Provider not configured (missing credentials).");
     }
+
+    if !complete {
+        println!(
+            "⚠️ [Catalog Sync] Partial sync for provider {}; instance types left as they are",
+            provider_name
+        );
+        return price_changes;
+    }
+    if seen_type_ids.is_empty() {
+        return price_changes;
+    }
+    let seen: Vec<Uuid> = seen_type_ids.into_iter().collect();
+    match sqlx::query(
+        "UPDATE instance_types SET is_active = false
+         WHERE provider_id = $1
+           AND is_active = true
+           AND NOT (id = ANY($2))",
+    )
+    .bind(provider_uuid)
+    .bind(&seen)
+    .execute(pool)
+    .await
+    {
+        Ok(r) if r.rows_affected() > 0 => println!(
+            "🗑️ [Catalog Sync] Deactivated {} instance types no longer offered by {}",
+            r.rows_affected(),
+            provider_name
+        ),
+        Ok(_) => {}
+        Err(e) => eprintln!(
+            "❌ [Catalog Sync] Failed to deactivate removed instance types: {}",
+            e
+        ),
+    }
+    price_changes
 }
 
-pub async fn process_full_reconciliation(pool: Pool<Postgres>) {
-    println!("🔄 [Full Reconciliation] Starting...");
-    
-    let provider_name = ProviderManager::current_provider_name();
-    
+/// Optional narrowing of a manual reconciliation (`CMD:RECONCILE` with `provider_code` / `zone`),
+/// so operators can reconcile only the provider or zone they are investigating.
+#[derive(Debug, Clone, Default)]
+pub struct ReconcileScope {
+    pub provider_code: Option<String>,
+    pub zone: Option<String>,
+}
+
+/// Active zones of `provider_code`, restricted to `zone` when given.
+async fn reconciliation_zones(
+    pool: &Pool<Postgres>,
+    provider_code: &str,
+    zone: Option<&str>,
+) -> Vec<String> {
+    sqlx::query_scalar(
+        r#"
+        SELECT z.code
+        FROM zones z
+        JOIN regions r ON r.id = z.region_id
+        JOIN providers p ON p.id = r.provider_id
+        WHERE z.is_active = true
+          AND p.code = $1
+          AND ($2::text IS NULL OR z.code = $2)
+        ORDER BY z.code
+        "#,
+    )
+    .bind(provider_code)
+    .bind(zone)
+    .fetch_all(pool)
+    .await
+    .unwrap_or_default()
+}
+
+pub async fn process_full_reconciliation(pool: Pool<Postgres>, scope: ReconcileScope) {
+    let provider_name = scope
+        .provider_code
+        .clone()
+        .unwrap_or_else(ProviderManager::current_provider_name);
+    println!(
+        "🔄 [Full Reconciliation] Starting (provider={}, zone={:?})...",
+        provider_name, scope.zone
+    );
+
     // Get provider_id
     let provider_id: Option<uuid::Uuid> = sqlx::query_scalar(
         "SELECT id FROM providers WHERE code = $1 LIMIT 1"
@@ -4252,6 +5657,16 @@ pub async fn process_full_reconciliation(pool: Pool<Postgres>) {
         return;
     };
 
+    // Zones for this provider (shared across all organizations), narrowed by the scope.
+    let zones = reconciliation_zones(&pool, &provider_name, scope.zone.as_deref()).await;
+    if zones.is_empty() {
+        eprintln!(
+            "❌ [Full Reconciliation] No active zone matches provider '{}' (zone={:?})",
+            provider_name, scope.zone
+        );
+        return;
+    }
+
     // Get all organizations that have Scaleway credentials configured
     let orgs_with_credentials: Vec<uuid::Uuid> = sqlx::query_scalar(
         r#"
@@ -4276,197 +5691,1582 @@ pub async fn process_full_reconciliation(pool: Pool<Postgres>) {
 
     println!("🔍 [Full Reconciliation] Found {} organization(s) with Scaleway credentials", orgs_with_credentials.len());
 
-    // Zones for this provider (shared across all organizations)
-    let zones: Vec<String> = sqlx::query_scalar(
-        r#"
-        SELECT z.code
-        FROM zones z
-        JOIN regions r ON r.id = z.region_id
-        JOIN providers p ON p.id = r.provider_id
-        WHERE z.is_active = true
-          AND p.code = $1
-        ORDER BY z.code
-        "#,
-    )
-    .bind(&provider_name)
-    .fetch_all(&pool)
-    .await
-    .unwrap_or_default();
-
     // Process each organization separately
     for org_id in orgs_with_credentials {
         println!("🔄 [Full Reconciliation] Processing organization {}", org_id);
         
         if let Ok(provider) = ProviderManager::get_provider(&provider_name, org_id, pool.clone()).await {
-            for zone in &zones {
-            match provider.list_instances(&zone).await {
-                Ok(instances) => {
-                    println!(
-                        "🔍 [Full Reconciliation] List returned {} instances in {}",
-                        instances.len(),
-                        zone
-                    );
-                    let mut import_count = 0;
-                    for inst in instances {
-                        // Check if exists AND belongs to this organization
-                        let exists_res = sqlx::query_scalar(
-                             "SELECT EXISTS(SELECT 1 FROM instances WHERE provider_instance_id = $1 AND organization_id = $2)"
-                         )
-                         .bind(&inst.provider_id)
-                         .bind(org_id)
-                         .fetch_one(&pool)
-                         .await;
+            reconcile_organization_zones(&pool, provider.as_ref(), &provider_name, org_id, &zones)
+                .await;
+        } else {
+            eprintln!("⚠️ [Full Reconciliation] Organization {}: Failed to get provider (missing credentials?)", org_id);
+        }
+    }
 
-                        let exists = exists_res.unwrap_or(false);
+    println!("✅ [Full Reconciliation] Completed for all organizations");
+}
 
-                        // Import if not exists and status is active-ish
-                        if !exists && inst.status != "terminated" && inst.status != "archived" {
-                            println!(
-                                "🔍 [Full Reconciliation] Found orphan: {} ({}) Status: {}",
-                                inst.name, inst.provider_id, inst.status
-                            );
+/// Compare provider listings with DB rows of one organization, zone by zone: import orphans and
+/// reactivate zombies. Only the given zones are listed.
+async fn reconcile_organization_zones(
+    pool: &Pool<Postgres>,
+    provider: &dyn inventiv_providers::CloudProvider,
+    provider_name: &str,
+    org_id: Uuid,
+    zones: &[String],
+) {
+    for zone in zones {
+        match provider.list_instances(zone).await {
+            Ok(instances) => {
+                println!(
+                    "🔍 [Full Reconciliation] List returned {} instances in {}",
+                    instances.len(),
+                    zone
+                );
+                let mut import_count = 0;
+                for inst in instances {
+                    // Check if exists AND belongs to this organization
+                    let exists_res = sqlx::query_scalar(
+                     "SELECT EXISTS(SELECT 1 FROM instances WHERE provider_instance_id = $1 AND organization_id = $2)"
+                 )
+                 .bind(&inst.provider_id)
+                 .bind(org_id)
+                 .fetch_one(pool)
+                 .await;
+
+                    let exists = exists_res.unwrap_or(false);
+
+                    // Import if not exists and status is active-ish
+                    if !exists && inst.status != "terminated" && inst.status != "archived" {
+                        println!(
+                            "🔍 [Full Reconciliation] Found orphan: {} ({}) Status: {}",
+                            inst.name, inst.provider_id, inst.status
+                        );
 
-                            // Resolve Provider ID (by code) + Zone ID (by provider + zone code)
-                            let provider_id: Option<Uuid> = sqlx::query_scalar(
-                                "SELECT id FROM providers WHERE code = $1 LIMIT 1",
+                        // Resolve Provider ID (by code) + Zone ID (by provider + zone code)
+                        let provider_id: Option<Uuid> =
+                            sqlx::query_scalar("SELECT id FROM providers WHERE code = $1 LIMIT 1")
+                                .bind(provider_name)
+                                .fetch_optional(pool)
+                                .await
+                                .unwrap_or(None);
+
+                        let zone_id: Option<Uuid> = if let Some(pid) = provider_id {
+                            sqlx::query_scalar(
+                                r#"
+                            SELECT z.id
+                            FROM zones z
+                            JOIN regions r ON r.id = z.region_id
+                            WHERE z.code = $1
+                              AND r.provider_id = $2
+                            LIMIT 1
+                            "#,
                             )
-                            .bind(&provider_name)
-                            .fetch_optional(&pool)
+                            .bind(zone)
+                            .bind(pid)
+                            .fetch_optional(pool)
+                            .await
+                            .unwrap_or(None)
+                        } else {
+                            None
+                        };
+
+                        if let (Some(pid), Some(zid)) = (provider_id, zone_id) {
+                            let new_id = Uuid::new_v4();
+                            let type_id: Option<Uuid> = sqlx::query_scalar(
+                                r#"
+                            SELECT it.id
+                            FROM instance_types it
+                            WHERE it.provider_id = $1
+                              AND it.is_active = true
+                            ORDER BY it.gpu_count DESC, it.vram_per_gpu_gb DESC, it.name ASC
+                            LIMIT 1
+                            "#,
+                            )
+                            .bind(pid)
+                            .fetch_optional(pool)
                             .await
                             .unwrap_or(None);
 
-                            let zone_id: Option<Uuid> = if let Some(pid) = provider_id {
-                                sqlx::query_scalar(
-                                    r#"
-                                    SELECT z.id
-                                    FROM zones z
-                                    JOIN regions r ON r.id = z.region_id
-                                    WHERE z.code = $1
-                                      AND r.provider_id = $2
-                                    LIMIT 1
-                                    "#,
-                                )
-                                .bind(&zone)
-                                .bind(pid)
-                                .fetch_optional(&pool)
-                                .await
-                                .unwrap_or(None)
-                            } else {
-                                None
+                            let Some(type_id) = type_id else {
+                                println!("⚠️ [Full Reconciliation] No instance_types found for provider '{}', skipping orphan import.", provider_name);
+                                continue;
                             };
 
-                            if let (Some(pid), Some(zid)) = (provider_id, zone_id) {
-                                let new_id = Uuid::new_v4();
-                                let type_id: Option<Uuid> = sqlx::query_scalar(
-                                    r#"
-                                    SELECT it.id
-                                    FROM instance_types it
-                                    WHERE it.provider_id = $1
-                                      AND it.is_active = true
-                                    ORDER BY it.gpu_count DESC, it.vram_per_gpu_gb DESC, it.name ASC
-                                    LIMIT 1
-                                    "#,
-                                )
-                                .bind(pid)
-                                .fetch_optional(&pool)
-                                .await
-                                .unwrap_or(None);
-
-                                let Some(type_id) = type_id else {
-                                    println!("⚠️ [Full Reconciliation] No instance_types found for provider '{}', skipping orphan import.", provider_name);
-                                    continue;
-                                };
-
-                                // Map Status (Simplistic)
-                                let status = match inst.status.as_str() {
-                                    "running" | "starting" => "ready",
-                                    "stopped" => "failed",
-                                    _ => "provisioning",
-                                };
+                            // Map Status (Simplistic)
+                            let status = match inst.status.as_str() {
+                                "running" | "starting" => "ready",
+                                "stopped" => "failed",
+                                _ => "provisioning",
+                            };
 
-                                // Import orphan with organization_id
-                                let insert_res = sqlx::query(
-                                     "INSERT INTO instances 
-                                     (id, provider_id, zone_id, instance_type_id, organization_id, status, provider_instance_id, ip_address, created_at, gpu_profile)
-                                     VALUES ($1, $2, $3, $4, $5, $6::instance_status, $7, $8::inet, NOW(), '{}')"
-                                 )
-                                 .bind(new_id)
-                                 .bind(pid)
-                                 .bind(zid)
-                                 .bind(type_id)
-                                 .bind(org_id)  // Assign to the organization whose credentials found it
-                                 .bind(status)
-                                 .bind(&inst.provider_id)
-                                 .bind(inst.ip_address)
-                                 .execute(&pool)
-                                 .await;
+                            // Import orphan with organization_id
+                            let insert_res = sqlx::query(
+                             "INSERT INTO instances 
+                             (id, provider_id, zone_id, instance_type_id, organization_id, status, provider_instance_id, ip_address, created_at, gpu_profile)
+                             VALUES ($1, $2, $3, $4, $5, $6::instance_status, $7, $8::inet, NOW(), '{}')"
+                         )
+                         .bind(new_id)
+                         .bind(pid)
+                         .bind(zid)
+                         .bind(type_id)
+                         .bind(org_id)  // Assign to the organization whose credentials found it
+                         .bind(status)
+                         .bind(&inst.provider_id)
+                         .bind(inst.ip_address)
+                         .execute(pool)
+                         .await;
 
-                                if let Err(e) = insert_res {
-                                    println!(
-                                        "❌ [Full Reconciliation] Failed to import orphan {}: {:?}",
-                                        inst.provider_id, e
-                                    );
-                                } else {
-                                    println!(
-                                        "✅ [Full Reconciliation] Imported orphan {} => {}",
-                                        inst.provider_id, new_id
-                                    );
-                                    import_count += 1;
-                                }
+                            if let Err(e) = insert_res {
+                                println!(
+                                    "❌ [Full Reconciliation] Failed to import orphan {}: {:?}",
+                                    inst.provider_id, e
+                                );
                             } else {
                                 println!(
-                                    "⚠️ [Full Reconciliation] Unknown zone '{}' for orphan {}",
-                                    zone, inst.provider_id
+                                    "✅ [Full Reconciliation] Imported orphan {} => {}",
+                                    inst.provider_id, new_id
                                 );
+                                import_count += 1;
                             }
-                        } else if exists {
-                                // Check for Zombie State (DB=terminated vs Cloud=running)
-                                // Only check instances that belong to this organization
-                                let current_status: Option<String> = sqlx::query_scalar(
-                                    "SELECT status::text FROM instances WHERE provider_instance_id = $1 AND organization_id = $2"
-                                )
-                                .bind(&inst.provider_id)
-                                .bind(org_id)
-                                .fetch_optional(&pool)
-                                .await.unwrap_or(None);
-
-                                if let Some(db_status) = current_status {
-                                    if (db_status == "terminated" || db_status == "archived")
-                                        && (inst.status == "running" || inst.status == "starting")
-                                    {
-                                        println!("⚠️ [Full Reconciliation] ZOMBIE DETECTED (org {}): {} is {} on Cloud but {} in DB. Reactivating...", org_id, inst.provider_id, inst.status, db_status);
-
-                                        let _ = sqlx::query(
-                                             "UPDATE instances SET status = 'ready', terminated_at = NULL, is_archived = false WHERE provider_instance_id = $1 AND organization_id = $2"
-                                         )
-                                         .bind(&inst.provider_id)
-                                         .bind(org_id)
-                                         .execute(&pool)
-                                         .await;
-                                        println!(
-                                            "✅ [Full Reconciliation] Zombie {} reactivated in DB (org {}).",
-                                            inst.provider_id, org_id
-                                        );
-                                    }
-                                }
-                            }
-                        }
-                        if import_count > 0 {
+                        } else {
                             println!(
-                                "✅ [Full Reconciliation] Organization {}: Imported {} orphaned instances in {}",
-                                org_id, import_count, zone
+                                "⚠️ [Full Reconciliation] Unknown zone '{}' for orphan {}",
+                                zone, inst.provider_id
                             );
                         }
+                    } else if exists {
+                        // Check for Zombie State (DB=terminated vs Cloud=running)
+                        // Only check instances that belong to this organization
+                        let current_status: Option<String> = sqlx::query_scalar(
+                            "SELECT status::text FROM instances WHERE provider_instance_id = $1 AND organization_id = $2"
+                        )
+                        .bind(&inst.provider_id)
+                        .bind(org_id)
+                        .fetch_optional(pool)
+                        .await.unwrap_or(None);
+
+                        if let Some(db_status) = current_status {
+                            if (db_status == "terminated" || db_status == "archived")
+                                && (inst.status == "running" || inst.status == "starting")
+                            {
+                                println!("⚠️ [Full Reconciliation] ZOMBIE DETECTED (org {}): {} is {} on Cloud but {} in DB. Reactivating...", org_id, inst.provider_id, inst.status, db_status);
+    
+                                let _ = sqlx::query(
+                                     "UPDATE instances SET status = 'ready', terminated_at = NULL, is_archived = false WHERE provider_instance_id = $1 AND organization_id = $2"
+                                 )
+                                 .bind(&inst.provider_id)
+                                 .bind(org_id)
+                                 .execute(pool)
+                                 .await;
+                                println!(
+                                    "✅ [Full Reconciliation] Zombie {} reactivated in DB (org {}).",
+                                    inst.provider_id, org_id
+                                );
+                            }
+                        }
                     }
-                    Err(e) => {
-                        eprintln!("❌ [Full Reconciliation] Organization {}: Error listing instances in zone {}: {:?}", org_id, zone, e);
-                    }
+                }
+                if import_count > 0 {
+                    println!(
+                        "✅ [Full Reconciliation] Organization {}: Imported {} orphaned instances in {}",
+                        org_id, import_count, zone
+                    );
                 }
             }
-        } else {
-            eprintln!("⚠️ [Full Reconciliation] Organization {}: Failed to get provider (missing credentials?)", org_id);
+            Err(e) => {
+                eprintln!("❌ [Full Reconciliation] Organization {}: Error listing instances in zone {}: {:?}", org_id, zone, e);
+            }
         }
     }
-    
-    println!("✅ [Full Reconciliation] Completed for all organizations");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{setup_pool, TestProvider};
+    use inventiv_providers::{inventory, CloudProvider};
+
+    #[tokio::test]
+    async fn model_boot_image_override_respects_diskless_validation() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(mock_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers WHERE code = 'mock'")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: mock provider not seeded");
+            return;
+        };
+
+        // Block Storage types carry a validated diskless boot image on the instance type.
+        let code = format!(
+            "test-model-image-{}",
+            &Uuid::new_v4().simple().to_string()[..8]
+        );
+        let type_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO instance_types (id, provider_id, name, code, gpu_count, vram_per_gpu_gb, allocation_params)
+             VALUES (gen_random_uuid(), $1, $2, $2, 1, 24, '{\"mock\": {\"boot_image_id\": \"validated-diskless\"}}')
+             RETURNING id",
+        )
+        .bind(mock_id)
+        .bind(&code)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        // Provisions an instance of a model whose boot image is `model_image`; returns the image
+        // the server was created with.
+        let provision = |model_image: &'static str, diskless: bool| {
+            let (pool, code) = (pool.clone(), code.clone());
+            async move {
+                let (model_id, instance_id) = (Uuid::new_v4(), Uuid::new_v4());
+                sqlx::query(
+                    "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, boot_image_id)
+                     VALUES ($1, $2, $2, 1, 2048, true, $3)",
+                )
+                .bind(model_id)
+                .bind(format!("{}-{}", code, model_id))
+                .bind(model_image)
+                .execute(&pool)
+                .await
+                .unwrap();
+                sqlx::query(
+                    "INSERT INTO instances (id, provider_id, instance_type_id, model_id, status, created_at, gpu_profile)
+                     VALUES ($1, $2, $3, $4, 'provisioning', NOW(), '{}')",
+                )
+                .bind(instance_id)
+                .bind(mock_id)
+                .bind(type_id)
+                .bind(model_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+
+                let provider = TestProvider {
+                    ip: Some("10.0.0.9".to_string()),
+                    known_images: Some(vec![
+                        "cuda-12-local".to_string(),
+                        "cuda-12-sbs".to_string(),
+                    ]),
+                    diskless_types: if diskless { vec![code.clone()] } else { vec![] },
+                    diskless_images: vec!["cuda-12-sbs".to_string()],
+                    ..Default::default()
+                };
+                let calls = provider.calls.clone();
+                provision_with_provider(
+                    ProvisioningRun {
+                        pool: pool.clone(),
+                        redis_client: redis::Client::open("redis://127.0.0.1/").unwrap(),
+                        instance_uuid: instance_id,
+                        zone: "mock-zone".to_string(),
+                        instance_type: code.clone(),
+                        type_id,
+                        provider_name: "mock".to_string(),
+                        correlation_id_meta: None,
+                        log_id_execute: None,
+                        start: Instant::now(),
+                    },
+                    Box::new(provider),
+                )
+                .await;
+
+                for table in ["action_logs", "instance_volumes"] {
+                    let _ = sqlx::query(&format!("DELETE FROM {} WHERE instance_id = $1", table))
+                        .bind(instance_id)
+                        .execute(&pool)
+                        .await;
+                }
+                let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+                    .bind(instance_id)
+                    .execute(&pool)
+                    .await;
+                let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+                    .bind(model_id)
+                    .execute(&pool)
+                    .await;
+
+                let creates = calls.creates.lock().unwrap();
+                creates.last().map(|c| c.image_id.clone())
+            }
+        };
+
+        let local_type_known = provision("cuda-12-local", false).await;
+        let local_type_missing = provision("cuda-12-missing", false).await;
+        let diskless_type_local_image = provision("cuda-12-local", true).await;
+        let diskless_type_sbs_image = provision("cuda-12-sbs", true).await;
+
+        let _ = sqlx::query("DELETE FROM instance_types WHERE id = $1")
+            .bind(type_id)
+            .execute(&pool)
+            .await;
+
+        assert_eq!(local_type_known.as_deref(), Some("cuda-12-local"));
+        assert_eq!(
+            local_type_missing.as_deref(),
+            Some("8e0da557-5d75-40ba-b928-5984075aa255")
+        );
+        // A model image that would boot from local storage keeps the validated diskless image.
+        assert_eq!(
+            diskless_type_local_image.as_deref(),
+            Some("validated-diskless")
+        );
+        assert_eq!(diskless_type_sbs_image.as_deref(), Some("cuda-12-sbs"));
+    }
+
+    #[tokio::test]
+    async fn name_collision_retries_with_disambiguated_name() {
+        let instance_id = Uuid::new_v4();
+        let provider = TestProvider {
+            taken_names: vec![instance_server_name(instance_id, 0)],
+            ..Default::default()
+        };
+
+        let (res, collided) = create_instance_with_name_retry(
+            &provider,
+            instance_id,
+            "fr-par-2",
+            "L4-1-24G",
+            "image",
+            None,
+            None,
+            &[],
+        )
+        .await;
+
+        assert!(res.is_ok());
+        assert_eq!(collided, vec![instance_server_name(instance_id, 0)]);
+        assert_eq!(
+            *provider.calls.named.lock().unwrap(),
+            vec![
+                instance_server_name(instance_id, 0),
+                format!("inventiv-worker-{}-r1", instance_id)
+            ]
+        );
+    }
+
+    #[test]
+    fn classified_provider_errors_keep_their_code() {
+        use inventiv_providers::ProviderErrorCode as P;
+        for code in [
+            P::QuotaExceeded,
+            P::ImageNotFound,
+            P::InvalidVolume,
+            P::RateLimited,
+            P::NameConflict,
+            P::OutOfCapacity,
+        ] {
+            assert_eq!(
+                classified_error_code(code).map(|c| c.as_str()),
+                Some(code.as_str())
+            );
+        }
+        assert_eq!(classified_error_code(P::Unknown), None);
+    }
+
+    #[test]
+    fn data_volume_strategy_follows_provider_hooks() {
+        let provider = TestProvider {
+            local_storage_types: vec!["RENDER-S".to_string()],
+            pre_create_volume_types: vec!["POP2-HC-8C-16G".to_string()],
+            ..Default::default()
+        };
+
+        let local = data_volume_strategy(&provider, "RENDER-S");
+        assert_eq!(
+            local,
+            DataVolumeStrategy::Skip {
+                volume_type: "l_ssd".to_string()
+            }
+        );
+        assert_eq!(local.as_str(), "skip");
+
+        let pre = data_volume_strategy(&provider, "POP2-HC-8C-16G");
+        assert_eq!(
+            pre,
+            DataVolumeStrategy::PreCreate {
+                volume_type: "sbs_volume".to_string()
+            }
+        );
+        assert_eq!(pre.as_str(), "pre_create");
+
+        let post = data_volume_strategy(&provider, "L4-1-24G");
+        assert_eq!(
+            post,
+            DataVolumeStrategy::PostAttach {
+                volume_type: "sbs_volume".to_string()
+            }
+        );
+        assert_eq!(post.as_str(), "post_attach");
+        assert_eq!(post.volume_type(), "sbs_volume");
+    }
+
+    #[test]
+    fn data_volume_skip_takes_precedence_over_pre_create() {
+        let provider = TestProvider {
+            local_storage_types: vec!["RENDER-S".to_string()],
+            pre_create_volume_types: vec!["RENDER-S".to_string()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            data_volume_strategy(&provider, "RENDER-S"),
+            DataVolumeStrategy::Skip { .. }
+        ));
+    }
+
+    #[test]
+    fn catalog_sync_targets_single_provider_when_requested() {
+        assert_eq!(catalog_sync_targets(Some(" Mock ")), vec!["mock"]);
+        assert_eq!(
+            catalog_sync_targets(None),
+            vec![ProviderManager::current_provider_name()]
+        );
+        assert_eq!(
+            catalog_sync_targets(Some("")),
+            vec![ProviderManager::current_provider_name()]
+        );
+    }
+
+    #[tokio::test]
+    async fn single_provider_catalog_sync_leaves_other_providers_untouched() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(mock_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers WHERE code = 'mock'")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: mock provider not seeded");
+            return;
+        };
+
+        let snapshot = || async {
+            sqlx::query_as::<_, (Uuid, String, bool)>(
+                "SELECT id, cost_per_hour::text, is_active FROM instance_types
+                 WHERE provider_id <> $1 ORDER BY id",
+            )
+            .bind(mock_id)
+            .fetch_all(&pool)
+            .await
+            .unwrap()
+        };
+        let before = snapshot().await;
+        // The stub catalog lacks the seeded mock types, so the sync soft-deletes them: restore after.
+        let mock_types: Vec<(Uuid, bool)> =
+            sqlx::query_as("SELECT id, is_active FROM instance_types WHERE provider_id = $1")
+                .bind(mock_id)
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        let mock_availability: Vec<(Uuid, Uuid, Option<bool>)> = sqlx::query_as(
+            "SELECT itz.instance_type_id, itz.zone_id, itz.is_available
+             FROM instance_type_zones itz
+             JOIN instance_types it ON it.id = itz.instance_type_id
+             WHERE it.provider_id = $1",
+        )
+        .bind(mock_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+
+        let code = format!("MOCK-SYNC-{}", &Uuid::new_v4().simple().to_string()[..8]);
+        let provider = TestProvider {
+            catalog: vec![inventory::CatalogItem {
+                name: code.clone(),
+                code: code.clone(),
+                cost_per_hour: 0.5,
+                cpu_count: 8,
+                ram_gb: 32,
+                gpu_count: 1,
+                vram_per_gpu_gb: 24,
+                bandwidth_bps: 1_000_000_000,
+            }],
+            ..Default::default()
+        };
+        for target in catalog_sync_targets(Some("mock")) {
+            sync_provider_catalog(&pool, &target, &provider).await;
+        }
+        for (id, is_active) in &mock_types {
+            let _ = sqlx::query("UPDATE instance_types SET is_active = $2 WHERE id = $1")
+                .bind(id)
+                .bind(is_active)
+                .execute(&pool)
+                .await;
+        }
+        for (type_id, zone_id, is_available) in &mock_availability {
+            let _ = sqlx::query(
+                "UPDATE instance_type_zones SET is_available = $3
+                 WHERE instance_type_id = $1 AND zone_id = $2",
+            )
+            .bind(type_id)
+            .bind(zone_id)
+            .bind(is_available)
+            .execute(&pool)
+            .await;
+        }
+
+        let synced: Option<Uuid> = sqlx::query_scalar(
+            "SELECT id FROM instance_types WHERE provider_id = $1 AND code = $2",
+        )
+        .bind(mock_id)
+        .bind(&code)
+        .fetch_optional(&pool)
+        .await
+        .unwrap();
+        let after = snapshot().await;
+
+        if let Some(type_id) = synced {
+            let _ = sqlx::query("DELETE FROM instance_type_zones WHERE instance_type_id = $1")
+                .bind(type_id)
+                .execute(&pool)
+                .await;
+            let _ = sqlx::query("DELETE FROM instance_types WHERE id = $1")
+                .bind(type_id)
+                .execute(&pool)
+                .await;
+        }
+
+        assert!(synced.is_some(), "mock catalog was not synced");
+        assert_eq!(before, after);
+    }
+
+    fn catalog_item(code: &str) -> inventory::CatalogItem {
+        inventory::CatalogItem {
+            name: code.to_string(),
+            code: code.to_string(),
+            cost_per_hour: 1.0,
+            cpu_count: 8,
+            ram_gb: 32,
+            gpu_count: 1,
+            vram_per_gpu_gb: 24,
+            bandwidth_bps: 1_000_000_000,
+        }
+    }
+
+    #[tokio::test]
+    async fn catalog_sync_soft_deletes_dropped_types_only_after_full_sync() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+
+        // Fresh provider: no zones in DB, so the sync uses the fr-par-1 / fr-par-2 fallback.
+        let provider_code = format!("catalog-sd-{}", &Uuid::new_v4().simple().to_string()[..8]);
+        let (kept, dropped) = ("KEEP-1", "DROP-1");
+        let sync = |items: Vec<inventory::CatalogItem>, failing_zone: Option<&str>| {
+            let provider = TestProvider {
+                catalog: items,
+                failing_catalog_zone: failing_zone.map(str::to_string),
+                ..Default::default()
+            };
+            let (pool, provider_code) = (pool.clone(), provider_code.clone());
+            async move { sync_provider_catalog(&pool, &provider_code, &provider).await }
+        };
+        let state = || async {
+            sqlx::query_as::<_, (String, bool, i64)>(
+                "SELECT it.code, it.is_active,
+                        COUNT(*) FILTER (WHERE itz.is_available IS DISTINCT FROM false)
+                 FROM instance_types it
+                 JOIN providers p ON p.id = it.provider_id
+                 LEFT JOIN instance_type_zones itz ON itz.instance_type_id = it.id
+                 WHERE p.code = $1
+                 GROUP BY it.code, it.is_active
+                 ORDER BY it.code",
+            )
+            .bind(&provider_code)
+            .fetch_all(&pool)
+            .await
+            .unwrap()
+        };
+
+        sync(vec![catalog_item(kept), catalog_item(dropped)], None).await;
+        let initial = state().await;
+        // A failed zone makes the data partial: the dropped type stays active.
+        sync(vec![catalog_item(kept)], Some("fr-par-2")).await;
+        let partial = state().await;
+        sync(vec![catalog_item(kept)], None).await;
+        let full = state().await;
+        sync(vec![catalog_item(kept), catalog_item(dropped)], None).await;
+        let reappeared = state().await;
+
+        for sql in [
+            "DELETE FROM instance_type_zones WHERE instance_type_id IN
+               (SELECT it.id FROM instance_types it JOIN providers p ON p.id = it.provider_id WHERE p.code = $1)",
+            "DELETE FROM instance_types WHERE provider_id = (SELECT id FROM providers WHERE code = $1)",
+            "DELETE FROM zones WHERE region_id IN
+               (SELECT r.id FROM regions r JOIN providers p ON p.id = r.provider_id WHERE p.code = $1)",
+            "DELETE FROM regions WHERE provider_id = (SELECT id FROM providers WHERE code = $1)",
+            "DELETE FROM providers WHERE code = $1",
+        ] {
+            let _ = sqlx::query(sql).bind(&provider_code).execute(&pool).await;
+        }
+
+        let row = |code: &str, active: bool, zones: i64| (code.to_string(), active, zones);
+        assert_eq!(initial, vec![row(dropped, true, 2), row(kept, true, 2)]);
+        // fr-par-1 succeeded, so availability there is already updated.
+        assert_eq!(partial, vec![row(dropped, true, 1), row(kept, true, 2)]);
+        assert_eq!(full, vec![row(dropped, false, 0), row(kept, true, 2)]);
+        assert_eq!(reappeared, initial);
+    }
+
+    #[tokio::test]
+    async fn parallel_catalog_sync_isolates_failing_provider() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+
+        let suffix = &Uuid::new_v4().simple().to_string()[..8];
+        let (first, second, broken) = (
+            format!("catalog-cc-a-{}", suffix),
+            format!("catalog-cc-b-{}", suffix),
+            format!("catalog-cc-x-{}", suffix),
+        );
+        let targets = vec![broken.clone(), first.clone(), second.clone()];
+        let results = sync_provider_catalogs(&pool, targets, 2, |code| {
+            let broken = broken.clone();
+            async move {
+                if code == broken {
+                    return Err(format!("Unknown provider '{}'", code));
+                }
+                let provider: Box<dyn CloudProvider> = Box::new(TestProvider {
+                    catalog: vec![catalog_item("PAR-1")],
+                    ..Default::default()
+                });
+                Ok(provider)
+            }
+        })
+        .await;
+
+        let mut synced: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT p.code, COUNT(it.id)
+             FROM providers p
+             JOIN instance_types it ON it.provider_id = p.id
+             WHERE p.code = ANY($1)
+             GROUP BY p.code",
+        )
+        .bind(vec![first.clone(), second.clone(), broken.clone()])
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        synced.sort();
+
+        for code in [&first, &second] {
+            for sql in [
+                "DELETE FROM instance_type_zones WHERE instance_type_id IN
+                   (SELECT it.id FROM instance_types it JOIN providers p ON p.id = it.provider_id WHERE p.code = $1)",
+                "DELETE FROM instance_types WHERE provider_id = (SELECT id FROM providers WHERE code = $1)",
+                "DELETE FROM zones WHERE region_id IN
+                   (SELECT r.id FROM regions r JOIN providers p ON p.id = r.provider_id WHERE p.code = $1)",
+                "DELETE FROM regions WHERE provider_id = (SELECT id FROM providers WHERE code = $1)",
+                "DELETE FROM providers WHERE code = $1",
+            ] {
+                let _ = sqlx::query(sql).bind(code).execute(&pool).await;
+            }
+        }
+
+        let mut outcomes: Vec<(String, bool)> = results
+            .iter()
+            .map(|r| (r.provider.clone(), r.outcome.is_ok()))
+            .collect();
+        outcomes.sort();
+        assert_eq!(
+            outcomes,
+            vec![
+                (first.clone(), true),
+                (second.clone(), true),
+                (broken.clone(), false),
+            ]
+        );
+        assert_eq!(synced, vec![(first, 1), (second, 1)]);
+    }
+
+    #[test]
+    fn price_change_threshold_applies_both_ways() {
+        assert_eq!(significant_price_change(1.0, 1.005, 1.0), None);
+        assert!((significant_price_change(1.0, 0.9, 1.0).unwrap() + 10.0).abs() < 1e-9);
+        // A type seen for the first time (or priced 0) has nothing to compare against.
+        assert_eq!(significant_price_change(0.0, 1.0, 1.0), None);
+    }
+
+    #[tokio::test]
+    async fn catalog_price_raise_emits_price_changed() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+
+        let provider_code = format!("catalog-pc-{}", &Uuid::new_v4().simple().to_string()[..8]);
+        let sync = |cost_per_hour: f64| {
+            let provider = TestProvider {
+                catalog: vec![inventory::CatalogItem {
+                    cost_per_hour,
+                    ..catalog_item("PRICE-1")
+                }],
+                ..Default::default()
+            };
+            let (pool, provider_code) = (pool.clone(), provider_code.clone());
+            async move { sync_provider_catalog(&pool, &provider_code, &provider).await }
+        };
+
+        let first = sync(1.0).await;
+        let unchanged = sync(1.0).await;
+        let raised = sync(1.2).await;
+
+        let type_id: Option<Uuid> = raised.first().map(|c| c.instance_type_id);
+        let logged: Vec<serde_json::Value> = sqlx::query_scalar(
+            "SELECT metadata FROM action_logs
+             WHERE action_type = 'PRICE_CHANGED' AND metadata->>'instance_type_id' = $1",
+        )
+        .bind(type_id.map(|id| id.to_string()))
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+
+        let _ = sqlx::query(
+            "DELETE FROM action_logs
+             WHERE action_type = 'PRICE_CHANGED' AND metadata->>'instance_type_id' = $1",
+        )
+        .bind(type_id.map(|id| id.to_string()))
+        .execute(&pool)
+        .await;
+        for sql in [
+            "DELETE FROM instance_type_zones WHERE instance_type_id IN
+               (SELECT it.id FROM instance_types it JOIN providers p ON p.id = it.provider_id WHERE p.code = $1)",
+            "DELETE FROM instance_types WHERE provider_id = (SELECT id FROM providers WHERE code = $1)",
+            "DELETE FROM zones WHERE region_id IN
+               (SELECT r.id FROM regions r JOIN providers p ON p.id = r.provider_id WHERE p.code = $1)",
+            "DELETE FROM regions WHERE provider_id = (SELECT id FROM providers WHERE code = $1)",
+            "DELETE FROM providers WHERE code = $1",
+        ] {
+            let _ = sqlx::query(sql).bind(&provider_code).execute(&pool).await;
+        }
+
+        assert!(first.is_empty());
+        assert!(unchanged.is_empty());
+        // Both fallback zones list the type; only the first upsert sees the old price.
+        assert_eq!(raised.len(), 1);
+        let change = &raised[0];
+        assert_eq!(change.instance_type_code, "PRICE-1");
+        assert_eq!(change.old_cost_per_hour, 1.0);
+        assert_eq!(change.new_cost_per_hour, 1.2);
+        assert!((change.pct_change - 20.0).abs() < 1e-6);
+
+        let evt = change.to_event("orchestrator");
+        assert_eq!(
+            evt.event_type,
+            inventiv_common::bus::FinopsEventType::PriceChanged
+        );
+        assert_eq!(evt.payload["old_cost_per_hour"], 1.0);
+        assert_eq!(evt.payload["new_cost_per_hour"], 1.2);
+        assert_eq!(logged.len(), 1);
+        assert_eq!(logged[0]["instance_type_code"], "PRICE-1");
+    }
+
+    #[tokio::test]
+    async fn zone_scoped_reconciliation_only_examines_that_zone() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(mock_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers WHERE code = 'mock'")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: mock provider not seeded");
+            return;
+        };
+
+        let suffix = &Uuid::new_v4().simple().to_string()[..8];
+        let region_id = Uuid::new_v4();
+        sqlx::query("INSERT INTO regions (id, provider_id, name, code) VALUES ($1, $2, $3, $3)")
+            .bind(region_id)
+            .bind(mock_id)
+            .bind(format!("test-rec-{}", suffix))
+            .execute(&pool)
+            .await
+            .unwrap();
+        let zones = [
+            format!("test-rec-a-{}", suffix),
+            format!("test-rec-b-{}", suffix),
+        ];
+        for zone in &zones {
+            sqlx::query("INSERT INTO zones (id, region_id, name, code) VALUES ($1, $2, $3, $3)")
+                .bind(Uuid::new_v4())
+                .bind(region_id)
+                .bind(zone)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let all = reconciliation_zones(&pool, "mock", None).await;
+        let scoped = reconciliation_zones(&pool, "mock", Some(zones[0].as_str())).await;
+        let provider = TestProvider::default();
+        reconcile_organization_zones(&pool, &provider, "mock", Uuid::new_v4(), &scoped).await;
+
+        let _ = sqlx::query("DELETE FROM zones WHERE region_id = $1")
+            .bind(region_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM regions WHERE id = $1")
+            .bind(region_id)
+            .execute(&pool)
+            .await;
+
+        assert!(zones.iter().all(|z| all.contains(z)));
+        assert_eq!(scoped, vec![zones[0].clone()]);
+        assert_eq!(
+            *provider.calls.listed_zones.lock().unwrap(),
+            vec![zones[0].clone()]
+        );
+    }
+
+    #[tokio::test]
+    async fn provisioning_logs_step_progress_in_order() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(mock_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers WHERE code = 'mock'")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: mock provider not seeded");
+            return;
+        };
+
+        let instance_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, status, created_at, gpu_profile)
+             VALUES ($1, $2, 'provisioning', NOW(), '{}')",
+        )
+        .bind(instance_id)
+        .bind(mock_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        provision_with_provider(
+            ProvisioningRun {
+                pool: pool.clone(),
+                redis_client: redis::Client::open("redis://127.0.0.1/").unwrap(),
+                instance_uuid: instance_id,
+                zone: "mock-zone".to_string(),
+                instance_type: "MOCK-GPU".to_string(),
+                type_id: Uuid::new_v4(),
+                provider_name: "mock".to_string(),
+                correlation_id_meta: Some("steps-test".to_string()),
+                log_id_execute: None,
+                start: Instant::now(),
+            },
+            Box::new(TestProvider {
+                ip: Some("10.0.0.7".to_string()),
+                ..Default::default()
+            }),
+        )
+        .await;
+
+        let steps: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT metadata->>'step', (metadata->>'elapsed_ms')::bigint
+             FROM action_logs
+             WHERE instance_id = $1 AND action_type = 'PROVISIONING_STEP'
+             ORDER BY created_at, (metadata->>'elapsed_ms')::bigint",
+        )
+        .bind(instance_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        let status: String = sqlx::query_scalar("SELECT status::text FROM instances WHERE id = $1")
+            .bind(instance_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+        for table in ["action_logs", "instance_volumes"] {
+            let _ = sqlx::query(&format!("DELETE FROM {} WHERE instance_id = $1", table))
+                .bind(instance_id)
+                .execute(&pool)
+                .await;
+        }
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+
+        let names: Vec<&str> = steps.iter().map(|(s, _)| s.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "create_server",
+                "discover_volumes",
+                "attach_storage",
+                "start_server",
+                "resolve_ip",
+                "finalize"
+            ]
+        );
+        assert!(steps.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert_eq!(status, "booting");
+    }
+
+    #[tokio::test]
+    async fn reused_volume_is_attached_instead_of_created() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(mock_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers WHERE code = 'mock'")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: mock provider not seeded");
+            return;
+        };
+
+        let instance_id = Uuid::new_v4();
+        let volume_id = format!("vol-cache-{}", instance_id);
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, status, created_at, gpu_profile)
+             VALUES ($1, $2, 'provisioning', NOW(), '{}')",
+        )
+        .bind(instance_id)
+        .bind(mock_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        // Reservation written by the API for `reuse_volume_id`.
+        sqlx::query(
+            "INSERT INTO instance_volumes (id, instance_id, provider_id, zone_code, provider_volume_id, volume_type, size_bytes, delete_on_terminate, status, is_boot)
+             VALUES ($1, $2, $3, 'mock-zone', $4, 'sbs_volume', 200000000000, FALSE, 'reserved', FALSE)",
+        )
+        .bind(Uuid::new_v4())
+        .bind(instance_id)
+        .bind(mock_id)
+        .bind(&volume_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let provider = TestProvider {
+            ip: Some("10.0.0.8".to_string()),
+            ..Default::default()
+        };
+        let calls = provider.calls.clone();
+
+        provision_with_provider(
+            ProvisioningRun {
+                pool: pool.clone(),
+                redis_client: redis::Client::open("redis://127.0.0.1/").unwrap(),
+                instance_uuid: instance_id,
+                zone: "mock-zone".to_string(),
+                instance_type: "MOCK-GPU".to_string(),
+                type_id: Uuid::new_v4(),
+                provider_name: "mock".to_string(),
+                correlation_id_meta: Some("reuse-volume-test".to_string()),
+                log_id_execute: None,
+                start: Instant::now(),
+            },
+            Box::new(provider),
+        )
+        .await;
+
+        let tracked: Vec<(String, String, bool)> = sqlx::query_as(
+            "SELECT provider_volume_id, status, delete_on_terminate FROM instance_volumes
+             WHERE instance_id = $1 AND deleted_at IS NULL",
+        )
+        .bind(instance_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+
+        for table in ["action_logs", "instance_volumes"] {
+            let _ = sqlx::query(&format!("DELETE FROM {} WHERE instance_id = $1", table))
+                .bind(instance_id)
+                .execute(&pool)
+                .await;
+        }
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+
+        let create_volumes: Vec<_> = calls
+            .creates
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|c| c.volumes.clone())
+            .collect();
+        assert_eq!(
+            calls
+                .volumes_created
+                .load(std::sync::atomic::Ordering::SeqCst),
+            0
+        );
+        assert_eq!(create_volumes, vec![Some(vec![volume_id.clone()])]);
+        assert_eq!(
+            *calls.attached.lock().unwrap(),
+            vec![(volume_id.clone(), false)]
+        );
+        assert_eq!(tracked, vec![(volume_id, "attached".to_string(), false)]);
+    }
+
+    #[tokio::test]
+    async fn retryable_provider_failures_are_retried_before_failing() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(mock_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers WHERE code = 'mock'")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: mock provider not seeded");
+            return;
+        };
+        // Only provider stubs returning retryable errors reach the backoff, so this is safe here.
+        std::env::set_var("PROVISION_RETRY_BACKOFF_MS", "1");
+
+        let provision = |code, failures| {
+            let pool = pool.clone();
+            async move {
+                let instance_id = Uuid::new_v4();
+                sqlx::query(
+                    "INSERT INTO instances (id, provider_id, status, created_at, gpu_profile)
+                     VALUES ($1, $2, 'provisioning', NOW(), '{}')",
+                )
+                .bind(instance_id)
+                .bind(mock_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+                let provider = TestProvider {
+                    ip: Some("10.0.0.8".to_string()),
+                    create_failures: Some((code, failures)),
+                    ..Default::default()
+                };
+                let calls = provider.calls.clone();
+                provision_with_provider(
+                    ProvisioningRun {
+                        pool: pool.clone(),
+                        redis_client: redis::Client::open("redis://127.0.0.1/").unwrap(),
+                        instance_uuid: instance_id,
+                        zone: "mock-zone".to_string(),
+                        instance_type: "MOCK-GPU".to_string(),
+                        type_id: Uuid::new_v4(),
+                        provider_name: "mock".to_string(),
+                        correlation_id_meta: None,
+                        log_id_execute: None,
+                        start: Instant::now(),
+                    },
+                    Box::new(provider),
+                )
+                .await;
+
+                let (status, error_code): (String, Option<String>) =
+                    sqlx::query_as("SELECT status::text, error_code FROM instances WHERE id = $1")
+                        .bind(instance_id)
+                        .fetch_one(&pool)
+                        .await
+                        .unwrap();
+                let retries: i64 = sqlx::query_scalar(
+                    "SELECT COUNT(*) FROM action_logs WHERE instance_id = $1 AND action_type = 'PROVISION_RETRY'",
+                )
+                .bind(instance_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+                let recorded: Option<i64> = sqlx::query_scalar(
+                    "SELECT jsonb_array_length(metadata->'retry_attempts')::bigint
+                     FROM action_logs
+                     WHERE instance_id = $1 AND action_type = 'PROVIDER_CREATE'",
+                )
+                .bind(instance_id)
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+                .flatten();
+
+                for table in ["action_logs", "instance_volumes"] {
+                    let _ = sqlx::query(&format!("DELETE FROM {} WHERE instance_id = $1", table))
+                        .bind(instance_id)
+                        .execute(&pool)
+                        .await;
+                }
+                let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+                    .bind(instance_id)
+                    .execute(&pool)
+                    .await;
+
+                let calls = calls.creates.lock().unwrap().len();
+                (status, error_code, calls, retries, recorded)
+            }
+        };
+
+        // Out of capacity twice, then the create goes through.
+        let (status, _, calls, retries, recorded) =
+            provision(inventiv_providers::ProviderErrorCode::OutOfCapacity, 2).await;
+        assert_eq!(status, "booting");
+        assert_eq!(calls, 3);
+        assert_eq!(retries, 2);
+        assert_eq!(recorded, Some(2));
+
+        // A missing image is not retried.
+        let (status, error_code, calls, retries, _) =
+            provision(inventiv_providers::ProviderErrorCode::ImageNotFound, 1).await;
+        assert_eq!(status, "provisioning_failed");
+        assert_eq!(error_code.as_deref(), Some("IMAGE_NOT_FOUND"));
+        assert_eq!(calls, 1);
+        assert_eq!(retries, 0);
+    }
+
+    #[tokio::test]
+    async fn provisioning_aborts_when_model_was_deactivated() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(mock_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers WHERE code = 'mock'")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: mock provider not seeded");
+            return;
+        };
+
+        // Catalog entries, owner/org and an active model, as the API would have validated them.
+        let suffix = &Uuid::new_v4().simple().to_string()[..8];
+        let (region_id, zone_id, type_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let (user_id, org_id, model_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let code = format!("test-inactive-{}", suffix);
+        for (sql, id) in [
+            ("INSERT INTO regions (id, provider_id, name, code) VALUES ($1, $2, $3, $3)", region_id),
+            (
+                "INSERT INTO zones (id, region_id, provider_id, name, code)
+                 VALUES ($1, $4, $2, $3, $3)",
+                zone_id,
+            ),
+            (
+                "INSERT INTO instance_types (id, provider_id, name, code, gpu_count, vram_per_gpu_gb)
+                 VALUES ($1, $2, $3, $3, 1, 24)",
+                type_id,
+            ),
+        ] {
+            sqlx::query(sql)
+                .bind(id)
+                .bind(mock_id)
+                .bind(&code)
+                .bind(region_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        sqlx::query(
+            "INSERT INTO users (id, email, username, password_hash) VALUES ($1, $2, $2, 'x')",
+        )
+        .bind(user_id)
+        .bind(format!("{}@test.local", code))
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO organizations (id, name, slug, created_by_user_id) VALUES ($1, $2, $2, $3)",
+        )
+        .bind(org_id)
+        .bind(&code)
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active)
+             VALUES ($1, $2, $2, 1, 2048, true)",
+        )
+        .bind(model_id)
+        .bind(&code)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // Accepted deployment...
+        let instance_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, zone_id, instance_type_id, organization_id, model_id, status, created_at, gpu_profile)
+             VALUES ($1, $2, $3, $4, $5, $6, 'accepted', NOW(), '{}')",
+        )
+        .bind(instance_id)
+        .bind(mock_id)
+        .bind(zone_id)
+        .bind(type_id)
+        .bind(org_id)
+        .bind(model_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        // ...then the model is disabled before the orchestrator picks it up.
+        sqlx::query("UPDATE models SET is_active = false WHERE id = $1")
+            .bind(model_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        process_provisioning(
+            pool.clone(),
+            redis::Client::open("redis://127.0.0.1/").unwrap(),
+            instance_id.to_string(),
+            code.clone(),
+            code.clone(),
+            None,
+        )
+        .await;
+
+        let (status, error_code, provider_instance_id): (String, Option<String>, Option<String>) =
+            sqlx::query_as(
+                "SELECT status::text, error_code, provider_instance_id FROM instances WHERE id = $1",
+            )
+            .bind(instance_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        let provider_calls: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM action_logs WHERE instance_id = $1 AND action_type LIKE 'PROVIDER_%'",
+        )
+        .bind(instance_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let history: Vec<(String, String)> = sqlx::query_as(
+            "SELECT from_status, to_status FROM instance_state_history
+             WHERE instance_id = $1 ORDER BY created_at, id",
+        )
+        .bind(instance_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+
+        for sql in [
+            "DELETE FROM action_logs WHERE instance_id = $1",
+            "DELETE FROM instance_state_history WHERE instance_id = $1",
+            "DELETE FROM instances WHERE id = $1",
+        ] {
+            let _ = sqlx::query(sql).bind(instance_id).execute(&pool).await;
+        }
+        for (sql, id) in [
+            ("DELETE FROM models WHERE id = $1", model_id),
+            ("DELETE FROM organizations WHERE id = $1", org_id),
+            ("DELETE FROM users WHERE id = $1", user_id),
+            ("DELETE FROM instance_types WHERE id = $1", type_id),
+            ("DELETE FROM zones WHERE id = $1", zone_id),
+            ("DELETE FROM regions WHERE id = $1", region_id),
+        ] {
+            let _ = sqlx::query(sql).bind(id).execute(&pool).await;
+        }
+
+        assert_eq!(status, "provisioning_failed");
+        assert_eq!(error_code.as_deref(), Some("INACTIVE_MODEL"));
+        assert_eq!(provider_instance_id, None);
+        assert_eq!(provider_calls, 0);
+        // Picked up (accepted -> provisioning) before failing.
+        assert_eq!(
+            history.first(),
+            Some(&("accepted".to_string(), "provisioning".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn provider_metadata_is_stored_on_instance() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(mock_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers WHERE code = 'mock'")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: mock provider not seeded");
+            return;
+        };
+
+        let instance_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, provider_instance_id, status, created_at, gpu_profile)
+             VALUES ($1, $2, 'srv-metadata', 'booting', NOW(), '{}')",
+        )
+        .bind(instance_id)
+        .bind(mock_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let provider = TestProvider {
+            instance_details: Some(inventory::InstanceDetails {
+                provider_id: "srv-metadata".to_string(),
+                name: Some("srv-metadata".to_string()),
+                state: Some("running".to_string()),
+                ip_address: Some("10.1.2.3".to_string()),
+                volumes: vec![],
+                commercial_type: Some("MOCK-GPU-S".to_string()),
+                zone: Some("mock-zone-1".to_string()),
+                hypervisor: Some("mock-hypervisor".to_string()),
+            }),
+            ..Default::default()
+        };
+        let stored =
+            sync_provider_metadata(&pool, &provider, instance_id, "mock-zone-1", "srv-metadata")
+                .await;
+        let metadata: Option<serde_json::Value> =
+            sqlx::query_scalar("SELECT provider_metadata FROM instances WHERE id = $1")
+                .bind(instance_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+
+        assert!(stored);
+        let metadata = metadata.expect("provider_metadata stored");
+        assert_eq!(metadata["commercial_type"], "MOCK-GPU-S");
+        assert_eq!(metadata["zone"], "mock-zone-1");
+        assert_eq!(metadata["hypervisor"], "mock-hypervisor");
+        assert_eq!(metadata["state"], "running");
+    }
+
+    #[tokio::test]
+    async fn resize_updates_instance_type_and_reboots() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some((old_type, provider_id)): Option<(Uuid, Uuid)> = sqlx::query_as(
+            "SELECT it.id, it.provider_id FROM instance_types it
+             JOIN providers p ON p.id = it.provider_id
+             WHERE p.code = 'mock' LIMIT 1",
+        )
+        .fetch_optional(&pool)
+        .await
+        .unwrap() else {
+            eprintln!("skipping integration test: mock catalog not seeded");
+            return;
+        };
+        let new_code = format!("mock-resize-{}", &Uuid::new_v4().to_string()[..8]);
+        let new_type: Uuid = sqlx::query_scalar(
+            "INSERT INTO instance_types (id, provider_id, name, code, gpu_count, vram_per_gpu_gb, is_active)
+             VALUES (gen_random_uuid(), $1, $2, $2, 2, 48, true) RETURNING id",
+        )
+        .bind(provider_id)
+        .bind(&new_code)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let instance_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, instance_type_id, provider_instance_id, status, created_at, gpu_profile)
+             VALUES ($1, $2, $3, 'srv-resize', 'ready', NOW(), '{}')",
+        )
+        .bind(instance_id)
+        .bind(provider_id)
+        .bind(old_type)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let provider = TestProvider {
+            ip: Some("10.1.2.4".to_string()),
+            ..Default::default()
+        };
+        let result = resize_with_provider(
+            &pool,
+            &provider,
+            instance_id,
+            "mock-zone-1",
+            "srv-resize",
+            new_type,
+            &new_code,
+        )
+        .await;
+        let (type_id, status): (Option<Uuid>, String) =
+            sqlx::query_as("SELECT instance_type_id, status::text FROM instances WHERE id = $1")
+                .bind(instance_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instance_types WHERE id = $1")
+            .bind(new_type)
+            .execute(&pool)
+            .await;
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            *provider.calls.resized_to.lock().unwrap(),
+            vec![new_code.clone()]
+        );
+        assert_eq!(type_id, Some(new_type));
+        assert_eq!(status, "booting");
+    }
+
+    #[tokio::test]
+    async fn resize_skips_instances_that_are_not_ready() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some((type_id, provider_id)): Option<(Uuid, Uuid)> = sqlx::query_as(
+            "SELECT it.id, it.provider_id FROM instance_types it
+             JOIN providers p ON p.id = it.provider_id
+             WHERE p.code = 'mock' LIMIT 1",
+        )
+        .fetch_optional(&pool)
+        .await
+        .unwrap() else {
+            eprintln!("skipping integration test: mock catalog not seeded");
+            return;
+        };
+        let instance_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, instance_type_id, provider_instance_id, status, created_at, gpu_profile)
+             VALUES ($1, $2, $3, 'srv-resize-busy', 'booting', NOW(), '{}')",
+        )
+        .bind(instance_id)
+        .bind(provider_id)
+        .bind(type_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let provider = TestProvider::default();
+        let result = resize_with_provider(
+            &pool,
+            &provider,
+            instance_id,
+            "mock-zone-1",
+            "srv-resize-busy",
+            type_id,
+            "mock-other",
+        )
+        .await;
+        let status: String =
+            sqlx::query_scalar("SELECT status::text FROM instances WHERE id = $1")
+                .bind(instance_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+
+        assert!(result.is_err());
+        assert!(provider.calls.resized_to.lock().unwrap().is_empty());
+        assert_eq!(status, "booting");
+    }
+
+    #[tokio::test]
+    async fn reinstall_with_larger_volume_model_grows_block_storage() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(provider_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers WHERE code = 'mock'")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: mock provider not seeded");
+            return;
+        };
+
+        // Reinstalled with a model that needs 300GB; the volume was sized 200GB for the old one.
+        let model_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, data_volume_gb, metadata, created_at, updated_at)
+             VALUES ($1, $2, $2, 24, 4096, true, 300, '{}'::jsonb, NOW(), NOW())",
+        )
+        .bind(model_id)
+        .bind(format!("test-org/reinstall-volume-{}", model_id))
+        .execute(&pool)
+        .await
+        .unwrap();
+        let instance_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, model_id, provider_instance_id, status, created_at, gpu_profile)
+             VALUES ($1, $2, $3, 'srv-reinstall', 'ready', NOW(), '{}')",
+        )
+        .bind(instance_id)
+        .bind(provider_id)
+        .bind(model_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let volume_id = format!("vol-{}", Uuid::new_v4());
+        sqlx::query(
+            "INSERT INTO instance_volumes (id, instance_id, provider_id, zone_code, provider_volume_id, volume_type, size_bytes, is_boot)
+             VALUES (gen_random_uuid(), $1, $2, 'mock-zone-1', $3, 'sbs_volume', 200000000000, false)",
+        )
+        .bind(instance_id)
+        .bind(provider_id)
+        .bind(&volume_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let provider = TestProvider {
+            block_storage_bytes: Some(200_000_000_000),
+            ..Default::default()
+        };
+        let grown =
+            grow_data_volume_for_model(&pool, &provider, instance_id, "mock-zone-1", None).await;
+        // Already large enough: no second resize.
+        let again =
+            grow_data_volume_for_model(&pool, &provider, instance_id, "mock-zone-1", None).await;
+        let tracked: i64 = sqlx::query_scalar(
+            "SELECT size_bytes FROM instance_volumes WHERE instance_id = $1 AND provider_volume_id = $2",
+        )
+        .bind(instance_id)
+        .bind(&volume_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let _ = sqlx::query("DELETE FROM action_logs WHERE instance_id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instance_volumes WHERE instance_id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instances WHERE id = $1")
+            .bind(instance_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+            .bind(model_id)
+            .execute(&pool)
+            .await;
+
+        assert_eq!(grown, Ok(Some((200, 300))));
+        assert_eq!(again, Ok(None));
+        assert_eq!(
+            *provider.calls.block_storage_resized_to_gb.lock().unwrap(),
+            vec![300]
+        );
+        assert_eq!(tracked, 300_000_000_000);
+    }
 }
@@ -1,3 +1,4 @@
+use inventiv_common::error_code::ErrorCode;
 use sqlx::{Pool, Postgres};
 use uuid::Uuid;
 
@@ -148,7 +149,70 @@ pub async fn installing_to_starting(
     }
 }
 
+/// Transition ACCEPTED -> PROVISIONING (idempotent).
+/// Called when process_provisioning picks up a queued deployment, before any provider call.
+pub async fn accepted_to_provisioning(
+    db: &Pool<Postgres>,
+    instance_id: Uuid,
+    reason: &str,
+) -> Result<bool, sqlx::Error> {
+    let res = sqlx::query(
+        "UPDATE instances
+         SET status = 'provisioning'
+         WHERE id = $1 AND status = 'accepted'",
+    )
+    .bind(instance_id)
+    .execute(db)
+    .await?;
+
+    if res.rows_affected() > 0 {
+        log_state_transition(db, instance_id, "accepted", "provisioning", reason).await;
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+/// Transition PROVISIONING -> PROVISIONING_FAILED (idempotent): the provider never delivered a
+/// running server (create/start refused, model disabled before allocation). Anything past
+/// `provisioning` fails as STARTUP_FAILED instead.
+pub async fn provisioning_to_provisioning_failed(
+    db: &Pool<Postgres>,
+    instance_id: Uuid,
+    error_code: &str,
+    error_message: &str,
+) -> Result<bool, sqlx::Error> {
+    let res = sqlx::query(
+        "UPDATE instances
+         SET status = 'provisioning_failed',
+             error_code = $2,
+             error_message = $3,
+             failed_at = COALESCE(failed_at, NOW())
+         WHERE id = $1 AND status = 'provisioning'",
+    )
+    .bind(instance_id)
+    .bind(error_code)
+    .bind(error_message)
+    .execute(db)
+    .await?;
+
+    if res.rows_affected() > 0 {
+        log_state_transition(
+            db,
+            instance_id,
+            "provisioning",
+            "provisioning_failed",
+            error_message,
+        )
+        .await;
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
 /// Transition BOOTING/INSTALLING/STARTING -> STARTUP_FAILED (idempotent) + logs in action_logs.
+/// The server exists at the provider but the worker never became healthy.
 pub async fn booting_to_startup_failed(
     db: &Pool<Postgres>,
     instance_id: Uuid,
@@ -216,6 +280,163 @@ pub async fn booting_to_startup_failed(
     }
 }
 
+/// Startup timeout policy used by the set-based sweep.
+/// Same resolution as the per-instance check: provider_settings -> env -> default.
+pub struct StartupTimeoutPolicy {
+    pub worker_auto_install: bool,
+    /// WORKER_AUTO_INSTALL_INSTANCE_PATTERNS (`*` wildcard, case-insensitive)
+    pub worker_instance_patterns: Vec<String>,
+    pub worker_timeout_s: i64,
+    pub default_timeout_s: i64,
+}
+
+impl StartupTimeoutPolicy {
+    pub fn from_env() -> Self {
+        let env_i64 = |key: &str| {
+            std::env::var(key)
+                .ok()
+                .and_then(|s| s.trim().parse::<i64>().ok())
+        };
+        Self {
+            worker_auto_install: std::env::var("WORKER_AUTO_INSTALL")
+                .ok()
+                .map(|v| {
+                    matches!(
+                        v.trim().to_ascii_lowercase().as_str(),
+                        "1" | "true" | "yes" | "on"
+                    )
+                })
+                .unwrap_or(false),
+            worker_instance_patterns: inventiv_common::worker_target::parse_instance_type_patterns(
+                std::env::var("WORKER_AUTO_INSTALL_INSTANCE_PATTERNS")
+                    .ok()
+                    .as_deref(),
+            ),
+            worker_timeout_s: env_i64("WORKER_INSTANCE_STARTUP_TIMEOUT_S").unwrap_or(3600),
+            default_timeout_s: env_i64("INSTANCE_STARTUP_TIMEOUT_S").unwrap_or(300),
+        }
+    }
+}
+
+/// Translate an instance-type pattern (`*` wildcard) into an uppercase SQL LIKE pattern.
+fn instance_type_pattern_to_like(pattern: &str) -> String {
+    let mut out = String::new();
+    for c in pattern.trim().to_ascii_uppercase().chars() {
+        match c {
+            '*' => out.push('%'),
+            '%' | '_' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Transition every overdue BOOTING/INSTALLING/STARTING instance -> STARTUP_FAILED in one statement.
+/// Returns (instance_id, from_status, timeout_s) for each transitioned instance.
+pub async fn startup_timeouts_to_failed(
+    db: &Pool<Postgres>,
+    policy: &StartupTimeoutPolicy,
+) -> Result<Vec<(Uuid, String, i64)>, sqlx::Error> {
+    let like_patterns: Vec<String> = policy
+        .worker_instance_patterns
+        .iter()
+        .map(|p| instance_type_pattern_to_like(p))
+        .filter(|p| !p.is_empty())
+        .collect();
+
+    let failed: Vec<(Uuid, String, i64)> = sqlx::query_as(
+        r#"
+        WITH candidates AS (
+            SELECT i.id,
+                   i.status::text AS from_status,
+                   COALESCE(i.boot_started_at, i.created_at) AS boot_started_at,
+                   CASE
+                     WHEN $1
+                      AND p.code = 'scaleway'
+                      AND btrim(COALESCE(it.code, '')) <> ''
+                      AND upper(btrim(it.code)) LIKE ANY($2::text[])
+                     THEN COALESCE(pw.value_int, $3)
+                     ELSE COALESCE(pd.value_int, $4)
+                   END::bigint AS timeout_s
+            FROM instances i
+            JOIN providers p ON p.id = i.provider_id
+            LEFT JOIN instance_types it ON it.id = i.instance_type_id
+            LEFT JOIN provider_settings pw
+              ON pw.provider_id = i.provider_id AND pw.key = 'WORKER_INSTANCE_STARTUP_TIMEOUT_S'
+            LEFT JOIN provider_settings pd
+              ON pd.provider_id = i.provider_id AND pd.key = 'INSTANCE_STARTUP_TIMEOUT_S'
+            WHERE i.status IN ('booting', 'installing', 'starting')
+            FOR UPDATE OF i SKIP LOCKED
+        ),
+        overdue AS (
+            SELECT id, from_status, timeout_s
+            FROM candidates
+            WHERE boot_started_at < NOW() - (timeout_s * INTERVAL '1 second')
+        )
+        UPDATE instances i
+        SET status = 'startup_failed',
+            error_code = $5,
+            error_message = 'Instance failed to become healthy within ' || o.timeout_s || ' seconds',
+            failed_at = COALESCE(i.failed_at, NOW())
+        FROM overdue o
+        WHERE i.id = o.id
+        RETURNING i.id, o.from_status, o.timeout_s
+        "#,
+    )
+    .bind(policy.worker_auto_install)
+    .bind(&like_patterns)
+    .bind(policy.worker_timeout_s)
+    .bind(policy.default_timeout_s)
+    .bind(ErrorCode::StartupTimeout.as_str())
+    .fetch_all(db)
+    .await?;
+
+    if failed.is_empty() {
+        return Ok(failed);
+    }
+
+    // History rows for the whole set in one insert; action logs per instance (timeline UI).
+    let ids: Vec<Uuid> = failed.iter().map(|(id, _, _)| *id).collect();
+    let from: Vec<String> = failed.iter().map(|(_, f, _)| f.clone()).collect();
+    let reasons: Vec<String> = failed
+        .iter()
+        .map(|(_, _, t)| format!("Instance failed to become healthy within {} seconds", t))
+        .collect();
+    let _ = sqlx::query(
+        "INSERT INTO instance_state_history (instance_id, from_status, to_status, reason)
+         SELECT t.id, t.from_status, 'startup_failed', t.reason
+         FROM UNNEST($1::uuid[], $2::text[], $3::text[]) AS t(id, from_status, reason)",
+    )
+    .bind(&ids)
+    .bind(&from)
+    .bind(&reasons)
+    .execute(db)
+    .await;
+
+    for ((instance_id, from_status, timeout_s), reason) in failed.iter().zip(reasons.iter()) {
+        let _ = logger::log_event_with_metadata(
+            db,
+            "INSTANCE_STARTUP_FAILED",
+            "failed",
+            *instance_id,
+            Some(reason),
+            Some(serde_json::json!({
+                "error_code": "STARTUP_TIMEOUT",
+                "error_message": reason,
+                "from_status": from_status,
+                "timeout_s": timeout_s,
+                "batch": true,
+            })),
+        )
+        .await;
+    }
+
+    Ok(failed)
+}
+
 /// Update health check failures for BOOTING/INSTALLING/STARTING/UNAVAILABLE instances (idempotent).
 pub async fn update_booting_health_failures(
     db: &Pool<Postgres>,
@@ -371,3 +592,214 @@ pub async fn terminating_to_terminated(
         Ok(false)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::setup_pool;
+
+    #[test]
+    fn instance_type_patterns_translate_to_like() {
+        assert_eq!(instance_type_pattern_to_like("l4-*"), "L4-%");
+        assert_eq!(instance_type_pattern_to_like(" RENDER-S "), "RENDER-S");
+        assert_eq!(instance_type_pattern_to_like("A_100%"), "A\\_100\\%");
+        assert_eq!(instance_type_pattern_to_like("*"), "%");
+    }
+
+    #[tokio::test]
+    async fn create_and_boot_failures_land_in_distinct_statuses() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(provider_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers LIMIT 1")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: no provider seeded");
+            return;
+        };
+
+        let never_created = Uuid::new_v4();
+        let never_booted = Uuid::new_v4();
+        for (id, status) in [(never_created, "provisioning"), (never_booted, "booting")] {
+            sqlx::query(
+                "INSERT INTO instances (id, provider_id, status, created_at, gpu_profile)
+                 VALUES ($1, $2, $3::instance_status, NOW(), '{}')",
+            )
+            .bind(id)
+            .bind(provider_id)
+            .bind(status)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        // Each transition only applies to its own stage.
+        let wrong_create =
+            booting_to_startup_failed(&pool, never_created, "SSH_NOT_ACCESSIBLE", "ssh down")
+                .await
+                .unwrap();
+        let wrong_boot = provisioning_to_provisioning_failed(
+            &pool,
+            never_booted,
+            "PROVIDER_CREATE_FAILED",
+            "create refused",
+        )
+        .await
+        .unwrap();
+        let create_failed = provisioning_to_provisioning_failed(
+            &pool,
+            never_created,
+            "PROVIDER_CREATE_FAILED",
+            "create refused",
+        )
+        .await
+        .unwrap();
+        let boot_failed =
+            booting_to_startup_failed(&pool, never_booted, "SSH_NOT_ACCESSIBLE", "ssh down")
+                .await
+                .unwrap();
+
+        let ids = vec![never_created, never_booted];
+        let rows: Vec<(Uuid, String, Option<String>)> =
+            sqlx::query_as("SELECT id, status::text, error_code FROM instances WHERE id = ANY($1)")
+                .bind(&ids)
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        let history: Vec<(Uuid, String, String)> = sqlx::query_as(
+            "SELECT instance_id, from_status, to_status FROM instance_state_history WHERE instance_id = ANY($1)",
+        )
+        .bind(&ids)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+
+        for table in ["action_logs", "instance_state_history"] {
+            let _ = sqlx::query(&format!(
+                "DELETE FROM {} WHERE instance_id = ANY($1)",
+                table
+            ))
+            .bind(&ids)
+            .execute(&pool)
+            .await;
+        }
+        let _ = sqlx::query("DELETE FROM instances WHERE id = ANY($1)")
+            .bind(&ids)
+            .execute(&pool)
+            .await;
+
+        assert!(!wrong_create && !wrong_boot);
+        assert!(create_failed && boot_failed);
+        for (id, status, error_code) in rows {
+            if id == never_created {
+                assert_eq!(status, "provisioning_failed");
+                assert_eq!(error_code.as_deref(), Some("PROVIDER_CREATE_FAILED"));
+            } else {
+                assert_eq!(status, "startup_failed");
+                assert_eq!(error_code.as_deref(), Some("SSH_NOT_ACCESSIBLE"));
+            }
+        }
+        assert!(history.contains(&(
+            never_created,
+            "provisioning".to_string(),
+            "provisioning_failed".to_string()
+        )));
+        assert!(history.contains(&(
+            never_booted,
+            "booting".to_string(),
+            "startup_failed".to_string()
+        )));
+    }
+
+    #[tokio::test]
+    async fn startup_timeouts_transition_all_overdue_instances_at_once() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(provider_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers LIMIT 1")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: no provider seeded");
+            return;
+        };
+
+        let overdue: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        let fresh = Uuid::new_v4();
+        for (id, status, age) in [
+            (overdue[0], "booting", "2 hours"),
+            (overdue[1], "installing", "2 hours"),
+            (overdue[2], "starting", "2 hours"),
+            (fresh, "booting", "10 seconds"),
+        ] {
+            sqlx::query(
+                "INSERT INTO instances (id, provider_id, status, created_at, boot_started_at, gpu_profile)
+                 VALUES ($1, $2, $3::instance_status, NOW() - $4::interval, NOW() - $4::interval, '{}')",
+            )
+            .bind(id)
+            .bind(provider_id)
+            .bind(status)
+            .bind(age)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let policy = StartupTimeoutPolicy {
+            worker_auto_install: false,
+            worker_instance_patterns: Vec::new(),
+            worker_timeout_s: 3600,
+            default_timeout_s: 300,
+        };
+        let failed = startup_timeouts_to_failed(&pool, &policy).await.unwrap();
+        let failed_ids: Vec<Uuid> = failed.iter().map(|(id, _, _)| *id).collect();
+
+        let mut all = overdue.clone();
+        all.push(fresh);
+        let statuses: Vec<(Uuid, String, Option<String>)> =
+            sqlx::query_as("SELECT id, status::text, error_code FROM instances WHERE id = ANY($1)")
+                .bind(&all)
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        let history: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM instance_state_history WHERE instance_id = ANY($1) AND to_status = 'startup_failed'",
+        )
+        .bind(&overdue)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let _ = sqlx::query("DELETE FROM action_logs WHERE instance_id = ANY($1)")
+            .bind(&all)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instance_state_history WHERE instance_id = ANY($1)")
+            .bind(&all)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instances WHERE id = ANY($1)")
+            .bind(&all)
+            .execute(&pool)
+            .await;
+
+        for id in &overdue {
+            assert!(failed_ids.contains(id));
+        }
+        assert!(!failed_ids.contains(&fresh));
+        assert_eq!(history, 3);
+        for (id, status, error_code) in statuses {
+            if id == fresh {
+                assert_eq!(status, "booting");
+            } else {
+                assert_eq!(status, "startup_failed");
+                assert_eq!(error_code.as_deref(), Some("STARTUP_TIMEOUT"));
+            }
+        }
+    }
+}
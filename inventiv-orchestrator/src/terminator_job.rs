@@ -5,6 +5,7 @@ use crate::finops_events;
 use crate::logger;
 use crate::provider_manager::ProviderManager;
 use crate::state_machine;
+use inventiv_common::error_code::ErrorCode;
 use inventiv_providers::CloudProvider;
 
 async fn delete_instance_volumes_best_effort(
@@ -231,7 +232,9 @@ pub async fn terminator_terminating_instances(
 
     let mut progressed = 0usize;
 
-    for (instance_id, provider_id, provider_instance_id_opt, zone_opt, organization_id_opt) in claimed {
+    for (instance_id, provider_id, provider_instance_id_opt, zone_opt, organization_id_opt) in
+        claimed
+    {
         // If we don't even have a provider instance id, we can safely finalize termination in DB.
         // This happens for invalid/failed provisioning requests that never created a provider resource.
         if provider_instance_id_opt.as_deref().unwrap_or("").is_empty() {
@@ -265,10 +268,12 @@ pub async fn terminator_terminating_instances(
                         .await
                         .unwrap_or(None)
                         .unwrap_or_else(ProviderManager::current_provider_name);
-                if let Ok(provider) = ProviderManager::get_provider(&provider_code, org_id, pool.clone()).await
+                if let Ok(provider) =
+                    ProviderManager::get_provider(&provider_code, org_id, pool.clone()).await
                 {
                     let _ =
-                        delete_instance_volumes_best_effort(pool, provider.as_ref(), instance_id).await;
+                        delete_instance_volumes_best_effort(pool, provider.as_ref(), instance_id)
+                            .await;
                 }
             }
 
@@ -301,8 +306,9 @@ pub async fn terminator_terminating_instances(
                 "⚠️  [job-terminator] Missing zone for instance {} (provider_instance_id present).",
                 instance_id
             );
-            let _ = sqlx::query("UPDATE instances SET last_reconciliation = NULL, error_code = 'MISSING_ZONE', error_message = 'Missing zone for termination' WHERE id = $1")
+            let _ = sqlx::query("UPDATE instances SET last_reconciliation = NULL, error_code = $2, error_message = 'Missing zone for termination' WHERE id = $1")
                 .bind(instance_id)
+                .bind(ErrorCode::MissingZone.as_str())
                 .execute(pool)
                 .await;
             continue;
@@ -310,9 +316,13 @@ pub async fn terminator_terminating_instances(
 
         let provider_instance_id = provider_instance_id_opt.unwrap_or_default();
         let Some(org_id) = organization_id_opt else {
-            eprintln!("❌ [job-terminator] Instance {} missing organization_id", instance_id);
-            let _ = sqlx::query("UPDATE instances SET last_reconciliation = NULL, error_code = 'MISSING_ORGANIZATION_ID', error_message = 'Instance missing organization_id' WHERE id = $1")
+            eprintln!(
+                "❌ [job-terminator] Instance {} missing organization_id",
+                instance_id
+            );
+            let _ = sqlx::query("UPDATE instances SET last_reconciliation = NULL, error_code = $2, error_message = 'Instance missing organization_id' WHERE id = $1")
                 .bind(instance_id)
+                .bind(ErrorCode::MissingOrganizationId.as_str())
                 .execute(pool)
                 .await;
             continue;
@@ -325,7 +335,9 @@ pub async fn terminator_terminating_instances(
             .unwrap_or(None)
             .unwrap_or_else(ProviderManager::current_provider_name);
 
-        let Ok(provider) = ProviderManager::get_provider(&provider_code, org_id, pool.clone()).await else {
+        let Ok(provider) =
+            ProviderManager::get_provider(&provider_code, org_id, pool.clone()).await
+        else {
             let _ = sqlx::query("UPDATE instances SET last_reconciliation = NULL WHERE id = $1")
                 .bind(instance_id)
                 .execute(pool)
@@ -357,11 +369,12 @@ pub async fn terminator_terminating_instances(
                     let _ = sqlx::query(
                         "UPDATE instances
                          SET last_reconciliation = NULL,
-                             error_code = COALESCE(error_code, 'VOLUMES_DELETE_PENDING'),
+                             error_code = COALESCE(error_code, $2),
                              error_message = COALESCE(error_message, 'Waiting for provider volumes deletion')
                          WHERE id = $1",
                     )
                     .bind(instance_id)
+                    .bind(ErrorCode::VolumesDeletePending.as_str())
                     .execute(pool)
                     .await;
 
@@ -510,11 +523,12 @@ pub async fn terminator_terminating_instances(
                                 let _ = sqlx::query(
                                     "UPDATE instances
                                      SET last_reconciliation = NULL,
-                                         error_code = COALESCE(error_code, 'TERMINATOR_RETRY_FAILED'),
+                                         error_code = COALESCE(error_code, $2),
                                          error_message = COALESCE(error_message, 'Provider returned non-success on terminate')
                                      WHERE id = $1"
                                 )
                                     .bind(instance_id)
+                                    .bind(ErrorCode::TerminatorRetryFailed.as_str())
                                     .execute(pool)
                                     .await;
                             }
@@ -570,12 +584,13 @@ pub async fn terminator_terminating_instances(
                                 let _ = sqlx::query(
                                     "UPDATE instances
                                      SET last_reconciliation = NULL,
-                                         error_code = COALESCE(error_code, 'TERMINATOR_RETRY_FAILED'),
+                                         error_code = COALESCE(error_code, $3),
                                          error_message = COALESCE(error_message, $2)
                                      WHERE id = $1",
                                 )
                                 .bind(instance_id)
                                 .bind(&msg)
+                                .bind(ErrorCode::TerminatorRetryFailed.as_str())
                                 .execute(pool)
                                 .await;
                             }
@@ -700,12 +715,13 @@ pub async fn terminator_terminating_instances(
                 let _ = sqlx::query(
                     "UPDATE instances
                      SET last_reconciliation = NULL,
-                         error_code = COALESCE(error_code, 'TERMINATOR_CHECK_FAILED'),
+                         error_code = COALESCE(error_code, $3),
                          error_message = COALESCE(error_message, $2)
                      WHERE id = $1",
                 )
                 .bind(instance_id)
                 .bind(&msg)
+                .bind(ErrorCode::TerminatorCheckFailed.as_str())
                 .execute(pool)
                 .await;
                 progressed += 1;
@@ -0,0 +1,303 @@
+//! Helpers shared by the orchestrator's unit tests: DB pool setup and a configurable provider.
+
+use inventiv_providers::{inventory, CloudProvider, ProviderError, ProviderErrorCode};
+use sqlx::postgres::PgPoolOptions;
+use sqlx::{Pool, Postgres};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Connects to `DATABASE_URL` and applies migrations; `None` (test skipped) when unset or
+/// unreachable.
+pub async fn setup_pool() -> Option<Pool<Postgres>> {
+    let Some(url) = std::env::var("DATABASE_URL")
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+    else {
+        eprintln!("skipping integration test: DATABASE_URL not set");
+        return None;
+    };
+    let pool = PgPoolOptions::new()
+        .max_connections(5)
+        .connect(&url)
+        .await
+        .ok()?;
+    let _ = sqlx::migrate!("../sqlx-migrations").run(&pool).await;
+    Some(pool)
+}
+
+/// In-memory `CloudProvider` for orchestrator tests. Behaviour is configured through the public
+/// fields (defaults: every call succeeds, nothing is listed); calls worth asserting on are
+/// recorded in `calls`, which stays reachable after the provider is boxed.
+#[derive(Default)]
+pub struct TestProvider {
+    /// IP reported for any server.
+    pub ip: Option<String>,
+    /// Images `check_image_exists` finds; `None` accepts any image.
+    pub known_images: Option<Vec<String>>,
+    /// Types that require diskless boot.
+    pub diskless_types: Vec<String>,
+    /// Images able to boot a diskless type.
+    pub diskless_images: Vec<String>,
+    /// Server names `create_instance_named` rejects as already taken.
+    pub taken_names: Vec<String>,
+    /// The first `n` creates fail with this code.
+    pub create_failures: Option<(ProviderErrorCode, usize)>,
+    /// Types whose data volume is local storage (creation skipped).
+    pub local_storage_types: Vec<String>,
+    /// Types whose data volume must exist before the server.
+    pub pre_create_volume_types: Vec<String>,
+    /// Catalog returned for every zone.
+    pub catalog: Vec<inventory::CatalogItem>,
+    /// Zone whose catalog fetch fails.
+    pub failing_catalog_zone: Option<String>,
+    /// Volumes attached to every server.
+    pub attached_volumes: Vec<inventory::AttachedVolume>,
+    /// Local (`l_ssd`) attached volumes disappear once `remove_local_volumes` was called this many
+    /// times; `None` keeps them forever.
+    pub local_volumes_removed_after: Option<usize>,
+    /// Returned by `list_volumes` (`None`: volume listing unsupported).
+    pub volumes: Option<Vec<inventory::ProviderVolume>>,
+    /// Returned by `get_instance_details` (the trait default composes it otherwise).
+    pub instance_details: Option<inventory::InstanceDetails>,
+    /// Initial size of every Block Storage volume; follows `resize_block_storage` afterwards.
+    pub block_storage_bytes: Option<u64>,
+    pub calls: Arc<TestProviderCalls>,
+}
+
+/// Calls recorded by [`TestProvider`].
+#[derive(Default)]
+pub struct TestProviderCalls {
+    /// Every create attempt (failed ones included).
+    pub creates: Mutex<Vec<CreateCall>>,
+    /// Names passed to `create_instance_named`, in order.
+    pub named: Mutex<Vec<String>>,
+    pub volumes_created: AtomicUsize,
+    pub local_volume_removals: AtomicUsize,
+    /// `(volume_id, delete_on_termination)` per attach.
+    pub attached: Mutex<Vec<(String, bool)>>,
+    pub listed_zones: Mutex<Vec<String>>,
+    pub resized_to: Mutex<Vec<String>>,
+    pub block_storage_resized_to_gb: Mutex<Vec<u64>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CreateCall {
+    pub instance_type: String,
+    pub image_id: String,
+    pub volumes: Option<Vec<String>>,
+}
+
+#[async_trait::async_trait]
+impl CloudProvider for TestProvider {
+    async fn create_instance(
+        &self,
+        _zone: &str,
+        instance_type: &str,
+        image_id: &str,
+        _cloud_init: Option<&str>,
+        volumes: Option<&[String]>,
+    ) -> anyhow::Result<String> {
+        let attempt = {
+            let mut creates = self.calls.creates.lock().unwrap();
+            creates.push(CreateCall {
+                instance_type: instance_type.to_string(),
+                image_id: image_id.to_string(),
+                volumes: volumes.map(<[String]>::to_vec),
+            });
+            creates.len()
+        };
+        if let Some((code, failures)) = self.create_failures {
+            if attempt <= failures {
+                return Err(ProviderError {
+                    code,
+                    message: format!("{} (attempt {})", code.as_str(), attempt),
+                }
+                .into());
+            }
+        }
+        Ok(format!("srv-test-{}", attempt))
+    }
+    async fn create_instance_named(
+        &self,
+        name: &str,
+        zone: &str,
+        instance_type: &str,
+        image_id: &str,
+        cloud_init: Option<&str>,
+        volumes: Option<&[String]>,
+    ) -> anyhow::Result<String> {
+        self.calls.named.lock().unwrap().push(name.to_string());
+        if self.taken_names.iter().any(|n| n == name) {
+            return Err(ProviderError {
+                code: ProviderErrorCode::NameConflict,
+                message: format!("server name {} already exists", name),
+            }
+            .into());
+        }
+        self.create_instance(zone, instance_type, image_id, cloud_init, volumes)
+            .await
+    }
+    async fn start_instance(&self, _zone: &str, _server_id: &str) -> anyhow::Result<bool> {
+        Ok(true)
+    }
+    async fn remove_local_volumes(
+        &self,
+        _zone: &str,
+        _server_id: &str,
+        _instance_type: &str,
+        _pre_created_volume_id: Option<&str>,
+    ) -> anyhow::Result<bool> {
+        self.calls
+            .local_volume_removals
+            .fetch_add(1, Ordering::SeqCst);
+        Ok(true)
+    }
+    async fn terminate_instance(&self, _zone: &str, _server_id: &str) -> anyhow::Result<bool> {
+        Ok(true)
+    }
+    async fn resize_instance(
+        &self,
+        _zone: &str,
+        _server_id: &str,
+        new_instance_type: &str,
+    ) -> anyhow::Result<bool> {
+        self.calls
+            .resized_to
+            .lock()
+            .unwrap()
+            .push(new_instance_type.to_string());
+        Ok(true)
+    }
+    async fn get_instance_ip(
+        &self,
+        _zone: &str,
+        _server_id: &str,
+    ) -> anyhow::Result<Option<String>> {
+        Ok(self.ip.clone())
+    }
+    async fn check_instance_exists(&self, _zone: &str, _server_id: &str) -> anyhow::Result<bool> {
+        Ok(true)
+    }
+    async fn list_attached_volumes(
+        &self,
+        _zone: &str,
+        _server_id: &str,
+    ) -> anyhow::Result<Vec<inventory::AttachedVolume>> {
+        let removals = self.calls.local_volume_removals.load(Ordering::SeqCst);
+        let local_removed = self
+            .local_volumes_removed_after
+            .is_some_and(|after| removals >= after);
+        Ok(self
+            .attached_volumes
+            .iter()
+            .filter(|v| !(local_removed && v.volume_type == "l_ssd"))
+            .cloned()
+            .collect())
+    }
+    async fn get_instance_details(
+        &self,
+        _zone: &str,
+        _server_id: &str,
+    ) -> anyhow::Result<Option<inventory::InstanceDetails>> {
+        Ok(self.instance_details.clone())
+    }
+    async fn fetch_catalog(&self, zone: &str) -> anyhow::Result<Vec<inventory::CatalogItem>> {
+        if self.failing_catalog_zone.as_deref() == Some(zone) {
+            anyhow::bail!("catalog unavailable for {}", zone);
+        }
+        Ok(self.catalog.clone())
+    }
+    async fn list_instances(
+        &self,
+        zone: &str,
+    ) -> anyhow::Result<Vec<inventory::DiscoveredInstance>> {
+        self.calls
+            .listed_zones
+            .lock()
+            .unwrap()
+            .push(zone.to_string());
+        Ok(vec![])
+    }
+    async fn check_image_exists(&self, _zone: &str, image_id: &str) -> anyhow::Result<bool> {
+        Ok(self
+            .known_images
+            .as_ref()
+            .map_or(true, |known| known.iter().any(|i| i == image_id)))
+    }
+    async fn check_diskless_boot_image(&self, _zone: &str, image_id: &str) -> anyhow::Result<bool> {
+        Ok(self.diskless_images.iter().any(|i| i == image_id))
+    }
+    async fn list_volumes(
+        &self,
+        _zone: &str,
+    ) -> anyhow::Result<Option<Vec<inventory::ProviderVolume>>> {
+        Ok(self.volumes.clone())
+    }
+    async fn create_volume(
+        &self,
+        _zone: &str,
+        _name: &str,
+        _size_bytes: i64,
+        _volume_type: &str,
+        _perf_iops: Option<i32>,
+    ) -> anyhow::Result<Option<String>> {
+        let n = self.calls.volumes_created.fetch_add(1, Ordering::SeqCst);
+        Ok(Some(format!("vol-test-{}", n + 1)))
+    }
+    async fn attach_volume(
+        &self,
+        _zone: &str,
+        _server_id: &str,
+        volume_id: &str,
+        delete_on_termination: bool,
+    ) -> anyhow::Result<bool> {
+        self.calls
+            .attached
+            .lock()
+            .unwrap()
+            .push((volume_id.to_string(), delete_on_termination));
+        Ok(true)
+    }
+    async fn resize_block_storage(
+        &self,
+        _zone: &str,
+        _volume_id: &str,
+        new_size_gb: u64,
+    ) -> anyhow::Result<bool> {
+        self.calls
+            .block_storage_resized_to_gb
+            .lock()
+            .unwrap()
+            .push(new_size_gb);
+        Ok(true)
+    }
+    async fn get_block_storage_size(
+        &self,
+        _zone: &str,
+        _volume_id: &str,
+    ) -> anyhow::Result<Option<u64>> {
+        let resized = self.calls.block_storage_resized_to_gb.lock().unwrap();
+        Ok(resized
+            .last()
+            .map(|gb| gb * 1_000_000_000)
+            .or(self.block_storage_bytes))
+    }
+    fn requires_diskless_boot(&self, instance_type: &str) -> bool {
+        self.diskless_types.iter().any(|t| t == instance_type)
+    }
+    fn should_pre_create_data_volume(&self, instance_type: &str) -> bool {
+        self.pre_create_volume_types
+            .iter()
+            .any(|t| t == instance_type)
+    }
+    fn should_skip_data_volume_creation(&self, instance_type: &str) -> bool {
+        self.local_storage_types.iter().any(|t| t == instance_type)
+    }
+    fn get_data_volume_type(&self, instance_type: &str) -> String {
+        if self.should_skip_data_volume_creation(instance_type) {
+            "l_ssd".to_string()
+        } else {
+            "sbs_volume".to_string()
+        }
+    }
+}
@@ -0,0 +1,435 @@
+use chrono::{DateTime, Utc};
+use inventiv_providers::{inventory, CloudProvider};
+use sqlx::{Pool, Postgres};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::provider_manager::ProviderManager;
+
+const DEFAULT_INTERVAL_SECONDS: u64 = 900;
+
+/// Volumes created less than this long ago are ignored (DB row and provider volume are not
+/// written atomically during provisioning).
+const DRIFT_GRACE_SECONDS: i64 = 600;
+
+/// job-volume-drift: compares provider volumes (`list_volumes`) with tracked `instance_volumes`.
+///
+/// Observability only: discrepancies are stored in `provider_volume_drift` and counted in
+/// `provider_volume_drift_checks` (gauges), nothing is deleted. Orphan cleanup stays in
+/// job-volume-reconciliation / job-terminator.
+pub async fn run(pool: Pool<Postgres>) {
+    let secs = std::env::var("VOLUME_DRIFT_CHECK_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_INTERVAL_SECONDS);
+    let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(secs));
+    println!("📦 job-volume-drift started (every {}s)", secs);
+
+    loop {
+        interval.tick().await;
+
+        match check_all_scopes(&pool).await {
+            Ok((untracked, missing)) if untracked + missing > 0 => println!(
+                "📦 job-volume-drift: {} untracked provider volume(s), {} tracked volume(s) missing on provider",
+                untracked, missing
+            ),
+            Ok(_) => {}
+            Err(e) => eprintln!("❌ job-volume-drift error: {:?}", e),
+        }
+    }
+}
+
+/// Volume row we track in `instance_volumes` (not deleted).
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct TrackedVolume {
+    pub id: Uuid,
+    pub instance_id: Uuid,
+    pub provider_volume_id: String,
+    pub provider_volume_name: Option<String>,
+    pub volume_type: String,
+    pub size_bytes: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default)]
+pub struct VolumeDrift<'a> {
+    pub untracked: Vec<&'a inventory::ProviderVolume>,
+    pub missing: Vec<&'a TrackedVolume>,
+}
+
+fn parse_created_at(v: &inventory::ProviderVolume) -> Option<DateTime<Utc>> {
+    v.created_at
+        .as_deref()
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map(|d| d.with_timezone(&Utc))
+}
+
+/// Discrepancies between provider volumes and tracked rows, ignoring volumes younger than the
+/// grace period on either side.
+pub fn compute_volume_drift<'a>(
+    provider_volumes: &'a [inventory::ProviderVolume],
+    tracked: &'a [TrackedVolume],
+    now: DateTime<Utc>,
+) -> VolumeDrift<'a> {
+    let grace = chrono::Duration::seconds(DRIFT_GRACE_SECONDS);
+    let tracked_ids: HashSet<&str> = tracked
+        .iter()
+        .map(|t| t.provider_volume_id.as_str())
+        .collect();
+    let provider_ids: HashSet<&str> = provider_volumes
+        .iter()
+        .map(|v| v.provider_volume_id.as_str())
+        .collect();
+
+    VolumeDrift {
+        untracked: provider_volumes
+            .iter()
+            .filter(|v| !tracked_ids.contains(v.provider_volume_id.as_str()))
+            .filter(|v| parse_created_at(v).is_none_or(|c| now - c >= grace))
+            .collect(),
+        missing: tracked
+            .iter()
+            .filter(|t| !provider_ids.contains(t.provider_volume_id.as_str()))
+            .filter(|t| now - t.created_at >= grace)
+            .collect(),
+    }
+}
+
+async fn check_all_scopes(
+    pool: &Pool<Postgres>,
+) -> Result<(usize, usize), Box<dyn std::error::Error>> {
+    // Scopes = provider + organization (credentials) + zone where we track volumes.
+    let scopes: Vec<(Uuid, String, Uuid, String)> = sqlx::query_as(
+        r#"
+        SELECT DISTINCT iv.provider_id, p.code, i.organization_id, iv.zone_code
+        FROM instance_volumes iv
+        JOIN instances i ON i.id = iv.instance_id
+        JOIN providers p ON p.id = iv.provider_id
+        WHERE i.organization_id IS NOT NULL
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let (mut untracked, mut missing) = (0usize, 0usize);
+    for (provider_id, provider_code, organization_id, zone) in scopes {
+        let provider = match ProviderManager::get_provider(
+            &provider_code,
+            organization_id,
+            pool.clone(),
+        )
+        .await
+        {
+            Ok(p) => p,
+            Err(e) => {
+                eprintln!(
+                    "⚠️ [Volume Drift] Provider {} unavailable for organization {}: {}",
+                    provider_code, organization_id, e
+                );
+                continue;
+            }
+        };
+        match check_scope(pool, provider.as_ref(), provider_id, organization_id, &zone).await {
+            Ok(Some((u, m))) => {
+                untracked += u;
+                missing += m;
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!(
+                "⚠️ [Volume Drift] Check failed for provider={} zone={}: {}",
+                provider_code, zone, e
+            ),
+        }
+    }
+    Ok((untracked, missing))
+}
+
+/// Check one provider/organization/zone scope and persist the drift report + gauges.
+/// Returns None when the provider cannot list volumes.
+pub async fn check_scope(
+    pool: &Pool<Postgres>,
+    provider: &dyn CloudProvider,
+    provider_id: Uuid,
+    organization_id: Uuid,
+    zone: &str,
+) -> anyhow::Result<Option<(usize, usize)>> {
+    let Some(provider_volumes) = provider.list_volumes(zone).await? else {
+        return Ok(None);
+    };
+
+    let tracked: Vec<TrackedVolume> = sqlx::query_as(
+        r#"
+        SELECT iv.id, iv.instance_id, iv.provider_volume_id, iv.provider_volume_name,
+               iv.volume_type, iv.size_bytes, iv.created_at
+        FROM instance_volumes iv
+        JOIN instances i ON i.id = iv.instance_id
+        WHERE iv.provider_id = $1
+          AND i.organization_id = $2
+          AND iv.zone_code = $3
+          AND iv.deleted_at IS NULL
+        "#,
+    )
+    .bind(provider_id)
+    .bind(organization_id)
+    .bind(zone)
+    .fetch_all(pool)
+    .await?;
+
+    let drift = compute_volume_drift(&provider_volumes, &tracked, Utc::now());
+
+    let mut tx = pool.begin().await?;
+    let mut seen_untracked: Vec<String> = Vec::new();
+    for v in &drift.untracked {
+        upsert_drift_row(
+            &mut tx,
+            (provider_id, organization_id, zone),
+            "untracked_provider_volume",
+            DriftRow {
+                provider_volume_id: &v.provider_volume_id,
+                instance_volume_id: None,
+                instance_id: None,
+                volume_name: v.name.as_deref(),
+                volume_type: Some(&v.volume_type),
+                size_bytes: v.size_bytes,
+                volume_created_at: parse_created_at(v),
+            },
+        )
+        .await?;
+        seen_untracked.push(v.provider_volume_id.clone());
+    }
+    let mut seen_missing: Vec<String> = Vec::new();
+    for t in &drift.missing {
+        upsert_drift_row(
+            &mut tx,
+            (provider_id, organization_id, zone),
+            "tracked_missing_on_provider",
+            DriftRow {
+                provider_volume_id: &t.provider_volume_id,
+                instance_volume_id: Some(t.id),
+                instance_id: Some(t.instance_id),
+                volume_name: t.provider_volume_name.as_deref(),
+                volume_type: Some(&t.volume_type),
+                size_bytes: Some(t.size_bytes),
+                volume_created_at: Some(t.created_at),
+            },
+        )
+        .await?;
+        seen_missing.push(t.provider_volume_id.clone());
+    }
+
+    // Resolved discrepancies disappear from the report.
+    sqlx::query(
+        r#"
+        DELETE FROM provider_volume_drift
+        WHERE provider_id = $1 AND organization_id = $2 AND zone_code = $3
+          AND ((kind = 'untracked_provider_volume' AND NOT (provider_volume_id = ANY($4)))
+            OR (kind = 'tracked_missing_on_provider' AND NOT (provider_volume_id = ANY($5))))
+        "#,
+    )
+    .bind(provider_id)
+    .bind(organization_id)
+    .bind(zone)
+    .bind(&seen_untracked)
+    .bind(&seen_missing)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO provider_volume_drift_checks
+            (provider_id, organization_id, zone_code, untracked_provider_volumes, tracked_missing_on_provider, checked_at)
+        VALUES ($1, $2, $3, $4, $5, NOW())
+        ON CONFLICT (provider_id, organization_id, zone_code) DO UPDATE
+        SET untracked_provider_volumes = EXCLUDED.untracked_provider_volumes,
+            tracked_missing_on_provider = EXCLUDED.tracked_missing_on_provider,
+            checked_at = EXCLUDED.checked_at
+        "#,
+    )
+    .bind(provider_id)
+    .bind(organization_id)
+    .bind(zone)
+    .bind(drift.untracked.len() as i32)
+    .bind(drift.missing.len() as i32)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    Ok(Some((drift.untracked.len(), drift.missing.len())))
+}
+
+struct DriftRow<'a> {
+    provider_volume_id: &'a str,
+    instance_volume_id: Option<Uuid>,
+    instance_id: Option<Uuid>,
+    volume_name: Option<&'a str>,
+    volume_type: Option<&'a str>,
+    size_bytes: Option<i64>,
+    volume_created_at: Option<DateTime<Utc>>,
+}
+
+async fn upsert_drift_row(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    (provider_id, organization_id, zone): (Uuid, Uuid, &str),
+    kind: &str,
+    row: DriftRow<'_>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO provider_volume_drift
+            (id, provider_id, organization_id, zone_code, kind, provider_volume_id, instance_volume_id,
+             instance_id, volume_name, volume_type, size_bytes, volume_created_at, first_seen_at, last_seen_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, NOW(), NOW())
+        ON CONFLICT (provider_id, organization_id, zone_code, kind, provider_volume_id) DO UPDATE
+        SET instance_volume_id = EXCLUDED.instance_volume_id,
+            instance_id = EXCLUDED.instance_id,
+            volume_name = EXCLUDED.volume_name,
+            volume_type = EXCLUDED.volume_type,
+            size_bytes = EXCLUDED.size_bytes,
+            volume_created_at = EXCLUDED.volume_created_at,
+            last_seen_at = NOW()
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(provider_id)
+    .bind(organization_id)
+    .bind(zone)
+    .bind(kind)
+    .bind(row.provider_volume_id)
+    .bind(row.instance_volume_id)
+    .bind(row.instance_id)
+    .bind(row.volume_name)
+    .bind(row.volume_type)
+    .bind(row.size_bytes)
+    .bind(row.volume_created_at)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{setup_pool, TestProvider};
+
+    fn provider_volume(id: &str, created_at: Option<&str>) -> inventory::ProviderVolume {
+        inventory::ProviderVolume {
+            provider_volume_id: id.to_string(),
+            name: Some(format!("name-{}", id)),
+            volume_type: "sbs_5k".to_string(),
+            size_bytes: Some(200_000_000_000),
+            created_at: created_at.map(|s| s.to_string()),
+        }
+    }
+
+    fn tracked_volume(id: &str, created_at: DateTime<Utc>) -> TrackedVolume {
+        TrackedVolume {
+            id: Uuid::new_v4(),
+            instance_id: Uuid::new_v4(),
+            provider_volume_id: id.to_string(),
+            provider_volume_name: None,
+            volume_type: "sbs_volume".to_string(),
+            size_bytes: 200_000_000_000,
+            created_at,
+        }
+    }
+
+    #[test]
+    fn drift_reports_both_sides_and_respects_grace_period() {
+        let now: DateTime<Utc> = "2026-01-09T12:00:00Z".parse().unwrap();
+        let old = now - chrono::Duration::hours(2);
+        let provider = vec![
+            provider_volume("vol-shared", Some("2026-01-09T08:00:00Z")),
+            provider_volume("vol-leaked", Some("2026-01-09T08:00:00Z")),
+            provider_volume("vol-no-date", None),
+            provider_volume("vol-just-created", Some("2026-01-09T11:59:00Z")),
+        ];
+        let tracked = vec![
+            tracked_volume("vol-shared", old),
+            tracked_volume("vol-gone", old),
+            tracked_volume("vol-row-just-inserted", now),
+        ];
+
+        let drift = compute_volume_drift(&provider, &tracked, now);
+        let untracked: Vec<&str> = drift
+            .untracked
+            .iter()
+            .map(|v| v.provider_volume_id.as_str())
+            .collect();
+        let missing: Vec<&str> = drift
+            .missing
+            .iter()
+            .map(|t| t.provider_volume_id.as_str())
+            .collect();
+        assert_eq!(untracked, vec!["vol-leaked", "vol-no-date"]);
+        assert_eq!(missing, vec!["vol-gone"]);
+    }
+
+    #[tokio::test]
+    async fn untracked_provider_volume_appears_in_drift_report() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(provider_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers WHERE code = 'mock'")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: mock provider not seeded");
+            return;
+        };
+
+        // Fresh scope so the check does not see other tracked volumes.
+        let organization_id = Uuid::new_v4();
+        let zone = format!("drift-{}", &Uuid::new_v4().simple().to_string()[..8]);
+        let leaked = format!("vol-{}", Uuid::new_v4());
+        let provider = TestProvider {
+            volumes: Some(vec![provider_volume(&leaked, Some("2026-01-01T00:00:00Z"))]),
+            ..Default::default()
+        };
+
+        let counts = check_scope(&pool, &provider, provider_id, organization_id, &zone)
+            .await
+            .unwrap();
+        let report: Vec<(String, String, Option<i64>)> = sqlx::query_as(
+            "SELECT kind, provider_volume_id, size_bytes FROM provider_volume_drift
+             WHERE provider_id = $1 AND organization_id = $2 AND zone_code = $3",
+        )
+        .bind(provider_id)
+        .bind(organization_id)
+        .bind(&zone)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        let gauges: Option<(i32, i32)> = sqlx::query_as(
+            "SELECT untracked_provider_volumes, tracked_missing_on_provider
+             FROM provider_volume_drift_checks
+             WHERE provider_id = $1 AND organization_id = $2 AND zone_code = $3",
+        )
+        .bind(provider_id)
+        .bind(organization_id)
+        .bind(&zone)
+        .fetch_optional(&pool)
+        .await
+        .unwrap();
+
+        for table in ["provider_volume_drift", "provider_volume_drift_checks"] {
+            let _ = sqlx::query(&format!("DELETE FROM {} WHERE organization_id = $1", table))
+                .bind(organization_id)
+                .execute(&pool)
+                .await;
+        }
+
+        assert_eq!(counts, Some((1, 0)));
+        assert_eq!(
+            report,
+            vec![(
+                "untracked_provider_volume".to_string(),
+                leaked,
+                Some(200_000_000_000)
+            )]
+        );
+        assert_eq!(gauges, Some((1, 0)));
+    }
+}
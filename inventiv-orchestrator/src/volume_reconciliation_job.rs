@@ -67,12 +67,21 @@ async fn reconcile_volumes(pool: &Pool<Postgres>) -> Result<usize, Box<dyn std::
     .fetch_all(pool)
     .await?;
 
-    for (row_id, provider_volume_id, zone_code, instance_id_str, provider_id_opt, organization_id_opt) in
-        deleted_but_existing
+    for (
+        row_id,
+        provider_volume_id,
+        zone_code,
+        instance_id_str,
+        provider_id_opt,
+        organization_id_opt,
+    ) in deleted_but_existing
     {
         if let Some(provider_id) = provider_id_opt {
             let Some(org_id) = organization_id_opt else {
-                eprintln!("❌ [Volume Reconciliation] Instance {} missing organization_id", instance_id_str);
+                eprintln!(
+                    "❌ [Volume Reconciliation] Instance {} missing organization_id",
+                    instance_id_str
+                );
                 continue;
             };
 
@@ -83,7 +92,8 @@ async fn reconcile_volumes(pool: &Pool<Postgres>) -> Result<usize, Box<dyn std::
                     .await?
                     .unwrap_or_else(ProviderManager::current_provider_name);
 
-            if let Ok(provider) = ProviderManager::get_provider(&provider_code, org_id, pool.clone()).await
+            if let Ok(provider) =
+                ProviderManager::get_provider(&provider_code, org_id, pool.clone()).await
             {
                 // Check if volume still exists at provider
                 match provider
@@ -244,12 +254,21 @@ async fn reconcile_volumes(pool: &Pool<Postgres>) -> Result<usize, Box<dyn std::
     .fetch_all(pool)
     .await?;
 
-    for (row_id, provider_volume_id, zone_code, instance_id_str, provider_id_opt, organization_id_opt) in
-        failed_deletions
+    for (
+        row_id,
+        provider_volume_id,
+        zone_code,
+        instance_id_str,
+        provider_id_opt,
+        organization_id_opt,
+    ) in failed_deletions
     {
         if let Some(provider_id) = provider_id_opt {
             let Some(org_id) = organization_id_opt else {
-                eprintln!("❌ [Volume Reconciliation] Instance {} missing organization_id", instance_id_str);
+                eprintln!(
+                    "❌ [Volume Reconciliation] Instance {} missing organization_id",
+                    instance_id_str
+                );
                 continue;
             };
 
@@ -260,7 +279,8 @@ async fn reconcile_volumes(pool: &Pool<Postgres>) -> Result<usize, Box<dyn std::
                     .await?
                     .unwrap_or_else(ProviderManager::current_provider_name);
 
-            if let Ok(provider) = ProviderManager::get_provider(&provider_code, org_id, pool.clone()).await
+            if let Ok(provider) =
+                ProviderManager::get_provider(&provider_code, org_id, pool.clone()).await
             {
                 // Check if volume still exists
                 match provider
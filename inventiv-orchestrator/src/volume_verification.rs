@@ -0,0 +1,243 @@
+//! Post-attach verification of data volumes.
+//!
+//! A provider reporting a volume as attached does not mean the guest sees it: if the block device
+//! never shows up, boot carries on and the model download fails much later with an unrelated
+//! "no space left on device". Once SSH is reachable we poll the instance for each attached data
+//! volume and fail the boot with `VOLUME_ATTACH_UNVERIFIED` when it does not appear in time.
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use inventiv_common::error_code::ErrorCode;
+use inventiv_common::net;
+use sqlx::{Pool, Postgres};
+use tokio::process::Command;
+use uuid::Uuid;
+
+pub const ERROR_CODE: &str = ErrorCode::VolumeAttachUnverified.as_str();
+
+const DEFAULT_TIMEOUT_S: u64 = 120;
+const DEFAULT_INTERVAL_S: u64 = 10;
+
+/// A data volume the control plane believes is attached to the instance.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ExpectedVolume {
+    pub provider_volume_id: String,
+    pub size_bytes: i64,
+}
+
+/// Looks for a volume's block device on the instance itself (provider-neutral).
+#[async_trait]
+pub trait BlockDeviceProbe: Send + Sync {
+    async fn is_visible(&self, volume: &ExpectedVolume) -> anyhow::Result<bool>;
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verification {
+    Verified {
+        attempts: u32,
+    },
+    Unverified {
+        attempts: u32,
+        last_error: Option<String>,
+    },
+}
+
+/// Polling window from `VOLUME_ATTACH_VERIFY_TIMEOUT_S` / `VOLUME_ATTACH_VERIFY_INTERVAL_S`.
+/// A timeout of 0 disables verification (returns `None`).
+pub fn verify_window() -> Option<(u32, Duration)> {
+    let read = |key: &str, default: u64| {
+        std::env::var(key)
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(default)
+    };
+    let timeout_s = read("VOLUME_ATTACH_VERIFY_TIMEOUT_S", DEFAULT_TIMEOUT_S);
+    if timeout_s == 0 {
+        return None;
+    }
+    let interval_s = read("VOLUME_ATTACH_VERIFY_INTERVAL_S", DEFAULT_INTERVAL_S).max(1);
+    let attempts = (timeout_s / interval_s).max(1) as u32;
+    Some((attempts, Duration::from_secs(interval_s)))
+}
+
+/// Non-boot volumes recorded as attached to this instance.
+pub async fn attached_data_volumes(db: &Pool<Postgres>, instance_id: Uuid) -> Vec<ExpectedVolume> {
+    sqlx::query_as(
+        "SELECT provider_volume_id, size_bytes
+         FROM instance_volumes
+         WHERE instance_id = $1
+           AND status = 'attached'
+           AND is_boot = false
+           AND deleted_at IS NULL
+         ORDER BY created_at",
+    )
+    .bind(instance_id)
+    .fetch_all(db)
+    .await
+    .unwrap_or_default()
+}
+
+/// Poll `probe` until the volume is visible, at most `attempts` times.
+pub async fn verify_attached(
+    probe: &dyn BlockDeviceProbe,
+    volume: &ExpectedVolume,
+    attempts: u32,
+    interval: Duration,
+) -> Verification {
+    let mut last_error = None;
+    for attempt in 1..=attempts {
+        match probe.is_visible(volume).await {
+            Ok(true) => return Verification::Verified { attempts: attempt },
+            Ok(false) => last_error = None,
+            Err(e) => last_error = Some(e.to_string()),
+        }
+        if attempt < attempts {
+            tokio::time::sleep(interval).await;
+        }
+    }
+    Verification::Unverified {
+        attempts,
+        last_error,
+    }
+}
+
+/// Checks `/dev/disk/by-id` and block device serials for the volume id over SSH, falling back to
+/// an exact size match for providers that expose neither.
+pub struct SshBlockDeviceProbe {
+    target: String,
+    ssh_key_path: String,
+}
+
+impl SshBlockDeviceProbe {
+    pub fn new(ip: &str) -> Option<Self> {
+        let clean_ip = net::normalize_instance_ip(ip)?;
+        let ssh_user = std::env::var("SSH_USER").unwrap_or_else(|_| "root".to_string());
+        Some(Self {
+            target: format!("{}@{}", ssh_user, clean_ip),
+            ssh_key_path: std::env::var("SSH_KEY_PATH")
+                .unwrap_or_else(|_| "/app/.ssh/llm-studio-key".to_string()),
+        })
+    }
+}
+
+fn probe_script(volume: &ExpectedVolume) -> anyhow::Result<String> {
+    let id = &volume.provider_volume_id;
+    if id.is_empty()
+        || !id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(anyhow::anyhow!("unexpected volume id format: {:?}", id));
+    }
+    Ok(format!(
+        r#"
+ls -1 /dev/disk/by-id 2>/dev/null | grep -qi -- '{id}' && {{ echo present; exit 0; }}
+lsblk -dn -o SERIAL 2>/dev/null | grep -qi -- '{id}' && {{ echo present; exit 0; }}
+lsblk -bdn -o SIZE,TYPE 2>/dev/null | awk -v s='{size}' '$2 == "disk" && $1 == s {{ f = 1 }} END {{ exit !f }}' && {{ echo present; exit 0; }}
+echo absent
+"#,
+        id = id,
+        size = volume.size_bytes,
+    ))
+}
+
+#[async_trait]
+impl BlockDeviceProbe for SshBlockDeviceProbe {
+    async fn is_visible(&self, volume: &ExpectedVolume) -> anyhow::Result<bool> {
+        use tokio::io::AsyncWriteExt;
+
+        let script = probe_script(volume)?;
+        let mut child = Command::new("ssh")
+            .arg("-i")
+            .arg(&self.ssh_key_path)
+            .arg("-o")
+            .arg("StrictHostKeyChecking=no")
+            .arg("-o")
+            .arg("UserKnownHostsFile=/dev/null")
+            .arg("-o")
+            .arg("ConnectTimeout=5")
+            .arg(&self.target)
+            .arg("bash -s")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(script.as_bytes()).await?;
+        }
+        let output = tokio::time::timeout(Duration::from_secs(20), child.wait_with_output())
+            .await
+            .map_err(|_| anyhow::anyhow!("ssh probe timed out"))??;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!("ssh probe exited with {}", output.status));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim() == "present")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Reports the volume present from the `visible_from`-th poll on.
+    struct MockProbe {
+        polls: AtomicU32,
+        visible_from: u32,
+    }
+
+    #[async_trait]
+    impl BlockDeviceProbe for MockProbe {
+        async fn is_visible(&self, _volume: &ExpectedVolume) -> anyhow::Result<bool> {
+            let poll = self.polls.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(poll >= self.visible_from)
+        }
+    }
+
+    fn volume() -> ExpectedVolume {
+        ExpectedVolume {
+            provider_volume_id: "11111111-2222-3333-4444-555555555555".to_string(),
+            size_bytes: 200_000_000_000,
+        }
+    }
+
+    #[tokio::test]
+    async fn volume_present_on_second_poll_is_verified() {
+        let probe = MockProbe {
+            polls: AtomicU32::new(0),
+            visible_from: 2,
+        };
+        let outcome = verify_attached(&probe, &volume(), 5, Duration::ZERO).await;
+        assert_eq!(outcome, Verification::Verified { attempts: 2 });
+        assert_eq!(probe.polls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn volume_never_present_is_unverified_after_bounded_polls() {
+        let probe = MockProbe {
+            polls: AtomicU32::new(0),
+            visible_from: u32::MAX,
+        };
+        let outcome = verify_attached(&probe, &volume(), 3, Duration::ZERO).await;
+        assert_eq!(
+            outcome,
+            Verification::Unverified {
+                attempts: 3,
+                last_error: None
+            }
+        );
+        assert_eq!(probe.polls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn probe_script_rejects_unsafe_volume_ids() {
+        let mut v = volume();
+        v.provider_volume_id = "x'; rm -rf /".to_string();
+        assert!(probe_script(&v).is_err());
+        assert!(probe_script(&volume())
+            .unwrap()
+            .contains(&volume().provider_volume_id));
+    }
+}
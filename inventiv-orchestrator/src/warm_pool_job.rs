@@ -0,0 +1,327 @@
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+use crate::logger;
+use crate::services;
+
+const DEFAULT_INTERVAL_SECONDS: u64 = 60;
+
+/// A warm-pool instance whose provisioning failed less than this long ago pauses the model's
+/// warm pool (provider quota / capacity errors would otherwise create a new instance every tick).
+const DEFAULT_FAILURE_BACKOFF_SECONDS: i64 = 600;
+
+/// Statuses counted as live capacity for a model (queued, in flight or serving).
+const LIVE_STATUSES: &[&str] = &[
+    "accepted",
+    "provisioning",
+    "booting",
+    "installing",
+    "starting",
+    "ready",
+    "unavailable",
+];
+
+/// job-warm-pool: keeps `models.min_instances` live instances for every active model.
+///
+/// Missing instances are created on the cheapest compatible instance type / zone, never past
+/// `models.max_instances`, owned by the platform organization (`WARM_POOL_ORGANIZATION_SLUG`,
+/// default `inventiv-it`) and provisioned like any other instance (job-provisioning re-queues
+/// them if the orchestrator restarts mid-way).
+pub async fn run(pool: Pool<Postgres>, redis_client: redis::Client) {
+    let secs = std::env::var("WARM_POOL_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_INTERVAL_SECONDS);
+    let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(secs));
+    println!("🔥 job-warm-pool started (every {}s)", secs);
+
+    loop {
+        interval.tick().await;
+
+        match reconcile_all(&pool).await {
+            Ok(created) => {
+                for p in created {
+                    let db = pool.clone();
+                    let redis = redis_client.clone();
+                    tokio::spawn(async move {
+                        services::process_provisioning(
+                            db,
+                            redis,
+                            p.instance_id.to_string(),
+                            p.zone,
+                            p.instance_type,
+                            Some(format!("warm-pool-{}", p.instance_id)),
+                        )
+                        .await;
+                    });
+                }
+            }
+            Err(e) => eprintln!("❌ [job-warm-pool] Error: {:?}", e),
+        }
+    }
+}
+
+/// An instance row created by the warm pool, ready to be provisioned.
+#[derive(Debug)]
+pub struct WarmPoolProvision {
+    pub instance_id: Uuid,
+    pub zone: String,
+    pub instance_type: String,
+}
+
+async fn reconcile_all(
+    pool: &Pool<Postgres>,
+) -> Result<Vec<WarmPoolProvision>, Box<dyn std::error::Error>> {
+    let model_ids: Vec<Uuid> =
+        sqlx::query_scalar("SELECT id FROM models WHERE is_active = true AND min_instances > 0")
+            .fetch_all(pool)
+            .await?;
+    if model_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let slug = std::env::var("WARM_POOL_ORGANIZATION_SLUG")
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or_else(|| "inventiv-it".to_string());
+    let organization_id: Option<Uuid> =
+        sqlx::query_scalar("SELECT id FROM organizations WHERE slug = $1")
+            .bind(slug.trim())
+            .fetch_optional(pool)
+            .await?;
+    let Some(organization_id) = organization_id else {
+        eprintln!(
+            "❌ [job-warm-pool] Organization '{}' not found; warm pools are not maintained",
+            slug
+        );
+        return Ok(Vec::new());
+    };
+
+    let mut created = Vec::new();
+    for model_id in model_ids {
+        match ensure_warm_pool(pool, model_id, organization_id).await {
+            Ok(mut c) => created.append(&mut c),
+            Err(e) => eprintln!("❌ [job-warm-pool] model {}: {:?}", model_id, e),
+        }
+    }
+    Ok(created)
+}
+
+/// Create the instance rows needed to bring `model_id` up to `min_instances` live instances.
+/// The model row is locked for the duration, so concurrent orchestrators cannot both scale it.
+pub async fn ensure_warm_pool(
+    pool: &Pool<Postgres>,
+    model_id: Uuid,
+    organization_id: Uuid,
+) -> Result<Vec<WarmPoolProvision>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let bounds: Option<(i32, Option<i32>)> = sqlx::query_as(
+        "SELECT min_instances, max_instances FROM models
+         WHERE id = $1 AND is_active = true
+         FOR UPDATE",
+    )
+    .bind(model_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some((min_instances, max_instances)) = bounds else {
+        return Ok(Vec::new());
+    };
+
+    let live: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM instances
+         WHERE model_id = $1 AND status::text = ANY($2) AND is_archived = false",
+    )
+    .bind(model_id)
+    .bind(LIVE_STATUSES)
+    .fetch_one(&mut *tx)
+    .await?;
+    let ceiling = max_instances.map_or(min_instances, |max| min_instances.min(max)) as i64;
+    let missing = ceiling - live;
+    if missing <= 0 {
+        return Ok(Vec::new());
+    }
+
+    let backoff = std::env::var("WARM_POOL_FAILURE_BACKOFF_SECONDS")
+        .ok()
+        .and_then(|s| s.trim().parse::<i64>().ok())
+        .filter(|v| *v >= 0)
+        .unwrap_or(DEFAULT_FAILURE_BACKOFF_SECONDS);
+    let recently_failed: bool = sqlx::query_scalar(
+        "SELECT EXISTS (
+           SELECT 1 FROM instances
+           WHERE model_id = $1
+             AND status IN ('provisioning_failed', 'startup_failed', 'failed')
+             AND failed_at > NOW() - make_interval(secs => $2)
+         )",
+    )
+    .bind(model_id)
+    .bind(backoff as f64)
+    .fetch_one(&mut *tx)
+    .await?;
+    if recently_failed {
+        eprintln!(
+            "⏸️ [job-warm-pool] model {} is {} instance(s) short but an instance failed in the last {}s; backing off",
+            model_id, missing, backoff
+        );
+        return Ok(Vec::new());
+    }
+
+    // Cheapest active instance type that fits the model, in an available zone.
+    let target: Option<(Uuid, Uuid, Uuid, String, String)> = sqlx::query_as(
+        r#"
+        SELECT it.provider_id, z.id, it.id, z.code, it.code
+        FROM instance_types it
+        JOIN providers p ON p.id = it.provider_id AND p.is_active = true
+        JOIN instance_type_zones itz ON itz.instance_type_id = it.id AND itz.is_available = true
+        JOIN zones z ON z.id = itz.zone_id AND z.is_active = true
+        WHERE it.is_active = true
+          AND check_model_instance_compatibility($1, it.id)
+        ORDER BY it.cost_per_hour ASC NULLS LAST, it.code, z.code
+        LIMIT 1
+        "#,
+    )
+    .bind(model_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some((provider_id, zone_id, instance_type_id, zone, instance_type)) = target else {
+        eprintln!(
+            "⚠️ [job-warm-pool] model {} needs {} instance(s) but no compatible instance type is available",
+            model_id, missing
+        );
+        return Ok(Vec::new());
+    };
+
+    let mut created = Vec::with_capacity(missing as usize);
+    for _ in 0..missing {
+        let instance_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO instances (id, provider_id, zone_id, instance_type_id, model_id, organization_id, status, created_at, gpu_profile)
+             VALUES ($1, $2, $3, $4, $5, $6, 'provisioning', NOW(), '{}')",
+        )
+        .bind(instance_id)
+        .bind(provider_id)
+        .bind(zone_id)
+        .bind(instance_type_id)
+        .bind(model_id)
+        .bind(organization_id)
+        .execute(&mut *tx)
+        .await?;
+        created.push(WarmPoolProvision {
+            instance_id,
+            zone: zone.clone(),
+            instance_type: instance_type.clone(),
+        });
+    }
+    tx.commit().await?;
+
+    println!(
+        "🔥 [job-warm-pool] model {}: {} live, min {}, max {:?} -> provisioning {} on {} ({})",
+        model_id,
+        live,
+        min_instances,
+        max_instances,
+        created.len(),
+        instance_type,
+        zone
+    );
+    for p in &created {
+        logger::log_event_with_metadata(
+            pool,
+            "WARM_POOL_SCALE",
+            "success",
+            p.instance_id,
+            None,
+            Some(serde_json::json!({
+                "model_id": model_id,
+                "live_instances": live,
+                "min_instances": min_instances,
+                "max_instances": max_instances,
+                "zone": p.zone,
+                "instance_type": p.instance_type,
+            })),
+        )
+        .await
+        .ok();
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::setup_pool;
+
+    #[tokio::test]
+    async fn model_with_min_one_and_no_instances_provisions_once() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(organization_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM organizations LIMIT 1")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: no organization seeded");
+            return;
+        };
+
+        let model_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO models (id, name, model_id, required_vram_gb, context_length, is_active, min_instances, metadata, created_at, updated_at)
+             VALUES ($1, $2, $2, 1, 4096, true, 1, '{}'::jsonb, NOW(), NOW())",
+        )
+        .bind(model_id)
+        .bind(format!("test-org/warm-pool-{}", model_id))
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let first = ensure_warm_pool(&pool, model_id, organization_id)
+            .await
+            .unwrap();
+        // The provisioning instance counts as live capacity: no second provision.
+        let second = ensure_warm_pool(&pool, model_id, organization_id)
+            .await
+            .unwrap();
+
+        let rows: Vec<(Uuid, String, Option<Uuid>)> = sqlx::query_as(
+            "SELECT id, status::text, organization_id FROM instances WHERE model_id = $1",
+        )
+        .bind(model_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        let logged: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM action_logs WHERE instance_id = ANY($1) AND action_type = 'WARM_POOL_SCALE'",
+        )
+        .bind(rows.iter().map(|r| r.0).collect::<Vec<_>>())
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let ids: Vec<Uuid> = rows.iter().map(|r| r.0).collect();
+        let _ = sqlx::query("DELETE FROM action_logs WHERE instance_id = ANY($1)")
+            .bind(&ids)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instances WHERE id = ANY($1)")
+            .bind(&ids)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM models WHERE id = $1")
+            .bind(model_id)
+            .execute(&pool)
+            .await;
+
+        assert_eq!(first.len(), 1);
+        assert!(second.is_empty());
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].0, first[0].instance_id);
+        assert_eq!(rows[0].1, "provisioning");
+        assert_eq!(rows[0].2, Some(organization_id));
+        assert_eq!(logged, 1);
+    }
+}
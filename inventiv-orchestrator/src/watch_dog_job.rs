@@ -1,8 +1,10 @@
+use redis::AsyncCommands;
 use sqlx::{Pool, Postgres};
 use uuid::Uuid;
 
 use crate::finops_events;
 use crate::health_check_flow;
+use crate::logger;
 use crate::provider_manager::ProviderManager;
 use crate::state_machine;
 
@@ -20,7 +22,172 @@ pub async fn run(pool: Pool<Postgres>, redis_client: redis::Client) {
             Ok(_) => {}
             Err(e) => eprintln!("❌ job-watch-dog error: {:?}", e),
         }
+
+        match enforce_max_runtime(&pool, &redis_client).await {
+            Ok(count) if count > 0 => {
+                println!("🐶 job-watch-dog: {} instance(s) over max runtime", count)
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("❌ job-watch-dog max-runtime error: {:?}", e),
+        }
+
+        match enforce_auto_terminate(&pool, &redis_client).await {
+            Ok(count) if count > 0 => {
+                println!("🐶 job-watch-dog: {} instance(s) past their TTL", count)
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("❌ job-watch-dog ttl error: {:?}", e),
+        }
+    }
+}
+
+/// Terminate active instances whose deployment TTL (`auto_terminate_at`) has passed.
+/// The instance is moved to `terminating` (so job-terminator retries if the event is lost) and
+/// `CMD:TERMINATE` is published on `orchestrator_events`, exactly like a user-initiated terminate.
+pub async fn enforce_auto_terminate(
+    pool: &Pool<Postgres>,
+    redis_client: &redis::Client,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    // Claim + flip to terminating in one statement so each instance is handled exactly once.
+    let claimed: Vec<(Uuid, String, i64)> = sqlx::query_as(
+        "WITH cte AS (
+            SELECT i.id, i.status::text AS previous_status
+            FROM instances i
+            WHERE i.auto_terminate_at IS NOT NULL
+              AND i.auto_terminate_at <= NOW()
+              AND i.status NOT IN ('terminating', 'terminated', 'archived', 'provisioning_failed', 'startup_failed', 'failed')
+            ORDER BY i.auto_terminate_at
+            LIMIT 50
+            FOR UPDATE SKIP LOCKED
+        )
+        UPDATE instances i
+        SET status = 'terminating',
+            last_reconciliation = NULL,
+            deletion_reason = COALESCE(i.deletion_reason, 'ttl_expired')
+        FROM cte
+        WHERE i.id = cte.id
+        RETURNING i.id,
+                  cte.previous_status,
+                  EXTRACT(EPOCH FROM (NOW() - i.auto_terminate_at))::bigint",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for (instance_id, previous_status, overdue_seconds) in &claimed {
+        let event = serde_json::json!({
+            "type": "CMD:TERMINATE",
+            "instance_id": instance_id.to_string(),
+        })
+        .to_string();
+        let published = match redis_client.get_multiplexed_async_connection().await {
+            Ok(mut conn) => conn
+                .publish::<_, _, ()>("orchestrator_events", &event)
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = &published {
+            eprintln!(
+                "⚠️ job-watch-dog: failed to publish CMD:TERMINATE for {} (job-terminator will retry): {}",
+                instance_id, e
+            );
+        }
+
+        let _ = logger::log_event_with_metadata(
+            pool,
+            "INSTANCE_TTL_EXPIRED",
+            "success",
+            *instance_id,
+            None,
+            Some(serde_json::json!({
+                "previous_status": previous_status,
+                "overdue_seconds": overdue_seconds,
+                "redis_published": published.is_ok(),
+            })),
+        )
+        .await;
     }
+
+    Ok(claimed.len())
+}
+
+/// Alert (once) on active instances running longer than `max_runtime_hours`.
+/// When `auto_terminate_on_max_runtime` is set, the instance is also moved to `terminating`
+/// and picked up by job-terminator.
+pub async fn enforce_max_runtime(
+    pool: &Pool<Postgres>,
+    redis_client: &redis::Client,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    // Claim + mark alerted in one statement so each instance alerts exactly once.
+    let claimed: Vec<(Uuid, Uuid, i32, bool, i64, String)> = sqlx::query_as(
+        "WITH cte AS (
+            SELECT i.id
+            FROM instances i
+            WHERE i.max_runtime_hours IS NOT NULL
+              AND i.max_runtime_alerted_at IS NULL
+              AND i.status NOT IN ('terminating', 'terminated', 'archived', 'provisioning_failed', 'startup_failed', 'failed')
+              AND i.created_at < NOW() - make_interval(hours => i.max_runtime_hours)
+            ORDER BY i.created_at
+            LIMIT 50
+            FOR UPDATE SKIP LOCKED
+        )
+        UPDATE instances i
+        SET max_runtime_alerted_at = NOW()
+        FROM cte
+        WHERE i.id = cte.id
+        RETURNING i.id,
+                  i.provider_id,
+                  i.max_runtime_hours,
+                  i.auto_terminate_on_max_runtime,
+                  EXTRACT(EPOCH FROM (NOW() - i.created_at))::bigint,
+                  i.status::text",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for (instance_id, provider_id, max_hours, auto_terminate, runtime_seconds, status) in &claimed {
+        let _ = logger::log_event_with_metadata(
+            pool,
+            "INSTANCE_MAX_RUNTIME_EXCEEDED",
+            "success",
+            *instance_id,
+            None,
+            Some(serde_json::json!({
+                "max_runtime_hours": max_hours,
+                "runtime_seconds": runtime_seconds,
+                "status": status,
+                "auto_terminate": auto_terminate,
+            })),
+        )
+        .await;
+
+        let _ = finops_events::emit_instance_runtime_alert(
+            redis_client,
+            *instance_id,
+            *provider_id,
+            *max_hours,
+            *runtime_seconds,
+            *auto_terminate,
+            "inventiv-orchestrator/watch_dog_job",
+        )
+        .await;
+
+        if *auto_terminate {
+            sqlx::query(
+                "UPDATE instances
+                 SET status = 'terminating',
+                     last_reconciliation = NULL,
+                     deletion_reason = COALESCE(deletion_reason, 'max_runtime_exceeded')
+                 WHERE id = $1
+                   AND status NOT IN ('terminating', 'terminated', 'archived')",
+            )
+            .bind(instance_id)
+            .execute(pool)
+            .await?;
+        }
+    }
+
+    Ok(claimed.len())
 }
 
 pub async fn watchdog_ready_instances(
@@ -86,11 +253,12 @@ pub async fn watchdog_ready_instances(
             continue;
         };
 
+        // Single provider round-trip: existence + attached volumes.
         match provider
-            .check_instance_exists(&zone, &provider_instance_id)
+            .get_instance_details(&zone, &provider_instance_id)
             .await
         {
-            Ok(false) => {
+            Ok(None) => {
                 let changed = state_machine::mark_provider_deleted(
                     pool,
                     instance_id,
@@ -111,7 +279,7 @@ pub async fn watchdog_ready_instances(
                 }
                 orphaned_count += 1;
             }
-            Ok(true) => {
+            Ok(Some(details)) => {
                 // If we don't have volume metadata for this instance yet, introspect provider-attached volumes
                 // and persist them into instance_volumes so UI can display Storage (and terminator can cleanup).
                 let has_any_volumes: bool = sqlx::query_scalar(
@@ -122,15 +290,11 @@ pub async fn watchdog_ready_instances(
                 .await
                 .unwrap_or(false);
                 if !has_any_volumes {
-                    if let Ok(attached) = provider
-                        .list_attached_volumes(&zone, &provider_instance_id)
-                        .await
-                    {
-                        for av in attached {
-                            if av.volume_type != "sbs_volume" {
-                                continue;
-                            }
-                            let exists: bool = sqlx::query_scalar(
+                    for av in details.volumes {
+                        if av.volume_type != "sbs_volume" {
+                            continue;
+                        }
+                        let exists: bool = sqlx::query_scalar(
                                 "SELECT EXISTS(SELECT 1 FROM instance_volumes WHERE instance_id=$1 AND provider_volume_id=$2)",
                             )
                             .bind(instance_id)
@@ -138,12 +302,10 @@ pub async fn watchdog_ready_instances(
                             .fetch_one(pool)
                             .await
                             .unwrap_or(false);
-                            if exists {
-                                // Update missing metadata (boot volume size/name can be absent on first insert).
-                                if av.size_bytes.unwrap_or(0) > 0
-                                    || av.provider_volume_name.is_some()
-                                {
-                                    let _ = sqlx::query(
+                        if exists {
+                            // Update missing metadata (boot volume size/name can be absent on first insert).
+                            if av.size_bytes.unwrap_or(0) > 0 || av.provider_volume_name.is_some() {
+                                let _ = sqlx::query(
                                         r#"
                                         UPDATE instance_volumes
                                         SET
@@ -164,11 +326,11 @@ pub async fn watchdog_ready_instances(
                                     .bind(av.boot)
                                     .execute(pool)
                                     .await;
-                                }
-                                continue;
                             }
-                            let row_id = Uuid::new_v4();
-                            let _ = sqlx::query(
+                            continue;
+                        }
+                        let row_id = Uuid::new_v4();
+                        let _ = sqlx::query(
                                 "INSERT INTO instance_volumes (id, instance_id, provider_id, zone_code, provider_volume_id, provider_volume_name, volume_type, size_bytes, perf_iops, delete_on_terminate, status, attached_at, is_boot)
                                  VALUES ($1,$2,$3,$4,$5,$6,$7,$8,NULL,TRUE,'attached',NOW(),$9)",
                             )
@@ -183,7 +345,6 @@ pub async fn watchdog_ready_instances(
                             .bind(av.boot)
                             .execute(pool)
                             .await;
-                        }
                     }
                 }
 
@@ -232,3 +393,166 @@ pub async fn watchdog_ready_instances(
 
     Ok(orphaned_count)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::setup_pool;
+
+    #[tokio::test]
+    async fn enforce_max_runtime_alerts_once_and_auto_terminates() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(provider_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers LIMIT 1")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: no provider seeded");
+            return;
+        };
+        // Redis is only used for best-effort event publishing.
+        let redis_client = redis::Client::open("redis://127.0.0.1:6379/").unwrap();
+
+        let alert_only = Uuid::new_v4();
+        let auto_terminate = Uuid::new_v4();
+        for (id, auto) in [(alert_only, false), (auto_terminate, true)] {
+            sqlx::query(
+                "INSERT INTO instances (id, provider_id, status, created_at, gpu_profile, max_runtime_hours, auto_terminate_on_max_runtime)
+                 VALUES ($1, $2, 'ready', NOW() - INTERVAL '3 hours', '{}', 2, $3)",
+            )
+            .bind(id)
+            .bind(provider_id)
+            .bind(auto)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let first = enforce_max_runtime(&pool, &redis_client).await.unwrap();
+        assert!(first >= 2);
+
+        let rows: Vec<(Uuid, String, bool, Option<String>)> = sqlx::query_as(
+            "SELECT id, status::text, max_runtime_alerted_at IS NOT NULL, deletion_reason
+             FROM instances WHERE id = ANY($1)",
+        )
+        .bind(vec![alert_only, auto_terminate])
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        for (id, status, alerted, reason) in &rows {
+            assert!(alerted);
+            if *id == auto_terminate {
+                assert_eq!(status, "terminating");
+                assert_eq!(reason.as_deref(), Some("max_runtime_exceeded"));
+            } else {
+                assert_eq!(status, "ready");
+            }
+        }
+
+        let logged: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM action_logs WHERE instance_id = $1 AND action_type = 'INSTANCE_MAX_RUNTIME_EXCEEDED'",
+        )
+        .bind(alert_only)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(logged, 1);
+
+        // Second pass must not re-alert.
+        let _ = enforce_max_runtime(&pool, &redis_client).await.unwrap();
+        let logged_again: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM action_logs WHERE instance_id = $1 AND action_type = 'INSTANCE_MAX_RUNTIME_EXCEEDED'",
+        )
+        .bind(alert_only)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(logged_again, 1);
+
+        let _ = sqlx::query("DELETE FROM action_logs WHERE instance_id = ANY($1)")
+            .bind(vec![alert_only, auto_terminate])
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instances WHERE id = ANY($1)")
+            .bind(vec![alert_only, auto_terminate])
+            .execute(&pool)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn enforce_auto_terminate_terminates_expired_ttl_once() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(provider_id): Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers LIMIT 1")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: no provider seeded");
+            return;
+        };
+        // Redis is only used for best-effort event publishing.
+        let redis_client = redis::Client::open("redis://127.0.0.1:6379/").unwrap();
+
+        // Deployed with a 1-minute TTL, backdated so it has already expired.
+        let expired = Uuid::new_v4();
+        let pending = Uuid::new_v4();
+        for (id, offset) in [(expired, "-1 minute"), (pending, "1 hour")] {
+            sqlx::query(
+                "INSERT INTO instances (id, provider_id, status, created_at, gpu_profile, auto_terminate_at)
+                 VALUES ($1, $2, 'ready', NOW() - INTERVAL '2 minutes', '{}', NOW() + $3::interval)",
+            )
+            .bind(id)
+            .bind(provider_id)
+            .bind(offset)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let first = enforce_auto_terminate(&pool, &redis_client).await.unwrap();
+        assert!(first >= 1);
+
+        let rows: Vec<(Uuid, String, Option<String>)> = sqlx::query_as(
+            "SELECT id, status::text, deletion_reason FROM instances WHERE id = ANY($1)",
+        )
+        .bind(vec![expired, pending])
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        for (id, status, reason) in &rows {
+            if *id == expired {
+                assert_eq!(status, "terminating");
+                assert_eq!(reason.as_deref(), Some("ttl_expired"));
+            } else {
+                assert_eq!(status, "ready");
+                assert!(reason.is_none());
+            }
+        }
+
+        // Second pass must not claim it again.
+        let _ = enforce_auto_terminate(&pool, &redis_client).await.unwrap();
+        let logged: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM action_logs WHERE instance_id = $1 AND action_type = 'INSTANCE_TTL_EXPIRED'",
+        )
+        .bind(expired)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(logged, 1);
+
+        let _ = sqlx::query("DELETE FROM action_logs WHERE instance_id = ANY($1)")
+            .bind(vec![expired, pending])
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instances WHERE id = ANY($1)")
+            .bind(vec![expired, pending])
+            .execute(&pool)
+            .await;
+    }
+}
@@ -0,0 +1,368 @@
+//! Optional mutual TLS for worker -> orchestrator calls (`/internal/worker/*`).
+//!
+//! Opt-in via env; nothing changes while `WORKER_MTLS_CA_CERT` is unset:
+//! - WORKER_MTLS_CA_CERT: CA (PEM) verifying worker client certificates; also handed to workers
+//!   to verify the orchestrator.
+//! - WORKER_MTLS_SERVER_CERT / WORKER_MTLS_SERVER_KEY: orchestrator certificate chain and key.
+//! - WORKER_MTLS_PORT: TLS listener port (default 8443).
+//! - WORKER_MTLS_CLIENT_CERT / WORKER_MTLS_CLIENT_KEY: client certificate written to workers via
+//!   cloud-init (optional when certificates are distributed another way).
+//!
+//! When enabled, the TLS listener requires a client certificate at the handshake and the plain
+//! HTTP listener answers 403 on the worker routes. Bearer tokens are still checked by the handlers.
+
+use axum::extract::{ConnectInfo, Request};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json, Router};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto;
+use hyper_util::service::TowerToHyperService;
+use inventiv_common::cloud_init::WorkerClientTls;
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::rustls::server::WebPkiClientVerifier;
+use tokio_rustls::rustls::{crypto, RootCertStore, ServerConfig};
+use tokio_rustls::TlsAcceptor;
+
+const DEFAULT_PORT: u16 = 8443;
+
+/// A client that connects but never finishes the handshake must not hold a task forever.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Request extension set on connections whose client certificate was verified at the handshake.
+#[derive(Debug, Clone, Copy)]
+pub struct VerifiedClientCert;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkerMtlsConfig {
+    pub port: u16,
+    pub ca_cert: PathBuf,
+    pub server_cert: PathBuf,
+    pub server_key: PathBuf,
+    pub client_cert: Option<PathBuf>,
+    pub client_key: Option<PathBuf>,
+}
+
+impl WorkerMtlsConfig {
+    /// None when mTLS is not configured; Err when it is only partially configured.
+    pub fn from_env() -> Result<Option<Self>, String> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Option<Self>, String> {
+        let path = |key: &str| {
+            lookup(key)
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from)
+        };
+        let Some(ca_cert) = path("WORKER_MTLS_CA_CERT") else {
+            return Ok(None);
+        };
+        let required = |key: &str| {
+            path(key).ok_or_else(|| format!("{} is required when WORKER_MTLS_CA_CERT is set", key))
+        };
+        let port = lookup("WORKER_MTLS_PORT")
+            .and_then(|s| s.trim().parse::<u16>().ok())
+            .filter(|p| *p > 0)
+            .unwrap_or(DEFAULT_PORT);
+        Ok(Some(Self {
+            port,
+            ca_cert,
+            server_cert: required("WORKER_MTLS_SERVER_CERT")?,
+            server_key: required("WORKER_MTLS_SERVER_KEY")?,
+            client_cert: path("WORKER_MTLS_CLIENT_CERT"),
+            client_key: path("WORKER_MTLS_CLIENT_KEY"),
+        }))
+    }
+
+    pub fn acceptor(&self) -> Result<TlsAcceptor, String> {
+        let read = |p: &PathBuf| {
+            std::fs::read(p).map_err(|e| format!("cannot read {}: {}", p.display(), e))
+        };
+        let config = server_config(
+            &read(&self.ca_cert)?,
+            &read(&self.server_cert)?,
+            &read(&self.server_key)?,
+        )?;
+        Ok(TlsAcceptor::from(config))
+    }
+
+    /// Material for worker cloud-init; None when no client certificate is configured.
+    pub fn client_tls(&self) -> Option<WorkerClientTls> {
+        let read = |p: &PathBuf| std::fs::read_to_string(p).ok();
+        Some(WorkerClientTls {
+            ca_cert: read(&self.ca_cert)?,
+            client_cert: read(self.client_cert.as_ref()?)?,
+            client_key: read(self.client_key.as_ref()?)?,
+        })
+    }
+}
+
+/// Client TLS material for worker cloud-init, when mTLS is configured.
+pub fn worker_client_tls() -> Option<WorkerClientTls> {
+    match WorkerMtlsConfig::from_env() {
+        Ok(Some(config)) => {
+            let tls = config.client_tls();
+            if tls.is_none() {
+                eprintln!("⚠️ [worker-mtls] WORKER_MTLS_CLIENT_CERT/KEY unreadable or unset; workers get no client certificate");
+            }
+            tls
+        }
+        _ => None,
+    }
+}
+
+/// TLS server config requiring a client certificate issued by `ca_pem`.
+pub fn server_config(
+    ca_pem: &[u8],
+    cert_pem: &[u8],
+    key_pem: &[u8],
+) -> Result<Arc<ServerConfig>, String> {
+    let provider = Arc::new(crypto::ring::default_provider());
+
+    let mut roots = RootCertStore::empty();
+    for cert in CertificateDer::pem_slice_iter(ca_pem) {
+        let cert = cert.map_err(|e| format!("invalid CA certificate: {}", e))?;
+        roots
+            .add(cert)
+            .map_err(|e| format!("invalid CA certificate: {}", e))?;
+    }
+    if roots.is_empty() {
+        return Err("no CA certificate found".to_string());
+    }
+    let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider.clone())
+        .build()
+        .map_err(|e| format!("client verifier: {}", e))?;
+
+    let chain = CertificateDer::pem_slice_iter(cert_pem)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("invalid server certificate: {}", e))?;
+    let key =
+        PrivateKeyDer::from_pem_slice(key_pem).map_err(|e| format!("invalid server key: {}", e))?;
+
+    let mut config = ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| format!("tls config: {}", e))?
+        .with_client_cert_verifier(verifier)
+        .with_single_cert(chain, key)
+        .map_err(|e| format!("server certificate: {}", e))?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(Arc::new(config))
+}
+
+/// Serve `app` over TLS; connections without a valid client certificate fail the handshake.
+pub async fn serve(listener: TcpListener, acceptor: TlsAcceptor, app: Router) {
+    loop {
+        let (tcp, remote) = match listener.accept().await {
+            Ok(v) => v,
+            Err(e) => {
+                eprintln!("⚠️ [worker-mtls] accept failed: {}", e);
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                continue;
+            }
+        };
+        let acceptor = acceptor.clone();
+        let app = app.clone();
+        tokio::spawn(async move {
+            let tls = match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(tcp)).await {
+                Ok(Ok(tls)) => tls,
+                Ok(Err(e)) => {
+                    eprintln!("⚠️ [worker-mtls] handshake from {} rejected: {}", remote, e);
+                    return;
+                }
+                Err(_) => {
+                    eprintln!("⚠️ [worker-mtls] handshake from {} timed out", remote);
+                    return;
+                }
+            };
+            let app = app
+                .layer(Extension(ConnectInfo(remote)))
+                .layer(Extension(VerifiedClientCert));
+            if let Err(e) = auto::Builder::new(TokioExecutor::new())
+                .serve_connection(TokioIo::new(tls), TowerToHyperService::new(app))
+                .await
+            {
+                eprintln!("⚠️ [worker-mtls] connection from {} failed: {}", remote, e);
+            }
+        });
+    }
+}
+
+/// Route layer for the worker routes when mTLS is enabled: only requests that came through the
+/// TLS listener (client certificate verified) reach the handlers.
+pub async fn require_client_cert(req: Request, next: Next) -> Response {
+    if req.extensions().get::<VerifiedClientCert>().is_some() {
+        return next.run(req).await;
+    }
+    (
+        StatusCode::FORBIDDEN,
+        Json(json!({
+            "error": "client_certificate_required",
+            "reason": "worker endpoints require mTLS: use the orchestrator TLS port with a client certificate",
+        })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::SocketAddr;
+    use axum::middleware;
+    use axum::routing::post;
+    use rcgen::{
+        BasicConstraints, CertificateParams, ExtendedKeyUsagePurpose, IsCa, KeyPair,
+        KeyUsagePurpose,
+    };
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio_rustls::rustls::pki_types::ServerName;
+    use tokio_rustls::rustls::ClientConfig;
+    use tokio_rustls::TlsConnector;
+
+    struct TestPki {
+        ca_pem: String,
+        server: (String, String),
+        client: (String, String),
+    }
+
+    fn test_pki() -> TestPki {
+        let ca_key = KeyPair::generate().unwrap();
+        let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        ca_params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+        let ca = ca_params.self_signed(&ca_key).unwrap();
+
+        let issue = |name: &str, usage: ExtendedKeyUsagePurpose| {
+            let key = KeyPair::generate().unwrap();
+            let mut params = CertificateParams::new(vec![name.to_string()]).unwrap();
+            params.extended_key_usages = vec![usage];
+            let cert = params.signed_by(&key, &ca, &ca_key).unwrap();
+            (cert.pem(), key.serialize_pem())
+        };
+        TestPki {
+            ca_pem: ca.pem(),
+            server: issue("localhost", ExtendedKeyUsagePurpose::ServerAuth),
+            client: issue("worker", ExtendedKeyUsagePurpose::ClientAuth),
+        }
+    }
+
+    fn worker_app() -> Router {
+        Router::new()
+            .route("/internal/worker/heartbeat", post(|| async { "ok" }))
+            .route_layer(middleware::from_fn(require_client_cert))
+    }
+
+    /// POST a heartbeat over TLS and return what the server answered (empty or Err if rejected).
+    async fn tls_heartbeat(
+        addr: SocketAddr,
+        pki: &TestPki,
+        client_cert: Option<&(String, String)>,
+    ) -> std::io::Result<String> {
+        let mut roots = RootCertStore::empty();
+        for cert in CertificateDer::pem_slice_iter(pki.ca_pem.as_bytes()) {
+            roots.add(cert.unwrap()).unwrap();
+        }
+        let builder =
+            ClientConfig::builder_with_provider(Arc::new(crypto::ring::default_provider()))
+                .with_safe_default_protocol_versions()
+                .unwrap()
+                .with_root_certificates(roots);
+        let config = match client_cert {
+            Some((cert, key)) => builder
+                .with_client_auth_cert(
+                    CertificateDer::pem_slice_iter(cert.as_bytes())
+                        .collect::<Result<Vec<_>, _>>()
+                        .unwrap(),
+                    PrivateKeyDer::from_pem_slice(key.as_bytes()).unwrap(),
+                )
+                .unwrap(),
+            None => builder.with_no_client_auth(),
+        };
+        let tcp = tokio::net::TcpStream::connect(addr).await?;
+        let mut tls = TlsConnector::from(Arc::new(config))
+            .connect(ServerName::try_from("localhost").unwrap(), tcp)
+            .await?;
+        tls.write_all(
+            b"POST /internal/worker/heartbeat HTTP/1.1\r\nHost: localhost\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        )
+        .await?;
+        let mut out = Vec::new();
+        tls.read_to_end(&mut out).await?;
+        Ok(String::from_utf8_lossy(&out).into_owned())
+    }
+
+    #[tokio::test]
+    async fn worker_routes_reject_requests_without_client_cert() {
+        let pki = test_pki();
+        let acceptor = TlsAcceptor::from(
+            server_config(
+                pki.ca_pem.as_bytes(),
+                pki.server.0.as_bytes(),
+                pki.server.1.as_bytes(),
+            )
+            .unwrap(),
+        );
+        let tls_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let tls_addr = tls_listener.local_addr().unwrap();
+        tokio::spawn(serve(tls_listener, acceptor, worker_app()));
+        let plain_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let plain_addr = plain_listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(plain_listener, worker_app()).await });
+
+        let with_cert = tls_heartbeat(tls_addr, &pki, Some(&pki.client))
+            .await
+            .unwrap();
+        let without_cert = tls_heartbeat(tls_addr, &pki, None).await;
+        let plain = reqwest::Client::new()
+            .post(format!("http://{}/internal/worker/heartbeat", plain_addr))
+            .send()
+            .await
+            .unwrap()
+            .status();
+
+        assert!(with_cert.starts_with("HTTP/1.1 200"), "{}", with_cert);
+        assert!(with_cert.ends_with("ok"));
+        // The handshake refuses the connection: no HTTP response at all.
+        assert!(
+            without_cert.as_deref().map_or(true, str::is_empty),
+            "{:?}",
+            without_cert
+        );
+        assert_eq!(plain, StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn config_is_opt_in_and_rejects_partial_setup() {
+        let env = |pairs: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                pairs
+                    .iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        assert_eq!(WorkerMtlsConfig::from_lookup(env(&[])), Ok(None));
+        assert!(
+            WorkerMtlsConfig::from_lookup(env(&[("WORKER_MTLS_CA_CERT", "/tls/ca.pem")]))
+                .unwrap_err()
+                .contains("WORKER_MTLS_SERVER_CERT")
+        );
+        let config = WorkerMtlsConfig::from_lookup(env(&[
+            ("WORKER_MTLS_CA_CERT", "/tls/ca.pem"),
+            ("WORKER_MTLS_SERVER_CERT", "/tls/server.pem"),
+            ("WORKER_MTLS_SERVER_KEY", "/tls/server.key"),
+        ]))
+        .unwrap()
+        .unwrap();
+        assert_eq!(config.port, DEFAULT_PORT);
+        assert_eq!(config.client_cert, None);
+    }
+}
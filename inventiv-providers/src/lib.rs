@@ -11,6 +11,39 @@ pub trait CloudProvider: Send + Sync {
         cloud_init: Option<&str>,
         volumes: Option<&[String]>, // Optional list of volume IDs to attach at creation
     ) -> Result<String>;
+
+    /// Create an instance with an explicit server name (used to retry after a name collision).
+    /// Default implementation ignores the name (provider generates its own).
+    async fn create_instance_named(
+        &self,
+        _name: &str,
+        zone: &str,
+        instance_type: &str,
+        image_id: &str,
+        cloud_init: Option<&str>,
+        volumes: Option<&[String]>,
+    ) -> Result<String> {
+        self.create_instance(zone, instance_type, image_id, cloud_init, volumes)
+            .await
+    }
+
+    /// Create a named instance carrying extra billing tags (e.g. `cost_center=<tag>`).
+    /// Default implementation ignores the tags (provider has no tag support).
+    #[allow(clippy::too_many_arguments)]
+    async fn create_instance_tagged(
+        &self,
+        name: &str,
+        zone: &str,
+        instance_type: &str,
+        image_id: &str,
+        cloud_init: Option<&str>,
+        volumes: Option<&[String]>,
+        _tags: &[String],
+    ) -> Result<String> {
+        self.create_instance_named(name, zone, instance_type, image_id, cloud_init, volumes)
+            .await
+    }
+
     async fn start_instance(&self, zone: &str, server_id: &str) -> Result<bool>;
 
     /// Phase 1: Remove local volumes from diskless instance (BEFORE startup).
@@ -84,6 +117,18 @@ pub trait CloudProvider: Send + Sync {
         Ok(None)
     }
 
+    // Optional: change the commercial type of an existing server (vertical scaling).
+    // Attached volumes are kept; the server is left running on the new type.
+    // Default implementation returns Ok(false) (not supported).
+    async fn resize_instance(
+        &self,
+        _zone: &str,
+        _server_id: &str,
+        _new_instance_type: &str,
+    ) -> Result<bool> {
+        Ok(false)
+    }
+
     // New Generic Methods
     async fn check_instance_exists(&self, zone: &str, server_id: &str) -> Result<bool>;
 
@@ -125,6 +170,25 @@ pub trait CloudProvider: Send + Sync {
         Ok(None)
     }
 
+    // Optional: cheap existence check for a boot image id in a zone.
+    // Default implementation returns Ok(true) (providers without an image catalog accept any id).
+    async fn check_image_exists(&self, _zone: &str, _image_id: &str) -> Result<bool> {
+        Ok(true)
+    }
+
+    // Optional: whether an image can boot an instance type that requires diskless boot
+    // (its root volume must be Block Storage). Ok(false) when the image does not exist.
+    // Default implementation defers to check_image_exists (no diskless constraint).
+    async fn check_diskless_boot_image(&self, zone: &str, image_id: &str) -> Result<bool> {
+        self.check_image_exists(zone, image_id).await
+    }
+
+    // Optional: cheap authenticated call confirming the configured credentials are accepted
+    // (used by deep health checks). Default: Ok (providers without remote credentials).
+    async fn validate_credentials(&self) -> Result<()> {
+        Ok(())
+    }
+
     // Optional: volume lifecycle (Block Storage, etc.)
     // Default implementations allow providers that don't support volumes to compile.
     async fn create_volume(
@@ -181,6 +245,29 @@ pub trait CloudProvider: Send + Sync {
         Ok(vec![])
     }
 
+    /// Normalized instance state (name, state, IP, attached volumes), Ok(None) when the server
+    /// does not exist. Default implementation composes the individual calls; providers that can
+    /// fetch everything at once should override it to save API requests during reconciliation.
+    async fn get_instance_details(
+        &self,
+        zone: &str,
+        server_id: &str,
+    ) -> Result<Option<inventory::InstanceDetails>> {
+        if !self.check_instance_exists(zone, server_id).await? {
+            return Ok(None);
+        }
+        Ok(Some(inventory::InstanceDetails {
+            provider_id: server_id.to_string(),
+            name: None,
+            state: self.get_server_state(zone, server_id).await?,
+            ip_address: self.get_instance_ip(zone, server_id).await?,
+            volumes: self.list_attached_volumes(zone, server_id).await?,
+            commercial_type: None,
+            zone: Some(zone.to_string()),
+            hypervisor: None,
+        }))
+    }
+
     // Optional: check if a volume exists at the provider.
     // Used for volume reconciliation to detect orphan volumes or verify deletions.
     // Default implementation returns Ok(false) (not supported).
@@ -188,6 +275,12 @@ pub trait CloudProvider: Send + Sync {
         Ok(false)
     }
 
+    /// Optional: list all volumes of the zone (used for volume drift detection).
+    /// Default implementation returns Ok(None) (not supported, drift detection skips the provider).
+    async fn list_volumes(&self, _zone: &str) -> Result<Option<Vec<inventory::ProviderVolume>>> {
+        Ok(None)
+    }
+
     // Optional: provider-specific instance type behavior
     // Default implementations return conservative defaults (no special handling needed)
 
@@ -224,6 +317,65 @@ pub trait CloudProvider: Send + Sync {
     }
 }
 
+/// Stable classification of provider API failures, surfaced as `instances.error_code`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProviderErrorCode {
+    QuotaExceeded,
+    ImageNotFound,
+    InvalidVolume,
+    RateLimited,
+    NameConflict,
+    /// Zone temporarily out of stock for the requested type.
+    OutOfCapacity,
+    Unknown,
+}
+
+impl ProviderErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProviderErrorCode::QuotaExceeded => "QUOTA_EXCEEDED",
+            ProviderErrorCode::ImageNotFound => "IMAGE_NOT_FOUND",
+            ProviderErrorCode::InvalidVolume => "INVALID_VOLUME",
+            ProviderErrorCode::RateLimited => "RATE_LIMITED",
+            ProviderErrorCode::NameConflict => "NAME_CONFLICT",
+            ProviderErrorCode::OutOfCapacity => "OUT_OF_CAPACITY",
+            ProviderErrorCode::Unknown => "UNKNOWN",
+        }
+    }
+
+    /// Transient failures worth retrying the same request later (capacity, throttling).
+    /// Quota, image and volume errors need an operator fix and are never retried.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ProviderErrorCode::RateLimited | ProviderErrorCode::OutOfCapacity
+        )
+    }
+}
+
+/// Structured provider API error. Returned wrapped in `anyhow::Error`, so callers can
+/// `downcast_ref::<ProviderError>()` to get the code; `Display` keeps the raw message.
+#[derive(Debug)]
+pub struct ProviderError {
+    pub code: ProviderErrorCode,
+    pub message: String,
+}
+
+impl std::fmt::Display for ProviderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ProviderError {}
+
+/// Classification code of a provider error, if the error chain carries one.
+pub fn provider_error_code(err: &anyhow::Error) -> Option<ProviderErrorCode> {
+    err.chain()
+        .find_map(|e| e.downcast_ref::<ProviderError>())
+        .map(|e| e.code)
+}
+
 pub mod inventory {
     #[derive(Clone, Debug)]
     pub struct CatalogItem {
@@ -255,6 +407,45 @@ pub mod inventory {
         pub size_bytes: Option<i64>,
         pub boot: bool,
     }
+
+    #[derive(Clone, Debug)]
+    pub struct ProviderVolume {
+        pub provider_volume_id: String,
+        pub name: Option<String>,
+        pub volume_type: String,
+        pub size_bytes: Option<i64>,
+        /// RFC 3339 creation timestamp, when the provider reports it.
+        pub created_at: Option<String>,
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct InstanceDetails {
+        pub provider_id: String,
+        pub name: Option<String>,
+        /// Provider-native state (e.g. "running", "stopped").
+        pub state: Option<String>,
+        pub ip_address: Option<String>,
+        pub volumes: Vec<AttachedVolume>,
+        /// Commercial type actually running, as reported by the provider.
+        pub commercial_type: Option<String>,
+        /// Provider zone the server landed in.
+        pub zone: Option<String>,
+        /// Hypervisor / host identifier, when exposed.
+        pub hypervisor: Option<String>,
+    }
+
+    impl InstanceDetails {
+        /// Normalized subset stored in `instances.provider_metadata` (for debugging).
+        pub fn provider_metadata(&self) -> serde_json::Value {
+            serde_json::json!({
+                "name": self.name,
+                "state": self.state,
+                "commercial_type": self.commercial_type,
+                "zone": self.zone,
+                "hypervisor": self.hypervisor,
+            })
+        }
+    }
 }
 
 #[cfg(feature = "mock")]
@@ -529,6 +529,34 @@ impl CloudProvider for MockProvider {
         Ok(res.rows_affected() > 0)
     }
 
+    async fn resize_instance(
+        &self,
+        zone: &str,
+        server_id: &str,
+        new_instance_type: &str,
+    ) -> Result<bool> {
+        self.maybe_finalize_termination(zone, server_id).await?;
+        self.validate_zone_and_type(zone, new_instance_type).await?;
+
+        // No hardware to swap: the runtime keeps running, only the recorded type changes.
+        let res = sqlx::query(
+            r#"
+            UPDATE mock_provider_instances
+            SET instance_type_code = $3
+            WHERE provider_instance_id = $1
+              AND zone_code = $2
+              AND status IN ('created', 'running')
+            "#,
+        )
+        .bind(server_id)
+        .bind(zone)
+        .bind(new_instance_type)
+        .execute(&self.db)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
     async fn get_instance_ip(&self, zone: &str, server_id: &str) -> Result<Option<String>> {
         // Try to get IP from DB first (set when runtime was started)
         let ip_from_db: Option<String> = sqlx::query_scalar(
@@ -604,6 +632,42 @@ impl CloudProvider for MockProvider {
         Ok(status.is_some() && status.as_deref() != Some("terminated"))
     }
 
+    async fn get_instance_details(
+        &self,
+        zone: &str,
+        server_id: &str,
+    ) -> Result<Option<inventory::InstanceDetails>> {
+        self.maybe_finalize_termination(zone, server_id).await?;
+
+        let row: Option<(String, Option<String>, String, Option<String>)> = sqlx::query_as(
+            r#"
+            SELECT status, host(ip_address), instance_type_code, metadata->>'hypervisor'
+            FROM mock_provider_instances
+            WHERE provider_instance_id = $1 AND zone_code = $2
+              AND status <> 'terminated'
+            "#,
+        )
+        .bind(server_id)
+        .bind(zone)
+        .fetch_optional(&self.db)
+        .await?;
+
+        let Some((state, ip_address, instance_type_code, hypervisor)) = row else {
+            return Ok(None);
+        };
+        Ok(Some(inventory::InstanceDetails {
+            provider_id: server_id.to_string(),
+            // Mock servers are named after their id (same as list_instances).
+            name: Some(server_id.to_string()),
+            state: Some(state),
+            ip_address: ip_address.filter(|ip| !ip.is_empty()),
+            volumes: vec![],
+            commercial_type: Some(instance_type_code),
+            zone: Some(zone.to_string()),
+            hypervisor: Some(hypervisor.unwrap_or_else(|| "mock-hypervisor".to_string())),
+        }))
+    }
+
     async fn fetch_catalog(&self, _zone: &str) -> Result<Vec<inventory::CatalogItem>> {
         // Catalog is seeded in DB for mock, so we return empty here.
         Ok(vec![])
@@ -661,3 +725,182 @@ impl CloudProvider for MockProvider {
         Ok(false)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::postgres::PgPoolOptions;
+
+    async fn setup_pool() -> Option<Pool<Postgres>> {
+        let Some(url) = std::env::var("DATABASE_URL")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+        else {
+            eprintln!("skipping integration test: DATABASE_URL not set");
+            return None;
+        };
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&url)
+            .await
+            .ok()?;
+        let _ = sqlx::migrate!("../sqlx-migrations").run(&pool).await;
+        Some(pool)
+    }
+
+    #[tokio::test]
+    async fn get_instance_details_returns_populated_struct() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(provider_id): Option<uuid::Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers WHERE code = 'mock' LIMIT 1")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: mock provider not seeded");
+            return;
+        };
+
+        let server_id = format!("mock-{}", uuid::Uuid::new_v4());
+        sqlx::query(
+            r#"
+            INSERT INTO mock_provider_instances (
+              provider_instance_id, provider_id, zone_code, instance_type_code,
+              status, ip_address, created_at, metadata
+            )
+            VALUES ($1, $2, 'mock-zone-1', 'MOCK-GPU-S', 'running', '10.1.2.3', NOW(), '{"hypervisor": "hv-7"}'::jsonb)
+            "#,
+        )
+        .bind(&server_id)
+        .bind(provider_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let provider = MockProvider::new(pool.clone());
+        let details = provider
+            .get_instance_details("mock-zone-1", &server_id)
+            .await
+            .unwrap();
+        let missing = provider
+            .get_instance_details("mock-zone-1", "mock-does-not-exist")
+            .await
+            .unwrap();
+
+        let _ = sqlx::query("DELETE FROM mock_provider_instances WHERE provider_instance_id = $1")
+            .bind(&server_id)
+            .execute(&pool)
+            .await;
+
+        let details = details.expect("details for existing mock server");
+        assert_eq!(details.provider_id, server_id);
+        assert_eq!(details.name.as_deref(), Some(server_id.as_str()));
+        assert_eq!(details.state.as_deref(), Some("running"));
+        assert_eq!(details.ip_address.as_deref(), Some("10.1.2.3"));
+        let metadata = details.provider_metadata();
+        assert_eq!(metadata["commercial_type"], "MOCK-GPU-S");
+        assert_eq!(metadata["zone"], "mock-zone-1");
+        assert_eq!(metadata["hypervisor"], "hv-7");
+        assert!(missing.is_none());
+    }
+
+    #[tokio::test]
+    async fn resize_instance_changes_instance_type() {
+        let Some(pool) = setup_pool().await else {
+            return;
+        };
+        let Some(provider_id): Option<uuid::Uuid> =
+            sqlx::query_scalar("SELECT id FROM providers WHERE code = 'mock' LIMIT 1")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+        else {
+            eprintln!("skipping integration test: mock provider not seeded");
+            return;
+        };
+        let Some(zone_id): Option<uuid::Uuid> = sqlx::query_scalar(
+            "SELECT z.id FROM zones z JOIN regions r ON r.id = z.region_id
+             WHERE r.provider_id = $1 AND z.code = 'local' AND z.is_active = true",
+        )
+        .bind(provider_id)
+        .fetch_optional(&pool)
+        .await
+        .unwrap() else {
+            eprintln!("skipping integration test: mock zone not seeded");
+            return;
+        };
+
+        // Larger type available in the same zone.
+        let new_type = format!(
+            "mock-resize-{}",
+            &uuid::Uuid::new_v4().simple().to_string()[..8]
+        );
+        let type_id: uuid::Uuid = sqlx::query_scalar(
+            "INSERT INTO instance_types (id, provider_id, name, code, gpu_count, vram_per_gpu_gb, is_active)
+             VALUES (gen_random_uuid(), $1, $2, $2, 2, 24, true) RETURNING id",
+        )
+        .bind(provider_id)
+        .bind(&new_type)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO instance_type_zones (instance_type_id, zone_id, is_available) VALUES ($1, $2, true)",
+        )
+        .bind(type_id)
+        .bind(zone_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let server_id = format!("mock-{}", uuid::Uuid::new_v4());
+        sqlx::query(
+            r#"
+            INSERT INTO mock_provider_instances (
+              provider_instance_id, provider_id, zone_code, instance_type_code,
+              status, created_at, metadata
+            )
+            VALUES ($1, $2, 'local', 'mock-local-instance', 'running', NOW(), '{}'::jsonb)
+            "#,
+        )
+        .bind(&server_id)
+        .bind(provider_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let provider = MockProvider::new(pool.clone());
+        let resized = provider
+            .resize_instance("local", &server_id, &new_type)
+            .await
+            .unwrap();
+        let unknown = provider
+            .resize_instance("local", &server_id, "mock-does-not-exist")
+            .await;
+        let type_code: String = sqlx::query_scalar(
+            "SELECT instance_type_code FROM mock_provider_instances WHERE provider_instance_id = $1",
+        )
+        .bind(&server_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let _ = sqlx::query("DELETE FROM mock_provider_instances WHERE provider_instance_id = $1")
+            .bind(&server_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instance_type_zones WHERE instance_type_id = $1")
+            .bind(type_id)
+            .execute(&pool)
+            .await;
+        let _ = sqlx::query("DELETE FROM instance_types WHERE id = $1")
+            .bind(type_id)
+            .execute(&pool)
+            .await;
+
+        assert!(resized);
+        assert_eq!(type_code, new_type);
+        assert!(unknown.is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{inventory, CloudProvider};
+use crate::{inventory, CloudProvider, ProviderError, ProviderErrorCode};
 use anyhow::Result;
 use async_trait::async_trait;
 use reqwest::Client;
@@ -18,6 +18,104 @@ pub struct ScalewayProvider {
     access_key: Option<String>,
 }
 
+/// Map a Scaleway API error response to a stable code.
+///
+/// Only the structured parts of the error are used (HTTP status, `type`, `resource` and invalid
+/// argument names), never the free-form `message`. Scaleway errors look like
+/// `{"type": "quotas_exceeded", ...}`, `{"type": "not_found", "resource": "instance_image", ...}`
+/// or `{"type": "invalid_arguments", "details": [{"argument_name": "volumes.0.id", ...}]}`.
+pub fn classify_error(status_code: u16, body: &str) -> ProviderErrorCode {
+    let v: serde_json::Value = serde_json::from_str(body).unwrap_or(serde_json::Value::Null);
+    let err_type = v["type"].as_str().unwrap_or("");
+    let resource = v["resource"].as_str().unwrap_or("");
+    let volume_argument = v["details"].as_array().is_some_and(|details| {
+        details.iter().any(|d| {
+            d["argument_name"]
+                .as_str()
+                .is_some_and(|a| a == "volumes" || a.starts_with("volumes."))
+        })
+    });
+
+    match (status_code, err_type, resource) {
+        (429, _, _) | (_, "rate_limited", _) => ProviderErrorCode::RateLimited,
+        (_, "quotas_exceeded", _) => ProviderErrorCode::QuotaExceeded,
+        (_, "out_of_stock", _) => ProviderErrorCode::OutOfCapacity,
+        // Server names are the only unique field on create.
+        (409, _, _) | (_, "conflict", _) => ProviderErrorCode::NameConflict,
+        (_, "not_found", "instance_image" | "marketplace_image" | "image") => {
+            ProviderErrorCode::ImageNotFound
+        }
+        (_, _, "instance_volume" | "volume") => ProviderErrorCode::InvalidVolume,
+        (_, "invalid_arguments", _) if volume_argument => ProviderErrorCode::InvalidVolume,
+        _ => ProviderErrorCode::Unknown,
+    }
+}
+
+/// Attached volumes of a Scaleway `server` object.
+///
+/// `server.volumes` is usually an object keyed by index (`{"0": {...}, "1": {...}}`) but may
+/// also come back as an array.
+fn parse_server_volumes(server: &serde_json::Value) -> Vec<inventory::AttachedVolume> {
+    let entries: Vec<&serde_json::Value> = match server.get("volumes") {
+        Some(serde_json::Value::Array(arr)) => arr.iter().collect(),
+        Some(serde_json::Value::Object(obj)) => obj.values().collect(),
+        _ => vec![],
+    };
+    entries
+        .into_iter()
+        .filter_map(|vol| {
+            let vol_obj = vol.as_object()?;
+            let id = vol_obj.get("id").and_then(|v| v.as_str())?;
+            Some(inventory::AttachedVolume {
+                provider_volume_id: id.to_string(),
+                provider_volume_name: vol_obj
+                    .get("name")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string()),
+                volume_type: vol_obj
+                    .get("volume_type")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| "unknown".to_string()),
+                size_bytes: vol_obj.get("size").and_then(|v| v.as_i64()),
+                boot: vol_obj
+                    .get("boot")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false),
+            })
+        })
+        .collect()
+}
+
+/// Public IPv4 of a Scaleway `server` object (None until the dynamic IP is assigned).
+fn parse_server_public_ip(server: &serde_json::Value) -> Option<String> {
+    server["public_ip"]["address"]
+        .as_str()
+        .filter(|s| !s.is_empty() && *s != "null")
+        .map(|s| s.to_string())
+}
+
+/// Block Storage volumes of a `GET /block/v1/zones/{zone}/volumes` page.
+fn parse_block_volumes(page: &serde_json::Value) -> Vec<inventory::ProviderVolume> {
+    page["volumes"]
+        .as_array()
+        .map(|vols| {
+            vols.iter()
+                .filter_map(|vol| {
+                    let id = vol["id"].as_str()?;
+                    Some(inventory::ProviderVolume {
+                        provider_volume_id: id.to_string(),
+                        name: vol["name"].as_str().map(|s| s.to_string()),
+                        volume_type: vol["type"].as_str().unwrap_or("sbs_volume").to_string(),
+                        size_bytes: vol["size"].as_i64(),
+                        created_at: vol["created_at"].as_str().map(|s| s.to_string()),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 impl ScalewayProvider {
     pub fn new(project_id: String, secret_key: String, ssh_public_key: Option<String>) -> Self {
         // Default reqwest client has no overall timeout. If Scaleway stalls, a job can hang forever.
@@ -108,6 +206,13 @@ impl ScalewayProvider {
     }
 }
 
+/// Base tags on every server, followed by caller-provided billing tags (e.g. `cost_center=<tag>`).
+fn server_tags(extra: &[String]) -> Vec<String> {
+    let mut tags = vec!["inventiv-agents".to_string(), "worker".to_string()];
+    tags.extend(extra.iter().cloned());
+    tags
+}
+
 #[async_trait]
 impl CloudProvider for ScalewayProvider {
     async fn create_instance(
@@ -115,14 +220,49 @@ impl CloudProvider for ScalewayProvider {
         zone: &str,
         instance_type: &str,
         image_id: &str,
-        _cloud_init: Option<&str>,
+        cloud_init: Option<&str>,
         volumes: Option<&[String]>, // Optional list of Block Storage volume IDs to attach at creation
+    ) -> Result<String> {
+        let name = format!("inventiv-worker-{}", Uuid::new_v4());
+        self.create_instance_named(&name, zone, instance_type, image_id, cloud_init, volumes)
+            .await
+    }
+
+    async fn create_instance_named(
+        &self,
+        name: &str,
+        zone: &str,
+        instance_type: &str,
+        image_id: &str,
+        cloud_init: Option<&str>,
+        volumes: Option<&[String]>,
+    ) -> Result<String> {
+        self.create_instance_tagged(
+            name,
+            zone,
+            instance_type,
+            image_id,
+            cloud_init,
+            volumes,
+            &[],
+        )
+        .await
+    }
+
+    async fn create_instance_tagged(
+        &self,
+        name: &str,
+        zone: &str,
+        instance_type: &str,
+        image_id: &str,
+        _cloud_init: Option<&str>,
+        volumes: Option<&[String]>,
+        tags: &[String],
     ) -> Result<String> {
         let url = format!(
             "https://api.scaleway.com/instance/v1/zones/{}/servers",
             zone
         );
-        let name = format!("inventiv-worker-{}", Uuid::new_v4());
 
         // Check if this instance type requires diskless boot (L4, L40S, RENDER-S)
         let instance_type_upper = instance_type.to_uppercase();
@@ -134,7 +274,7 @@ impl CloudProvider for ScalewayProvider {
             "name": name,
             "commercial_type": instance_type,
             "project": self.project_id,
-            "tags": ["inventiv-agents", "worker"],
+            "tags": server_tags(tags),
             "dynamic_ip_required": true
         });
         body["image"] = json!(image_id);
@@ -202,11 +342,14 @@ impl CloudProvider for ScalewayProvider {
                 "❌ [Scaleway API] POST {} failed: status={}, response={}",
                 url, status_code, text
             );
-            return Err(anyhow::anyhow!(
-                "Scaleway create_instance failed: status={} body={}",
-                status_code,
-                text
-            ));
+            return Err(ProviderError {
+                code: classify_error(status_code, &text),
+                message: format!(
+                    "Scaleway create_instance failed: status={} body={}",
+                    status_code, text
+                ),
+            }
+            .into());
         } else {
             eprintln!(
                 "✅ [Scaleway API] POST {} succeeded: status={}",
@@ -1111,6 +1254,87 @@ impl CloudProvider for ScalewayProvider {
         Ok(true)
     }
 
+    async fn resize_instance(
+        &self,
+        zone: &str,
+        server_id: &str,
+        new_instance_type: &str,
+    ) -> Result<bool> {
+        // commercial_type can only be changed while the server is stopped.
+        // stop_instance waits (up to 60s) for the stopped state.
+        self.stop_instance(zone, server_id).await?;
+        if let Some(state) = self.get_server_state(zone, server_id).await? {
+            let state_lower = state.to_ascii_lowercase();
+            if state_lower != "stopped" && state_lower != "stopped_in_place" {
+                return Err(anyhow::anyhow!(
+                    "Instance {} must be stopped before resizing (current state: {})",
+                    server_id,
+                    state
+                ));
+            }
+        }
+
+        let url = format!(
+            "https://api.scaleway.com/instance/v1/zones/{}/servers/{}",
+            zone, server_id
+        );
+        let body = json!({ "commercial_type": new_instance_type });
+        eprintln!(
+            "🔵 [Scaleway API] PATCH {} - Resizing server: server_id={}, commercial_type={}",
+            url, server_id, new_instance_type
+        );
+
+        // Attached volumes are not part of the patch, so the data volume stays attached.
+        let patched: Result<()> = async {
+            let resp = self
+                .client
+                .patch(&url)
+                .headers(self.headers())
+                .json(&body)
+                .send()
+                .await?;
+            let status = resp.status();
+            if !status.is_success() {
+                let error_text = resp.text().await.unwrap_or_default();
+                eprintln!(
+                    "❌ [Scaleway API] PATCH {} failed: status={}, response={}",
+                    url,
+                    status.as_u16(),
+                    error_text
+                );
+                return Err(ProviderError {
+                    code: classify_error(status.as_u16(), &error_text),
+                    message: format!(
+                        "Scaleway resize failed: status={} body={}",
+                        status.as_u16(),
+                        error_text
+                    ),
+                }
+                .into());
+            }
+            eprintln!(
+                "✅ [Scaleway API] PATCH {} succeeded: status={}",
+                url,
+                status.as_u16()
+            );
+            Ok(())
+        }
+        .await;
+
+        if let Err(e) = patched {
+            // The type is unchanged: power the server back on as it was before failing.
+            if let Err(start_err) = self.start_instance(zone, server_id).await {
+                eprintln!(
+                    "❌ [Scaleway API] Restart of {} after failed resize failed: {}",
+                    server_id, start_err
+                );
+            }
+            return Err(e);
+        }
+
+        self.start_instance(zone, server_id).await
+    }
+
     async fn terminate_instance(&self, zone: &str, server_id: &str) -> Result<bool> {
         // Scaleway requires instances to be powered off before deletion
         // Stop the instance first if it's running and WAIT for it to be completely stopped
@@ -1365,65 +1589,10 @@ impl CloudProvider for ScalewayProvider {
             eprintln!("🔍 [Scaleway API] Server object keys: {:?}", all_keys);
         }
 
-        let mut volumes = Vec::new();
-
-        if let Some(server_obj) = server {
-            // Scaleway returns volumes in server.volumes array
-            // For RENDER-S, volumes might be in a different format (object with numeric keys like "0", "1")
-            let volumes_value = server_obj.get("volumes");
-            let volumes_to_iterate: Vec<&serde_json::Value> = if let Some(v) = volumes_value {
-                // Try as array first
-                if let Some(arr) = v.as_array() {
-                    arr.iter().collect()
-                } else if let Some(obj) = v.as_object() {
-                    // If it's an object (e.g., {"0": {...}, "1": {...}}), convert values to Vec
-                    eprintln!(
-                        "🔍 [Scaleway API] Volumes is an object with {} keys, converting to array",
-                        obj.len()
-                    );
-                    obj.values().collect()
-                } else {
-                    vec![]
-                }
-            } else {
-                vec![]
-            };
-
-            if !volumes_to_iterate.is_empty() {
-                for vol in volumes_to_iterate {
-                    if let Some(vol_obj) = vol.as_object() {
-                        let volume_id = vol_obj
-                            .get("id")
-                            .and_then(|v| v.as_str())
-                            .map(|s| s.to_string());
-                        let volume_name = vol_obj
-                            .get("name")
-                            .and_then(|v| v.as_str())
-                            .map(|s| s.to_string());
-                        let volume_type = vol_obj
-                            .get("volume_type")
-                            .and_then(|v| v.as_str())
-                            .map(|s| s.to_string())
-                            .unwrap_or_else(|| "unknown".to_string());
-                        let size = vol_obj.get("size").and_then(|v| v.as_i64());
-                        let boot = vol_obj
-                            .get("boot")
-                            .and_then(|v| v.as_bool())
-                            .unwrap_or(false);
-
-                        if let Some(id) = volume_id {
-                            volumes.push(inventory::AttachedVolume {
-                                provider_volume_id: id,
-                                provider_volume_name: volume_name,
-                                volume_type,
-                                size_bytes: size,
-                                boot,
-                            });
-                        }
-                    }
-                }
-            }
-        }
+        let volumes = json_resp
+            .get("server")
+            .map(parse_server_volumes)
+            .unwrap_or_default();
 
         eprintln!(
             "✅ [Scaleway API] Found {} attached volume(s) for server {}",
@@ -1535,6 +1704,40 @@ impl CloudProvider for ScalewayProvider {
         ))
     }
 
+    async fn list_volumes(&self, zone: &str) -> Result<Option<Vec<inventory::ProviderVolume>>> {
+        // Block Storage only: local volumes (l_ssd) live and die with their server.
+        const PAGE_SIZE: usize = 100;
+        let mut volumes = Vec::new();
+        for page in 1..=50 {
+            let url = format!(
+                "https://api.scaleway.com/block/v1/zones/{}/volumes?project_id={}&page={}&page_size={}",
+                zone, self.project_id, page, PAGE_SIZE
+            );
+            let resp = self.client.get(&url).headers(self.headers()).send().await?;
+            let status = resp.status();
+            if !status.is_success() {
+                let text = resp.text().await.unwrap_or_default();
+                return Err(ProviderError {
+                    code: classify_error(status.as_u16(), &text),
+                    message: format!(
+                        "Scaleway list_volumes failed: status={} body={}",
+                        status.as_u16(),
+                        text
+                    ),
+                }
+                .into());
+            }
+            let json_resp: serde_json::Value = resp.json().await?;
+            let batch = parse_block_volumes(&json_resp);
+            let done = batch.len() < PAGE_SIZE;
+            volumes.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(Some(volumes))
+    }
+
     async fn check_volume_exists(&self, zone: &str, volume_id: &str) -> Result<bool> {
         // Try Instance API first (for local volumes like l_ssd)
         let instance_url = format!(
@@ -1809,10 +2012,7 @@ impl CloudProvider for ScalewayProvider {
 
         // Extract IP address from public_ip.address
         // Scaleway assigns dynamic IPs only after the server reaches "running" state
-        let ip = json_resp["server"]["public_ip"]["address"]
-            .as_str()
-            .filter(|s| !s.is_empty() && *s != "null")
-            .map(|s| s.to_string());
+        let ip = parse_server_public_ip(&json_resp["server"]);
 
         if let Some(ip_addr) = &ip {
             eprintln!(
@@ -1849,6 +2049,50 @@ impl CloudProvider for ScalewayProvider {
         Ok(resp.status().is_success())
     }
 
+    async fn get_instance_details(
+        &self,
+        zone: &str,
+        server_id: &str,
+    ) -> Result<Option<inventory::InstanceDetails>> {
+        // One GET /servers/{id} carries state, IP and volumes.
+        let url = format!(
+            "https://api.scaleway.com/instance/v1/zones/{}/servers/{}",
+            zone, server_id
+        );
+        let resp = self.client.get(&url).headers(self.headers()).send().await?;
+        let status = resp.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            let text = resp.text().await.unwrap_or_default();
+            return Err(ProviderError {
+                code: classify_error(status.as_u16(), &text),
+                message: format!(
+                    "Scaleway get_instance_details failed: status={} body={}",
+                    status.as_u16(),
+                    text
+                ),
+            }
+            .into());
+        }
+
+        let json_resp: serde_json::Value = resp.json().await?;
+        let server = &json_resp["server"];
+        Ok(Some(inventory::InstanceDetails {
+            provider_id: server_id.to_string(),
+            name: server["name"].as_str().map(|s| s.to_string()),
+            state: server["state"].as_str().map(|s| s.to_string()),
+            ip_address: parse_server_public_ip(server),
+            volumes: parse_server_volumes(server),
+            commercial_type: server["commercial_type"].as_str().map(|s| s.to_string()),
+            zone: server["zone"].as_str().map(|s| s.to_string()),
+            hypervisor: server["location"]["hypervisor_id"]
+                .as_str()
+                .map(|s| s.to_string()),
+        }))
+    }
+
     async fn fetch_catalog(&self, _zone: &str) -> Result<Vec<inventory::CatalogItem>> {
         // Existing catalog seeding happens elsewhere; keep best-effort empty here for now.
         Ok(vec![])
@@ -1888,6 +2132,81 @@ impl CloudProvider for ScalewayProvider {
         Ok(None)
     }
 
+    async fn check_image_exists(&self, zone: &str, image_id: &str) -> Result<bool> {
+        let url = format!(
+            "https://api.scaleway.com/instance/v1/zones/{}/images/{}",
+            zone, image_id
+        );
+        let resp = self.client.get(&url).headers(self.headers()).send().await?;
+        let status = resp.status();
+        if status.is_success() {
+            return Ok(true);
+        }
+        if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::BAD_REQUEST {
+            return Ok(false);
+        }
+        let text = resp.text().await.unwrap_or_default();
+        Err(ProviderError {
+            code: classify_error(status.as_u16(), &text),
+            message: format!(
+                "Scaleway check_image_exists failed: status={} body={}",
+                status.as_u16(),
+                text
+            ),
+        }
+        .into())
+    }
+
+    async fn check_diskless_boot_image(&self, zone: &str, image_id: &str) -> Result<bool> {
+        let url = format!(
+            "https://api.scaleway.com/instance/v1/zones/{}/images/{}",
+            zone, image_id
+        );
+        let resp = self.client.get(&url).headers(self.headers()).send().await?;
+        let status = resp.status();
+        if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::BAD_REQUEST {
+            return Ok(false);
+        }
+        if !status.is_success() {
+            let text = resp.text().await.unwrap_or_default();
+            return Err(ProviderError {
+                code: classify_error(status.as_u16(), &text),
+                message: format!(
+                    "Scaleway check_diskless_boot_image failed: status={} body={}",
+                    status.as_u16(),
+                    text
+                ),
+            }
+            .into());
+        }
+        let image_json: serde_json::Value = resp.json().await?;
+        // Diskless types boot from Block Storage: the image root volume must be an SBS snapshot
+        // (local-storage images would recreate the l_ssd volume we have to avoid).
+        Ok(image_json["image"]["root_volume"]["volume_type"].as_str() == Some("sbs_snapshot"))
+    }
+
+    async fn validate_credentials(&self) -> Result<()> {
+        let url = format!(
+            "https://api.scaleway.com/account/v3/projects/{}",
+            self.project_id
+        );
+        let resp = self.client.get(&url).headers(self.headers()).send().await?;
+        let status = resp.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let text = resp.text().await.unwrap_or_default();
+        Err(ProviderError {
+            code: classify_error(status.as_u16(), &text),
+            message: format!(
+                "Scaleway validate_credentials failed: status={} body={}",
+                status.as_u16(),
+                text
+            ),
+        }
+        .into())
+    }
+
     async fn ensure_inbound_tcp_ports(
         &self,
         zone: &str,
@@ -2605,3 +2924,112 @@ impl CloudProvider for ScalewayProvider {
             || Self::is_render_s_instance(instance_type)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_quota_exceeded() {
+        let body = r#"{"type":"quotas_exceeded","message":"Quotas exceeded: L4-1-24G has reached its quota (1/1)","details":[{"resource":"L4-1-24G","current":1,"quota":1}]}"#;
+        assert_eq!(classify_error(403, body), ProviderErrorCode::QuotaExceeded);
+    }
+
+    #[test]
+    fn classify_image_volume_and_rate_limit() {
+        let image = r#"{"type":"not_found","resource":"instance_image","resource_id":"00000000-0000-0000-0000-000000000000","message":"resource is not found"}"#;
+        assert_eq!(classify_error(404, image), ProviderErrorCode::ImageNotFound);
+
+        let volume = r#"{"type":"invalid_arguments","message":"invalid argument(s)","details":[{"argument_name":"volumes.0.id","reason":"constraint"}]}"#;
+        assert_eq!(
+            classify_error(400, volume),
+            ProviderErrorCode::InvalidVolume
+        );
+
+        assert_eq!(classify_error(429, ""), ProviderErrorCode::RateLimited);
+        assert_eq!(
+            classify_error(500, "upstream error"),
+            ProviderErrorCode::Unknown
+        );
+    }
+
+    #[test]
+    fn classify_ignores_free_form_message() {
+        // Mentions volume/quota/image in prose only: not enough to pick a code.
+        let body = r#"{"type":"internal_error","message":"volume quota check failed while resolving image"}"#;
+        assert_eq!(classify_error(500, body), ProviderErrorCode::Unknown);
+        assert_eq!(
+            classify_error(400, "invalid volume size"),
+            ProviderErrorCode::Unknown
+        );
+    }
+
+    #[test]
+    fn classify_out_of_stock_as_retryable() {
+        let body = r#"{"type":"out_of_stock","message":"server type L4-1-24G is out of stock in fr-par-2"}"#;
+        let code = classify_error(412, body);
+        assert_eq!(code, ProviderErrorCode::OutOfCapacity);
+        assert!(code.is_retryable());
+        assert!(ProviderErrorCode::RateLimited.is_retryable());
+        assert!(!ProviderErrorCode::ImageNotFound.is_retryable());
+        assert!(!ProviderErrorCode::QuotaExceeded.is_retryable());
+    }
+
+    #[test]
+    fn classify_name_conflict() {
+        let body = r#"{"type":"conflict","message":"a server with name inventiv-worker-1 already exists"}"#;
+        assert_eq!(classify_error(409, body), ProviderErrorCode::NameConflict);
+    }
+
+    #[test]
+    fn server_tags_append_billing_tags() {
+        assert_eq!(server_tags(&[]), vec!["inventiv-agents", "worker"]);
+        assert_eq!(
+            server_tags(&["cost_center=ml-research".to_string()]),
+            vec!["inventiv-agents", "worker", "cost_center=ml-research"]
+        );
+    }
+
+    #[test]
+    fn parse_server_details_fields() {
+        let server = json!({
+            "name": "inventiv-worker-1",
+            "state": "running",
+            "public_ip": {"address": "51.15.0.10"},
+            "volumes": {
+                "0": {"id": "vol-boot", "volume_type": "sbs_volume", "size": 20000000000_i64, "boot": true},
+                "1": {"name": "no-id"}
+            }
+        });
+        assert_eq!(
+            parse_server_public_ip(&server).as_deref(),
+            Some("51.15.0.10")
+        );
+        let volumes = parse_server_volumes(&server);
+        assert_eq!(volumes.len(), 1);
+        assert_eq!(volumes[0].provider_volume_id, "vol-boot");
+        assert!(volumes[0].boot);
+        assert_eq!(parse_server_public_ip(&json!({"public_ip": null})), None);
+    }
+
+    #[test]
+    fn parse_block_volumes_page() {
+        let page = json!({
+            "volumes": [
+                {"id": "vol-1", "name": "inventiv-data-1", "type": "sbs_5k", "size": 200000000000_i64, "created_at": "2026-01-09T10:00:00Z"},
+                {"name": "no-id"}
+            ],
+            "total_count": 2
+        });
+        let volumes = parse_block_volumes(&page);
+        assert_eq!(volumes.len(), 1);
+        assert_eq!(volumes[0].provider_volume_id, "vol-1");
+        assert_eq!(volumes[0].volume_type, "sbs_5k");
+        assert_eq!(volumes[0].size_bytes, Some(200_000_000_000));
+        assert_eq!(
+            volumes[0].created_at.as_deref(),
+            Some("2026-01-09T10:00:00Z")
+        );
+        assert!(parse_block_volumes(&json!({})).is_empty());
+    }
+}
@@ -7,6 +7,8 @@ MODEL_ID = os.getenv("MODEL_ID", "demo-model")
 PORT = int(os.getenv("PORT", "8000"))
 REQUESTS_WAITING = float(os.getenv("VLLM_NUM_REQUESTS_WAITING", os.getenv("MOCK_VLLM_REQUESTS_WAITING", "0")))
 REQUESTS_RUNNING = float(os.getenv("VLLM_NUM_REQUESTS_RUNNING", os.getenv("MOCK_VLLM_REQUESTS_RUNNING", "0")))
+# Optional backend id echoed as X-Worker-Backend (what HAProxy adds in multi-vLLM mode).
+BACKEND_ID = os.getenv("MOCK_VLLM_BACKEND_ID", "").strip()
 
 
 class Handler(BaseHTTPRequestHandler):
@@ -21,6 +23,11 @@ class Handler(BaseHTTPRequestHandler):
                 import sys
                 print(f"Unexpected error: {e}", file=sys.stderr)
     
+    def end_headers(self):
+        if BACKEND_ID:
+            self.send_header("X-Worker-Backend", BACKEND_ID)
+        super().end_headers()
+
     def _json(self, code: int, payload: dict):
         body = json.dumps(payload).encode("utf-8")
         self.send_response(code)
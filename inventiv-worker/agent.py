@@ -33,6 +33,10 @@ def _get_agent_checksum():
 CONTROL_PLANE_URL = os.getenv("CONTROL_PLANE_URL", "").rstrip("/")
 WORKER_AUTH_TOKEN = os.getenv("WORKER_AUTH_TOKEN", "").strip()
 WORKER_AUTH_TOKEN_FILE = os.getenv("WORKER_AUTH_TOKEN_FILE", "").strip()
+# Optional mTLS towards the control plane (written by cloud-init when the orchestrator requires it).
+WORKER_TLS_CLIENT_CERT = os.getenv("WORKER_TLS_CLIENT_CERT", "").strip()
+WORKER_TLS_CLIENT_KEY = os.getenv("WORKER_TLS_CLIENT_KEY", "").strip()
+WORKER_TLS_CA_CERT = os.getenv("WORKER_TLS_CA_CERT", "").strip()
 
 INSTANCE_ID = os.getenv("INSTANCE_ID", "").strip()
 WORKER_ID = os.getenv("WORKER_ID", "").strip() or str(uuid4())
@@ -44,6 +48,8 @@ VLLM_METRICS_URL = os.getenv("VLLM_METRICS_URL", f"{VLLM_BASE_URL}/metrics").rst
 
 WORKER_HEALTH_PORT = int(os.getenv("WORKER_HEALTH_PORT", "8080"))
 WORKER_VLLM_PORT = int(os.getenv("WORKER_VLLM_PORT", "8000"))
+# Optional front port (HAProxy in multi-vLLM mode); when set, the control plane routes here.
+WORKER_PROXY_PORT = int(os.getenv("WORKER_PROXY_PORT", "0") or "0")
 HEARTBEAT_INTERVAL_S = float(os.getenv("WORKER_HEARTBEAT_INTERVAL_S", "4"))
 WORKER_DISK_PATH = os.getenv("WORKER_DISK_PATH", "/").strip() or "/"
 WORKER_ADVERTISE_IP = os.getenv("WORKER_ADVERTISE_IP", "").strip()
@@ -69,6 +75,16 @@ def _auth_headers():
     return {"Authorization": f"Bearer {WORKER_AUTH_TOKEN}"}
 
 
+def _control_plane_tls():
+    """requests kwargs presenting the worker client certificate (mTLS), when configured."""
+    kwargs = {}
+    if WORKER_TLS_CLIENT_CERT and WORKER_TLS_CLIENT_KEY:
+        kwargs["cert"] = (WORKER_TLS_CLIENT_CERT, WORKER_TLS_CLIENT_KEY)
+    if WORKER_TLS_CA_CERT:
+        kwargs["verify"] = WORKER_TLS_CA_CERT
+    return kwargs
+
+
 def _log_event(event_type: str, message: str, details: dict | None = None):
     """
     Log structured events to a file for diagnostics.
@@ -786,6 +802,7 @@ def register_worker_once():
             "worker_id": WORKER_ID,
             "model_id": MODEL_ID or None,
             "vllm_port": WORKER_VLLM_PORT,
+            "proxy_port": WORKER_PROXY_PORT or None,
             "health_port": WORKER_HEALTH_PORT,
             "ip_address": _local_ip_best_effort(),
             "metadata": {
@@ -800,6 +817,7 @@ def register_worker_once():
             headers=_auth_headers(),
             json=payload,
             timeout=3,
+            **_control_plane_tls(),
         )
         ok = resp.status_code // 100 == 2
         if ok:
@@ -881,6 +899,7 @@ def send_heartbeat(status: str):
             headers=_auth_headers(),
             json=payload,
             timeout=3,
+            **_control_plane_tls(),
         )
         ok = resp.status_code // 100 == 2
         if ok:
@@ -308,24 +308,32 @@ WHERE NOT EXISTS (SELECT 1 FROM models WHERE model_id = 'microsoft/Phi-3-medium-
 -- Keep in sync with frontend Tailwind safelist.
 -- Note: Using DELETE then INSERT for idempotency (no UNIQUE constraint on code)
 DELETE FROM action_types WHERE code IN (
-  'REQUEST_CREATE', 'EXECUTE_CREATE', 'PROVIDER_CREATE', 'PERSIST_PROVIDER_ID', 'PROVIDER_START', 'PROVIDER_GET_IP', 'INSTANCE_CREATED',
-  'HEALTH_CHECK', 'WORKER_MODEL_READY_CHECK', 'WORKER_VLLM_HTTP_OK', 'WORKER_MODEL_LOADED', 'WORKER_VLLM_WARMUP', 'INSTANCE_READY', 'INSTANCE_STARTUP_FAILED',
+  'REQUEST_CREATE', 'EXECUTE_CREATE', 'PROVIDER_CREATE', 'NAME_COLLISION_RETRY', 'PROVISION_RETRY', 'PERSIST_PROVIDER_ID', 'PROVIDER_START', 'PROVIDER_GET_IP', 'PROVISIONING_STEP', 'INSTANCE_CREATED', 'VOLUME_ATTACH_VERIFY', 'LOCAL_VOLUME_REMOVAL', 'WARM_POOL_SCALE',
+  'HEALTH_CHECK', 'WORKER_MODEL_READY_CHECK', 'WORKER_VLLM_HTTP_OK', 'WORKER_MODEL_LOADED', 'WORKER_VLLM_WARMUP', 'WORKER_MODEL_REVISION_MISMATCH', 'WORKER_DEREGISTERED', 'INSTANCE_READY', 'INSTANCE_STARTUP_FAILED',
   'REQUEST_TERMINATE', 'EXECUTE_TERMINATE', 'PROVIDER_TERMINATE', 'TERMINATION_PENDING', 'TERMINATOR_RETRY', 'TERMINATION_CONFIRMED', 'INSTANCE_TERMINATED',
-  'REQUEST_REINSTALL', 'EXECUTE_REINSTALL', 'ARCHIVE_INSTANCE', 'PROVIDER_DELETED_DETECTED', 'TERMINATE_INSTANCE', 'SCALEWAY_CREATE', 'SCALEWAY_DELETE'
+  'REQUEST_REINSTALL', 'EXECUTE_REINSTALL', 'REQUEST_RESIZE', 'EXECUTE_RESIZE', 'PROVIDER_VOLUME_RESIZE', 'REQUEST_RETRY_PROVISION', 'SET_INSTANCE_ROUTING', 'ARCHIVE_INSTANCE', 'PROVIDER_DELETED_DETECTED', 'RECONCILIATION_STALE', 'CATALOG_IMPORT', 'PRICE_CHANGED', 'REVOKE_USER_SESSIONS', 'INSTANCE_MAX_RUNTIME_EXCEEDED', 'INSTANCE_TTL_EXPIRED', 'TERMINATE_INSTANCE', 'SCALEWAY_CREATE', 'SCALEWAY_DELETE'
 );
 INSERT INTO action_types (code, label, icon, color_class, category, is_active) VALUES
   ('REQUEST_CREATE', 'Request Create', 'Zap', 'bg-blue-500 hover:bg-blue-600 text-white', 'create', TRUE),
   ('EXECUTE_CREATE', 'Execute Create', 'Server', 'bg-purple-500 hover:bg-purple-600 text-white', 'create', TRUE),
   ('PROVIDER_CREATE', 'Provider Create', 'Cloud', 'bg-orange-500 hover:bg-orange-600 text-white', 'create', TRUE),
+  ('NAME_COLLISION_RETRY', 'Name Collision Retry', 'Cloud', 'bg-yellow-500 hover:bg-yellow-600 text-white', 'create', TRUE),
+  ('PROVISION_RETRY', 'Provision Retry', 'Clock', 'bg-yellow-500 hover:bg-yellow-600 text-white', 'create', TRUE),
   ('PERSIST_PROVIDER_ID', 'Persist Provider ID', 'Database', 'bg-indigo-500 hover:bg-indigo-600 text-white', 'create', TRUE),
   ('PROVIDER_START', 'Provider Start', 'Cloud', 'bg-orange-500 hover:bg-orange-600 text-white', 'create', TRUE),
   ('PROVIDER_GET_IP', 'Provider Get IP', 'Cloud', 'bg-orange-500 hover:bg-orange-600 text-white', 'create', TRUE),
+  ('PROVISIONING_STEP', 'Provisioning Step', 'Activity', 'bg-purple-600 hover:bg-purple-700 text-white', 'create', TRUE),
   ('INSTANCE_CREATED', 'Instance Created', 'Database', 'bg-green-500 hover:bg-green-600 text-white', 'create', TRUE),
+  ('VOLUME_ATTACH_VERIFY', 'Volume Attach Verify', 'Database', 'bg-indigo-500 hover:bg-indigo-600 text-white', 'create', TRUE),
+  ('LOCAL_VOLUME_REMOVAL', 'Local Volume Removal', 'Database', 'bg-indigo-500 hover:bg-indigo-600 text-white', 'create', TRUE),
+  ('WARM_POOL_SCALE', 'Warm Pool Scale', 'Zap', 'bg-blue-500 hover:bg-blue-600 text-white', 'create', TRUE),
   ('HEALTH_CHECK', 'Health Check', 'Clock', 'bg-teal-600 hover:bg-teal-700 text-white', 'health', TRUE),
   ('WORKER_MODEL_READY_CHECK', 'Worker Model Ready Check', 'Activity', 'bg-sky-600 hover:bg-sky-700 text-white', 'health', TRUE),
   ('WORKER_VLLM_HTTP_OK', 'vLLM HTTP Ready', 'Activity', 'bg-sky-600 hover:bg-sky-700 text-white', 'health', TRUE),
   ('WORKER_MODEL_LOADED', 'Model Loaded', 'CheckCircle', 'bg-sky-600 hover:bg-sky-700 text-white', 'health', TRUE),
   ('WORKER_VLLM_WARMUP', 'vLLM Warmup', 'Activity', 'bg-sky-600 hover:bg-sky-700 text-white', 'health', TRUE),
+  ('WORKER_MODEL_REVISION_MISMATCH', 'Model Revision Mismatch', 'AlertTriangle', 'bg-yellow-500 hover:bg-yellow-600 text-white', 'health', TRUE),
+  ('WORKER_DEREGISTERED', 'Worker Deregistered', 'Clock', 'bg-gray-600 hover:bg-gray-700 text-white', 'health', TRUE),
   ('INSTANCE_READY', 'Instance Ready', 'CheckCircle', 'bg-green-600 hover:bg-green-700 text-white', 'health', TRUE),
   ('INSTANCE_STARTUP_FAILED', 'Instance Startup Failed', 'AlertTriangle', 'bg-gray-600 hover:bg-gray-700 text-white', 'health', TRUE),
   ('REQUEST_TERMINATE', 'Request Terminate', 'Zap', 'bg-blue-600 hover:bg-blue-700 text-white', 'terminate', TRUE),
@@ -337,8 +345,19 @@ INSERT INTO action_types (code, label, icon, color_class, category, is_active) V
   ('INSTANCE_TERMINATED', 'Instance Terminated', 'Database', 'bg-red-500 hover:bg-red-600 text-white', 'terminate', TRUE),
   ('REQUEST_REINSTALL', 'Request Reinstall', 'Wrench', 'bg-sky-600 hover:bg-sky-700 text-white', 'repair', TRUE),
   ('EXECUTE_REINSTALL', 'Execute Reinstall', 'Server', 'bg-sky-600 hover:bg-sky-700 text-white', 'repair', TRUE),
+  ('REQUEST_RESIZE', 'Request Resize', 'Wrench', 'bg-sky-600 hover:bg-sky-700 text-white', 'repair', TRUE),
+  ('EXECUTE_RESIZE', 'Execute Resize', 'Server', 'bg-sky-600 hover:bg-sky-700 text-white', 'repair', TRUE),
+  ('PROVIDER_VOLUME_RESIZE', 'Provider Volume Resize', 'Database', 'bg-sky-600 hover:bg-sky-700 text-white', 'repair', TRUE),
+  ('REQUEST_RETRY_PROVISION', 'Request Retry Provision', 'Zap', 'bg-blue-500 hover:bg-blue-600 text-white', 'create', TRUE),
+  ('SET_INSTANCE_ROUTING', 'Set Routing', 'Wrench', 'bg-sky-600 hover:bg-sky-700 text-white', 'repair', TRUE),
   ('ARCHIVE_INSTANCE', 'Archive Instance', 'Archive', 'bg-gray-600 hover:bg-gray-700 text-white', 'archive', TRUE),
   ('PROVIDER_DELETED_DETECTED', 'Provider Deleted', 'AlertTriangle', 'bg-yellow-600 hover:bg-yellow-700 text-white', 'reconcile', TRUE),
+  ('RECONCILIATION_STALE', 'Reconciliation Stale', 'AlertTriangle', 'bg-yellow-600 hover:bg-yellow-700 text-white', 'reconcile', TRUE),
+  ('CATALOG_IMPORT', 'Catalog Import', 'Database', 'bg-indigo-500 hover:bg-indigo-600 text-white', 'reconcile', TRUE),
+  ('PRICE_CHANGED', 'Price Changed', 'AlertTriangle', 'bg-yellow-600 hover:bg-yellow-700 text-white', 'reconcile', TRUE),
+  ('REVOKE_USER_SESSIONS', 'Revoke Sessions', 'AlertTriangle', 'bg-red-500 hover:bg-red-600 text-white', 'security', TRUE),
+  ('INSTANCE_MAX_RUNTIME_EXCEEDED', 'Max Runtime Exceeded', 'AlertTriangle', 'bg-yellow-600 hover:bg-yellow-700 text-white', 'reconcile', TRUE),
+  ('INSTANCE_TTL_EXPIRED', 'TTL Expired', 'Clock', 'bg-yellow-600 hover:bg-yellow-700 text-white', 'terminate', TRUE),
   ('TERMINATE_INSTANCE', 'Terminate Instance', 'Server', 'bg-purple-600 hover:bg-purple-700 text-white', 'legacy', TRUE),
   ('SCALEWAY_CREATE', 'Provider Create', 'Cloud', 'bg-orange-500 hover:bg-orange-600 text-white', 'legacy', TRUE),
   ('SCALEWAY_DELETE', 'Provider Delete', 'Cloud', 'bg-orange-600 hover:bg-orange-700 text-white', 'legacy', TRUE);
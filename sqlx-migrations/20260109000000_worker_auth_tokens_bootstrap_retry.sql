@@ -0,0 +1,7 @@
+-- Migration: make worker bootstrap registration retry-safe
+-- A worker whose register response was lost must be able to retry and receive the same token.
+-- We only store token hashes, so the plaintext is kept for a short bootstrap window and
+-- cleared as soon as the worker authenticates with it for the first time.
+
+ALTER TABLE public.worker_auth_tokens
+  ADD COLUMN IF NOT EXISTS bootstrap_token text;
@@ -0,0 +1,13 @@
+-- Migration: per-model worker staleness window
+-- Slow-booting models (huge weights) need a longer freshness window than the global
+-- OPENAI_WORKER_STALE_SECONDS before being dropped from routing and /v1/models.
+-- NULL = use the global value.
+
+ALTER TABLE public.models
+  ADD COLUMN IF NOT EXISTS stale_window_seconds integer;
+
+ALTER TABLE public.models
+  DROP CONSTRAINT IF EXISTS models_stale_window_seconds_check;
+ALTER TABLE public.models
+  ADD CONSTRAINT models_stale_window_seconds_check
+  CHECK (stale_window_seconds IS NULL OR (stale_window_seconds >= 10 AND stale_window_seconds <= 86400));
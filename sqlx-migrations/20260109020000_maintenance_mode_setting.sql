@@ -0,0 +1,16 @@
+-- Migration: maintenance mode for the OpenAI-compatible proxy
+-- When MAINTENANCE_MODE is true, new /v1/* requests are rejected with 503 + Retry-After
+-- while dashboard/management routes keep working. In-flight requests are not interrupted.
+
+INSERT INTO public.settings_definitions (key, scope, value_type, min_int, max_int, default_int, default_bool, description)
+VALUES
+  ('MAINTENANCE_MODE', 'global', 'bool', NULL, NULL, NULL, false, 'Reject new /v1/* inference requests with 503 (maintenance/upgrade drain).'),
+  ('MAINTENANCE_RETRY_AFTER_SECONDS', 'global', 'int', 1, 86400, 60, NULL, 'Retry-After value (seconds) returned while MAINTENANCE_MODE is on.')
+ON CONFLICT (key) DO UPDATE SET
+  scope = EXCLUDED.scope,
+  value_type = EXCLUDED.value_type,
+  min_int = EXCLUDED.min_int,
+  max_int = EXCLUDED.max_int,
+  default_int = EXCLUDED.default_int,
+  default_bool = EXCLUDED.default_bool,
+  description = EXCLUDED.description;
@@ -0,0 +1,14 @@
+-- Per-minute request counters per runtime model.
+-- `runtime_models.total_requests/failed_requests` stay lifetime totals; this table lets
+-- `/runtime/models?since=&until=` report counts within a time window.
+
+CREATE TABLE IF NOT EXISTS public.runtime_model_counters_minute (
+    model_id text NOT NULL,
+    bucket_minute timestamp with time zone NOT NULL,
+    requests bigint DEFAULT 0 NOT NULL,
+    failed_requests bigint DEFAULT 0 NOT NULL,
+    PRIMARY KEY (model_id, bucket_minute)
+);
+
+CREATE INDEX IF NOT EXISTS idx_runtime_model_counters_minute_bucket
+    ON public.runtime_model_counters_minute USING btree (bucket_minute DESC);
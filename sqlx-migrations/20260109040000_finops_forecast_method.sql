@@ -0,0 +1,16 @@
+-- FinOps forecast: keep several projection methods side by side.
+--   allocation: current allocated burn rate (historical behaviour)
+--   blended:    current burn rate blended with the trailing average of actual cost
+
+ALTER TABLE finops.cost_forecast_minute
+  ADD COLUMN IF NOT EXISTS method text DEFAULT 'allocation' NOT NULL;
+
+ALTER TABLE finops.cost_forecast_minute
+  DROP CONSTRAINT IF EXISTS cost_forecast_minute_method_check;
+ALTER TABLE finops.cost_forecast_minute
+  ADD CONSTRAINT cost_forecast_minute_method_check CHECK (method IN ('allocation', 'blended'));
+
+ALTER TABLE finops.cost_forecast_minute
+  DROP CONSTRAINT IF EXISTS cost_forecast_minute_pkey;
+ALTER TABLE finops.cost_forecast_minute
+  ADD CONSTRAINT cost_forecast_minute_pkey PRIMARY KEY (bucket_minute, provider_id_key, method);
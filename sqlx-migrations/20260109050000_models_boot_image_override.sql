@@ -0,0 +1,6 @@
+-- Per-model boot image override (e.g. a specific CUDA/driver image).
+-- When set, provisioning uses it instead of the instance-type/provider-resolved image
+-- (falls back with a warning if the provider cannot find it).
+
+ALTER TABLE public.models
+  ADD COLUMN IF NOT EXISTS boot_image_id text;
@@ -0,0 +1,36 @@
+-- Per-instance max runtime guard (cost alert).
+-- The orchestrator watchdog emits EVT:INSTANCE_RUNTIME_ALERT once an active instance has been
+-- running longer than max_runtime_hours, and optionally terminates it.
+-- Deployments inherit the organization defaults when no explicit value is given.
+
+ALTER TABLE public.instances
+  ADD COLUMN IF NOT EXISTS max_runtime_hours integer,
+  ADD COLUMN IF NOT EXISTS auto_terminate_on_max_runtime boolean DEFAULT false NOT NULL,
+  ADD COLUMN IF NOT EXISTS max_runtime_alerted_at timestamptz;
+
+ALTER TABLE public.organizations
+  ADD COLUMN IF NOT EXISTS default_max_runtime_hours integer,
+  ADD COLUMN IF NOT EXISTS default_auto_terminate_on_max_runtime boolean DEFAULT false NOT NULL;
+
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM pg_constraint
+        WHERE conname = 'instances_max_runtime_hours_check'
+    ) THEN
+        ALTER TABLE public.instances
+        ADD CONSTRAINT instances_max_runtime_hours_check CHECK (max_runtime_hours IS NULL OR max_runtime_hours > 0);
+    END IF;
+    IF NOT EXISTS (
+        SELECT 1 FROM pg_constraint
+        WHERE conname = 'organizations_default_max_runtime_hours_check'
+    ) THEN
+        ALTER TABLE public.organizations
+        ADD CONSTRAINT organizations_default_max_runtime_hours_check CHECK (default_max_runtime_hours IS NULL OR default_max_runtime_hours > 0);
+    END IF;
+END $$;
+
+-- Watchdog scan: only instances with a guard that has not fired yet.
+CREATE INDEX IF NOT EXISTS idx_instances_max_runtime_pending
+  ON public.instances(created_at)
+  WHERE max_runtime_hours IS NOT NULL AND max_runtime_alerted_at IS NULL;
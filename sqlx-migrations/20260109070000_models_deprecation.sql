@@ -0,0 +1,7 @@
+-- Model deprecation lifecycle.
+-- A deprecated model keeps being served (running instances are untouched), but OpenAI proxy
+-- responses carry an X-Model-Deprecated header pointing to the replacement (if any).
+
+ALTER TABLE public.models
+  ADD COLUMN IF NOT EXISTS deprecated_at timestamptz,
+  ADD COLUMN IF NOT EXISTS replacement_model_id uuid REFERENCES public.models(id) ON DELETE SET NULL;
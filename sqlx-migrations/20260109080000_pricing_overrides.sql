@@ -0,0 +1,39 @@
+-- Pricing overrides (reserved / committed-use discounts).
+-- Effective cost_per_hour per instance type over a validity window; FinOps consults these
+-- before falling back to instance_types.cost_per_hour (catalog price).
+
+CREATE TABLE IF NOT EXISTS public.pricing_overrides (
+    id uuid PRIMARY KEY,
+    provider_id uuid NOT NULL REFERENCES public.providers(id) ON DELETE CASCADE,
+    instance_type_id uuid NOT NULL REFERENCES public.instance_types(id) ON DELETE CASCADE,
+    cost_per_hour numeric(10,4) NOT NULL CHECK (cost_per_hour >= 0),
+    valid_from timestamptz NOT NULL DEFAULT now(),
+    valid_to timestamptz,
+    note text,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz NOT NULL DEFAULT now(),
+    CONSTRAINT pricing_overrides_validity_check CHECK (valid_to IS NULL OR valid_to > valid_from)
+);
+
+CREATE INDEX IF NOT EXISTS idx_pricing_overrides_instance_type
+  ON public.pricing_overrides(instance_type_id, valid_from DESC);
+
+-- Effective hourly price of an instance type at a point in time:
+-- latest override valid at p_at -> catalog price -> 0.
+CREATE OR REPLACE FUNCTION public.effective_cost_per_hour(p_instance_type_id uuid, p_at timestamptz) RETURNS numeric
+    LANGUAGE sql STABLE
+    AS $$
+  SELECT COALESCE(
+    (
+      SELECT po.cost_per_hour
+      FROM public.pricing_overrides po
+      WHERE po.instance_type_id = p_instance_type_id
+        AND po.valid_from <= p_at
+        AND (po.valid_to IS NULL OR po.valid_to > p_at)
+      ORDER BY po.valid_from DESC
+      LIMIT 1
+    ),
+    (SELECT it.cost_per_hour FROM public.instance_types it WHERE it.id = p_instance_type_id),
+    0
+  );
+$$;
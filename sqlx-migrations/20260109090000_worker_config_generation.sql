@@ -0,0 +1,24 @@
+-- Worker desired-state polling (GET /internal/worker/config).
+-- Workers poll their target model/vLLM args and compare `worker_config_generation` with the last
+-- applied value, so a model change or reinstall can be applied without SSH or reprovisioning.
+
+ALTER TABLE public.instances
+  ADD COLUMN IF NOT EXISTS worker_vllm_args text[] DEFAULT '{}'::text[] NOT NULL,
+  ADD COLUMN IF NOT EXISTS worker_config_generation bigint DEFAULT 1 NOT NULL;
+
+CREATE OR REPLACE FUNCTION public.bump_worker_config_generation() RETURNS trigger
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+  IF NEW.model_id IS DISTINCT FROM OLD.model_id
+     OR NEW.worker_vllm_args IS DISTINCT FROM OLD.worker_vllm_args THEN
+    NEW.worker_config_generation := OLD.worker_config_generation + 1;
+  END IF;
+  RETURN NEW;
+END;
+$$;
+
+DROP TRIGGER IF EXISTS trg_instances_bump_worker_config_generation ON public.instances;
+CREATE TRIGGER trg_instances_bump_worker_config_generation
+  BEFORE UPDATE OF model_id, worker_vllm_args ON public.instances
+  FOR EACH ROW EXECUTE FUNCTION public.bump_worker_config_generation();
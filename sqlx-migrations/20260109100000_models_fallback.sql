@@ -0,0 +1,10 @@
+-- Optional fallback model: when the requested model has no READY worker and the request opted in
+-- (X-Allow-Model-Fallback or OPENAI_MODEL_FALLBACK_ENABLED), the OpenAI proxy routes to the
+-- fallback's workers and reports the served model in X-Served-Model.
+
+ALTER TABLE public.models
+  ADD COLUMN IF NOT EXISTS fallback_model_id uuid REFERENCES public.models(id) ON DELETE SET NULL;
+
+ALTER TABLE public.models DROP CONSTRAINT IF EXISTS models_fallback_not_self;
+ALTER TABLE public.models
+  ADD CONSTRAINT models_fallback_not_self CHECK (fallback_model_id IS NULL OR fallback_model_id <> id);
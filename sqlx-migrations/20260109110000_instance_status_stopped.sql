@@ -0,0 +1,4 @@
+-- `stopped`: compute stopped at the provider (disks kept). Not billed for compute, so FinOps
+-- excludes it from allocation/actual proration (see NON_BILLABLE_COMPUTE_STATUSES).
+
+ALTER TYPE public.instance_status ADD VALUE IF NOT EXISTS 'stopped';
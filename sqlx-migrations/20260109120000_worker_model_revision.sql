@@ -0,0 +1,6 @@
+-- Worker-reported model revision (HF commit sha) from heartbeats.
+-- Instances serving the same `worker_model_id` at different revisions can produce inconsistent
+-- outputs; the orchestrator logs WORKER_MODEL_REVISION_MISMATCH when it detects one.
+
+ALTER TABLE public.instances
+  ADD COLUMN IF NOT EXISTS worker_model_revision text;
@@ -0,0 +1,32 @@
+-- Provider volume drift (observability only, nothing is deleted from here).
+-- job-volume-drift compares provider volumes with tracked instance_volumes per provider/organization/zone:
+-- - provider_volume_drift: current discrepancies (replaced on every check of the scope)
+-- - provider_volume_drift_checks: latest gauges per scope
+
+CREATE TABLE IF NOT EXISTS public.provider_volume_drift (
+    id uuid PRIMARY KEY,
+    provider_id uuid NOT NULL REFERENCES public.providers(id) ON DELETE CASCADE,
+    organization_id uuid NOT NULL,
+    zone_code text NOT NULL,
+    kind text NOT NULL CHECK (kind IN ('untracked_provider_volume', 'tracked_missing_on_provider')),
+    provider_volume_id text NOT NULL,
+    instance_volume_id uuid,
+    instance_id uuid,
+    volume_name text,
+    volume_type text,
+    size_bytes bigint,
+    volume_created_at timestamptz,
+    first_seen_at timestamptz NOT NULL DEFAULT now(),
+    last_seen_at timestamptz NOT NULL DEFAULT now(),
+    CONSTRAINT provider_volume_drift_unique UNIQUE (provider_id, organization_id, zone_code, kind, provider_volume_id)
+);
+
+CREATE TABLE IF NOT EXISTS public.provider_volume_drift_checks (
+    provider_id uuid NOT NULL REFERENCES public.providers(id) ON DELETE CASCADE,
+    organization_id uuid NOT NULL,
+    zone_code text NOT NULL,
+    untracked_provider_volumes integer NOT NULL DEFAULT 0,
+    tracked_missing_on_provider integer NOT NULL DEFAULT 0,
+    checked_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (provider_id, organization_id, zone_code)
+);
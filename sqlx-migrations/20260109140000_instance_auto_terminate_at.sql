@@ -0,0 +1,11 @@
+-- Deployment TTL ("terminate-after").
+-- POST /deployments accepts ttl_minutes; the instance stores the resulting deadline and the
+-- orchestrator watchdog publishes CMD:TERMINATE once it has passed.
+
+ALTER TABLE public.instances
+  ADD COLUMN IF NOT EXISTS auto_terminate_at timestamptz;
+
+-- Watchdog scan: only instances with a pending TTL.
+CREATE INDEX IF NOT EXISTS idx_instances_auto_terminate_pending
+  ON public.instances(auto_terminate_at)
+  WHERE auto_terminate_at IS NOT NULL;
@@ -0,0 +1,29 @@
+-- Worker proxy port (HAProxy front in multi-vLLM mode) and configurable default worker port.
+-- Workers may report worker_proxy_port at register; the OpenAI proxy routes to it when present
+-- (sticky affinity stays within the worker), else to worker_vllm_port, else WORKER_DEFAULT_VLLM_PORT.
+
+ALTER TABLE public.instances
+  ADD COLUMN IF NOT EXISTS worker_proxy_port integer;
+
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM pg_constraint
+        WHERE conname = 'instances_worker_proxy_port_check'
+    ) THEN
+        ALTER TABLE public.instances
+        ADD CONSTRAINT instances_worker_proxy_port_check CHECK (worker_proxy_port IS NULL OR (worker_proxy_port > 0 AND worker_proxy_port <= 65535));
+    END IF;
+END $$;
+
+INSERT INTO public.settings_definitions (key, scope, value_type, min_int, max_int, default_int, default_bool, description)
+VALUES
+  ('WORKER_DEFAULT_VLLM_PORT', 'global', 'int', 1, 65535, 8000, NULL, 'Worker port used for OpenAI routing when a worker reports neither a proxy nor a vLLM port.')
+ON CONFLICT (key) DO UPDATE SET
+  scope = EXCLUDED.scope,
+  value_type = EXCLUDED.value_type,
+  min_int = EXCLUDED.min_int,
+  max_int = EXCLUDED.max_int,
+  default_int = EXCLUDED.default_int,
+  default_bool = EXCLUDED.default_bool,
+  description = EXCLUDED.description;
@@ -0,0 +1,50 @@
+-- Canonical compute billing math for instances.
+-- The instance list/search/detail total_cost and FinOps actual minutes used slightly different
+-- expressions (instance list billed instances never allocated on the provider, FinOps dropped the
+-- last partial minute of terminated instances). Both now go through these functions.
+--
+-- Billing rules:
+-- - nothing is billed until the provider resource exists (provider_instance_id present)
+-- - billing starts at created_at and ends at terminated_at
+-- - without terminated_at, non-billable statuses (keep in sync with
+--   inventiv_common::NON_BILLABLE_COMPUTE_STATUSES) stop billing at failed_at (or bill nothing)
+
+-- Billable seconds of `i` overlapping [p_from, p_to).
+CREATE OR REPLACE FUNCTION public.instance_billable_seconds(i public.instances, p_from timestamptz, p_to timestamptz) RETURNS double precision
+    LANGUAGE sql STABLE
+    AS $$
+  SELECT CASE
+    WHEN i.provider_instance_id IS NULL OR i.created_at IS NULL THEN 0::float8
+    ELSE GREATEST(
+      0,
+      EXTRACT(EPOCH FROM (
+        LEAST(
+          COALESCE(
+            i.terminated_at,
+            CASE
+              WHEN i.status::text = ANY (ARRAY['terminated', 'failed', 'provisioning_failed', 'startup_failed', 'archived', 'stopped'])
+                THEN COALESCE(i.failed_at, i.created_at)
+              ELSE p_to
+            END
+          ),
+          p_to
+        ) - GREATEST(i.created_at, p_from)
+      ))
+    )::float8
+  END;
+$$;
+
+-- Billable seconds of `i` over its whole life, up to p_at.
+CREATE OR REPLACE FUNCTION public.instance_billable_seconds(i public.instances, p_at timestamptz) RETURNS double precision
+    LANGUAGE sql STABLE
+    AS $$
+  SELECT public.instance_billable_seconds(i, '-infinity'::timestamptz, p_at);
+$$;
+
+-- Compute cost of `i` up to p_at at the effective hourly price (pricing_overrides, else catalog).
+CREATE OR REPLACE FUNCTION public.instance_total_cost(i public.instances, p_at timestamptz) RETURNS double precision
+    LANGUAGE sql STABLE
+    AS $$
+  SELECT (public.instance_billable_seconds(i, p_at) / 3600.0)
+         * public.effective_cost_per_hour(i.instance_type_id, p_at)::float8;
+$$;
@@ -0,0 +1,10 @@
+-- Internal-only models.
+-- Non-public models are hidden from /v1/models for API-key callers (user sessions still see them).
+-- API keys may carry an allowed_models scope (models.model_id list): when set, the key can only
+-- call those models, and it is the only way for an API key to call a non-public model.
+
+ALTER TABLE public.models
+  ADD COLUMN IF NOT EXISTS public boolean NOT NULL DEFAULT true;
+
+ALTER TABLE public.api_keys
+  ADD COLUMN IF NOT EXISTS allowed_models text[];
@@ -0,0 +1,6 @@
+-- Per-key request rate limit for the OpenAI-compatible routes (/v1/*).
+-- Requests per minute over a sliding window (counted in Redis); NULL = unlimited.
+
+ALTER TABLE public.api_keys
+  ADD COLUMN IF NOT EXISTS rate_limit_per_minute integer
+  CHECK (rate_limit_per_minute IS NULL OR rate_limit_per_minute > 0);
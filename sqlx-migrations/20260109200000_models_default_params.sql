@@ -0,0 +1,12 @@
+-- Per-model default generation parameters (opt-in).
+-- When set (JSON object, e.g. {"temperature": 0.2, "max_tokens": 512}), the /v1 proxy fills in any
+-- of these keys the client omitted on chat/completions requests. Client values always win.
+
+ALTER TABLE public.models
+  ADD COLUMN IF NOT EXISTS default_params jsonb;
+
+ALTER TABLE public.models
+  DROP CONSTRAINT IF EXISTS models_default_params_object_check;
+ALTER TABLE public.models
+  ADD CONSTRAINT models_default_params_object_check
+  CHECK (default_params IS NULL OR jsonb_typeof(default_params) = 'object');
@@ -0,0 +1,13 @@
+-- Worker bootstrap NAT allowlist.
+-- A worker without a token may bootstrap (register and receive its token) when its source IP equals
+-- the instance IP. Workers egressing through a NAT get a different source IP, so these CIDRs are
+-- accepted too (comma-separated, e.g. '100.64.0.0/10, 203.0.113.0/24').
+-- Global ranges come from the orchestrator env WORKER_BOOTSTRAP_ALLOWED_CIDRS.
+
+INSERT INTO public.settings_definitions (key, scope, value_type, description)
+VALUES
+  ('WORKER_BOOTSTRAP_ALLOWED_CIDRS', 'provider', 'text', 'Comma-separated CIDRs (NAT/egress ranges) accepted as source IP for worker bootstrap, in addition to the instance IP.')
+ON CONFLICT (key) DO UPDATE SET
+  scope = EXCLUDED.scope,
+  value_type = EXCLUDED.value_type,
+  description = EXCLUDED.description;
@@ -0,0 +1,14 @@
+-- Cost attribution tag.
+-- Deployments may carry a cost_center; when omitted, the organization default applies.
+-- The orchestrator propagates it to provider tags so provider invoices can be split the same way,
+-- and FinOps aggregates finops.cost_actual_minute per cost_center through the instance.
+
+ALTER TABLE public.instances
+  ADD COLUMN IF NOT EXISTS cost_center text;
+
+ALTER TABLE public.organizations
+  ADD COLUMN IF NOT EXISTS default_cost_center text;
+
+CREATE INDEX IF NOT EXISTS idx_instances_cost_center
+  ON public.instances(cost_center)
+  WHERE cost_center IS NOT NULL;
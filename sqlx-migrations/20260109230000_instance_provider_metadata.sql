@@ -0,0 +1,6 @@
+-- Provider-side instance metadata.
+-- Once the server is running, the orchestrator stores a normalized subset of the provider's view
+-- (commercial type actually running, zone, hypervisor, ...) to help debug provider-specific issues.
+
+ALTER TABLE public.instances
+  ADD COLUMN IF NOT EXISTS provider_metadata jsonb;
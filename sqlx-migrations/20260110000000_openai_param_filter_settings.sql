@@ -0,0 +1,13 @@
+-- Request parameter denylist for the OpenAI-compatible proxy.
+-- Top-level request fields listed in OPENAI_PARAM_DENYLIST (comma-separated, e.g. 'logit_bias, guided_json')
+-- are handled before forwarding to workers: 'strip' removes them silently, 'reject' answers 422 naming the field.
+
+INSERT INTO public.settings_definitions (key, scope, value_type, default_text, description)
+VALUES
+  ('OPENAI_PARAM_DENYLIST', 'global', 'text', NULL, 'Comma-separated top-level request fields filtered by the OpenAI proxy before forwarding to workers.'),
+  ('OPENAI_PARAM_FILTER_MODE', 'global', 'text', 'strip', 'How denylisted request fields are handled: strip (remove silently) or reject (422).')
+ON CONFLICT (key) DO UPDATE SET
+  scope = EXCLUDED.scope,
+  value_type = EXCLUDED.value_type,
+  default_text = EXCLUDED.default_text,
+  description = EXCLUDED.description;
@@ -0,0 +1,5 @@
+-- Operator routing toggle: take a healthy worker out of OpenAI routing without draining or
+-- terminating it (status, heartbeats and provider state are left untouched).
+
+ALTER TABLE public.instances
+  ADD COLUMN IF NOT EXISTS routing_enabled boolean NOT NULL DEFAULT true;
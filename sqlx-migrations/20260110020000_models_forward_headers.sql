@@ -0,0 +1,12 @@
+-- Per-model extra headers sent to workers (opt-in).
+-- JSON object of header name -> value (e.g. {"Authorization": "Bearer <worker token>", "X-Tenant": "acme"})
+-- added by the /v1 proxy to every worker request for that model. Client headers are never forwarded.
+
+ALTER TABLE public.models
+  ADD COLUMN IF NOT EXISTS forward_headers jsonb;
+
+ALTER TABLE public.models
+  DROP CONSTRAINT IF EXISTS models_forward_headers_object_check;
+ALTER TABLE public.models
+  ADD CONSTRAINT models_forward_headers_object_check
+  CHECK (forward_headers IS NULL OR jsonb_typeof(forward_headers) = 'object');
@@ -0,0 +1,6 @@
+-- Per-key cap on concurrent streaming requests for the OpenAI-compatible routes (/v1/*).
+-- Open streams are counted in Redis and released when the response body is dropped; NULL = unlimited.
+
+ALTER TABLE public.api_keys
+  ADD COLUMN IF NOT EXISTS max_concurrent_streams integer
+  CHECK (max_concurrent_streams IS NULL OR max_concurrent_streams > 0);
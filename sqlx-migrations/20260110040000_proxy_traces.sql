@@ -0,0 +1,37 @@
+-- Migration: sampled request/response capture for the OpenAI-compatible proxy (debugging).
+-- Off by default. When PROXY_TRACE_ENABLED is true, a PROXY_TRACE_SAMPLE_PER_MILLE fraction of
+-- non-streaming requests is stored with its response. Authorization-like headers are redacted
+-- and traces older than PROXY_TRACE_RETENTION_HOURS are pruned.
+
+CREATE TABLE IF NOT EXISTS public.proxy_traces (
+    id uuid DEFAULT gen_random_uuid() NOT NULL,
+    model text NOT NULL,
+    instance_id uuid,
+    correlation_id text,
+    path text NOT NULL,
+    upstream_status integer,
+    request_headers jsonb NOT NULL DEFAULT '{}'::jsonb,
+    request_body jsonb,
+    response_body jsonb,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    CONSTRAINT proxy_traces_pkey PRIMARY KEY (id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_proxy_traces_model_created_at
+  ON public.proxy_traces (model, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_proxy_traces_created_at
+  ON public.proxy_traces (created_at);
+
+INSERT INTO public.settings_definitions (key, scope, value_type, min_int, max_int, default_int, default_bool, description)
+VALUES
+  ('PROXY_TRACE_ENABLED', 'global', 'bool', NULL, NULL, NULL, false, 'Capture sampled non-streaming OpenAI proxy request/response pairs into proxy_traces (debugging).'),
+  ('PROXY_TRACE_SAMPLE_PER_MILLE', 'global', 'int', 0, 1000, 1, NULL, 'Captured fraction of non-streaming requests, per mille (1000 = every request).'),
+  ('PROXY_TRACE_RETENTION_HOURS', 'global', 'int', 1, 168, 24, NULL, 'How long captured proxy traces are kept.')
+ON CONFLICT (key) DO UPDATE SET
+  scope = EXCLUDED.scope,
+  value_type = EXCLUDED.value_type,
+  min_int = EXCLUDED.min_int,
+  max_int = EXCLUDED.max_int,
+  default_int = EXCLUDED.default_int,
+  default_bool = EXCLUDED.default_bool,
+  description = EXCLUDED.description;
@@ -0,0 +1,12 @@
+-- Migration: store the last naive timestamps as timestamptz.
+-- instances.last_reconciliation and instance_type_zones.created_at were `timestamp without time zone`
+-- written with NOW() by UTC sessions; existing values are interpreted as UTC. Readers no longer need
+-- `AT TIME ZONE 'UTC'` conversions, and comparisons with NOW() no longer depend on the session TimeZone.
+
+ALTER TABLE public.instances
+  ALTER COLUMN last_reconciliation TYPE timestamp with time zone
+  USING last_reconciliation AT TIME ZONE 'UTC';
+
+ALTER TABLE public.instance_type_zones
+  ALTER COLUMN created_at TYPE timestamp with time zone
+  USING created_at AT TIME ZONE 'UTC';
@@ -0,0 +1,16 @@
+-- Warm pool: the orchestrator keeps at least `min_instances` live instances per active model
+-- (0 = no warm pool) so critical models avoid cold starts. `max_instances` caps the number of
+-- live instances per model (NULL = unlimited); the warm pool never provisions past it.
+
+ALTER TABLE public.models
+  ADD COLUMN IF NOT EXISTS min_instances integer NOT NULL DEFAULT 0,
+  ADD COLUMN IF NOT EXISTS max_instances integer;
+
+ALTER TABLE public.models
+  DROP CONSTRAINT IF EXISTS models_instance_bounds_check;
+ALTER TABLE public.models
+  ADD CONSTRAINT models_instance_bounds_check
+  CHECK (
+    min_instances >= 0
+    AND (max_instances IS NULL OR (max_instances > 0 AND min_instances <= max_instances))
+  );
@@ -0,0 +1,14 @@
+-- Migration: configurable connect timeout for OpenAI proxy -> worker requests.
+-- Overrides the OPENAI_PROXY_CONNECT_TIMEOUT_MS env var (default 30s). A worker that cannot be
+-- connected to within the timeout is skipped for a cooldown and the request fails over.
+
+INSERT INTO public.settings_definitions (key, scope, value_type, min_int, max_int, default_int, description)
+VALUES
+  ('OPENAI_PROXY_CONNECT_TIMEOUT_MS', 'global', 'int', 50, 60000, 30000, 'Connect timeout for OpenAI proxy requests to workers, in milliseconds. Connect failures fail over to another worker.')
+ON CONFLICT (key) DO UPDATE SET
+  scope = EXCLUDED.scope,
+  value_type = EXCLUDED.value_type,
+  min_int = EXCLUDED.min_int,
+  max_int = EXCLUDED.max_int,
+  default_int = EXCLUDED.default_int,
+  description = EXCLUDED.description;
@@ -0,0 +1,40 @@
+-- Migration: per-provider billing increment for FinOps teardown cost.
+-- Providers bill a terminated instance up to their billing increment (e.g. a started minute or
+-- hour is due in full). With FINOPS_BILLING_INCREMENT_SECONDS set on a provider, the FinOps
+-- actual minute containing terminated_at also carries the teardown grace: the billed lifetime
+-- rounded up to the increment, minus the lifetime itself. 0 (default) keeps exact proration.
+
+INSERT INTO public.settings_definitions (key, scope, value_type, min_int, max_int, default_int, description)
+VALUES
+  ('FINOPS_BILLING_INCREMENT_SECONDS', 'provider', 'int', 0, 86400, 0, 'Provider billing increment in seconds. FinOps rounds the billed lifetime of a terminated instance up to it in the termination minute (0 = exact proration).')
+ON CONFLICT (key) DO UPDATE SET
+  scope = EXCLUDED.scope,
+  value_type = EXCLUDED.value_type,
+  min_int = EXCLUDED.min_int,
+  max_int = EXCLUDED.max_int,
+  default_int = EXCLUDED.default_int,
+  description = EXCLUDED.description;
+
+-- Teardown grace seconds of `i` billed in (p_from, p_to]: non-zero only in the bucket where the
+-- instance terminated, on providers with a billing increment.
+CREATE OR REPLACE FUNCTION public.instance_teardown_grace_seconds(i public.instances, p_from timestamptz, p_to timestamptz) RETURNS double precision
+    LANGUAGE sql STABLE
+    AS $$
+  SELECT COALESCE((
+    SELECT CEIL(billed / inc) * inc - billed
+    FROM (
+      SELECT
+        public.instance_billable_seconds(i, i.terminated_at) AS billed,
+        (
+          SELECT MAX(ps.value_int)::float8
+          FROM public.provider_settings ps
+          WHERE ps.provider_id = i.provider_id
+            AND ps.key = 'FINOPS_BILLING_INCREMENT_SECONDS'
+        ) AS inc
+    ) t
+    WHERE i.terminated_at > p_from
+      AND i.terminated_at <= p_to
+      AND inc > 0
+      AND billed > 0
+  ), 0)::float8;
+$$;
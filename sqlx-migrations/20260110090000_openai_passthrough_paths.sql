@@ -0,0 +1,13 @@
+-- Allowlisted passthrough for vLLM endpoints the OpenAI proxy has no dedicated route for.
+-- OPENAI_PROXY_PASSTHROUGH_PATHS is a comma-separated list of worker paths (e.g. '/v1/score, /tokenize').
+-- GET/POST /v1/<rest> is forwarded when '/v1/<rest>' or '/<rest>' is listed (vLLM serves some endpoints,
+-- such as /tokenize and /version, at the root); anything else answers 404. Empty = no passthrough.
+
+INSERT INTO public.settings_definitions (key, scope, value_type, default_text, description)
+VALUES
+  ('OPENAI_PROXY_PASSTHROUGH_PATHS', 'global', 'text', NULL, 'Comma-separated vLLM paths the OpenAI proxy forwards as-is under /v1/* (e.g. /v1/score, /tokenize).')
+ON CONFLICT (key) DO UPDATE SET
+  scope = EXCLUDED.scope,
+  value_type = EXCLUDED.value_type,
+  default_text = EXCLUDED.default_text,
+  description = EXCLUDED.description;
@@ -0,0 +1,5 @@
+-- `accepted`: deployment queued (row inserted, CMD:PROVISION published) but not yet picked up by the
+-- orchestrator. process_provisioning moves it to `provisioning` when it actually starts, so a backlog
+-- no longer looks in-flight. No server exists yet, so FinOps treats it as non-billable.
+
+ALTER TYPE public.instance_status ADD VALUE IF NOT EXISTS 'accepted' BEFORE 'provisioning';
@@ -0,0 +1,5 @@
+-- `resizing`: the server is being power-cycled onto another instance type (stop -> type change ->
+-- start). Not routable; the orchestrator moves it to `booting` once the provider confirms, so
+-- health checks bring it back to `ready` on the new hardware.
+
+ALTER TYPE public.instance_status ADD VALUE IF NOT EXISTS 'resizing' AFTER 'ready';